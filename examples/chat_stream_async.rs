@@ -33,10 +33,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     println!();
-    if let Some(resp) = last {
-        if let Some(ns) = resp.total_duration {
-            println!("total_duration (ns): {}", ns);
-        }
+    if let Some(resp) = last
+        && let Some(ns) = resp.total_duration
+    {
+        println!("total_duration (ns): {}", ns);
     }
 
     Ok(())
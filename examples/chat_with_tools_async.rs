@@ -127,7 +127,7 @@ impl WeatherService {
             51 | 53 | 55 => "Drizzle",
             61 | 63 | 65 => "Rain",
             71 | 73 | 75 => "Snow",
-            80 | 81 | 82 => "Rain showers",
+            80..=82 => "Rain showers",
             95 => "Thunderstorm",
             _ => "Unknown",
         }
@@ -294,7 +294,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Tool requested: {:?}", call.function_name());
             println!(
                 "Arguments: {}",
-                serde_json::to_string_pretty(call.arguments().unwrap_or(&serde_json::json!({})))?
+                serde_json::to_string_pretty(
+                    call.arguments()
+                        .map_or(&serde_json::json!({}), |a| a.as_value())
+                )?
             );
         }
 
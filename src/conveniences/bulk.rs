@@ -0,0 +1,101 @@
+//! `OllamaClient::delete_models` and `OllamaClient::pull_models`
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::{BulkItem, BulkReport};
+use crate::http::OllamaClient;
+use crate::model::{DeleteRequest, PullRequest, PullResponse};
+use crate::{Error, OllamaApiAsync, Result};
+
+impl OllamaClient {
+    /// Delete each model in `names`, reporting every outcome.
+    ///
+    /// Deletes run one after another and a failure does not stop the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let report = client.delete_models(&["old-a", "old-b"]).await;
+    /// println!("{report}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_models(&self, names: &[&str]) -> BulkReport<()> {
+        let mut items = Vec::with_capacity(names.len());
+        for name in names {
+            let result = self.delete_model(&DeleteRequest::new(*name)).await;
+            items.push(BulkItem {
+                name: name.to_string(),
+                result,
+            });
+        }
+        BulkReport { items }
+    }
+
+    /// Pull each model in `names`, at most `concurrency` at a time.
+    ///
+    /// Every model gets an entry in the report, in the order given, whether
+    /// its pull succeeded or not. A `concurrency` of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let report = client
+    ///     .pull_models(&["llama3.2", "nomic-embed-text", "qwen3:0.6b"], 2)
+    ///     .await;
+    /// if !report.is_success() {
+    ///     eprintln!("{report}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pull_models(
+        &self,
+        names: &[&str],
+        concurrency: usize,
+    ) -> BulkReport<PullResponse> {
+        let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut tasks = JoinSet::new();
+        for (i, name) in names.iter().enumerate() {
+            let client = self.clone();
+            let permits = permits.clone();
+            let request = PullRequest::new(*name);
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                (i, client.pull_model(&request).await)
+            });
+        }
+
+        let mut slots: Vec<Option<Result<PullResponse>>> = names.iter().map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((i, result)) = joined {
+                slots[i] = Some(result);
+            }
+        }
+
+        let items = names
+            .iter()
+            .zip(slots)
+            .map(|(name, result)| BulkItem {
+                name: name.to_string(),
+                result: result.unwrap_or_else(|| {
+                    Err(Error::ApiError {
+                        message: "pull task failed".to_string(),
+                    })
+                }),
+            })
+            .collect();
+        BulkReport { items }
+    }
+}
@@ -0,0 +1,20 @@
+//! One entry of a `BulkReport`
+
+use crate::Result;
+
+/// Outcome of a bulk operation for one model.
+#[derive(Debug)]
+pub struct BulkItem<T> {
+    /// Model the operation ran on
+    pub name: String,
+
+    /// What the operation returned for this model
+    pub result: Result<T>,
+}
+
+impl<T> BulkItem<T> {
+    /// Check whether the operation succeeded for this model
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
@@ -0,0 +1,80 @@
+//! Result type for the bulk model operations
+
+use std::fmt;
+
+use super::BulkItem;
+
+/// Per-model outcomes of [`delete_models`](crate::OllamaClient::delete_models)
+/// or [`pull_models`](crate::OllamaClient::pull_models).
+///
+/// Every requested model gets an entry, in request order, whether it
+/// succeeded or not; one failure never hides the others.
+#[derive(Debug)]
+pub struct BulkReport<T> {
+    /// One entry per requested model, in request order
+    pub items: Vec<BulkItem<T>>,
+}
+
+impl<T> BulkReport<T> {
+    /// Check whether every operation succeeded
+    pub fn is_success(&self) -> bool {
+        self.items.iter().all(BulkItem::is_ok)
+    }
+
+    /// Iterate over the entries that succeeded
+    pub fn succeeded(&self) -> impl Iterator<Item = &BulkItem<T>> {
+        self.items.iter().filter(|i| i.is_ok())
+    }
+
+    /// Iterate over the entries that failed
+    pub fn failed(&self) -> impl Iterator<Item = &BulkItem<T>> {
+        self.items.iter().filter(|i| !i.is_ok())
+    }
+}
+
+/// A count line, then one `- name: error` line per failure.
+impl<T> fmt::Display for BulkReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed: Vec<&BulkItem<T>> = self.failed().collect();
+        write!(
+            f,
+            "{} succeeded, {} failed",
+            self.items.len() - failed.len(),
+            failed.len()
+        )?;
+        for item in failed {
+            if let Err(e) = &item.result {
+                write!(f, "\n- {}: {e}", item.name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_counts_and_display() {
+        let report = BulkReport {
+            items: vec![
+                BulkItem {
+                    name: "a".to_string(),
+                    result: Ok(()),
+                },
+                BulkItem {
+                    name: "b".to_string(),
+                    result: Err(Error::HttpStatusError(404)),
+                },
+            ],
+        };
+        assert!(!report.is_success());
+        assert_eq!(report.succeeded().count(), 1);
+        assert_eq!(
+            report.to_string(),
+            "1 succeeded, 1 failed\n- b: HTTP status error: 404"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! `OllamaClient::chat_batch` and `ChatSession::send_batch`
+
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::ChatSession;
+use crate::http::OllamaClient;
+use crate::{ChatRequest, ChatResponse, Error, OllamaApiAsync, Result};
+
+/// Run `jobs` at most `concurrency` at a time, returning their results in
+/// the order given. A `concurrency` of 0 is treated as 1.
+pub(super) async fn run_ordered<T, F>(jobs: Vec<F>, concurrency: usize) -> Vec<Result<T>>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T>> + Send + 'static,
+{
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    let count = jobs.len();
+    for (i, job) in jobs.into_iter().enumerate() {
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            (i, job.await)
+        });
+    }
+
+    let mut slots: Vec<Option<Result<T>>> = (0..count).map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((i, result)) = joined {
+            slots[i] = Some(result);
+        }
+    }
+    slots
+        .into_iter()
+        .map(|slot| {
+            slot.unwrap_or_else(|| {
+                Err(Error::ApiError {
+                    message: "batch task failed".to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
+impl OllamaClient {
+    /// Send each of `requests`, at most `concurrency` at a time.
+    ///
+    /// The requests are independent conversations: each carries its own
+    /// history and nothing is shared between them. Results come back in
+    /// the order given, one per request, and a failed request does not
+    /// stop the others. A `concurrency` of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let questions = ["What is 2 + 2?", "Name a prime above 10."];
+    /// let requests = questions
+    ///     .iter()
+    ///     .map(|q| ChatRequest::new("qwen3:0.6b", [ChatMessage::user(*q)]));
+    /// for (question, result) in questions.iter().zip(client.chat_batch(requests, 4).await) {
+    ///     match result {
+    ///         Ok(response) => println!("{question} {}", response.content().unwrap_or_default()),
+    ///         Err(e) => eprintln!("{question} failed: {e}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_batch<I>(&self, requests: I, concurrency: usize) -> Vec<Result<ChatResponse>>
+    where
+        I: IntoIterator<Item = ChatRequest>,
+    {
+        let jobs = requests
+            .into_iter()
+            .map(|request| {
+                let client = self.clone();
+                async move { client.chat(&request).await }
+            })
+            .collect();
+        run_ordered(jobs, concurrency).await
+    }
+}
+
+impl ChatSession {
+    /// Send `prompts[i]` to `sessions[i]` for every session, at most
+    /// `concurrency` at a time.
+    ///
+    /// Each session records its own turn exactly as
+    /// [`send`](Self::send) would, so histories stay independent; a
+    /// session whose call fails is left as it was. Results come back in
+    /// session order. A `concurrency` of 0 is treated as 1.
+    ///
+    /// # Errors
+    ///
+    /// Each result fails as [`send`](Self::send) does. If the number of
+    /// prompts differs from the number of sessions, nothing is sent and
+    /// every result is an [`Error::ValidationError`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatSession, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let mut sessions = vec![
+    ///     client.session("qwen3:0.6b").with_system("Answer as a pirate."),
+    ///     client.session("qwen3:0.6b").with_system("Answer as a poet."),
+    /// ];
+    /// let prompts = ["Describe the sea.", "Describe the sea."];
+    /// let results = ChatSession::send_batch(&mut sessions, &prompts, 2).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_batch(
+        sessions: &mut [ChatSession],
+        prompts: &[&str],
+        concurrency: usize,
+    ) -> Vec<Result<ChatResponse>> {
+        if sessions.len() != prompts.len() {
+            let message = format!(
+                "send_batch got {} sessions but {} prompts",
+                sessions.len(),
+                prompts.len()
+            );
+            return sessions
+                .iter()
+                .map(|_| Err(Error::ValidationError(message.clone())))
+                .collect();
+        }
+
+        let jobs = sessions
+            .iter()
+            .zip(prompts)
+            .map(|(session, prompt)| {
+                let mut session = session.clone();
+                let prompt = prompt.to_string();
+                async move {
+                    let response = session.send(prompt).await?;
+                    Ok((session, response))
+                }
+            })
+            .collect();
+        run_ordered(jobs, concurrency)
+            .await
+            .into_iter()
+            .zip(sessions.iter_mut())
+            .map(|(result, slot)| {
+                result.map(|(session, response)| {
+                    *slot = session;
+                    response
+                })
+            })
+            .collect()
+    }
+}
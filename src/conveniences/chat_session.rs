@@ -0,0 +1,483 @@
+//! `ChatSession`: a conversation that keeps its own history
+
+use super::history::History;
+use super::{SessionRecord, SummarizePolicy};
+use crate::http::OllamaClient;
+use crate::http::token_budget::estimate_message_tokens as estimate_tokens;
+use crate::{
+    ChatMessage, ChatRequest, ChatResponse, ChatRole, Error, ModelOptions, OllamaApiAsync, Result,
+};
+
+/// Prefix of the system message that replaces summarized turns.
+const SUMMARY_PREFIX: &str = "Summary of the earlier conversation:\n";
+
+const SUMMARIZER_PROMPT: &str = "Summarize the conversation the user provides so it can \
+    replace the original messages. Keep facts, names, numbers, decisions, user preferences, \
+    and open questions. Write plain prose, no preamble.";
+
+/// The assistant turn to store in history for `response`.
+fn assistant_message(response: &ChatResponse) -> ChatMessage {
+    #[allow(unused_mut)]
+    let mut message = ChatMessage::assistant(response.content().unwrap_or_default());
+    #[cfg(feature = "tools")]
+    if let Some(calls) = response.tool_calls() {
+        message = message.with_tool_calls(calls.to_vec());
+    }
+    message
+}
+
+/// `base` with every option set in `overrides` replaced.
+fn overlay(base: Option<&ModelOptions>, overrides: ModelOptions) -> ModelOptions {
+    let Some(base) = base else {
+        return overrides;
+    };
+    ModelOptions {
+        seed: overrides.seed.or(base.seed),
+        temperature: overrides.temperature.or(base.temperature),
+        top_k: overrides.top_k.or(base.top_k),
+        top_p: overrides.top_p.or(base.top_p),
+        min_p: overrides.min_p.or(base.min_p),
+        num_ctx: overrides.num_ctx.or(base.num_ctx),
+        num_predict: overrides.num_predict.or(base.num_predict),
+        stop: overrides.stop.or_else(|| base.stop.clone()),
+    }
+}
+
+/// A multi-turn chat with one model that keeps its message history.
+///
+/// Each [`send`](Self::send) appends the user message and the assistant's
+/// reply. With a [`SummarizePolicy`], the oldest turns are summarized
+/// before a send would push the history over the policy's token budget,
+/// so long-running assistants stay within the model's context.
+///
+/// [`fork`](Self::fork) branches the conversation at any message. Branches
+/// share the earlier history instead of copying it, so trying several
+/// continuations of a long chat stays cheap.
+///
+/// The history size is the server's `prompt_eval_count + eval_count` from
+/// the last reply, plus an estimate of about four characters per token for
+/// messages added since.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{OllamaClient, SummarizePolicy};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let mut session = client
+///     .session("qwen3:8b")
+///     .with_system("You are a concise assistant.")
+///     .with_summarize(SummarizePolicy::new(4_000).with_model("qwen3:0.6b"));
+///
+/// let reply = session.send("What is Rust?").await?;
+/// println!("{}", reply.content().unwrap_or_default());
+/// let reply = session.send("Who maintains it?").await?;
+/// println!("{}", reply.content().unwrap_or_default());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChatSession {
+    client: OllamaClient,
+    model: String,
+    options: Option<ModelOptions>,
+    messages: History,
+    summarize: Option<SummarizePolicy>,
+    /// Message count and total tokens the server reported for them
+    reported: Option<(usize, usize)>,
+}
+
+impl OllamaClient {
+    /// Start a [`ChatSession`] with `model` and an empty history.
+    pub fn session(&self, model: impl Into<String>) -> ChatSession {
+        ChatSession {
+            client: self.clone(),
+            model: model.into(),
+            options: None,
+            messages: History::default(),
+            summarize: None,
+            reported: None,
+        }
+    }
+
+    /// Continue a [`ChatSession`] from a saved [`SessionRecord`].
+    pub fn resume_session(&self, record: SessionRecord) -> ChatSession {
+        ChatSession {
+            messages: History::from(record.messages),
+            ..self.session(record.model)
+        }
+    }
+}
+
+impl ChatSession {
+    /// Start the history with a system prompt.
+    ///
+    /// Replaces an existing system prompt at the start of the history.
+    pub fn with_system(mut self, prompt: impl Into<String>) -> Self {
+        let system = ChatMessage::system(prompt);
+        let mut messages = self.messages.to_vec();
+        match messages.first_mut() {
+            Some(first) if first.role == ChatRole::System => *first = system,
+            _ => messages.insert(0, system),
+        }
+        self.messages = History::from(messages);
+        self.reported = None;
+        self
+    }
+
+    /// Send `options` with every request.
+    pub fn with_options(mut self, options: ModelOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Compress the history according to `policy`.
+    pub fn with_summarize(mut self, policy: SummarizePolicy) -> Self {
+        self.summarize = Some(policy);
+        self
+    }
+
+    /// Model the session chats with
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The history, oldest first
+    pub fn messages(&self) -> Vec<&ChatMessage> {
+        self.messages.iter().collect()
+    }
+
+    /// Continue the conversation with `model`, keeping the history.
+    ///
+    /// Lets an app escalate from a small model to a larger one when answers
+    /// fall short, or step back down afterwards. A `note`, if given, is
+    /// appended as a system message so the new model knows about the
+    /// handover, e.g. "The previous answers came from a smaller model;
+    /// double-check them."
+    ///
+    /// The history size falls back to the estimate until the new model
+    /// reports its own token counts, since tokenizers differ.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let mut session = client.session("qwen3:0.6b");
+    /// session.send("Prove that there are infinitely many primes.").await?;
+    ///
+    /// session.switch_model("qwen3:32b", Some("Check the previous answer carefully."));
+    /// session.send("Is that proof complete?").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn switch_model(&mut self, model: impl Into<String>, note: Option<&str>) {
+        self.model = model.into();
+        if let Some(note) = note {
+            self.messages.push(ChatMessage::system(note));
+        }
+        self.reported = None;
+    }
+
+    /// Snapshot of the model and history, for persistence.
+    pub fn to_record(&self) -> SessionRecord {
+        SessionRecord::new(&self.model, self.messages.to_vec())
+    }
+
+    /// Branch the conversation after its first `at` messages.
+    ///
+    /// The branch has the same model, options, and summarize policy, and
+    /// continues independently: sends on either session do not show up in
+    /// the other. The shared part of the history is not copied. Forking
+    /// before an assistant reply and sending the user message again is how
+    /// to "regenerate from here"; `at` past the end forks the whole history.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let mut session = client.session("qwen3:0.6b");
+    /// session.send("Name a color.").await?;
+    ///
+    /// let mut other = session.fork(0);
+    /// other.send("Name a fruit.").await?;
+    /// assert_eq!(session.messages()[0].content, "Name a color.");
+    /// assert_eq!(other.messages()[0].content, "Name a fruit.");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fork(&self, at: usize) -> ChatSession {
+        let mut branch = self.clone();
+        branch.messages.truncate(at);
+        if branch.reported.is_some_and(|(count, _)| count > at) {
+            branch.reported = None;
+        }
+        branch
+    }
+
+    /// Append a message without sending it, e.g. a tool result.
+    pub fn push(&mut self, message: ChatMessage) {
+        self.messages.push(message);
+    }
+
+    /// Drop the history, keeping the system prompt if there is one.
+    pub fn clear(&mut self) {
+        let keep = usize::from(self.has_system_prompt());
+        self.messages.truncate(keep);
+        self.reported = None;
+    }
+
+    /// Approximate size of the history in tokens.
+    pub fn context_tokens(&self) -> usize {
+        match self.reported {
+            Some((count, tokens)) if count <= self.messages.len() => {
+                tokens + estimate_tokens(&self.messages.since(count))
+            }
+            _ => estimate_tokens(&self.messages.to_vec()),
+        }
+    }
+
+    /// Send a user message and record the reply.
+    ///
+    /// Compresses the history first if the summarize policy's budget would
+    /// be exceeded. If the chat fails, the user message is removed again so
+    /// the call can be retried.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`chat`](crate::OllamaApiAsync::chat), including from
+    /// the summarization call.
+    pub async fn send(&mut self, content: impl Into<String>) -> Result<ChatResponse> {
+        let message = ChatMessage::user(content);
+        if let Some(policy) = &self.summarize {
+            let incoming = estimate_tokens(std::slice::from_ref(&message));
+            if self.context_tokens() + incoming > policy.max_tokens() {
+                self.compress().await?;
+            }
+        }
+
+        self.messages.push(message);
+        match self.client.chat(&self.request()).await {
+            Ok(response) => {
+                self.record(&response);
+                Ok(response)
+            }
+            Err(e) => {
+                self.messages.pop();
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace the last assistant reply with a fresh one.
+    ///
+    /// Drops the last assistant turn and asks again from the same history.
+    /// Options set in `overrides`, such as a new temperature or seed, take
+    /// precedence over the session's options for this call only. If the
+    /// history ends with a user or tool message instead, the reply to it is
+    /// requested without dropping anything.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if the history has no user or tool
+    ///   message to answer
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat); the previous
+    ///   reply is then kept
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ModelOptions, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let mut session = client.session("qwen3:0.6b");
+    /// session.send("Suggest a name for a cat.").await?;
+    ///
+    /// let options = ModelOptions::default().with_temperature(1.2).with_seed(7);
+    /// let reply = session.regenerate(Some(options)).await?;
+    /// println!("{}", reply.content().unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn regenerate(&mut self, overrides: Option<ModelOptions>) -> Result<ChatResponse> {
+        let model = self.model.clone();
+        self.regenerate_with(&model, overrides).await
+    }
+
+    /// Like [`regenerate`](Self::regenerate), but asks `model` instead.
+    ///
+    /// The session keeps chatting with its own model afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`regenerate`](Self::regenerate).
+    pub async fn regenerate_with(
+        &mut self,
+        model: &str,
+        overrides: Option<ModelOptions>,
+    ) -> Result<ChatResponse> {
+        let previous = match self.messages.last() {
+            Some(last) if last.role == ChatRole::Assistant => self.messages.pop(),
+            _ => None,
+        };
+        if !self
+            .messages
+            .last()
+            .is_some_and(|m| m.role == ChatRole::User || m.role == ChatRole::Tool)
+        {
+            self.messages.extend(previous);
+            return Err(Error::ValidationError(
+                "regenerate needs a user or tool message to answer".to_string(),
+            ));
+        }
+
+        let mut request = self.request();
+        request.model = model.to_string();
+        if let Some(overrides) = overrides {
+            request.options = Some(overlay(self.options.as_ref(), overrides));
+        }
+        match self.client.chat(&request).await {
+            Ok(response) => {
+                self.record(&response);
+                Ok(response)
+            }
+            Err(e) => {
+                self.messages.extend(previous);
+                Err(e)
+            }
+        }
+    }
+
+    /// Summarize the oldest turns now, regardless of the token budget.
+    ///
+    /// Keeps a leading system prompt and the policy's most recent messages
+    /// (four without a policy), extended back to the nearest user message so
+    /// a tool exchange is never split. Returns `false` if there was nothing
+    /// old enough to summarize.
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`chat`](crate::OllamaApiAsync::chat); the history is
+    /// left unchanged.
+    pub async fn compress(&mut self) -> Result<bool> {
+        let start = usize::from(self.has_system_prompt());
+        let mut messages = self.messages.to_vec();
+        let keep_recent = self
+            .summarize
+            .as_ref()
+            .map_or(SummarizePolicy::DEFAULT_KEEP_RECENT, |p| p.keep_recent());
+        let mut split = messages.len().saturating_sub(keep_recent).max(start);
+        while split > start
+            && messages
+                .get(split)
+                .is_some_and(|m| m.role != ChatRole::User)
+        {
+            split -= 1;
+        }
+        if split <= start {
+            return Ok(false);
+        }
+
+        let transcript = messages[start..split]
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.content))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let model = self
+            .summarize
+            .as_ref()
+            .and_then(SummarizePolicy::model)
+            .unwrap_or(&self.model);
+        let request = ChatRequest::new(
+            model,
+            [
+                ChatMessage::system(SUMMARIZER_PROMPT),
+                ChatMessage::user(transcript),
+            ],
+        )
+        .with_options(ModelOptions::default().with_temperature(0.0));
+        let response = self.client.chat(&request).await?;
+        let summary = response.content().unwrap_or_default().trim();
+
+        let summary = ChatMessage::system(format!("{SUMMARY_PREFIX}{summary}"));
+        messages.splice(start..split, [summary]);
+        self.messages = History::from(messages);
+        self.reported = None;
+        Ok(true)
+    }
+
+    /// Append the assistant's reply and the token count it reports.
+    fn record(&mut self, response: &ChatResponse) {
+        self.messages.push(assistant_message(response));
+        if let Some(total) = response.total_tokens() {
+            self.reported = Some((self.messages.len(), total.max(0) as usize));
+        }
+    }
+
+    fn has_system_prompt(&self) -> bool {
+        self.messages
+            .iter()
+            .next()
+            .is_some_and(|m| m.role == ChatRole::System && !m.content.starts_with(SUMMARY_PREFIX))
+    }
+
+    fn request(&self) -> ChatRequest {
+        let request = ChatRequest::new(&self.model, self.messages.iter().cloned());
+        match &self.options {
+            Some(options) => request.with_options(options.clone()),
+            None => request,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_system_replaces_prompt() {
+        let client = OllamaClient::default().unwrap();
+        let session = client.session("m").with_system("a").with_system("b");
+        assert_eq!(session.messages().len(), 1);
+        assert_eq!(session.messages()[0].content, "b");
+    }
+
+    #[test]
+    fn test_overlay_keeps_unset_options() {
+        let base = ModelOptions::default()
+            .with_temperature(0.2)
+            .with_num_ctx(4096);
+        let options = overlay(Some(&base), ModelOptions::default().with_temperature(0.9));
+        assert_eq!(options.temperature, Some(0.9));
+        assert_eq!(options.num_ctx, Some(4096));
+    }
+
+    #[test]
+    fn test_switch_model_keeps_history() {
+        let client = OllamaClient::default().unwrap();
+        let mut session = client.session("small");
+        session.push(ChatMessage::user("hi"));
+        session.switch_model("large", Some("Escalated."));
+        session.switch_model("larger", None);
+        assert_eq!(session.model(), "larger");
+        assert_eq!(session.messages().len(), 2);
+        assert!(session.messages()[1].is_system());
+    }
+
+    #[test]
+    fn test_clear_keeps_system_prompt() {
+        let client = OllamaClient::default().unwrap();
+        let mut session = client.session("m").with_system("sys");
+        session.push(ChatMessage::user("hi"));
+        session.clear();
+        assert_eq!(session.messages().len(), 1);
+        assert!(session.messages()[0].is_system());
+    }
+}
@@ -0,0 +1,129 @@
+//! `OllamaClient::choose`
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::http::OllamaClient;
+use crate::{ChatMessage, ChatRequest, Error, FormatSetting, ModelOptions, OllamaApiAsync, Result};
+
+/// Calls made before giving up on a reply outside the options
+const ATTEMPTS: usize = 3;
+
+#[derive(Deserialize)]
+struct Choice {
+    choice: String,
+}
+
+/// The index of the option `reply` names, ignoring case and surrounding
+/// whitespace if there is no exact match.
+fn position(options: &[&str], reply: &str) -> Option<usize> {
+    let reply = reply.trim();
+    options.iter().position(|o| *o == reply).or_else(|| {
+        options
+            .iter()
+            .position(|o| o.trim().eq_ignore_ascii_case(reply))
+    })
+}
+
+impl OllamaClient {
+    /// Ask `prompt` and return the index of the one of `options` the model
+    /// picks.
+    ///
+    /// The reply is constrained to a JSON object whose `choice` is one of
+    /// the options, at temperature 0. Models that do not honor the schema
+    /// are told what went wrong and asked again, up to three calls in
+    /// total. A reply matching an option except for case still counts.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `options` is empty or has duplicates
+    /// - [`Error::SerializationError`] if no reply names an option
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let options = ["Paris", "Lyon", "Marseille"];
+    /// let index = client
+    ///     .choose("qwen3:0.6b", "What is the capital of France?", &options)
+    ///     .await?;
+    /// println!("{}", options[index]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn choose(&self, model: &str, prompt: &str, options: &[&str]) -> Result<usize> {
+        if options.is_empty() {
+            return Err(Error::ValidationError(
+                "choose needs at least one option".to_string(),
+            ));
+        }
+        if let Some(duplicate) = options
+            .iter()
+            .enumerate()
+            .find_map(|(i, o)| options[..i].contains(o).then_some(o))
+        {
+            return Err(Error::ValidationError(format!(
+                "choose got option '{duplicate}' more than once"
+            )));
+        }
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "choice": { "type": "string", "enum": options }
+            },
+            "required": ["choice"]
+        });
+        let system = format!(
+            "Answer with exactly one of these options, copied verbatim: {}. \
+             Reply with JSON containing the chosen \"choice\".",
+            options.join(", ")
+        );
+        let mut request = ChatRequest::new(
+            model,
+            [ChatMessage::system(system), ChatMessage::user(prompt)],
+        )
+        .with_format(FormatSetting::schema(schema))
+        .with_options(ModelOptions::default().with_temperature(0.0));
+
+        let mut problem = String::new();
+        for _ in 0..ATTEMPTS {
+            let response = self.chat(&request).await?;
+            let reply = response.content().unwrap_or_default().to_string();
+            problem = match serde_json::from_str::<Choice>(reply.trim()) {
+                Ok(choice) => match position(options, &choice.choice) {
+                    Some(index) => return Ok(index),
+                    None => format!("model chose unknown option '{}'", choice.choice),
+                },
+                Err(e) => format!("model returned malformed JSON: {e}"),
+            };
+            request.messages.push(ChatMessage::assistant(reply));
+            request.messages.push(ChatMessage::user(format!(
+                "That is not a valid answer ({problem}). Reply with JSON whose \
+                 \"choice\" is exactly one of: {}.",
+                options.join(", ")
+            )));
+        }
+
+        Err(Error::SerializationError(format!(
+            "{problem} after {ATTEMPTS} attempts"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_prefers_exact_match() {
+        let options = ["yes", "Yes", "no"];
+        assert_eq!(position(&options, "Yes"), Some(1));
+        assert_eq!(position(&options, " NO\n"), Some(2));
+        assert_eq!(position(&options, "maybe"), None);
+    }
+}
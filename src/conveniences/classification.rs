@@ -0,0 +1,35 @@
+//! Result type for `OllamaClient::classify`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Label chosen by [`OllamaClient::classify`](crate::OllamaClient::classify).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Classification {
+    /// One of the candidate labels
+    pub label: String,
+
+    /// Model-reported confidence in `0.0..=1.0`
+    pub confidence: f64,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:.0}%)", self.label, self.confidence * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let c = Classification {
+            label: "spam".to_string(),
+            confidence: 0.875,
+        };
+        assert_eq!(c.to_string(), "spam (88%)");
+    }
+}
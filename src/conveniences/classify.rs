@@ -0,0 +1,82 @@
+//! `OllamaClient::classify`
+
+use serde_json::json;
+
+use super::Classification;
+use crate::http::OllamaClient;
+use crate::{Error, FormatSetting, Result};
+
+impl OllamaClient {
+    /// Pick the best of `labels` for `text`.
+    ///
+    /// The reply is constrained to a JSON object whose `label` is one of the
+    /// candidates, so the result is always a label you passed in. The
+    /// confidence is the model's own estimate, clamped to `0.0..=1.0`; treat
+    /// it as a ranking hint rather than a calibrated probability.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `labels` is empty
+    /// - [`Error::SerializationError`] if the model ignores the schema
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let result = client
+    ///     .classify("qwen3:0.6b", "Win a free cruise!!!", &["spam", "ham"])
+    ///     .await?;
+    /// assert!(result.label == "spam" || result.label == "ham");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn classify(
+        &self,
+        model: &str,
+        text: &str,
+        labels: &[&str],
+    ) -> Result<Classification> {
+        if labels.is_empty() {
+            return Err(Error::ValidationError(
+                "classify needs at least one label".to_string(),
+            ));
+        }
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string", "enum": labels },
+                "confidence": { "type": "number", "minimum": 0, "maximum": 1 }
+            },
+            "required": ["label", "confidence"]
+        });
+        let system = format!(
+            "Classify the user's text into exactly one of these labels: {}. \
+             Reply with JSON containing the chosen \"label\" and your \"confidence\" \
+             between 0 and 1.",
+            labels.join(", ")
+        );
+
+        let mut result: Classification = self
+            .chat_structured(
+                model,
+                system,
+                text.to_string(),
+                FormatSetting::schema(schema),
+            )
+            .await?;
+
+        if !labels.contains(&result.label.as_str()) {
+            return Err(Error::SerializationError(format!(
+                "model chose unknown label '{}'",
+                result.label
+            )));
+        }
+        result.confidence = result.confidence.clamp(0.0, 1.0);
+        Ok(result)
+    }
+}
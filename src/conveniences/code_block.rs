@@ -0,0 +1,110 @@
+//! `CodeBlock` and `extract_code_blocks`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A fenced code block found in a model reply.
+///
+/// Returned by [`extract_code_blocks`] and
+/// [`OllamaClient::generate_code`](crate::OllamaClient::generate_code).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeBlock {
+    /// Language from the opening fence (`rust` in `` ```rust ``), if any
+    pub language: Option<String>,
+
+    /// The code, without the fence lines
+    pub code: String,
+}
+
+impl fmt::Display for CodeBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+/// The opening fence of `line`: its character, length, and info string.
+fn opening_fence(line: &str) -> Option<(char, usize, &str)> {
+    let line = line.trim_start();
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|c| *c == marker).count();
+    let info = line[len..].trim();
+    (len >= 3 && !(marker == '`' && info.contains('`'))).then_some((marker, len, info))
+}
+
+/// Whether `line` closes a block opened with `len` × `marker`.
+fn closes(line: &str, marker: char, len: usize) -> bool {
+    let line = line.trim();
+    line.chars().count() >= len && line.chars().all(|c| c == marker)
+}
+
+/// Collect the fenced code blocks of `text`, in order.
+///
+/// Follows Markdown fences: a line of at least three backticks or tildes
+/// opens a block, whose language is the first word after the fence, and a
+/// line of at least as many of the same character closes it. A block left
+/// open, as in a reply cut off by `num_predict`, runs to the end of the
+/// text. Text outside fences is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::extract_code_blocks;
+///
+/// let reply = "Try this:\n\n```rust\nfn main() {}\n```\n\nThen run:\n```\ncargo run\n```";
+/// let blocks = extract_code_blocks(reply);
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+/// assert_eq!(blocks[0].code, "fn main() {}\n");
+/// assert_eq!(blocks[1].language, None);
+/// ```
+pub fn extract_code_blocks(text: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, CodeBlock)> = None;
+    for line in text.split_inclusive('\n') {
+        match &mut open {
+            Some((marker, len, block)) => {
+                if closes(line, *marker, *len) {
+                    blocks.extend(open.take().map(|(_, _, block)| block));
+                } else {
+                    block.code.push_str(line);
+                }
+            }
+            None => {
+                if let Some((marker, len, info)) = opening_fence(line) {
+                    let language = info.split_whitespace().next().map(str::to_string);
+                    let code = String::new();
+                    open = Some((marker, len, CodeBlock { language, code }));
+                }
+            }
+        }
+    }
+    blocks.extend(open.map(|(_, _, block)| block));
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tilde_and_longer_fences() {
+        let text = "~~~python title\nprint('```')\n~~~\n````md\n```\nnested\n```\n````\n";
+        let blocks = extract_code_blocks(text);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+        assert_eq!(blocks[0].code, "print('```')\n");
+        assert_eq!(blocks[1].code, "```\nnested\n```\n");
+    }
+
+    #[test]
+    fn test_unclosed_block_runs_to_end() {
+        let blocks = extract_code_blocks("```js\nlet x = 1;");
+        assert_eq!(blocks[0].code, "let x = 1;");
+    }
+
+    #[test]
+    fn test_inline_backticks_are_not_fences() {
+        assert!(extract_code_blocks("Use ```x``` inline, or `y`.").is_empty());
+    }
+}
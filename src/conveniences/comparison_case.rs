@@ -0,0 +1,34 @@
+//! One entry of a `ComparisonReport`
+
+use super::PromptWinner;
+use crate::Result;
+
+/// Outcome of both prompt variants on one input.
+#[derive(Debug)]
+pub struct ComparisonCase {
+    /// The input both prompts were filled with
+    pub input: String,
+
+    /// Reply to the first prompt, if the call succeeded
+    pub output_a: Option<String>,
+
+    /// Reply to the second prompt, if the call succeeded
+    pub output_b: Option<String>,
+
+    /// The scorer's scores for the two replies, when a scorer is used
+    pub scores: Option<(f64, f64)>,
+
+    /// The judge's explanation, when a judge model is used
+    pub rationale: Option<String>,
+
+    /// The better variant, or the error that kept the case from being
+    /// decided
+    pub result: Result<PromptWinner>,
+}
+
+impl ComparisonCase {
+    /// The better variant, if the case was decided
+    pub fn winner(&self) -> Option<PromptWinner> {
+        self.result.as_ref().ok().copied()
+    }
+}
@@ -0,0 +1,102 @@
+//! Result type for `PromptComparison::run`
+
+use std::fmt;
+
+use super::{ComparisonCase, PromptWinner};
+
+/// Per-input outcomes of [`PromptComparison::run`](super::PromptComparison::run).
+///
+/// Every input gets an entry, in input order, whether it was decided or
+/// not; one failure never hides the others.
+#[derive(Debug)]
+pub struct ComparisonReport {
+    /// One entry per input, in input order
+    pub cases: Vec<ComparisonCase>,
+}
+
+impl ComparisonReport {
+    /// Number of cases decided as `winner`
+    pub fn wins(&self, winner: PromptWinner) -> usize {
+        self.cases
+            .iter()
+            .filter(|c| c.winner() == Some(winner))
+            .count()
+    }
+
+    /// Iterate over the cases that could not be decided
+    pub fn errors(&self) -> impl Iterator<Item = &ComparisonCase> {
+        self.cases.iter().filter(|c| c.result.is_err())
+    }
+
+    /// The variant that won more cases; a tie when both won as many
+    pub fn overall(&self) -> PromptWinner {
+        let (a, b) = (self.wins(PromptWinner::A), self.wins(PromptWinner::B));
+        PromptWinner::from_scores(a as f64, b as f64)
+    }
+}
+
+/// A count line, then one `- input: error` line per undecided case.
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "A won {}, B won {}, {} ties, {} errors",
+            self.wins(PromptWinner::A),
+            self.wins(PromptWinner::B),
+            self.wins(PromptWinner::Tie),
+            self.errors().count()
+        )?;
+        for case in self.errors() {
+            if let Err(e) = &case.result {
+                write!(f, "\n- {:?}: {e}", case.input)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn case(input: &str, result: crate::Result<PromptWinner>) -> ComparisonCase {
+        ComparisonCase {
+            input: input.to_string(),
+            output_a: None,
+            output_b: None,
+            scores: None,
+            rationale: None,
+            result,
+        }
+    }
+
+    #[test]
+    fn test_counts_and_display() {
+        let report = ComparisonReport {
+            cases: vec![
+                case("x", Ok(PromptWinner::A)),
+                case("y", Ok(PromptWinner::B)),
+                case("z", Ok(PromptWinner::A)),
+                case("w", Err(Error::HttpStatusError(404))),
+            ],
+        };
+        assert_eq!(report.wins(PromptWinner::A), 2);
+        assert_eq!(report.overall(), PromptWinner::A);
+        assert_eq!(
+            report.to_string(),
+            "A won 2, B won 1, 0 ties, 1 errors\n- \"w\": HTTP status error: 404"
+        );
+    }
+
+    #[test]
+    fn test_overall_tie() {
+        let report = ComparisonReport {
+            cases: vec![
+                case("x", Ok(PromptWinner::A)),
+                case("y", Ok(PromptWinner::B)),
+            ],
+        };
+        assert_eq!(report.overall(), PromptWinner::Tie);
+    }
+}
@@ -0,0 +1,75 @@
+//! Result type for `OllamaClient::disk_usage`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::FamilyUsage;
+use crate::inference::humanize;
+
+/// Local model storage grouped by family, from
+/// [`OllamaClient::disk_usage`](crate::OllamaClient::disk_usage).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// One entry per family, largest first
+    pub families: Vec<FamilyUsage>,
+
+    /// Size of every local model in bytes
+    pub total: u64,
+}
+
+impl DiskUsage {
+    /// Get the number of local models
+    pub fn model_count(&self) -> usize {
+        self.families.iter().map(|f| f.models.len()).sum()
+    }
+
+    /// Find the entry for `family`
+    pub fn family(&self, family: &str) -> Option<&FamilyUsage> {
+        self.families.iter().find(|f| f.family == family)
+    }
+}
+
+/// One line per family, then the total.
+impl fmt::Display for DiskUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for family in &self.families {
+            writeln!(f, "{family}")?;
+        }
+        write!(
+            f,
+            "total: {} models, {}",
+            self.model_count(),
+            humanize::bytes(self.total)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let usage = DiskUsage {
+            families: vec![
+                FamilyUsage {
+                    family: "gemma3".to_string(),
+                    models: vec!["gemma3:4b".to_string(), "gemma3:1b".to_string()],
+                    size: 4_000_000_000,
+                },
+                FamilyUsage {
+                    family: "llama".to_string(),
+                    models: vec!["llama3.2:latest".to_string()],
+                    size: 2_000_000_000,
+                },
+            ],
+            total: 6_000_000_000,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "gemma3: 2 models, 4.0 GB\nllama: 1 model, 2.0 GB\ntotal: 3 models, 6.0 GB"
+        );
+        assert_eq!(usage.family("llama").unwrap().size, 2_000_000_000);
+    }
+}
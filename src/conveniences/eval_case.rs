@@ -0,0 +1,46 @@
+//! One row of an `EvalRunner`'s test table
+
+use std::collections::BTreeMap;
+
+use super::EvalCheck;
+
+/// A named set of template inputs and the check its output must pass.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{EvalCase, EvalCheck};
+///
+/// let case = EvalCase::new("addition", EvalCheck::exact("4"))
+///     .with_var("a", "2")
+///     .with_var("b", "2");
+/// assert_eq!(case.vars["a"], "2");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvalCase {
+    /// Name shown in the report
+    pub name: String,
+
+    /// Values for the template's `{placeholders}`
+    pub vars: BTreeMap<String, String>,
+
+    /// What the output must satisfy
+    pub check: EvalCheck,
+}
+
+impl EvalCase {
+    /// A case with no inputs yet
+    pub fn new(name: impl Into<String>, check: EvalCheck) -> Self {
+        Self {
+            name: name.into(),
+            vars: BTreeMap::new(),
+            check,
+        }
+    }
+
+    /// Fill the `{name}` placeholder with `value`.
+    pub fn with_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+}
@@ -0,0 +1,142 @@
+//! How an `EvalRunner` decides whether an output passes
+
+use regex::Regex;
+
+use super::Verdict;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Exact(String),
+    Regex(Regex),
+    Judge { criteria: String, min_score: u8 },
+}
+
+/// The expected-output check of an [`EvalCase`](super::EvalCase).
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::EvalCheck;
+///
+/// let exact = EvalCheck::exact("4");
+/// assert!(exact.matches(" 4\n"));
+///
+/// let regex = EvalCheck::regex(r"(?i)\bparis\b")?;
+/// assert!(regex.matches("The capital is Paris."));
+///
+/// let judged = EvalCheck::judge("Names the capital of France.").with_min_score(7);
+/// assert!(judged.is_judged());
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvalCheck {
+    kind: Kind,
+}
+
+impl EvalCheck {
+    /// Pass when the output equals `expected`, ignoring surrounding
+    /// whitespace.
+    pub fn exact(expected: impl Into<String>) -> Self {
+        Self {
+            kind: Kind::Exact(expected.into()),
+        }
+    }
+
+    /// Pass when `pattern` matches anywhere in the output; anchor it with
+    /// `^` and `$` to match the whole output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if `pattern` is not a valid
+    /// regular expression.
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| Error::ValidationError(format!("invalid eval regex: {e}")))?;
+        Ok(Self {
+            kind: Kind::Regex(regex),
+        })
+    }
+
+    /// Pass when a judge model finds the output meets `criteria`; see
+    /// [`OllamaClient::judge`](crate::OllamaClient::judge).
+    pub fn judge(criteria: impl Into<String>) -> Self {
+        Self {
+            kind: Kind::Judge {
+                criteria: criteria.into(),
+                min_score: Verdict::MIN_SCORE,
+            },
+        }
+    }
+
+    /// Also require a judge score of at least `min_score`.
+    ///
+    /// Has no effect on checks that are not judged.
+    pub fn with_min_score(mut self, min_score: u8) -> Self {
+        if let Kind::Judge { min_score: min, .. } = &mut self.kind {
+            *min = min_score;
+        }
+        self
+    }
+
+    /// Whether the check needs a judge model
+    pub fn is_judged(&self) -> bool {
+        matches!(self.kind, Kind::Judge { .. })
+    }
+
+    /// Check `output` without a model; judged checks always return
+    /// `false`.
+    pub fn matches(&self, output: &str) -> bool {
+        match &self.kind {
+            Kind::Exact(expected) => output.trim() == expected.trim(),
+            Kind::Regex(regex) => regex.is_match(output),
+            Kind::Judge { .. } => false,
+        }
+    }
+
+    /// The judge criteria and minimum score, for judged checks
+    pub(super) fn judge_criteria(&self) -> Option<(&str, u8)> {
+        match &self.kind {
+            Kind::Judge {
+                criteria,
+                min_score,
+            } => Some((criteria, *min_score)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_ignores_surrounding_whitespace() {
+        let check = EvalCheck::exact("Paris");
+        assert!(check.matches("Paris\n"));
+        assert!(!check.matches("paris"));
+        assert!(!check.matches("Paris, France"));
+    }
+
+    #[test]
+    fn test_regex_matches_anywhere() {
+        let check = EvalCheck::regex(r"\d+").unwrap();
+        assert!(check.matches("It is 42."));
+        assert!(!check.matches("forty-two"));
+        assert!(matches!(
+            EvalCheck::regex("("),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_judge_criteria() {
+        let check = EvalCheck::judge("Is polite.").with_min_score(8);
+        assert_eq!(check.judge_criteria(), Some(("Is polite.", 8)));
+        assert!(!check.matches("Thank you!"));
+        assert_eq!(
+            EvalCheck::exact("x").with_min_score(8).judge_criteria(),
+            None
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Result type for `EvalRunner::run`
+
+use std::fmt;
+
+use super::EvalResult;
+
+/// Per-case outcomes of [`EvalRunner::run`](super::EvalRunner::run).
+///
+/// Every case gets an entry, in the order it was added, whether it passed,
+/// failed its check, or could not be run.
+#[derive(Debug)]
+pub struct EvalReport {
+    /// One entry per case, in case order
+    pub results: Vec<EvalResult>,
+}
+
+impl EvalReport {
+    /// Check whether every case passed
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(EvalResult::passed)
+    }
+
+    /// Iterate over the cases that passed
+    pub fn passed(&self) -> impl Iterator<Item = &EvalResult> {
+        self.results.iter().filter(|r| r.passed())
+    }
+
+    /// Iterate over the cases whose output failed the check
+    pub fn failed(&self) -> impl Iterator<Item = &EvalResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.result, Ok(false)))
+    }
+
+    /// Iterate over the cases that could not be run or checked
+    pub fn errors(&self) -> impl Iterator<Item = &EvalResult> {
+        self.results.iter().filter(|r| r.is_error())
+    }
+
+    /// Fraction of cases that passed, from 0.0 to 1.0; 1.0 when there are
+    /// no cases
+    pub fn pass_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 1.0;
+        }
+        self.passed().count() as f64 / self.results.len() as f64
+    }
+}
+
+/// A count line, then one line per case that did not pass.
+impl fmt::Display for EvalReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} errors",
+            self.passed().count(),
+            self.failed().count(),
+            self.errors().count()
+        )?;
+        for result in self.results.iter().filter(|r| !r.passed()) {
+            match (&result.result, &result.verdict) {
+                (Err(e), _) => write!(f, "\n- {}: error: {e}", result.name)?,
+                (Ok(_), Some(verdict)) => write!(f, "\n- {}: {verdict}", result.name)?,
+                (Ok(_), None) => write!(
+                    f,
+                    "\n- {}: got {:?}",
+                    result.name,
+                    result.output.as_deref().unwrap_or_default()
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Verdict};
+
+    fn result(name: &str, output: &str, result: crate::Result<bool>) -> EvalResult {
+        EvalResult {
+            name: name.to_string(),
+            output: Some(output.to_string()),
+            verdict: None,
+            result,
+        }
+    }
+
+    #[test]
+    fn test_counts_and_display() {
+        let mut judged = result("polite", "No.", Ok(false));
+        judged.verdict = Some(Verdict {
+            score: 2,
+            pass: false,
+            rationale: "Curt.".to_string(),
+        });
+        let report = EvalReport {
+            results: vec![
+                result("sum", "4", Ok(true)),
+                result("capital", "Lyon", Ok(false)),
+                judged,
+                result("broken", "", Err(Error::HttpStatusError(404))),
+            ],
+        };
+        assert!(!report.is_success());
+        assert_eq!(report.pass_rate(), 0.25);
+        assert_eq!(
+            report.to_string(),
+            "1 passed, 2 failed, 1 errors\n\
+             - capital: got \"Lyon\"\n\
+             - polite: fail 2/10: Curt.\n\
+             - broken: error: HTTP status error: 404"
+        );
+    }
+
+    #[test]
+    fn test_empty_report_succeeds() {
+        let report = EvalReport { results: vec![] };
+        assert!(report.is_success());
+        assert_eq!(report.pass_rate(), 1.0);
+    }
+}
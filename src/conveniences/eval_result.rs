@@ -0,0 +1,33 @@
+//! One entry of an `EvalReport`
+
+use super::Verdict;
+use crate::Result;
+
+/// Outcome of one [`EvalCase`](super::EvalCase).
+#[derive(Debug)]
+pub struct EvalResult {
+    /// Name of the case
+    pub name: String,
+
+    /// The model's reply, if the call succeeded
+    pub output: Option<String>,
+
+    /// The judge's assessment, for judged checks
+    pub verdict: Option<Verdict>,
+
+    /// Whether the output passed the check, or the error that kept the
+    /// case from being checked
+    pub result: Result<bool>,
+}
+
+impl EvalResult {
+    /// Check whether the case passed
+    pub fn passed(&self) -> bool {
+        matches!(self.result, Ok(true))
+    }
+
+    /// Check whether the case could not be run or checked
+    pub fn is_error(&self) -> bool {
+        self.result.is_err()
+    }
+}
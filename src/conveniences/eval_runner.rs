@@ -0,0 +1,268 @@
+//! `EvalRunner`: a prompt template run over a table of test cases
+
+use std::collections::BTreeMap;
+
+use super::chat_batch::run_ordered;
+use super::{EvalCase, EvalReport, EvalResult};
+use crate::http::OllamaClient;
+use crate::{ChatMessage, ChatRequest, Error, ModelOptions, OllamaApiAsync, Result};
+
+/// `template` with each `{name}` replaced by its value in `vars`; `{{` and
+/// `}}` stand for literal braces.
+pub(super) fn render(template: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err(Error::ValidationError(
+                "unmatched '}' in eval template; write '}}' for a literal brace".to_string(),
+            ));
+        }
+        let Some(end) = tail.find('}') else {
+            return Err(Error::ValidationError(
+                "unclosed '{' in eval template; write '{{' for a literal brace".to_string(),
+            ));
+        };
+        let name = tail[1..end].trim();
+        let value = vars.get(name).ok_or_else(|| {
+            Error::ValidationError(format!("no value for eval template placeholder {{{name}}}"))
+        })?;
+        out.push_str(value);
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Runs a prompt template over a table of [`EvalCase`]s and checks each
+/// output, for prompt regression testing.
+///
+/// Each case fills the template's `{name}` placeholders from its
+/// [`vars`](EvalCase::vars) (`{{` and `}}` are literal braces), sends the
+/// result as the user message, and checks the reply with its
+/// [`EvalCheck`](super::EvalCheck). Judged checks are graded by the
+/// [judge model](Self::with_judge_model), which defaults to the model
+/// under test.
+///
+/// Cases run [`concurrency`](Self::with_concurrency) at a time, one by one
+/// unless set. A case that cannot be rendered, sent, or judged is reported
+/// as an error and does not stop the others.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{EvalCase, EvalCheck, ModelOptions, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let report = client
+///     .eval_runner("qwen3:0.6b", "What is {a} + {b}? Reply with the number only.")
+///     .with_options(ModelOptions::default().with_temperature(0.0))
+///     .with_judge_model("qwen3:8b")
+///     .with_case(
+///         EvalCase::new("small", EvalCheck::exact("4"))
+///             .with_var("a", "2")
+///             .with_var("b", "2"),
+///     )
+///     .with_case(
+///         EvalCase::new("large", EvalCheck::regex(r"^\s*1000\s*$")?)
+///             .with_var("a", "600")
+///             .with_var("b", "400"),
+///     )
+///     .with_case(
+///         EvalCase::new("terse", EvalCheck::judge("A bare number, no words."))
+///             .with_var("a", "1")
+///             .with_var("b", "1"),
+///     )
+///     .run()
+///     .await;
+/// println!("{report}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvalRunner {
+    client: OllamaClient,
+    model: String,
+    template: String,
+    system: Option<String>,
+    options: Option<ModelOptions>,
+    judge_model: Option<String>,
+    concurrency: usize,
+    cases: Vec<EvalCase>,
+}
+
+impl OllamaClient {
+    /// Start an [`EvalRunner`] that sends `template` to `model`.
+    pub fn eval_runner(&self, model: impl Into<String>, template: impl Into<String>) -> EvalRunner {
+        EvalRunner {
+            client: self.clone(),
+            model: model.into(),
+            template: template.into(),
+            system: None,
+            options: None,
+            judge_model: None,
+            concurrency: 1,
+            cases: Vec::new(),
+        }
+    }
+}
+
+impl EvalRunner {
+    /// Send `prompt` as the system message of every case.
+    pub fn with_system(mut self, prompt: impl Into<String>) -> Self {
+        self.system = Some(prompt.into());
+        self
+    }
+
+    /// Send `options` with every case.
+    pub fn with_options(mut self, options: ModelOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Grade judged checks with `model` instead of the model under test.
+    pub fn with_judge_model(mut self, model: impl Into<String>) -> Self {
+        self.judge_model = Some(model.into());
+        self
+    }
+
+    /// Run at most `concurrency` cases at a time; 0 is treated as 1.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Add a case to the table.
+    pub fn with_case(mut self, case: EvalCase) -> Self {
+        self.cases.push(case);
+        self
+    }
+
+    /// Add several cases to the table.
+    pub fn with_cases(mut self, cases: impl IntoIterator<Item = EvalCase>) -> Self {
+        self.cases.extend(cases);
+        self
+    }
+
+    /// The cases added so far
+    pub fn cases(&self) -> &[EvalCase] {
+        &self.cases
+    }
+
+    /// Run every case and report each outcome, in case order.
+    pub async fn run(&self) -> EvalReport {
+        let jobs = self
+            .cases
+            .iter()
+            .map(|case| {
+                let runner = self.clone();
+                let case = case.clone();
+                async move { Ok(runner.run_case(case).await) }
+            })
+            .collect();
+        let results = run_ordered(jobs, self.concurrency)
+            .await
+            .into_iter()
+            .zip(&self.cases)
+            .map(|(result, case)| {
+                result.unwrap_or_else(|e| EvalResult {
+                    name: case.name.clone(),
+                    output: None,
+                    verdict: None,
+                    result: Err(e),
+                })
+            })
+            .collect();
+        EvalReport { results }
+    }
+
+    async fn run_case(&self, case: EvalCase) -> EvalResult {
+        let mut result = EvalResult {
+            name: case.name,
+            output: None,
+            verdict: None,
+            result: Ok(false),
+        };
+        let output = match self.ask(&case.vars).await {
+            Ok(output) => output,
+            Err(e) => {
+                result.result = Err(e);
+                return result;
+            }
+        };
+        result.result = match case.check.judge_criteria() {
+            None => Ok(case.check.matches(&output)),
+            Some((criteria, min_score)) => {
+                let judge = self.judge_model.as_deref().unwrap_or(&self.model);
+                match self.client.judge(judge, criteria, &output).await {
+                    Ok(verdict) => {
+                        let passed = verdict.meets(min_score);
+                        result.verdict = Some(verdict);
+                        Ok(passed)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+        };
+        result.output = Some(output);
+        result
+    }
+
+    /// The model's reply to the template filled from `vars`
+    async fn ask(&self, vars: &BTreeMap<String, String>) -> Result<String> {
+        let prompt = render(&self.template, vars)?;
+        let messages = self
+            .system
+            .iter()
+            .map(ChatMessage::system)
+            .chain([ChatMessage::user(prompt)]);
+        let mut request = ChatRequest::new(self.model.as_str(), messages);
+        if let Some(options) = &self.options {
+            request = request.with_options(options.clone());
+        }
+        let response = self.client.chat(&request).await?;
+        Ok(response.content().unwrap_or_default().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let rendered = render("Add { a } and {b}.", &vars(&[("a", "2"), ("b", "3")])).unwrap();
+        assert_eq!(rendered, "Add 2 and 3.");
+    }
+
+    #[test]
+    fn test_render_escapes_braces() {
+        let rendered = render("Reply as {{\"n\": {n}}}", &vars(&[("n", "1")])).unwrap();
+        assert_eq!(rendered, "Reply as {\"n\": 1}");
+    }
+
+    #[test]
+    fn test_render_rejects_missing_and_unbalanced() {
+        for template in ["{missing}", "{open", "close}"] {
+            assert!(matches!(
+                render(template, &vars(&[])),
+                Err(Error::ValidationError(_))
+            ));
+        }
+    }
+}
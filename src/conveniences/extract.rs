@@ -0,0 +1,74 @@
+//! `OllamaClient::extract`
+
+use serde::de::DeserializeOwned;
+
+use crate::http::OllamaClient;
+use crate::{FormatSetting, Result};
+
+const EXTRACT_SYSTEM: &str = "Extract the requested information from the user's text. \
+    Reply with a single JSON object and nothing else. Use null for fields the text does not mention.";
+
+impl OllamaClient {
+    /// Extract structured data of type `T` from free text.
+    ///
+    /// Uses JSON mode and decodes the reply with serde, so `T` only needs
+    /// [`DeserializeOwned`]. Name your fields clearly; the model sees no
+    /// schema. Use [`extract_with_schema`](Self::extract_with_schema) to
+    /// constrain the output to an exact shape.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::SerializationError`](crate::Error::SerializationError) if the
+    ///   reply does not decode as `T`
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Contact {
+    ///     name: String,
+    ///     email: Option<String>,
+    /// }
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let contact: Contact = client
+    ///     .extract("qwen3:0.6b", "Reach Ana at ana@example.com.")
+    ///     .await?;
+    /// println!("{} <{:?}>", contact.name, contact.email);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn extract<T: DeserializeOwned>(&self, model: &str, text: &str) -> Result<T> {
+        self.chat_structured(
+            model,
+            EXTRACT_SYSTEM.to_string(),
+            text.to_string(),
+            FormatSetting::json(),
+        )
+        .await
+    }
+
+    /// Extract structured data of type `T`, constraining the reply to `schema`.
+    ///
+    /// Same as [`extract`](Self::extract) but the server enforces the given
+    /// JSON schema, which also tells the model which fields to fill.
+    pub async fn extract_with_schema<T: DeserializeOwned>(
+        &self,
+        model: &str,
+        text: &str,
+        schema: serde_json::Value,
+    ) -> Result<T> {
+        self.chat_structured(
+            model,
+            EXTRACT_SYSTEM.to_string(),
+            text.to_string(),
+            FormatSetting::schema(schema),
+        )
+        .await
+    }
+}
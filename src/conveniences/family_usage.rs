@@ -0,0 +1,53 @@
+//! Per-family entry of a `DiskUsage` report
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::inference::humanize;
+
+/// Disk space taken by the local models of one family.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FamilyUsage {
+    /// Model family from `details.family`, or `"unknown"`
+    pub family: String,
+
+    /// Names of the models in this family
+    pub models: Vec<String>,
+
+    /// Total size on disk in bytes
+    pub size: u64,
+}
+
+impl fmt::Display for FamilyUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.models.len() == 1 {
+            "model"
+        } else {
+            "models"
+        };
+        write!(
+            f,
+            "{}: {} {}, {}",
+            self.family,
+            self.models.len(),
+            noun,
+            humanize::bytes(self.size)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let usage = FamilyUsage {
+            family: "llama".to_string(),
+            models: vec!["llama3.2:latest".to_string()],
+            size: 2_019_393_189,
+        };
+        assert_eq!(usage.to_string(), "llama: 1 model, 2.0 GB");
+    }
+}
@@ -0,0 +1,81 @@
+//! `OllamaClient::generate_code`
+
+use super::{CodeBlock, extract_code_blocks};
+use crate::http::OllamaClient;
+use crate::{ChatMessage, ChatRequest, Error, OllamaApiAsync, Result};
+
+impl OllamaClient {
+    /// Ask `model` to write `language` code for `task` and return the code.
+    ///
+    /// The model is told to answer with one fenced code block; the first
+    /// block tagged with `language` (ignoring case) is returned, else the
+    /// first block of any language. A reply without fences is taken as
+    /// bare code. Use [`extract_code_blocks`] on a reply of your own to get
+    /// every block.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `task` or `language` is blank
+    /// - [`Error::SerializationError`] if the reply has no code
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let block = client
+    ///     .generate_code("qwen2.5-coder:1.5b", "Reverse a string.", "rust")
+    ///     .await?;
+    /// println!("{block}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_code(
+        &self,
+        model: &str,
+        task: &str,
+        language: &str,
+    ) -> Result<CodeBlock> {
+        if task.trim().is_empty() || language.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "generate_code needs a task and a language".to_string(),
+            ));
+        }
+
+        let system = format!(
+            "You are an expert {language} programmer. Write {language} code for the \
+             user's task. Reply with a single fenced code block tagged `{language}` \
+             and no explanation outside it."
+        );
+        let request = ChatRequest::new(
+            model,
+            [ChatMessage::system(system), ChatMessage::user(task)],
+        );
+        let response = self.chat(&request).await?;
+        let reply = response.content().unwrap_or_default();
+
+        let mut blocks = extract_code_blocks(reply);
+        let tagged = blocks.iter().position(|b| {
+            b.language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        });
+        let block = match tagged {
+            Some(index) => blocks.swap_remove(index),
+            None if !blocks.is_empty() => blocks.swap_remove(0),
+            None => CodeBlock {
+                language: Some(language.to_string()),
+                code: reply.trim().to_string(),
+            },
+        };
+        if block.code.trim().is_empty() {
+            return Err(Error::SerializationError(
+                "model returned no code".to_string(),
+            ));
+        }
+        Ok(block)
+    }
+}
@@ -0,0 +1,138 @@
+//! Persistent message history shared between forked sessions
+
+use std::sync::Arc;
+
+use crate::ChatMessage;
+
+#[derive(Debug)]
+struct Node {
+    message: ChatMessage,
+    parent: Option<Arc<Node>>,
+}
+
+/// A message list stored newest first as a chain of shared nodes.
+///
+/// Cloning or truncating a history never copies messages: branches point
+/// at the same earlier nodes and only add their own newer ones.
+#[derive(Debug, Clone, Default)]
+pub(super) struct History {
+    tip: Option<Arc<Node>>,
+    len: usize,
+}
+
+impl History {
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The messages, oldest first
+    pub(super) fn iter(&self) -> impl DoubleEndedIterator<Item = &ChatMessage> {
+        let mut newest_first = Vec::with_capacity(self.len);
+        let mut node = self.tip.as_deref();
+        while let Some(current) = node {
+            newest_first.push(&current.message);
+            node = current.parent.as_deref();
+        }
+        newest_first.into_iter().rev()
+    }
+
+    /// The messages from `start` on, oldest first
+    pub(super) fn since(&self, start: usize) -> Vec<ChatMessage> {
+        self.iter().skip(start).cloned().collect()
+    }
+
+    pub(super) fn to_vec(&self) -> Vec<ChatMessage> {
+        self.since(0)
+    }
+
+    pub(super) fn last(&self) -> Option<&ChatMessage> {
+        self.tip.as_deref().map(|node| &node.message)
+    }
+
+    pub(super) fn push(&mut self, message: ChatMessage) {
+        self.tip = Some(Arc::new(Node {
+            message,
+            parent: self.tip.take(),
+        }));
+        self.len += 1;
+    }
+
+    pub(super) fn pop(&mut self) -> Option<ChatMessage> {
+        let tip = self.tip.take()?;
+        self.len -= 1;
+        match Arc::try_unwrap(tip) {
+            Ok(node) => {
+                self.tip = node.parent;
+                Some(node.message)
+            }
+            Err(shared) => {
+                self.tip = shared.parent.clone();
+                Some(shared.message.clone())
+            }
+        }
+    }
+
+    /// Append `messages`, oldest first
+    pub(super) fn extend(&mut self, messages: impl IntoIterator<Item = ChatMessage>) {
+        for message in messages {
+            self.push(message);
+        }
+    }
+
+    /// Keep only the first `len` messages
+    pub(super) fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+}
+
+impl From<Vec<ChatMessage>> for History {
+    fn from(messages: Vec<ChatMessage>) -> Self {
+        let mut history = Self::default();
+        history.extend(messages);
+        history
+    }
+}
+
+impl Drop for History {
+    /// Unlinks unshared nodes one at a time, so dropping a long history
+    /// cannot overflow the stack through nested `Arc` drops.
+    fn drop(&mut self) {
+        let mut tip = self.tip.take();
+        while let Some(node) = tip {
+            tip = Arc::try_unwrap(node).ok().and_then(|node| node.parent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(history: &History) -> Vec<&str> {
+        history.iter().map(|m| m.content.as_str()).collect()
+    }
+
+    #[test]
+    fn test_branches_share_earlier_messages() {
+        let mut trunk = History::from(vec![ChatMessage::user("a"), ChatMessage::assistant("b")]);
+        let mut branch = trunk.clone();
+        branch.truncate(1);
+        branch.push(ChatMessage::assistant("c"));
+        trunk.push(ChatMessage::user("d"));
+
+        assert_eq!(contents(&trunk), ["a", "b", "d"]);
+        assert_eq!(contents(&branch), ["a", "c"]);
+        assert_eq!(branch.len(), 2);
+    }
+
+    #[test]
+    fn test_pop_returns_shared_message() {
+        let mut trunk = History::from(vec![ChatMessage::user("a")]);
+        let branch = trunk.clone();
+        assert_eq!(trunk.pop().unwrap().content, "a");
+        assert_eq!(trunk.len(), 0);
+        assert_eq!(contents(&branch), ["a"]);
+    }
+}
@@ -0,0 +1,28 @@
+//! Persistence trait for `JobQueue`
+
+use async_trait::async_trait;
+
+use super::JobRecord;
+use crate::Result;
+
+/// Where a [`JobQueue`](super::JobQueue) keeps its job records.
+///
+/// [`MemoryBackend`](super::MemoryBackend) and
+/// [`JsonDirBackend`](super::JsonDirBackend) implement this as well as
+/// [`SessionBackend`](super::SessionBackend); give jobs and sessions
+/// separate directories. Implement it to keep jobs in a database shared by
+/// several web workers.
+#[async_trait]
+pub trait JobBackend: Send + Sync {
+    /// The job `id`, or `None` if there is none.
+    async fn load_job(&self, id: &str) -> Result<Option<JobRecord>>;
+
+    /// Store `record` under its id, replacing any previous record.
+    async fn save_job(&self, record: &JobRecord) -> Result<()>;
+
+    /// Remove the job `id`. Returns `false` if there was none.
+    async fn delete_job(&self, id: &str) -> Result<bool>;
+
+    /// Ids of all stored jobs, in no particular order.
+    async fn list_jobs(&self) -> Result<Vec<String>>;
+}
@@ -0,0 +1,35 @@
+//! Completion callback for `JobQueue`
+
+use async_trait::async_trait;
+
+use super::JobRecord;
+
+/// Called by a [`JobQueue`](super::JobQueue) when one of its jobs runs to
+/// completion, successfully or not; register it with
+/// [`with_callback`](super::JobQueue::with_callback).
+///
+/// Cancelled jobs are not reported: whoever cancelled them already knows.
+/// The callback runs on the job's task after its record is stored, so
+/// [`wait`](super::JobQueue::wait) returns only once it has finished.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use ollama_oxide::{JobCallback, JobRecord};
+///
+/// /// Logs every finished job.
+/// struct LogJobs;
+///
+/// #[async_trait]
+/// impl JobCallback for LogJobs {
+///     async fn on_finished(&self, job: &JobRecord) {
+///         println!("job {} {:?}", job.id, job.status);
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait JobCallback: Send + Sync {
+    /// `job` has finished with status `Succeeded` or `Failed`.
+    async fn on_finished(&self, job: &JobRecord);
+}
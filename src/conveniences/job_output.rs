@@ -0,0 +1,26 @@
+//! Response of a finished `JobQueue` job
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChatResponse, GenerateResponse};
+
+/// The response of a successful [`JobQueue`](super::JobQueue) job, matching
+/// its [`JobRequest`](super::JobRequest).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "endpoint", content = "response", rename_all = "snake_case")]
+pub enum JobOutput {
+    /// Response to a generate request
+    Generate(GenerateResponse),
+    /// Response to a chat request
+    Chat(ChatResponse),
+}
+
+impl JobOutput {
+    /// The generated text
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            JobOutput::Generate(response) => response.text(),
+            JobOutput::Chat(response) => response.content(),
+        }
+    }
+}
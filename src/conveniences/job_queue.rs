@@ -0,0 +1,379 @@
+//! `JobQueue`: generate and chat calls run in the background
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, Semaphore, watch};
+use tokio::task::AbortHandle;
+
+use url::Url;
+
+use super::{JobBackend, JobCallback, JobOutput, JobRecord, JobRequest, JobStatus, MemoryBackend};
+use crate::http::OllamaClient;
+use crate::http::idempotency_key;
+use crate::{Error, OllamaApiAsync, Result};
+
+/// A job whose task runs in this queue
+#[derive(Debug)]
+struct Live {
+    abort: AbortHandle,
+    /// Closes when the task ends, however it ends; holds the error that
+    /// kept the job's final record from being stored, if any
+    done: watch::Receiver<Option<Error>>,
+}
+
+/// Runs generate and chat requests as background jobs, so a web handler
+/// can answer at once with a job id and let the client poll or wait for a
+/// multi-minute generation.
+///
+/// [`submit`](Self::submit) stores the job and returns its id; the job
+/// then waits for one of the queue's workers (one by default, see
+/// [`with_concurrency`](Self::with_concurrency)) and runs without
+/// streaming. [`status`](Self::status) polls a job, [`wait`](Self::wait)
+/// awaits it, and [`cancel`](Self::cancel) stops it.
+///
+/// Job records live in memory by default. With a persistent
+/// [`JobBackend`] such as [`JsonDirBackend`](super::JsonDirBackend),
+/// outcomes survive restarts, and [`resume`](Self::resume) reruns jobs a
+/// previous process left unfinished.
+///
+/// To hear about finished jobs without polling, submit them with
+/// [`submit_with_webhook`](Self::submit_with_webhook) to have the record
+/// POSTed to a URL, or register a [`JobCallback`] with
+/// [`with_callback`](Self::with_callback).
+///
+/// Jobs are plain calls on the client, so its timeout applies: raise it
+/// with [`ClientConfig::with_timeout`](crate::ClientConfig::with_timeout)
+/// for long generations.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{GenerateRequest, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let jobs = client.job_queue().with_concurrency(2);
+///
+/// let id = jobs
+///     .submit(GenerateRequest::new("qwen3:0.6b", "Write a long story."))
+///     .await?;
+/// // ... later, perhaps from another request handler
+/// let job = jobs.wait(&id).await?;
+/// println!("{:?}: {}", job.status, job.text().unwrap_or_default());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct JobQueue {
+    client: OllamaClient,
+    backend: Arc<dyn JobBackend>,
+    workers: Arc<Semaphore>,
+    live: Arc<Mutex<HashMap<String, Live>>>,
+    callbacks: Vec<Arc<dyn JobCallback>>,
+}
+
+impl fmt::Debug for JobQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobQueue")
+            .field("workers", &self.workers)
+            .field("callbacks", &self.callbacks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl OllamaClient {
+    /// Create a [`JobQueue`] running jobs on this client, one at a time,
+    /// with records kept in memory.
+    pub fn job_queue(&self) -> JobQueue {
+        JobQueue {
+            client: self.clone(),
+            backend: Arc::new(MemoryBackend::new()),
+            workers: Arc::new(Semaphore::new(1)),
+            live: Arc::default(),
+            callbacks: Vec::new(),
+        }
+    }
+}
+
+impl JobQueue {
+    /// Keep job records in `backend` instead of memory.
+    pub fn with_backend(mut self, backend: Arc<dyn JobBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Run up to `workers` jobs at once; values below 1 are raised to 1.
+    ///
+    /// Set this before submitting: jobs already submitted keep the workers
+    /// they were submitted with.
+    pub fn with_concurrency(mut self, workers: usize) -> Self {
+        self.workers = Arc::new(Semaphore::new(workers.max(1)));
+        self
+    }
+
+    /// Call `callback` whenever a job submitted from now on finishes.
+    /// Callbacks run in the order they were added.
+    pub fn with_callback(mut self, callback: Arc<dyn JobCallback>) -> Self {
+        self.callbacks.push(callback);
+        self
+    }
+
+    /// Store a job running `request` and start it in the background.
+    /// Returns the job id.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend; the job is not started then.
+    pub async fn submit(&self, request: impl Into<JobRequest>) -> Result<String> {
+        self.enqueue(JobRecord::new(idempotency_key::new_key(), request.into()))
+            .await
+    }
+
+    /// Like [`submit`](Self::submit), and once the job has run, POST its
+    /// [`JobRecord`] as JSON to `webhook`.
+    ///
+    /// The webhook gets one attempt, within the client's timeout and
+    /// without the client's API key. If it fails or answers with an error
+    /// status, the reason is stored in the record's
+    /// [`webhook_error`](JobRecord::webhook_error). Cancelled jobs are not
+    /// delivered.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] if `webhook` is not an http(s) URL, or any
+    /// error from the backend; the job is not started then.
+    pub async fn submit_with_webhook(
+        &self,
+        request: impl Into<JobRequest>,
+        webhook: &str,
+    ) -> Result<String> {
+        let url = Url::parse(webhook)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .ok_or_else(|| Error::ValidationError(format!("invalid webhook URL '{webhook}'")))?;
+        let mut record = JobRecord::new(idempotency_key::new_key(), request.into());
+        record.webhook = Some(url.into());
+        self.enqueue(record).await
+    }
+
+    /// Store a new job and start it.
+    async fn enqueue(&self, record: JobRecord) -> Result<String> {
+        self.backend.save_job(&record).await?;
+        let id = record.id.clone();
+        self.start(record).await;
+        Ok(id)
+    }
+
+    /// The job `id` as last stored, or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn status(&self, id: &str) -> Result<Option<JobRecord>> {
+        self.backend.load_job(id).await
+    }
+
+    /// Wait for the job `id` to finish and return its record.
+    ///
+    /// A job not running in this queue, e.g. one from an earlier process,
+    /// is returned as stored, finished or not.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] for an unknown id, or any error from the
+    /// backend, including one that kept this job's final record from being
+    /// stored. A failed job is not an error: check its
+    /// [`status`](JobRecord::status).
+    pub async fn wait(&self, id: &str) -> Result<JobRecord> {
+        let done = self.live.lock().await.get(id).map(|job| job.done.clone());
+        if let Some(mut done) = done {
+            // Returns once the task ends or reports a storage error
+            let _ = done.changed().await;
+            // A cancel stores its record while holding the lock
+            drop(self.live.lock().await);
+            if let Some(e) = done.borrow().clone() {
+                return Err(e);
+            }
+        }
+        self.backend
+            .load_job(id)
+            .await?
+            .ok_or_else(|| Error::ValidationError(format!("unknown job '{id}'")))
+    }
+
+    /// Cancel the job `id` if it is queued or running here, dropping its
+    /// request. Returns `false` if it was not.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend while storing the cancellation.
+    pub async fn cancel(&self, id: &str) -> Result<bool> {
+        let mut live = self.live.lock().await;
+        let Some(job) = live.remove(id) else {
+            return Ok(false);
+        };
+        job.abort.abort();
+        if let Some(mut record) = self.backend.load_job(id).await? {
+            record.set_status(JobStatus::Cancelled);
+            self.backend.save_job(&record).await?;
+        }
+        Ok(true)
+    }
+
+    /// Delete the record of job `id`, cancelling it first if it is still
+    /// running. Returns `false` if none was stored.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn remove(&self, id: &str) -> Result<bool> {
+        self.cancel(id).await?;
+        self.backend.delete_job(id).await
+    }
+
+    /// All stored jobs, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn jobs(&self) -> Result<Vec<JobRecord>> {
+        let mut jobs = Vec::new();
+        for id in self.backend.list_jobs().await? {
+            if let Some(record) = self.backend.load_job(&id).await? {
+                jobs.push(record);
+            }
+        }
+        jobs.sort_by(|a, b| (a.submitted_at, &a.id).cmp(&(b.submitted_at, &b.id)));
+        Ok(jobs)
+    }
+
+    /// Restart every stored job that is neither finished nor running here,
+    /// e.g. after a crash. Returns how many were restarted.
+    ///
+    /// Call this once at startup, and only from one process: a job another
+    /// process is running would be run twice.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn resume(&self) -> Result<usize> {
+        let mut resumed = 0;
+        for mut record in self.jobs().await? {
+            if record.status.is_finished() || self.live.lock().await.contains_key(&record.id) {
+                continue;
+            }
+            record.set_status(JobStatus::Queued);
+            self.backend.save_job(&record).await?;
+            self.start(record).await;
+            resumed += 1;
+        }
+        Ok(resumed)
+    }
+
+    /// Spawn the task running `record`.
+    async fn start(&self, record: JobRecord) {
+        // Registered before the task can finish and look itself up
+        let mut live = self.live.lock().await;
+        let (done_tx, done) = watch::channel(None);
+        let id = record.id.clone();
+        let queue = self.clone();
+        let task = tokio::spawn(async move {
+            if let Err(e) = queue.run(record).await {
+                done_tx.send_replace(Some(e));
+            }
+        });
+        live.insert(
+            id,
+            Live {
+                abort: task.abort_handle(),
+                done,
+            },
+        );
+    }
+
+    /// Waits for a worker, runs the request, stores the outcome, and
+    /// reports it.
+    ///
+    /// Returns the first save that fails, since the stored record is then
+    /// out of date; [`wait`](Self::wait) hands it on. A job that cannot be
+    /// stored as running is not run at all.
+    async fn run(&self, mut record: JobRecord) -> Result<()> {
+        let Ok(worker) = self.workers.acquire().await else {
+            return Ok(());
+        };
+        record.set_status(JobStatus::Running);
+        if let Err(e) = self.backend.save_job(&record).await {
+            self.live.lock().await.remove(&record.id);
+            return Err(e);
+        }
+
+        let outcome = match &record.request {
+            JobRequest::Generate(request) => {
+                self.client.generate(request).await.map(JobOutput::Generate)
+            }
+            JobRequest::Chat(request) => self.client.chat(request).await.map(JobOutput::Chat),
+        };
+        match outcome {
+            Ok(output) => {
+                record.output = Some(output);
+                record.set_status(JobStatus::Succeeded);
+            }
+            Err(e) => {
+                record.error = Some(e.to_string());
+                record.set_status(JobStatus::Failed);
+            }
+        }
+
+        drop(worker);
+
+        let stored = {
+            let mut live = self.live.lock().await;
+            // Gone if cancelled meanwhile; the cancel stores the record then
+            if live.remove(&record.id).is_none() {
+                return Ok(());
+            }
+            self.backend.save_job(&record).await
+        };
+        // Notified even if unstored, so the outcome is not lost
+        let notified = self.notify(record).await;
+        stored.and(notified)
+    }
+
+    /// Delivers a finished job to its webhook and the callbacks.
+    ///
+    /// The callbacks run even if storing the webhook error fails; that
+    /// error is returned afterwards.
+    async fn notify(&self, mut record: JobRecord) -> Result<()> {
+        let mut stored = Ok(());
+        if let Some(webhook) = &record.webhook
+            && let Err(e) = self.deliver(webhook, &record).await
+        {
+            record.webhook_error = Some(e.to_string());
+            stored = self.backend.save_job(&record).await;
+        }
+        for callback in &self.callbacks {
+            callback.on_finished(&record).await;
+        }
+        stored
+    }
+
+    /// POSTs `record` to `webhook`.
+    ///
+    /// Goes through the client's pool for other hosts, so the webhook never
+    /// sees the client's API key.
+    async fn deliver(&self, webhook: &str, record: &JobRecord) -> Result<()> {
+        let response = self
+            .client
+            .http_external()?
+            .post(webhook)
+            .json(record)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(Error::HttpStatusError(response.status().as_u16()));
+        }
+        Ok(())
+    }
+}
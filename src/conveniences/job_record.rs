@@ -0,0 +1,78 @@
+//! Serializable state of a `JobQueue` job
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::{JobOutput, JobRequest, JobStatus};
+
+/// Seconds since the Unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Everything known about one [`JobQueue`](super::JobQueue) job: what it
+/// runs, where it is, and how it ended.
+///
+/// This is what a [`JobBackend`](super::JobBackend) stores, so a job's
+/// outcome can be looked up after the process that ran it has exited.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRecord {
+    /// The job id returned by [`submit`](super::JobQueue::submit)
+    pub id: String,
+    /// The request the job runs
+    pub request: JobRequest,
+    /// Current state
+    pub status: JobStatus,
+    /// The response, once the job succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<JobOutput>,
+    /// The error message, once the job failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the job was submitted, in seconds since the Unix epoch
+    pub submitted_at: u64,
+    /// When the job finished, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    /// URL the finished record is POSTed to, set with
+    /// [`submit_with_webhook`](super::JobQueue::submit_with_webhook)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Why the webhook could not be delivered, if it could not
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_error: Option<String>,
+}
+
+impl JobRecord {
+    /// A queued job `id` running `request`, submitted now.
+    pub(crate) fn new(id: String, request: JobRequest) -> Self {
+        Self {
+            id,
+            request,
+            status: JobStatus::Queued,
+            output: None,
+            error: None,
+            submitted_at: now(),
+            finished_at: None,
+            webhook: None,
+            webhook_error: None,
+        }
+    }
+
+    /// Moves the job to `status`, stamping the finish time if it is final.
+    pub(crate) fn set_status(&mut self, status: JobStatus) {
+        self.status = status;
+        if status.is_finished() {
+            self.finished_at = Some(now());
+        }
+    }
+
+    /// The generated text, once the job succeeded
+    pub fn text(&self) -> Option<&str> {
+        self.output.as_ref().and_then(JobOutput::text)
+    }
+}
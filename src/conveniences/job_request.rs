@@ -0,0 +1,41 @@
+//! Request run by a `JobQueue` job
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChatRequest, GenerateRequest};
+
+/// The request a [`JobQueue`](super::JobQueue) job runs: a generate or chat
+/// call, sent without streaming.
+///
+/// Built from either request type with `into()`, so
+/// [`submit`](super::JobQueue::submit) accepts both.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "endpoint", content = "request", rename_all = "snake_case")]
+pub enum JobRequest {
+    /// `POST /api/generate`
+    Generate(GenerateRequest),
+    /// `POST /api/chat`
+    Chat(ChatRequest),
+}
+
+impl JobRequest {
+    /// Model the request is sent to
+    pub fn model(&self) -> &str {
+        match self {
+            JobRequest::Generate(request) => &request.model,
+            JobRequest::Chat(request) => &request.model,
+        }
+    }
+}
+
+impl From<GenerateRequest> for JobRequest {
+    fn from(request: GenerateRequest) -> Self {
+        JobRequest::Generate(request)
+    }
+}
+
+impl From<ChatRequest> for JobRequest {
+    fn from(request: ChatRequest) -> Self {
+        JobRequest::Chat(request)
+    }
+}
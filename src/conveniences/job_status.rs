@@ -0,0 +1,26 @@
+//! Lifecycle state of a `JobQueue` job
+
+use serde::{Deserialize, Serialize};
+
+/// Where a [`JobQueue`](super::JobQueue) job is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for a free worker
+    Queued,
+    /// Request sent, waiting for the response
+    Running,
+    /// Finished with a response
+    Succeeded,
+    /// Finished with an error
+    Failed,
+    /// Cancelled before it finished
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Whether the job has stopped, successfully or not
+    pub fn is_finished(self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
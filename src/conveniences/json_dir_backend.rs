@@ -0,0 +1,190 @@
+//! `SessionBackend` and `JobBackend` storing one JSON file per record
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::{JobBackend, JobRecord, SessionBackend, SessionRecord};
+use crate::{Error, Result};
+
+const EXTENSION: &str = "json";
+
+/// Suffix making each temporary file unique within the process
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Stores each session as `<name>.json` in a directory, or each job as
+/// `<id>.json`; keep sessions and jobs in separate directories.
+///
+/// The directory is created on the first save. Files are written to a
+/// temporary file of their own and renamed into place, so a crash never
+/// leaves a half-written record behind and concurrent saves of one record
+/// never mix their bytes; the last rename wins. Names may contain ASCII
+/// letters, digits, `-`, `_`, `.` and `@`, and must not start with `.`.
+///
+/// File access is synchronous; records are small, so each call blocks only
+/// briefly.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use ollama_oxide::{JsonDirBackend, OllamaClient};
+///
+/// let client = OllamaClient::default()?;
+/// let store = client
+///     .session_store("qwen3:0.6b")
+///     .with_backend(Arc::new(JsonDirBackend::new("./sessions")));
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonDirBackend {
+    dir: PathBuf,
+}
+
+impl JsonDirBackend {
+    /// Store sessions in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the records are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@'));
+        if !valid {
+            return Err(Error::ValidationError(format!(
+                "invalid record name '{name}'"
+            )));
+        }
+        Ok(self.dir.join(format!("{name}.{EXTENSION}")))
+    }
+}
+
+impl JsonDirBackend {
+    fn read<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        let path = self.path(name)?;
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::storage(&path, e)),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| Error::storage(&path, e))
+    }
+
+    fn write<T: Serialize>(&self, name: &str, record: &T) -> Result<()> {
+        let path = self.path(name)?;
+        let json = serde_json::to_vec_pretty(record)?;
+        fs::create_dir_all(&self.dir).map_err(|e| Error::storage(&self.dir, e))?;
+        let tmp = self.dir.join(format!(
+            ".{name}.{EXTENSION}.{}-{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, json).map_err(|e| Error::storage(&tmp, e))?;
+        fs::rename(&tmp, &path).map_err(|e| {
+            let _ = fs::remove_file(&tmp);
+            Error::storage(&path, e)
+        })
+    }
+
+    fn remove(&self, name: &str) -> Result<bool> {
+        let path = self.path(name)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(Error::storage(&path, e)),
+        }
+    }
+
+    fn names(&self) -> Result<Vec<String>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::storage(&self.dir, e)),
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| Error::storage(&self.dir, e))?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION)
+                && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+                && !name.starts_with('.')
+            {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+}
+
+#[async_trait]
+impl SessionBackend for JsonDirBackend {
+    async fn load(&self, name: &str) -> Result<Option<SessionRecord>> {
+        self.read(name)
+    }
+
+    async fn save(&self, name: &str, record: &SessionRecord) -> Result<()> {
+        self.write(name, record)
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool> {
+        self.remove(name)
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        self.names()
+    }
+}
+
+/// Stores each job as `<id>.json`.
+#[async_trait]
+impl JobBackend for JsonDirBackend {
+    async fn load_job(&self, id: &str) -> Result<Option<JobRecord>> {
+        self.read(id)
+    }
+
+    async fn save_job(&self, record: &JobRecord) -> Result<()> {
+        self.write(&record.id, record)
+    }
+
+    async fn delete_job(&self, id: &str) -> Result<bool> {
+        self.remove(id)
+    }
+
+    async fn list_jobs(&self) -> Result<Vec<String>> {
+        self.names()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_rejects_traversal() {
+        let backend = JsonDirBackend::new("sessions");
+        for name in ["", "../x", ".hidden", "a/b", "a\\b", "a b"] {
+            assert!(
+                matches!(backend.path(name), Err(Error::ValidationError(_))),
+                "{name:?}"
+            );
+        }
+        assert_eq!(
+            backend.path("user-42@web").unwrap(),
+            Path::new("sessions").join("user-42@web.json")
+        );
+    }
+}
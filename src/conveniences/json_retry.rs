@@ -0,0 +1,133 @@
+//! `OllamaClient::generate_json_with_retries`
+
+use serde::de::DeserializeOwned;
+
+use crate::http::OllamaClient;
+use crate::{Error, GenerateRequest, OllamaApiAsync, Result};
+
+/// Parse `text` as JSON, falling back to the outermost `{...}` or `[...]`
+/// span so replies wrapped in prose or code fences still decode.
+fn parse_lenient<T: DeserializeOwned>(text: &str) -> std::result::Result<T, serde_json::Error> {
+    let whole = serde_json::from_str(text.trim());
+    if whole.is_ok() {
+        return whole;
+    }
+
+    let span = |open: char, close: char| {
+        let start = text.find(open)?;
+        let end = text.rfind(close)?;
+        (start < end).then(|| &text[start..=end])
+    };
+    [span('{', '}'), span('[', ']')]
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| serde_json::from_str(candidate).ok())
+        .map_or(whole, Ok)
+}
+
+fn retry_prompt(original: &str, reply: &str, error: &serde_json::Error) -> String {
+    format!(
+        "{original}\n\nYour previous reply was:\n{reply}\n\n\
+         It could not be parsed as JSON ({error}). \
+         Reply again with only the JSON value and no other text."
+    )
+}
+
+impl OllamaClient {
+    /// Generate a completion and parse it as JSON, re-prompting on failure.
+    ///
+    /// The reply is parsed leniently: if the whole text is not valid JSON,
+    /// the outermost object or array inside it is tried, which handles models
+    /// that wrap JSON in prose or code fences. If that still fails, the
+    /// request is sent again with the bad reply and the parse error appended
+    /// to the prompt, up to `max_attempts` calls in total.
+    ///
+    /// This does not constrain the output; pair it with
+    /// [`GenerateRequest::with_format`] when the model supports structured
+    /// outputs.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `max_attempts` is 0
+    /// - [`Error::SerializationError`] with the last parse error once all
+    ///   attempts are used
+    /// - Any error from [`generate`](crate::OllamaApiAsync::generate)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new(
+    ///     "qwen3:0.6b",
+    ///     "List three primary colors as a JSON array of strings.",
+    /// );
+    /// let colors: Vec<String> = client.generate_json_with_retries(&request, 3).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_json_with_retries<T: DeserializeOwned>(
+        &self,
+        req: &GenerateRequest,
+        max_attempts: u32,
+    ) -> Result<T> {
+        if max_attempts == 0 {
+            return Err(Error::ValidationError(
+                "generate_json_with_retries needs at least one attempt".to_string(),
+            ));
+        }
+
+        let original = req.prompt.clone().unwrap_or_default();
+        let mut attempt_req = req.clone();
+        let mut last_error = None;
+
+        for _ in 0..max_attempts {
+            let response = self.generate(&attempt_req).await?;
+            let reply = response.text().unwrap_or_default();
+            match parse_lenient(reply) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    attempt_req.prompt = Some(retry_prompt(&original, reply, &e));
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(Error::SerializationError(format!(
+            "no valid JSON after {max_attempts} attempts: {}",
+            last_error.map(|e| e.to_string()).unwrap_or_default()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_parse_lenient_plain_json() {
+        let value: Value = parse_lenient(" {\"a\": 1} ").unwrap();
+        assert_eq!(value, json!({"a": 1}));
+    }
+
+    #[test]
+    fn test_parse_lenient_strips_prose_and_fences() {
+        let text = "Sure! Here it is:\n```json\n{\"a\": [1, 2]}\n```\nHope that helps.";
+        let value: Value = parse_lenient(text).unwrap();
+        assert_eq!(value, json!({"a": [1, 2]}));
+    }
+
+    #[test]
+    fn test_parse_lenient_array() {
+        let colors: Vec<String> = parse_lenient("Colors: [\"red\", \"blue\"].").unwrap();
+        assert_eq!(colors, ["red", "blue"]);
+    }
+
+    #[test]
+    fn test_parse_lenient_rejects_prose() {
+        assert!(parse_lenient::<Value>("I cannot do that.").is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! `OllamaClient::judge`
+
+use serde_json::json;
+
+use super::Verdict;
+use crate::http::OllamaClient;
+use crate::{FormatSetting, Result};
+
+impl OllamaClient {
+    /// Grade `candidate_output` against `criteria` with a judge model.
+    ///
+    /// The judge is asked for a 1-10 score, a pass/fail decision, and a
+    /// short rationale, constrained by a JSON schema. Scores outside the
+    /// range are clamped. Pick a judge model at least as capable as the one
+    /// that produced the output.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::SerializationError`](crate::Error::SerializationError) if
+    ///   the judge ignores the schema
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let verdict = client
+    ///     .judge(
+    ///         "qwen3:8b",
+    ///         "Answers the question correctly in one sentence.",
+    ///         "Paris is the capital of France.",
+    ///     )
+    ///     .await?;
+    /// if !verdict.meets(7) {
+    ///     eprintln!("rejected: {verdict}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn judge(
+        &self,
+        model: &str,
+        criteria: &str,
+        candidate_output: &str,
+    ) -> Result<Verdict> {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "score": {
+                    "type": "integer",
+                    "minimum": Verdict::MIN_SCORE,
+                    "maximum": Verdict::MAX_SCORE
+                },
+                "pass": { "type": "boolean" },
+                "rationale": { "type": "string" }
+            },
+            "required": ["score", "pass", "rationale"]
+        });
+        let system = format!(
+            "You are a strict evaluator. Grade the output the user provides against \
+             these criteria:\n{criteria}\n\n\
+             Reply with JSON: \"score\" from {} (fails entirely) to {} (fully meets the \
+             criteria), \"pass\" true if the output is acceptable, and a one or two \
+             sentence \"rationale\".",
+            Verdict::MIN_SCORE,
+            Verdict::MAX_SCORE
+        );
+        let user = format!("Output to evaluate:\n{candidate_output}");
+
+        let mut verdict: Verdict = self
+            .chat_structured(model, system, user, FormatSetting::schema(schema))
+            .await?;
+        verdict.score = verdict.score.clamp(Verdict::MIN_SCORE, Verdict::MAX_SCORE);
+        Ok(verdict)
+    }
+}
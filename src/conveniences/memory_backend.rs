@@ -0,0 +1,74 @@
+//! In-memory `SessionBackend` and `JobBackend`
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use super::{JobBackend, JobRecord, SessionBackend, SessionRecord};
+use crate::Result;
+
+/// Keeps sessions and jobs in maps for the lifetime of the process.
+///
+/// The default backend of a [`SessionStore`](super::SessionStore) and a
+/// [`JobQueue`](super::JobQueue).
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    records: Mutex<HashMap<String, SessionRecord>>,
+    jobs: Mutex<HashMap<String, JobRecord>>,
+}
+
+impl MemoryBackend {
+    /// Create an empty backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, HashMap<String, SessionRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn jobs(&self) -> std::sync::MutexGuard<'_, HashMap<String, JobRecord>> {
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl SessionBackend for MemoryBackend {
+    async fn load(&self, name: &str) -> Result<Option<SessionRecord>> {
+        Ok(self.records().get(name).cloned())
+    }
+
+    async fn save(&self, name: &str, record: &SessionRecord) -> Result<()> {
+        self.records().insert(name.to_string(), record.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool> {
+        Ok(self.records().remove(name).is_some())
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        Ok(self.records().keys().cloned().collect())
+    }
+}
+
+#[async_trait]
+impl JobBackend for MemoryBackend {
+    async fn load_job(&self, id: &str) -> Result<Option<JobRecord>> {
+        Ok(self.jobs().get(id).cloned())
+    }
+
+    async fn save_job(&self, record: &JobRecord) -> Result<()> {
+        self.jobs().insert(record.id.clone(), record.clone());
+        Ok(())
+    }
+
+    async fn delete_job(&self, id: &str) -> Result<bool> {
+        Ok(self.jobs().remove(id).is_some())
+    }
+
+    async fn list_jobs(&self) -> Result<Vec<String>> {
+        Ok(self.jobs().keys().cloned().collect())
+    }
+}
@@ -0,0 +1,163 @@
+//! High-level task helpers built on the inference API
+//!
+//! These helpers wrap common "LLM as a function" patterns in a single call
+//! on [`OllamaClient`](crate::OllamaClient). Each one builds the prompt,
+//! constrains the output with a JSON schema, and decodes the reply into a
+//! typed value.
+//!
+//! [`ChatSession`], from [`session`](crate::OllamaClient::session), keeps a
+//! conversation's history and can summarize old turns to stay within a
+//! token budget; [`send_batch`](ChatSession::send_batch) advances many
+//! sessions at once, and [`chat_batch`](crate::OllamaClient::chat_batch)
+//! runs many independent chat requests. [`SessionStore`], from
+//! [`session_store`](crate::OllamaClient::session_store), keeps named
+//! sessions in a pluggable [`SessionBackend`]. [`JobQueue`], from
+//! [`job_queue`](crate::OllamaClient::job_queue), runs generate and chat
+//! calls as background jobs tracked by id in a [`JobBackend`].
+//!
+//! [`EvalRunner`], from [`eval_runner`](crate::OllamaClient::eval_runner),
+//! runs a prompt template over a table of [`EvalCase`]s and reports which
+//! outputs pass their exact, regex, or judge-model [`EvalCheck`];
+//! [`compare_prompts`](crate::OllamaClient::compare_prompts) runs two
+//! prompt variants over the same inputs and reports which one wins each.
+//!
+//! [`moderate`](crate::OllamaClient::moderate) scores text in each
+//! [`ModerationCategory`] with a safety-tuned model, and
+//! [`moderation_guardrail`](crate::OllamaClient::moderation_guardrail) runs
+//! it on every prompt and reply of a client as a
+//! [`Guardrail`](crate::Guardrail).
+//!
+//! With the `model` feature as well, [`disk_usage`](crate::OllamaClient::disk_usage)
+//! and [`prune`](crate::OllamaClient::prune) help keep local model storage
+//! in check, and [`pull_models`](crate::OllamaClient::pull_models) /
+//! [`delete_models`](crate::OllamaClient::delete_models) provision many
+//! models at once with a per-model [`BulkReport`].
+//!
+//! # Feature Flag
+//!
+//! This module requires the `conveniences` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! ollama-oxide = { version = "0.2", features = ["conveniences"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::OllamaClient;
+//!
+//! # async fn example() -> ollama_oxide::Result<()> {
+//! let client = OllamaClient::default()?;
+//! let result = client
+//!     .classify("qwen3:0.6b", "The package arrived broken.", &["positive", "negative"])
+//!     .await?;
+//! println!("{} ({:.0}%)", result.label, result.confidence * 100.0);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "model")]
+mod bulk;
+#[cfg(feature = "model")]
+mod bulk_item;
+#[cfg(feature = "model")]
+mod bulk_report;
+mod chat_batch;
+mod chat_session;
+mod choose;
+mod classification;
+mod classify;
+mod code_block;
+mod comparison_case;
+mod comparison_report;
+#[cfg(feature = "model")]
+mod disk_usage;
+mod eval_case;
+mod eval_check;
+mod eval_report;
+mod eval_result;
+mod eval_runner;
+mod extract;
+#[cfg(feature = "model")]
+mod family_usage;
+mod generate_code;
+mod history;
+mod job_backend;
+mod job_callback;
+mod job_output;
+mod job_queue;
+mod job_record;
+mod job_request;
+mod job_status;
+mod json_dir_backend;
+mod json_retry;
+mod judge;
+mod memory_backend;
+mod moderate;
+mod moderation;
+mod moderation_category;
+mod moderation_guardrail;
+mod prompt_comparison;
+mod prompt_winner;
+#[cfg(feature = "model")]
+mod prune_plan;
+mod race;
+mod race_winner;
+mod self_consistency;
+mod session_backend;
+mod session_record;
+mod session_store;
+#[cfg(feature = "model")]
+mod storage;
+mod structured;
+mod summarize_policy;
+mod translate;
+mod translated_chunk;
+mod translation;
+mod verdict;
+mod vote;
+
+#[cfg(feature = "model")]
+pub use bulk_item::BulkItem;
+#[cfg(feature = "model")]
+pub use bulk_report::BulkReport;
+pub use chat_session::ChatSession;
+pub use classification::Classification;
+pub use code_block::{CodeBlock, extract_code_blocks};
+pub use comparison_case::ComparisonCase;
+pub use comparison_report::ComparisonReport;
+#[cfg(feature = "model")]
+pub use disk_usage::DiskUsage;
+pub use eval_case::EvalCase;
+pub use eval_check::EvalCheck;
+pub use eval_report::EvalReport;
+pub use eval_result::EvalResult;
+pub use eval_runner::EvalRunner;
+#[cfg(feature = "model")]
+pub use family_usage::FamilyUsage;
+pub use job_backend::JobBackend;
+pub use job_callback::JobCallback;
+pub use job_output::JobOutput;
+pub use job_queue::JobQueue;
+pub use job_record::JobRecord;
+pub use job_request::JobRequest;
+pub use job_status::JobStatus;
+pub use json_dir_backend::JsonDirBackend;
+pub use memory_backend::MemoryBackend;
+pub use moderation::Moderation;
+pub use moderation_category::ModerationCategory;
+pub use moderation_guardrail::ModerationGuardrail;
+pub use prompt_comparison::PromptComparison;
+pub use prompt_winner::PromptWinner;
+#[cfg(feature = "model")]
+pub use prune_plan::PrunePlan;
+pub use race_winner::RaceWinner;
+pub use session_backend::SessionBackend;
+pub use session_record::SessionRecord;
+pub use session_store::SessionStore;
+pub use summarize_policy::SummarizePolicy;
+pub use translated_chunk::TranslatedChunk;
+pub use translation::Translation;
+pub use verdict::Verdict;
+pub use vote::Vote;
@@ -0,0 +1,96 @@
+//! Result type for `OllamaClient::moderate`
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::ModerationCategory;
+
+/// Category scores returned by
+/// [`OllamaClient::moderate`](crate::OllamaClient::moderate).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Moderation {
+    /// How strongly the text falls into each category, from 0.0 (not at
+    /// all) to 1.0 (clearly); every category is present
+    pub scores: BTreeMap<ModerationCategory, f64>,
+}
+
+impl Moderation {
+    /// Score the text got in `category`
+    pub fn score(&self, category: ModerationCategory) -> f64 {
+        self.scores.get(&category).copied().unwrap_or_default()
+    }
+
+    /// Categories scoring at least `threshold`, highest first
+    pub fn flagged(&self, threshold: f64) -> Vec<ModerationCategory> {
+        let mut flagged: Vec<_> = self
+            .scores
+            .iter()
+            .filter(|(_, score)| **score >= threshold)
+            .collect();
+        flagged.sort_by(|a, b| b.1.total_cmp(a.1));
+        flagged.into_iter().map(|(category, _)| *category).collect()
+    }
+
+    /// Whether any category scores at least `threshold`
+    pub fn is_flagged(&self, threshold: f64) -> bool {
+        self.scores.values().any(|score| *score >= threshold)
+    }
+
+    /// The highest-scoring category and its score
+    pub fn top(&self) -> Option<(ModerationCategory, f64)> {
+        self.scores
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(category, score)| (*category, *score))
+    }
+}
+
+impl fmt::Display for Moderation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (category, score)) in self.scores.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{category} {score:.2}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moderation() -> Moderation {
+        Moderation {
+            scores: BTreeMap::from([
+                (ModerationCategory::Hate, 0.1),
+                (ModerationCategory::Violence, 0.9),
+                (ModerationCategory::Illicit, 0.6),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_flagged_highest_first() {
+        let moderation = moderation();
+        assert_eq!(
+            moderation.flagged(0.5),
+            [ModerationCategory::Violence, ModerationCategory::Illicit]
+        );
+        assert!(moderation.is_flagged(0.9));
+        assert!(!moderation.is_flagged(0.95));
+        assert_eq!(moderation.top(), Some((ModerationCategory::Violence, 0.9)));
+        assert_eq!(moderation.score(ModerationCategory::Sexual), 0.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            moderation().to_string(),
+            "hate 0.10, violence 0.90, illicit 0.60"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Content-safety categories scored by `OllamaClient::moderate`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A kind of harmful content scored by
+/// [`moderate`](crate::OllamaClient::moderate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationCategory {
+    /// Hatred or demeaning content aimed at protected groups
+    Hate,
+    /// Threats, bullying, or abuse aimed at a person
+    Harassment,
+    /// Encouragement of, or instructions for, self-harm or suicide
+    SelfHarm,
+    /// Sexually explicit content
+    Sexual,
+    /// Graphic violence, or threats or incitement to it
+    Violence,
+    /// Help with crimes, weapons, or dangerous substances
+    Illicit,
+}
+
+impl ModerationCategory {
+    /// Every category, in the order they are scored
+    pub const ALL: [Self; 6] = [
+        Self::Hate,
+        Self::Harassment,
+        Self::SelfHarm,
+        Self::Sexual,
+        Self::Violence,
+        Self::Illicit,
+    ];
+
+    /// Name used in the moderation reply, e.g. `self_harm`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hate => "hate",
+            Self::Harassment => "harassment",
+            Self::SelfHarm => "self_harm",
+            Self::Sexual => "sexual",
+            Self::Violence => "violence",
+            Self::Illicit => "illicit",
+        }
+    }
+
+    /// What the category covers, as given to the judge model
+    pub(super) fn rubric(self) -> &'static str {
+        match self {
+            Self::Hate => "hatred, slurs, or demeaning content aimed at a protected group",
+            Self::Harassment => "threats, bullying, or abuse aimed at a person",
+            Self::SelfHarm => "encouragement of, or instructions for, self-harm or suicide",
+            Self::Sexual => "sexually explicit content",
+            Self::Violence => "graphic violence, or threats or incitement to violence",
+            Self::Illicit => "help committing crimes or obtaining weapons or dangerous substances",
+        }
+    }
+}
+
+impl fmt::Display for ModerationCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_match_serde() {
+        for category in ModerationCategory::ALL {
+            assert_eq!(
+                serde_json::to_value(category).unwrap(),
+                category.as_str(),
+                "{category:?}"
+            );
+        }
+    }
+}
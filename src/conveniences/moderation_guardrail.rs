@@ -0,0 +1,103 @@
+//! `ModerationGuardrail`: `OllamaClient::moderate` as a guardrail
+
+use async_trait::async_trait;
+
+use crate::http::OllamaClient;
+use crate::{Guardrail, GuardrailAction, Result};
+
+/// A [`Guardrail`] that blocks prompts and replies
+/// [`moderate`](OllamaClient::moderate) flags, from
+/// [`OllamaClient::moderation_guardrail`].
+///
+/// Text is blocked when any category scores at least the
+/// [threshold](Self::with_threshold), 0.5 unless set; the reason names the
+/// flagged categories with their scores. Each check is one call to the
+/// moderation model, so checking replies as well as prompts doubles the
+/// cost of a call.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{Error, OllamaApiAsync, OllamaClient, GenerateRequest};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let moderator = OllamaClient::default()?;
+/// let guardrail = moderator.moderation_guardrail("llama-guard3:1b").with_threshold(0.7);
+/// let client = OllamaClient::default()?.with_guardrail(Arc::new(guardrail));
+///
+/// match client.generate(&GenerateRequest::new("qwen3", "...")).await {
+///     Err(Error::Blocked { reason, .. }) => eprintln!("refused: {reason}"),
+///     other => println!("{:?}", other?.response),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModerationGuardrail {
+    client: OllamaClient,
+    model: String,
+    threshold: f64,
+    outputs: bool,
+}
+
+impl ModerationGuardrail {
+    /// Score threshold used unless [`with_threshold`](Self::with_threshold)
+    /// sets one
+    pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+    pub(super) fn new(client: OllamaClient, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            threshold: Self::DEFAULT_THRESHOLD,
+            outputs: true,
+        }
+    }
+
+    /// Block text scoring at least `threshold` in any category.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Check prompts only, letting replies through unchecked.
+    pub fn inputs_only(mut self) -> Self {
+        self.outputs = false;
+        self
+    }
+
+    async fn check(&self, text: &str) -> Result<GuardrailAction> {
+        let moderation = self.client.moderate(&self.model, text).await?;
+        let flagged = moderation.flagged(self.threshold);
+        if flagged.is_empty() {
+            return Ok(GuardrailAction::Allow);
+        }
+        let reasons: Vec<String> = flagged
+            .iter()
+            .map(|c| format!("{c} {:.2}", moderation.score(*c)))
+            .collect();
+        Ok(GuardrailAction::Block(format!(
+            "flagged for {}",
+            reasons.join(", ")
+        )))
+    }
+}
+
+#[async_trait]
+impl Guardrail for ModerationGuardrail {
+    fn name(&self) -> &str {
+        "moderation"
+    }
+
+    async fn check_input(&self, text: &str) -> Result<GuardrailAction> {
+        self.check(text).await
+    }
+
+    async fn check_output(&self, text: &str) -> Result<GuardrailAction> {
+        if !self.outputs {
+            return Ok(GuardrailAction::Allow);
+        }
+        self.check(text).await
+    }
+}
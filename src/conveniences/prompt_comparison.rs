@@ -0,0 +1,292 @@
+//! `PromptComparison`: two prompt variants run over the same inputs
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::chat_batch::run_ordered;
+use super::eval_runner::render;
+use super::{ComparisonCase, ComparisonReport, PromptWinner};
+use crate::http::OllamaClient;
+use crate::{ChatMessage, ChatRequest, FormatSetting, ModelOptions, OllamaApiAsync, Result};
+
+type Scorer = Arc<dyn Fn(&str, &str) -> f64 + Send + Sync>;
+
+/// Criteria the judge uses when none are given.
+const DEFAULT_CRITERIA: &str =
+    "Follows the request and answers it correctly, clearly, and helpfully.";
+
+/// The judge's pick between two replies, as constrained by its schema.
+#[derive(Deserialize)]
+struct PairVerdict {
+    winner: String,
+    rationale: String,
+}
+
+/// Runs two prompt variants over the same inputs and decides, per input,
+/// which reply is better.
+///
+/// Both prompts fill `{input}` from each input (`{{` and `}}` are literal
+/// braces) and are sent with the same seed, so differences come from the
+/// prompts rather than sampling. Input `i` uses seed
+/// `options.seed + i`, starting from 0 when no seed is set and wrapping
+/// past `i64::MAX`.
+///
+/// Replies are compared by a judge model, by default the model under test,
+/// or by a [scorer](Self::with_scorer) that rates each reply locally. The
+/// judge sees the replies in alternating order from one input to the next,
+/// which evens out any bias towards the first or second position.
+///
+/// Inputs run [`concurrency`](Self::with_concurrency) at a time, one by
+/// one unless set. An input whose calls fail is reported as an error and
+/// does not stop the others.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{OllamaClient, PromptWinner};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let report = client
+///     .compare_prompts(
+///         "qwen3:0.6b",
+///         "Summarize: {input}",
+///         "Summarize in one sentence for a busy executive: {input}",
+///         ["Q3 revenue rose 12%...", "The migration finished two weeks late..."],
+///     )
+///     .with_judge("qwen3:8b", "Accurate, short, and easy to skim.")
+///     .run()
+///     .await;
+/// println!("{report}");
+/// if report.overall() == PromptWinner::B {
+///     println!("ship the new prompt");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PromptComparison {
+    client: OllamaClient,
+    model: String,
+    prompt_a: String,
+    prompt_b: String,
+    inputs: Vec<String>,
+    system: Option<String>,
+    options: Option<ModelOptions>,
+    judge_model: Option<String>,
+    criteria: String,
+    scorer: Option<Scorer>,
+    concurrency: usize,
+}
+
+impl OllamaClient {
+    /// Start a [`PromptComparison`] of `prompt_a` against `prompt_b` on
+    /// `model` over `inputs`.
+    pub fn compare_prompts<I>(
+        &self,
+        model: impl Into<String>,
+        prompt_a: impl Into<String>,
+        prompt_b: impl Into<String>,
+        inputs: I,
+    ) -> PromptComparison
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        PromptComparison {
+            client: self.clone(),
+            model: model.into(),
+            prompt_a: prompt_a.into(),
+            prompt_b: prompt_b.into(),
+            inputs: inputs.into_iter().map(Into::into).collect(),
+            system: None,
+            options: None,
+            judge_model: None,
+            criteria: DEFAULT_CRITERIA.to_string(),
+            scorer: None,
+            concurrency: 1,
+        }
+    }
+}
+
+impl PromptComparison {
+    /// Send `prompt` as the system message of every call.
+    pub fn with_system(mut self, prompt: impl Into<String>) -> Self {
+        self.system = Some(prompt.into());
+        self
+    }
+
+    /// Send `options` with every call; the seed is set per input.
+    pub fn with_options(mut self, options: ModelOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Have `model` pick the reply that better meets `criteria`.
+    pub fn with_judge(mut self, model: impl Into<String>, criteria: impl Into<String>) -> Self {
+        self.judge_model = Some(model.into());
+        self.criteria = criteria.into();
+        self
+    }
+
+    /// Rate each reply with `score(input, output)` instead of a judge
+    /// model; the higher score wins and equal scores tie.
+    pub fn with_scorer<F>(mut self, score: F) -> Self
+    where
+        F: Fn(&str, &str) -> f64 + Send + Sync + 'static,
+    {
+        self.scorer = Some(Arc::new(score));
+        self
+    }
+
+    /// Run at most `concurrency` inputs at a time; 0 is treated as 1.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Run both prompts over every input and report each outcome, in input
+    /// order.
+    pub async fn run(&self) -> ComparisonReport {
+        let jobs = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let comparison = self.clone();
+                let input = input.clone();
+                async move { Ok(comparison.run_case(i, input).await) }
+            })
+            .collect();
+        let cases = run_ordered(jobs, self.concurrency)
+            .await
+            .into_iter()
+            .zip(&self.inputs)
+            .map(|(case, input)| {
+                case.unwrap_or_else(|e| ComparisonCase {
+                    input: input.clone(),
+                    output_a: None,
+                    output_b: None,
+                    scores: None,
+                    rationale: None,
+                    result: Err(e),
+                })
+            })
+            .collect();
+        ComparisonReport { cases }
+    }
+
+    async fn run_case(&self, i: usize, input: String) -> ComparisonCase {
+        let (a, b) = tokio::join!(
+            self.ask(&self.prompt_a, &input, i),
+            self.ask(&self.prompt_b, &input, i)
+        );
+        let mut case = ComparisonCase {
+            input,
+            output_a: a.as_ref().ok().cloned(),
+            output_b: b.as_ref().ok().cloned(),
+            scores: None,
+            rationale: None,
+            result: Ok(PromptWinner::Tie),
+        };
+        let (a, b) = match (a, b) {
+            (Ok(a), Ok(b)) => (a, b),
+            (Err(e), _) | (_, Err(e)) => {
+                case.result = Err(e);
+                return case;
+            }
+        };
+
+        case.result = match &self.scorer {
+            Some(score) => {
+                let scores = (score(&case.input, &a), score(&case.input, &b));
+                case.scores = Some(scores);
+                Ok(PromptWinner::from_scores(scores.0, scores.1))
+            }
+            None => self
+                .judge(&case.input, &a, &b, i % 2 == 1)
+                .await
+                .map(|(winner, rationale)| {
+                    case.rationale = Some(rationale);
+                    winner
+                }),
+        };
+        case
+    }
+
+    /// The reply to `prompt` filled with `input`, sent with the seed for
+    /// input `i`
+    async fn ask(&self, prompt: &str, input: &str, i: usize) -> Result<String> {
+        let vars = BTreeMap::from([("input".to_string(), input.to_string())]);
+        let prompt = render(prompt, &vars)?;
+        let messages = self
+            .system
+            .iter()
+            .map(ChatMessage::system)
+            .chain([ChatMessage::user(prompt)]);
+        let options = self.options.clone().unwrap_or_default();
+        let seed = options.seed.unwrap_or(0).wrapping_add(i as i64);
+        let request =
+            ChatRequest::new(self.model.as_str(), messages).with_options(options.with_seed(seed));
+        let response = self.client.chat(&request).await?;
+        Ok(response.content().unwrap_or_default().to_string())
+    }
+
+    /// The judge's pick between `a` and `b`, shown in swapped order when
+    /// `swap` is set
+    async fn judge(
+        &self,
+        input: &str,
+        a: &str,
+        b: &str,
+        swap: bool,
+    ) -> Result<(PromptWinner, String)> {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "winner": { "type": "string", "enum": ["first", "second", "tie"] },
+                "rationale": { "type": "string" }
+            },
+            "required": ["winner", "rationale"]
+        });
+        let system = format!(
+            "You are a strict evaluator. Two replies answer the same input. Decide which \
+             better meets these criteria:\n{}\n\n\
+             Reply with JSON: \"winner\" as \"first\", \"second\", or \"tie\", and a one or \
+             two sentence \"rationale\".",
+            self.criteria
+        );
+        let (first, second) = if swap { (b, a) } else { (a, b) };
+        let user = format!("Input:\n{input}\n\nFirst reply:\n{first}\n\nSecond reply:\n{second}");
+
+        let model = self.judge_model.as_deref().unwrap_or(&self.model);
+        let verdict: PairVerdict = self
+            .client
+            .chat_structured(model, system, user, FormatSetting::schema(schema))
+            .await?;
+        let winner = match (verdict.winner.as_str(), swap) {
+            ("first", false) | ("second", true) => PromptWinner::A,
+            ("second", false) | ("first", true) => PromptWinner::B,
+            _ => PromptWinner::Tie,
+        };
+        Ok((winner, verdict.rationale))
+    }
+}
+
+impl fmt::Debug for PromptComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PromptComparison")
+            .field("model", &self.model)
+            .field("prompt_a", &self.prompt_a)
+            .field("prompt_b", &self.prompt_b)
+            .field("inputs", &self.inputs.len())
+            .field("judge_model", &self.judge_model)
+            .field("scorer", &self.scorer.is_some())
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
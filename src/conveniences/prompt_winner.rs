@@ -0,0 +1,50 @@
+//! Outcome of one case of a prompt comparison
+
+use std::fmt;
+
+/// Which prompt variant did better on one input of
+/// [`compare_prompts`](crate::OllamaClient::compare_prompts).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromptWinner {
+    /// The first prompt
+    A,
+    /// The second prompt
+    B,
+    /// Neither did better
+    Tie,
+}
+
+impl PromptWinner {
+    /// The winner between two scores, higher being better; incomparable
+    /// scores (NaN) tie.
+    pub fn from_scores(a: f64, b: f64) -> Self {
+        match a.partial_cmp(&b) {
+            Some(std::cmp::Ordering::Greater) => Self::A,
+            Some(std::cmp::Ordering::Less) => Self::B,
+            _ => Self::Tie,
+        }
+    }
+}
+
+impl fmt::Display for PromptWinner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::Tie => "tie",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_scores() {
+        assert_eq!(PromptWinner::from_scores(0.9, 0.4), PromptWinner::A);
+        assert_eq!(PromptWinner::from_scores(1.0, 2.0), PromptWinner::B);
+        assert_eq!(PromptWinner::from_scores(3.0, 3.0), PromptWinner::Tie);
+        assert_eq!(PromptWinner::from_scores(f64::NAN, 1.0), PromptWinner::Tie);
+    }
+}
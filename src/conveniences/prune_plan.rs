@@ -0,0 +1,77 @@
+//! Dry-run result of `OllamaClient::prune`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::inference::humanize;
+use crate::model::ModelSummary;
+
+/// Models a prune would delete and keep.
+///
+/// Built by [`OllamaClient::prune`](crate::OllamaClient::prune) or
+/// [`prune_older_than`](crate::OllamaClient::prune_older_than) without
+/// touching the server; pass it to
+/// [`apply_prune`](crate::OllamaClient::apply_prune) to delete.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PrunePlan {
+    /// Models that would be deleted
+    pub delete: Vec<ModelSummary>,
+
+    /// Models that would stay
+    pub keep: Vec<ModelSummary>,
+}
+
+impl PrunePlan {
+    /// Check whether the prune would delete nothing
+    pub fn is_empty(&self) -> bool {
+        self.delete.is_empty()
+    }
+
+    /// Get the bytes the prune would free
+    pub fn reclaimable(&self) -> u64 {
+        self.delete.iter().filter_map(|m| m.size).sum()
+    }
+}
+
+/// One `- name` line per model to delete, then the space freed.
+impl fmt::Display for PrunePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "nothing to prune");
+        }
+        for model in &self.delete {
+            writeln!(f, "- {model}")?;
+        }
+        write!(
+            f,
+            "would delete {} of {} models, freeing {}",
+            self.delete.len(),
+            self.delete.len() + self.keep.len(),
+            humanize::bytes(self.reclaimable())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(name: &str, size: u64) -> ModelSummary {
+        serde_json::from_value(serde_json::json!({"name": name, "size": size})).unwrap()
+    }
+
+    #[test]
+    fn test_reclaimable_and_display() {
+        let plan = PrunePlan {
+            delete: vec![model("old:latest", 1_500_000_000)],
+            keep: vec![model("new:latest", 2_000_000_000)],
+        };
+        assert_eq!(plan.reclaimable(), 1_500_000_000);
+        assert_eq!(
+            plan.to_string(),
+            "- old:latest (1.5 GB)\nwould delete 1 of 2 models, freeing 1.5 GB"
+        );
+        assert_eq!(PrunePlan::default().to_string(), "nothing to prune");
+    }
+}
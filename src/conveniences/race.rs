@@ -0,0 +1,136 @@
+//! `OllamaClient::race_generate` and `ModelHandle::race`
+
+use std::time::Instant;
+
+use tokio::task::JoinSet;
+
+use super::RaceWinner;
+use crate::http::{ModelHandle, OllamaClient};
+use crate::{Error, Result};
+
+impl OllamaClient {
+    /// Send `prompt` to every model in `models` and return the first to finish.
+    ///
+    /// The other generations are cancelled as soon as a winner is in. Failed
+    /// contestants are skipped; the race only fails if every model fails.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `models` is empty
+    /// - The last contestant's error if all of them fail
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let winner = client
+    ///     .race_generate(&["qwen3:0.6b", "llama3.2:1b"], "Name a primary colour.")
+    ///     .await?;
+    /// println!("{} answered first in {:?}", winner.model, winner.latency);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn race_generate(&self, models: &[&str], prompt: &str) -> Result<RaceWinner> {
+        let mut winners = self.race_generate_n(models, prompt, 1).await?;
+        Ok(winners.remove(0))
+    }
+
+    /// Like [`race_generate`](Self::race_generate), but wait for the first
+    /// `n` responses, in finishing order.
+    ///
+    /// Returns fewer than `n` winners when too many contestants fail, but
+    /// at least one.
+    pub async fn race_generate_n(
+        &self,
+        models: &[&str],
+        prompt: &str,
+        n: usize,
+    ) -> Result<Vec<RaceWinner>> {
+        let handles: Vec<ModelHandle> = models.iter().map(|m| self.model(*m)).collect();
+        ModelHandle::race(&handles, prompt, n).await
+    }
+}
+
+impl ModelHandle {
+    /// Race `prompt` across handles that may point at different hosts,
+    /// returning the first `n` responses in finishing order.
+    ///
+    /// Each handle's model and options are used. Remaining generations are
+    /// cancelled once `n` have finished.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `handles` is empty or `n` is 0
+    /// - The last contestant's error if all of them fail
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ModelHandle, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let local = OllamaClient::default()?;
+    /// let remote = OllamaClient::with_base_url("http://gpu-box:11434")?;
+    /// let winners = ModelHandle::race(
+    ///     &[local.model("qwen3:0.6b"), remote.model("llama3.1:8b")],
+    ///     "Summarize: the meeting moved to Tuesday.",
+    ///     1,
+    /// )
+    /// .await?;
+    /// println!("{}", winners[0].text().unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn race(handles: &[ModelHandle], prompt: &str, n: usize) -> Result<Vec<RaceWinner>> {
+        if handles.is_empty() || n == 0 {
+            return Err(Error::ValidationError(
+                "a race needs at least one contestant and n >= 1".to_string(),
+            ));
+        }
+
+        let started = Instant::now();
+        let mut tasks = JoinSet::new();
+        for handle in handles {
+            let handle = handle.clone();
+            let prompt = prompt.to_string();
+            tasks.spawn(async move {
+                let result = handle.generate(prompt).await;
+                (handle, result)
+            });
+        }
+
+        let mut winners = Vec::with_capacity(n);
+        let mut last_error = None;
+        while let Some(joined) = tasks.join_next().await {
+            let Ok((handle, result)) = joined else {
+                continue;
+            };
+            match result {
+                Ok(response) => {
+                    winners.push(RaceWinner {
+                        model: handle.name().to_string(),
+                        host: handle.client().config.base_url().to_string(),
+                        response,
+                        latency: started.elapsed(),
+                    });
+                    if winners.len() == n {
+                        break;
+                    }
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        tasks.abort_all();
+
+        match (winners.is_empty(), last_error) {
+            (true, Some(e)) => Err(e),
+            (true, None) => Err(Error::ApiError {
+                message: "every race task failed".to_string(),
+            }),
+            _ => Ok(winners),
+        }
+    }
+}
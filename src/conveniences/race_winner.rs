@@ -0,0 +1,28 @@
+//! Result type for `OllamaClient::race_generate`
+
+use std::time::Duration;
+
+use crate::GenerateResponse;
+
+/// A response that finished a generation race.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaceWinner {
+    /// Model that produced the response
+    pub model: String,
+
+    /// Base URL of the host that served it
+    pub host: String,
+
+    /// The completed response
+    pub response: GenerateResponse,
+
+    /// Time from the start of the race until this response completed
+    pub latency: Duration,
+}
+
+impl RaceWinner {
+    /// Get the generated text
+    pub fn text(&self) -> Option<&str> {
+        self.response.text()
+    }
+}
@@ -0,0 +1,115 @@
+//! `OllamaClient::generate_n_and_vote`
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use tokio::task::JoinSet;
+
+use super::Vote;
+use crate::http::OllamaClient;
+use crate::{Error, GenerateRequest, GenerateResponse, OllamaApiAsync, Result};
+
+impl OllamaClient {
+    /// Sample `n` completions concurrently and return the majority answer.
+    ///
+    /// Each sample runs `req` with a different seed: the request's own seed
+    /// (or 0) plus the sample index, wrapping past `i64::MAX`. `extractor` maps a response to the
+    /// answer being voted on, e.g. the final number in a chain-of-thought
+    /// reply; samples it returns `None` for are not counted. Ties go to the
+    /// answer from the lowest-indexed sample.
+    ///
+    /// Returns `Ok(None)` if no sample produced an answer. Use a non-zero
+    /// temperature so the samples actually differ.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `n` is 0
+    /// - The first error returned by any sample's
+    ///   [`generate`](crate::OllamaApiAsync::generate) call
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, ModelOptions, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new(
+    ///     "qwen3:0.6b",
+    ///     "What is 17 * 23? Think step by step, then give the number on the last line.",
+    /// )
+    /// .with_options(ModelOptions::default().with_temperature(0.7));
+    ///
+    /// let vote = client
+    ///     .generate_n_and_vote(&request, 5, |r| {
+    ///         r.text()?.lines().last()?.trim().parse::<i64>().ok()
+    ///     })
+    ///     .await?;
+    /// if let Some(vote) = vote {
+    ///     println!("{} ({}/{} agree)", vote.answer, vote.votes, vote.answered);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_n_and_vote<K, F>(
+        &self,
+        req: &GenerateRequest,
+        n: usize,
+        extractor: F,
+    ) -> Result<Option<Vote<K>>>
+    where
+        K: Eq + Hash + Clone,
+        F: Fn(&GenerateResponse) -> Option<K>,
+    {
+        if n == 0 {
+            return Err(Error::ValidationError(
+                "generate_n_and_vote needs at least one sample".to_string(),
+            ));
+        }
+
+        let options = req.options.clone().unwrap_or_default();
+        let base_seed = options.seed.unwrap_or(0);
+
+        let mut tasks = JoinSet::new();
+        for i in 0..n {
+            let client = self.clone();
+            let mut sample = req.clone();
+            sample.options = Some(options.clone().with_seed(base_seed.wrapping_add(i as i64)));
+            tasks.spawn(async move { (i, client.generate(&sample).await) });
+        }
+
+        let mut slots: Vec<Option<GenerateResponse>> = vec![None; n];
+        while let Some(joined) = tasks.join_next().await {
+            let (i, result) = joined.map_err(|e| Error::ApiError {
+                message: format!("sample task failed: {e}"),
+            })?;
+            slots[i] = Some(result?);
+        }
+        let responses: Vec<GenerateResponse> = slots.into_iter().flatten().collect();
+
+        // answer -> (votes, index of first sample that produced it)
+        let mut tally: HashMap<K, (usize, usize)> = HashMap::new();
+        let mut answered = 0;
+        for (i, response) in responses.iter().enumerate() {
+            if let Some(answer) = extractor(response) {
+                tally.entry(answer).or_insert((0, i)).0 += 1;
+                answered += 1;
+            }
+        }
+
+        let Some((answer, &(votes, first))) = tally
+            .iter()
+            .max_by(|(_, (va, ia)), (_, (vb, ib))| va.cmp(vb).then(ib.cmp(ia)))
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Vote {
+            answer: answer.clone(),
+            votes,
+            answered,
+            samples: n,
+            response: responses[first].clone(),
+        }))
+    }
+}
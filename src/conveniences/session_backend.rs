@@ -0,0 +1,54 @@
+//! Persistence trait for `SessionStore`
+
+use async_trait::async_trait;
+
+use super::SessionRecord;
+use crate::Result;
+
+/// Where a [`SessionStore`](super::SessionStore) keeps its sessions.
+///
+/// The crate ships [`MemoryBackend`](super::MemoryBackend) and
+/// [`JsonDirBackend`](super::JsonDirBackend); implement this trait to keep
+/// conversations in a database or cache instead. Backends only store and
+/// return records by name; the store serializes concurrent sends to the
+/// same name.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use ollama_oxide::{Result, SessionBackend, SessionRecord};
+///
+/// /// Forgets everything, e.g. for stateless deployments.
+/// struct Discard;
+///
+/// #[async_trait]
+/// impl SessionBackend for Discard {
+///     async fn load(&self, _name: &str) -> Result<Option<SessionRecord>> {
+///         Ok(None)
+///     }
+///     async fn save(&self, _name: &str, _record: &SessionRecord) -> Result<()> {
+///         Ok(())
+///     }
+///     async fn delete(&self, _name: &str) -> Result<bool> {
+///         Ok(false)
+///     }
+///     async fn list(&self) -> Result<Vec<String>> {
+///         Ok(Vec::new())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait SessionBackend: Send + Sync {
+    /// The record saved under `name`, or `None` if there is none.
+    async fn load(&self, name: &str) -> Result<Option<SessionRecord>>;
+
+    /// Store `record` under `name`, replacing any previous record.
+    async fn save(&self, name: &str, record: &SessionRecord) -> Result<()>;
+
+    /// Remove the record under `name`. Returns `false` if there was none.
+    async fn delete(&self, name: &str) -> Result<bool>;
+
+    /// Names of all stored records, in no particular order.
+    async fn list(&self) -> Result<Vec<String>>;
+}
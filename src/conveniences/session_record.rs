@@ -0,0 +1,43 @@
+//! Serializable snapshot of a `ChatSession`
+
+use serde::{Deserialize, Serialize};
+
+use crate::ChatMessage;
+
+/// The persisted state of a [`ChatSession`](super::ChatSession): its model
+/// and message history.
+///
+/// Produced by [`ChatSession::to_record`](super::ChatSession::to_record) and
+/// turned back into a session with
+/// [`OllamaClient::resume_session`](crate::OllamaClient::resume_session).
+/// Options and the summarize policy are configuration, not state, and are
+/// not part of the record.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatMessage, SessionRecord};
+///
+/// let record = SessionRecord::new("qwen3:0.6b", vec![ChatMessage::user("Hi")]);
+/// let json = serde_json::to_string(&record).unwrap();
+/// let back: SessionRecord = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back, record);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Model the session chats with
+    pub model: String,
+    /// The history, oldest first
+    #[serde(default)]
+    pub messages: Vec<ChatMessage>,
+}
+
+impl SessionRecord {
+    /// Create a record for `model` with `messages`.
+    pub fn new(model: impl Into<String>, messages: Vec<ChatMessage>) -> Self {
+        Self {
+            model: model.into(),
+            messages,
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! `SessionStore`: named chat sessions with pluggable persistence
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::{ChatSession, MemoryBackend, SessionBackend, SummarizePolicy};
+use crate::http::OllamaClient;
+use crate::{ChatResponse, ModelOptions, Result};
+
+/// Named [`ChatSession`]s, e.g. one per user of a chatbot.
+///
+/// Sessions are loaded from and saved to a [`SessionBackend`]: in memory by
+/// default, or [`JsonDirBackend`](super::JsonDirBackend) or your own
+/// implementation via [`with_backend`](Self::with_backend). New sessions
+/// start with the store's model and system prompt; the store's options and
+/// summarize policy apply to every session it hands out.
+///
+/// [`send`](Self::send) loads, sends, and saves in one call. Sends and
+/// saves to the same name are serialized so concurrent requests never lose
+/// turns; those to different names run in parallel.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::OllamaClient;
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let store = client
+///     .session_store("qwen3:0.6b")
+///     .with_system("You are a helpful support agent.");
+///
+/// let reply = store.send("user-42", "My order is late.").await?;
+/// println!("{}", reply.content().unwrap_or_default());
+///
+/// let history = store.get("user-42").await?;
+/// assert_eq!(history.messages().len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SessionStore {
+    client: OllamaClient,
+    model: String,
+    system: Option<String>,
+    options: Option<ModelOptions>,
+    summarize: Option<SummarizePolicy>,
+    backend: Arc<dyn SessionBackend>,
+    /// Per-name locks serializing `send` and `save`, dropped once unused
+    locks: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>,
+}
+
+impl fmt::Debug for SessionStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionStore")
+            .field("model", &self.model)
+            .field("system", &self.system)
+            .field("options", &self.options)
+            .field("summarize", &self.summarize)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OllamaClient {
+    /// Create a [`SessionStore`] whose new sessions chat with `model`,
+    /// kept in memory.
+    pub fn session_store(&self, model: impl Into<String>) -> SessionStore {
+        SessionStore {
+            client: self.clone(),
+            model: model.into(),
+            system: None,
+            options: None,
+            summarize: None,
+            backend: Arc::new(MemoryBackend::new()),
+            locks: Arc::default(),
+        }
+    }
+}
+
+impl SessionStore {
+    /// Persist sessions in `backend` instead of memory.
+    pub fn with_backend(mut self, backend: Arc<dyn SessionBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Start new sessions with a system prompt.
+    pub fn with_system(mut self, prompt: impl Into<String>) -> Self {
+        self.system = Some(prompt.into());
+        self
+    }
+
+    /// Send `options` with every request.
+    pub fn with_options(mut self, options: ModelOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Compress session histories according to `policy`.
+    pub fn with_summarize(mut self, policy: SummarizePolicy) -> Self {
+        self.summarize = Some(policy);
+        self
+    }
+
+    /// Model new sessions chat with
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The session named `name`, or a new one if none is stored.
+    ///
+    /// Changes to the returned session are kept only once passed to
+    /// [`save`](Self::save).
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn get(&self, name: &str) -> Result<ChatSession> {
+        let session = match self.backend.load(name).await? {
+            Some(record) => self.client.resume_session(record),
+            None => {
+                let session = self.client.session(&self.model);
+                match &self.system {
+                    Some(system) => session.with_system(system),
+                    None => session,
+                }
+            }
+        };
+        let session = match &self.options {
+            Some(options) => session.with_options(options.clone()),
+            None => session,
+        };
+        Ok(match &self.summarize {
+            Some(policy) => session.with_summarize(policy.clone()),
+            None => session,
+        })
+    }
+
+    /// Store `session` under `name`.
+    ///
+    /// Waits for any [`send`](Self::send) or save to the same name to finish.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn save(&self, name: &str, session: &ChatSession) -> Result<()> {
+        let lock = self.lock(name);
+        let result = {
+            let _guard = lock.lock().await;
+            self.backend.save(name, &session.to_record()).await
+        };
+        self.release(name, lock);
+        result
+    }
+
+    /// Send a user message in the session named `name` and save the result.
+    ///
+    /// Creates the session if none is stored. If the chat fails, the stored
+    /// session is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend or from [`ChatSession::send`].
+    pub async fn send(&self, name: &str, content: impl Into<String>) -> Result<ChatResponse> {
+        let lock = self.lock(name);
+        let result = {
+            let _guard = lock.lock().await;
+            self.send_locked(name, content.into()).await
+        };
+        self.release(name, lock);
+        result
+    }
+
+    async fn send_locked(&self, name: &str, content: String) -> Result<ChatResponse> {
+        let mut session = self.get(name).await?;
+        let response = session.send(content).await?;
+        self.backend.save(name, &session.to_record()).await?;
+        Ok(response)
+    }
+
+    /// Delete the session named `name`. Returns `false` if none was stored.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn remove(&self, name: &str) -> Result<bool> {
+        self.backend.delete(name).await
+    }
+
+    /// Names of all stored sessions, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Any error from the backend.
+    pub async fn names(&self) -> Result<Vec<String>> {
+        let mut names = self.backend.list().await?;
+        names.sort();
+        Ok(names)
+    }
+
+    fn lock(&self, name: &str) -> Arc<tokio::sync::Mutex<()>> {
+        self.locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Drop `lock`, and its entry for `name` once no other call holds it.
+    fn release(&self, name: &str, lock: Arc<tokio::sync::Mutex<()>>) {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        drop(lock);
+        if locks
+            .get(name)
+            .is_some_and(|entry| Arc::strong_count(entry) == 1)
+        {
+            locks.remove(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_drops_unused_locks() {
+        let store = OllamaClient::default().unwrap().session_store("m");
+        let session = store.get("a").await.unwrap();
+
+        let held = store.lock("a");
+        store.save("b", &session).await.unwrap();
+        assert!(store.locks.lock().unwrap().contains_key("a"));
+        assert!(!store.locks.lock().unwrap().contains_key("b"));
+
+        store.release("a", held);
+        assert!(store.locks.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! `OllamaClient::disk_usage`, `prune`, `prune_older_than`, and `apply_prune`
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{DiskUsage, FamilyUsage, PrunePlan};
+use crate::http::OllamaClient;
+use crate::model::{DeleteRequest, ModelSummary};
+use crate::{OllamaApiAsync, Result};
+
+/// Family used for models whose details omit one.
+const UNKNOWN_FAMILY: &str = "unknown";
+
+/// Adds the implicit `:latest` tag so `llama3.2` matches `llama3.2:latest`.
+fn canonical(name: &str) -> String {
+    if name.rsplit('/').next().is_some_and(|n| n.contains(':')) {
+        name.to_string()
+    } else {
+        format!("{name}:latest")
+    }
+}
+
+fn split(models: Vec<ModelSummary>, delete: impl Fn(&ModelSummary) -> bool) -> PrunePlan {
+    let (delete, keep) = models.into_iter().partition(|m| delete(m));
+    PrunePlan { delete, keep }
+}
+
+impl OllamaClient {
+    /// Summarize local model storage by family.
+    ///
+    /// Reads `GET /api/tags` and groups models by `details.family`, largest
+    /// family first. Models without a family are grouped as `"unknown"`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// println!("{}", client.disk_usage().await?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn disk_usage(&self) -> Result<DiskUsage> {
+        let models = self.list_models().await?.models;
+
+        let mut by_family: HashMap<String, FamilyUsage> = HashMap::new();
+        for model in models {
+            let family = model
+                .details
+                .as_ref()
+                .and_then(|d| d.family.clone())
+                .unwrap_or_else(|| UNKNOWN_FAMILY.to_string());
+            let entry = by_family
+                .entry(family.clone())
+                .or_insert_with(|| FamilyUsage {
+                    family,
+                    models: Vec::new(),
+                    size: 0,
+                });
+            entry.size += model.size.unwrap_or(0);
+            entry.models.push(model.name);
+        }
+
+        let mut families: Vec<FamilyUsage> = by_family.into_values().collect();
+        families.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.family.cmp(&b.family)));
+        let total = families.iter().map(|f| f.size).sum();
+        Ok(DiskUsage { families, total })
+    }
+
+    /// Plan deleting every local model not named in `keep`.
+    ///
+    /// Nothing is deleted: inspect the returned [`PrunePlan`], then pass it
+    /// to [`apply_prune`](Self::apply_prune). Names without a tag match
+    /// `:latest`, as in the Ollama CLI.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let plan = client.prune(&["llama3.2", "nomic-embed-text"]).await?;
+    /// println!("{plan}");
+    /// client.apply_prune(&plan).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prune(&self, keep: &[&str]) -> Result<PrunePlan> {
+        let keep: Vec<String> = keep.iter().map(|n| canonical(n)).collect();
+        let models = self.list_models().await?.models;
+        Ok(split(models, |m| !keep.contains(&canonical(&m.name))))
+    }
+
+    /// Plan deleting every local model last modified more than `age` ago.
+    ///
+    /// Models with a missing or unreadable `modified_at` are kept. Like
+    /// [`prune`](Self::prune), this only builds the plan.
+    pub async fn prune_older_than(&self, age: Duration) -> Result<PrunePlan> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        // Ages beyond i64 seconds reach back before any model existed.
+        let age = i64::try_from(age.as_secs()).unwrap_or(i64::MAX);
+        let cutoff = now.saturating_sub(age);
+        let models = self.list_models().await?.models;
+        Ok(split(models, |m| {
+            m.modified_at_unix().is_some_and(|t| t < cutoff)
+        }))
+    }
+
+    /// Delete the models in `plan.delete`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failed delete and returns its error; models
+    /// before it are already gone.
+    pub async fn apply_prune(&self, plan: &PrunePlan) -> Result<()> {
+        for model in &plan.delete {
+            self.delete_model(&DeleteRequest::new(&model.name)).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical() {
+        assert_eq!(canonical("llama3.2"), "llama3.2:latest");
+        assert_eq!(canonical("llama3.2:1b"), "llama3.2:1b");
+        assert_eq!(
+            canonical("localhost:5000/me/model"),
+            "localhost:5000/me/model:latest"
+        );
+    }
+}
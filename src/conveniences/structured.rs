@@ -0,0 +1,32 @@
+//! Shared plumbing for schema-constrained chat calls
+
+use serde::de::DeserializeOwned;
+
+use crate::http::OllamaClient;
+use crate::{ChatMessage, ChatRequest, Error, FormatSetting, ModelOptions, OllamaApiAsync, Result};
+
+impl OllamaClient {
+    /// Send a system + user exchange with a constrained output format and
+    /// decode the reply as `T`.
+    ///
+    /// Runs at temperature 0 so repeated calls give stable answers.
+    pub(super) async fn chat_structured<T: DeserializeOwned>(
+        &self,
+        model: &str,
+        system: String,
+        user: String,
+        format: FormatSetting,
+    ) -> Result<T> {
+        let request = ChatRequest::new(
+            model,
+            [ChatMessage::system(system), ChatMessage::user(user)],
+        )
+        .with_format(format)
+        .with_options(ModelOptions::default().with_temperature(0.0));
+
+        let response = self.chat(&request).await?;
+        let content = response.content().unwrap_or_default();
+        serde_json::from_str(content.trim())
+            .map_err(|e| Error::SerializationError(format!("model returned malformed JSON: {e}")))
+    }
+}
@@ -0,0 +1,68 @@
+//! History compression policy for `ChatSession`
+
+/// When and how a [`ChatSession`](super::ChatSession) compresses its
+/// history.
+///
+/// Once the history would exceed `max_tokens`, the oldest turns are
+/// summarized by a model and replaced with a single system message holding
+/// the summary. The first message is kept if it is a system prompt, and the
+/// most recent messages are always kept verbatim.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::SummarizePolicy;
+///
+/// let policy = SummarizePolicy::new(6_000)
+///     .with_model("qwen3:0.6b")
+///     .with_keep_recent(6);
+/// assert_eq!(policy.max_tokens(), 6_000);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummarizePolicy {
+    max_tokens: usize,
+    keep_recent: usize,
+    model: Option<String>,
+}
+
+impl SummarizePolicy {
+    /// Messages kept verbatim when none is set
+    pub const DEFAULT_KEEP_RECENT: usize = 4;
+
+    /// Compress once the history would exceed `max_tokens`.
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            keep_recent: Self::DEFAULT_KEEP_RECENT,
+            model: None,
+        }
+    }
+
+    /// Summarize with `model` instead of the session's model, e.g. a
+    /// smaller, faster one.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Always keep at least the last `n` messages verbatim.
+    pub fn with_keep_recent(mut self, n: usize) -> Self {
+        self.keep_recent = n;
+        self
+    }
+
+    /// Token budget that triggers compression
+    pub fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    /// Messages kept verbatim
+    pub fn keep_recent(&self) -> usize {
+        self.keep_recent
+    }
+
+    /// Summarizer model, if different from the session's
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+}
@@ -0,0 +1,252 @@
+//! `OllamaClient::translate`
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{TranslatedChunk, Translation};
+use crate::http::OllamaClient;
+use crate::{Error, FormatSetting, Result};
+
+/// Longest piece, in characters, translated in one call
+const CHUNK_CHARS: usize = 2000;
+
+/// Places to end a piece, best first; the cut falls after the trimmed
+/// separator, so sentences keep their punctuation.
+const BREAKS: [&str; 6] = ["\n\n", "\n", ". ", "? ", "! ", " "];
+
+#[derive(Deserialize)]
+struct ChunkReply {
+    translation: String,
+    #[serde(default)]
+    source_language: Option<String>,
+    confidence: f64,
+}
+
+/// Split `text` into pieces of at most `max_chars` characters, preferring
+/// paragraph, line, sentence, then word boundaries. Each piece comes with
+/// the whitespace that followed it, so translations can be rejoined in the
+/// original layout.
+fn split(text: &str, max_chars: usize) -> Vec<(&str, &str)> {
+    let mut pieces = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(i, _)| i);
+        let cut = if end == rest.len() {
+            end
+        } else {
+            let window = &rest[..end];
+            BREAKS
+                .iter()
+                .find_map(|sep| {
+                    window
+                        .rfind(sep)
+                        .map(|i| i + sep.trim_end().len())
+                        .filter(|&cut| cut > 0)
+                })
+                .unwrap_or(end)
+        };
+        let (piece, tail) = rest.split_at(cut);
+        let next = tail.trim_start();
+        pieces.push((piece.trim_end(), &tail[..tail.len() - next.len()]));
+        rest = next;
+    }
+    pieces
+}
+
+impl OllamaClient {
+    /// Translate `text` into `target_lang`, detecting the source language.
+    ///
+    /// Long texts are split at paragraph, sentence, or word boundaries into
+    /// pieces of up to 2000 characters, each translated in its own call and
+    /// rejoined with the original spacing. The first piece's reply also
+    /// names the source language, which is then given to the rest. Each
+    /// [`TranslatedChunk`] carries the model's own confidence; treat it as
+    /// a hint for review rather than a calibrated probability.
+    ///
+    /// Use [`translate_from`](Self::translate_from) when the source
+    /// language is known.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `text` or `target_lang` is blank
+    /// - [`Error::SerializationError`] if the model ignores the schema
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let translation = client
+    ///     .translate("qwen3:0.6b", "Où est la gare ?", "English")
+    ///     .await?;
+    /// println!("{} (from {})", translation, translation.source_lang);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn translate(
+        &self,
+        model: &str,
+        text: &str,
+        target_lang: &str,
+    ) -> Result<Translation> {
+        self.translate_chunks(model, text, None, target_lang).await
+    }
+
+    /// Translate `text` from `source_lang` into `target_lang`.
+    ///
+    /// Same as [`translate`](Self::translate), without source-language
+    /// detection.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ValidationError`] if `text`, `source_lang`, or
+    ///   `target_lang` is blank
+    /// - [`Error::SerializationError`] if the model ignores the schema
+    /// - Any error from [`chat`](crate::OllamaApiAsync::chat)
+    pub async fn translate_from(
+        &self,
+        model: &str,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Result<Translation> {
+        if source_lang.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "translate_from needs a source language".to_string(),
+            ));
+        }
+        self.translate_chunks(model, text, Some(source_lang), target_lang)
+            .await
+    }
+
+    async fn translate_chunks(
+        &self,
+        model: &str,
+        text: &str,
+        source_lang: Option<&str>,
+        target_lang: &str,
+    ) -> Result<Translation> {
+        if text.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "translate needs some text".to_string(),
+            ));
+        }
+        if target_lang.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "translate needs a target language".to_string(),
+            ));
+        }
+
+        let mut source_lang = source_lang.map(str::to_string);
+        let mut translated = String::new();
+        let mut chunks = Vec::new();
+        for (piece, separator) in split(text, CHUNK_CHARS) {
+            let reply = self
+                .translate_piece(model, piece, source_lang.as_deref(), target_lang)
+                .await?;
+            if source_lang.is_none() {
+                source_lang = Some(reply.source_language.unwrap_or_default());
+            }
+            translated.push_str(reply.translation.trim());
+            translated.push_str(separator);
+            chunks.push(TranslatedChunk {
+                source: piece.to_string(),
+                text: reply.translation.trim().to_string(),
+                confidence: reply.confidence.clamp(0.0, 1.0),
+            });
+        }
+
+        Ok(Translation {
+            text: translated,
+            source_lang: source_lang.unwrap_or_default(),
+            target_lang: target_lang.to_string(),
+            chunks,
+        })
+    }
+
+    async fn translate_piece(
+        &self,
+        model: &str,
+        piece: &str,
+        source_lang: Option<&str>,
+        target_lang: &str,
+    ) -> Result<ChunkReply> {
+        let mut properties = json!({
+            "translation": { "type": "string" },
+            "confidence": { "type": "number", "minimum": 0, "maximum": 1 }
+        });
+        let mut required = vec!["translation", "confidence"];
+        let system = match source_lang {
+            Some(source_lang) => format!(
+                "Translate the user's text from {source_lang} into {target_lang}. \
+                 Keep the meaning, tone, and formatting, and add no explanations. \
+                 Reply with JSON containing the \"translation\" and your \
+                 \"confidence\" in it between 0 and 1."
+            ),
+            None => {
+                properties["source_language"] = json!({ "type": "string" });
+                required.push("source_language");
+                format!(
+                    "Translate the user's text into {target_lang}. \
+                     Keep the meaning, tone, and formatting, and add no explanations. \
+                     Reply with JSON containing the \"translation\", the English name \
+                     of the \"source_language\" the text is written in, and your \
+                     \"confidence\" in the translation between 0 and 1."
+                )
+            }
+        };
+        let schema = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required
+        });
+
+        self.chat_structured(
+            model,
+            system,
+            piece.to_string(),
+            FormatSetting::schema(schema),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_keeps_short_text_whole() {
+        assert_eq!(split("  Hola, mundo.\n", 100), [("Hola, mundo.", "")]);
+    }
+
+    #[test]
+    fn test_split_prefers_paragraphs_then_sentences() {
+        let text = "One. Two.\n\nThree. Four.";
+        assert_eq!(
+            split(text, 16),
+            [("One. Two.", "\n\n"), ("Three. Four.", "")]
+        );
+        assert_eq!(
+            split(text, 8),
+            [
+                ("One.", " "),
+                ("Two.", "\n\n"),
+                ("Three.", " "),
+                ("Four.", "")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_cuts_unbroken_text() {
+        assert_eq!(split("abcdef", 4), [("abcd", ""), ("ef", "")]);
+        assert_eq!(split("ééé", 2), [("éé", ""), ("é", "")]);
+    }
+}
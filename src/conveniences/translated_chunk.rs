@@ -0,0 +1,18 @@
+//! One translated piece of a `Translation`
+
+use serde::{Deserialize, Serialize};
+
+/// A piece of the text passed to
+/// [`OllamaClient::translate`](crate::OllamaClient::translate), translated
+/// in one call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslatedChunk {
+    /// The original text of this piece
+    pub source: String,
+
+    /// Its translation
+    pub text: String,
+
+    /// Model-reported confidence in `0.0..=1.0`
+    pub confidence: f64,
+}
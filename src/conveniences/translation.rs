@@ -0,0 +1,62 @@
+//! Result type for `OllamaClient::translate`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::TranslatedChunk;
+
+/// Text translated by [`OllamaClient::translate`](crate::OllamaClient::translate).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Translation {
+    /// The whole translated text
+    pub text: String,
+
+    /// Language of the original, as given or as detected by the model
+    pub source_lang: String,
+
+    /// Language translated into
+    pub target_lang: String,
+
+    /// The pieces translated one call at a time, in order
+    pub chunks: Vec<TranslatedChunk>,
+}
+
+impl Translation {
+    /// The lowest confidence of any chunk, or `0.0` without chunks
+    pub fn confidence(&self) -> f64 {
+        self.chunks
+            .iter()
+            .map(|c| c.confidence)
+            .reduce(f64::min)
+            .unwrap_or(0.0)
+    }
+}
+
+impl fmt::Display for Translation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confidence_is_lowest_chunk() {
+        let chunk = |confidence| TranslatedChunk {
+            source: "Hola".to_string(),
+            text: "Hello".to_string(),
+            confidence,
+        };
+        let translation = Translation {
+            text: "Hello\n\nHello".to_string(),
+            source_lang: "Spanish".to_string(),
+            target_lang: "English".to_string(),
+            chunks: vec![chunk(0.9), chunk(0.6)],
+        };
+        assert_eq!(translation.confidence(), 0.6);
+        assert_eq!(translation.to_string(), "Hello\n\nHello");
+    }
+}
@@ -0,0 +1,75 @@
+//! Result type for `OllamaClient::judge`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Assessment returned by [`OllamaClient::judge`](crate::OllamaClient::judge).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Verdict {
+    /// Score from 1 (fails the criteria entirely) to 10 (fully meets them)
+    pub score: u8,
+
+    /// Whether the judge considers the output acceptable
+    pub pass: bool,
+
+    /// Short explanation of the score
+    pub rationale: String,
+}
+
+impl Verdict {
+    /// Lowest possible score
+    pub const MIN_SCORE: u8 = 1;
+
+    /// Highest possible score
+    pub const MAX_SCORE: u8 = 10;
+
+    /// Check whether the verdict passed with at least `min_score`.
+    ///
+    /// Useful as a quality gate when the judge's own `pass` flag is too
+    /// lenient.
+    pub fn meets(&self, min_score: u8) -> bool {
+        self.pass && self.score >= min_score
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = if self.pass { "pass" } else { "fail" };
+        write!(
+            f,
+            "{outcome} {}/{}: {}",
+            self.score,
+            Self::MAX_SCORE,
+            self.rationale
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verdict(score: u8, pass: bool) -> Verdict {
+        Verdict {
+            score,
+            pass,
+            rationale: "Accurate and concise.".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_meets() {
+        assert!(verdict(8, true).meets(7));
+        assert!(!verdict(6, true).meets(7));
+        assert!(!verdict(9, false).meets(7));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            verdict(8, true).to_string(),
+            "pass 8/10: Accurate and concise."
+        );
+    }
+}
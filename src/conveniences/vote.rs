@@ -0,0 +1,51 @@
+//! Result type for `OllamaClient::generate_n_and_vote`
+
+use crate::GenerateResponse;
+
+/// Majority answer chosen by
+/// [`OllamaClient::generate_n_and_vote`](crate::OllamaClient::generate_n_and_vote).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vote<K> {
+    /// The answer extracted from the most samples
+    pub answer: K,
+
+    /// Number of samples that produced `answer`
+    pub votes: usize,
+
+    /// Number of samples that produced any answer
+    pub answered: usize,
+
+    /// Number of samples requested
+    pub samples: usize,
+
+    /// The first response that produced `answer`
+    pub response: GenerateResponse,
+}
+
+impl<K> Vote<K> {
+    /// Share of answering samples that agreed with the winner, in `0.0..=1.0`.
+    pub fn agreement(&self) -> f64 {
+        if self.answered == 0 {
+            0.0
+        } else {
+            self.votes as f64 / self.answered as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agreement() {
+        let vote = Vote {
+            answer: 42,
+            votes: 3,
+            answered: 4,
+            samples: 5,
+            response: GenerateResponse::default(),
+        };
+        assert_eq!(vote.agreement(), 0.75);
+    }
+}
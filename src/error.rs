@@ -3,10 +3,11 @@
 //! This module defines the error types used throughout the library,
 //! including conversions from external error types and the Result type alias.
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Error type for all ollama-oxide operations
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum Error {
     #[error("HTTP request failed: {0}")]
     HttpError(String),
@@ -35,6 +36,121 @@ pub enum Error {
     /// Error while reading or parsing a streaming (NDJSON) response.
     #[error("Stream error: {0}")]
     StreamError(String),
+
+    /// Serialized request body exceeds the configured limit.
+    #[error("Request body of {size} bytes exceeds limit of {limit} bytes")]
+    RequestTooLarge { size: usize, limit: usize },
+
+    /// Response body exceeds the configured limit.
+    ///
+    /// `limit` is in bytes, except when a
+    /// [`JsonLimits`](crate::JsonLimits) depth guard tripped, where it is
+    /// the maximum nesting depth.
+    #[error("Response exceeds limit of {limit}")]
+    ResponseTooLarge { limit: usize },
+
+    /// Request rejected locally before any network I/O.
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    /// Server kept answering 429 Too Many Requests after all retries.
+    ///
+    /// `retry_after` is the server's `Retry-After` hint, when it sent one.
+    #[error("Rate limited by server{}", retry_after_suffix(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// Error while launching or supervising a local `ollama serve` process.
+    #[error("Server process error: {0}")]
+    ServerProcessError(String),
+
+    /// A generate or chat request's estimated tokens exceed the context
+    /// window allowed by the client's token budget.
+    #[error("Request needs about {estimated} tokens, context window is {limit}")]
+    TokenBudgetExceeded { estimated: usize, limit: usize },
+
+    /// Error while loading or saving persisted data, such as chat sessions.
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    /// A model's prompt template could not be parsed or rendered locally.
+    #[error("Template error: {0}")]
+    TemplateError(String),
+
+    /// The client was shut down, so the request was refused or cancelled.
+    #[error("Client is shut down")]
+    ClientShutdown,
+
+    /// A guardrail refused a prompt before it was sent, or a reply after it
+    /// arrived.
+    #[error("Blocked by guardrail {guardrail}: {reason}")]
+    Blocked { guardrail: String, reason: String },
+
+    /// A streamed reply broke off before its final event, e.g. because the
+    /// server restarted; `partial` holds the text received until then.
+    #[error("Stream interrupted: {message}")]
+    StreamInterrupted { partial: String, message: String },
+
+    /// A streamed generation failed with `error` after part of the reply
+    /// had arrived; `partial` holds that text.
+    #[error("{error} (after {} bytes of output)", partial.len())]
+    PartialOutput {
+        partial: String,
+        #[source]
+        error: Box<Error>,
+    },
+
+    /// Vectors from different embedding models or lengths were compared or
+    /// mixed; see `EmbeddingSpace`.
+    #[error("Embedding space mismatch: expected {expected}, found {found}")]
+    EmbeddingSpaceMismatch { expected: String, found: String },
+}
+
+impl Error {
+    /// Text a streamed generation produced before failing, if any.
+    ///
+    /// Set by [`StreamInterrupted`](Self::StreamInterrupted) and
+    /// [`PartialOutput`](Self::PartialOutput).
+    pub fn partial_output(&self) -> Option<&str> {
+        match self {
+            Self::StreamInterrupted { partial, .. } | Self::PartialOutput { partial, .. }
+                if !partial.is_empty() =>
+            {
+                Some(partial)
+            }
+            _ => None,
+        }
+    }
+
+    /// This error with `partial` attached, unless `partial` is empty or
+    /// the error already carries output.
+    #[cfg(feature = "http")]
+    pub(crate) fn with_partial_output(self, partial: &str) -> Self {
+        if partial.is_empty() || self.partial_output().is_some() {
+            return self;
+        }
+        match self {
+            Self::StreamInterrupted { message, .. } => Self::StreamInterrupted {
+                partial: partial.to_string(),
+                message,
+            },
+            error => Self::PartialOutput {
+                partial: partial.to_string(),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// A [`StorageError`](Self::StorageError) for `e` while accessing `path`.
+    #[cfg(feature = "http")]
+    pub(crate) fn storage(path: &std::path::Path, e: impl std::fmt::Display) -> Self {
+        Self::StorageError(format!("{}: {}", path.display(), e))
+    }
+}
+
+fn retry_after_suffix(retry_after: &Option<Duration>) -> String {
+    retry_after
+        .map(|d| format!("; retry after {}s", d.as_secs()))
+        .unwrap_or_default()
 }
 
 impl From<reqwest::Error> for Error {
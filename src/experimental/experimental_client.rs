@@ -0,0 +1,129 @@
+//! Raw access to endpoints without stable bindings
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use reqwest::Method;
+
+use crate::http::endpoints::Target;
+use crate::{Error, OllamaClient, ResponseStream, ResponseStreamBlocking, Result};
+
+/// Raw endpoint access, returned by [`OllamaClient::experimental`].
+///
+/// Paths are relative to the client's base URL and must start with `/`.
+/// Responses decode into any [`DeserializeOwned`] type; use
+/// [`serde_json::Value`] when the shape is still in flux.
+///
+/// Not covered by semver; see the [module docs](super).
+#[derive(Debug, Clone, Copy)]
+pub struct Experimental<'a> {
+    client: &'a OllamaClient,
+}
+
+impl OllamaClient {
+    /// Access endpoints that have no stable binding yet.
+    ///
+    /// Requires the `experimental` feature and is exempt from semver.
+    pub fn experimental(&self) -> Experimental<'_> {
+        Experimental { client: self }
+    }
+}
+
+impl Experimental<'_> {
+    fn target(&self, method: Method, path: &str) -> Result<Target> {
+        if !path.starts_with('/') {
+            return Err(Error::ValidationError(format!(
+                "experimental endpoint path must start with '/', got '{path}'"
+            )));
+        }
+        Ok(Target::custom(
+            method,
+            self.client.config.endpoint_url(path),
+        ))
+    }
+
+    /// `GET path` and decode the JSON reply (async)
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] for a path without a leading `/`, otherwise
+    /// the same errors as the stable endpoints.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let target = self.target(Method::GET, path)?;
+        self.client.get_with_retry(&target).await
+    }
+
+    /// `GET path` and decode the JSON reply (blocking)
+    pub fn get_blocking<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let target = self.target(Method::GET, path)?;
+        self.client.get_blocking_with_retry(&target)
+    }
+
+    /// `POST path` with a JSON body and decode the JSON reply (async)
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] for a path without a leading `/`, otherwise
+    /// the same errors as the stable endpoints.
+    pub async fn post<B, T>(&self, path: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + Sync,
+        T: DeserializeOwned,
+    {
+        let target = self.target(Method::POST, path)?;
+        self.client.post_with_retry(&target, body).await
+    }
+
+    /// `POST path` with a JSON body and decode the JSON reply (blocking)
+    pub fn post_blocking<B, T>(&self, path: &str, body: &B) -> Result<T>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let target = self.target(Method::POST, path)?;
+        self.client.post_blocking_with_retry(&target, body)
+    }
+
+    /// `POST path` and stream the NDJSON reply line by line (async)
+    ///
+    /// The body is sent as given; set any `"stream": true` flag the endpoint
+    /// expects yourself. Not retried, like the other streaming calls.
+    pub async fn post_stream<B, T>(&self, path: &str, body: &B) -> Result<ResponseStream<T>>
+    where
+        B: Serialize + Sync,
+        T: DeserializeOwned + Send + 'static,
+    {
+        let target = self.target(Method::POST, path)?;
+        self.client.post_ndjson_stream(&target, body).await
+    }
+
+    /// `POST path` and stream the NDJSON reply line by line (blocking)
+    pub fn post_stream_blocking<B, T>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<ResponseStreamBlocking<T>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let target = self.target(Method::POST, path)?;
+        self.client.post_ndjson_stream_blocking(&target, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_must_be_absolute() {
+        let client = OllamaClient::default().unwrap();
+        let experimental = client.experimental();
+        assert!(experimental.target(Method::GET, "/api/new").is_ok());
+        assert!(matches!(
+            experimental.target(Method::GET, "api/new"),
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
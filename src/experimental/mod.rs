@@ -0,0 +1,48 @@
+//! Unstable and newly added Ollama endpoints
+//!
+//! Ollama's server grows endpoints faster than this crate can design and
+//! stabilize typed bindings for them. This module is where they land first:
+//! [`Experimental`] calls any path on the configured server with the same
+//! retries, auth, body limits, and observers as the stable API, and decodes
+//! the reply into whatever type the caller asks for, usually
+//! [`serde_json::Value`]. Only `GET` and `HEAD` calls are retried after a
+//! server error or timeout; other methods are retried only on connection
+//! failures and 429, where the server cannot have acted on them.
+//!
+//! # Stability
+//!
+//! **Everything here is exempt from semantic versioning.** Items may change
+//! or disappear in any release, including patch releases. Once an endpoint's
+//! shape settles upstream it graduates to [`OllamaApiAsync`](crate::OllamaApiAsync)
+//! / [`OllamaApiSync`](crate::OllamaApiSync) with typed request and response
+//! structs.
+//!
+//! # Feature Flag
+//!
+//! This module requires the `experimental` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! ollama-oxide = { version = "0.2", features = ["experimental"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::OllamaClient;
+//! use serde_json::{Value, json};
+//!
+//! # async fn example() -> ollama_oxide::Result<()> {
+//! let client = OllamaClient::default()?;
+//! let reply: Value = client
+//!     .experimental()
+//!     .post("/api/some_new_endpoint", &json!({"model": "qwen3:0.6b"}))
+//!     .await?;
+//! println!("{reply:#}");
+//! # Ok(())
+//! # }
+//! ```
+
+mod experimental_client;
+
+pub use experimental_client::Experimental;
@@ -5,18 +5,29 @@ use crate::{
     Result, VersionResponse,
 };
 
-use super::streaming::ChatStream;
+use super::streaming::{ChatStream, GenerateStream, RawChunkStream};
 
+#[cfg(feature = "model")]
+use super::PullStream;
+#[cfg(feature = "model")]
+use crate::{ChatMessage, ModelCapabilities, PromptTemplate};
 #[cfg(feature = "model")]
 use crate::{
     CopyRequest, CreateRequest, CreateResponse, DeleteRequest, ListResponse, PsResponse,
     PullRequest, PullResponse, PushRequest, PushResponse, ShowRequest, ShowResponse,
 };
 
+#[cfg(feature = "web")]
+use crate::web::{WebFetchRequest, WebFetchResponse, WebSearchRequest, WebSearchResponse};
+
 use async_trait::async_trait;
 
 use super::OllamaClient;
-use super::endpoints::Endpoints;
+use super::endpoints::Endpoint;
+use super::image_io::image_body;
+use super::post_processor::post_processed;
+use super::request_defaults::{streaming_with_defaults, with_defaults};
+use super::token_budget::within_budget;
 
 /// Async API operations trait
 ///
@@ -227,11 +238,77 @@ pub trait OllamaApiAsync: Send + Sync {
     #[cfg(feature = "model")]
     async fn show_model(&self, request: &ShowRequest) -> Result<ShowResponse>;
 
+    /// Report whether a model supports tools, images, or thinking (async)
+    ///
+    /// Calls `/api/show` and derives a [`ModelCapabilities`] summary, so
+    /// callers can drop unsupported request fields before sending them.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Name of the model to inspect
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model does not exist or the request fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{OllamaClient, OllamaApiAsync};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::default()?;
+    /// let caps = client.model_capabilities("llama3.2").await?;
+    /// if !caps.tools {
+    ///     println!("tool calling unavailable, falling back to plain chat");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    async fn model_capabilities(&self, model: &str) -> Result<ModelCapabilities>;
+
+    /// Render the prompt a model is given for a conversation (async)
+    ///
+    /// Fetches the model's template from `/api/show` and renders it locally
+    /// with [`PromptTemplate`], to debug exactly what the model sees. A
+    /// model without a template is rendered with `{{ .Prompt }}`, as the
+    /// server does.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Name of the model whose template to use
+    /// * `messages` - The conversation, as it would be sent to chat
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model does not exist or the request fails,
+    /// or [`Error::TemplateError`](crate::Error::TemplateError) if the
+    /// template cannot be rendered locally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::default()?;
+    /// let prompt = client
+    ///     .render_prompt("qwen3:0.6b", &[ChatMessage::user("Hello!")])
+    ///     .await?;
+    /// println!("{prompt}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    async fn render_prompt(&self, model: &str, messages: &[ChatMessage]) -> Result<String>;
+
     /// Generate embeddings for text (async)
     ///
     /// Creates vector embeddings representing the input text(s).
     /// Embeddings are useful for semantic search, similarity comparison,
-    /// and machine learning tasks.
+    /// and machine learning tasks. Values are `f32`; use
+    /// [`OllamaClient::embed_as`] for `f64`.
     ///
     /// # Arguments
     ///
@@ -240,6 +317,7 @@ pub trait OllamaApiAsync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Input exceeds context window and truncate is false
     /// - Network request fails
@@ -289,6 +367,7 @@ pub trait OllamaApiAsync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Network request fails
     /// - Maximum retry attempts exceeded
@@ -308,6 +387,50 @@ pub trait OllamaApiAsync: Send + Sync {
     /// ```
     async fn generate(&self, request: &GenerateRequest) -> Result<GenerateResponse>;
 
+    /// Text completion with streaming (NDJSON), yielding tokens as the
+    /// model produces them.
+    ///
+    /// Sends `stream: true` regardless of the value on `request`. Each line of the
+    /// response body is deserialized as [`GenerateResponse`], whose
+    /// [`text`](GenerateResponse::text) is the newly generated fragment; the
+    /// final event has `done: Some(true)` and timing fields populated.
+    /// Guardrails check the request, and the client's post-processor applies
+    /// to each event, as for [`chat_stream`](Self::chat_stream).
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Generate request (model, prompt, options). Streaming methods
+    ///   set `stream` to `true` on a clone of this value before sending.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
+    /// - The HTTP status is not success (including 4xx)
+    /// - A line is not valid JSON (see [`Error::StreamError`](crate::Error::StreamError))
+    /// - The connection drops, or the body ends, before the final event
+    ///   (see [`Error::StreamInterrupted`](crate::Error::StreamInterrupted),
+    ///   which carries the text received)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaApiAsync, OllamaClient};
+    /// use std::io::Write;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new("qwen3:0.6b", "Tell me a joke.");
+    /// let stream = client.generate_stream(&request).await?;
+    /// while let Some(ev) = stream.next().await {
+    ///     print!("{}", ev?.text().unwrap_or_default());
+    ///     std::io::stdout().flush().ok();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn generate_stream(&self, request: &GenerateRequest) -> Result<GenerateStream>;
+
     /// Chat completion (async, non-streaming)
     ///
     /// Generates the next message in a chat conversation.
@@ -320,6 +443,7 @@ pub trait OllamaApiAsync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Network request fails
     /// - Maximum retry attempts exceeded
@@ -401,8 +525,13 @@ pub trait OllamaApiAsync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - The HTTP status is not success (including 4xx)
-    /// - A chunk cannot be read or a line is not valid JSON (see [`Error::StreamError`](crate::Error::StreamError))
+    /// - A line is not valid JSON (see [`Error::StreamError`](crate::Error::StreamError))
+    /// - The connection drops, or the body ends, before the final event
+    ///   (see [`Error::StreamInterrupted`](crate::Error::StreamInterrupted),
+    ///   which carries the text received, and
+    ///   [`ClientConfig::with_stream_resume`](super::ClientConfig::with_stream_resume))
     ///
     /// # Examples
     ///
@@ -424,6 +553,50 @@ pub trait OllamaApiAsync: Send + Sync {
     /// ```
     async fn chat_stream(&self, request: &ChatRequest) -> Result<ChatStream>;
 
+    /// Chat completion with streaming, yielding each line unparsed.
+    ///
+    /// Like [`chat_stream`](Self::chat_stream), but each event is a
+    /// [`RawChunk`](crate::RawChunk) that shares the response buffer. Parse
+    /// it into a borrowing type with [`RawChunk::parse`](crate::RawChunk::parse)
+    /// to skip the per-token `String` allocations of [`ChatResponse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails validation or the HTTP status
+    /// is not success. Read failures are yielded from the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Token<'a> {
+    ///     #[serde(borrow)]
+    ///     message: Option<Message<'a>>,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Message<'a> {
+    ///     content: &'a str,
+    /// }
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi!")]);
+    /// let stream = client.chat_stream_raw(&request).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     let chunk = chunk?;
+    ///     if let Some(message) = chunk.parse::<Token>()?.message {
+    ///         print!("{}", message.content);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn chat_stream_raw(&self, request: &ChatRequest) -> Result<RawChunkStream>;
+
     /// Create a custom model (async, non-streaming)
     ///
     /// Creates a new model from an existing model with custom configuration.
@@ -494,6 +667,35 @@ pub trait OllamaApiAsync: Send + Sync {
     #[cfg(feature = "model")]
     async fn pull_model(&self, request: &PullRequest) -> Result<PullResponse>;
 
+    /// Download a model and stream progress events (async)
+    ///
+    /// Sends the request with `stream: true` (the value set on `request` is
+    /// ignored) and yields one [`PullResponse`] per NDJSON line. Use
+    /// [`PullStream::progress_summary`](super::ResponseStream::progress_summary)
+    /// for deduplicated per-layer progress suited to progress bars.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP status is not success before the body is read.
+    /// Per-line errors are yielded from [`next`](super::ResponseStream::next).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{OllamaApiAsync, OllamaClient, PullRequest};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let stream = client.pull_model_stream(&PullRequest::new("qwen3:0.6b")).await?;
+    /// while let Some(event) = stream.next().await {
+    ///     println!("{:?}", event?.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    async fn pull_model_stream(&self, request: &PullRequest) -> Result<PullStream>;
+
     /// Push (upload) a model to the Ollama registry.
     ///
     /// Uploads the specified model to a remote registry. Requires proper
@@ -529,83 +731,252 @@ pub trait OllamaApiAsync: Send + Sync {
     /// ```
     #[cfg(feature = "model")]
     async fn push_model(&self, request: &PushRequest) -> Result<PushResponse>;
+
+    /// Search the web (async)
+    ///
+    /// Calls the hosted `POST /api/web_search` endpoint. The client must be
+    /// built from [`ClientConfig::ollama_cloud`](crate::ClientConfig::ollama_cloud)
+    /// (or otherwise target `https://ollama.com` with an API key).
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` - Blank query or `max_results` outside `1..=10`
+    /// * `HttpStatusError(401)` - Missing or invalid API key
+    /// * `HttpError` - Network or HTTP errors
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::web::WebSearchRequest;
+    /// use ollama_oxide::{ClientConfig, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::new(ClientConfig::ollama_cloud("my-api-key"))?;
+    /// let response = client.web_search(&WebSearchRequest::new("ollama")).await?;
+    /// println!("{} results", response.results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "web")]
+    async fn web_search(&self, request: &WebSearchRequest) -> Result<WebSearchResponse>;
+
+    /// Fetch a web page (async)
+    ///
+    /// Calls the hosted `POST /api/web_fetch` endpoint and returns the page
+    /// title, main text content, and links. Requires the same `ollama.com`
+    /// base URL and API key as [`web_search`](OllamaApiAsync::web_search).
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` - URL does not parse or is not http/https
+    /// * `HttpStatusError(401)` - Missing or invalid API key
+    /// * `HttpError` - Network or HTTP errors
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::web::WebFetchRequest;
+    /// use ollama_oxide::{ClientConfig, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::new(ClientConfig::ollama_cloud("my-api-key"))?;
+    /// let page = client.web_fetch(&WebFetchRequest::new("https://ollama.com")).await?;
+    /// println!("{}: {} links", page.title, page.links.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "web")]
+    async fn web_fetch(&self, request: &WebFetchRequest) -> Result<WebFetchResponse>;
 }
 
 #[async_trait]
 impl OllamaApiAsync for OllamaClient {
     async fn version(&self) -> Result<VersionResponse> {
-        let url = self.config.url(Endpoints::VERSION);
-        self.get_with_retry(&url).await
+        let target = self.target(Endpoint::Version);
+        self.get_with_retry(&target).await
     }
 
     #[cfg(feature = "model")]
     async fn list_models(&self) -> Result<ListResponse> {
-        let url = self.config.url(Endpoints::TAGS);
-        self.get_with_retry(&url).await
+        let target = self.target(Endpoint::Tags);
+        self.get_with_retry(&target).await
     }
 
     #[cfg(feature = "model")]
     async fn copy_model(&self, request: &CopyRequest) -> Result<()> {
-        let url = self.config.url(Endpoints::COPY);
-        self.post_empty_with_retry(&url, request).await
+        let target = self.target(Endpoint::Copy);
+        self.send_empty_with_retry(&target, request).await
     }
 
     #[cfg(feature = "model")]
     async fn list_running_models(&self) -> Result<PsResponse> {
-        let url = self.config.url(Endpoints::PS);
-        self.get_with_retry(&url).await
+        let target = self.target(Endpoint::Ps);
+        self.get_with_retry(&target).await
     }
 
     #[cfg(feature = "model")]
     async fn delete_model(&self, request: &DeleteRequest) -> Result<()> {
-        let url = self.config.url(Endpoints::DELETE);
-        self.delete_empty_with_retry(&url, request).await
+        let target = self.target(Endpoint::Delete);
+        self.send_empty_with_retry(&target, request).await
     }
 
     #[cfg(feature = "model")]
     async fn show_model(&self, request: &ShowRequest) -> Result<ShowResponse> {
-        let url = self.config.url(Endpoints::SHOW);
-        self.post_with_retry(&url, request).await
+        let target = self.target(Endpoint::Show);
+        self.post_with_retry(&target, request).await
+    }
+
+    #[cfg(feature = "model")]
+    async fn model_capabilities(&self, model: &str) -> Result<ModelCapabilities> {
+        let response = self.show_model(&ShowRequest::new(model)).await?;
+        Ok(ModelCapabilities::resolve(model, &response))
+    }
+
+    #[cfg(feature = "model")]
+    async fn render_prompt(&self, model: &str, messages: &[ChatMessage]) -> Result<String> {
+        let response = self.show_model(&ShowRequest::new(model)).await?;
+        response
+            .prompt_template()?
+            .map_or_else(PromptTemplate::fallback, Ok)?
+            .render(messages)
     }
 
     async fn embed(&self, request: &EmbedRequest) -> Result<EmbedResponse> {
-        let url = self.config.url(Endpoints::EMBED);
-        self.post_with_retry(&url, request).await
+        self.embed_as(request).await
     }
 
     async fn generate(&self, request: &GenerateRequest) -> Result<GenerateResponse> {
-        let url = self.config.url(Endpoints::GENERATE);
-        self.post_with_retry(&url, request).await
+        let request = with_defaults(&self.config, request);
+        let request = within_budget(&self.config, request)?;
+        request.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let request = self
+            .guardrails
+            .check_request(&target, &self.observers, request)
+            .await?;
+        let body = image_body(self, &*request).await?;
+        let response = self.post_with_retry(&target, &body).await?;
+        let response = post_processed(&self.config, response);
+        self.guardrails
+            .check_response(&target, &self.observers, response)
+            .await
+    }
+
+    async fn generate_stream(&self, request: &GenerateRequest) -> Result<GenerateStream> {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let body = image_body(self, &*req).await?;
+        let stream = self
+            .post_ndjson_stream(&target, &body)
+            .await?
+            .track_partial();
+        Ok(match self.config.post_processor() {
+            Some(processor) => stream.post_process(processor),
+            None => stream,
+        })
     }
 
     async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse> {
-        let url = self.config.url(Endpoints::CHAT);
-        self.post_with_retry(&url, request).await
+        let request = with_defaults(&self.config, request);
+        let request = within_budget(&self.config, request)?;
+        request.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let request = self
+            .guardrails
+            .check_request(&target, &self.observers, request)
+            .await?;
+        // The fallback goes through `generate`, which checks the reply.
+        #[cfg(feature = "model")]
+        if let Some(response) = self.chat_fallback(&request).await? {
+            return Ok(response);
+        }
+        let body = image_body(self, &*request).await?;
+        let response = self.post_with_retry(&target, &body).await?;
+        let response = post_processed(&self.config, response);
+        self.guardrails
+            .check_response(&target, &self.observers, response)
+            .await
     }
 
     async fn chat_stream(&self, request: &ChatRequest) -> Result<ChatStream> {
-        let mut req = request.clone();
-        req.stream = Some(true);
-        let url = self.config.url(Endpoints::CHAT);
-        let rx = self.post_ndjson_stream(&url, &req).await?;
-        Ok(ChatStream::new(rx))
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let stream = match self.config.stream_resume() {
+            0 => self
+                .post_ndjson_stream(&target, &image_body(self, &*req).await?)
+                .await?
+                .track_partial(),
+            attempts => {
+                self.resumable_chat_stream(&target, req.into_owned(), attempts)
+                    .await?
+            }
+        };
+        Ok(match self.config.post_processor() {
+            Some(processor) => stream.post_process(processor),
+            None => stream,
+        })
+    }
+
+    async fn chat_stream_raw(&self, request: &ChatRequest) -> Result<RawChunkStream> {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let body = image_body(self, &*req).await?;
+        self.post_ndjson_raw(&target, &body).await
     }
 
     #[cfg(feature = "model")]
     async fn create_model(&self, request: &CreateRequest) -> Result<CreateResponse> {
-        let url = self.config.url(Endpoints::CREATE);
-        self.post_with_retry(&url, request).await
+        let target = self.target(Endpoint::Create);
+        self.post_with_retry(&target, request).await
     }
 
     #[cfg(feature = "model")]
     async fn pull_model(&self, request: &PullRequest) -> Result<PullResponse> {
-        let url = self.config.url(Endpoints::PULL);
-        self.post_with_retry(&url, request).await
+        let target = self.target(Endpoint::Pull);
+        self.post_with_retry(&target, request).await
+    }
+
+    #[cfg(feature = "model")]
+    async fn pull_model_stream(&self, request: &PullRequest) -> Result<PullStream> {
+        let target = self.target(Endpoint::Pull);
+        self.post_ndjson_stream(&target, &request.streaming()).await
     }
 
     #[cfg(feature = "model")]
     async fn push_model(&self, request: &PushRequest) -> Result<PushResponse> {
-        let url = self.config.url(Endpoints::PUSH);
-        self.post_with_retry(&url, request).await
+        let target = self.target(Endpoint::Push);
+        self.post_with_retry(&target, request).await
+    }
+
+    #[cfg(feature = "web")]
+    async fn web_search(&self, request: &WebSearchRequest) -> Result<WebSearchResponse> {
+        request.validate()?;
+        let target = self.target(Endpoint::WebSearch);
+        self.post_with_retry(&target, request).await
+    }
+
+    #[cfg(feature = "web")]
+    async fn web_fetch(&self, request: &WebFetchRequest) -> Result<WebFetchResponse> {
+        request.validate()?;
+        let target = self.target(Endpoint::WebFetch);
+        self.post_with_retry(&target, request).await
     }
 }
@@ -5,16 +5,26 @@ use crate::{
     Result, VersionResponse,
 };
 
-use super::streaming::ChatStreamBlocking;
+use super::streaming::{ChatStreamBlocking, GenerateStreamBlocking, RawChunkStreamBlocking};
 
+#[cfg(feature = "model")]
+use super::PullStreamBlocking;
+#[cfg(feature = "model")]
+use crate::{ChatMessage, ModelCapabilities, PromptTemplate};
 #[cfg(feature = "model")]
 use crate::{
     CopyRequest, CreateRequest, CreateResponse, DeleteRequest, ListResponse, PsResponse,
     PullRequest, PullResponse, PushRequest, PushResponse, ShowRequest, ShowResponse,
 };
 
+#[cfg(feature = "web")]
+use crate::web::{WebFetchRequest, WebFetchResponse, WebSearchRequest, WebSearchResponse};
+
 use super::OllamaClient;
-use super::endpoints::Endpoints;
+use super::endpoints::Endpoint;
+use super::post_processor::post_processed;
+use super::request_defaults::{streaming_with_defaults, with_defaults};
+use super::token_budget::within_budget;
 
 /// Sync API operations trait
 ///
@@ -230,10 +240,73 @@ pub trait OllamaApiSync: Send + Sync {
     #[cfg(feature = "model")]
     fn show_model_blocking(&self, request: &ShowRequest) -> Result<ShowResponse>;
 
+    /// Report whether a model supports tools, images, or thinking (blocking)
+    ///
+    /// Calls `/api/show` and derives a [`ModelCapabilities`] summary.
+    /// This method blocks the current thread until the request completes.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Name of the model to inspect
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model does not exist or the request fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{OllamaClient, OllamaApiSync};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::default()?;
+    /// let caps = client.model_capabilities_blocking("llava")?;
+    /// println!("vision: {}", caps.vision);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    fn model_capabilities_blocking(&self, model: &str) -> Result<ModelCapabilities>;
+
+    /// Render the prompt a model is given for a conversation (blocking)
+    ///
+    /// Fetches the model's template from `/api/show` and renders it locally
+    /// with [`PromptTemplate`], to debug exactly what the model sees. A
+    /// model without a template is rendered with `{{ .Prompt }}`, as the
+    /// server does.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Name of the model whose template to use
+    /// * `messages` - The conversation, as it would be sent to chat
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model does not exist or the request fails,
+    /// or [`Error::TemplateError`](crate::Error::TemplateError) if the
+    /// template cannot be rendered locally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, OllamaApiSync, OllamaClient};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::default()?;
+    /// let prompt = client
+    ///     .render_prompt_blocking("qwen3:0.6b", &[ChatMessage::user("Hello!")])?;
+    /// println!("{prompt}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    fn render_prompt_blocking(&self, model: &str, messages: &[ChatMessage]) -> Result<String>;
+
     /// Generate embeddings for text (blocking)
     ///
     /// Creates vector embeddings representing the input text(s).
     /// This method blocks the current thread until the request completes.
+    /// Values are `f32`; use [`OllamaClient::embed_as_blocking`] for `f64`.
     ///
     /// # Arguments
     ///
@@ -242,6 +315,7 @@ pub trait OllamaApiSync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Input exceeds context window and truncate is false
     /// - Network request fails
@@ -274,6 +348,7 @@ pub trait OllamaApiSync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Network request fails
     /// - Maximum retry attempts exceeded
@@ -293,6 +368,33 @@ pub trait OllamaApiSync: Send + Sync {
     /// ```
     fn generate_blocking(&self, request: &GenerateRequest) -> Result<GenerateResponse>;
 
+    /// Text completion with streaming (NDJSON), blocking iterator.
+    ///
+    /// See [`OllamaApiAsync::generate_stream`](super::OllamaApiAsync::generate_stream).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails validation or the HTTP status is not
+    /// success before the body is read.
+    /// Per-line JSON errors are yielded as [`Err`](crate::Result) from the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaApiSync, OllamaClient};
+    ///
+    /// # fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new("qwen3:0.6b", "Tell me a joke.");
+    /// for ev in client.generate_stream_blocking(&request)? {
+    ///     print!("{}", ev?.text().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn generate_stream_blocking(&self, request: &GenerateRequest)
+    -> Result<GenerateStreamBlocking>;
+
     /// Chat completion (blocking, non-streaming)
     ///
     /// Generates the next message in a chat conversation.
@@ -305,6 +407,7 @@ pub trait OllamaApiSync: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The request fails local validation (`validate()`)
     /// - Model doesn't exist (404)
     /// - Network request fails
     /// - Maximum retry attempts exceeded
@@ -338,7 +441,8 @@ pub trait OllamaApiSync: Send + Sync {
     ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP status is not success before the body is read.
+    /// Returns an error if the request fails validation or the HTTP status is not
+    /// success before the body is read.
     /// Per-line JSON errors are yielded as [`Err`](crate::Result) from the iterator.
     ///
     /// # Examples
@@ -360,6 +464,18 @@ pub trait OllamaApiSync: Send + Sync {
     /// ```
     fn chat_stream_blocking(&self, request: &ChatRequest) -> Result<ChatStreamBlocking>;
 
+    /// Chat completion with streaming, yielding each line unparsed (blocking).
+    ///
+    /// See [`OllamaApiAsync::chat_stream_raw`](super::OllamaApiAsync::chat_stream_raw).
+    /// Lines are read into one reused buffer and copied into each
+    /// [`RawChunk`](crate::RawChunk).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails validation or the HTTP status
+    /// is not success. Read failures are yielded from the iterator.
+    fn chat_stream_raw_blocking(&self, request: &ChatRequest) -> Result<RawChunkStreamBlocking>;
+
     /// Create a custom model (blocking, non-streaming)
     ///
     /// Creates a new model from an existing model with custom configuration.
@@ -430,6 +546,35 @@ pub trait OllamaApiSync: Send + Sync {
     #[cfg(feature = "model")]
     fn pull_model_blocking(&self, request: &PullRequest) -> Result<PullResponse>;
 
+    /// Download a model and iterate over progress events (blocking)
+    ///
+    /// Sends the request with `stream: true` and yields one [`PullResponse`]
+    /// per NDJSON line. Call `progress_summary()` on the result for
+    /// deduplicated per-layer progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP status is not success before the body is read.
+    /// Per-line errors are yielded as [`Err`](crate::Result) from the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{OllamaApiSync, OllamaClient, PullRequest};
+    ///
+    /// # fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let stream = client.pull_model_stream_blocking(&PullRequest::new("qwen3:0.6b"))?;
+    /// for update in stream.progress_summary() {
+    ///     let update = update?;
+    ///     println!("{:>5.1}% {}", update.percent(), update.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "model")]
+    fn pull_model_stream_blocking(&self, request: &PullRequest) -> Result<PullStreamBlocking>;
+
     /// Push (upload) a model to the Ollama registry (blocking).
     ///
     /// Uploads the specified model to a remote registry. Requires proper
@@ -465,81 +610,224 @@ pub trait OllamaApiSync: Send + Sync {
     /// ```
     #[cfg(feature = "model")]
     fn push_model_blocking(&self, request: &PushRequest) -> Result<PushResponse>;
+
+    /// Search the web (blocking)
+    ///
+    /// Calls the hosted `POST /api/web_search` endpoint. The client must be
+    /// built from [`ClientConfig::ollama_cloud`](crate::ClientConfig::ollama_cloud)
+    /// (or otherwise target `https://ollama.com` with an API key).
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` - Blank query or `max_results` outside `1..=10`
+    /// * `HttpStatusError(401)` - Missing or invalid API key
+    /// * `HttpError` - Network or HTTP errors
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::web::WebSearchRequest;
+    /// use ollama_oxide::{ClientConfig, OllamaApiSync, OllamaClient};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::new(ClientConfig::ollama_cloud("my-api-key"))?;
+    /// let response = client.web_search_blocking(&WebSearchRequest::new("ollama"))?;
+    /// println!("{} results", response.results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "web")]
+    fn web_search_blocking(&self, request: &WebSearchRequest) -> Result<WebSearchResponse>;
+
+    /// Fetch a web page (blocking)
+    ///
+    /// Calls the hosted `POST /api/web_fetch` endpoint and returns the page
+    /// title, main text content, and links. Requires the same `ollama.com`
+    /// base URL and API key as [`web_search_blocking`](OllamaApiSync::web_search_blocking).
+    ///
+    /// # Errors
+    ///
+    /// * `ValidationError` - URL does not parse or is not http/https
+    /// * `HttpStatusError(401)` - Missing or invalid API key
+    /// * `HttpError` - Network or HTTP errors
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::web::WebFetchRequest;
+    /// use ollama_oxide::{ClientConfig, OllamaApiSync, OllamaClient};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::new(ClientConfig::ollama_cloud("my-api-key"))?;
+    /// let page = client.web_fetch_blocking(&WebFetchRequest::new("https://ollama.com"))?;
+    /// println!("{}: {} links", page.title, page.links.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "web")]
+    fn web_fetch_blocking(&self, request: &WebFetchRequest) -> Result<WebFetchResponse>;
 }
 
 impl OllamaApiSync for OllamaClient {
     fn version_blocking(&self) -> Result<VersionResponse> {
-        let url = self.config.url(Endpoints::VERSION);
-        self.get_blocking_with_retry(&url)
+        let target = self.target(Endpoint::Version);
+        self.get_blocking_with_retry(&target)
     }
 
     #[cfg(feature = "model")]
     fn list_models_blocking(&self) -> Result<ListResponse> {
-        let url = self.config.url(Endpoints::TAGS);
-        self.get_blocking_with_retry(&url)
+        let target = self.target(Endpoint::Tags);
+        self.get_blocking_with_retry(&target)
     }
 
     #[cfg(feature = "model")]
     fn copy_model_blocking(&self, request: &CopyRequest) -> Result<()> {
-        let url = self.config.url(Endpoints::COPY);
-        self.post_empty_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Copy);
+        self.send_empty_blocking_with_retry(&target, request)
     }
 
     #[cfg(feature = "model")]
     fn list_running_models_blocking(&self) -> Result<PsResponse> {
-        let url = self.config.url(Endpoints::PS);
-        self.get_blocking_with_retry(&url)
+        let target = self.target(Endpoint::Ps);
+        self.get_blocking_with_retry(&target)
     }
 
     #[cfg(feature = "model")]
     fn delete_model_blocking(&self, request: &DeleteRequest) -> Result<()> {
-        let url = self.config.url(Endpoints::DELETE);
-        self.delete_empty_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Delete);
+        self.send_empty_blocking_with_retry(&target, request)
     }
 
     #[cfg(feature = "model")]
     fn show_model_blocking(&self, request: &ShowRequest) -> Result<ShowResponse> {
-        let url = self.config.url(Endpoints::SHOW);
-        self.post_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Show);
+        self.post_blocking_with_retry(&target, request)
+    }
+
+    #[cfg(feature = "model")]
+    fn model_capabilities_blocking(&self, model: &str) -> Result<ModelCapabilities> {
+        let response = self.show_model_blocking(&ShowRequest::new(model))?;
+        Ok(ModelCapabilities::resolve(model, &response))
+    }
+
+    #[cfg(feature = "model")]
+    fn render_prompt_blocking(&self, model: &str, messages: &[ChatMessage]) -> Result<String> {
+        let response = self.show_model_blocking(&ShowRequest::new(model))?;
+        response
+            .prompt_template()?
+            .map_or_else(PromptTemplate::fallback, Ok)?
+            .render(messages)
     }
 
     fn embed_blocking(&self, request: &EmbedRequest) -> Result<EmbedResponse> {
-        let url = self.config.url(Endpoints::EMBED);
-        self.post_blocking_with_retry(&url, request)
+        self.embed_as_blocking(request)
     }
 
     fn generate_blocking(&self, request: &GenerateRequest) -> Result<GenerateResponse> {
-        let url = self.config.url(Endpoints::GENERATE);
-        self.post_blocking_with_retry(&url, request)
+        let request = with_defaults(&self.config, request);
+        let request = within_budget(&self.config, request)?;
+        request.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let request = self.guarded_request_blocking(&target, request)?;
+        let response = self.post_blocking_with_retry(&target, &*request)?;
+        let response = post_processed(&self.config, response);
+        self.guarded_response_blocking(&target, response)
+    }
+
+    fn generate_stream_blocking(
+        &self,
+        request: &GenerateRequest,
+    ) -> Result<GenerateStreamBlocking> {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let req = self.guarded_request_blocking(&target, req)?;
+        let stream = self
+            .post_ndjson_stream_blocking(&target, &*req)?
+            .track_partial();
+        Ok(match self.config.post_processor() {
+            Some(processor) => stream.post_process(processor),
+            None => stream,
+        })
     }
 
     fn chat_blocking(&self, request: &ChatRequest) -> Result<ChatResponse> {
-        let url = self.config.url(Endpoints::CHAT);
-        self.post_blocking_with_retry(&url, request)
+        let request = with_defaults(&self.config, request);
+        let request = within_budget(&self.config, request)?;
+        request.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let request = self.guarded_request_blocking(&target, request)?;
+        // The fallback goes through `generate_blocking`, which checks the
+        // reply.
+        #[cfg(feature = "model")]
+        if let Some(response) = self.chat_fallback_blocking(&request)? {
+            return Ok(response);
+        }
+        let response = self.post_blocking_with_retry(&target, &*request)?;
+        let response = post_processed(&self.config, response);
+        self.guarded_response_blocking(&target, response)
     }
 
     fn chat_stream_blocking(&self, request: &ChatRequest) -> Result<ChatStreamBlocking> {
-        let mut req = request.clone();
-        req.stream = Some(true);
-        let url = self.config.url(Endpoints::CHAT);
-        self.post_ndjson_stream_blocking(&url, &req)
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self.guarded_request_blocking(&target, req)?;
+        let stream = self
+            .post_ndjson_stream_blocking(&target, &*req)?
+            .track_partial();
+        Ok(match self.config.post_processor() {
+            Some(processor) => stream.post_process(processor),
+            None => stream,
+        })
+    }
+
+    fn chat_stream_raw_blocking(&self, request: &ChatRequest) -> Result<RawChunkStreamBlocking> {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self.guarded_request_blocking(&target, req)?;
+        self.post_ndjson_raw_blocking(&target, &*req)
     }
 
     #[cfg(feature = "model")]
     fn create_model_blocking(&self, request: &CreateRequest) -> Result<CreateResponse> {
-        let url = self.config.url(Endpoints::CREATE);
-        self.post_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Create);
+        self.post_blocking_with_retry(&target, request)
     }
 
     #[cfg(feature = "model")]
     fn pull_model_blocking(&self, request: &PullRequest) -> Result<PullResponse> {
-        let url = self.config.url(Endpoints::PULL);
-        self.post_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Pull);
+        self.post_blocking_with_retry(&target, request)
+    }
+
+    #[cfg(feature = "model")]
+    fn pull_model_stream_blocking(&self, request: &PullRequest) -> Result<PullStreamBlocking> {
+        let target = self.target(Endpoint::Pull);
+        self.post_ndjson_stream_blocking(&target, &request.streaming())
     }
 
     #[cfg(feature = "model")]
     fn push_model_blocking(&self, request: &PushRequest) -> Result<PushResponse> {
-        let url = self.config.url(Endpoints::PUSH);
-        self.post_blocking_with_retry(&url, request)
+        let target = self.target(Endpoint::Push);
+        self.post_blocking_with_retry(&target, request)
+    }
+
+    #[cfg(feature = "web")]
+    fn web_search_blocking(&self, request: &WebSearchRequest) -> Result<WebSearchResponse> {
+        request.validate()?;
+        let target = self.target(Endpoint::WebSearch);
+        self.post_blocking_with_retry(&target, request)
+    }
+
+    #[cfg(feature = "web")]
+    fn web_fetch_blocking(&self, request: &WebFetchRequest) -> Result<WebFetchResponse> {
+        request.validate()?;
+        let target = self.target(Endpoint::WebFetch);
+        self.post_blocking_with_retry(&target, request)
     }
 }
@@ -0,0 +1,60 @@
+//! `OllamaClient::generate_stream_bytes`
+
+use super::OllamaClient;
+use super::endpoints::Endpoint;
+use super::image_io::image_body;
+use super::request_defaults::streaming_with_defaults;
+use super::streaming::ByteStream;
+use super::token_budget::within_budget;
+use crate::{GenerateRequest, Result};
+
+impl OllamaClient {
+    /// Streamed generation, yielding the response body bytes as received.
+    ///
+    /// For proxies that forward Ollama's NDJSON verbatim: nothing is
+    /// parsed, split into lines, or post-processed, so chunk boundaries
+    /// fall wherever the server's writes did. The request is validated,
+    /// given the client's defaults, and sent with streaming enabled; a
+    /// non-success status fails the call before any bytes are read.
+    ///
+    /// [`ClientConfig::with_max_response_bytes`](super::ClientConfig::with_max_response_bytes),
+    /// JSON limits, and chunk observers do not apply; use
+    /// [`NdjsonReader`](super::NdjsonReader) to frame the bytes if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails validation or the HTTP status
+    /// is not success. Read failures are yielded from the stream as
+    /// [`Error::StreamInterrupted`](crate::Error::StreamInterrupted), with
+    /// an empty `partial`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaClient};
+    /// use std::io::Write;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new("qwen3:0.6b", "Tell me a joke.");
+    /// let stream = client.generate_stream_bytes(&request).await?;
+    /// let mut out = std::io::stdout();
+    /// while let Some(bytes) = stream.next().await {
+    ///     out.write_all(&bytes?).ok();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_stream_bytes(&self, request: &GenerateRequest) -> Result<ByteStream> {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let body = image_body(self, &*req).await?;
+        self.post_bytes_stream(&target, &body).await
+    }
+}
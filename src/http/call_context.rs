@@ -0,0 +1,115 @@
+//! Per-call state behind structured error reports
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
+
+use bytes::Bytes;
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::CallId;
+use super::endpoints::{Endpoint, Target};
+
+/// The `model` field of a request body, if it has one
+#[derive(Deserialize)]
+struct ModelField {
+    model: Option<String>,
+}
+
+/// What one API call has done so far: when it started, how many attempts
+/// it sent, and the body it sent them with.
+///
+/// Attempts and body are recorded through `&self` so a context can be
+/// shared by the retry loop and the helper that reports the outcome.
+#[derive(Debug)]
+pub(crate) struct CallContext {
+    id: CallId,
+    method: Method,
+    url: String,
+    started: Instant,
+    attempts: AtomicU32,
+    payload: OnceLock<Bytes>,
+}
+
+impl CallContext {
+    /// Context for a call to `target` starting now
+    pub(crate) fn new(target: &Target) -> Self {
+        Self {
+            id: target.call,
+            method: target.method.clone(),
+            url: target.as_str().to_string(),
+            started: Instant::now(),
+            attempts: AtomicU32::new(0),
+            payload: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn id(&self) -> CallId {
+        self.id
+    }
+
+    pub(crate) fn method(&self) -> &str {
+        self.method.as_str()
+    }
+
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// API path of the endpoint the call targets, if it is a known one
+    pub(crate) fn endpoint(&self) -> Option<&'static str> {
+        let path = self.url.split(['?', '#']).next().unwrap_or_default();
+        Endpoint::from_path(path).map(Endpoint::path)
+    }
+
+    /// Number of attempts sent so far
+    pub(crate) fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    /// Records that another attempt is being sent.
+    pub(crate) fn add_attempt(&self) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the request body; only the first one is kept.
+    pub(crate) fn set_payload(&self, payload: &Bytes) {
+        let _ = self.payload.set(payload.clone());
+    }
+
+    /// The `model` named in the request body, decoded on demand since it
+    /// is only needed once a call has failed.
+    pub(crate) fn model(&self) -> Option<String> {
+        let payload = self.payload.get()?;
+        serde_json::from_slice::<ModelField>(payload).ok()?.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientConfig;
+
+    #[test]
+    fn test_call_context_records_attempts_and_model() {
+        let target = Target::new(&ClientConfig::default(), Endpoint::Chat);
+        let call = CallContext::new(&target);
+        assert_eq!(call.id(), target.call);
+        assert_eq!(call.endpoint(), Some("/api/chat"));
+        assert_eq!(call.model(), None);
+
+        call.add_attempt();
+        call.add_attempt();
+        call.set_payload(&Bytes::from_static(
+            br#"{"model":"qwen3:0.6b","messages":[]}"#,
+        ));
+        call.set_payload(&Bytes::from_static(br#"{"model":"other"}"#));
+        assert_eq!(call.attempts(), 2);
+        assert_eq!(call.model().as_deref(), Some("qwen3:0.6b"));
+    }
+}
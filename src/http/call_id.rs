@@ -0,0 +1,50 @@
+//! Identifiers telling concurrent calls apart
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Next identifier to hand out, process-wide.
+static NEXT: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies one API call in every [`Observer`](super::Observer) hook.
+///
+/// Each call gets a fresh id, unique within the process, so an observer
+/// that keeps per-call state can key it on the id instead of the URL and
+/// tell concurrent calls to one endpoint apart. The input and output
+/// guardrail notes of a call carry its id too. A chat stream re-issued by
+/// [stream resumption](super::ClientConfig::with_stream_resume) keeps the
+/// id of the stream it continues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallId(u64);
+
+impl CallId {
+    /// A fresh id, never handed out before.
+    pub(crate) fn next() -> Self {
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The id as a number, e.g. for logs
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for CallId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_is_unique() {
+        let a = CallId::next();
+        let b = CallId::next();
+        assert_ne!(a, b);
+        assert!(b > a);
+        assert_eq!(a.to_string(), format!("#{}", a.as_u64()));
+    }
+}
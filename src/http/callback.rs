@@ -0,0 +1,322 @@
+//! Per-token callback helpers for streamed generate and chat
+
+use super::OllamaClient;
+use super::endpoints::Endpoint;
+use super::image_io::image_body;
+use super::request_defaults::streaming_with_defaults;
+use super::token_budget::within_budget;
+use crate::{
+    ChatRequest, ChatResponse, GenerateRequest, GenerateResponse, ResponseMessage, Result,
+};
+
+fn append(target: &mut Option<String>, delta: Option<String>) {
+    if let Some(delta) = delta {
+        target.get_or_insert_with(String::new).push_str(&delta);
+    }
+}
+
+/// Fold a streamed generate chunk into the running total.
+///
+/// Text, thinking, and logprobs accumulate; every other field is taken from
+/// the latest chunk that sets it, so the final metrics come from the `done`
+/// chunk.
+fn merge_generate(total: &mut GenerateResponse, chunk: GenerateResponse) {
+    append(&mut total.response, chunk.response);
+    append(&mut total.thinking, chunk.thinking);
+    if let Some(logprobs) = chunk.logprobs {
+        total.logprobs.get_or_insert_with(Vec::new).extend(logprobs);
+    }
+    total.model = chunk.model.or(total.model.take());
+    total.created_at = chunk.created_at.or(total.created_at.take());
+    total.done = chunk.done.or(total.done);
+    total.done_reason = chunk.done_reason.or(total.done_reason.take());
+    total.total_duration = chunk.total_duration.or(total.total_duration);
+    total.load_duration = chunk.load_duration.or(total.load_duration);
+    total.prompt_eval_count = chunk.prompt_eval_count.or(total.prompt_eval_count);
+    total.prompt_eval_duration = chunk.prompt_eval_duration.or(total.prompt_eval_duration);
+    total.eval_count = chunk.eval_count.or(total.eval_count);
+    total.eval_duration = chunk.eval_duration.or(total.eval_duration);
+    total.context = chunk.context.or(total.context.take());
+}
+
+/// Fold a streamed chat chunk into the running total.
+///
+/// Same rules as [`merge_generate`]; message content, thinking, and tool
+/// calls accumulate.
+fn merge_chat(total: &mut ChatResponse, chunk: ChatResponse) {
+    if let Some(delta) = chunk.message {
+        let message = total.message.get_or_insert_with(ResponseMessage::default);
+        message.role = delta.role.or(message.role.take());
+        append(&mut message.content, delta.content);
+        append(&mut message.thinking, delta.thinking);
+        #[cfg(feature = "tools")]
+        if let Some(calls) = delta.tool_calls {
+            message
+                .tool_calls
+                .get_or_insert_with(Vec::new)
+                .extend(calls);
+        }
+        if let Some(images) = delta.images {
+            message.images.get_or_insert_with(Vec::new).extend(images);
+        }
+    }
+    if let Some(logprobs) = chunk.logprobs {
+        total.logprobs.get_or_insert_with(Vec::new).extend(logprobs);
+    }
+    total.model = chunk.model.or(total.model.take());
+    total.created_at = chunk.created_at.or(total.created_at.take());
+    total.done = chunk.done.or(total.done);
+    total.done_reason = chunk.done_reason.or(total.done_reason.take());
+    total.total_duration = chunk.total_duration.or(total.total_duration);
+    total.load_duration = chunk.load_duration.or(total.load_duration);
+    total.prompt_eval_count = chunk.prompt_eval_count.or(total.prompt_eval_count);
+    total.prompt_eval_duration = chunk.prompt_eval_duration.or(total.prompt_eval_duration);
+    total.eval_count = chunk.eval_count.or(total.eval_count);
+    total.eval_duration = chunk.eval_duration.or(total.eval_duration);
+}
+
+impl OllamaClient {
+    /// Stream a generation, calling `on_token` with each text chunk.
+    ///
+    /// An alternative to driving a stream by hand: the request is sent with
+    /// streaming enabled, `on_token` sees every non-empty `response` delta as
+    /// it arrives, and the chunks are reassembled into one
+    /// [`GenerateResponse`] whose metrics come from the final chunk.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`generate`](super::OllamaApiAsync::generate), plus
+    /// [`Error::StreamInterrupted`](crate::Error::StreamInterrupted) if the
+    /// stream breaks part-way. Errors after some text arrived carry it; see
+    /// [`Error::partial_output`](crate::Error::partial_output).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaClient};
+    /// use std::io::Write;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = GenerateRequest::new("qwen3:0.6b", "Tell me a joke.");
+    /// let response = client
+    ///     .generate_with_callback(&request, |token| {
+    ///         print!("{token}");
+    ///         std::io::stdout().flush().ok();
+    ///     })
+    ///     .await?;
+    /// println!("\n{}", response.metrics());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn generate_with_callback<F>(
+        &self,
+        request: &GenerateRequest,
+        mut on_token: F,
+    ) -> Result<GenerateResponse>
+    where
+        F: FnMut(&str),
+    {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let body = image_body(self, &*req).await?;
+        let stream = self
+            .post_ndjson_stream::<_, GenerateResponse>(&target, &body)
+            .await?
+            .track_partial();
+
+        let mut total = GenerateResponse::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| {
+                e.with_partial_output(total.response.as_deref().unwrap_or_default())
+            })?;
+            if let Some(token) = chunk.text().filter(|t| !t.is_empty()) {
+                on_token(token);
+            }
+            merge_generate(&mut total, chunk);
+        }
+        Ok(total)
+    }
+
+    /// Blocking version of [`generate_with_callback`](Self::generate_with_callback).
+    pub fn generate_with_callback_blocking<F>(
+        &self,
+        request: &GenerateRequest,
+        mut on_token: F,
+    ) -> Result<GenerateResponse>
+    where
+        F: FnMut(&str),
+    {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Generate);
+        let req = self.guarded_request_blocking(&target, req)?;
+        let stream = self
+            .post_ndjson_stream_blocking::<_, GenerateResponse>(&target, &*req)?
+            .track_partial();
+
+        let mut total = GenerateResponse::default();
+        for chunk in stream {
+            let chunk = chunk.map_err(|e| {
+                e.with_partial_output(total.response.as_deref().unwrap_or_default())
+            })?;
+            if let Some(token) = chunk.text().filter(|t| !t.is_empty()) {
+                on_token(token);
+            }
+            merge_generate(&mut total, chunk);
+        }
+        Ok(total)
+    }
+
+    /// Stream a chat turn, calling `on_token` with each content chunk.
+    ///
+    /// Chat counterpart of
+    /// [`generate_with_callback`](Self::generate_with_callback). Thinking
+    /// deltas are not passed to `on_token` but are kept in the returned
+    /// message, as are any tool calls.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hello!")]);
+    /// let mut tokens = 0;
+    /// let response = client
+    ///     .chat_with_callback(&request, |_| tokens += 1)
+    ///     .await?;
+    /// println!("{tokens} chunks: {:?}", response.content());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn chat_with_callback<F>(
+        &self,
+        request: &ChatRequest,
+        mut on_token: F,
+    ) -> Result<ChatResponse>
+    where
+        F: FnMut(&str),
+    {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self
+            .guardrails
+            .check_request(&target, &self.observers, req)
+            .await?;
+        let body = image_body(self, &*req).await?;
+        let stream = self
+            .post_ndjson_stream::<_, ChatResponse>(&target, &body)
+            .await?
+            .track_partial();
+
+        let mut total = ChatResponse::default();
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| e.with_partial_output(total.content().unwrap_or_default()))?;
+            if let Some(token) = chunk.content().filter(|t| !t.is_empty()) {
+                on_token(token);
+            }
+            merge_chat(&mut total, chunk);
+        }
+        Ok(total)
+    }
+
+    /// Blocking version of [`chat_with_callback`](Self::chat_with_callback).
+    pub fn chat_with_callback_blocking<F>(
+        &self,
+        request: &ChatRequest,
+        mut on_token: F,
+    ) -> Result<ChatResponse>
+    where
+        F: FnMut(&str),
+    {
+        let req = streaming_with_defaults(&self.config, request);
+        let req = within_budget(&self.config, req)?;
+        req.validate()?;
+        let target = self.target(Endpoint::Chat);
+        let req = self.guarded_request_blocking(&target, req)?;
+        let stream = self
+            .post_ndjson_stream_blocking::<_, ChatResponse>(&target, &*req)?
+            .track_partial();
+
+        let mut total = ChatResponse::default();
+        for chunk in stream {
+            let chunk =
+                chunk.map_err(|e| e.with_partial_output(total.content().unwrap_or_default()))?;
+            if let Some(token) = chunk.content().filter(|t| !t.is_empty()) {
+                on_token(token);
+            }
+            merge_chat(&mut total, chunk);
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DoneReason;
+
+    #[test]
+    fn test_merge_generate_accumulates_text_and_keeps_final_metrics() {
+        let mut total = GenerateResponse::default();
+        merge_generate(
+            &mut total,
+            GenerateResponse {
+                model: Some("m".to_string()),
+                response: Some("Hel".to_string()),
+                done: Some(false),
+                ..Default::default()
+            },
+        );
+        merge_generate(
+            &mut total,
+            GenerateResponse {
+                response: Some("lo".to_string()),
+                done: Some(true),
+                done_reason: Some(DoneReason::Stop),
+                eval_count: Some(2),
+                context: Some(vec![1, 2]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(total.text(), Some("Hello"));
+        assert_eq!(total.model.as_deref(), Some("m"));
+        assert!(total.is_done());
+        assert_eq!(total.eval_count, Some(2));
+        assert_eq!(total.context, Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_merge_chat_accumulates_content_and_thinking() {
+        let chunk = |content: &str, thinking: Option<&str>| ChatResponse {
+            message: Some(ResponseMessage {
+                role: Some("assistant".to_string()),
+                content: Some(content.to_string()),
+                thinking: thinking.map(str::to_string),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let mut total = ChatResponse::default();
+        merge_chat(&mut total, chunk("", Some("hmm")));
+        merge_chat(&mut total, chunk("Hi", None));
+        merge_chat(&mut total, chunk(" there", None));
+
+        assert_eq!(total.content(), Some("Hi there"));
+        assert_eq!(total.thinking(), Some("hmm"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Cancelling streams from another task
+
+use std::fmt;
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+use crate::CancelReason;
+
+/// Cancels the streams it is attached to with
+/// [`ResponseStream::with_cancel`](super::ResponseStream::with_cancel),
+/// e.g. from a UI's "stop generating" button.
+///
+/// Cheap to clone; every clone controls the same streams, and one token
+/// can cancel any number of them. Once cancelled it stays cancelled, with
+/// the reason it was first given.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{CancelToken, ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Tell a story.")]);
+/// let cancel = CancelToken::new();
+/// let stream = client.chat_stream(&request).await?.with_cancel(&cancel);
+///
+/// tokio::spawn({
+///     let cancel = cancel.clone();
+///     async move {
+///         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+///         cancel.cancel();
+///     }
+/// });
+///
+/// while let Some(event) = stream.next().await {
+///     let chunk = event?;
+///     print!("{}", chunk.content().unwrap_or_default());
+///     if let Some(reason) = chunk.cancel_reason {
+///         eprintln!("\n[cancelled: {reason}, ~{:?} tokens]", chunk.eval_count);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CancelToken(Arc<watch::Sender<Option<CancelReason>>>);
+
+impl CancelToken {
+    /// A token that has not been cancelled
+    pub fn new() -> Self {
+        Self(Arc::new(watch::Sender::new(None)))
+    }
+
+    /// Cancel with [`CancelReason::User`].
+    pub fn cancel(&self) {
+        self.cancel_with(CancelReason::User);
+    }
+
+    /// Whether the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Why the token was cancelled, once it has been
+    pub fn reason(&self) -> Option<CancelReason> {
+        *self.0.borrow()
+    }
+
+    /// A guard that cancels the token with [`CancelReason::Dropped`] when
+    /// dropped, tying the streams to the scope or owner holding it.
+    pub fn drop_guard(&self) -> CancelGuard {
+        CancelGuard(Some(self.clone()))
+    }
+
+    /// Completes with the reason once the token is cancelled.
+    pub async fn cancelled(&self) -> CancelReason {
+        let mut reason = self.0.subscribe();
+        match reason.wait_for(Option::is_some).await {
+            Ok(reason) => reason.unwrap_or(CancelReason::User),
+            // The sender lives in `self`, so it cannot close while we wait.
+            Err(_) => CancelReason::User,
+        }
+    }
+
+    fn cancel_with(&self, reason: CancelReason) {
+        self.0.send_if_modified(|current| {
+            let unset = current.is_none();
+            if unset {
+                *current = Some(reason);
+            }
+            unset
+        });
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("reason", &self.reason())
+            .finish()
+    }
+}
+
+/// Cancels its [`CancelToken`] with [`CancelReason::Dropped`] when dropped,
+/// from [`CancelToken::drop_guard`].
+#[must_use = "the token is cancelled as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct CancelGuard(Option<CancelToken>);
+
+impl CancelGuard {
+    /// Drop the guard without cancelling the token.
+    pub fn disarm(mut self) -> CancelToken {
+        self.0.take().unwrap_or_default()
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel_with(CancelReason::Dropped);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_reason_sticks() {
+        let token = CancelToken::new();
+        assert!(!token.is_cancelled());
+        token.cancel();
+        drop(token.drop_guard());
+        assert_eq!(token.reason(), Some(CancelReason::User));
+    }
+
+    #[test]
+    fn test_guard_cancels_on_drop_unless_disarmed() {
+        let token = CancelToken::new();
+        let _ = token.drop_guard().disarm();
+        assert!(!token.is_cancelled());
+
+        drop(token.drop_guard());
+        assert_eq!(token.reason(), Some(CancelReason::Dropped));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_waits_for_cancel() {
+        let token = CancelToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        token.cancel();
+        assert_eq!(waiter.await.unwrap(), CancelReason::User);
+    }
+}
@@ -0,0 +1,146 @@
+//! Chat through `/api/generate` for models without a chat template
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use super::OllamaClient;
+use crate::{
+    ChatRequest, ChatResponse, GenerateRequest, GenerateResponse, OllamaApiAsync, OllamaApiSync,
+    ResponseMessage, Result, ShowRequest,
+};
+
+/// Whether each model seen so far has a chat template, as reported by
+/// `/api/show`
+#[derive(Debug, Default)]
+pub(super) struct ChatTemplates(Mutex<HashMap<String, bool>>);
+
+impl ChatTemplates {
+    fn get(&self, model: &str) -> Option<bool> {
+        let known = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        known.get(model).copied()
+    }
+
+    fn insert(&self, model: &str, templated: bool) {
+        let mut known = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        known.insert(model.to_string(), templated);
+    }
+}
+
+/// A template is missing when the server sends none or its own default,
+/// which only passes the last prompt through.
+fn has_chat_template(template: Option<&str>) -> bool {
+    template
+        .map(str::trim)
+        .is_some_and(|t| !t.is_empty() && t != "{{ .Prompt }}")
+}
+
+/// The raw generate request standing in for `request`
+fn generate_request(request: &ChatRequest, prompt: String) -> GenerateRequest {
+    let images: Vec<_> = request
+        .messages
+        .iter()
+        .flat_map(|m| m.images.iter().flatten().cloned())
+        .collect();
+    let mut generate = GenerateRequest::new(request.model.clone(), prompt);
+    generate.raw = Some(true);
+    generate.stream = Some(false);
+    generate.images = (!images.is_empty()).then_some(images);
+    generate.format = request.format.clone();
+    generate.options = request.options.clone();
+    generate.think = request.think.clone();
+    generate.keep_alive = request.keep_alive.clone();
+    generate.logprobs = request.logprobs;
+    generate.top_logprobs = request.top_logprobs;
+    generate
+}
+
+/// `response` as the assistant's chat reply
+fn chat_response(response: GenerateResponse) -> ChatResponse {
+    ChatResponse {
+        model: response.model,
+        created_at: response.created_at,
+        message: Some(ResponseMessage {
+            role: Some("assistant".to_string()),
+            content: response.response,
+            thinking: response.thinking,
+            ..ResponseMessage::default()
+        }),
+        done: response.done,
+        done_reason: response.done_reason,
+        total_duration: response.total_duration,
+        load_duration: response.load_duration,
+        prompt_eval_count: response.prompt_eval_count,
+        prompt_eval_duration: response.prompt_eval_duration,
+        eval_count: response.eval_count,
+        eval_duration: response.eval_duration,
+        logprobs: response.logprobs,
+        cancel_reason: response.cancel_reason,
+        counts_estimated: response.counts_estimated,
+    }
+}
+
+impl OllamaClient {
+    /// Answers `request` through `/api/generate` if a chat fallback is
+    /// configured and the model has no chat template; `None` otherwise.
+    pub(super) async fn chat_fallback(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Option<ChatResponse>> {
+        let Some(template) = self.config.chat_fallback() else {
+            return Ok(None);
+        };
+        let templated = match self.chat_templates.get(&request.model) {
+            Some(templated) => templated,
+            None => {
+                let show = self.show_model(&ShowRequest::new(&request.model)).await?;
+                let templated = has_chat_template(show.template.as_deref());
+                self.chat_templates.insert(&request.model, templated);
+                templated
+            }
+        };
+        if templated {
+            return Ok(None);
+        }
+        let prompt = template.render(&request.messages)?;
+        let response = self.generate(&generate_request(request, prompt)).await?;
+        Ok(Some(chat_response(response)))
+    }
+
+    /// Blocking counterpart of [`chat_fallback`](Self::chat_fallback).
+    pub(super) fn chat_fallback_blocking(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<Option<ChatResponse>> {
+        let Some(template) = self.config.chat_fallback() else {
+            return Ok(None);
+        };
+        let templated = match self.chat_templates.get(&request.model) {
+            Some(templated) => templated,
+            None => {
+                let show = self.show_model_blocking(&ShowRequest::new(&request.model))?;
+                let templated = has_chat_template(show.template.as_deref());
+                self.chat_templates.insert(&request.model, templated);
+                templated
+            }
+        };
+        if templated {
+            return Ok(None);
+        }
+        let prompt = template.render(&request.messages)?;
+        let response = self.generate_blocking(&generate_request(request, prompt))?;
+        Ok(Some(chat_response(response)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_template_is_not_a_chat_template() {
+        assert!(!has_chat_template(None));
+        assert!(!has_chat_template(Some("")));
+        assert!(!has_chat_template(Some("{{ .Prompt }}\n")));
+        assert!(has_chat_template(Some("[INST] {{ .Prompt }} [/INST]")));
+    }
+}
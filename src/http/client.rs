@@ -1,31 +1,84 @@
 //! Ollama HTTP client implementation
 
 use crate::{Error, Result};
-use reqwest::Client;
-use std::sync::Arc;
+use bytes::{BufMut, Bytes, BytesMut};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, RETRY_AFTER};
+use reqwest::{Client, Method, StatusCode};
+use std::io::Read;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
-use super::ClientConfig;
-use super::streaming::ChatStreamBlocking;
+use super::RawChunk;
+use super::call_context::CallContext;
+#[cfg(feature = "model")]
+use super::chat_fallback::ChatTemplates;
+use super::endpoints::{Endpoint, Target, TimeoutClass};
+use super::gateway_profile::{GatewayProfile, is_event_stream, sse_data};
+use super::guardrail::GuardrailSet;
+use super::idempotency_key;
+use super::lifecycle::{InFlight, Lifecycle};
+use super::observer::{Observer, ObserverSet};
+use super::scheduler::{Permit, Scheduler};
+use super::singleflight::Singleflight;
+use super::stream_state::StreamState;
+use super::streaming::{
+    ByteStream, LineParser, RawChunkStream, RawChunkStreamBlocking, ResponseStream,
+    ResponseStreamBlocking, parse_json_line, parse_raw_line,
+};
+use super::transport::Transport;
+use super::{ClientConfig, Extensions, Guardrail, NdjsonReader, Priority};
 
-/// Per-request timeout for NDJSON streaming bodies (long-running generations).
+/// Minimum total timeout for NDJSON streaming bodies (long-running generations).
 const STREAMING_TIMEOUT: Duration = Duration::from_secs(300);
 
-/// Trims leading and trailing ASCII whitespace from a byte slice.
-fn trim_bytes(mut s: &[u8]) -> &[u8] {
-    while s.first().is_some_and(|b| b.is_ascii_whitespace()) {
-        s = &s[1..];
-    }
-    while s.last().is_some_and(|b| b.is_ascii_whitespace()) {
-        s = &s[..s.len() - 1];
+/// Longest `Retry-After` the retry loop will honour before giving up early.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// A body the async request helpers can send.
+///
+/// Every [`Serialize`](serde::Serialize) type is one, encoded in place by
+/// [`OllamaClient::encode_body`]; requests reading image files are encoded
+/// off the runtime first, see [`image_body`](super::image_io::image_body).
+pub(crate) trait RequestBody {
+    /// The JSON bytes to send, enforcing `max_request_bytes`.
+    fn encode(&self, client: &OllamaClient) -> Result<Bytes>;
+}
+
+impl<T: serde::Serialize + ?Sized> RequestBody for T {
+    fn encode(&self, client: &OllamaClient) -> Result<Bytes> {
+        client.encode_body(self)
     }
-    s
+}
+
+/// Attaches a pre-serialized JSON body to an async request.
+///
+/// Cloning [`Bytes`] only bumps a reference count, so retries share one
+/// encoded body.
+fn json_request(builder: reqwest::RequestBuilder, payload: &Bytes) -> reqwest::RequestBuilder {
+    builder
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload.clone())
+}
+
+/// Attaches a pre-serialized JSON body to a blocking request.
+fn json_request_blocking(
+    builder: reqwest::blocking::RequestBuilder,
+    payload: &Bytes,
+) -> reqwest::blocking::RequestBuilder {
+    builder
+        .header(CONTENT_TYPE, "application/json")
+        .body(payload.clone())
 }
 
 /// HTTP client for Ollama API
 ///
 /// This client is cloneable and can be safely shared across threads.
-/// The internal HTTP client is wrapped in Arc for efficient cloning.
+/// The internal HTTP client and configuration are wrapped in Arc for efficient cloning;
+/// clones share one connection pool.
+///
+/// Construction is cheap: the underlying HTTP client, with its TLS setup,
+/// is built on the first request, so short-lived clients that are never
+/// used cost almost nothing.
 ///
 /// # Thread Safety
 ///
@@ -54,8 +107,17 @@ fn trim_bytes(mut s: &[u8]) -> &[u8] {
 /// ```
 #[derive(Clone, Debug)]
 pub struct OllamaClient {
-    pub(super) config: ClientConfig,
-    pub(super) client: Arc<Client>,
+    pub(crate) config: Arc<ClientConfig>,
+    transport: Arc<Transport>,
+    pub(super) lifecycle: Arc<Lifecycle>,
+    pub(super) observers: ObserverSet,
+    pub(super) guardrails: GuardrailSet,
+    flights: Arc<Singleflight>,
+    scheduler: Option<Arc<Scheduler>>,
+    priority: Option<Priority>,
+    #[cfg(feature = "model")]
+    pub(super) chat_templates: Arc<ChatTemplates>,
+    gateway: Arc<OnceLock<GatewayProfile>>,
 }
 
 impl OllamaClient {
@@ -70,7 +132,10 @@ impl OllamaClient {
     /// Returns an error if:
     /// - The base URL is invalid or malformed
     /// - The URL scheme is not http or https
-    /// - The HTTP client cannot be built
+    /// - The API key is not a valid header value
+    ///
+    /// The HTTP client itself is built on first use; a failure there is
+    /// returned by that request.
     ///
     /// # Examples
     ///
@@ -88,11 +153,26 @@ impl OllamaClient {
     /// # Ok::<(), ollama_oxide::Error>(())
     /// ```
     pub fn new(config: ClientConfig) -> Result<Self> {
-        let client = Client::builder().timeout(config.timeout()).build()?;
-
+        let transport = Transport::new(&config)?;
+        let scheduler = config
+            .max_concurrency()
+            .map(|limit| Arc::new(Scheduler::new(limit)));
+        let gateway = config
+            .known_gateway()
+            .map(OnceLock::from)
+            .unwrap_or_default();
         Ok(Self {
-            config,
-            client: Arc::new(client),
+            config: Arc::new(config),
+            transport: Arc::new(transport),
+            lifecycle: Arc::default(),
+            observers: ObserverSet::default(),
+            guardrails: GuardrailSet::default(),
+            flights: Arc::default(),
+            scheduler,
+            priority: None,
+            #[cfg(feature = "model")]
+            chat_templates: Arc::default(),
+            gateway: Arc::new(gateway),
         })
     }
 
@@ -169,47 +249,384 @@ impl OllamaClient {
         Self::new(ClientConfig::default())
     }
 
-    /// Execute async HTTP GET request with retry logic
+    /// The shared async HTTP client, built on first use.
+    pub(super) fn http(&self) -> Result<Client> {
+        self.transport.client(&self.config)
+    }
+
+    /// The shared async HTTP client for other hosts, such as job webhooks,
+    /// built on first use. It never sends the API key.
+    #[cfg(feature = "conveniences")]
+    pub(crate) fn http_external(&self) -> Result<Client> {
+        self.transport.external(&self.config)
+    }
+
+    /// The shared blocking HTTP client, built on first use. Requests must
+    /// set their own timeout.
+    fn http_blocking(&self) -> Result<reqwest::blocking::Client> {
+        self.transport.blocking(&self.config)
+    }
+
+    /// Stop accepting requests, let in-flight ones finish, and release the
+    /// connection pools.
     ///
-    /// This helper handles exponential backoff and automatic retries for:
-    /// - Network errors
-    /// - Server errors (5xx status codes)
+    /// New requests on this client and all its clones fail with
+    /// [`Error::ClientShutdown`] from the moment this is called. In-flight
+    /// calls and open streams get up to `grace` to finish; after that,
+    /// async calls end with `Error::ClientShutdown` at once and blocking
+    /// streams do so at their next line. A blocking call that is still
+    /// waiting on the server is not interrupted. Calling it again only
+    /// repeats the wait.
     ///
-    /// # Type Parameters
+    /// Returns the number of calls that were still in flight when the grace
+    /// period ran out, `0` if everything drained in time.
     ///
-    /// * `T` - Response type that implements `DeserializeOwned`
+    /// # Examples
     ///
-    /// # Arguments
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    /// use std::time::Duration;
     ///
-    /// * `url` - Full URL to request
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// // ... serve requests ...
+    /// let cancelled = client.shutdown(Duration::from_secs(10)).await;
+    /// if cancelled > 0 {
+    ///     eprintln!("cancelled {cancelled} requests");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&self, grace: Duration) -> usize {
+        let cancelled = self.lifecycle.shutdown(grace).await;
+        if let Some(blocking) = self.transport.release() {
+            // Dropping it joins its runtime thread, which must not happen here
+            let _ = tokio::task::spawn_blocking(move || drop(blocking)).await;
+        }
+        cancelled
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) was called on this client or a
+    /// clone
+    pub fn is_shut_down(&self) -> bool {
+        self.lifecycle.is_closed()
+    }
+
+    /// The gateway this client talks to Ollama through.
     ///
-    /// # Errors
+    /// The configured one, or else the one detected from the base URL or
+    /// the first response that gives it away; [`GatewayProfile::Direct`]
+    /// until then. Clones share what was detected.
     ///
-    /// Returns an error if:
-    /// - Maximum retry attempts exceeded
-    /// - Response cannot be deserialized
-    /// - Client errors (4xx) occur (no retry)
-    pub(super) async fn get_with_retry<T>(&self, url: &str) -> Result<T>
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{GatewayProfile, OllamaClient};
+    ///
+    /// let client = OllamaClient::with_base_url("http://localhost:3000/ollama")?;
+    /// assert_eq!(client.gateway(), GatewayProfile::OpenWebUi);
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn gateway(&self) -> GatewayProfile {
+        self.gateway.get().copied().unwrap_or_default()
+    }
+
+    /// Records the gateway `headers` reveal, if none is known yet.
+    fn detect_gateway(&self, headers: &HeaderMap) {
+        if self.gateway.get().is_none()
+            && let Some(gateway) = GatewayProfile::from_headers(headers)
+        {
+            let _ = self.gateway.set(gateway);
+        }
+    }
+
+    /// Whether a stream with response `headers` arrives as server-sent
+    /// events.
+    fn frames_sse(&self, headers: &HeaderMap) -> bool {
+        self.gateway().frames_sse() || is_event_stream(headers)
+    }
+
+    /// Runs one async call as in flight, ending it with
+    /// [`Error::ClientShutdown`] if shutdown cancels it first.
+    async fn in_flight<T>(&self, call: impl Future<Output = Result<T>>) -> Result<T> {
+        let in_flight = self.lifecycle.enter()?;
+        tokio::select! {
+            result = call => result,
+            () = in_flight.cancelled() => Err(Error::ClientShutdown),
+        }
+    }
+
+    /// Runs one blocking call as in flight.
+    fn in_flight_blocking<T>(&self, call: impl FnOnce() -> Result<T>) -> Result<T> {
+        let _in_flight = self.lifecycle.enter()?;
+        call()
+    }
+
+    /// Total timeout for a request of the given class: the configured
+    /// timeout, raised to at least [`STREAMING_TIMEOUT`] for
+    /// [`TimeoutClass::Long`].
+    fn timeout(&self, class: TimeoutClass) -> Duration {
+        match class {
+            TimeoutClass::Standard => self.config.timeout(),
+            TimeoutClass::Long => self.config.timeout().max(STREAMING_TIMEOUT),
+        }
+    }
+
+    /// A fresh idempotency key for one call to `target`, with its header
+    /// name, if keys are enabled and the call is not a `GET`.
+    fn idempotency_key<'a>(&'a self, target: &Target) -> Option<(&'a str, String)> {
+        let header = self.config.idempotency_header()?;
+        if target.method == Method::GET {
+            return None;
+        }
+        let key = idempotency_key::new_key();
+        self.observers.idempotency_key(target, &key);
+        Some((header, key))
+    }
+
+    /// Priority of `call` to `target`: the client's, else that of the
+    /// model named in `payload`, else the endpoint's.
+    fn priority_of(
+        &self,
+        call: &CallContext,
+        target: &Target,
+        payload: Option<&Bytes>,
+    ) -> Priority {
+        if let Some(priority) = self.priority {
+            return priority;
+        }
+        if let Some(payload) = payload
+            && self.config.has_model_priorities()
+        {
+            call.set_payload(payload);
+            if let Some(priority) = call.model().and_then(|m| self.config.model_priority(&m)) {
+                return priority;
+            }
+        }
+        target.priority
+    }
+
+    /// Waits for a concurrency slot for `call`, if concurrency is limited.
+    async fn slot(
+        &self,
+        call: &CallContext,
+        target: &Target,
+        payload: Option<&Bytes>,
+    ) -> Option<Permit> {
+        let scheduler = self.scheduler.as_ref()?;
+        let priority = self.priority_of(call, target, payload);
+        Some(scheduler.acquire(priority).await)
+    }
+
+    /// Blocking counterpart of [`slot`](Self::slot).
+    fn slot_blocking(
+        &self,
+        call: &CallContext,
+        target: &Target,
+        payload: Option<&Bytes>,
+    ) -> Option<Permit> {
+        let scheduler = self.scheduler.as_ref()?;
+        Some(scheduler.acquire_blocking(self.priority_of(call, target, payload)))
+    }
+
+    /// Resolve `endpoint` against the configured base URL.
+    pub(crate) fn target(&self, endpoint: Endpoint) -> Target {
+        Target::new(&self.config, endpoint)
+    }
+
+    /// Serialize a request body, enforcing `max_request_bytes`.
+    ///
+    /// Bodies are encoded once up front so retries reuse the same bytes.
+    /// Each call encodes into its own buffer, grown to fit that body as it
+    /// is written, so concurrent calls never wait on one another and small
+    /// requests stay small.
+    pub(super) fn encode_body<R>(&self, body: &R) -> Result<Bytes>
+    where
+        R: serde::Serialize + ?Sized,
+    {
+        let mut buffer = BytesMut::new();
+        serde_json::to_writer((&mut buffer).writer(), body)?;
+        let payload = buffer.freeze();
+        if let Some(limit) = self.config.max_request_bytes()
+            && payload.len() > limit
+        {
+            return Err(Error::RequestTooLarge {
+                size: payload.len(),
+                limit,
+            });
+        }
+        Ok(payload)
+    }
+
+    /// Read a JSON response body, enforcing `max_response_bytes` and the
+    /// JSON guards.
+    ///
+    /// Stops reading as soon as the limit is crossed, so an oversized body is
+    /// never fully buffered.
+    async fn read_body(
+        &self,
+        call: &CallContext,
+        mut response: reqwest::Response,
+    ) -> Result<Bytes> {
+        let Some(limit) = self.config.max_response_bytes() else {
+            let bytes = response.bytes().await?;
+            self.observers.response_body(call, &bytes);
+            self.config.json_limits().check(&bytes)?;
+            return Ok(bytes);
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(Error::ResponseTooLarge { limit });
+        }
+
+        let mut bytes = BytesMut::new();
+        while let Some(chunk) = response.chunk().await? {
+            if bytes.len() + chunk.len() > limit {
+                return Err(Error::ResponseTooLarge { limit });
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        self.observers.response_body(call, &bytes);
+        self.config.json_limits().check(&bytes)?;
+        Ok(bytes.freeze())
+    }
+
+    /// Runs `call`, which fetches the response body for `target`, unless an
+    /// identical call is already in flight and coalescing is enabled, in
+    /// which case that call's outcome is shared.
+    async fn coalesce(
+        &self,
+        target: &Target,
+        payload: Option<&Bytes>,
+        call: impl Future<Output = Result<Bytes>>,
+    ) -> Result<Bytes> {
+        if !self.config.coalesces_requests() || !Singleflight::can_coalesce(target, payload) {
+            return call.await;
+        }
+        let (outcome, shared) = self.flights.run(target, payload, call).await;
+        if shared {
+            self.observers.coalesced(target);
+        }
+        outcome
+    }
+
+    /// Blocking counterpart of [`read_body`](Self::read_body), also
+    /// deserializing the body.
+    pub(super) fn read_json_blocking<T>(
+        &self,
+        call: &CallContext,
+        response: reqwest::blocking::Response,
+    ) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        let Some(limit) = self.config.max_response_bytes() else {
+            let bytes = response.bytes()?;
+            self.observers.response_body(call, &bytes);
+            self.config.json_limits().check(&bytes)?;
+            return Ok(serde_json::from_slice(&bytes)?);
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(Error::ResponseTooLarge { limit });
+        }
+
+        let mut bytes: Vec<u8> = Vec::new();
+        response
+            .take(limit as u64 + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::HttpError(e.to_string()))?;
+        if bytes.len() > limit {
+            return Err(Error::ResponseTooLarge { limit });
+        }
+        self.observers.response_body(call, &bytes);
+        self.config.json_limits().check(&bytes)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Send a request with retry logic and return the final response.
+    ///
+    /// This is the single retry loop behind every non-streaming helper. It
+    /// handles exponential backoff and automatic retries for:
+    /// - Network errors
+    /// - Server errors (5xx status codes)
+    /// - Rate limiting (429), waiting for the server's `Retry-After` when given
+    ///
+    /// Targets that are not idempotent are only retried on connection
+    /// failures and 429, where the server cannot have acted on the request;
+    /// other failures are returned at once.
+    ///
+    /// The response is returned as-is once retries are exhausted or the
+    /// status is not a server error; callers decide how to treat it.
+    /// Reports `on_request_start`, `on_retry`, and `on_complete` to observers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MaxRetriesExceededError`] if every attempt failed
+    /// with a network error, or [`Error::RateLimited`] if the last attempt
+    /// was still answered with 429.
+    pub(super) async fn send_with_retry(
+        &self,
+        call: &CallContext,
+        target: &Target,
+        payload: Option<&Bytes>,
+    ) -> Result<reqwest::Response> {
+        let started = call.started();
+        let client = self.http()?;
+        self.observers.request_start(call);
+        if let Some(payload) = payload {
+            call.set_payload(payload);
+            self.observers.request_body(call, payload);
+        }
+        let idempotency = self.idempotency_key(target);
+
         for attempt in 0..=self.config.max_retries() {
-            match self.client.get(url).send().await {
+            let mut request = client
+                .request(target.method.clone(), target.url.clone())
+                .timeout(self.timeout(target.timeout));
+            if let Some(payload) = payload {
+                request = json_request(request, payload);
+            }
+            if let Some((header, key)) = &idempotency {
+                request = request.header(*header, key);
+            }
+            call.add_attempt();
+
+            match request.send().await {
                 Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
+                    // Retry on server errors (5xx) and rate limiting (429)
+                    if is_retryable(response.status())
+                        && (target.idempotent || response.status() == StatusCode::TOO_MANY_REQUESTS)
+                        && attempt < self.config.max_retries()
+                    {
+                        self.observers
+                            .retry(call, attempt + 1, response.status().as_str());
+                        tokio::time::sleep(status_delay(attempt, response.headers())).await;
                         continue;
                     }
 
-                    // Deserialize and return
-                    let result = response.json::<T>().await?;
-                    return Ok(result);
+                    self.observers
+                        .complete(call, response.status().as_u16(), started.elapsed());
+                    self.detect_gateway(response.headers());
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                        return Err(status_error(response.status(), response.headers()));
+                    }
+                    return Ok(response);
                 }
-                Err(_e) => {
+                Err(e) => {
                     // Retry on network errors
+                    if !target.idempotent && !e.is_connect() {
+                        return Err(e.into());
+                    }
                     if attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
+                        self.observers.retry(call, attempt + 1, &e.to_string());
+                        tokio::time::sleep(retry_delay(attempt)).await;
                     }
                 }
             }
@@ -218,52 +635,63 @@ impl OllamaClient {
         Err(Error::MaxRetriesExceededError(self.config.max_retries()))
     }
 
-    /// Execute blocking HTTP GET request with retry logic
-    ///
-    /// This helper handles exponential backoff and automatic retries for:
-    /// - Network errors
-    /// - Server errors (5xx status codes)
-    ///
-    /// # Type Parameters
-    ///
-    /// * `T` - Response type that implements `DeserializeOwned`
-    ///
-    /// # Arguments
-    ///
-    /// * `url` - Full URL to request
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - Maximum retry attempts exceeded
-    /// - Response cannot be deserialized
-    /// - Client errors (4xx) occur (no retry)
-    pub(super) fn get_blocking_with_retry<T>(&self, url: &str) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        // Create blocking client
-        let blocking_client = reqwest::blocking::Client::builder()
-            .timeout(self.config.timeout())
-            .build()?;
+    /// Blocking counterpart of [`send_with_retry`](Self::send_with_retry).
+    pub(super) fn send_blocking_with_retry(
+        &self,
+        call: &CallContext,
+        target: &Target,
+        payload: Option<&Bytes>,
+    ) -> Result<reqwest::blocking::Response> {
+        let started = call.started();
+        let blocking_client = self.http_blocking()?;
+        self.observers.request_start(call);
+        if let Some(payload) = payload {
+            call.set_payload(payload);
+            self.observers.request_body(call, payload);
+        }
+        let idempotency = self.idempotency_key(target);
 
         for attempt in 0..=self.config.max_retries() {
-            match blocking_client.get(url).send() {
+            let mut request = blocking_client
+                .request(target.method.clone(), target.url.clone())
+                .timeout(self.timeout(target.timeout));
+            if let Some(payload) = payload {
+                request = json_request_blocking(request, payload);
+            }
+            if let Some((header, key)) = &idempotency {
+                request = request.header(*header, key);
+            }
+            call.add_attempt();
+
+            match request.send() {
                 Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
+                    // Retry on server errors (5xx) and rate limiting (429)
+                    if is_retryable(response.status())
+                        && (target.idempotent || response.status() == StatusCode::TOO_MANY_REQUESTS)
+                        && attempt < self.config.max_retries()
+                    {
+                        self.observers
+                            .retry(call, attempt + 1, response.status().as_str());
+                        std::thread::sleep(status_delay(attempt, response.headers()));
                         continue;
                     }
 
-                    // Deserialize and return
-                    let result = response.json::<T>()?;
-                    return Ok(result);
+                    self.observers
+                        .complete(call, response.status().as_u16(), started.elapsed());
+                    self.detect_gateway(response.headers());
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                        return Err(status_error(response.status(), response.headers()));
+                    }
+                    return Ok(response);
                 }
-                Err(_e) => {
+                Err(e) => {
                     // Retry on network errors
+                    if !target.idempotent && !e.is_connect() {
+                        return Err(e.into());
+                    }
                     if attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
+                        self.observers.retry(call, attempt + 1, &e.to_string());
+                        std::thread::sleep(retry_delay(attempt));
                     }
                 }
             }
@@ -272,19 +700,15 @@ impl OllamaClient {
         Err(Error::MaxRetriesExceededError(self.config.max_retries()))
     }
 
-    /// Execute async HTTP POST request with retry logic (with JSON response)
-    ///
-    /// For endpoints that accept a request body and return a JSON response.
+    /// Execute async HTTP GET request with retry logic
     ///
     /// # Type Parameters
     ///
-    /// * `R` - Request type that implements `Serialize`
     /// * `T` - Response type that implements `DeserializeOwned`
     ///
     /// # Arguments
     ///
-    /// * `url` - Full URL to request
-    /// * `body` - Request body to serialize as JSON
+    /// * `target` - Resolved request target
     ///
     /// # Errors
     ///
@@ -292,54 +716,34 @@ impl OllamaClient {
     /// - Maximum retry attempts exceeded
     /// - Response cannot be deserialized
     /// - Client errors (4xx) occur (no retry)
-    pub(super) async fn post_with_retry<R, T>(&self, url: &str, body: &R) -> Result<T>
+    pub(crate) async fn get_with_retry<T>(&self, target: &Target) -> Result<T>
     where
-        R: serde::Serialize,
         T: serde::de::DeserializeOwned,
     {
-        for attempt in 0..=self.config.max_retries() {
-            match self.client.post(url).json(body).send().await {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
-                        continue;
-                    }
-
-                    // Check for client errors (no retry)
-                    if response.status().is_client_error() {
-                        return Err(Error::HttpStatusError(response.status().as_u16()));
-                    }
-
-                    // Deserialize and return
-                    let result = response.json::<T>().await?;
-                    return Ok(result);
-                }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
-                    }
-                }
-            }
-        }
-
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+        let call = CallContext::new(target);
+        let result = self
+            .in_flight(async {
+                let fetch = async {
+                    let _slot = self.slot(&call, target, None).await;
+                    let response = self.send_with_retry(&call, target, None).await?;
+                    self.read_body(&call, response).await
+                };
+                let bytes = self.coalesce(target, None, fetch).await?;
+                Ok(serde_json::from_slice::<T>(&bytes)?)
+            })
+            .await;
+        self.observers.observe(&call, result)
     }
 
-    /// Execute blocking HTTP POST request with retry logic (with JSON response)
-    ///
-    /// For endpoints that accept a request body and return a JSON response.
+    /// Execute blocking HTTP GET request with retry logic
     ///
     /// # Type Parameters
     ///
-    /// * `R` - Request type that implements `Serialize`
     /// * `T` - Response type that implements `DeserializeOwned`
     ///
     /// # Arguments
     ///
-    /// * `url` - Full URL to request
-    /// * `body` - Request body to serialize as JSON
+    /// * `target` - Resolved request target
     ///
     /// # Errors
     ///
@@ -347,156 +751,111 @@ impl OllamaClient {
     /// - Maximum retry attempts exceeded
     /// - Response cannot be deserialized
     /// - Client errors (4xx) occur (no retry)
-    pub(super) fn post_blocking_with_retry<R, T>(&self, url: &str, body: &R) -> Result<T>
+    pub(crate) fn get_blocking_with_retry<T>(&self, target: &Target) -> Result<T>
     where
-        R: serde::Serialize,
         T: serde::de::DeserializeOwned,
     {
-        let blocking_client = reqwest::blocking::Client::builder()
-            .timeout(self.config.timeout())
-            .build()?;
-
-        for attempt in 0..=self.config.max_retries() {
-            match blocking_client.post(url).json(body).send() {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                        continue;
-                    }
-
-                    // Check for client errors (no retry)
-                    if response.status().is_client_error() {
-                        return Err(Error::HttpStatusError(response.status().as_u16()));
-                    }
-
-                    // Deserialize and return
-                    let result = response.json::<T>()?;
-                    return Ok(result);
-                }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                    }
-                }
-            }
-        }
-
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+        let call = CallContext::new(target);
+        let result = self.in_flight_blocking(|| {
+            let _slot = self.slot_blocking(&call, target, None);
+            let response = self.send_blocking_with_retry(&call, target, None)?;
+            self.read_json_blocking::<T>(&call, response)
+        });
+        self.observers.observe(&call, result)
     }
 
-    /// Execute async HTTP POST request with retry logic (no response body)
+    /// Execute async HTTP request with a JSON body and retry logic (with JSON response)
     ///
-    /// For endpoints that return 200 OK with empty body.
+    /// For endpoints that accept a request body and return a JSON response.
     ///
     /// # Type Parameters
     ///
     /// * `R` - Request type that implements `Serialize`
+    /// * `T` - Response type that implements `DeserializeOwned`
     ///
     /// # Arguments
     ///
-    /// * `url` - Full URL to request
+    /// * `target` - Resolved request target
     /// * `body` - Request body to serialize as JSON
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Maximum retry attempts exceeded
+    /// - Response cannot be deserialized
     /// - Client errors (4xx) occur (no retry)
-    #[cfg(feature = "model")]
-    pub(super) async fn post_empty_with_retry<R>(&self, url: &str, body: &R) -> Result<()>
+    pub(crate) async fn post_with_retry<R, T>(&self, target: &Target, body: &R) -> Result<T>
     where
-        R: serde::Serialize,
+        R: RequestBody + ?Sized,
+        T: serde::de::DeserializeOwned,
     {
-        for attempt in 0..=self.config.max_retries() {
-            match self.client.post(url).json(body).send().await {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
-                        continue;
-                    }
+        let call = CallContext::new(target);
+        let result = self
+            .in_flight(async {
+                let payload = body.encode(self)?;
+                let fetch = async {
+                    let _slot = self.slot(&call, target, Some(&payload)).await;
+                    let response = self.send_with_retry(&call, target, Some(&payload)).await?;
 
-                    // Check for success status
-                    if response.status().is_success() {
-                        return Ok(());
-                    }
-
-                    // Client error - no retry
-                    return Err(Error::HttpStatusError(response.status().as_u16()));
-                }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
+                    // Check for client errors (no retry)
+                    if response.status().is_client_error() {
+                        return Err(Error::HttpStatusError(response.status().as_u16()));
                     }
-                }
-            }
-        }
 
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+                    self.read_body(&call, response).await
+                };
+                let bytes = self.coalesce(target, Some(&payload), fetch).await?;
+                Ok(serde_json::from_slice::<T>(&bytes)?)
+            })
+            .await;
+        self.observers.observe(&call, result)
     }
 
-    /// Execute blocking HTTP POST request with retry logic (no response body)
+    /// Execute blocking HTTP request with a JSON body and retry logic (with JSON response)
     ///
-    /// For endpoints that return 200 OK with empty body.
+    /// For endpoints that accept a request body and return a JSON response.
     ///
     /// # Type Parameters
     ///
     /// * `R` - Request type that implements `Serialize`
+    /// * `T` - Response type that implements `DeserializeOwned`
     ///
     /// # Arguments
     ///
-    /// * `url` - Full URL to request
+    /// * `target` - Resolved request target
     /// * `body` - Request body to serialize as JSON
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Maximum retry attempts exceeded
+    /// - Response cannot be deserialized
     /// - Client errors (4xx) occur (no retry)
-    #[cfg(feature = "model")]
-    pub(super) fn post_empty_blocking_with_retry<R>(&self, url: &str, body: &R) -> Result<()>
+    pub(crate) fn post_blocking_with_retry<R, T>(&self, target: &Target, body: &R) -> Result<T>
     where
         R: serde::Serialize,
+        T: serde::de::DeserializeOwned,
     {
-        let blocking_client = reqwest::blocking::Client::builder()
-            .timeout(self.config.timeout())
-            .build()?;
+        let call = CallContext::new(target);
+        let result = self.in_flight_blocking(|| {
+            let payload = self.encode_body(body)?;
+            let _slot = self.slot_blocking(&call, target, Some(&payload));
+            let response = self.send_blocking_with_retry(&call, target, Some(&payload))?;
 
-        for attempt in 0..=self.config.max_retries() {
-            match blocking_client.post(url).json(body).send() {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                        continue;
-                    }
-
-                    // Check for success status
-                    if response.status().is_success() {
-                        return Ok(());
-                    }
-
-                    // Client error - no retry
-                    return Err(Error::HttpStatusError(response.status().as_u16()));
-                }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                    }
-                }
+            // Check for client errors (no retry)
+            if response.status().is_client_error() {
+                return Err(Error::HttpStatusError(response.status().as_u16()));
             }
-        }
 
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+            self.read_json_blocking::<T>(&call, response)
+        });
+        self.observers.observe(&call, result)
     }
 
-    /// Execute async HTTP DELETE request with retry logic (no response body)
+    /// Execute async HTTP request with a JSON body and retry logic (no response body)
     ///
-    /// For endpoints that return 200 OK with empty body.
+    /// For endpoints that return 200 OK with empty body, such as copy (POST)
+    /// and delete (DELETE).
     ///
     /// # Type Parameters
     ///
@@ -504,7 +863,7 @@ impl OllamaClient {
     ///
     /// # Arguments
     ///
-    /// * `url` - Full URL to request
+    /// * `target` - Resolved request target
     /// * `body` - Request body to serialize as JSON
     ///
     /// # Errors
@@ -513,51 +872,29 @@ impl OllamaClient {
     /// - Maximum retry attempts exceeded
     /// - Client errors (4xx) occur (no retry)
     #[cfg(feature = "model")]
-    pub(super) async fn delete_empty_with_retry<R>(&self, url: &str, body: &R) -> Result<()>
+    pub(super) async fn send_empty_with_retry<R>(&self, target: &Target, body: &R) -> Result<()>
     where
         R: serde::Serialize,
     {
-        for attempt in 0..=self.config.max_retries() {
-            match self.client.delete(url).json(body).send().await {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
-                        continue;
-                    }
-
-                    // Check for success status
-                    if response.status().is_success() {
-                        return Ok(());
-                    }
-
-                    // Client error - no retry
-                    return Err(Error::HttpStatusError(response.status().as_u16()));
+        let call = CallContext::new(target);
+        let result = self
+            .in_flight(async {
+                let payload = self.encode_body(body)?;
+                let _slot = self.slot(&call, target, Some(&payload)).await;
+                let response = self.send_with_retry(&call, target, Some(&payload)).await?;
+                if response.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(Error::HttpStatusError(response.status().as_u16()))
                 }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        tokio::time::sleep(Duration::from_millis(100 * (attempt as u64 + 1))).await;
-                    }
-                }
-            }
-        }
-
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+            })
+            .await;
+        self.observers.observe(&call, result)
     }
 
-    /// Execute blocking HTTP DELETE request with retry logic (no response body)
+    /// Execute blocking HTTP request with a JSON body and retry logic (no response body)
     ///
-    /// For endpoints that return 200 OK with empty body.
-    ///
-    /// # Type Parameters
-    ///
-    /// * `R` - Request type that implements `Serialize`
-    ///
-    /// # Arguments
-    ///
-    /// * `url` - Full URL to request
-    /// * `body` - Request body to serialize as JSON
+    /// Blocking counterpart of [`send_empty_with_retry`](Self::send_empty_with_retry).
     ///
     /// # Errors
     ///
@@ -565,148 +902,559 @@ impl OllamaClient {
     /// - Maximum retry attempts exceeded
     /// - Client errors (4xx) occur (no retry)
     #[cfg(feature = "model")]
-    pub(super) fn delete_empty_blocking_with_retry<R>(&self, url: &str, body: &R) -> Result<()>
+    pub(super) fn send_empty_blocking_with_retry<R>(&self, target: &Target, body: &R) -> Result<()>
     where
         R: serde::Serialize,
     {
-        let blocking_client = reqwest::blocking::Client::builder()
-            .timeout(self.config.timeout())
-            .build()?;
-
-        for attempt in 0..=self.config.max_retries() {
-            match blocking_client.delete(url).json(body).send() {
-                Ok(response) => {
-                    // Retry on server errors (5xx)
-                    if response.status().is_server_error() && attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                        continue;
-                    }
-
-                    // Check for success status
-                    if response.status().is_success() {
-                        return Ok(());
-                    }
-
-                    // Client error - no retry
-                    return Err(Error::HttpStatusError(response.status().as_u16()));
-                }
-                Err(_e) => {
-                    // Retry on network errors
-                    if attempt < self.config.max_retries() {
-                        std::thread::sleep(Duration::from_millis(100 * (attempt as u64 + 1)));
-                    }
-                }
+        let call = CallContext::new(target);
+        let result = self.in_flight_blocking(|| {
+            let payload = self.encode_body(body)?;
+            let _slot = self.slot_blocking(&call, target, Some(&payload));
+            let response = self.send_blocking_with_retry(&call, target, Some(&payload))?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(Error::HttpStatusError(response.status().as_u16()))
             }
-        }
-
-        Err(Error::MaxRetriesExceededError(self.config.max_retries()))
+        });
+        self.observers.observe(&call, result)
     }
 
     /// Execute async HTTP POST and stream newline-delimited JSON (NDJSON) responses.
     ///
     /// Does not retry: streaming responses are long-lived and partial data would be lost.
-    /// Uses a 300-second per-request timeout override suitable for long generations.
-    pub(super) async fn post_ndjson_stream<R, T>(
+    /// Raises the total timeout to at least 300 seconds for long generations.
+    /// Reports each line to `on_chunk`, the first decoded event to
+    /// `on_first_token`, and stream failures to `on_error`.
+    pub(crate) async fn post_ndjson_stream<R, T>(
         &self,
-        url: &str,
+        target: &Target,
         body: &R,
-    ) -> Result<tokio::sync::mpsc::Receiver<Result<T>>>
+    ) -> Result<ResponseStream<T>>
     where
-        R: serde::Serialize + ?Sized,
+        R: RequestBody + ?Sized,
         T: serde::de::DeserializeOwned + Send + 'static,
     {
-        let response = self
-            .client
-            .post(url)
-            .json(body)
-            .timeout(STREAMING_TIMEOUT)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(Error::HttpStatusError(response.status().as_u16()));
+        self.post_ndjson_lines(target, body, |line| parse_json_line(&line))
+            .await
+    }
+
+    /// Like [`post_ndjson_stream`](Self::post_ndjson_stream), but yields
+    /// each line unparsed.
+    pub(crate) async fn post_ndjson_raw<R>(
+        &self,
+        target: &Target,
+        body: &R,
+    ) -> Result<RawChunkStream>
+    where
+        R: RequestBody + ?Sized,
+    {
+        self.post_ndjson_lines(target, body, |line| Ok(RawChunk::new(line)))
+            .await
+    }
+
+    /// Sends a streaming request and returns its successful response,
+    /// with the slot it holds until the body is read.
+    async fn open_stream<R>(
+        &self,
+        target: &Target,
+        body: &R,
+    ) -> Result<(CallContext, reqwest::Response, InFlight)>
+    where
+        R: RequestBody + ?Sized,
+    {
+        let call = CallContext::new(target);
+        let started = call.started();
+        self.observers.request_start(&call);
+
+        let response = async {
+            let mut in_flight = self.lifecycle.enter()?;
+            let payload = body.encode(self)?;
+            let slot = tokio::select! {
+                slot = self.slot(&call, target, Some(&payload)) => slot,
+                () = in_flight.cancelled() => return Err(Error::ClientShutdown),
+            };
+            in_flight.hold(slot);
+            call.set_payload(&payload);
+            self.observers.request_body(&call, &payload);
+            let mut request = json_request(
+                self.http()?
+                    .request(target.method.clone(), target.url.clone()),
+                &payload,
+            )
+            .timeout(self.timeout(TimeoutClass::Long));
+            if let Some((header, key)) = self.idempotency_key(target) {
+                request = request.header(header, key);
+            }
+            call.add_attempt();
+            let response = tokio::select! {
+                response = request.send() => response?,
+                () = in_flight.cancelled() => return Err(Error::ClientShutdown),
+            };
+            self.observers
+                .complete(&call, response.status().as_u16(), started.elapsed());
+            self.detect_gateway(response.headers());
+
+            if !response.status().is_success() {
+                return Err(status_error(response.status(), response.headers()));
+            }
+            Ok((response, in_flight))
         }
+        .await;
+        let (response, in_flight) = self.observers.observe(&call, response)?;
+        Ok((call, response, in_flight))
+    }
+
+    /// Shared body of the async NDJSON helpers; `parse` turns each
+    /// non-empty line into an item.
+    async fn post_ndjson_lines<R, T>(
+        &self,
+        target: &Target,
+        body: &R,
+        parse: fn(Bytes) -> Result<T>,
+    ) -> Result<ResponseStream<T>>
+    where
+        R: RequestBody + ?Sized,
+        T: Send + 'static,
+    {
+        let (call, response, in_flight) = self.open_stream(target, body).await?;
+        let started = call.started();
+        let sse = self.frames_sse(response.headers());
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<Result<T>>(32);
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<T>>(self.config.stream_buffer());
+        let max_line = self.config.max_response_bytes();
+        let json_limits = self.config.json_limits();
+        let observers = self.observers.clone();
+        let state = Arc::new(StreamState::new(started, self.config.stream_buffer()));
+        let producer_state = state.clone();
 
         tokio::spawn(async move {
-            let mut response = response;
-            let mut buf: Vec<u8> = Vec::new();
-
-            loop {
-                match response.chunk().await {
-                    Ok(Some(chunk)) => {
-                        buf.extend_from_slice(&chunk);
-                        while let Some(idx) = buf.iter().position(|&b| b == b'\n') {
-                            let mut line: Vec<u8> = buf.drain(..=idx).collect();
-                            if line.last() == Some(&b'\n') {
-                                line.pop();
-                            }
-                            if line.last() == Some(&b'\r') {
-                                line.pop();
-                            }
-                            if line.is_empty() {
-                                continue;
-                            }
-                            match serde_json::from_slice::<T>(&line) {
-                                Ok(v) => {
-                                    if tx.send(Ok(v)).await.is_err() {
+            // Forwards one item; returns false when the receiver is gone.
+            let send = |item: Result<T>| {
+                match &item {
+                    Ok(_) => {
+                        let latency = started.elapsed();
+                        if producer_state.set_first_token(latency) {
+                            observers.first_token(&call, latency);
+                        }
+                    }
+                    Err(e) => observers.error(&call, e),
+                }
+                tx.send(item)
+            };
+
+            let produce = async {
+                let mut response = response;
+                let mut reader = match max_line {
+                    Some(limit) => NdjsonReader::new().with_max_line_bytes(limit),
+                    None => NdjsonReader::new(),
+                };
+                loop {
+                    producer_state.resumed().await;
+                    match response.chunk().await {
+                        Ok(Some(chunk)) => {
+                            reader.feed(chunk);
+                            while let Some(line) = reader.next_line() {
+                                let line = match line {
+                                    Ok(line) => line.into_bytes(),
+                                    Err(e) => {
+                                        let _ = send(Err(e)).await;
                                         return;
                                     }
-                                }
-                                Err(e) => {
-                                    let _ = tx.send(Err(Error::StreamError(e.to_string()))).await;
+                                };
+                                let Some(line) = unframe(line, sse) else {
+                                    continue;
+                                };
+                                observers.chunk(&call, &line);
+                                // A line that fails to decode is reported on
+                                // its own; the lines after it still arrive.
+                                let item = json_limits.check(&line).and_then(|()| parse(line));
+                                if send(item).await.is_err() {
                                     return;
                                 }
                             }
                         }
-                    }
-                    Ok(None) => {
-                        let trimmed = trim_bytes(&buf);
-                        if !trimmed.is_empty() {
-                            match serde_json::from_slice::<T>(trimmed) {
-                                Ok(v) => {
-                                    let _ = tx.send(Ok(v)).await;
-                                }
-                                Err(e) => {
-                                    let _ = tx.send(Err(Error::StreamError(e.to_string()))).await;
-                                }
+                        Ok(None) => {
+                            if let Some(tail) = reader
+                                .finish()
+                                .and_then(|tail| unframe(tail.into_bytes(), sse))
+                            {
+                                observers.chunk(&call, &tail);
+                                let _ =
+                                    send(json_limits.check(&tail).and_then(|()| parse(tail))).await;
                             }
+                            break;
+                        }
+                        Err(e) => {
+                            let _ = send(Err(interrupted(&e))).await;
+                            break;
                         }
-                        break;
                     }
-                    Err(e) => {
-                        let _ = tx.send(Err(Error::StreamError(e.to_string()))).await;
-                        break;
+                }
+            };
+            // A dropped stream stops reading at once, freeing its slot
+            let cancelled = tokio::select! {
+                () = produce => false,
+                () = tx.closed() => false,
+                () = in_flight.cancelled() => true,
+            };
+            if cancelled {
+                // The reader may not be polling, so never wait for room
+                observers.error(&call, &Error::ClientShutdown);
+                let _ = tx.try_send(Err(Error::ClientShutdown));
+            }
+        });
+
+        Ok(ResponseStream::new(rx, state))
+    }
+
+    /// Like [`post_ndjson_raw`](Self::post_ndjson_raw), but yields the body
+    /// chunks as received, without splitting lines or checking limits.
+    pub(crate) async fn post_bytes_stream<R>(&self, target: &Target, body: &R) -> Result<ByteStream>
+    where
+        R: RequestBody + ?Sized,
+    {
+        let (call, mut response, in_flight) = self.open_stream(target, body).await?;
+        let started = call.started();
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes>>(self.config.stream_buffer());
+        let observers = self.observers.clone();
+        let state = Arc::new(StreamState::new(started, self.config.stream_buffer()));
+        let producer_state = state.clone();
+
+        tokio::spawn(async move {
+            let produce = async {
+                loop {
+                    producer_state.resumed().await;
+                    match response.chunk().await {
+                        Ok(Some(chunk)) => {
+                            let latency = started.elapsed();
+                            if producer_state.set_first_token(latency) {
+                                observers.first_token(&call, latency);
+                            }
+                            if tx.send(Ok(chunk)).await.is_err() {
+                                return;
+                            }
+                        }
+                        Ok(None) => return,
+                        Err(e) => {
+                            let e = interrupted(&e);
+                            observers.error(&call, &e);
+                            let _ = tx.send(Err(e)).await;
+                            return;
+                        }
                     }
                 }
+            };
+            let cancelled = tokio::select! {
+                () = produce => false,
+                () = tx.closed() => false,
+                () = in_flight.cancelled() => true,
+            };
+            if cancelled {
+                observers.error(&call, &Error::ClientShutdown);
+                let _ = tx.try_send(Err(Error::ClientShutdown));
             }
         });
 
-        Ok(rx)
+        Ok(ResponseStream::new(rx, state))
     }
 
     /// Execute blocking HTTP POST and return a line iterator over NDJSON responses.
     ///
-    /// Does not retry. Uses a 300-second per-request timeout override.
-    pub(super) fn post_ndjson_stream_blocking<R>(
+    /// Does not retry. Raises the total timeout to at least 300 seconds.
+    pub(crate) fn post_ndjson_stream_blocking<R, T>(
         &self,
-        url: &str,
+        target: &Target,
         body: &R,
-    ) -> Result<ChatStreamBlocking>
+    ) -> Result<ResponseStreamBlocking<T>>
     where
         R: serde::Serialize + ?Sized,
+        T: serde::de::DeserializeOwned,
     {
-        let blocking_client = reqwest::blocking::Client::builder()
-            .timeout(STREAMING_TIMEOUT)
-            .build()?;
+        self.post_ndjson_lines_blocking(target, body, parse_json_line::<T>)
+    }
+
+    /// Like [`post_ndjson_stream_blocking`](Self::post_ndjson_stream_blocking),
+    /// but yields each line unparsed.
+    pub(crate) fn post_ndjson_raw_blocking<R>(
+        &self,
+        target: &Target,
+        body: &R,
+    ) -> Result<RawChunkStreamBlocking>
+    where
+        R: serde::Serialize + ?Sized,
+    {
+        self.post_ndjson_lines_blocking(target, body, parse_raw_line)
+    }
+
+    /// Shared body of the blocking NDJSON helpers.
+    fn post_ndjson_lines_blocking<R, T>(
+        &self,
+        target: &Target,
+        body: &R,
+        parse: LineParser<T>,
+    ) -> Result<ResponseStreamBlocking<T>>
+    where
+        R: serde::Serialize + ?Sized,
+    {
+        let call = CallContext::new(target);
+        let started = call.started();
+        self.observers.request_start(&call);
+
+        let response = (|| {
+            let mut in_flight = self.lifecycle.enter()?;
+            let payload = self.encode_body(body)?;
+            in_flight.hold(self.slot_blocking(&call, target, Some(&payload)));
+            call.set_payload(&payload);
+            self.observers.request_body(&call, &payload);
+            let request = self
+                .http_blocking()?
+                .request(target.method.clone(), target.url.clone());
+            let mut request =
+                json_request_blocking(request, &payload).timeout(self.timeout(TimeoutClass::Long));
+            if let Some((header, key)) = self.idempotency_key(target) {
+                request = request.header(header, key);
+            }
+            call.add_attempt();
+            let response = request.send()?;
+            self.observers
+                .complete(&call, response.status().as_u16(), started.elapsed());
+            self.detect_gateway(response.headers());
+
+            if !response.status().is_success() {
+                return Err(status_error(response.status(), response.headers()));
+            }
+            Ok((response, in_flight))
+        })();
+        let (response, in_flight) = self.observers.observe(&call, response)?;
+        let sse = self.frames_sse(response.headers());
+
+        Ok(ResponseStreamBlocking::new(
+            response,
+            self.config.max_response_bytes(),
+            self.config.json_limits(),
+            self.observers.clone(),
+            call,
+            in_flight,
+            parse,
+        )
+        .with_sse(sse))
+    }
+
+    /// Dispatch every call of this client at `priority` when concurrency is
+    /// limited, overriding model and endpoint defaults.
+    ///
+    /// Clones share one concurrency limit, so a clone with its own priority
+    /// is the way to tag a class of work.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ClientConfig, OllamaClient, Priority};
+    ///
+    /// let client = OllamaClient::new(ClientConfig::default().with_max_concurrency(2))?;
+    /// let interactive = client.clone().with_priority(Priority::High);
+    /// let indexing = client.with_priority(Priority::Low);
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Returns the priority set with [`with_priority`](Self::with_priority),
+    /// if any
+    pub fn priority(&self) -> Option<Priority> {
+        self.priority
+    }
+
+    /// Register an [`Observer`] to receive request lifecycle callbacks.
+    ///
+    /// Observers are shared by clones of the client made afterwards.
+    /// Several observers may be registered; each receives every event in
+    /// registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use ollama_oxide::{CallId, Observer, OllamaClient};
+    ///
+    /// struct Log;
+    ///
+    /// impl Observer for Log {
+    ///     fn on_retry(&self, call: CallId, url: &str, attempt: u32, reason: &str) {
+    ///         eprintln!("{} retry #{} for {}: {}", call, attempt, url, reason);
+    ///     }
+    /// }
+    ///
+    /// let client = OllamaClient::default()?.with_observer(Arc::new(Log));
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observers.push(observer);
+        self
+    }
 
-        let response = blocking_client.post(url).json(body).send()?;
+    /// Check the prompts of every chat and generate call on this client, and
+    /// the replies of `chat` and `generate`, with `guardrail`, after those
+    /// already added.
+    ///
+    /// See [`Guardrail`] for what is checked and when. Clones made before
+    /// this call do not get the guardrail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{FnGuardrail, GuardrailAction, OllamaClient};
+    /// use std::sync::Arc;
+    ///
+    /// let client = OllamaClient::default()?.with_guardrail(Arc::new(
+    ///     FnGuardrail::new("no-passwords").on_input(|text| {
+    ///         if text.contains("password") {
+    ///             GuardrailAction::Block("looks like a credential".to_string())
+    ///         } else {
+    ///             GuardrailAction::Allow
+    ///         }
+    ///     }),
+    /// ));
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn with_guardrail(mut self, guardrail: Arc<dyn Guardrail>) -> Self {
+        self.guardrails.push(guardrail);
+        self
+    }
+
+    /// Attach `value` to every call of this client, for observers to read
+    /// in [`Observer::on_extensions`] and error reports.
+    ///
+    /// Like [`with_priority`](Self::with_priority), this is meant for a
+    /// clone that tags one class of work, e.g. one tenant's requests. A
+    /// value replaces an earlier one of the same type.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// #[derive(Debug)]
+    /// struct TenantId(String);
+    ///
+    /// let client = OllamaClient::default()?;
+    /// let tenant = client.clone().with_extension(TenantId("acme".into()));
+    /// assert!(tenant.extensions().contains::<TenantId>());
+    /// assert!(client.extensions().is_empty());
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.observers.extensions_mut().insert(value);
+        self
+    }
 
-        if !response.status().is_success() {
-            return Err(Error::HttpStatusError(response.status().as_u16()));
+    /// Values attached with [`with_extension`](Self::with_extension)
+    pub fn extensions(&self) -> &Extensions {
+        self.observers.extensions()
+    }
+
+    /// Returns `true` if at least one observer is registered.
+    pub fn has_observers(&self) -> bool {
+        !self.observers.is_empty()
+    }
+}
+
+/// Linear backoff between attempts: 100ms, 200ms, 300ms, ...
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis(100 * (attempt as u64 + 1))
+}
+
+/// The error for a response body that broke off while streaming; stream
+/// adapters that track the reply attach its text.
+fn interrupted(error: &reqwest::Error) -> Error {
+    Error::StreamInterrupted {
+        partial: String::new(),
+        message: error.to_string(),
+    }
+}
+
+/// Statuses the retry loop tries again: server errors and 429.
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Wait before retrying a response: the server's `Retry-After` (capped at
+/// [`MAX_RETRY_AFTER`]) when present, otherwise the usual backoff.
+fn status_delay(attempt: u32, headers: &HeaderMap) -> Duration {
+    retry_after(headers)
+        .map(|d| d.min(MAX_RETRY_AFTER))
+        .unwrap_or_else(|| retry_delay(attempt))
+}
+
+/// Parses a `Retry-After` header given in seconds. HTTP-date values are ignored.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// `line` without its server-sent event framing when `sse` is set; `None`
+/// for lines that carry no event data.
+fn unframe(line: Bytes, sse: bool) -> Option<Bytes> {
+    if !sse {
+        return Some(line);
+    }
+    let data = sse_data(&line)?;
+    Some(line.slice_ref(data))
+}
+
+/// Maps a non-success status to an error, keeping the `Retry-After` hint on 429.
+fn status_error(status: StatusCode, headers: &HeaderMap) -> Error {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        Error::RateLimited {
+            retry_after: retry_after(headers),
         }
+    } else {
+        Error::HttpStatusError(status.as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_body_sizes_each_buffer_to_its_body() {
+        let client = OllamaClient::default().unwrap();
+        let big = "x".repeat(1024 * 1024);
+        client
+            .encode_body(&serde_json::json!({ "input": big }))
+            .unwrap();
+        let small = client
+            .encode_body(&serde_json::json!({"input": "a"}))
+            .unwrap();
+        assert_eq!(&small[..], br#"{"input":"a"}"#);
+        // The big body leaves nothing behind for later calls to inherit.
+        assert!(small.try_into_mut().unwrap().capacity() < 1024);
+    }
+
+    #[test]
+    fn test_transport_is_lazy_and_shared() {
+        let client = OllamaClient::default().unwrap();
+        let clone = client.clone();
+        assert!(!client.transport.is_built());
+        clone.http().unwrap();
+        assert!(Arc::ptr_eq(&client.transport, &clone.transport));
+        assert!(client.transport.is_built());
+    }
 
-        Ok(ChatStreamBlocking::new(response))
+    #[test]
+    fn test_encode_body_enforces_limit() {
+        let client = OllamaClient::new(ClientConfig::default().with_max_request_bytes(4)).unwrap();
+        let err = client.encode_body(&"too long").unwrap_err();
+        assert!(matches!(err, Error::RequestTooLarge { size: 10, limit: 4 }));
     }
 }
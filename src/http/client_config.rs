@@ -1,19 +1,48 @@
 //! HTTP client configuration
 
+use std::collections::HashMap;
 use std::time::Duration;
 
-use crate::{Error, Result};
+use super::{GatewayProfile, JsonLimits, PostProcessor, Priority, SystemPromptPolicy, TokenBudget};
+#[cfg(feature = "model")]
+use crate::PromptTemplate;
+use crate::{Error, KeepAliveSetting, ModelOptions, Result};
 use url::Url;
 
-/// Validates that a URL is well-formed and uses http or https scheme
-fn validate_base_url(base_url: &str) -> Result<()> {
+/// Default base URL of a local Ollama server
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// Events buffered between the network reader and a stream's consumer.
+const DEFAULT_STREAM_BUFFER: usize = 32;
+
+/// Header used by [`ClientConfig::with_idempotency_keys`].
+const DEFAULT_IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+/// Validates that a URL is well-formed and uses http or https scheme,
+/// returning it parsed
+fn parse_base_url(base_url: &str) -> Result<Url> {
     let url = Url::parse(base_url)?;
     if url.scheme() != "http" && url.scheme() != "https" {
         return Err(Error::InvalidUrlError(
             url::ParseError::RelativeUrlWithoutBase,
         ));
     }
-    Ok(())
+    Ok(url)
+}
+
+/// Parses one of the crate's built-in base URLs.
+fn builtin_base_url(base_url: &str) -> Url {
+    Url::parse(base_url).expect("built-in base URL is valid")
+}
+
+/// Bearer token sent as `Authorization`; redacted in `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
+struct ApiKey(String);
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ApiKey(<redacted>)")
+    }
 }
 
 /// Configuration for Ollama HTTP client
@@ -47,19 +76,107 @@ pub struct ClientConfig {
     /// Base URL for Ollama API (validated: must be http or https)
     base_url: String,
 
-    /// Request timeout duration
+    /// `base_url` parsed once, so endpoint URLs are joined without reparsing
+    base: Url,
+
+    /// Total per-request timeout, from connecting until the body is read
     timeout: Duration,
 
+    /// Time allowed to establish a connection (`None` = bounded only by `timeout`)
+    connect_timeout: Option<Duration>,
+
+    /// Time allowed between reads of the response (`None` = bounded only by `timeout`)
+    read_timeout: Option<Duration>,
+
     /// Maximum retry attempts on failure (0 = no retries)
     max_retries: u32,
+
+    /// Largest serialized request body allowed (`None` = unlimited)
+    max_request_bytes: Option<usize>,
+
+    /// Largest response body, or streamed NDJSON line, allowed (`None` = unlimited)
+    max_response_bytes: Option<usize>,
+
+    /// Depth and string size guards checked before decoding response JSON
+    json_limits: JsonLimits,
+
+    /// Events a streaming response buffers ahead of its consumer
+    stream_buffer: usize,
+
+    /// Times an interrupted chat stream is re-issued to continue the reply
+    stream_resume: u32,
+
+    /// API key sent as a bearer token on every request (`None` = no auth)
+    api_key: Option<ApiKey>,
+
+    /// Header carrying a per-request idempotency key (`None` = not sent)
+    idempotency_header: Option<String>,
+
+    /// Share one upstream call among identical concurrent deterministic calls
+    coalesce_requests: bool,
+
+    /// Most async and blocking calls running at once (`None` = unlimited)
+    max_concurrency: Option<usize>,
+
+    /// Priority of calls naming each model, when concurrency is limited
+    model_priorities: HashMap<String, Priority>,
+
+    /// Model used by generate, chat, and embed requests whose `model` is empty
+    default_model: Option<String>,
+
+    /// Options used by generate, chat, and embed requests that set none
+    default_options: Option<ModelOptions>,
+
+    /// Keep-alive used by generate, chat, and embed requests that set none
+    default_keep_alive: Option<KeepAliveSetting>,
+
+    /// Context-window check applied to generate and chat requests (`None` = off)
+    token_budget: Option<TokenBudget>,
+
+    /// Template turning chats into raw prompts for models without a chat
+    /// template (`None` = off)
+    #[cfg(feature = "model")]
+    chat_fallback: Option<PromptTemplate>,
+
+    /// Rewrites applied to the text of chat and generate replies (`None` = off)
+    post_processor: Option<PostProcessor>,
+
+    /// Text added around the system prompt of chat and generate requests
+    /// (`None` = off)
+    system_prompt_policy: Option<SystemPromptPolicy>,
+
+    /// Gateway in front of Ollama (`None` = detect)
+    gateway: Option<GatewayProfile>,
 }
 
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
-            base_url: "http://localhost:11434".to_string(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            base: builtin_base_url(DEFAULT_BASE_URL),
             timeout: Duration::from_secs(30),
             max_retries: 3,
+            connect_timeout: None,
+            read_timeout: None,
+            max_request_bytes: None,
+            max_response_bytes: None,
+            json_limits: JsonLimits::default(),
+            stream_buffer: DEFAULT_STREAM_BUFFER,
+            stream_resume: 0,
+            api_key: None,
+            idempotency_header: None,
+            coalesce_requests: false,
+            max_concurrency: None,
+            model_priorities: HashMap::new(),
+            default_model: None,
+            default_options: None,
+            default_keep_alive: None,
+            token_budget: None,
+            #[cfg(feature = "model")]
+            chat_fallback: None,
+            post_processor: None,
+            system_prompt_policy: None,
+            gateway: None,
         }
     }
 }
@@ -85,11 +202,13 @@ impl ClientConfig {
     /// # Ok::<(), ollama_oxide::Error>(())
     /// ```
     pub fn new(base_url: String, timeout: Duration, max_retries: u32) -> Result<Self> {
-        validate_base_url(&base_url)?;
+        let base = parse_base_url(&base_url)?;
         Ok(Self {
             base_url,
+            base,
             timeout,
             max_retries,
+            ..Self::default()
         })
     }
 
@@ -110,9 +229,10 @@ impl ClientConfig {
     /// # Ok::<(), ollama_oxide::Error>(())
     /// ```
     pub fn with_base_url(base_url: String) -> Result<Self> {
-        validate_base_url(&base_url)?;
+        let base = parse_base_url(&base_url)?;
         Ok(Self {
             base_url,
+            base,
             ..Self::default()
         })
     }
@@ -137,37 +257,581 @@ impl ClientConfig {
     /// # Ok::<(), ollama_oxide::Error>(())
     /// ```
     pub fn with_base_url_and_timeout(base_url: String, timeout: Duration) -> Result<Self> {
-        validate_base_url(&base_url)?;
+        let base = parse_base_url(&base_url)?;
         Ok(Self {
             base_url,
+            base,
             timeout,
             ..Self::default()
         })
     }
 
+    /// Base URL of the hosted Ollama service
+    pub const OLLAMA_CLOUD_URL: &'static str = "https://ollama.com";
+
+    /// Creates a config for the hosted Ollama service at `https://ollama.com`.
+    ///
+    /// Sends `api_key` as a bearer token on every request and raises the
+    /// timeout to 120 seconds, since hosted models are large and non-streamed
+    /// replies take longer than local ones. Everything else, including
+    /// retries, uses the defaults; rate-limited (429) responses are retried
+    /// and surface as [`Error::RateLimited`](crate::Error::RateLimited).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::ollama_cloud("my-key");
+    /// assert_eq!(config.base_url(), "https://ollama.com");
+    /// assert_eq!(config.api_key(), Some("my-key"));
+    /// ```
+    pub fn ollama_cloud(api_key: impl Into<String>) -> Self {
+        Self {
+            base_url: Self::OLLAMA_CLOUD_URL.to_string(),
+            base: builtin_base_url(Self::OLLAMA_CLOUD_URL),
+            timeout: Duration::from_secs(120),
+            ..Self::default()
+        }
+        .with_api_key(api_key)
+    }
+
     /// Returns the base URL
     #[inline]
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
-    /// Returns the request timeout duration
+    /// Returns the total request timeout duration
     #[inline]
     pub fn timeout(&self) -> Duration {
         self.timeout
     }
 
+    /// Returns the connect timeout, if set
+    #[inline]
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Returns the read timeout, if set
+    #[inline]
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Sets the total per-request timeout (connect, send, and read the body).
+    ///
+    /// Streaming endpoints use at least 300 seconds regardless of this value.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how long to wait for a TCP/TLS connection to be established.
+    ///
+    /// Keep this short so an unreachable host fails fast while long
+    /// generations are still allowed by the read and total timeouts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = ClientConfig::default()
+    ///     .with_connect_timeout(Duration::from_secs(2))
+    ///     .with_read_timeout(Duration::from_secs(600))
+    ///     .with_timeout(Duration::from_secs(900));
+    /// assert_eq!(config.connect_timeout(), Some(Duration::from_secs(2)));
+    /// ```
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum idle time between reads of a response.
+    ///
+    /// The timer resets after every successful read, so this bounds stalls
+    /// rather than total generation time. Only the async client honors it;
+    /// blocking calls rely on the connect and total timeouts.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
     /// Returns the maximum retry attempts
     #[inline]
     pub fn max_retries(&self) -> u32 {
         self.max_retries
     }
 
-    /// Build full URL from base URL and endpoint path
+    /// Sets the largest serialized request body the client will send.
+    ///
+    /// Oversized requests (for example, runaway base64 images) fail with
+    /// [`Error::RequestTooLarge`] before any network I/O.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::default()
+    ///     .with_max_request_bytes(8 * 1024 * 1024)
+    ///     .with_max_response_bytes(16 * 1024 * 1024);
+    /// assert_eq!(config.max_request_bytes(), Some(8 * 1024 * 1024));
+    /// ```
+    pub fn with_max_request_bytes(mut self, limit: usize) -> Self {
+        self.max_request_bytes = Some(limit);
+        self
+    }
+
+    /// Sets the largest response body the client will read.
+    ///
+    /// For streaming endpoints the limit applies to each NDJSON line rather
+    /// than the whole stream. Oversized responses fail with
+    /// [`Error::ResponseTooLarge`] without buffering past the limit.
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = Some(limit);
+        self
+    }
+
+    /// Sets nesting depth and string size guards for response JSON.
+    ///
+    /// Applied to whole response bodies and to each streamed NDJSON line.
+    /// JSON that exceeds a limit fails with [`Error::ResponseTooLarge`]
+    /// before it is deserialized.
+    pub fn with_json_limits(mut self, limits: JsonLimits) -> Self {
+        self.json_limits = limits;
+        self
+    }
+
+    /// Returns the response JSON guards
+    #[inline]
+    pub fn json_limits(&self) -> JsonLimits {
+        self.json_limits
+    }
+
+    /// Sets how many decoded events an async stream buffers ahead of its
+    /// consumer (default 32).
+    ///
+    /// The network reader runs ahead of [`ResponseStream::next`](super::ResponseStream::next)
+    /// until this many events wait, then stops reading. A small buffer, down
+    /// to 1, keeps the reader in lockstep with the consumer, so backpressure
+    /// and [`pause`](super::ResponseStream::pause) reach the server sooner;
+    /// a larger one smooths over slow consumers. Adapters such as
+    /// [`tee`](super::ResponseStream::tee) use the same size. Values below 1
+    /// are raised to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::default().with_stream_buffer(1);
+    /// assert_eq!(config.stream_buffer(), 1);
+    /// ```
+    pub fn with_stream_buffer(mut self, events: usize) -> Self {
+        self.stream_buffer = events.max(1);
+        self
+    }
+
+    /// Returns the number of events a stream buffers ahead of its consumer
+    #[inline]
+    pub fn stream_buffer(&self) -> usize {
+        self.stream_buffer
+    }
+
+    /// Sets how many times an interrupted chat stream is resumed (default 0,
+    /// never).
+    ///
+    /// When the connection drops, or the body ends, before the final event
+    /// of [`chat_stream`](super::OllamaApiAsync::chat_stream), e.g. because
+    /// the server restarted, the request is sent again with the reply so
+    /// far as a trailing assistant message, which the server continues. The
+    /// stream carries on with the continuation as if nothing happened. Only
+    /// replies without thinking or tool calls are resumed, since neither
+    /// can be continued; those, and streams out of attempts, end with
+    /// [`Error::StreamInterrupted`](crate::Error::StreamInterrupted)
+    /// carrying the text received. Blocking streams are never resumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::default().with_stream_resume(2);
+    /// assert_eq!(config.stream_resume(), 2);
+    /// ```
+    pub fn with_stream_resume(mut self, attempts: u32) -> Self {
+        self.stream_resume = attempts;
+        self
+    }
+
+    /// Returns how many times an interrupted chat stream is resumed
+    #[inline]
+    pub fn stream_resume(&self) -> u32 {
+        self.stream_resume
+    }
+
+    /// Returns the request body size limit, if any
+    #[inline]
+    pub fn max_request_bytes(&self) -> Option<usize> {
+        self.max_request_bytes
+    }
+
+    /// Returns the response body size limit, if any
+    #[inline]
+    pub fn max_response_bytes(&self) -> Option<usize> {
+        self.max_response_bytes
+    }
+
+    /// Sets an API key, sent as `Authorization: Bearer <key>` on every request.
+    ///
+    /// Required by hosted endpoints such as `ollama.com` web search; local
+    /// servers ignore it. The key is redacted from `Debug` output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::with_base_url("https://ollama.com".to_string())?
+    ///     .with_api_key("my-key");
+    /// assert_eq!(config.api_key(), Some("my-key"));
+    /// assert!(!format!("{config:?}").contains("my-key"));
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(ApiKey(api_key.into()));
+        self
+    }
+
+    /// Returns the API key, if any
+    #[inline]
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_ref().map(|k| k.0.as_str())
+    }
+
+    /// Sends an `Idempotency-Key` header with every request that is not a
+    /// `GET`.
+    ///
+    /// One random key (a UUID) is generated per logical request and reused
+    /// on every retry, so gateways that deduplicate by key never execute or
+    /// bill a retried request twice. Observers receive each key through
+    /// [`Observer::on_idempotency_key`](super::Observer::on_idempotency_key).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::default().with_idempotency_keys();
+    /// assert_eq!(config.idempotency_header(), Some("Idempotency-Key"));
+    /// ```
+    pub fn with_idempotency_keys(self) -> Self {
+        self.with_idempotency_header(DEFAULT_IDEMPOTENCY_HEADER)
+    }
+
+    /// Like [`with_idempotency_keys`](Self::with_idempotency_keys), with a
+    /// custom header name, e.g. `X-Request-Id`.
+    pub fn with_idempotency_header(mut self, name: impl Into<String>) -> Self {
+        self.idempotency_header = Some(name.into());
+        self
+    }
+
+    /// Returns the idempotency key header name, if keys are sent
+    #[inline]
+    pub fn idempotency_header(&self) -> Option<&str> {
+        self.idempotency_header.as_deref()
+    }
+
+    /// Coalesces concurrent identical async calls into one upstream request
+    /// (singleflight).
+    ///
+    /// While a non-streaming call is in flight, an identical one — same
+    /// method, URL, and encoded body — waits for it instead of sending its
+    /// own request, and both get the same outcome, success or error. This
+    /// keeps a burst of retries from a web backend from reaching the server
+    /// as a thundering herd.
+    ///
+    /// Only deterministic calls are coalesced: generate and chat requests
+    /// qualify when their options set a temperature of 0 or a seed, since
+    /// otherwise each caller expects its own sample. Creating and pushing a
+    /// model, streams, and blocking calls are never coalesced. Calls that
+    /// shared a response are reported through
+    /// [`Observer::on_coalesced`](super::Observer::on_coalesced).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::default().with_request_coalescing();
+    /// assert!(config.coalesces_requests());
+    /// ```
+    pub fn with_request_coalescing(mut self) -> Self {
+        self.coalesce_requests = true;
+        self
+    }
+
+    /// Returns whether identical concurrent calls are coalesced
+    #[inline]
+    pub fn coalesces_requests(&self) -> bool {
+        self.coalesce_requests
+    }
+
+    /// Limits how many calls the client and its clones run at once,
+    /// dispatching waiting calls by [`Priority`].
+    ///
+    /// A call holds its slot from sending until its response is read; a
+    /// stream holds it until it ends or is dropped. When all slots are
+    /// taken, further calls wait, and each freed slot goes to the oldest
+    /// waiting call of the highest priority, so interactive chat is not
+    /// stuck behind a backlog of embeddings. Values below 1 are raised
+    /// to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ClientConfig, Priority};
+    ///
+    /// let config = ClientConfig::default()
+    ///     .with_max_concurrency(4)
+    ///     .with_model_priority("nomic-embed-text", Priority::Low);
+    /// assert_eq!(config.max_concurrency(), Some(4));
+    /// ```
+    pub fn with_max_concurrency(mut self, calls: usize) -> Self {
+        self.max_concurrency = Some(calls.max(1));
+        self
+    }
+
+    /// Returns the concurrency limit, if any
+    #[inline]
+    pub fn max_concurrency(&self) -> Option<usize> {
+        self.max_concurrency
+    }
+
+    /// Sets the priority of calls whose request names `model`, unless the
+    /// client sets one with
+    /// [`OllamaClient::with_priority`](super::OllamaClient::with_priority).
+    ///
+    /// Only matters with [`with_max_concurrency`](Self::with_max_concurrency).
+    pub fn with_model_priority(mut self, model: impl Into<String>, priority: Priority) -> Self {
+        self.model_priorities.insert(model.into(), priority);
+        self
+    }
+
+    /// Returns the priority set for `model`, if any
+    pub fn model_priority(&self, model: &str) -> Option<Priority> {
+        self.model_priorities.get(model).copied()
+    }
+
+    /// Whether any model has a priority, so request bodies need decoding
+    /// to find theirs
+    pub(crate) fn has_model_priorities(&self) -> bool {
+        !self.model_priorities.is_empty()
+    }
+
+    /// Sets the model used when a generate, chat, or embed request leaves
+    /// `model` empty.
+    ///
+    /// Lets config-driven apps build requests without threading the model
+    /// name through every call site.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ClientConfig, GenerateRequest, KeepAliveSetting, ModelOptions};
+    ///
+    /// let config = ClientConfig::default()
+    ///     .with_default_model("qwen3:0.6b")
+    ///     .with_default_options(ModelOptions::default().with_temperature(0.2))
+    ///     .with_default_keep_alive("10m");
+    /// assert_eq!(config.default_model(), Some("qwen3:0.6b"));
+    /// assert_eq!(config.default_keep_alive(), Some(&KeepAliveSetting::duration("10m")));
+    ///
+    /// // The model is filled in when the request is sent
+    /// let request = GenerateRequest::new("", "Why is the sky blue?");
+    /// ```
+    pub fn with_default_model(mut self, model: impl Into<String>) -> Self {
+        self.default_model = Some(model.into());
+        self
+    }
+
+    /// Sets the options used when a generate, chat, or embed request has none.
+    ///
+    /// Requests that set their own options keep them unchanged; the two are
+    /// not merged.
+    pub fn with_default_options(mut self, options: ModelOptions) -> Self {
+        self.default_options = Some(options);
+        self
+    }
+
+    /// Sets the keep-alive used when a generate, chat, or embed request has none.
+    pub fn with_default_keep_alive(mut self, keep_alive: impl Into<KeepAliveSetting>) -> Self {
+        self.default_keep_alive = Some(keep_alive.into());
+        self
+    }
+
+    /// Returns the default model, if any
+    #[inline]
+    pub fn default_model(&self) -> Option<&str> {
+        self.default_model.as_deref()
+    }
+
+    /// Returns the default model options, if any
+    #[inline]
+    pub fn default_options(&self) -> Option<&ModelOptions> {
+        self.default_options.as_ref()
+    }
+
+    /// Returns the default keep-alive, if any
+    #[inline]
+    pub fn default_keep_alive(&self) -> Option<&KeepAliveSetting> {
+        self.default_keep_alive.as_ref()
+    }
+
+    /// Checks generate and chat requests against `budget` before sending.
+    ///
+    /// Requests whose estimated prompt plus output exceeds the context
+    /// window fail with [`Error::TokenBudgetExceeded`] or are truncated,
+    /// depending on the budget's [`OverflowPolicy`](super::OverflowPolicy).
+    pub fn with_token_budget(mut self, budget: TokenBudget) -> Self {
+        self.token_budget = Some(budget);
+        self
+    }
+
+    /// Returns the token budget, if any
+    #[inline]
+    pub fn token_budget(&self) -> Option<&TokenBudget> {
+        self.token_budget.as_ref()
+    }
+
+    /// Sends chat calls for models without a chat template through
+    /// `/api/generate`, with the messages rendered into a raw prompt by
+    /// `template`.
+    ///
+    /// Keeps one `chat` / `chat_blocking` call path for base and
+    /// instruction models alike. The first chat with each model asks
+    /// `/api/show` for its template; a model has none if the server reports
+    /// no template or only its `{{ .Prompt }}` default. The reply comes back
+    /// as an ordinary [`ChatResponse`](crate::ChatResponse) from the
+    /// assistant. Tools are not offered to fallback models, and streaming
+    /// chats always use `/api/chat`.
+    ///
+    /// Requires the `model` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ClientConfig, PromptTemplate};
+    ///
+    /// let template = PromptTemplate::parse(
+    ///     "{{ range .Messages }}### {{ .Role }}\n{{ .Content }}\n\n{{ end }}### assistant\n",
+    /// )?;
+    /// let config = ClientConfig::default().with_chat_fallback(template);
+    /// assert!(config.chat_fallback().is_some());
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    #[cfg(feature = "model")]
+    pub fn with_chat_fallback(mut self, template: PromptTemplate) -> Self {
+        self.chat_fallback = Some(template);
+        self
+    }
+
+    /// Returns the chat fallback template, if any
+    #[cfg(feature = "model")]
+    #[inline]
+    pub fn chat_fallback(&self) -> Option<&PromptTemplate> {
+        self.chat_fallback.as_ref()
+    }
+
+    /// Rewrite the text of every reply with `processor`.
+    ///
+    /// Applies to `chat`, `generate`, `chat_stream`, and their blocking
+    /// forms; streams are rewritten event by event with the same result as
+    /// the buffered calls. Raw streams are left alone. An empty chain turns
+    /// post-processing off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ClientConfig, PostProcessor};
+    ///
+    /// let config = ClientConfig::default()
+    ///     .with_post_processor(PostProcessor::new().strip_think().trim());
+    /// assert!(config.post_processor().is_some());
+    /// ```
+    pub fn with_post_processor(mut self, processor: PostProcessor) -> Self {
+        self.post_processor = (!processor.is_empty()).then_some(processor);
+        self
+    }
+
+    /// Returns the reply post-processor, if any
+    #[inline]
+    pub fn post_processor(&self) -> Option<&PostProcessor> {
+        self.post_processor.as_ref()
+    }
+
+    /// Wrap the system prompt of every chat and generate request in
+    /// `policy`.
+    ///
+    /// Applies to every chat and generate entry point, streaming and
+    /// blocking ones included. An empty policy turns it off. See
+    /// [`SystemPromptPolicy`] for how prompts are combined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ClientConfig, SystemPromptPolicy};
+    ///
+    /// let config = ClientConfig::default().with_system_prompt_policy(
+    ///     SystemPromptPolicy::new()
+    ///         .with_prefix("Follow the ACME acceptable use policy.")
+    ///         .with_current_date(),
+    /// );
+    /// assert!(config.system_prompt_policy().is_some());
+    /// ```
+    pub fn with_system_prompt_policy(mut self, policy: SystemPromptPolicy) -> Self {
+        self.system_prompt_policy = (!policy.is_empty()).then_some(policy);
+        self
+    }
+
+    /// Returns the system prompt policy, if any
+    #[inline]
+    pub fn system_prompt_policy(&self) -> Option<&SystemPromptPolicy> {
+        self.system_prompt_policy.as_ref()
+    }
+
+    /// Talk to Ollama through `gateway`, instead of detecting it.
+    ///
+    /// See [`GatewayProfile`] for the quirks each one adjusts; the API key
+    /// is sent in the header it expects.
+    pub fn with_gateway(mut self, gateway: GatewayProfile) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Returns the configured gateway, if any
+    #[inline]
+    pub fn gateway(&self) -> Option<GatewayProfile> {
+        self.gateway
+    }
+
+    /// The configured gateway, or the one the base URL implies
+    pub(crate) fn known_gateway(&self) -> Option<GatewayProfile> {
+        self.gateway
+            .or_else(|| GatewayProfile::from_base_url(&self.base))
+    }
+
+    /// Build full URL from base URL and endpoint path, as a string
+    ///
+    /// See [`endpoint_url`](Self::endpoint_url), which the client uses.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use ollama_oxide::ClientConfig;
     ///
     /// let config = ClientConfig::default();
@@ -176,6 +840,35 @@ impl ClientConfig {
     /// ```
     #[inline]
     pub fn url(&self, endpoint: &str) -> String {
-        format!("{}{}", self.base_url, endpoint)
+        self.endpoint_url(endpoint).into()
+    }
+
+    /// Build full URL from the cached base URL and an endpoint path
+    ///
+    /// The endpoint is appended to the base URL's path, so a base such as
+    /// `http://proxy/ollama` keeps its prefix. The base is parsed once, at
+    /// construction; only the path is rebuilt here.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::ClientConfig;
+    ///
+    /// let config = ClientConfig::with_base_url("http://proxy:8080/ollama/".to_string())?;
+    /// let url = config.endpoint_url("/api/chat");
+    /// assert_eq!(url.as_str(), "http://proxy:8080/ollama/api/chat");
+    /// # Ok::<(), ollama_oxide::Error>(())
+    /// ```
+    pub fn endpoint_url(&self, endpoint: &str) -> Url {
+        let (path, query) = match endpoint.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (endpoint, None),
+        };
+        let mut url = self.base.clone();
+        url.set_path(&[self.base.path().trim_end_matches('/'), path].concat());
+        if query.is_some() {
+            url.set_query(query);
+        }
+        url
     }
 }
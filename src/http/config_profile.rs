@@ -0,0 +1,268 @@
+//! One named target in a client configuration file
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ClientConfig, GatewayProfile};
+use crate::{Error, KeepAliveSetting, ModelOptions, Result};
+
+/// The serializable settings of a [`ClientConfig`], stored under a name in
+/// [`ConfigProfiles`](super::ConfigProfiles).
+///
+/// Every field is optional; unset fields keep the [`ClientConfig`]
+/// default. Durations are in seconds and may be fractional. Settings made
+/// of code, such as post-processors, system prompt policies, token
+/// budgets, JSON limits, model priorities, and chat fallback templates, are
+/// not part of a profile and are added to the built config as usual.
+///
+/// Prefer `api_key_env`, naming an environment variable that holds the
+/// key, over `api_key` in files that are shared or committed.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::ConfigProfile;
+///
+/// let profile: ConfigProfile = serde_json::from_str(
+///     r#"{ "base_url": "https://ollama.staging.internal", "timeout_secs": 90, "max_retries": 5 }"#,
+/// )?;
+/// let config = profile.to_config()?;
+/// assert_eq!(config.base_url(), "https://ollama.staging.internal");
+/// assert_eq!(config.max_retries(), 5);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigProfile {
+    /// Base URL of the server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+
+    /// Total per-request timeout, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<f64>,
+
+    /// Connect timeout, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<f64>,
+
+    /// Read timeout, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout_secs: Option<f64>,
+
+    /// Maximum retry attempts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+
+    /// API key sent with every request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Environment variable to read the API key from when the config is
+    /// built; takes precedence over `api_key` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+
+    /// Gateway in front of Ollama: `"direct"`, `"open_webui"`, or
+    /// `"litellm"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<GatewayProfile>,
+
+    /// Most calls running at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+
+    /// Largest serialized request body, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_bytes: Option<usize>,
+
+    /// Largest response body or streamed line, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes: Option<usize>,
+
+    /// Events a stream buffers ahead of its consumer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_buffer: Option<usize>,
+
+    /// Header carrying a per-request idempotency key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_header: Option<String>,
+
+    /// Whether identical concurrent deterministic calls are coalesced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coalesce_requests: Option<bool>,
+
+    /// Model for requests that name none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+
+    /// Options for requests that set none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_options: Option<ModelOptions>,
+
+    /// Keep-alive for requests that set none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_keep_alive: Option<KeepAliveSetting>,
+}
+
+/// `secs` as a duration, rejecting negative and non-finite values.
+fn seconds(field: &str, secs: f64) -> Result<Duration> {
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| Error::ValidationError(format!("profile {field} of {secs} is not valid")))
+}
+
+impl ConfigProfile {
+    /// Build a [`ClientConfig`] from the profile.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::InvalidUrlError`] if `base_url` is invalid
+    /// - [`Error::ValidationError`] if a timeout is negative or not finite,
+    ///   or `api_key_env` names a variable that is not set
+    pub fn to_config(&self) -> Result<ClientConfig> {
+        let defaults = ClientConfig::default();
+        let base_url = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| defaults.base_url().to_string());
+        let timeout = match self.timeout_secs {
+            Some(secs) => seconds("timeout_secs", secs)?,
+            None => defaults.timeout(),
+        };
+        let max_retries = self.max_retries.unwrap_or(defaults.max_retries());
+        let mut config = ClientConfig::new(base_url, timeout, max_retries)?;
+        if let Some(secs) = self.connect_timeout_secs {
+            config = config.with_connect_timeout(seconds("connect_timeout_secs", secs)?);
+        }
+        if let Some(secs) = self.read_timeout_secs {
+            config = config.with_read_timeout(seconds("read_timeout_secs", secs)?);
+        }
+        if let Some(gateway) = self.gateway {
+            config = config.with_gateway(gateway);
+        }
+        if let Some(name) = &self.api_key_env {
+            let key = std::env::var(name).map_err(|_| {
+                Error::ValidationError(format!("profile api_key_env {name} is not set"))
+            })?;
+            config = config.with_api_key(key);
+        } else if let Some(key) = &self.api_key {
+            config = config.with_api_key(key.clone());
+        }
+        if let Some(calls) = self.max_concurrency {
+            config = config.with_max_concurrency(calls);
+        }
+        if let Some(limit) = self.max_request_bytes {
+            config = config.with_max_request_bytes(limit);
+        }
+        if let Some(limit) = self.max_response_bytes {
+            config = config.with_max_response_bytes(limit);
+        }
+        if let Some(events) = self.stream_buffer {
+            config = config.with_stream_buffer(events);
+        }
+        if let Some(header) = &self.idempotency_header {
+            config = config.with_idempotency_header(header.clone());
+        }
+        if self.coalesce_requests == Some(true) {
+            config = config.with_request_coalescing();
+        }
+        if let Some(model) = &self.default_model {
+            config = config.with_default_model(model.clone());
+        }
+        if let Some(options) = &self.default_options {
+            config = config.with_default_options(options.clone());
+        }
+        if let Some(keep_alive) = &self.default_keep_alive {
+            config = config.with_default_keep_alive(keep_alive.clone());
+        }
+        Ok(config)
+    }
+}
+
+impl From<&ClientConfig> for ConfigProfile {
+    /// The profile that rebuilds `config`'s serializable settings. Every
+    /// field is written, defaults included, so the profile does not depend
+    /// on the defaults of the crate version that reads it.
+    fn from(config: &ClientConfig) -> Self {
+        Self {
+            base_url: Some(config.base_url().to_string()),
+            timeout_secs: Some(config.timeout().as_secs_f64()),
+            connect_timeout_secs: config.connect_timeout().map(|d| d.as_secs_f64()),
+            read_timeout_secs: config.read_timeout().map(|d| d.as_secs_f64()),
+            max_retries: Some(config.max_retries()),
+            api_key: config.api_key().map(str::to_string),
+            api_key_env: None,
+            gateway: config.gateway(),
+            max_concurrency: config.max_concurrency(),
+            max_request_bytes: config.max_request_bytes(),
+            max_response_bytes: config.max_response_bytes(),
+            stream_buffer: Some(config.stream_buffer()),
+            idempotency_header: config.idempotency_header().map(str::to_string),
+            coalesce_requests: Some(config.coalesces_requests()),
+            default_model: config.default_model().map(str::to_string),
+            default_options: config.default_options().cloned(),
+            default_keep_alive: config.default_keep_alive().cloned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_through_config() {
+        let config = ClientConfig::new(
+            "https://ollama.internal".to_string(),
+            Duration::from_millis(1500),
+            1,
+        )
+        .unwrap()
+        .with_gateway(GatewayProfile::LiteLlm)
+        .with_api_key("sk-1")
+        .with_max_concurrency(4)
+        .with_request_coalescing()
+        .with_default_model("qwen3:8b")
+        .with_default_keep_alive("10m");
+
+        let profile = ConfigProfile::from(&config);
+        let rebuilt = profile.to_config().unwrap();
+        assert_eq!(ConfigProfile::from(&rebuilt), profile);
+        assert_eq!(rebuilt.timeout(), Duration::from_millis(1500));
+        assert_eq!(rebuilt.api_key(), Some("sk-1"));
+    }
+
+    #[test]
+    fn test_empty_profile_is_default_config() {
+        let config = ConfigProfile::default().to_config().unwrap();
+        assert_eq!(
+            ConfigProfile::from(&config),
+            ConfigProfile::from(&ClientConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_values() {
+        let profile = ConfigProfile {
+            timeout_secs: Some(-1.0),
+            ..ConfigProfile::default()
+        };
+        assert!(matches!(
+            profile.to_config(),
+            Err(Error::ValidationError(_))
+        ));
+
+        let profile = ConfigProfile {
+            api_key_env: Some("OLLAMA_OXIDE_TEST_UNSET_KEY_VARIABLE".to_string()),
+            ..ConfigProfile::default()
+        };
+        assert!(matches!(
+            profile.to_config(),
+            Err(Error::ValidationError(_))
+        ));
+
+        let unknown = serde_json::from_str::<ConfigProfile>(r#"{ "base_uri": "x" }"#);
+        assert!(unknown.is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Named client configurations kept in one file
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{ClientConfig, ConfigProfile};
+use crate::{Error, Result};
+
+/// A set of named [`ConfigProfile`]s, such as `local`, `staging`, and
+/// `cloud`, saved together as one JSON file so a team can switch targets
+/// without code changes.
+///
+/// ```json
+/// {
+///   "default": "local",
+///   "profiles": {
+///     "local": { "base_url": "http://localhost:11434" },
+///     "staging": { "base_url": "https://ollama.staging.internal", "timeout_secs": 90 },
+///     "cloud": { "base_url": "https://ollama.com", "api_key_env": "OLLAMA_API_KEY" }
+///   }
+/// }
+/// ```
+///
+/// [`ClientConfig::profile`] builds one profile from the file, and
+/// [`ClientConfig::from_profile_env`] builds the one named by the
+/// `OLLAMA_OXIDE_PROFILE` environment variable, or the file's `default`.
+/// The file is read from the path in `OLLAMA_OXIDE_PROFILES`, or
+/// `ollama-profiles.json` in the working directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{ClientConfig, ConfigProfile, ConfigProfiles};
+///
+/// # fn example() -> ollama_oxide::Result<()> {
+/// // Export the current setup as a profile...
+/// let staging = ClientConfig::with_base_url("https://ollama.staging.internal".to_string())?;
+/// ConfigProfiles::new()
+///     .with_profile("local", ConfigProfile::default())
+///     .with_profile("staging", ConfigProfile::from(&staging))
+///     .with_default("local")
+///     .save("ollama-profiles.json")?;
+///
+/// // ...and select it elsewhere by name, or with OLLAMA_OXIDE_PROFILE=staging.
+/// let config = ClientConfig::profile("staging")?;
+/// let config = ClientConfig::from_profile_env()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigProfiles {
+    /// Profile used when `OLLAMA_OXIDE_PROFILE` is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Profiles by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ConfigProfile>,
+}
+
+impl ConfigProfiles {
+    /// Environment variable naming the profile to use
+    pub const PROFILE_ENV: &'static str = "OLLAMA_OXIDE_PROFILE";
+
+    /// Environment variable holding the path of the profiles file
+    pub const FILE_ENV: &'static str = "OLLAMA_OXIDE_PROFILES";
+
+    /// File read when `OLLAMA_OXIDE_PROFILES` is not set
+    pub const DEFAULT_FILE: &'static str = "ollama-profiles.json";
+
+    /// An empty set of profiles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path of the profiles file: `OLLAMA_OXIDE_PROFILES`, or
+    /// `ollama-profiles.json` in the working directory
+    pub fn path() -> PathBuf {
+        std::env::var_os(Self::FILE_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(Self::DEFAULT_FILE))
+    }
+
+    /// Read profiles from the JSON file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::StorageError`] if the file cannot be read
+    /// - [`Error::SerializationError`] if it is not a valid profiles file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|e| Error::storage(path, e))?;
+        serde_json::from_slice(&bytes).map_err(|e| {
+            Error::SerializationError(format!("{}: invalid profiles: {e}", path.display()))
+        })
+    }
+
+    /// Write the profiles as pretty-printed JSON to `path`, replacing the
+    /// file if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::StorageError`] if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec_pretty(self)?;
+        fs::write(path, json).map_err(|e| Error::storage(path, e))
+    }
+
+    /// Add or replace the profile called `name`.
+    pub fn with_profile(mut self, name: impl Into<String>, profile: ConfigProfile) -> Self {
+        self.profiles.insert(name.into(), profile);
+        self
+    }
+
+    /// Use `name` when `OLLAMA_OXIDE_PROFILE` is not set.
+    pub fn with_default(mut self, name: impl Into<String>) -> Self {
+        self.default = Some(name.into());
+        self
+    }
+
+    /// The profile called `name`, if any
+    pub fn get(&self, name: &str) -> Option<&ConfigProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Build the config of the profile called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if there is no such profile, or
+    /// any error from [`ConfigProfile::to_config`].
+    pub fn config(&self, name: &str) -> Result<ClientConfig> {
+        let profile = self.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            Error::ValidationError(format!(
+                "no config profile named '{name}' (available: {})",
+                known.join(", ")
+            ))
+        })?;
+        profile.to_config()
+    }
+
+    /// Name of the selected profile: `OLLAMA_OXIDE_PROFILE`, or the
+    /// default
+    pub fn selected_name(&self) -> Option<String> {
+        self.select(std::env::var(Self::PROFILE_ENV).ok())
+    }
+
+    fn select(&self, from_env: Option<String>) -> Option<String> {
+        from_env
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| self.default.clone())
+    }
+
+    /// Build the config of the [selected](Self::selected_name) profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if no profile is selected or the
+    /// selected one does not exist, or any error from
+    /// [`ConfigProfile::to_config`].
+    pub fn selected(&self) -> Result<ClientConfig> {
+        let name = self.selected_name().ok_or_else(|| {
+            Error::ValidationError(format!(
+                "no config profile selected: set {} or a default",
+                Self::PROFILE_ENV
+            ))
+        })?;
+        self.config(&name)
+    }
+}
+
+impl ClientConfig {
+    /// Build the config of the profile called `name` in the
+    /// [profiles file](ConfigProfiles::path).
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`ConfigProfiles::load`] or [`ConfigProfiles::config`].
+    pub fn profile(name: &str) -> Result<Self> {
+        ConfigProfiles::load(ConfigProfiles::path())?.config(name)
+    }
+
+    /// Build the config of the profile named by `OLLAMA_OXIDE_PROFILE`, or
+    /// the default one, in the [profiles file](ConfigProfiles::path).
+    ///
+    /// # Errors
+    ///
+    /// Any error from [`ConfigProfiles::load`] or
+    /// [`ConfigProfiles::selected`].
+    pub fn from_profile_env() -> Result<Self> {
+        ConfigProfiles::load(ConfigProfiles::path())?.selected()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profiles() -> ConfigProfiles {
+        ConfigProfiles::new()
+            .with_profile("local", ConfigProfile::default())
+            .with_profile(
+                "staging",
+                ConfigProfile {
+                    base_url: Some("https://ollama.staging.internal".to_string()),
+                    ..ConfigProfile::default()
+                },
+            )
+            .with_default("local")
+    }
+
+    #[test]
+    fn test_select_prefers_env() {
+        let profiles = profiles();
+        assert_eq!(
+            profiles.select(Some("staging".to_string())).as_deref(),
+            Some("staging")
+        );
+        assert_eq!(
+            profiles.select(Some(" ".to_string())).as_deref(),
+            Some("local")
+        );
+        assert_eq!(profiles.select(None).as_deref(), Some("local"));
+        assert_eq!(ConfigProfiles::new().select(None), None);
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_known() {
+        let err = profiles().config("prod").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Validation error: no config profile named 'prod' (available: local, staging)"
+        );
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let json = serde_json::to_value(profiles()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "default": "local",
+                "profiles": {
+                    "local": {},
+                    "staging": { "base_url": "https://ollama.staging.internal" }
+                }
+            })
+        );
+    }
+}
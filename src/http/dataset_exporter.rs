@@ -0,0 +1,323 @@
+//! Prompt/completion pairs from client traffic as a JSONL dataset
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Map, Value, json};
+
+use super::endpoints::Endpoint;
+use super::transcript_recorder::{decode, merge_chunk, now_ms};
+use super::{CallId, Observer};
+use crate::Error;
+
+type Redactor = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Response fields copied into each example's `metadata`.
+const METADATA_FIELDS: [&str; 4] = ["model", "done_reason", "prompt_eval_count", "eval_count"];
+
+/// Requests and streamed responses in flight, keyed by call.
+#[derive(Default)]
+struct Pending {
+    requests: HashMap<CallId, Value>,
+    responses: HashMap<CallId, Value>,
+    /// Completed pairs seen so far, exported or not
+    seen: u64,
+}
+
+/// [`Observer`] that turns generate and chat calls into a JSONL dataset of
+/// prompt/completion pairs, e.g. for fine-tuning.
+///
+/// Each successful call appends one line:
+///
+/// ```json
+/// {"prompt":"Why is the sky blue?","completion":"Rayleigh scattering.","metadata":{"endpoint":"generate","model":"qwen3:0.6b","eval_count":4,"ts_ms":1760000000000}}
+/// {"prompt":[{"role":"user","content":"Hi"}],"completion":"Hello!","metadata":{"endpoint":"chat","model":"qwen3:0.6b","ts_ms":1760000000950}}
+/// ```
+///
+/// Generate prompts are strings (the request's `system` goes into
+/// `metadata`); chat prompts are the request's message list. Streamed
+/// responses are reassembled before export. Failed calls are dropped.
+///
+/// [`with_sample_rate`](Self::with_sample_rate) keeps an evenly spaced
+/// fraction of pairs, and [`with_redactor`](Self::with_redactor) rewrites
+/// every prompt and completion text before it is written.
+///
+/// Requests and responses are paired by [`CallId`], so concurrent calls on
+/// one client each export their own pair. Write failures are ignored so
+/// exporting never fails an API call.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use ollama_oxide::http::DatasetExporter;
+/// use ollama_oxide::OllamaClient;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let exporter = DatasetExporter::create("dataset.jsonl")?
+///     .with_sample_rate(0.1)
+///     .with_redactor(|text| text.replace("hunter2", "[REDACTED]"));
+/// let client = OllamaClient::default()?.with_observer(Arc::new(exporter));
+/// # Ok(())
+/// # }
+/// ```
+pub struct DatasetExporter {
+    writer: Mutex<BufWriter<File>>,
+    pending: Mutex<Pending>,
+    sample_rate: f64,
+    redactor: Option<Redactor>,
+}
+
+impl DatasetExporter {
+    /// Open `path` for appending, creating it if needed.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+            pending: Mutex::new(Pending::default()),
+            sample_rate: 1.0,
+            redactor: None,
+        })
+    }
+
+    /// Export only a fraction `rate` (clamped to `0.0..=1.0`) of the pairs.
+    ///
+    /// Sampling is deterministic: with `0.25`, every fourth pair is kept.
+    pub fn with_sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = if rate.is_nan() {
+            0.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Pass every prompt and completion text through `redact` before it is
+    /// written, e.g. to mask emails or secrets.
+    pub fn with_redactor<F>(mut self, redact: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.redactor = Some(Arc::new(redact));
+        self
+    }
+
+    /// Fraction of pairs exported
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Whether pair number `n` (starting at 0) falls into the sample.
+    fn sampled(&self, n: u64) -> bool {
+        (((n + 1) as f64) * self.sample_rate).floor() > ((n as f64) * self.sample_rate).floor()
+    }
+
+    fn redact(&self, text: &str) -> Value {
+        match &self.redactor {
+            Some(redact) => Value::String(redact(text)),
+            None => Value::String(text.to_string()),
+        }
+    }
+
+    /// The dataset line for `request` and its complete `response`.
+    fn example(&self, endpoint: Endpoint, request: &Value, response: &Value) -> Option<Value> {
+        let mut metadata = Map::new();
+        let (prompt, completion) = match endpoint {
+            Endpoint::Generate => {
+                if let Some(system) = request.get("system").and_then(Value::as_str) {
+                    metadata.insert("system".to_string(), self.redact(system));
+                }
+                (
+                    self.redact(request.get("prompt")?.as_str()?),
+                    response.get("response")?.as_str()?,
+                )
+            }
+            _ => {
+                let mut messages = request.get("messages")?.clone();
+                for message in messages.as_array_mut()? {
+                    if let Some(content) = message.get_mut("content") {
+                        let text = content.as_str().unwrap_or_default().to_string();
+                        *content = self.redact(&text);
+                    }
+                }
+                (messages, response.get("message")?.get("content")?.as_str()?)
+            }
+        };
+
+        let name = endpoint.path().trim_start_matches("/api/");
+        metadata.insert("endpoint".to_string(), json!(name));
+        for field in METADATA_FIELDS {
+            if let Some(value) = response.get(field) {
+                metadata.insert(field.to_string(), value.clone());
+            }
+        }
+        metadata.insert("ts_ms".to_string(), json!(now_ms()));
+        Some(json!({
+            "prompt": prompt,
+            "completion": self.redact(completion),
+            "metadata": metadata,
+        }))
+    }
+
+    fn write(&self, example: &Value) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = serde_json::to_writer(&mut *writer, example);
+            let _ = writer.write_all(b"\n");
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// The endpoint of `url` if it is one the exporter records.
+fn exported_endpoint(url: &str) -> Option<Endpoint> {
+    let path = url::Url::parse(url).ok()?.path().to_string();
+    Endpoint::from_path(&path).filter(|e| matches!(e, Endpoint::Generate | Endpoint::Chat))
+}
+
+impl Observer for DatasetExporter {
+    fn on_request_body(&self, call: CallId, url: &str, body: &[u8]) {
+        if exported_endpoint(url).is_none() {
+            return;
+        }
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.requests.insert(call, decode(body));
+            pending.responses.remove(&call);
+        }
+    }
+
+    fn on_response_body(&self, call: CallId, url: &str, body: &[u8]) {
+        let Some(endpoint) = exported_endpoint(url) else {
+            return;
+        };
+        let body = decode(body);
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        if !pending.requests.contains_key(&call) {
+            return;
+        }
+
+        let response = match body.get("done").and_then(Value::as_bool) {
+            Some(false) => {
+                let total = pending.responses.entry(call).or_insert(Value::Null);
+                merge_chunk(total, body);
+                return;
+            }
+            _ => match pending.responses.remove(&call) {
+                Some(mut total) => {
+                    merge_chunk(&mut total, body);
+                    total
+                }
+                None => body,
+            },
+        };
+        let Some(request) = pending.requests.remove(&call) else {
+            return;
+        };
+        let n = pending.seen;
+        pending.seen += 1;
+        drop(pending);
+
+        if self.sampled(n)
+            && let Some(example) = self.example(endpoint, &request, &response)
+        {
+            self.write(&example);
+        }
+    }
+
+    fn on_error(&self, call: CallId, _url: &str, _error: &Error) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.requests.remove(&call);
+            pending.responses.remove(&call);
+        }
+    }
+}
+
+impl fmt::Debug for DatasetExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatasetExporter")
+            .field("sample_rate", &self.sample_rate)
+            .field("redactor", &self.redactor.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exporter(rate: f64) -> DatasetExporter {
+        let path = std::env::temp_dir().join(format!(
+            "ollama-oxide-dataset-unit-{}.jsonl",
+            std::process::id()
+        ));
+        DatasetExporter::create(path)
+            .unwrap()
+            .with_sample_rate(rate)
+    }
+
+    #[test]
+    fn test_sampled_is_evenly_spaced() {
+        let exporter = exporter(0.25);
+        let kept: Vec<u64> = (0..8).filter(|n| exporter.sampled(*n)).collect();
+        assert_eq!(kept, [3, 7]);
+        assert!((0..5).all(|n| self::exporter(1.0).sampled(n)));
+        assert!(!(0..5).any(|n| self::exporter(0.0).sampled(n)));
+    }
+
+    #[test]
+    fn test_example_redacts_chat_messages() {
+        let exporter = exporter(1.0).with_redactor(|t| t.replace("secret", "***"));
+        let request = json!({"model": "m", "messages": [{"role": "user", "content": "my secret"}]});
+        let response = json!({"model": "m", "message": {"role": "assistant", "content": "secret ok"},
+                              "done": true, "eval_count": 3});
+        let example = exporter
+            .example(Endpoint::Chat, &request, &response)
+            .unwrap();
+        assert_eq!(example["prompt"][0]["content"], "my ***");
+        assert_eq!(example["completion"], "*** ok");
+        assert_eq!(example["metadata"]["endpoint"], "chat");
+        assert_eq!(example["metadata"]["eval_count"], 3);
+    }
+
+    #[test]
+    fn test_interleaved_calls_keep_their_own_pairs() {
+        let path = std::env::temp_dir().join(format!(
+            "ollama-oxide-dataset-interleaved-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let exporter = DatasetExporter::create(&path).unwrap();
+        let url = "http://localhost:11434/api/generate";
+        let (a, b) = (CallId::next(), CallId::next());
+
+        exporter.on_request_body(a, url, br#"{"model":"m","prompt":"A?"}"#);
+        exporter.on_request_body(b, url, br#"{"model":"m","prompt":"B?"}"#);
+        exporter.on_response_body(a, url, br#"{"response":"a","done":false}"#);
+        exporter.on_response_body(b, url, br#"{"response":"b","done":false}"#);
+        exporter.on_response_body(b, url, br#"{"response":"b","done":true}"#);
+        exporter.on_response_body(a, url, br#"{"response":"a","done":true}"#);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let pairs: Vec<(String, String)> = written
+            .lines()
+            .map(|line| {
+                let example: Value = serde_json::from_str(line).unwrap();
+                let field = |name: &str| example[name].as_str().unwrap().to_string();
+                (field("prompt"), field("completion"))
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("B?".to_string(), "bb".to_string()),
+                ("A?".to_string(), "aa".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! `OllamaClient::embed_as`
+
+use super::OllamaClient;
+use super::endpoints::Endpoint;
+use super::request_defaults::with_defaults;
+use crate::{EmbedRequest, EmbedResponse, EmbeddingFloat, Result};
+
+impl OllamaClient {
+    /// [`embed`](super::OllamaApiAsync::embed) with the values parsed as
+    /// `F`; `embed` itself yields `f32`.
+    ///
+    /// Values are read straight from the JSON at the requested precision,
+    /// so `f64` avoids rounding through `f32` on the way.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`embed`](super::OllamaApiAsync::embed).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{EmbedRequest, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = EmbedRequest::new("nomic-embed-text", "Hello, world!");
+    /// let response = client.embed_as::<f64>(&request).await?;
+    /// let norm: f64 = response.embeddings[0].iter().map(|v| v * v).sum::<f64>().sqrt();
+    /// println!("norm: {norm}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn embed_as<F: EmbeddingFloat>(
+        &self,
+        request: &EmbedRequest,
+    ) -> Result<EmbedResponse<F>> {
+        let request = with_defaults(&self.config, request);
+        request.validate()?;
+        let target = self.target(Endpoint::Embed);
+        self.post_with_retry(&target, &*request).await
+    }
+
+    /// Blocking [`embed_as`](Self::embed_as).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`embed_blocking`](super::OllamaApiSync::embed_blocking).
+    pub fn embed_as_blocking<F: EmbeddingFloat>(
+        &self,
+        request: &EmbedRequest,
+    ) -> Result<EmbedResponse<F>> {
+        let request = with_defaults(&self.config, request);
+        request.validate()?;
+        let target = self.target(Endpoint::Embed);
+        self.post_blocking_with_retry(&target, &*request)
+    }
+}
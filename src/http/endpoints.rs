@@ -1,48 +1,202 @@
-//! API endpoint path constants
+//! Typed API endpoints
 //!
-//! This module defines all Ollama API endpoint paths as constants,
-//! providing a single source of truth for API routes.
+//! This module defines every Ollama API endpoint the client calls as an
+//! [`Endpoint`] variant, the single source of truth for its path, HTTP
+//! method, retry safety, sampling, and timeout class.
 
-/// API endpoint paths relative to base URL
-pub struct Endpoints;
+use reqwest::Method;
+use url::Url;
 
-#[allow(dead_code)]
-impl Endpoints {
-    /// GET /api/version - Get Ollama server version
-    pub const VERSION: &'static str = "/api/version";
+use super::{CallId, ClientConfig, Priority};
 
-    /// POST /api/generate - Generate text from prompt
-    pub const GENERATE: &'static str = "/api/generate";
+/// How long a request to an endpoint may take by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeoutClass {
+    /// The configured timeout
+    Standard,
+    /// The configured timeout, but at least the streaming minimum of 300
+    /// seconds; for model transfers and NDJSON streams
+    Long,
+}
 
+/// An Ollama API endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Endpoint {
+    /// GET /api/version - Get Ollama server version
+    Version,
+    /// POST /api/generate - Generate text from prompt
+    Generate,
     /// POST /api/chat - Generate chat messages
-    pub const CHAT: &'static str = "/api/chat";
-
+    Chat,
     /// POST /api/embed - Generate embeddings
-    pub const EMBED: &'static str = "/api/embed";
-
+    Embed,
     /// GET /api/tags - List available models
-    pub const TAGS: &'static str = "/api/tags";
-
+    Tags,
     /// GET /api/ps - List running models
-    pub const PS: &'static str = "/api/ps";
-
+    Ps,
     /// POST /api/show - Show model information
-    pub const SHOW: &'static str = "/api/show";
-
+    Show,
     /// POST /api/create - Create a model from Modelfile
-    pub const CREATE: &'static str = "/api/create";
-
+    Create,
     /// POST /api/copy - Copy a model
-    pub const COPY: &'static str = "/api/copy";
-
+    Copy,
     /// POST /api/pull - Pull a model from registry
-    pub const PULL: &'static str = "/api/pull";
-
+    Pull,
     /// POST /api/push - Push a model to registry
-    pub const PUSH: &'static str = "/api/push";
-
+    Push,
     /// DELETE /api/delete - Delete a model
-    pub const DELETE: &'static str = "/api/delete";
+    Delete,
+    /// POST /api/web_search - Search the web (ollama.com, API key required)
+    WebSearch,
+    /// POST /api/web_fetch - Fetch a web page (ollama.com, API key required)
+    WebFetch,
+}
+
+impl Endpoint {
+    /// Every endpoint, in declaration order
+    pub(crate) const ALL: [Endpoint; 14] = [
+        Endpoint::Version,
+        Endpoint::Generate,
+        Endpoint::Chat,
+        Endpoint::Embed,
+        Endpoint::Tags,
+        Endpoint::Ps,
+        Endpoint::Show,
+        Endpoint::Create,
+        Endpoint::Copy,
+        Endpoint::Pull,
+        Endpoint::Push,
+        Endpoint::Delete,
+        Endpoint::WebSearch,
+        Endpoint::WebFetch,
+    ];
+
+    /// Path relative to the base URL
+    pub(crate) const fn path(self) -> &'static str {
+        match self {
+            Endpoint::Version => "/api/version",
+            Endpoint::Generate => "/api/generate",
+            Endpoint::Chat => "/api/chat",
+            Endpoint::Embed => "/api/embed",
+            Endpoint::Tags => "/api/tags",
+            Endpoint::Ps => "/api/ps",
+            Endpoint::Show => "/api/show",
+            Endpoint::Create => "/api/create",
+            Endpoint::Copy => "/api/copy",
+            Endpoint::Pull => "/api/pull",
+            Endpoint::Push => "/api/push",
+            Endpoint::Delete => "/api/delete",
+            Endpoint::WebSearch => "/api/web_search",
+            Endpoint::WebFetch => "/api/web_fetch",
+        }
+    }
+
+    /// HTTP method the endpoint expects
+    pub(crate) const fn method(self) -> Method {
+        match self {
+            Endpoint::Version | Endpoint::Tags | Endpoint::Ps => Method::GET,
+            Endpoint::Delete => Method::DELETE,
+            _ => Method::POST,
+        }
+    }
+
+    /// Whether a request may be repeated after the server may have acted
+    /// on it.
+    ///
+    /// Creating and pushing a model redo the whole job when repeated, so
+    /// those are only retried when the server cannot have started them: on
+    /// connection failures and 429.
+    pub(crate) const fn is_idempotent(self) -> bool {
+        !matches!(self, Endpoint::Create | Endpoint::Push)
+    }
+
+    /// Whether the response is sampled from a model, so identical requests
+    /// may get different answers unless the temperature is 0 or a seed is
+    /// fixed.
+    pub(crate) const fn is_sampled(self) -> bool {
+        matches!(self, Endpoint::Generate | Endpoint::Chat)
+    }
+
+    /// Priority of calls that neither the client nor the model sets one
+    /// for: embeddings are usually background work.
+    pub(crate) const fn priority(self) -> Priority {
+        match self {
+            Endpoint::Embed => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
+
+    /// Default timeout class of non-streaming calls; NDJSON streams are
+    /// always [`TimeoutClass::Long`].
+    pub(crate) const fn timeout_class(self) -> TimeoutClass {
+        match self {
+            Endpoint::Create | Endpoint::Pull | Endpoint::Push => TimeoutClass::Long,
+            _ => TimeoutClass::Standard,
+        }
+    }
+
+    /// The endpoint a URL path ends with, ignoring any base path prefix
+    pub(crate) fn from_path(path: &str) -> Option<Self> {
+        let path = path.trim_end_matches('/');
+        Self::ALL.into_iter().find(|e| path.ends_with(e.path()))
+    }
+}
+
+/// A fully resolved request: method, URL, and the retry and timeout
+/// behavior of the endpoint it targets.
+///
+/// Every request helper on the client takes one, so retry, timeout, and
+/// observer logic never inspect paths. Each target is built for one call
+/// and carries its [`CallId`], so guardrail checks made before the request
+/// is sent report the same id as the request itself.
+#[derive(Debug, Clone)]
+pub(crate) struct Target {
+    pub(crate) call: CallId,
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    pub(crate) idempotent: bool,
+    pub(crate) sampled: bool,
+    pub(crate) priority: Priority,
+    pub(crate) timeout: TimeoutClass,
+}
+
+impl Target {
+    /// Target for a known endpoint on the configured server
+    pub(crate) fn new(config: &ClientConfig, endpoint: Endpoint) -> Self {
+        Self {
+            call: CallId::next(),
+            method: endpoint.method(),
+            url: config.endpoint_url(endpoint.path()),
+            idempotent: endpoint.is_idempotent(),
+            sampled: endpoint.is_sampled(),
+            priority: endpoint.priority(),
+            timeout: endpoint.timeout_class(),
+        }
+    }
+
+    /// Target for an arbitrary URL, timed like the read endpoints
+    ///
+    /// Only `GET` and `HEAD` are treated as idempotent: the server may have
+    /// acted on an unknown write before failing, so other methods are only
+    /// retried on connection failures and 429, like
+    /// [`Endpoint::is_idempotent`] does for create and push.
+    #[cfg(feature = "experimental")]
+    pub(crate) fn custom(method: Method, url: Url) -> Self {
+        Self {
+            call: CallId::next(),
+            idempotent: method == Method::GET || method == Method::HEAD,
+            method,
+            url,
+            sampled: false,
+            priority: Priority::Normal,
+            timeout: TimeoutClass::Standard,
+        }
+    }
+
+    /// The URL as observers see it
+    pub(crate) fn as_str(&self) -> &str {
+        self.url.as_str()
+    }
 }
 
 #[cfg(test)]
@@ -51,33 +205,98 @@ mod tests {
 
     #[test]
     fn test_all_endpoints_start_with_api() {
-        assert!(Endpoints::VERSION.starts_with("/api/"));
-        assert!(Endpoints::GENERATE.starts_with("/api/"));
-        assert!(Endpoints::CHAT.starts_with("/api/"));
-        assert!(Endpoints::EMBED.starts_with("/api/"));
-        assert!(Endpoints::TAGS.starts_with("/api/"));
-        assert!(Endpoints::PS.starts_with("/api/"));
-        assert!(Endpoints::SHOW.starts_with("/api/"));
-        assert!(Endpoints::CREATE.starts_with("/api/"));
-        assert!(Endpoints::COPY.starts_with("/api/"));
-        assert!(Endpoints::PULL.starts_with("/api/"));
-        assert!(Endpoints::PUSH.starts_with("/api/"));
-        assert!(Endpoints::DELETE.starts_with("/api/"));
+        for endpoint in Endpoint::ALL {
+            assert!(endpoint.path().starts_with("/api/"), "{endpoint:?}");
+        }
     }
 
     #[test]
     fn test_endpoint_values() {
-        assert_eq!(Endpoints::VERSION, "/api/version");
-        assert_eq!(Endpoints::GENERATE, "/api/generate");
-        assert_eq!(Endpoints::CHAT, "/api/chat");
-        assert_eq!(Endpoints::EMBED, "/api/embed");
-        assert_eq!(Endpoints::TAGS, "/api/tags");
-        assert_eq!(Endpoints::PS, "/api/ps");
-        assert_eq!(Endpoints::SHOW, "/api/show");
-        assert_eq!(Endpoints::CREATE, "/api/create");
-        assert_eq!(Endpoints::COPY, "/api/copy");
-        assert_eq!(Endpoints::PULL, "/api/pull");
-        assert_eq!(Endpoints::PUSH, "/api/push");
-        assert_eq!(Endpoints::DELETE, "/api/delete");
+        assert_eq!(Endpoint::Version.path(), "/api/version");
+        assert_eq!(Endpoint::Generate.path(), "/api/generate");
+        assert_eq!(Endpoint::Chat.path(), "/api/chat");
+        assert_eq!(Endpoint::Embed.path(), "/api/embed");
+        assert_eq!(Endpoint::Tags.path(), "/api/tags");
+        assert_eq!(Endpoint::Ps.path(), "/api/ps");
+        assert_eq!(Endpoint::Show.path(), "/api/show");
+        assert_eq!(Endpoint::Create.path(), "/api/create");
+        assert_eq!(Endpoint::Copy.path(), "/api/copy");
+        assert_eq!(Endpoint::Pull.path(), "/api/pull");
+        assert_eq!(Endpoint::Push.path(), "/api/push");
+        assert_eq!(Endpoint::Delete.path(), "/api/delete");
+        assert_eq!(Endpoint::WebSearch.path(), "/api/web_search");
+        assert_eq!(Endpoint::WebFetch.path(), "/api/web_fetch");
+    }
+
+    #[test]
+    fn test_endpoint_methods() {
+        assert_eq!(Endpoint::Version.method(), Method::GET);
+        assert_eq!(Endpoint::Tags.method(), Method::GET);
+        assert_eq!(Endpoint::Ps.method(), Method::GET);
+        assert_eq!(Endpoint::Delete.method(), Method::DELETE);
+        assert_eq!(Endpoint::Chat.method(), Method::POST);
+        assert_eq!(Endpoint::Copy.method(), Method::POST);
+    }
+
+    #[test]
+    fn test_only_create_and_push_are_not_idempotent() {
+        let not_idempotent: Vec<_> = Endpoint::ALL
+            .into_iter()
+            .filter(|e| !e.is_idempotent())
+            .collect();
+        assert_eq!(not_idempotent, [Endpoint::Create, Endpoint::Push]);
+    }
+
+    #[test]
+    fn test_only_generate_and_chat_are_sampled() {
+        let sampled: Vec<_> = Endpoint::ALL
+            .into_iter()
+            .filter(|e| e.is_sampled())
+            .collect();
+        assert_eq!(sampled, [Endpoint::Generate, Endpoint::Chat]);
+    }
+
+    #[test]
+    fn test_only_embeddings_default_to_low_priority() {
+        assert_eq!(Endpoint::Embed.priority(), Priority::Low);
+        assert_eq!(Endpoint::Chat.priority(), Priority::Normal);
+        assert_eq!(Endpoint::Generate.priority(), Priority::Normal);
+    }
+
+    #[test]
+    fn test_model_transfers_use_long_timeout() {
+        assert_eq!(Endpoint::Pull.timeout_class(), TimeoutClass::Long);
+        assert_eq!(Endpoint::Create.timeout_class(), TimeoutClass::Long);
+        assert_eq!(Endpoint::Chat.timeout_class(), TimeoutClass::Standard);
+    }
+
+    #[test]
+    fn test_from_path_ignores_base_prefix() {
+        assert_eq!(Endpoint::from_path("/api/chat"), Some(Endpoint::Chat));
+        assert_eq!(
+            Endpoint::from_path("/ollama/api/tags/"),
+            Some(Endpoint::Tags)
+        );
+        assert_eq!(Endpoint::from_path("/api/unknown"), None);
+    }
+
+    #[test]
+    fn test_target_joins_base_url() {
+        let config = ClientConfig::with_base_url("http://proxy/ollama".to_string()).unwrap();
+        let target = Target::new(&config, Endpoint::Delete);
+        assert_eq!(target.as_str(), "http://proxy/ollama/api/delete");
+        assert_eq!(target.method, Method::DELETE);
+        assert!(target.idempotent);
+    }
+
+    #[cfg(feature = "experimental")]
+    #[test]
+    fn test_custom_targets_retry_only_reads() {
+        let url = Url::parse("http://localhost:11434/api/new").unwrap();
+        assert!(Target::custom(Method::GET, url.clone()).idempotent);
+        assert!(Target::custom(Method::HEAD, url.clone()).idempotent);
+        assert!(!Target::custom(Method::POST, url.clone()).idempotent);
+        assert!(!Target::custom(Method::PUT, url.clone()).idempotent);
+        assert!(!Target::custom(Method::DELETE, url).idempotent);
     }
 }
@@ -0,0 +1,99 @@
+//! Structured context for a failed API call
+
+use std::time::Duration;
+
+use super::{CallId, Extensions};
+use crate::Error;
+
+/// Everything known about a failed call, handed to
+/// [`Observer::on_error_report`](super::Observer::on_error_report).
+///
+/// Lets error trackers group and tag failures by endpoint, model, and
+/// retry count without parsing error strings.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{ErrorReport, Observer};
+///
+/// struct Tracker;
+///
+/// impl Observer for Tracker {
+///     fn on_error_report(&self, report: &ErrorReport<'_>) {
+///         eprintln!(
+///             "{} model={:?} attempts={} after {:?}: {}",
+///             report.endpoint.unwrap_or(report.url),
+///             report.model,
+///             report.attempts,
+///             report.elapsed,
+///             report.chain().join(": "),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ErrorReport<'a> {
+    /// The call that failed, as passed to every other hook
+    pub call: CallId,
+    /// HTTP method of the call
+    pub method: &'a str,
+    /// Full request URL
+    pub url: &'a str,
+    /// API path of the endpoint, e.g. `/api/chat`; `None` for custom URLs
+    pub endpoint: Option<&'static str>,
+    /// Model named in the request body, if any
+    pub model: Option<&'a str>,
+    /// Attempts sent, including retries; 0 if the call failed before
+    /// sending, e.g. on validation
+    pub attempts: u32,
+    /// Time from the start of the call until it failed
+    pub elapsed: Duration,
+    /// The error the call failed with
+    pub error: &'a Error,
+    /// Values the call carried, set with
+    /// [`OllamaClient::with_extension`](super::OllamaClient::with_extension)
+    pub extensions: &'a Extensions,
+}
+
+impl ErrorReport<'_> {
+    /// The error's message followed by those of its sources, outermost
+    /// first.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = vec![self.error.to_string()];
+        let mut source = std::error::Error::source(self.error);
+        while let Some(e) = source {
+            chain.push(e.to_string());
+            source = e.source();
+        }
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_includes_sources() {
+        let error = Error::InvalidUrlError(url::ParseError::EmptyHost);
+        let report = ErrorReport {
+            call: CallId::next(),
+            method: "GET",
+            url: "http://localhost/api/version",
+            endpoint: Some("/api/version"),
+            model: None,
+            attempts: 1,
+            elapsed: Duration::ZERO,
+            error: &error,
+            extensions: &Extensions::new(),
+        };
+        assert_eq!(
+            report.chain(),
+            vec![
+                "Invalid URL: empty host".to_string(),
+                "empty host".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Typed values carried by a client's calls to its observers
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A map holding at most one value of each type, attached to calls with
+/// [`OllamaClient::with_extension`](super::OllamaClient::with_extension).
+///
+/// Observers read it in
+/// [`Observer::on_extensions`](super::Observer::on_extensions) and
+/// [`ErrorReport::extensions`](super::ErrorReport::extensions), which lets
+/// cross-cutting concerns such as tenant ids, trace context, or feature
+/// flags reach them without threading extra arguments through every call.
+/// Values are shared, so cloning the map is cheap.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::Extensions;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct TenantId(u32);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.insert(TenantId(7));
+/// assert_eq!(extensions.get::<TenantId>(), Some(&TenantId(7)));
+/// assert!(extensions.get::<String>().is_none());
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// An empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value`, replacing any earlier value of the same type.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// The value of type `T`, if any
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Whether a value of type `T` is stored
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Drop the value of type `T`; returns whether there was one.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Number of values stored
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no value is stored
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_value_per_type() {
+        let mut extensions = Extensions::new();
+        extensions.insert(1u32);
+        extensions.insert(2u32);
+        extensions.insert("flag");
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions.get::<u32>(), Some(&2));
+
+        let clone = extensions.clone();
+        assert!(extensions.remove::<u32>());
+        assert!(!extensions.remove::<u32>());
+        assert!(!extensions.contains::<u32>());
+        assert_eq!(clone.get::<&str>(), Some(&"flag"));
+    }
+}
@@ -0,0 +1,95 @@
+//! `FnGuardrail`: a guardrail made of plain closures
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{Guardrail, GuardrailAction};
+use crate::Result;
+
+type Check = Arc<dyn Fn(&str) -> GuardrailAction + Send + Sync>;
+
+/// A [`Guardrail`] built from synchronous closures, for checks that need no
+/// I/O such as pattern-based scrubbing or keyword lists.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{FnGuardrail, GuardrailAction, OllamaClient};
+/// use std::sync::Arc;
+///
+/// let scrub_emails = FnGuardrail::new("scrub-emails").on_input(|text| {
+///     if text.contains('@') {
+///         let words: Vec<&str> = text
+///             .split(' ')
+///             .map(|w| if w.contains('@') { "[email]" } else { w })
+///             .collect();
+///         GuardrailAction::Replace(words.join(" "))
+///     } else {
+///         GuardrailAction::Allow
+///     }
+/// });
+/// let client = OllamaClient::default()?.with_guardrail(Arc::new(scrub_emails));
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct FnGuardrail {
+    name: String,
+    input: Option<Check>,
+    output: Option<Check>,
+}
+
+impl FnGuardrail {
+    /// A guardrail called `name` that allows everything
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            input: None,
+            output: None,
+        }
+    }
+
+    /// Check prompts with `check`.
+    pub fn on_input<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&str) -> GuardrailAction + Send + Sync + 'static,
+    {
+        self.input = Some(Arc::new(check));
+        self
+    }
+
+    /// Check reply text with `check`.
+    pub fn on_output<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&str) -> GuardrailAction + Send + Sync + 'static,
+    {
+        self.output = Some(Arc::new(check));
+        self
+    }
+}
+
+#[async_trait]
+impl Guardrail for FnGuardrail {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn check_input(&self, text: &str) -> Result<GuardrailAction> {
+        Ok(self.input.as_ref().map(|f| f(text)).unwrap_or_default())
+    }
+
+    async fn check_output(&self, text: &str) -> Result<GuardrailAction> {
+        Ok(self.output.as_ref().map(|f| f(text)).unwrap_or_default())
+    }
+}
+
+impl fmt::Debug for FnGuardrail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnGuardrail")
+            .field("name", &self.name)
+            .field("input", &self.input.is_some())
+            .field("output", &self.output.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,158 @@
+//! Gateways in front of Ollama and the quirks the client adjusts for
+
+use std::fmt;
+
+use reqwest::header::{CONTENT_TYPE, HeaderMap};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The kind of server the client talks to, when Ollama sits behind a
+/// gateway that changes how it is reached.
+///
+/// Set one with [`ClientConfig::with_gateway`](super::ClientConfig::with_gateway),
+/// or leave it unset to have the client detect it:
+///
+/// - a base URL ending in `/ollama` is taken for Open WebUI's Ollama proxy
+/// - a response carrying `x-litellm-*` headers marks a LiteLLM proxy
+///
+/// [`OllamaClient::gateway`](super::OllamaClient::gateway) reports the
+/// result. The quirks handled are:
+///
+/// - **API key header**: LiteLLM reads it from `x-litellm-api-key`, which
+///   stays clear of proxies that reuse `Authorization`; the others use a
+///   bearer `Authorization` header. A key is only sent with the header of a
+///   profile known when the client is built, so set LiteLLM explicitly
+///   when it needs a key.
+/// - **Streaming format**: gateways may re-emit streams as server-sent
+///   events (`data: {...}` lines ending in `data: [DONE]`). Streams are
+///   read that way from a detected or configured gateway, and from any
+///   response sent as `text/event-stream`.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ClientConfig, GatewayProfile};
+///
+/// let config = ClientConfig::with_base_url("https://litellm.internal:4000".to_string())?
+///     .with_gateway(GatewayProfile::LiteLlm)
+///     .with_api_key("sk-1234");
+/// assert_eq!(config.gateway(), Some(GatewayProfile::LiteLlm));
+/// assert_eq!(GatewayProfile::LiteLlm.auth_header(), "x-litellm-api-key");
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum GatewayProfile {
+    /// Ollama itself, or a proxy that passes requests through unchanged
+    #[default]
+    Direct,
+    /// Open WebUI, whose Ollama proxy is mounted at `/ollama`
+    #[serde(rename = "open_webui")]
+    OpenWebUi,
+    /// LiteLLM proxy
+    LiteLlm,
+}
+
+impl GatewayProfile {
+    /// Name of the header carrying the API key
+    pub fn auth_header(self) -> &'static str {
+        match self {
+            Self::Direct | Self::OpenWebUi => "authorization",
+            Self::LiteLlm => "x-litellm-api-key",
+        }
+    }
+
+    /// Whether streams may arrive as server-sent events
+    pub(crate) fn frames_sse(self) -> bool {
+        self != Self::Direct
+    }
+
+    /// The gateway implied by where the client points, if any
+    pub(crate) fn from_base_url(base: &Url) -> Option<Self> {
+        base.path()
+            .trim_end_matches('/')
+            .ends_with("/ollama")
+            .then_some(Self::OpenWebUi)
+    }
+
+    /// The gateway that sent `headers`, if they give it away
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .keys()
+            .any(|name| name.as_str().starts_with("x-litellm-"))
+            .then_some(Self::LiteLlm)
+    }
+}
+
+impl fmt::Display for GatewayProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Direct => "Ollama",
+            Self::OpenWebUi => "Open WebUI",
+            Self::LiteLlm => "LiteLLM",
+        })
+    }
+}
+
+/// Whether a response is a server-sent event stream
+pub(crate) fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim_start().starts_with("text/event-stream"))
+}
+
+/// The JSON carried by one server-sent event line, or `None` for lines
+/// that carry none (event names, ids, comments, and the `[DONE]` marker).
+/// Lines that are not event fields pass through unchanged.
+pub(crate) fn sse_data(line: &[u8]) -> Option<&[u8]> {
+    if let Some(data) = line.strip_prefix(b"data:") {
+        let data = data.trim_ascii();
+        return (!data.is_empty() && data != b"[DONE]").then_some(data);
+    }
+    let fields = [&b"event:"[..], b"id:", b"retry:", b":"];
+    if fields.iter().any(|f| line.starts_with(f)) {
+        return None;
+    }
+    Some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_detects_open_webui_from_base_url() {
+        let url = Url::parse("http://localhost:3000/ollama/").unwrap();
+        assert_eq!(
+            GatewayProfile::from_base_url(&url),
+            Some(GatewayProfile::OpenWebUi)
+        );
+        let url = Url::parse("http://localhost:11434").unwrap();
+        assert_eq!(GatewayProfile::from_base_url(&url), None);
+    }
+
+    #[test]
+    fn test_detects_litellm_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(GatewayProfile::from_headers(&headers), None);
+        headers.insert("x-litellm-version", HeaderValue::from_static("1.60.0"));
+        assert_eq!(
+            GatewayProfile::from_headers(&headers),
+            Some(GatewayProfile::LiteLlm)
+        );
+    }
+
+    #[test]
+    fn test_sse_data() {
+        let data = |line: &'static str| sse_data(line.as_bytes());
+        assert_eq!(data("data: {\"a\":1} "), Some(&b"{\"a\":1}"[..]));
+        assert_eq!(data("data:{}"), Some(&b"{}"[..]));
+        assert_eq!(data("data: [DONE]"), None);
+        assert_eq!(data("event: message"), None);
+        assert_eq!(data(": keep-alive"), None);
+        assert_eq!(data("{\"a\":1}"), Some(&b"{\"a\":1}"[..]));
+    }
+}
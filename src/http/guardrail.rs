@@ -0,0 +1,376 @@
+//! Checks run on prompts before they are sent and on replies after they
+//! arrive
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::endpoints::Target;
+use super::observer::ObserverSet;
+use super::{GuardrailAction, OllamaClient, TextDelta};
+use crate::{ChatRequest, Error, GenerateRequest, Result};
+
+/// A check registered on a client with
+/// [`OllamaClient::with_guardrail`](super::OllamaClient::with_guardrail)
+/// that can block, rewrite, or annotate prompts before they are sent and
+/// replies after they arrive, e.g. to scrub personal data or refuse banned
+/// topics.
+///
+/// Both hooks are async so a guardrail can call out to a classifier or
+/// moderation model; purely local checks are easier to write as an
+/// [`FnGuardrail`](super::FnGuardrail). Every hook has an allowing default,
+/// so implement only what you need.
+///
+/// Guardrails run in registration order on the text left by the previous
+/// one, and the first [`Block`](GuardrailAction::Block) stops the call with
+/// [`Error::Blocked`]. An error returned by a hook fails the call with that
+/// error.
+///
+/// What is checked:
+/// - input: the content of every user message of a chat request and the
+///   prompt of a generate request, from every entry point (plain,
+///   streamed, raw, byte, and callback), after client defaults are
+///   applied; changes go into the request that is sent, not the caller's
+/// - output: the message content of a `chat` reply and the response text of
+///   a `generate` reply, after any
+///   [post-processing](crate::ClientConfig::with_post_processor)
+///
+/// Streams are checked on input only, since their output reaches the
+/// caller as it is generated. The blocking entry points run the hooks to completion on the calling thread.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use ollama_oxide::{Guardrail, GuardrailAction, OllamaClient};
+/// use std::sync::Arc;
+///
+/// /// Refuses prompts about one banned topic.
+/// struct NoPoker;
+///
+/// #[async_trait]
+/// impl Guardrail for NoPoker {
+///     fn name(&self) -> &str {
+///         "no-poker"
+///     }
+///
+///     async fn check_input(&self, text: &str) -> ollama_oxide::Result<GuardrailAction> {
+///         Ok(if text.to_lowercase().contains("poker") {
+///             GuardrailAction::Block("gambling is off topic".to_string())
+///         } else {
+///             GuardrailAction::Allow
+///         })
+///     }
+/// }
+///
+/// let client = OllamaClient::default()?.with_guardrail(Arc::new(NoPoker));
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[async_trait]
+pub trait Guardrail: Send + Sync {
+    /// Name reported in [`Error::Blocked`] and annotations
+    fn name(&self) -> &str;
+
+    /// Check a prompt about to be sent.
+    async fn check_input(&self, text: &str) -> Result<GuardrailAction> {
+        let _ = text;
+        Ok(GuardrailAction::Allow)
+    }
+
+    /// Check the text of a reply that arrived.
+    async fn check_output(&self, text: &str) -> Result<GuardrailAction> {
+        let _ = text;
+        Ok(GuardrailAction::Allow)
+    }
+}
+
+/// Which hook a check runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Input,
+    Output,
+}
+
+/// Requests whose prompts input guardrails check.
+pub(super) trait GuardedRequest: Clone {
+    /// The prompts, in order
+    fn prompts(&self) -> Vec<&str>;
+
+    /// The same prompts, for rewriting
+    fn prompts_mut(&mut self) -> Vec<&mut String>;
+}
+
+impl GuardedRequest for ChatRequest {
+    fn prompts(&self) -> Vec<&str> {
+        self.messages
+            .iter()
+            .filter(|m| m.is_user())
+            .map(|m| m.content.as_str())
+            .collect()
+    }
+
+    fn prompts_mut(&mut self) -> Vec<&mut String> {
+        self.messages
+            .iter_mut()
+            .filter(|m| m.is_user())
+            .map(|m| &mut m.content)
+            .collect()
+    }
+}
+
+impl GuardedRequest for GenerateRequest {
+    fn prompts(&self) -> Vec<&str> {
+        self.prompt.as_deref().into_iter().collect()
+    }
+
+    fn prompts_mut(&mut self) -> Vec<&mut String> {
+        self.prompt.as_mut().into_iter().collect()
+    }
+}
+
+/// Guardrails registered on a client; cheap to clone.
+#[derive(Clone, Default)]
+pub(crate) struct GuardrailSet {
+    guardrails: Vec<Arc<dyn Guardrail>>,
+}
+
+impl GuardrailSet {
+    pub(crate) fn push(&mut self, guardrail: Arc<dyn Guardrail>) {
+        self.guardrails.push(guardrail);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.guardrails.is_empty()
+    }
+
+    /// `text` after every guardrail's `stage` hook, or `None` if none
+    /// changed it.
+    async fn check(
+        &self,
+        stage: Stage,
+        target: &Target,
+        observers: &ObserverSet,
+        text: &str,
+    ) -> Result<Option<String>> {
+        let mut replaced: Option<String> = None;
+        for guardrail in &self.guardrails {
+            let current = replaced.as_deref().unwrap_or(text);
+            let action = match stage {
+                Stage::Input => guardrail.check_input(current).await?,
+                Stage::Output => guardrail.check_output(current).await?,
+            };
+            match action {
+                GuardrailAction::Allow => {}
+                GuardrailAction::Replace(text) => replaced = Some(text),
+                GuardrailAction::Annotate(note) => {
+                    observers.guardrail_note(target, guardrail.name(), &note);
+                }
+                GuardrailAction::Block(reason) => {
+                    return Err(Error::Blocked {
+                        guardrail: guardrail.name().to_string(),
+                        reason,
+                    });
+                }
+            }
+        }
+        Ok(replaced)
+    }
+
+    /// `request` with its prompts passed through the input hooks.
+    pub(super) async fn check_request<'a, R: GuardedRequest>(
+        &self,
+        target: &Target,
+        observers: &ObserverSet,
+        mut request: Cow<'a, R>,
+    ) -> Result<Cow<'a, R>> {
+        if self.is_empty() {
+            return Ok(request);
+        }
+        let mut replacements = Vec::new();
+        for (i, prompt) in request.prompts().into_iter().enumerate() {
+            if let Some(text) = self.check(Stage::Input, target, observers, prompt).await? {
+                replacements.push((i, text));
+            }
+        }
+        if !replacements.is_empty() {
+            let mut prompts = request.to_mut().prompts_mut();
+            for (i, text) in replacements {
+                *prompts[i] = text;
+            }
+        }
+        Ok(request)
+    }
+
+    /// `response` with its text passed through the output hooks.
+    pub(super) async fn check_response<T: TextDelta>(
+        &self,
+        target: &Target,
+        observers: &ObserverSet,
+        mut response: T,
+    ) -> Result<T> {
+        if self.is_empty() {
+            return Ok(response);
+        }
+        if let Some(text) = response.text_delta()
+            && let Some(text) = self.check(Stage::Output, target, observers, text).await?
+        {
+            response.set_text_delta(text);
+        }
+        Ok(response)
+    }
+}
+
+/// Run `check` to completion on the calling thread, for the blocking entry
+/// points.
+fn block_on<F: Future>(check: F) -> Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| Error::ApiError {
+            message: format!("cannot run guardrails: {e}"),
+        })?;
+    Ok(runtime.block_on(check))
+}
+
+impl OllamaClient {
+    /// A clone of this client that runs no guardrails, for guardrails that
+    /// call the server themselves.
+    #[cfg(feature = "conveniences")]
+    pub(crate) fn without_guardrails(&self) -> Self {
+        let mut client = self.clone();
+        client.guardrails = GuardrailSet::default();
+        client
+    }
+
+    /// Blocking counterpart of [`GuardrailSet::check_request`].
+    pub(super) fn guarded_request_blocking<'a, R: GuardedRequest>(
+        &self,
+        target: &Target,
+        request: Cow<'a, R>,
+    ) -> Result<Cow<'a, R>> {
+        if self.guardrails.is_empty() {
+            return Ok(request);
+        }
+        block_on(
+            self.guardrails
+                .check_request(target, &self.observers, request),
+        )?
+    }
+
+    /// Blocking counterpart of [`GuardrailSet::check_response`].
+    pub(super) fn guarded_response_blocking<T: TextDelta>(
+        &self,
+        target: &Target,
+        response: T,
+    ) -> Result<T> {
+        if self.guardrails.is_empty() {
+            return Ok(response);
+        }
+        block_on(
+            self.guardrails
+                .check_response(target, &self.observers, response),
+        )?
+    }
+}
+
+impl fmt::Debug for GuardrailSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.guardrails.iter().map(|g| g.name()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::endpoints::Endpoint;
+    use crate::{ChatMessage, ChatResponse, ClientConfig};
+
+    struct Upper;
+
+    #[async_trait]
+    impl Guardrail for Upper {
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        async fn check_input(&self, text: &str) -> Result<GuardrailAction> {
+            Ok(GuardrailAction::Replace(text.to_uppercase()))
+        }
+    }
+
+    struct NoSecrets;
+
+    #[async_trait]
+    impl Guardrail for NoSecrets {
+        fn name(&self) -> &str {
+            "no-secrets"
+        }
+
+        async fn check_input(&self, text: &str) -> Result<GuardrailAction> {
+            Ok(match text.contains("SECRET") {
+                true => GuardrailAction::Block("mentions a secret".to_string()),
+                false => GuardrailAction::Allow,
+            })
+        }
+
+        async fn check_output(&self, text: &str) -> Result<GuardrailAction> {
+            Ok(GuardrailAction::Replace(text.replace("42", "[redacted]")))
+        }
+    }
+
+    fn target() -> Target {
+        Target::new(&ClientConfig::default(), Endpoint::Chat)
+    }
+
+    fn set() -> GuardrailSet {
+        let mut set = GuardrailSet::default();
+        set.push(Arc::new(Upper));
+        set.push(Arc::new(NoSecrets));
+        set
+    }
+
+    #[tokio::test]
+    async fn test_rewrites_user_messages_only() {
+        let request = ChatRequest::new(
+            "qwen3",
+            [ChatMessage::system("be nice"), ChatMessage::user("hi")],
+        );
+        let checked = set()
+            .check_request(&target(), &ObserverSet::default(), Cow::Borrowed(&request))
+            .await
+            .unwrap();
+        assert_eq!(checked.messages[0].content, "be nice");
+        assert_eq!(checked.messages[1].content, "HI");
+        assert_eq!(request.messages[1].content, "hi");
+    }
+
+    #[tokio::test]
+    async fn test_later_guardrails_see_replacements() {
+        let request = GenerateRequest::new("qwen3", "the secret is out");
+        let err = set()
+            .check_request(&target(), &ObserverSet::default(), Cow::Borrowed(&request))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Blocked { ref guardrail, .. } if guardrail == "no-secrets"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_checks_reply_text() {
+        let response: ChatResponse = serde_json::from_str(
+            r#"{"message":{"role":"assistant","content":"it is 42"},"done":true}"#,
+        )
+        .unwrap();
+        let checked = set()
+            .check_response(&target(), &ObserverSet::default(), response)
+            .await
+            .unwrap();
+        assert_eq!(checked.content(), Some("it is [redacted]"));
+    }
+}
@@ -0,0 +1,21 @@
+//! What a guardrail decides about one prompt or reply
+
+/// The outcome of a [`Guardrail`](super::Guardrail) check on one prompt or
+/// reply.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum GuardrailAction {
+    /// Let the text through unchanged.
+    #[default]
+    Allow,
+    /// Let the text through with this replacement, e.g. with personal data
+    /// scrubbed. Later guardrails see the replacement.
+    Replace(String),
+    /// Let the text through unchanged, reporting this note to the client's
+    /// observers through
+    /// [`Observer::on_guardrail_note`](super::Observer::on_guardrail_note).
+    Annotate(String),
+    /// Stop the call with [`Error::Blocked`](crate::Error::Blocked) and
+    /// this reason.
+    Block(String),
+}
@@ -0,0 +1,109 @@
+//! Background health watcher for the Ollama server
+
+use std::mem;
+use std::time::Duration;
+
+use reqwest::Client;
+use tokio::sync::watch;
+
+use super::OllamaClient;
+use super::ServerStatus;
+use super::endpoints::{Endpoint, Target};
+use crate::VersionResponse;
+
+/// Shortest accepted probe interval, so a zero interval cannot spin.
+const MIN_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Probes `GET /api/version` once, without retries.
+async fn probe(client: &Client, target: &Target, timeout: Duration) -> ServerStatus {
+    let request = client.request(target.method.clone(), target.url.clone());
+    let response = match request.timeout(timeout).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            return ServerStatus::Down {
+                last_error: e.to_string(),
+            };
+        }
+    };
+
+    if !response.status().is_success() {
+        return ServerStatus::Degraded {
+            last_error: format!("HTTP status {}", response.status().as_u16()),
+        };
+    }
+
+    match response.json::<VersionResponse>().await {
+        Ok(v) => ServerStatus::Up { version: v.version },
+        Err(e) => ServerStatus::Degraded {
+            last_error: e.to_string(),
+        },
+    }
+}
+
+impl OllamaClient {
+    /// Watch server availability in the background.
+    ///
+    /// Spawns a task that probes `GET /api/version` every `interval` and
+    /// publishes the result on a [`watch`] channel. Receivers are only woken
+    /// when the server moves between up, degraded, and down, so dashboards
+    /// and failover logic can simply await [`watch::Receiver::changed`]. A
+    /// new error text or version for the same state is stored without waking
+    /// them. The initial value is [`ServerStatus::Unknown`] until the first
+    /// probe completes.
+    ///
+    /// Probes are never retried and use the client's configured timeout.
+    /// Intervals shorter than 10ms, including zero, are raised to 10ms.
+    /// The background task stops once every receiver has been dropped, or
+    /// when the client is [shut down](Self::shutdown).
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let mut status = client.watch_health(Duration::from_secs(10));
+    /// status.changed().await.ok();
+    /// println!("Ollama is up: {}", status.borrow().is_up());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_health(&self, interval: Duration) -> watch::Receiver<ServerStatus> {
+        let (tx, rx) = watch::channel(ServerStatus::Unknown);
+        let client = self.http();
+        let target = self.target(Endpoint::Version);
+        let timeout = self.config.timeout();
+        let lifecycle = self.lifecycle.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval.max(MIN_INTERVAL));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    _ = lifecycle.closed() => break,
+                    _ = ticker.tick() => {
+                        let status = match &client {
+                            Ok(client) => probe(client, &target, timeout).await,
+                            Err(e) => ServerStatus::Down {
+                                last_error: e.to_string(),
+                            },
+                        };
+                        tx.send_if_modified(|current| {
+                            let changed = mem::discriminant(current) != mem::discriminant(&status);
+                            *current = status;
+                            changed
+                        });
+                    }
+                }
+            }
+        });
+
+        rx
+    }
+}
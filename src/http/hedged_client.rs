@@ -0,0 +1,176 @@
+//! Hedged requests across replicated Ollama hosts
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use super::{OllamaApiAsync, OllamaClient};
+use crate::{EmbedRequest, EmbedResponse, Error, Result, VersionResponse};
+
+#[cfg(feature = "model")]
+use crate::ListResponse;
+
+/// Sends read-mostly calls to one replica and, if it is slow, a hedge
+/// request to the next; the first successful reply wins.
+///
+/// Primaries rotate round-robin across the replicas. When the primary has
+/// not answered within `hedge_after`, the same call goes to the next
+/// replica and both race; the loser's future is dropped, which cancels its
+/// HTTP request. A primary that fails before the threshold is hedged
+/// immediately.
+///
+/// Only idempotent calls should be hedged: the server may process both
+/// requests. Hedging is async-only.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{EmbedRequest, HedgedClient, OllamaClient};
+/// use std::time::Duration;
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let hedged = HedgedClient::new(
+///     vec![
+///         OllamaClient::with_base_url("http://gpu-a:11434")?,
+///         OllamaClient::with_base_url("http://gpu-b:11434")?,
+///     ],
+///     Duration::from_millis(150),
+/// )?;
+/// let response = hedged
+///     .embed(&EmbedRequest::new("nomic-embed-text", "hello"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HedgedClient {
+    replicas: Arc<[OllamaClient]>,
+    hedge_after: Duration,
+    next: Arc<AtomicUsize>,
+}
+
+impl HedgedClient {
+    /// Create a hedged client over `replicas`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if `replicas` is empty.
+    pub fn new(replicas: Vec<OllamaClient>, hedge_after: Duration) -> Result<Self> {
+        if replicas.is_empty() {
+            return Err(Error::ValidationError(
+                "HedgedClient needs at least one replica".to_string(),
+            ));
+        }
+        Ok(Self {
+            replicas: replicas.into(),
+            hedge_after,
+            next: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Get the replicas, in rotation order
+    pub fn replicas(&self) -> &[OllamaClient] {
+        &self.replicas
+    }
+
+    /// Get the delay before a hedge request is sent
+    pub fn hedge_after(&self) -> Duration {
+        self.hedge_after
+    }
+
+    /// Run `call` against a primary replica, hedging to the next one.
+    ///
+    /// The building block behind [`embed`](Self::embed) and friends; use it
+    /// for any other idempotent call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the hedge's error when both requests fail, or the primary's
+    /// error when there is a single replica.
+    pub async fn hedge<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: Fn(OllamaClient) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let count = self.replicas.len();
+        let first = self.next.fetch_add(1, Ordering::Relaxed) % count;
+        let primary = call(self.replicas[first].clone());
+        if count == 1 {
+            return primary.await;
+        }
+        let backup = || call(self.replicas[(first + 1) % count].clone());
+
+        tokio::pin!(primary);
+        tokio::select! {
+            result = &mut primary => match result {
+                Ok(value) => return Ok(value),
+                Err(_) => return backup().await,
+            },
+            _ = tokio::time::sleep(self.hedge_after) => {}
+        }
+
+        let hedge = backup();
+        tokio::pin!(hedge);
+        tokio::select! {
+            result = &mut primary => match result {
+                Ok(value) => Ok(value),
+                Err(_) => hedge.await,
+            },
+            result = &mut hedge => match result {
+                Ok(value) => Ok(value),
+                Err(e) => primary.await.map_err(|_| e),
+            },
+        }
+    }
+
+    /// Hedged [`embed`](OllamaApiAsync::embed)
+    pub async fn embed(&self, request: &EmbedRequest) -> Result<EmbedResponse> {
+        self.hedge(|client| async move { client.embed(request).await })
+            .await
+    }
+
+    /// Hedged [`version`](OllamaApiAsync::version)
+    pub async fn version(&self) -> Result<VersionResponse> {
+        self.hedge(|client| async move { client.version().await })
+            .await
+    }
+
+    /// Hedged [`list_models`](OllamaApiAsync::list_models)
+    #[cfg(feature = "model")]
+    pub async fn list_models(&self) -> Result<ListResponse> {
+        self.hedge(|client| async move { client.list_models().await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_rejects_empty() {
+        assert!(matches!(
+            HedgedClient::new(Vec::new(), Duration::from_millis(10)),
+            Err(Error::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_primary_rotates() {
+        let replicas = vec![
+            OllamaClient::with_base_url("http://a:1").unwrap(),
+            OllamaClient::with_base_url("http://b:1").unwrap(),
+        ];
+        let hedged = HedgedClient::new(replicas, Duration::from_secs(60)).unwrap();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            let url = hedged
+                .hedge(|client| async move { Ok(client.config.base_url().to_string()) })
+                .await
+                .unwrap();
+            seen.push(url);
+        }
+        assert_eq!(seen, vec!["http://a:1", "http://b:1", "http://a:1"]);
+    }
+}
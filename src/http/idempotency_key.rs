@@ -0,0 +1,63 @@
+//! Random keys identifying one logical request across retries
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Distinguishes keys generated within the same clock tick.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 64 random bits from a freshly seeded hasher, mixed with the time and a
+/// process-wide counter.
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+/// A new random key formatted as a version 4 UUID.
+pub(crate) fn new_key() -> String {
+    let bits = (u128::from(random_u64()) << 64) | u128::from(random_u64());
+    // Set the version (4) and variant (10xx) bits.
+    let bits = (bits & !(0xf << 76) & !(0x3 << 62)) | (0x4 << 76) | (0x2 << 62);
+    let hex = format!("{bits:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_key_is_uuid_v4() {
+        let key = new_key();
+        assert_eq!(key.len(), 36);
+        let parts: Vec<&str> = key.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('4'));
+        assert!(matches!(&parts[3][..1], "8" | "9" | "a" | "b"));
+    }
+
+    #[test]
+    fn test_new_key_is_unique() {
+        let keys: std::collections::HashSet<String> = (0..1000).map(|_| new_key()).collect();
+        assert_eq!(keys.len(), 1000);
+    }
+}
@@ -0,0 +1,112 @@
+//! Encoding requests with image files and readers off the async runtime
+
+use bytes::Bytes;
+use serde::Serialize;
+
+use super::OllamaClient;
+use super::client::RequestBody;
+use crate::{ChatRequest, Error, GenerateRequest, ImageData, Result};
+
+/// Requests that can carry [`ImageData`] backed by a file or a reader.
+pub(super) trait ImageRequest: Serialize + Clone + Send + 'static {
+    /// Whether encoding the request reads an image file or reader
+    fn needs_image_io(&self) -> bool;
+}
+
+/// Whether encoding `image` reads a file or a reader
+fn needs_io(image: &ImageData) -> bool {
+    matches!(image, ImageData::File(_) | ImageData::Reader(_))
+}
+
+impl ImageRequest for ChatRequest {
+    fn needs_image_io(&self) -> bool {
+        self.messages
+            .iter()
+            .filter_map(|m| m.images.as_ref())
+            .flatten()
+            .any(needs_io)
+    }
+}
+
+impl ImageRequest for GenerateRequest {
+    fn needs_image_io(&self) -> bool {
+        self.images.iter().flatten().any(needs_io)
+    }
+}
+
+/// A request body for the async helpers, encoded on a blocking thread when
+/// it reads image files or readers.
+pub(super) enum ImageBody<'a, R> {
+    /// No image I/O: encoded in place, like any other body
+    Plain(&'a R),
+    /// Already encoded by [`image_body`]
+    Encoded(Bytes),
+}
+
+impl<R: Serialize> RequestBody for ImageBody<'_, R> {
+    fn encode(&self, client: &OllamaClient) -> Result<Bytes> {
+        match self {
+            Self::Plain(request) => client.encode_body(*request),
+            Self::Encoded(payload) => Ok(payload.clone()),
+        }
+    }
+}
+
+/// The body for `request`, with its image files and readers read and
+/// base64-encoded on a blocking thread, chunk by chunk, so the runtime does
+/// no file I/O and no image is held in memory twice.
+///
+/// # Errors
+///
+/// - [`Error::SerializationError`] if an image cannot be read
+/// - [`Error::RequestTooLarge`] if the body exceeds `max_request_bytes`
+pub(super) async fn image_body<'a, R: ImageRequest>(
+    client: &OllamaClient,
+    request: &'a R,
+) -> Result<ImageBody<'a, R>> {
+    if !request.needs_image_io() {
+        return Ok(ImageBody::Plain(request));
+    }
+    let client = client.clone();
+    let request = request.clone();
+    tokio::task::spawn_blocking(move || client.encode_body(&request))
+        .await
+        .map_err(|e| Error::SerializationError(format!("cannot encode request: {e}")))?
+        .map(ImageBody::Encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChatMessage;
+
+    #[tokio::test]
+    async fn test_encodes_readers_off_runtime_and_borrows_otherwise() {
+        let client = OllamaClient::default().unwrap();
+        let reader = ImageData::from_reader(std::io::Cursor::new(b"hello".to_vec()));
+        let request = ChatRequest::new("m", [ChatMessage::user("?").with_image(reader)]);
+        let body = image_body(&client, &request).await.unwrap();
+        let ImageBody::Encoded(payload) = &body else {
+            panic!("reader was not encoded up front");
+        };
+        assert!(
+            std::str::from_utf8(payload)
+                .unwrap()
+                .contains("\"aGVsbG8=\"")
+        );
+        assert_eq!(body.encode(&client).unwrap(), payload);
+
+        let plain = ChatRequest::new("m", [ChatMessage::user("?")]);
+        let body = image_body(&client, &plain).await.unwrap();
+        assert!(matches!(body, ImageBody::Plain(_)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_fails() {
+        let client = OllamaClient::default().unwrap();
+        let request = GenerateRequest::new("m", "?")
+            .with_image(ImageData::from_path("/nonexistent/image.png"));
+        let err = image_body(&client, &request).await.err().unwrap();
+        assert!(err.to_string().contains("/nonexistent/image.png"));
+    }
+}
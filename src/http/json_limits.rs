@@ -0,0 +1,161 @@
+//! Nesting depth and string size guards for response JSON
+
+use crate::{Error, Result};
+
+/// Structural limits checked on every response body and streamed line
+/// before it is deserialized.
+///
+/// A hostile or broken proxy can send JSON that is small on the wire but
+/// expensive to decode: thousands of nested arrays, or one huge string.
+/// With limits set, such input fails with [`Error::ResponseTooLarge`]
+/// before serde allocates anything for it. Limits are checked in a single
+/// pass over the raw bytes; by default nothing is limited.
+///
+/// String sizes are measured as encoded, escapes included, and apply to
+/// object keys as well as values.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ClientConfig, JsonLimits};
+///
+/// let config = ClientConfig::default().with_json_limits(
+///     JsonLimits::new()
+///         .with_max_depth(32)
+///         .with_max_string_bytes(4 * 1024 * 1024),
+/// );
+/// assert_eq!(config.json_limits().max_depth(), Some(32));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonLimits {
+    max_depth: Option<usize>,
+    max_string_bytes: Option<usize>,
+}
+
+impl JsonLimits {
+    /// No limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject JSON with more than `depth` levels of nested objects and
+    /// arrays. A flat object has depth 1.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Reject JSON containing a string longer than `bytes`.
+    pub fn with_max_string_bytes(mut self, bytes: usize) -> Self {
+        self.max_string_bytes = Some(bytes);
+        self
+    }
+
+    /// Maximum nesting depth, if any
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Maximum string size in bytes, if any
+    pub fn max_string_bytes(&self) -> Option<usize> {
+        self.max_string_bytes
+    }
+
+    /// Whether any limit is set
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.max_depth.is_none() && self.max_string_bytes.is_none()
+    }
+
+    /// Checks `json` against the limits without parsing it.
+    ///
+    /// Only the structure is inspected; malformed JSON that stays within
+    /// the limits passes and is left for the deserializer to reject.
+    pub(crate) fn check(&self, json: &[u8]) -> Result<()> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+        let max_depth = self.max_depth.unwrap_or(usize::MAX);
+        let max_string = self.max_string_bytes.unwrap_or(usize::MAX);
+
+        let mut depth = 0usize;
+        // Start of the string being scanned, if inside one
+        let mut string_start: Option<usize> = None;
+        let mut escaped = false;
+        for (i, &b) in json.iter().enumerate() {
+            if let Some(start) = string_start {
+                if i - start > max_string {
+                    return Err(Error::ResponseTooLarge { limit: max_string });
+                }
+                if escaped {
+                    escaped = false;
+                } else if b == b'\\' {
+                    escaped = true;
+                } else if b == b'"' {
+                    string_start = None;
+                }
+                continue;
+            }
+            match b {
+                b'"' => string_start = Some(i + 1),
+                b'{' | b'[' => {
+                    depth += 1;
+                    if depth > max_depth {
+                        return Err(Error::ResponseTooLarge { limit: max_depth });
+                    }
+                }
+                b'}' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> String {
+        format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+    }
+
+    #[test]
+    fn test_unlimited_accepts_anything() {
+        assert!(JsonLimits::new().check(nested(10_000).as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let limits = JsonLimits::new().with_max_depth(3);
+        assert!(limits.check(nested(3).as_bytes()).is_ok());
+        assert!(matches!(
+            limits.check(nested(4).as_bytes()),
+            Err(Error::ResponseTooLarge { limit: 3 })
+        ));
+        assert!(limits.check(br#"{"a":{"b":[1]},"c":{"d":[2]}}"#).is_ok());
+    }
+
+    #[test]
+    fn test_brackets_inside_strings_are_ignored() {
+        let limits = JsonLimits::new().with_max_depth(1);
+        assert!(limits.check(br#"{"a":"[[[{{{\"]]]"}"#).is_ok());
+    }
+
+    #[test]
+    fn test_string_limit() {
+        let limits = JsonLimits::new().with_max_string_bytes(5);
+        assert!(limits.check(br#"{"key":"12345"}"#).is_ok());
+        assert!(matches!(
+            limits.check(br#"{"key":"123456"}"#),
+            Err(Error::ResponseTooLarge { limit: 5 })
+        ));
+        // Escapes count as encoded, and an escaped quote does not end the string
+        assert!(limits.check(br#"{"k":"\"\"\""}"#).is_err());
+    }
+
+    #[test]
+    fn test_unterminated_string_is_bounded() {
+        let limits = JsonLimits::new().with_max_string_bytes(4);
+        assert!(limits.check(br#"{"k":"abcdefgh"#).is_err());
+    }
+}
@@ -0,0 +1,364 @@
+//! Rolling latency percentiles per endpoint and model
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::endpoints::Endpoint;
+use super::latency_summary::{LatencySummary, Percentiles};
+use super::{CallId, Observer};
+use crate::Error;
+
+type Key = (String, Option<String>);
+
+/// One completed call.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    ttft: Option<Duration>,
+    total: Duration,
+}
+
+/// A call observed by the [`Observer`] impl that has not finished yet.
+#[derive(Debug)]
+struct InFlight {
+    started: Instant,
+    model: Option<String>,
+    streaming: bool,
+    ttft: Option<Duration>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    samples: HashMap<Key, VecDeque<Sample>>,
+    in_flight: HashMap<CallId, InFlight>,
+}
+
+/// Endpoint path of `url`, e.g. `/api/chat`, without any base path prefix
+/// for known endpoints.
+fn endpoint(url: &str) -> String {
+    let Ok(url) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    match Endpoint::from_path(url.path()) {
+        Some(endpoint) => endpoint.path().to_string(),
+        None => url.path().to_string(),
+    }
+}
+
+/// Collects p50/p95/p99 of time-to-first-token and total duration per
+/// endpoint and model over a sliding time window.
+///
+/// Register it as an [`Observer`] to record every call automatically, or
+/// call [`record`](Self::record) yourself. Query it at any time with
+/// [`summary`](Self::summary) or [`snapshot`](Self::snapshot), e.g. from a
+/// dashboard handler.
+///
+/// As an observer, the model comes from the request body's `model` field
+/// and the total duration ends when the last line of a streaming response
+/// arrives, or when a non-streaming response arrives. Calls are told apart
+/// by their [`CallId`], so concurrent calls to one endpoint are each
+/// recorded against their own model. Failed calls are not recorded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use ollama_oxide::http::LatencyStats;
+/// use ollama_oxide::OllamaClient;
+///
+/// # fn example() -> ollama_oxide::Result<()> {
+/// let stats = Arc::new(LatencyStats::new(Duration::from_secs(300)));
+/// let client = OllamaClient::default()?.with_observer(stats.clone());
+///
+/// // ... later, from a dashboard:
+/// for summary in stats.snapshot() {
+///     println!("{summary}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LatencyStats {
+    window: Duration,
+    state: Mutex<State>,
+}
+
+impl LatencyStats {
+    /// Keep samples for `window` after they are recorded.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// The sliding window length.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record one completed call.
+    pub fn record(
+        &self,
+        endpoint: &str,
+        model: Option<&str>,
+        ttft: Option<Duration>,
+        total: Duration,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state
+            .samples
+            .entry((endpoint.to_string(), model.map(str::to_string)))
+            .or_default()
+            .push_back(Sample {
+                at: Instant::now(),
+                ttft,
+                total,
+            });
+    }
+
+    /// Percentiles for one endpoint and model, or `None` if no call in the
+    /// window matched.
+    pub fn summary(&self, endpoint: &str, model: Option<&str>) -> Option<LatencySummary> {
+        let mut state = self.state.lock().ok()?;
+        self.prune(&mut state);
+        let key = (endpoint.to_string(), model.map(str::to_string));
+        state.samples.get(&key).and_then(|s| summarize(&key, s))
+    }
+
+    /// Percentiles for every endpoint and model seen in the window, sorted
+    /// by endpoint then model.
+    pub fn snapshot(&self) -> Vec<LatencySummary> {
+        let Ok(mut state) = self.state.lock() else {
+            return Vec::new();
+        };
+        self.prune(&mut state);
+        let mut out: Vec<LatencySummary> = state
+            .samples
+            .iter()
+            .filter_map(|(key, samples)| summarize(key, samples))
+            .collect();
+        out.sort_by(|a, b| (&a.endpoint, &a.model).cmp(&(&b.endpoint, &b.model)));
+        out
+    }
+
+    /// Discard every sample.
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.samples.clear();
+        }
+    }
+
+    fn prune(&self, state: &mut State) {
+        let Some(cutoff) = Instant::now().checked_sub(self.window) else {
+            return;
+        };
+        state.samples.retain(|_, samples| {
+            while samples.front().is_some_and(|s| s.at < cutoff) {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+    }
+
+    /// Finish in-flight `call` to `url` if `done` says it ended.
+    fn finish(&self, call: CallId, url: &str, done: impl Fn(&InFlight) -> Option<Duration>) {
+        let finished = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let Some(total) = state.in_flight.get(&call).and_then(done) else {
+                return;
+            };
+            let call = state.in_flight.remove(&call).expect("call is in flight");
+            (call, total)
+        };
+        let (call, total) = finished;
+        self.record(&endpoint(url), call.model.as_deref(), call.ttft, total);
+    }
+}
+
+fn summarize(key: &Key, samples: &VecDeque<Sample>) -> Option<LatencySummary> {
+    Some(LatencySummary {
+        endpoint: key.0.clone(),
+        model: key.1.clone(),
+        count: samples.len(),
+        ttft: Percentiles::of(samples.iter().filter_map(|s| s.ttft)),
+        total: Percentiles::of(samples.iter().map(|s| s.total))?,
+    })
+}
+
+impl Observer for LatencyStats {
+    fn on_request_start(&self, call: CallId, _method: &str, _url: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.in_flight.insert(
+                call,
+                InFlight {
+                    started: Instant::now(),
+                    model: None,
+                    streaming: false,
+                    ttft: None,
+                },
+            );
+        }
+    }
+
+    fn on_request_body(&self, call: CallId, _url: &str, body: &[u8]) {
+        let Ok(body) = serde_json::from_slice::<Value>(body) else {
+            return;
+        };
+        if let Ok(mut state) = self.state.lock()
+            && let Some(call) = state.in_flight.get_mut(&call)
+        {
+            call.model = body
+                .get("model")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            call.streaming = body.get("stream").and_then(Value::as_bool) == Some(true);
+        }
+    }
+
+    fn on_first_token(&self, call: CallId, _url: &str, latency: Duration) {
+        if let Ok(mut state) = self.state.lock()
+            && let Some(call) = state.in_flight.get_mut(&call).filter(|c| c.streaming)
+        {
+            call.ttft = Some(latency);
+        }
+    }
+
+    fn on_complete(&self, call: CallId, url: &str, status: u16, elapsed: Duration) {
+        if (200..300).contains(&status) {
+            self.finish(call, url, |c| (!c.streaming).then_some(elapsed));
+        }
+    }
+
+    fn on_response_body(&self, call: CallId, url: &str, body: &[u8]) {
+        let done = serde_json::from_slice::<Value>(body)
+            .ok()
+            .and_then(|v| v.get("done").and_then(Value::as_bool))
+            .unwrap_or(false);
+        if done {
+            self.finish(call, url, |c| c.streaming.then(|| c.started.elapsed()));
+        }
+    }
+
+    fn on_error(&self, call: CallId, _url: &str, _error: &Error) {
+        if let Ok(mut state) = self.state.lock() {
+            state.in_flight.remove(&call);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_record_and_summary() {
+        let stats = LatencyStats::new(Duration::from_secs(60));
+        for n in 1..=10 {
+            stats.record("/api/chat", Some("m"), Some(ms(n)), ms(n * 10));
+        }
+        stats.record("/api/embed", None, None, ms(5));
+
+        let chat = stats.summary("/api/chat", Some("m")).unwrap();
+        assert_eq!(chat.count, 10);
+        assert_eq!(chat.total.p50, ms(50));
+        assert_eq!(chat.ttft.unwrap().p99, ms(10));
+
+        let embed = stats.summary("/api/embed", None).unwrap();
+        assert_eq!(embed.ttft, None);
+        assert_eq!(stats.summary("/api/chat", Some("other")), None);
+
+        let all = stats.snapshot();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].endpoint, "/api/chat");
+    }
+
+    #[test]
+    fn test_endpoint_strips_base_path() {
+        assert_eq!(endpoint("http://proxy/ollama/api/chat"), "/api/chat");
+        assert_eq!(endpoint("http://localhost:11434/api/new"), "/api/new");
+    }
+
+    #[test]
+    fn test_window_prunes_old_samples() {
+        let stats = LatencyStats::new(Duration::ZERO);
+        stats.record("/api/chat", None, None, ms(1));
+        std::thread::sleep(ms(2));
+        assert!(stats.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_observer_non_streaming_call() {
+        let stats = LatencyStats::new(Duration::from_secs(60));
+        let url = "http://localhost:11434/api/generate";
+        let call = CallId::next();
+        stats.on_request_start(call, "POST", url);
+        stats.on_request_body(call, url, br#"{"model":"m","prompt":"x","stream":false}"#);
+        stats.on_complete(call, url, 200, ms(40));
+
+        let summary = stats.summary("/api/generate", Some("m")).unwrap();
+        assert_eq!(summary.total.p50, ms(40));
+        assert_eq!(summary.ttft, None);
+    }
+
+    #[test]
+    fn test_observer_streaming_call_waits_for_done() {
+        let stats = LatencyStats::new(Duration::from_secs(60));
+        let url = "http://localhost:11434/api/chat";
+        let call = CallId::next();
+        stats.on_request_start(call, "POST", url);
+        stats.on_request_body(call, url, br#"{"model":"m","messages":[],"stream":true}"#);
+        stats.on_complete(call, url, 200, ms(1));
+        stats.on_first_token(call, url, ms(3));
+        stats.on_response_body(call, url, br#"{"done":false}"#);
+        assert!(stats.snapshot().is_empty());
+
+        stats.on_response_body(call, url, br#"{"done":true}"#);
+        let summary = stats.summary("/api/chat", Some("m")).unwrap();
+        assert_eq!(summary.ttft.unwrap().p50, ms(3));
+        assert_eq!(summary.count, 1);
+    }
+
+    #[test]
+    fn test_observer_error_is_not_recorded() {
+        let stats = LatencyStats::new(Duration::from_secs(60));
+        let url = "http://localhost:11434/api/chat";
+        let call = CallId::next();
+        stats.on_request_start(call, "POST", url);
+        stats.on_complete(call, url, 404, ms(1));
+        stats.on_error(call, url, &Error::HttpStatusError(404));
+        assert!(stats.snapshot().is_empty());
+        assert!(stats.state.lock().unwrap().in_flight.is_empty());
+    }
+
+    #[test]
+    fn test_observer_overlapping_calls_finish_out_of_order() {
+        let stats = LatencyStats::new(Duration::from_secs(60));
+        let url = "http://localhost:11434/api/chat";
+        let (slow, fast) = (CallId::next(), CallId::next());
+        stats.on_request_start(slow, "POST", url);
+        stats.on_request_body(slow, url, br#"{"model":"a","messages":[],"stream":false}"#);
+        stats.on_request_start(fast, "POST", url);
+        stats.on_request_body(fast, url, br#"{"model":"b","messages":[],"stream":false}"#);
+        stats.on_complete(fast, url, 200, ms(10));
+        stats.on_complete(slow, url, 200, ms(900));
+
+        let a = stats.summary("/api/chat", Some("a")).unwrap();
+        let b = stats.summary("/api/chat", Some("b")).unwrap();
+        assert_eq!(a.total.p50, ms(900));
+        assert_eq!(b.total.p50, ms(10));
+        assert!(stats.state.lock().unwrap().in_flight.is_empty());
+    }
+}
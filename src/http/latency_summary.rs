@@ -0,0 +1,102 @@
+//! Percentile summaries reported by `LatencyStats`
+
+use std::fmt;
+use std::time::Duration;
+
+/// p50/p95/p99 of a set of latency samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Percentiles {
+    /// Median
+    pub p50: Duration,
+    /// 95th percentile
+    pub p95: Duration,
+    /// 99th percentile
+    pub p99: Duration,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles of `samples`, or `None` if it is empty.
+    pub(crate) fn of(samples: impl IntoIterator<Item = Duration>) -> Option<Self> {
+        let mut sorted: Vec<Duration> = samples.into_iter().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = |p: f64| {
+            let idx = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+            sorted[idx.clamp(1, sorted.len()) - 1]
+        };
+        Some(Self {
+            p50: rank(50.0),
+            p95: rank(95.0),
+            p99: rank(99.0),
+        })
+    }
+}
+
+impl fmt::Display for Percentiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p50 {:?}, p95 {:?}, p99 {:?}",
+            self.p50, self.p95, self.p99
+        )
+    }
+}
+
+/// Latency percentiles for one endpoint and model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Endpoint path, e.g. `/api/chat`
+    pub endpoint: String,
+
+    /// Model named in the request body, if any
+    pub model: Option<String>,
+
+    /// Number of completed calls in the window
+    pub count: usize,
+
+    /// Time to first token, for streaming calls
+    pub ttft: Option<Percentiles>,
+
+    /// Time from sending the request to the complete response
+    pub total: Percentiles,
+}
+
+impl fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.endpoint)?;
+        if let Some(model) = &self.model {
+            write!(f, " [{model}]")?;
+        }
+        write!(f, " n={} total: {}", self.count, self.total)?;
+        if let Some(ttft) = &self.ttft {
+            write!(f, "; ttft: {ttft}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_percentiles_nearest_rank() {
+        let p = Percentiles::of((1..=100).map(ms)).unwrap();
+        assert_eq!(p.p50, ms(50));
+        assert_eq!(p.p95, ms(95));
+        assert_eq!(p.p99, ms(99));
+    }
+
+    #[test]
+    fn test_percentiles_single_and_empty() {
+        let p = Percentiles::of([ms(7)]).unwrap();
+        assert_eq!((p.p50, p.p95, p.p99), (ms(7), ms(7), ms(7)));
+        assert_eq!(Percentiles::of([]), None);
+    }
+}
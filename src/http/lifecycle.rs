@@ -0,0 +1,189 @@
+//! In-flight request tracking for graceful shutdown
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{Notify, watch};
+
+use super::scheduler::Permit;
+use crate::{Error, Result};
+
+/// Where a client is in its shutdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Accepting requests
+    Open,
+    /// Refusing new requests, letting in-flight ones finish
+    Draining,
+    /// In-flight requests are being cancelled
+    Cancelled,
+}
+
+/// Shutdown state shared by an [`OllamaClient`](super::OllamaClient) and
+/// its clones.
+///
+/// Every call holds an [`InFlight`] guard while it runs; streams hold theirs
+/// until they end or are dropped. Shutdown refuses new guards, waits for
+/// the count to reach zero, and signals cancellation when the grace period
+/// runs out.
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    phase: watch::Sender<Phase>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            phase: watch::Sender::new(Phase::Open),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Registers a new call.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ClientShutdown`] once shutdown has begun.
+    pub(crate) fn enter(self: &Arc<Self>) -> Result<InFlight> {
+        // Count first, so shutdown either sees this call or we see shutdown
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlight {
+            lifecycle: self.clone(),
+            slot: None,
+        };
+        if self.is_closed() {
+            return Err(Error::ClientShutdown);
+        }
+        Ok(guard)
+    }
+
+    /// Whether shutdown has begun
+    pub(crate) fn is_closed(&self) -> bool {
+        *self.phase.borrow() != Phase::Open
+    }
+
+    /// Whether in-flight calls have been told to stop
+    pub(crate) fn is_cancelled(&self) -> bool {
+        *self.phase.borrow() == Phase::Cancelled
+    }
+
+    /// Completes once shutdown has begun.
+    pub(crate) async fn closed(&self) {
+        let mut phase = self.phase.subscribe();
+        let _ = phase.wait_for(|p| *p != Phase::Open).await;
+    }
+
+    /// Completes once in-flight calls have been told to stop.
+    pub(crate) async fn cancelled(&self) {
+        let mut phase = self.phase.subscribe();
+        let _ = phase.wait_for(|p| *p == Phase::Cancelled).await;
+    }
+
+    /// Refuses new calls, waits up to `grace` for in-flight ones, then
+    /// cancels the rest. Returns the number of calls still in flight when
+    /// the grace period ran out.
+    pub(crate) async fn shutdown(&self, grace: Duration) -> usize {
+        self.phase.send_if_modified(|phase| {
+            let open = *phase == Phase::Open;
+            if open {
+                *phase = Phase::Draining;
+            }
+            open
+        });
+        if tokio::time::timeout(grace, self.wait_idle()).await.is_ok() {
+            return 0;
+        }
+        self.phase.send_replace(Phase::Cancelled);
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Marks one call as in flight until dropped, along with the concurrency
+/// slot it runs in, if any.
+#[derive(Debug)]
+pub(crate) struct InFlight {
+    lifecycle: Arc<Lifecycle>,
+    slot: Option<Permit>,
+}
+
+impl InFlight {
+    /// Whether the call should stop because shutdown cancelled it
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.lifecycle.is_cancelled()
+    }
+
+    /// Completes once shutdown cancels the call.
+    pub(crate) async fn cancelled(&self) {
+        self.lifecycle.cancelled().await
+    }
+
+    /// Holds `slot` until the call ends.
+    pub(crate) fn hold(&mut self, slot: Option<Permit>) {
+        self.slot = slot;
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.slot = None;
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        let guard = lifecycle.enter().unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+        });
+
+        assert_eq!(lifecycle.shutdown(Duration::from_secs(5)).await, 0);
+        assert!(lifecycle.is_closed());
+        assert!(!lifecycle.is_cancelled());
+        assert!(matches!(lifecycle.enter(), Err(Error::ClientShutdown)));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_after_grace() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        let guard = lifecycle.enter().unwrap();
+
+        assert_eq!(lifecycle.shutdown(Duration::from_millis(10)).await, 1);
+        assert!(guard.is_cancelled());
+        guard.cancelled().await;
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_when_idle_returns_at_once() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        drop(lifecycle.enter().unwrap());
+        assert_eq!(lifecycle.shutdown(Duration::from_secs(60)).await, 0);
+        lifecycle.closed().await;
+    }
+}
@@ -6,9 +6,23 @@
 //! # Components
 //!
 //! - [`ClientConfig`] - Configuration for the HTTP client
+//! - [`ConfigProfiles`] - Named [`ConfigProfile`]s saved in one file and selected by name or environment
 //! - [`OllamaClient`] - The main HTTP client
 //! - [`OllamaApiAsync`] - Async API trait
 //! - [`OllamaApiSync`] - Sync (blocking) API trait
+//! - [`HedgedClient`] - Hedges read-mostly calls across replicated hosts
+//! - [`ModelHandle`] - Client bound to one model, from [`OllamaClient::model`]
+//! - [`Observer`] - Request lifecycle callbacks registered on the client
+//! - [`CallId`] - Identifies the call each observer hook reports on
+//! - [`Extensions`] - Typed values a client's calls carry to its observers
+//! - [`Guardrail`] - Checks that block, rewrite, or annotate prompts and replies
+//! - [`Prefetch`] - Background request from [`OllamaClient::prefetch`], awaited or cancelled
+//! - [`LatencyStats`] - Observer that tracks latency percentiles per endpoint and model
+//! - [`TranscriptRecorder`] - Observer that logs every request/response to an NDJSON file
+//! - [`DatasetExporter`] - Observer that exports prompt/completion pairs as a JSONL dataset
+//! - [`NdjsonReader`] - NDJSON line framing for custom transports and proxies
+//! - [`ServerStatus`] - Server availability reported by [`OllamaClient::watch_health`]
+//! - [`SystemPromptPolicy`] - Organization text wrapped around every outgoing system prompt
 //!
 //! # Examples
 //!
@@ -41,13 +55,102 @@
 
 mod api_async;
 mod api_sync;
+mod byte_stream;
+mod call_context;
+mod call_id;
+mod callback;
+mod cancel_token;
+#[cfg(feature = "model")]
+mod chat_fallback;
 mod client;
 mod client_config;
+mod config_profile;
+mod config_profiles;
+mod dataset_exporter;
+mod embed_precision;
 pub(crate) mod endpoints;
+mod error_report;
+mod extensions;
+mod fn_guardrail;
+mod gateway_profile;
+mod guardrail;
+mod guardrail_action;
+mod health_watch;
+mod hedged_client;
+pub(crate) mod idempotency_key;
+mod image_io;
+mod json_limits;
+mod latency_stats;
+mod latency_summary;
+mod lifecycle;
+mod model_handle;
+mod ndjson_reader;
+mod observer;
+mod overflow_policy;
+mod partial_output;
+mod pause_handle;
+mod post_processor;
+mod prefetch;
+mod priority;
+#[cfg(feature = "model")]
+mod pull_stream;
+mod raw_chunk;
+mod request_defaults;
+mod scheduler;
+mod segmenter;
+mod server_status;
+mod singleflight;
+mod stop_matcher;
+mod stream_resume;
+mod stream_state;
+mod stream_usage;
 mod streaming;
+mod system_prompt_policy;
+mod text_delta;
+mod text_filter;
+pub(crate) mod token_budget;
+mod transcript_recorder;
+mod transport;
 
 pub use api_async::OllamaApiAsync;
 pub use api_sync::OllamaApiSync;
+pub use call_id::CallId;
+pub use cancel_token::{CancelGuard, CancelToken};
 pub use client::OllamaClient;
 pub use client_config::ClientConfig;
-pub use streaming::{ChatStream, ChatStreamBlocking};
+pub use config_profile::ConfigProfile;
+pub use config_profiles::ConfigProfiles;
+pub use dataset_exporter::DatasetExporter;
+pub use error_report::ErrorReport;
+pub use extensions::Extensions;
+pub use fn_guardrail::FnGuardrail;
+pub use gateway_profile::GatewayProfile;
+pub use guardrail::Guardrail;
+pub use guardrail_action::GuardrailAction;
+pub use hedged_client::HedgedClient;
+pub use json_limits::JsonLimits;
+pub use latency_stats::LatencyStats;
+pub use latency_summary::{LatencySummary, Percentiles};
+pub use model_handle::ModelHandle;
+pub use ndjson_reader::NdjsonReader;
+pub use observer::Observer;
+pub use overflow_policy::OverflowPolicy;
+pub use pause_handle::PauseHandle;
+pub use post_processor::PostProcessor;
+pub use prefetch::Prefetch;
+pub use priority::Priority;
+#[cfg(feature = "model")]
+pub use pull_stream::{
+    PullProgressStream, PullProgressStreamBlocking, PullStream, PullStreamBlocking,
+};
+pub use raw_chunk::RawChunk;
+pub use server_status::ServerStatus;
+pub use stream_usage::StreamUsage;
+pub use streaming::{
+    ByteStream, ChatStream, ChatStreamBlocking, GenerateStream, GenerateStreamBlocking,
+    RawChunkStream, RawChunkStreamBlocking, ResponseStream, ResponseStreamBlocking,
+};
+pub use system_prompt_policy::SystemPromptPolicy;
+pub use text_delta::TextDelta;
+pub use token_budget::TokenBudget;
+pub use transcript_recorder::TranscriptRecorder;
@@ -0,0 +1,184 @@
+//! Model-scoped handle that pre-binds the model name and options
+
+use super::{OllamaApiAsync, OllamaApiSync, OllamaClient};
+use crate::{
+    ChatMessage, ChatRequest, ChatResponse, EmbedInput, EmbedRequest, EmbedResponse,
+    GenerateRequest, GenerateResponse, ModelOptions, Result,
+};
+
+/// A client bound to one model, created by [`OllamaClient::model`].
+///
+/// Every request built through the handle carries the model name and, if
+/// set, the handle's [`ModelOptions`]. Use the `*_request` builders when a
+/// call needs more than the shortcut methods expose.
+///
+/// Cloning is cheap: the underlying HTTP client is shared.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{ChatMessage, ModelOptions, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let llama = client
+///     .model("llama3.2")
+///     .with_options(ModelOptions::default().with_temperature(0.2));
+///
+/// let reply = llama.generate("Why is the sky blue?").await?;
+/// println!("{}", reply.text().unwrap_or_default());
+///
+/// let reply = llama.chat([ChatMessage::user("Hello!")]).await?;
+/// println!("{}", reply.content().unwrap_or_default());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModelHandle {
+    client: OllamaClient,
+    name: String,
+    options: Option<ModelOptions>,
+}
+
+impl OllamaClient {
+    /// Bind this client to `name`, returning a [`ModelHandle`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::OllamaClient;
+    ///
+    /// # fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let qwen = client.model("qwen3:0.6b");
+    /// assert_eq!(qwen.name(), "qwen3:0.6b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn model(&self, name: impl Into<String>) -> ModelHandle {
+        ModelHandle {
+            client: self.clone(),
+            name: name.into(),
+            options: None,
+        }
+    }
+}
+
+impl ModelHandle {
+    /// Set the options applied to every request from this handle
+    pub fn with_options(mut self, options: ModelOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Get the bound model name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the options applied to every request, if any
+    pub fn options(&self) -> Option<&ModelOptions> {
+        self.options.as_ref()
+    }
+
+    /// Get the underlying client
+    pub fn client(&self) -> &OllamaClient {
+        &self.client
+    }
+
+    /// Build a generate request for `prompt` with the bound model and options
+    pub fn generate_request(&self, prompt: impl Into<String>) -> GenerateRequest {
+        let request = GenerateRequest::new(self.name.as_str(), prompt);
+        match &self.options {
+            Some(options) => request.with_options(options.clone()),
+            None => request,
+        }
+    }
+
+    /// Build a chat request for `messages` with the bound model and options
+    pub fn chat_request(&self, messages: impl IntoIterator<Item = ChatMessage>) -> ChatRequest {
+        let request = ChatRequest::new(self.name.as_str(), messages);
+        match &self.options {
+            Some(options) => request.with_options(options.clone()),
+            None => request,
+        }
+    }
+
+    /// Build an embed request for `input` with the bound model and options
+    pub fn embed_request(&self, input: impl Into<EmbedInput>) -> EmbedRequest {
+        let request = EmbedRequest::new(self.name.as_str(), input);
+        match &self.options {
+            Some(options) => request.with_options(options.clone()),
+            None => request,
+        }
+    }
+
+    /// Generate a completion for `prompt` (async)
+    pub async fn generate(&self, prompt: impl Into<String>) -> Result<GenerateResponse> {
+        self.client.generate(&self.generate_request(prompt)).await
+    }
+
+    /// Generate a completion for `prompt` (blocking)
+    pub fn generate_blocking(&self, prompt: impl Into<String>) -> Result<GenerateResponse> {
+        self.client
+            .generate_blocking(&self.generate_request(prompt))
+    }
+
+    /// Send a chat conversation (async)
+    pub async fn chat(
+        &self,
+        messages: impl IntoIterator<Item = ChatMessage>,
+    ) -> Result<ChatResponse> {
+        self.client.chat(&self.chat_request(messages)).await
+    }
+
+    /// Send a chat conversation (blocking)
+    pub fn chat_blocking(
+        &self,
+        messages: impl IntoIterator<Item = ChatMessage>,
+    ) -> Result<ChatResponse> {
+        self.client.chat_blocking(&self.chat_request(messages))
+    }
+
+    /// Embed one or more texts (async)
+    pub async fn embed(&self, input: impl Into<EmbedInput>) -> Result<EmbedResponse> {
+        self.client.embed(&self.embed_request(input)).await
+    }
+
+    /// Embed one or more texts (blocking)
+    pub fn embed_blocking(&self, input: impl Into<EmbedInput>) -> Result<EmbedResponse> {
+        self.client.embed_blocking(&self.embed_request(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_carry_model_and_options() {
+        let handle = OllamaClient::default()
+            .unwrap()
+            .model("qwen3:0.6b")
+            .with_options(ModelOptions::default().with_temperature(0.1));
+
+        let generate = handle.generate_request("hi");
+        assert_eq!(generate.model, "qwen3:0.6b");
+        assert!(generate.options.is_some());
+
+        let chat = handle.chat_request([ChatMessage::user("hi")]);
+        assert_eq!(chat.model, "qwen3:0.6b");
+        assert!(chat.options.is_some());
+
+        let embed = handle.embed_request(["a", "b"]);
+        assert_eq!(embed.model, "qwen3:0.6b");
+        assert!(embed.options.is_some());
+    }
+
+    #[test]
+    fn test_requests_without_options() {
+        let handle = OllamaClient::default().unwrap().model("qwen3:0.6b");
+        assert!(handle.options().is_none());
+        assert!(handle.generate_request("hi").options.is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Incremental NDJSON framing for custom transports
+
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+
+use super::streaming::{LineBuffer, parse_json_line};
+use super::{JsonLimits, RawChunk};
+use crate::{Error, Result};
+
+/// Splits NDJSON bytes, fed in chunks of any size, into complete lines.
+///
+/// This is the framing the client's streaming calls use, for code that
+/// reads Ollama streams over its own transport or relays them through a
+/// proxy. Lines may be split anywhere across chunks and end in `\n` or
+/// `\r\n`; blank lines are skipped. A line that lies within one chunk is
+/// returned without copying.
+///
+/// With [`with_max_line_bytes`](Self::with_max_line_bytes) set, a line
+/// longer than the limit fails with [`Error::ResponseTooLarge`] as soon as
+/// that much is buffered, without waiting for its end. After an error the
+/// reader yields nothing more.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatResponse, NdjsonReader};
+///
+/// let mut reader = NdjsonReader::new();
+/// reader.feed(&b"{\"model\":\"qwen3\",\"done\":false}\n{\"model\":"[..]);
+/// let first: ChatResponse = reader.next_value().unwrap().unwrap();
+/// assert_eq!(first.model.as_deref(), Some("qwen3"));
+/// assert!(reader.next_value::<ChatResponse>().is_none());
+///
+/// reader.feed(&b"\"qwen3\",\"done\":true}"[..]);
+/// let last: ChatResponse = reader.finish_value().unwrap().unwrap();
+/// assert_eq!(last.done, Some(true));
+/// ```
+#[derive(Debug, Default)]
+pub struct NdjsonReader {
+    lines: LineBuffer,
+    queued: VecDeque<Bytes>,
+    max_line: Option<usize>,
+    json_limits: JsonLimits,
+    failed: bool,
+}
+
+impl NdjsonReader {
+    /// A reader with no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail lines longer than `bytes`, excluding the line terminator.
+    pub fn with_max_line_bytes(mut self, bytes: usize) -> Self {
+        self.max_line = Some(bytes);
+        self
+    }
+
+    /// Check each line against `limits` in
+    /// [`next_value`](Self::next_value) and
+    /// [`finish_value`](Self::finish_value).
+    pub fn with_json_limits(mut self, limits: JsonLimits) -> Self {
+        self.json_limits = limits;
+        self
+    }
+
+    /// Add the next chunk of input.
+    pub fn feed(&mut self, chunk: impl Into<Bytes>) {
+        let chunk = chunk.into();
+        if !chunk.is_empty() {
+            self.queued.push_back(chunk);
+        }
+    }
+
+    /// Bytes buffered towards a line that has not ended yet
+    pub fn pending_len(&self) -> usize {
+        self.lines.pending_len() + self.queued.iter().map(Bytes::len).sum::<usize>()
+    }
+
+    /// The next complete, non-blank line, or `None` until more input is
+    /// fed.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ResponseTooLarge`] if the line exceeds the
+    /// [line limit](Self::with_max_line_bytes).
+    pub fn next_line(&mut self) -> Option<Result<RawChunk>> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            if let Some(line) = self.lines.next_line() {
+                if let Some(limit) = self.max_line
+                    && line.len() > limit
+                {
+                    return Some(self.fail(limit));
+                }
+                if line.is_empty() {
+                    continue;
+                }
+                return Some(Ok(RawChunk::new(line)));
+            }
+            if let Some(limit) = self.max_line
+                && self.lines.pending_len() > limit
+            {
+                return Some(self.fail(limit));
+            }
+            self.lines.push(self.queued.pop_front()?);
+        }
+    }
+
+    /// The next complete line deserialized as `T`, or `None` until more
+    /// input is fed.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::ResponseTooLarge`] if the line exceeds a limit
+    /// - [`Error::StreamError`] if the line is not a `T`
+    pub fn next_value<T: DeserializeOwned>(&mut self) -> Option<Result<T>> {
+        let line = self.next_line()?;
+        Some(line.and_then(|line| self.parse(line.as_bytes())))
+    }
+
+    /// The trailing line left without a terminator once input has ended,
+    /// trimmed of whitespace; `None` if there is none.
+    ///
+    /// Complete lines not yet taken with [`next_line`](Self::next_line)
+    /// are dropped.
+    pub fn finish(mut self) -> Option<RawChunk> {
+        if self.failed {
+            return None;
+        }
+        while self.next_line().is_some_and(|line| line.is_ok()) {}
+        if self.failed {
+            return None;
+        }
+        let tail = std::mem::take(&mut self.lines).finish();
+        (!tail.is_empty()).then(|| RawChunk::new(tail))
+    }
+
+    /// The trailing line, deserialized as `T`; see [`finish`](Self::finish).
+    ///
+    /// # Errors
+    ///
+    /// As for [`next_value`](Self::next_value).
+    pub fn finish_value<T: DeserializeOwned>(self) -> Option<Result<T>> {
+        let json_limits = self.json_limits;
+        let tail = self.finish()?;
+        Some(
+            json_limits
+                .check(tail.as_bytes())
+                .and_then(|()| parse_json_line(tail.as_bytes())),
+        )
+    }
+
+    /// Checks `line` against the JSON limits and deserializes it.
+    fn parse<T: DeserializeOwned>(&mut self, line: &[u8]) -> Result<T> {
+        let parsed = self
+            .json_limits
+            .check(line)
+            .and_then(|()| parse_json_line(line));
+        if parsed.is_err() {
+            self.failed = true;
+        }
+        parsed
+    }
+
+    fn fail<T>(&mut self, limit: usize) -> Result<T> {
+        self.failed = true;
+        Err(Error::ResponseTooLarge { limit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(reader: &mut NdjsonReader) -> Vec<String> {
+        std::iter::from_fn(|| reader.next_line())
+            .map(|line| String::from_utf8(line.unwrap().into_bytes().to_vec()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks_fed_before_reading() {
+        let mut reader = NdjsonReader::new();
+        reader.feed(&b"{\"a\":1}\n\n{\"b\""[..]);
+        reader.feed(&b":2}\r\n{\"c\":"[..]);
+        assert_eq!(lines(&mut reader), ["{\"a\":1}", "{\"b\":2}"]);
+        assert_eq!(reader.pending_len(), 5);
+        reader.feed(&b"3}  "[..]);
+        assert!(lines(&mut reader).is_empty());
+        assert_eq!(reader.finish().unwrap().as_bytes(), b"{\"c\":3}");
+    }
+
+    #[test]
+    fn test_long_line_fails_before_it_ends() {
+        let mut reader = NdjsonReader::new().with_max_line_bytes(4);
+        reader.feed(&b"{}\n{\"abc\""[..]);
+        assert_eq!(reader.next_line().unwrap().unwrap().as_bytes(), b"{}");
+        assert!(matches!(
+            reader.next_line(),
+            Some(Err(Error::ResponseTooLarge { limit: 4 }))
+        ));
+        reader.feed(&b"}\n{}\n"[..]);
+        assert!(reader.next_line().is_none());
+    }
+
+    #[test]
+    fn test_values_checked_against_json_limits() {
+        let mut reader = NdjsonReader::new().with_json_limits(JsonLimits::new().with_max_depth(1));
+        reader.feed(&b"{\"a\":1}\n{\"a\":[1]}\n"[..]);
+        let value: serde_json::Value = reader.next_value().unwrap().unwrap();
+        assert_eq!(value["a"], 1);
+        assert!(matches!(
+            reader.next_value::<serde_json::Value>(),
+            Some(Err(Error::ResponseTooLarge { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_line_is_a_stream_error() {
+        let mut reader = NdjsonReader::new();
+        reader.feed(&b"not json\n"[..]);
+        assert!(matches!(
+            reader.next_value::<serde_json::Value>(),
+            Some(Err(Error::StreamError(_)))
+        ));
+        assert!(reader.finish().is_none());
+    }
+}
@@ -0,0 +1,280 @@
+//! Lightweight request lifecycle callbacks
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::call_context::CallContext;
+use super::endpoints::Target;
+use super::{CallId, ErrorReport, Extensions};
+use crate::Error;
+
+/// Callbacks invoked by [`OllamaClient`](super::OllamaClient) around each API call.
+///
+/// Every hook has a no-op default, so implement only what you need. Hooks run
+/// inline on the calling task or thread and should return quickly.
+///
+/// Every hook receives the [`CallId`] of the call it reports on. Calls on
+/// one client may run concurrently and interleave their hooks, so key any
+/// per-call state on the id rather than the URL.
+///
+/// Lifecycle of one call:
+/// - `on_request_start` once, before the first attempt
+/// - `on_extensions` once, if the call carries
+///   [extensions](super::OllamaClient::with_extension)
+/// - `on_request_body` once with the encoded JSON body, if the call has one
+/// - `on_idempotency_key` once, if idempotency keys are enabled and the call
+///   is not a `GET`
+/// - `on_retry` before each retry after a network error or 5xx response
+/// - `on_complete` when a response arrives (any status), with the total elapsed time
+/// - `on_response_body` with the body of a successful non-streaming response
+/// - `on_chunk` and `on_response_body` for each NDJSON line of a streaming response
+/// - `on_first_token` once, when the first event of a streaming response is decoded
+/// - `on_coalesced` instead of everything from `on_request_start` to
+///   `on_response_body`, if the call shared the response of an identical
+///   call already in flight
+/// - `on_guardrail_note` for each note a [guardrail](super::Guardrail)
+///   attaches to the prompts before the first attempt, or to the reply
+///   after it arrives
+/// - `on_error` and then `on_error_report` if the call ultimately fails,
+///   including after `on_complete` for non-success statuses or undecodable
+///   bodies
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::time::Duration;
+/// use ollama_oxide::{CallId, Observer, OllamaClient};
+///
+/// #[derive(Default)]
+/// struct Counter(AtomicUsize);
+///
+/// impl Observer for Counter {
+///     fn on_complete(&self, call: CallId, url: &str, status: u16, elapsed: Duration) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         println!("{} {} -> {} in {:?}", call, url, status, elapsed);
+///     }
+/// }
+///
+/// let counter = Arc::new(Counter::default());
+/// let client = OllamaClient::default()?.with_observer(counter.clone());
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+pub trait Observer: Send + Sync {
+    /// A call is about to send its first attempt.
+    fn on_request_start(&self, call: CallId, method: &str, url: &str) {
+        let _ = (call, method, url);
+    }
+
+    /// A call to `url` carries `extensions`, set with
+    /// [`OllamaClient::with_extension`](super::OllamaClient::with_extension).
+    ///
+    /// Only called when there is at least one.
+    fn on_extensions(&self, call: CallId, url: &str, extensions: &Extensions) {
+        let _ = (call, url, extensions);
+    }
+
+    /// The JSON request `body` is about to be sent.
+    fn on_request_body(&self, call: CallId, url: &str, body: &[u8]) {
+        let _ = (call, url, body);
+    }
+
+    /// A JSON response `body` arrived: the whole body of a non-streaming
+    /// call, or one NDJSON line of a streaming call.
+    fn on_response_body(&self, call: CallId, url: &str, body: &[u8]) {
+        let _ = (call, url, body);
+    }
+
+    /// One NDJSON line of `bytes` length arrived on a streaming response.
+    fn on_chunk(&self, call: CallId, url: &str, bytes: usize) {
+        let _ = (call, url, bytes);
+    }
+
+    /// The first event of a streaming response arrived `latency` after the
+    /// request was sent (time-to-first-token).
+    fn on_first_token(&self, call: CallId, url: &str, latency: Duration) {
+        let _ = (call, url, latency);
+    }
+
+    /// A response with `status` arrived, `elapsed` after `on_request_start`.
+    fn on_complete(&self, call: CallId, url: &str, status: u16, elapsed: Duration) {
+        let _ = (call, url, status, elapsed);
+    }
+
+    /// A call was assigned the idempotency `key` it sends on every attempt.
+    ///
+    /// Only called when the client is configured with
+    /// [`with_idempotency_keys`](crate::ClientConfig::with_idempotency_keys).
+    fn on_idempotency_key(&self, call: CallId, url: &str, key: &str) {
+        let _ = (call, url, key);
+    }
+
+    /// A call to `url` shared the outcome of an identical call already in
+    /// flight instead of sending its own request.
+    ///
+    /// Only called when the client is configured with
+    /// [`with_request_coalescing`](crate::ClientConfig::with_request_coalescing).
+    fn on_coalesced(&self, call: CallId, url: &str) {
+        let _ = (call, url);
+    }
+
+    /// Attempt number `attempt` (starting at 1) is about to be retried.
+    fn on_retry(&self, call: CallId, url: &str, attempt: u32, reason: &str) {
+        let _ = (call, url, attempt, reason);
+    }
+
+    /// The guardrail called `guardrail` let a prompt or reply of a call to
+    /// `url` through with `note`.
+    fn on_guardrail_note(&self, call: CallId, url: &str, guardrail: &str, note: &str) {
+        let _ = (call, url, guardrail, note);
+    }
+
+    /// The call failed with `error`.
+    fn on_error(&self, call: CallId, url: &str, error: &Error) {
+        let _ = (call, url, error);
+    }
+
+    /// The call failed; `report` has the error with the endpoint, model,
+    /// attempt count, and elapsed time, ready to forward to an error
+    /// tracker.
+    fn on_error_report(&self, report: &ErrorReport<'_>) {
+        let _ = report;
+    }
+}
+
+/// Observers registered on a client, and the extensions its calls carry to
+/// them; cheap to clone.
+#[derive(Clone, Default)]
+pub(crate) struct ObserverSet {
+    observers: Vec<Arc<dyn Observer>>,
+    extensions: Extensions,
+}
+
+impl ObserverSet {
+    pub(crate) fn push(&mut self, observer: Arc<dyn Observer>) {
+        self.observers.push(observer);
+    }
+
+    pub(crate) fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    pub(crate) fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    pub(crate) fn request_start(&self, call: &CallContext) {
+        for o in &self.observers {
+            o.on_request_start(call.id(), call.method(), call.url());
+            if !self.extensions.is_empty() {
+                o.on_extensions(call.id(), call.url(), &self.extensions);
+            }
+        }
+    }
+
+    pub(crate) fn request_body(&self, call: &CallContext, body: &[u8]) {
+        for o in &self.observers {
+            o.on_request_body(call.id(), call.url(), body);
+        }
+    }
+
+    pub(crate) fn response_body(&self, call: &CallContext, body: &[u8]) {
+        for o in &self.observers {
+            o.on_response_body(call.id(), call.url(), body);
+        }
+    }
+
+    /// Reports one NDJSON line to `on_chunk` and `on_response_body`.
+    pub(crate) fn chunk(&self, call: &CallContext, line: &[u8]) {
+        for o in &self.observers {
+            o.on_chunk(call.id(), call.url(), line.len());
+            o.on_response_body(call.id(), call.url(), line);
+        }
+    }
+
+    pub(crate) fn first_token(&self, call: &CallContext, latency: Duration) {
+        for o in &self.observers {
+            o.on_first_token(call.id(), call.url(), latency);
+        }
+    }
+
+    pub(crate) fn complete(&self, call: &CallContext, status: u16, elapsed: Duration) {
+        for o in &self.observers {
+            o.on_complete(call.id(), call.url(), status, elapsed);
+        }
+    }
+
+    pub(crate) fn idempotency_key(&self, target: &Target, key: &str) {
+        for o in &self.observers {
+            o.on_idempotency_key(target.call, target.as_str(), key);
+        }
+    }
+
+    pub(crate) fn coalesced(&self, target: &Target) {
+        for o in &self.observers {
+            o.on_coalesced(target.call, target.as_str());
+        }
+    }
+
+    pub(crate) fn retry(&self, call: &CallContext, attempt: u32, reason: &str) {
+        for o in &self.observers {
+            o.on_retry(call.id(), call.url(), attempt, reason);
+        }
+    }
+
+    pub(crate) fn guardrail_note(&self, target: &Target, guardrail: &str, note: &str) {
+        for o in &self.observers {
+            o.on_guardrail_note(target.call, target.as_str(), guardrail, note);
+        }
+    }
+
+    /// Reports a failed `call` to `on_error` and `on_error_report`.
+    pub(crate) fn error(&self, call: &CallContext, error: &Error) {
+        if self.observers.is_empty() {
+            return;
+        }
+        let model = call.model();
+        let report = ErrorReport {
+            call: call.id(),
+            method: call.method(),
+            url: call.url(),
+            endpoint: call.endpoint(),
+            model: model.as_deref(),
+            attempts: call.attempts(),
+            elapsed: call.started().elapsed(),
+            error,
+            extensions: &self.extensions,
+        };
+        for o in &self.observers {
+            o.on_error(call.id(), call.url(), error);
+            o.on_error_report(&report);
+        }
+    }
+
+    /// Reports `result` to `on_error` when it failed, then passes it through.
+    pub(crate) fn observe<T>(
+        &self,
+        call: &CallContext,
+        result: crate::Result<T>,
+    ) -> crate::Result<T> {
+        if let Err(e) = &result {
+            self.error(call, e);
+        }
+        result
+    }
+}
+
+impl fmt::Debug for ObserverSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObserverSet")
+            .field("len", &self.observers.len())
+            .field("extensions", &self.extensions.len())
+            .finish()
+    }
+}
@@ -0,0 +1,17 @@
+//! What a `TokenBudget` does with requests that do not fit
+
+/// How a [`TokenBudget`](super::TokenBudget) handles a request whose
+/// estimated size exceeds the context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum OverflowPolicy {
+    /// Fail with [`Error::TokenBudgetExceeded`](crate::Error::TokenBudgetExceeded)
+    /// before sending.
+    #[default]
+    Reject,
+    /// Drop the oldest input until the request fits: the oldest chat
+    /// messages after leading system prompts (the last message is always
+    /// kept), or the start of a generate prompt. Fails like
+    /// [`Reject`](Self::Reject) if the request still does not fit.
+    Truncate,
+}
@@ -0,0 +1,87 @@
+//! Text of a streamed reply so far, for interrupted streams
+
+use super::TextDelta;
+use crate::Error;
+
+/// Collects the text of one streamed reply, so an interruption can hand it
+/// back in [`Error::StreamInterrupted`].
+#[derive(Debug, Default)]
+pub(crate) struct PartialOutput {
+    text: String,
+    events: usize,
+    /// The final event or an error was seen
+    ended: bool,
+}
+
+impl PartialOutput {
+    /// Record one event of the reply.
+    pub(crate) fn record<T: TextDelta>(&mut self, event: &T) {
+        self.text.push_str(event.text_delta().unwrap_or_default());
+        self.events += 1;
+        self.ended |= event.is_done();
+    }
+
+    /// `error` with the text so far attached, if it interrupted the stream.
+    pub(crate) fn attach(&mut self, error: Error) -> Error {
+        self.ended = true;
+        match error {
+            Error::StreamInterrupted { partial, message } if partial.is_empty() => {
+                Error::StreamInterrupted {
+                    partial: self.text.clone(),
+                    message,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// The error for a body that ended after some events but before the
+    /// final one; `None` if the reply is complete, failed, or never
+    /// started.
+    pub(crate) fn ended_early(&self) -> Option<Error> {
+        (self.events > 0 && !self.ended).then(|| Error::StreamInterrupted {
+            partial: self.text.clone(),
+            message: "connection closed before the final event".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateResponse;
+
+    fn delta(text: &str, done: bool) -> GenerateResponse {
+        GenerateResponse {
+            response: Some(text.to_string()),
+            done: Some(done),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_attaches_text_to_interruptions_only() {
+        let mut partial = PartialOutput::default();
+        partial.record(&delta("Hel", false));
+        partial.record(&delta("lo", false));
+
+        let err = partial.attach(Error::StreamInterrupted {
+            partial: String::new(),
+            message: "reset".to_string(),
+        });
+        assert!(matches!(err, Error::StreamInterrupted { ref partial, .. } if partial == "Hello"));
+        let err = partial.attach(Error::StreamError("bad json".to_string()));
+        assert!(matches!(err, Error::StreamError(_)));
+        assert!(partial.ended_early().is_none());
+    }
+
+    #[test]
+    fn test_ended_early_only_mid_reply() {
+        let mut partial = PartialOutput::default();
+        assert!(partial.ended_early().is_none());
+        partial.record(&delta("Hi", false));
+        assert!(partial.ended_early().is_some());
+        partial.record(&delta("", true));
+        assert!(partial.ended_early().is_none());
+    }
+}
@@ -0,0 +1,40 @@
+//! Remote control for pausing a `ResponseStream`
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::stream_state::StreamState;
+
+/// Pauses and resumes a [`ResponseStream`](super::ResponseStream) from
+/// another task, e.g. a UI's "pause generation" button.
+///
+/// Obtained from [`ResponseStream::pause_handle`](super::ResponseStream::pause_handle);
+/// cheap to clone. See [`ResponseStream::pause`](super::ResponseStream::pause)
+/// for what pausing does.
+#[derive(Clone)]
+pub struct PauseHandle(pub(super) Arc<StreamState>);
+
+impl PauseHandle {
+    /// Stop reading from the stream until [`resume`](Self::resume).
+    pub fn pause(&self) {
+        self.0.set_paused(true);
+    }
+
+    /// Continue reading after [`pause`](Self::pause).
+    pub fn resume(&self) {
+        self.0.set_paused(false);
+    }
+
+    /// Whether the stream is paused
+    pub fn is_paused(&self) -> bool {
+        self.0.is_paused()
+    }
+}
+
+impl fmt::Debug for PauseHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PauseHandle")
+            .field("paused", &self.is_paused())
+            .finish()
+    }
+}
@@ -0,0 +1,199 @@
+//! Rewrites of generated text, for whole replies and streams alike
+
+use super::ClientConfig;
+use super::TextDelta;
+use super::stop_matcher::StopMatcher;
+use super::text_filter::TextFilter;
+
+/// One rewrite in a [`PostProcessor`] chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Trim,
+    StripThink,
+    ExtractCode,
+    StripStop(Vec<String>),
+}
+
+/// A chain of rewrites applied to the generated text of replies.
+///
+/// Steps run in the order they are added, each on the output of the one
+/// before. The same chain gives the same text whether it rewrites a whole
+/// reply ([`apply`](Self::apply)) or a stream piece by piece
+/// ([`ResponseStream::post_process`](super::ResponseStream::post_process)):
+/// text that may still belong to a `<think>` tag, a code fence, a stop
+/// sequence, or trailing whitespace is held back until a later event
+/// decides it, and released with the final one.
+///
+/// Set one on [`ClientConfig::with_post_processor`] to rewrite every
+/// `chat`, `generate`, and `chat_stream` reply of a client. Only the
+/// message content / response text is rewritten; thinking, tool calls, and
+/// metrics pass through.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::PostProcessor;
+///
+/// let processor = PostProcessor::new().strip_think().extract_code();
+/// let text = "<think>Use a loop.</think>Sure:\n```rust\nloop {}\n```\n";
+/// assert_eq!(processor.apply(text), "loop {}\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostProcessor {
+    steps: Vec<Step>,
+}
+
+impl PostProcessor {
+    /// An empty chain, which leaves text unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop leading and trailing whitespace.
+    pub fn trim(mut self) -> Self {
+        self.steps.push(Step::Trim);
+        self
+    }
+
+    /// Drop `<think>...</think>` blocks, for reasoning models that write
+    /// their reasoning into the reply instead of the `thinking` field. An
+    /// unclosed block is dropped to the end of the text.
+    pub fn strip_think(mut self) -> Self {
+        self.steps.push(Step::StripThink);
+        self
+    }
+
+    /// Keep only the contents of `` ``` `` fenced code blocks, without the
+    /// fence lines. Text outside any block is dropped, so a reply with no
+    /// fences becomes empty.
+    pub fn extract_code(mut self) -> Self {
+        self.steps.push(Step::ExtractCode);
+        self
+    }
+
+    /// Cut the text at the first of `sequences`, e.g. end-of-turn markers
+    /// a model leaks into its output.
+    ///
+    /// Unlike [`ResponseStream::stop_at`](super::ResponseStream::stop_at),
+    /// the stream is not ended; later text is dropped and the final event
+    /// still arrives with its metrics.
+    pub fn strip_stop<I, S>(mut self, sequences: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let sequences: Vec<String> = sequences
+            .into_iter()
+            .map(Into::into)
+            .filter(|s| !s.is_empty())
+            .collect();
+        if !sequences.is_empty() {
+            self.steps.push(Step::StripStop(sequences));
+        }
+        self
+    }
+
+    /// Whether the chain has no steps
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Rewrite a whole text.
+    pub fn apply(&self, text: &str) -> String {
+        self.start().push(text, true)
+    }
+
+    /// Fresh state for rewriting one reply.
+    pub(crate) fn start(&self) -> PostProcessing {
+        let filters = self
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Trim => TextFilter::trim(),
+                Step::StripThink => TextFilter::think(),
+                Step::ExtractCode => TextFilter::code(),
+                Step::StripStop(sequences) => {
+                    TextFilter::Stop(StopMatcher::new(sequences.iter().cloned()))
+                }
+            })
+            .collect();
+        PostProcessing { filters }
+    }
+}
+
+/// A [`PostProcessor`] chain working through one reply.
+#[derive(Debug, Clone)]
+pub(crate) struct PostProcessing {
+    filters: Vec<TextFilter>,
+}
+
+impl PostProcessing {
+    /// Feed the next piece of text; returns the text to emit now.
+    fn push(&mut self, delta: &str, last: bool) -> String {
+        let mut text = delta.to_string();
+        for filter in &mut self.filters {
+            text = filter.push(&text, last);
+        }
+        text
+    }
+
+    /// Rewrite `event`'s text; the final event flushes held-back text.
+    pub(crate) fn apply<T: TextDelta>(&mut self, event: &mut T) {
+        let last = event.is_done();
+        let delta = event.text_delta().unwrap_or_default().to_string();
+        let text = self.push(&delta, last);
+        if event.text_delta().is_some() || !text.is_empty() {
+            event.set_text_delta(text);
+        }
+    }
+}
+
+/// `response` with the configured post-processor applied to its whole text.
+pub(super) fn post_processed<T: TextDelta>(config: &ClientConfig, mut response: T) -> T {
+    if let Some(processor) = config.post_processor()
+        && let Some(text) = response.text_delta()
+    {
+        let text = processor.apply(text);
+        response.set_text_delta(text);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_run_in_order() {
+        let text = "  <think>x</think> Done.</s>junk ";
+        let processor = PostProcessor::new()
+            .strip_think()
+            .strip_stop(["</s>"])
+            .trim();
+        assert_eq!(processor.apply(text), "Done.");
+        assert_eq!(PostProcessor::new().apply(text), text);
+    }
+
+    #[test]
+    fn test_streamed_events_match_whole_text() {
+        let processor = PostProcessor::new().strip_think().trim();
+        let mut processing = processor.start();
+        let mut out = String::new();
+        for (delta, done) in [
+            ("<thi", false),
+            ("nk>a</think>\n", false),
+            ("Hi ", false),
+            ("", true),
+        ] {
+            let mut event = crate::GenerateResponse {
+                response: Some(delta.to_string()),
+                done: Some(done),
+                ..Default::default()
+            };
+            processing.apply(&mut event);
+            out.push_str(event.response.as_deref().unwrap_or_default());
+        }
+        assert_eq!(out, "Hi");
+        assert_eq!(processor.apply("<think>a</think>\nHi "), out);
+    }
+}
@@ -0,0 +1,99 @@
+//! Speculative background generations
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::task::JoinHandle;
+
+use super::{OllamaApiAsync, OllamaClient};
+use crate::{ChatRequest, ChatResponse, Error, GenerateRequest, GenerateResponse, Result};
+
+/// A request running in the background, from
+/// [`OllamaClient::prefetch`] or [`OllamaClient::prefetch_chat`].
+///
+/// `.await` the handle to take the response once the user confirms the
+/// step; call [`cancel`](Self::cancel) or drop the handle to abort the
+/// request, which closes the connection so the server stops generating.
+#[derive(Debug)]
+#[must_use = "dropping a Prefetch cancels its request"]
+pub struct Prefetch<T> {
+    task: JoinHandle<Result<T>>,
+}
+
+impl<T> Prefetch<T> {
+    /// Whether the response (or error) is already available.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Abort the request.
+    pub fn cancel(self) {
+        // Dropping aborts the task.
+    }
+}
+
+impl<T> Future for Prefetch<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.task).poll(cx).map(|joined| {
+            joined.unwrap_or_else(|e| {
+                Err(Error::ApiError {
+                    message: format!("prefetch task failed: {e}"),
+                })
+            })
+        })
+    }
+}
+
+impl<T> Drop for Prefetch<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl OllamaClient {
+    /// Start `request` in the background to hide latency for a likely next
+    /// step.
+    ///
+    /// Returns at once. Await the [`Prefetch`] if the step is confirmed;
+    /// otherwise cancel or drop it. Must be called from within a Tokio
+    /// runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{GenerateRequest, OllamaClient};
+    ///
+    /// # async fn example(confirmed: bool) -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let next = client.prefetch(&GenerateRequest::new("qwen3:0.6b", "Summarize chapter 2."));
+    ///
+    /// if confirmed {
+    ///     let response = next.await?;
+    ///     println!("{}", response.text().unwrap_or_default());
+    /// } else {
+    ///     next.cancel();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefetch(&self, request: &GenerateRequest) -> Prefetch<GenerateResponse> {
+        let client = self.clone();
+        let request = request.clone();
+        Prefetch {
+            task: tokio::spawn(async move { client.generate(&request).await }),
+        }
+    }
+
+    /// Start a chat `request` in the background; see
+    /// [`prefetch`](Self::prefetch).
+    pub fn prefetch_chat(&self, request: &ChatRequest) -> Prefetch<ChatResponse> {
+        let client = self.clone();
+        let request = request.clone();
+        Prefetch {
+            task: tokio::spawn(async move { client.chat(&request).await }),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Scheduling priority of a call
+
+/// How urgently a call is dispatched when the client limits concurrency
+/// with [`ClientConfig::with_max_concurrency`](super::ClientConfig::with_max_concurrency).
+///
+/// When every slot is taken, calls wait in one queue per priority and a
+/// freed slot goes to the oldest call of the highest priority waiting.
+/// Without a concurrency limit, priorities have no effect.
+///
+/// A call's priority is the first of:
+/// 1. the client's, set with [`OllamaClient::with_priority`](super::OllamaClient::with_priority)
+/// 2. its model's, set with [`ClientConfig::with_model_priority`](super::ClientConfig::with_model_priority)
+/// 3. [`Priority::Low`] for embeddings, which are usually background
+///    indexing work, and [`Priority::Normal`] for everything else
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Background work, dispatched only when nothing more urgent waits
+    Low,
+    /// The default
+    #[default]
+    Normal,
+    /// Interactive work, dispatched first
+    High,
+}
+
+impl Priority {
+    /// Every priority, lowest first
+    pub(crate) const ALL: [Priority; 3] = [Priority::Low, Priority::Normal, Priority::High];
+}
@@ -0,0 +1,90 @@
+//! Streaming pull progress and its coalescing adapters
+
+use super::streaming::{ResponseStream, ResponseStreamBlocking};
+use crate::model::PullProgressTracker;
+use crate::{PullProgress, PullResponse, Result};
+
+/// Async stream of raw [`PullResponse`] events from a streaming pull.
+pub type PullStream = ResponseStream<PullResponse>;
+
+/// Blocking iterator over raw [`PullResponse`] events from a streaming pull.
+pub type PullStreamBlocking = ResponseStreamBlocking<PullResponse>;
+
+/// Async stream of coalesced [`PullProgress`] snapshots.
+pub type PullProgressStream = ResponseStream<PullProgress>;
+
+impl ResponseStream<PullResponse> {
+    /// Coalesce raw events into [`PullProgress`] snapshots.
+    ///
+    /// Emits only on phase changes, layer state transitions, and whole-percent
+    /// steps of the overall download. Errors are forwarded and end the stream.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn progress_summary(self) -> PullProgressStream {
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            let mut tracker = PullProgressTracker::default();
+            while let Some(item) = rx.recv().await {
+                let forward = match item {
+                    Ok(event) => match tracker.update(&event) {
+                        Some(progress) => Ok(progress),
+                        None => continue,
+                    },
+                    Err(e) => Err(e),
+                };
+                let is_err = forward.is_err();
+                if tx.send(forward).await.is_err() || is_err {
+                    return;
+                }
+            }
+        });
+
+        ResponseStream::new(out, state)
+    }
+}
+
+impl ResponseStreamBlocking<PullResponse> {
+    /// Coalesce raw events into [`PullProgress`] snapshots (blocking).
+    ///
+    /// See [`ResponseStream::progress_summary`] for what is emitted.
+    pub fn progress_summary(self) -> PullProgressStreamBlocking {
+        PullProgressStreamBlocking {
+            inner: self,
+            tracker: PullProgressTracker::default(),
+            done: false,
+        }
+    }
+}
+
+/// Blocking iterator over coalesced [`PullProgress`] snapshots.
+pub struct PullProgressStreamBlocking {
+    inner: PullStreamBlocking,
+    tracker: PullProgressTracker,
+    done: bool,
+}
+
+impl Iterator for PullProgressStreamBlocking {
+    type Item = Result<PullProgress>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        for item in self.inner.by_ref() {
+            match item {
+                Ok(event) => {
+                    if let Some(progress) = self.tracker.update(&event) {
+                        return Some(Ok(progress));
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,85 @@
+//! Unparsed NDJSON line from a streaming response
+
+use bytes::Bytes;
+use serde::Deserialize;
+
+use crate::{Error, Result};
+
+/// One line of a streaming response, left unparsed.
+///
+/// Yielded by [`chat_stream_raw`](super::OllamaApiAsync::chat_stream_raw)
+/// and its blocking counterpart. The line shares the response's buffer, so
+/// receiving it costs no copy; [`parse`](Self::parse) can then deserialize
+/// into a type that borrows its strings from the line, avoiding a `String`
+/// per token.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::RawChunk;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Token<'a> {
+///     #[serde(borrow)]
+///     message: Message<'a>,
+///     done: bool,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Message<'a> {
+///     content: &'a str,
+/// }
+///
+/// let chunk = RawChunk::from(r#"{"message":{"role":"assistant","content":"Hi"},"done":false}"#);
+/// let token: Token = chunk.parse().unwrap();
+/// assert_eq!(token.message.content, "Hi");
+/// assert!(!token.done);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawChunk(Bytes);
+
+impl RawChunk {
+    /// Create a chunk from one NDJSON line, without its terminator
+    pub fn new(line: impl Into<Bytes>) -> Self {
+        Self(line.into())
+    }
+
+    /// Get the line's bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Take the line's bytes
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+
+    /// Deserialize the line, borrowing from it where `T` allows
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::StreamError`] if the line is not valid JSON for `T`,
+    /// as the parsed streams do.
+    pub fn parse<'a, T: Deserialize<'a>>(&'a self) -> Result<T> {
+        serde_json::from_slice(&self.0).map_err(|e| Error::StreamError(e.to_string()))
+    }
+}
+
+impl From<&'static str> for RawChunk {
+    fn from(line: &'static str) -> Self {
+        Self(Bytes::from_static(line.as_bytes()))
+    }
+}
+
+impl From<Bytes> for RawChunk {
+    fn from(line: Bytes) -> Self {
+        Self(line)
+    }
+}
+
+impl AsRef<[u8]> for RawChunk {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
@@ -0,0 +1,271 @@
+//! Client-level defaults filled into requests that omit them
+
+use std::borrow::Cow;
+
+use super::ClientConfig;
+use crate::{ChatMessage, ChatRequest, EmbedRequest, GenerateRequest, KeepAliveSetting};
+
+/// Requests that can take the model, options, and keep-alive from
+/// [`ClientConfig`] when they leave them unset, and have its system prompt
+/// policy applied.
+pub(super) trait RequestDefaults: Clone {
+    /// Fill every unset field that has a configured default.
+    fn apply_defaults(&mut self, config: &ClientConfig);
+}
+
+/// Borrow `request` as-is when the config has no defaults, otherwise return
+/// a copy with the defaults filled in.
+pub(super) fn with_defaults<'a, R: RequestDefaults>(
+    config: &ClientConfig,
+    request: &'a R,
+) -> Cow<'a, R> {
+    if config.default_model().is_none()
+        && config.default_options().is_none()
+        && config.default_keep_alive().is_none()
+        && config.system_prompt_policy().is_none()
+    {
+        return Cow::Borrowed(request);
+    }
+    let mut request = request.clone();
+    request.apply_defaults(config);
+    Cow::Owned(request)
+}
+
+/// Requests with a `stream` flag the streaming entry points force on.
+pub(super) trait StreamingRequest: RequestDefaults {
+    /// Get the stream flag
+    fn stream_flag(&self) -> Option<bool>;
+
+    /// Set the stream flag
+    fn set_stream_flag(&mut self, stream: bool);
+}
+
+/// Like [`with_defaults`], but with `stream` set to `true`.
+///
+/// Borrows `request` when nothing needs filling in and it already streams,
+/// so prompts and images are only copied when a field actually changes.
+pub(super) fn streaming_with_defaults<'a, R: StreamingRequest>(
+    config: &ClientConfig,
+    request: &'a R,
+) -> Cow<'a, R> {
+    let mut request = with_defaults(config, request);
+    if request.stream_flag() != Some(true) {
+        request.to_mut().set_stream_flag(true);
+    }
+    request
+}
+
+fn fill_model(model: &mut String, config: &ClientConfig) {
+    if model.is_empty()
+        && let Some(default) = config.default_model()
+    {
+        *model = default.to_string();
+    }
+}
+
+impl RequestDefaults for GenerateRequest {
+    fn apply_defaults(&mut self, config: &ClientConfig) {
+        fill_model(&mut self.model, config);
+        if self.options.is_none() {
+            self.options = config.default_options().cloned();
+        }
+        if self.keep_alive.is_none() {
+            self.keep_alive = config.default_keep_alive().cloned();
+        }
+        // Raw prompts bypass the template, so there is no system prompt.
+        if let Some(policy) = config.system_prompt_policy()
+            && self.raw != Some(true)
+            && (self.system.is_some() || policy.fills_missing_prompts())
+        {
+            self.system = Some(policy.apply(self.system.as_deref()));
+        }
+    }
+}
+
+impl RequestDefaults for ChatRequest {
+    fn apply_defaults(&mut self, config: &ClientConfig) {
+        fill_model(&mut self.model, config);
+        if self.options.is_none() {
+            self.options = config.default_options().cloned();
+        }
+        if self.keep_alive.is_none() {
+            self.keep_alive = config.default_keep_alive().cloned();
+        }
+        // An empty chat only loads or unloads the model; adding a system
+        // message would turn it into a real turn.
+        if let Some(policy) = config.system_prompt_policy()
+            && !self.messages.is_empty()
+        {
+            match self.messages.iter_mut().find(|m| m.is_system()) {
+                Some(message) => message.content = policy.apply(Some(&message.content)),
+                None if policy.fills_missing_prompts() => self
+                    .messages
+                    .insert(0, ChatMessage::system(policy.apply(None))),
+                // Opted out: leave the Modelfile's SYSTEM prompt in effect.
+                None => {}
+            }
+        }
+    }
+}
+
+impl StreamingRequest for GenerateRequest {
+    fn stream_flag(&self) -> Option<bool> {
+        self.stream
+    }
+
+    fn set_stream_flag(&mut self, stream: bool) {
+        self.stream = Some(stream);
+    }
+}
+
+impl StreamingRequest for ChatRequest {
+    fn stream_flag(&self) -> Option<bool> {
+        self.stream
+    }
+
+    fn set_stream_flag(&mut self, stream: bool) {
+        self.stream = Some(stream);
+    }
+}
+
+impl RequestDefaults for EmbedRequest {
+    fn apply_defaults(&mut self, config: &ClientConfig) {
+        fill_model(&mut self.model, config);
+        if self.options.is_none() {
+            self.options = config.default_options().cloned();
+        }
+        if self.keep_alive.is_none() {
+            // Embed takes keep_alive as a duration string only.
+            self.keep_alive = config.default_keep_alive().map(|k| match k {
+                KeepAliveSetting::Duration(d) => d.clone(),
+                KeepAliveSetting::Seconds(s) => format!("{s}s"),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChatMessage, ModelOptions};
+
+    fn config() -> ClientConfig {
+        ClientConfig::default()
+            .with_default_model("qwen3:0.6b")
+            .with_default_options(ModelOptions::default().with_temperature(0.3))
+            .with_default_keep_alive(600)
+    }
+
+    #[test]
+    fn test_no_defaults_borrows() {
+        let request = GenerateRequest::new("", "hi");
+        let applied = with_defaults(&ClientConfig::default(), &request);
+        assert!(matches!(applied, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_fills_unset_fields() {
+        let request = GenerateRequest::new("", "hi");
+        let applied = with_defaults(&config(), &request);
+        assert_eq!(applied.model, "qwen3:0.6b");
+        assert!(applied.options.is_some());
+        assert_eq!(applied.keep_alive, Some(KeepAliveSetting::Seconds(600)));
+    }
+
+    #[test]
+    fn test_keeps_explicit_fields() {
+        let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")])
+            .with_options(ModelOptions::default().with_temperature(0.9))
+            .with_keep_alive("1m");
+        let applied = with_defaults(&config(), &request);
+        assert_eq!(applied.model, "llama3.2");
+        assert_eq!(applied.options, request.options);
+        assert_eq!(applied.keep_alive, Some(KeepAliveSetting::duration("1m")));
+    }
+
+    #[test]
+    fn test_streaming_borrows_when_already_streaming() {
+        let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")]).with_stream(true);
+        let applied = streaming_with_defaults(&ClientConfig::default(), &request);
+        assert!(matches!(applied, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_streaming_sets_flag() {
+        let request = GenerateRequest::new("llama3.2", "hi");
+        let applied = streaming_with_defaults(&ClientConfig::default(), &request);
+        assert_eq!(applied.stream, Some(true));
+        assert_eq!(request.stream, Some(false));
+    }
+
+    fn policy_config() -> ClientConfig {
+        ClientConfig::default()
+            .with_system_prompt_policy(crate::SystemPromptPolicy::new().with_prefix("Be safe."))
+    }
+
+    #[test]
+    fn test_policy_wraps_existing_system_message() {
+        let request = ChatRequest::new(
+            "llama3.2",
+            [ChatMessage::system("You help."), ChatMessage::user("hi")],
+        );
+        let applied = with_defaults(&policy_config(), &request);
+        assert_eq!(applied.messages.len(), 2);
+        assert_eq!(applied.messages[0].content, "Be safe.\n\nYou help.");
+        assert_eq!(request.messages[0].content, "You help.");
+    }
+
+    #[test]
+    fn test_policy_inserts_missing_system_prompt() {
+        let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")]);
+        let applied = with_defaults(&policy_config(), &request);
+        assert!(applied.messages[0].is_system());
+        assert_eq!(applied.messages[0].content, "Be safe.");
+
+        let request = GenerateRequest::new("llama3.2", "hi");
+        let applied = with_defaults(&policy_config(), &request);
+        assert_eq!(applied.system.as_deref(), Some("Be safe."));
+    }
+
+    #[test]
+    fn test_policy_opt_out_keeps_modelfile_system_prompt() {
+        let config = ClientConfig::default().with_system_prompt_policy(
+            crate::SystemPromptPolicy::new()
+                .with_prefix("Be safe.")
+                .without_missing_prompts(),
+        );
+        let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")]);
+        let applied = with_defaults(&config, &request);
+        assert_eq!(applied.messages, request.messages);
+
+        let request = GenerateRequest::new("llama3.2", "hi");
+        let applied = with_defaults(&config, &request);
+        assert_eq!(applied.system, None);
+
+        let request = GenerateRequest::new("llama3.2", "hi").with_system("You help.");
+        let applied = with_defaults(&config, &request);
+        assert_eq!(applied.system.as_deref(), Some("Be safe.\n\nYou help."));
+    }
+
+    #[test]
+    fn test_policy_skips_raw_generate() {
+        let request = GenerateRequest::new("llama3.2", "hi").with_raw(true);
+        let applied = with_defaults(&policy_config(), &request);
+        assert_eq!(applied.system, None);
+    }
+
+    #[test]
+    fn test_policy_skips_empty_chat() {
+        let request = ChatRequest::new("llama3.2", Vec::<ChatMessage>::new());
+        let applied = with_defaults(&policy_config(), &request);
+        assert!(applied.messages.is_empty());
+    }
+
+    #[test]
+    fn test_embed_keep_alive_as_duration_string() {
+        let request = EmbedRequest::new("", "text");
+        let applied = with_defaults(&config(), &request);
+        assert_eq!(applied.model, "qwen3:0.6b");
+        assert_eq!(applied.keep_alive.as_deref(), Some("600s"));
+    }
+}
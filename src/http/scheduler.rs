@@ -0,0 +1,160 @@
+//! Concurrency limit with priority queues
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use tokio::sync::oneshot;
+
+use super::Priority;
+
+/// Calls running and calls waiting, one queue per [`Priority`]
+#[derive(Debug, Default)]
+struct State {
+    running: usize,
+    waiting: [VecDeque<oneshot::Sender<Permit>>; Priority::ALL.len()],
+}
+
+/// Lets at most `limit` calls run at once; the rest wait in priority
+/// order.
+///
+/// A finishing call hands its slot straight to the next waiter by sending
+/// it a [`Permit`], so a slot is never free while calls wait. A permit
+/// sent to a waiter that has since given up is dropped with the waiter's
+/// channel, which passes the slot on again.
+#[derive(Debug)]
+pub(crate) struct Scheduler {
+    limit: usize,
+    state: Mutex<State>,
+}
+
+impl Scheduler {
+    /// Scheduler running up to `limit` calls at once; at least one
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            state: Mutex::default(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits for a slot for a call of `priority`.
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: Priority) -> Permit {
+        match self.enqueue(priority) {
+            Ok(permit) => permit,
+            Err(rx) => rx.await.expect("queued senders are always sent a permit"),
+        }
+    }
+
+    /// Blocking counterpart of [`acquire`](Self::acquire).
+    pub(crate) fn acquire_blocking(self: &Arc<Self>, priority: Priority) -> Permit {
+        match self.enqueue(priority) {
+            Ok(permit) => permit,
+            Err(rx) => rx
+                .blocking_recv()
+                .expect("queued senders are always sent a permit"),
+        }
+    }
+
+    /// Takes a free slot, or joins the queue for `priority`.
+    fn enqueue(self: &Arc<Self>, priority: Priority) -> Result<Permit, oneshot::Receiver<Permit>> {
+        let mut state = self.lock();
+        if state.running < self.limit {
+            state.running += 1;
+            return Ok(Permit(Some(self.clone())));
+        }
+        let (tx, rx) = oneshot::channel();
+        state.waiting[priority as usize].push_back(tx);
+        Err(rx)
+    }
+
+    /// Hands a finished call's slot to the next waiter, or frees it.
+    fn release(self: &Arc<Self>) {
+        let mut state = self.lock();
+        for queue in state.waiting.iter_mut().rev() {
+            while let Some(tx) = queue.pop_front() {
+                match tx.send(Permit(Some(self.clone()))) {
+                    Ok(()) => return,
+                    // The waiter gave up; disarm the permit and try the next
+                    Err(mut unsent) => unsent.0 = None,
+                }
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+/// One call's slot; passed on when dropped.
+#[derive(Debug)]
+pub(crate) struct Permit(Option<Arc<Scheduler>>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.0.take() {
+            scheduler.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_freed_slot_goes_to_highest_priority() {
+        let scheduler = Arc::new(Scheduler::new(1));
+        let running = scheduler.acquire(Priority::Normal).await;
+
+        let (order_tx, mut order) = tokio::sync::mpsc::unbounded_channel();
+        for priority in [Priority::Low, Priority::Normal, Priority::High] {
+            let (scheduler, order_tx) = (scheduler.clone(), order_tx.clone());
+            tokio::spawn(async move {
+                let _permit = scheduler.acquire(priority).await;
+                order_tx.send(priority).unwrap();
+            });
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        drop(running);
+        let mut dispatched = Vec::new();
+        for _ in 0..3 {
+            dispatched.push(order.recv().await.unwrap());
+        }
+        assert_eq!(
+            dispatched,
+            [Priority::High, Priority::Normal, Priority::Low]
+        );
+        assert_eq!(scheduler.lock().running, 0);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_waiter_passes_slot_on() {
+        let scheduler = Arc::new(Scheduler::new(1));
+        let running = scheduler.acquire(Priority::Normal).await;
+
+        let abandoned = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire(Priority::High).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        abandoned.abort();
+        let _ = abandoned.await;
+
+        drop(running);
+        let permit = tokio::time::timeout(Duration::from_secs(1), scheduler.acquire(Priority::Low))
+            .await
+            .expect("slot was freed");
+        drop(permit);
+        assert_eq!(scheduler.lock().running, 0);
+    }
+
+    #[test]
+    fn test_limit_is_at_least_one() {
+        let scheduler = Arc::new(Scheduler::new(0));
+        drop(scheduler.acquire_blocking(Priority::Normal));
+        assert_eq!(scheduler.lock().running, 0);
+    }
+}
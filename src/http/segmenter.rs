@@ -0,0 +1,178 @@
+//! Re-segmenting streamed text into whole words or sentences
+
+use super::TextDelta;
+
+/// Where a [`Segmenter`] may cut the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Boundary {
+    /// After whitespace
+    Word,
+    /// After whitespace that follows `.`, `!`, `?` or `…` (optionally
+    /// closed by quotes or brackets), after a newline, and after CJK
+    /// full stops
+    Sentence,
+}
+
+/// Punctuation that ends a sentence when followed by whitespace.
+const TERMINATORS: [char; 4] = ['.', '!', '?', '…'];
+
+/// Closing characters allowed between a terminator and the whitespace.
+const CLOSERS: [char; 6] = ['"', '\'', ')', ']', '”', '’'];
+
+/// Punctuation that ends a sentence on its own.
+const CJK_TERMINATORS: [char; 3] = ['。', '！', '？'];
+
+/// Buffers streamed text and releases it only up to the last boundary.
+#[derive(Debug, Clone)]
+pub(crate) struct Segmenter {
+    boundary: Boundary,
+    held: String,
+}
+
+impl Segmenter {
+    pub(crate) fn new(boundary: Boundary) -> Self {
+        Self {
+            boundary,
+            held: String::new(),
+        }
+    }
+
+    /// Feed the next piece of text; returns the complete segments now
+    /// available, or everything held when `last` is set.
+    pub(crate) fn push(&mut self, delta: &str, last: bool) -> String {
+        self.held.push_str(delta);
+        let cut = if last {
+            self.held.len()
+        } else {
+            self.last_boundary()
+        };
+        let rest = self.held.split_off(cut);
+        std::mem::replace(&mut self.held, rest)
+    }
+
+    /// Byte offset just past the last boundary in the held text, or 0.
+    fn last_boundary(&self) -> usize {
+        let text = &self.held;
+        let mut cut = 0;
+        for (i, c) in text.char_indices() {
+            let end = i + c.len_utf8();
+            let is_boundary = match self.boundary {
+                Boundary::Word => c.is_whitespace(),
+                Boundary::Sentence => {
+                    c == '\n'
+                        || CJK_TERMINATORS.contains(&c)
+                        || (c.is_whitespace() && ends_sentence(&text[..i]))
+                }
+            };
+            if is_boundary {
+                cut = end;
+            }
+        }
+        cut
+    }
+}
+
+/// Whether `text` ends with sentence punctuation, ignoring closing quotes
+/// and brackets.
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end_matches(CLOSERS)
+        .chars()
+        .next_back()
+        .is_some_and(|c| TERMINATORS.contains(&c))
+}
+
+/// Applies a [`Segmenter`] to whole events, holding back events whose text
+/// has no complete segment yet.
+pub(crate) struct Resegment<T> {
+    segmenter: Segmenter,
+    pending: Option<T>,
+}
+
+impl<T: TextDelta> Resegment<T> {
+    pub(crate) fn new(boundary: Boundary) -> Self {
+        Self {
+            segmenter: Segmenter::new(boundary),
+            pending: None,
+        }
+    }
+
+    /// The event to yield for `event`, or `None` to hold it until a later
+    /// event completes a segment.
+    ///
+    /// A held event's other deltas (thinking, tool calls) are merged into
+    /// the next yielded event, so nothing is lost.
+    pub(crate) fn apply(&mut self, mut event: T) -> Option<T> {
+        let last = event.is_done();
+        let delta = event.text_delta().unwrap_or_default().to_string();
+        let text = self.segmenter.push(&delta, last);
+        if event.text_delta().is_some() || !text.is_empty() {
+            event.set_text_delta(text.clone());
+        }
+        if let Some(earlier) = self.pending.take() {
+            event.merge_earlier(earlier);
+        }
+        if text.is_empty() && !last {
+            self.pending = Some(event);
+            return None;
+        }
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(boundary: Boundary, deltas: &[&str]) -> Vec<String> {
+        let mut segmenter = Segmenter::new(boundary);
+        deltas
+            .iter()
+            .enumerate()
+            .map(|(i, d)| segmenter.push(d, i + 1 == deltas.len()))
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            run(Boundary::Word, &["Hel", "lo wo", "rld, ", "bye"]),
+            ["Hello ", "world, ", "bye"]
+        );
+    }
+
+    #[test]
+    fn test_sentences() {
+        assert_eq!(
+            run(
+                Boundary::Sentence,
+                &["Pi is 3.", "14. It is ", "irrational! Right?\" Yes", "."]
+            ),
+            ["Pi is 3.14. ", "It is irrational! Right?\" ", "Yes."]
+        );
+    }
+
+    #[test]
+    fn test_sentences_newline_and_cjk() {
+        assert_eq!(
+            run(Boundary::Sentence, &["- one\n- tw", "o\n你好。再", "见"]),
+            ["- one\n", "- two\n你好。", "再见"]
+        );
+    }
+
+    #[test]
+    fn test_resegment_holds_and_merges_events() {
+        let event = |text: &str, done| crate::GenerateResponse {
+            response: Some(text.to_string()),
+            done: Some(done),
+            ..Default::default()
+        };
+        let mut resegment = Resegment::new(Boundary::Word);
+        assert!(resegment.apply(event("Hel", false)).is_none());
+        let out = resegment.apply(event("lo th", false)).unwrap();
+        assert_eq!(out.response.as_deref(), Some("Hello "));
+        let out = resegment.apply(event("ere", true)).unwrap();
+        assert_eq!(out.response.as_deref(), Some("there"));
+        assert!(out.is_done());
+    }
+}
@@ -0,0 +1,94 @@
+//! Server availability status reported by the health watcher
+
+/// Availability of an Ollama server as observed by
+/// [`OllamaClient::watch_health`](super::OllamaClient::watch_health).
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::{OllamaClient, ServerStatus};
+/// use std::time::Duration;
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let mut status = client.watch_health(Duration::from_secs(5));
+/// while status.changed().await.is_ok() {
+///     match &*status.borrow() {
+///         ServerStatus::Up { version } => println!("up (v{})", version),
+///         ServerStatus::Degraded { last_error } => println!("degraded: {}", last_error),
+///         ServerStatus::Down { last_error } => println!("down: {}", last_error),
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ServerStatus {
+    /// No probe has completed yet.
+    #[default]
+    Unknown,
+
+    /// The server answered `GET /api/version` successfully.
+    Up {
+        /// Version reported by the server
+        version: String,
+    },
+
+    /// The server is reachable but answered with an error status or an
+    /// unreadable body.
+    Degraded {
+        /// Description of the last failed probe
+        last_error: String,
+    },
+
+    /// The server could not be reached (connection refused, timeout, DNS).
+    Down {
+        /// Description of the last failed probe
+        last_error: String,
+    },
+}
+
+impl ServerStatus {
+    /// Returns `true` if the last probe succeeded.
+    pub fn is_up(&self) -> bool {
+        matches!(self, Self::Up { .. })
+    }
+
+    /// Returns the error message of the last failed probe, if any.
+    pub fn last_error(&self) -> Option<&str> {
+        match self {
+            Self::Degraded { last_error } | Self::Down { last_error } => Some(last_error),
+            Self::Unknown | Self::Up { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_unknown() {
+        assert_eq!(ServerStatus::default(), ServerStatus::Unknown);
+    }
+
+    #[test]
+    fn test_is_up() {
+        let up = ServerStatus::Up {
+            version: "0.12.6".to_string(),
+        };
+        assert!(up.is_up());
+        assert!(!ServerStatus::Unknown.is_up());
+    }
+
+    #[test]
+    fn test_last_error() {
+        let down = ServerStatus::Down {
+            last_error: "connection refused".to_string(),
+        };
+        assert_eq!(down.last_error(), Some("connection refused"));
+        assert_eq!(ServerStatus::Unknown.last_error(), None);
+    }
+}
@@ -0,0 +1,235 @@
+//! Coalescing of identical concurrent calls
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use bytes::Bytes;
+use reqwest::Method;
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+use tokio::sync::watch;
+
+use super::endpoints::Target;
+use crate::Result;
+
+/// What makes two calls identical: method, URL, and encoded body.
+///
+/// Bodies are encoded from typed requests with a fixed field order, so
+/// equal requests always encode to equal bytes.
+type FlightKey = (Method, String, Bytes);
+
+/// Outcome of a flight; `None` until the leading call finishes
+type Outcome = Option<Result<Bytes>>;
+
+/// The sampling controls of a generate or chat body
+#[derive(Deserialize)]
+struct Sampling {
+    options: Option<SamplingOptions>,
+}
+
+#[derive(Deserialize)]
+struct SamplingOptions {
+    temperature: Option<f64>,
+    seed: Option<IgnoredAny>,
+}
+
+/// Whether a generate or chat body pins its output with a temperature of 0
+/// or a fixed seed
+fn is_deterministic(payload: &[u8]) -> bool {
+    let Ok(Sampling {
+        options: Some(options),
+    }) = serde_json::from_slice(payload)
+    else {
+        return false;
+    };
+    options.seed.is_some() || options.temperature == Some(0.0)
+}
+
+/// Response bodies of calls in flight, shared with identical calls that
+/// arrive before they finish.
+///
+/// The first call for a key leads: it runs and publishes its outcome.
+/// Later calls follow: they wait on the leader's channel. If the leader is
+/// dropped before finishing, e.g. cancelled by shutdown, its channel closes
+/// and one of the followers takes over.
+#[derive(Debug, Default)]
+pub(crate) struct Singleflight {
+    flights: Mutex<HashMap<FlightKey, watch::Receiver<Outcome>>>,
+}
+
+impl Singleflight {
+    /// Whether a call to `target` with `payload` may share another's
+    /// response: it must be safe to repeat and, if sampled, deterministic.
+    pub(crate) fn can_coalesce(target: &Target, payload: Option<&Bytes>) -> bool {
+        target.idempotent && (!target.sampled || payload.is_some_and(|p| is_deterministic(p)))
+    }
+
+    /// Runs `call` for `target`, or waits for an identical call already in
+    /// flight and returns its outcome. The flag is `true` when the outcome
+    /// was shared.
+    pub(crate) async fn run(
+        &self,
+        target: &Target,
+        payload: Option<&Bytes>,
+        call: impl Future<Output = Result<Bytes>>,
+    ) -> (Result<Bytes>, bool) {
+        let key: FlightKey = (
+            target.method.clone(),
+            target.as_str().to_owned(),
+            payload.cloned().unwrap_or_default(),
+        );
+        loop {
+            let leading = {
+                let mut flights = self.flights.lock().unwrap_or_else(PoisonError::into_inner);
+                match flights.get(&key) {
+                    Some(rx) => Err(rx.clone()),
+                    None => {
+                        let (tx, rx) = watch::channel(None);
+                        flights.insert(key.clone(), rx);
+                        Ok(tx)
+                    }
+                }
+            };
+            match leading {
+                Ok(tx) => {
+                    let flight = Flight {
+                        flights: self,
+                        key,
+                        tx,
+                    };
+                    let outcome = call.await;
+                    flight.tx.send_replace(Some(outcome.clone()));
+                    return (outcome, false);
+                }
+                Err(mut rx) => {
+                    if let Ok(outcome) = rx.wait_for(Option::is_some).await
+                        && let Some(outcome) = outcome.clone()
+                    {
+                        return (outcome, true);
+                    }
+                    // The leader was dropped unfinished; try again
+                }
+            }
+        }
+    }
+}
+
+/// A leading call; removes its entry when finished or dropped, which also
+/// closes the channel of any follower still waiting.
+struct Flight<'a> {
+    flights: &'a Singleflight,
+    key: FlightKey,
+    tx: watch::Sender<Outcome>,
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        self.flights
+            .flights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientConfig;
+    use crate::http::endpoints::Endpoint;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn target(endpoint: Endpoint) -> Target {
+        Target::new(&ClientConfig::default(), endpoint)
+    }
+
+    #[test]
+    fn test_only_deterministic_sampled_calls_coalesce() {
+        let chat = target(Endpoint::Chat);
+        let body = |s: &'static str| Bytes::from_static(s.as_bytes());
+        assert!(!Singleflight::can_coalesce(
+            &chat,
+            Some(&body(r#"{"model":"m"}"#))
+        ));
+        assert!(!Singleflight::can_coalesce(
+            &chat,
+            Some(&body(r#"{"options":{"temperature":0.7}}"#))
+        ));
+        assert!(Singleflight::can_coalesce(
+            &chat,
+            Some(&body(r#"{"options":{"temperature":0}}"#))
+        ));
+        assert!(Singleflight::can_coalesce(
+            &chat,
+            Some(&body(r#"{"options":{"temperature":0.7,"seed":42}}"#))
+        ));
+
+        assert!(Singleflight::can_coalesce(
+            &target(Endpoint::Embed),
+            Some(&body("{}"))
+        ));
+        assert!(Singleflight::can_coalesce(&target(Endpoint::Tags), None));
+        assert!(!Singleflight::can_coalesce(
+            &target(Endpoint::Create),
+            Some(&body("{}"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_run() {
+        let flights = Arc::new(Singleflight::default());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let (flights, runs) = (flights.clone(), runs.clone());
+                tokio::spawn(async move {
+                    let call = async {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(Bytes::from_static(b"shared"))
+                    };
+                    flights.run(&target(Endpoint::Tags), None, call).await
+                })
+            })
+            .collect();
+
+        let mut shared = 0;
+        for task in tasks {
+            let (outcome, was_shared) = task.await.unwrap();
+            assert_eq!(outcome.unwrap(), "shared");
+            shared += usize::from(was_shared);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(shared, 3);
+        assert!(flights.flights.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_follower_takes_over_from_dropped_leader() {
+        let flights = Arc::new(Singleflight::default());
+        let leader = tokio::spawn({
+            let flights = flights.clone();
+            async move {
+                let call = std::future::pending();
+                flights.run(&target(Endpoint::Tags), None, call).await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let follower = tokio::spawn({
+            let flights = flights.clone();
+            async move {
+                let call = async { Ok(Bytes::from_static(b"own")) };
+                flights.run(&target(Endpoint::Tags), None, call).await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+
+        let (outcome, shared) = follower.await.unwrap();
+        assert_eq!(outcome.unwrap(), "own");
+        assert!(!shared);
+    }
+}
@@ -0,0 +1,155 @@
+//! Client-side stop sequences over streamed text
+
+use super::TextDelta;
+
+/// Finds stop sequences in streamed text, including sequences split across
+/// events.
+///
+/// Text that could be the start of a stop sequence is held back until the
+/// next event shows whether the sequence completes; it is released with the
+/// next event or the final one.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StopMatcher {
+    sequences: Vec<String>,
+    held: String,
+    stopped: bool,
+}
+
+impl StopMatcher {
+    pub(crate) fn new<I, S>(sequences: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            sequences: sequences
+                .into_iter()
+                .map(Into::into)
+                .filter(|s| !s.is_empty())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Feed the next piece of text; returns the text safe to emit now.
+    ///
+    /// After a stop sequence is found, returns the text before it and
+    /// nothing on later calls.
+    pub(crate) fn push(&mut self, delta: &str, last: bool) -> String {
+        if self.stopped {
+            return String::new();
+        }
+        let mut text = std::mem::take(&mut self.held);
+        text.push_str(delta);
+
+        let first_match = self
+            .sequences
+            .iter()
+            .filter_map(|s| text.find(s.as_str()))
+            .min();
+        if let Some(at) = first_match {
+            self.stopped = true;
+            text.truncate(at);
+            return text;
+        }
+        if !last {
+            let keep = self.partial_suffix(&text);
+            self.held = text.split_off(text.len() - keep);
+        }
+        text
+    }
+
+    /// Length of the longest suffix of `text` that starts a stop sequence.
+    fn partial_suffix(&self, text: &str) -> usize {
+        self.sequences
+            .iter()
+            .filter_map(|s| {
+                (1..s.len())
+                    .rev()
+                    .filter(|&n| s.is_char_boundary(n))
+                    .find(|&n| text.ends_with(&s[..n]))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Rewrite `event`'s text; returns `true` once the stream should end.
+    pub(crate) fn apply<T: TextDelta>(&mut self, event: &mut T) -> bool {
+        let last = event.is_done();
+        let delta = event.text_delta().unwrap_or_default().to_string();
+        let text = self.push(&delta, last);
+        if event.text_delta().is_some() || !text.is_empty() {
+            event.set_text_delta(text);
+        }
+        if self.stopped {
+            event.finish_stopped();
+        }
+        self.stopped || last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(sequences: &[&str], deltas: &[&str]) -> (String, bool) {
+        let mut matcher = StopMatcher::new(sequences.iter().copied());
+        let mut out = String::new();
+        for (i, delta) in deltas.iter().enumerate() {
+            out.push_str(&matcher.push(delta, i + 1 == deltas.len()));
+            if matcher.stopped {
+                break;
+            }
+        }
+        (out, matcher.stopped)
+    }
+
+    #[test]
+    fn test_stop_within_one_delta() {
+        assert_eq!(run(&["END"], &["abcENDdef"]), ("abc".to_string(), true));
+    }
+
+    #[test]
+    fn test_stop_across_deltas() {
+        assert_eq!(
+            run(&["</answer>"], &["42</an", "swer> trailing"]),
+            ("42".to_string(), true)
+        );
+    }
+
+    #[test]
+    fn test_held_text_released_when_sequence_breaks() {
+        assert_eq!(
+            run(&["###"], &["a#", "#b", "c"]),
+            ("a##bc".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_earliest_of_several_sequences_wins() {
+        assert_eq!(run(&["Z", "b"], &["abcZ"]), ("a".to_string(), true));
+    }
+
+    #[test]
+    fn test_multibyte_partial_match() {
+        assert_eq!(run(&["é!"], &["caf", "é", "!"]), ("caf".to_string(), true));
+    }
+
+    #[test]
+    fn test_apply_marks_event_stopped() {
+        let mut matcher = StopMatcher::new(["\n\n"]);
+        let mut event = crate::GenerateResponse {
+            response: Some("done.\n\nextra".to_string()),
+            done: Some(false),
+            ..Default::default()
+        };
+        assert!(matcher.apply(&mut event));
+        assert_eq!(event.response.as_deref(), Some("done."));
+        assert_eq!(event.done, Some(true));
+        assert_eq!(event.done_reason, Some(crate::DoneReason::ClientStop));
+    }
+}
@@ -0,0 +1,97 @@
+//! Resuming chat streams interrupted mid-reply
+
+use super::OllamaClient;
+use super::endpoints::Target;
+use super::image_io::image_body;
+use crate::{ChatMessage, ChatRequest, ChatResponse, ChatStream, Error, Result};
+
+/// Whether a reply containing `event` can still be continued from its text
+fn continuable(event: &ChatResponse) -> bool {
+    #[cfg(feature = "tools")]
+    if event.has_tool_calls() {
+        return false;
+    }
+    event.thinking().is_none_or(str::is_empty)
+}
+
+impl OllamaClient {
+    /// Stream `request` through `target`, re-issuing it up to `attempts`
+    /// times with the reply so far as a trailing assistant message when the
+    /// stream is interrupted.
+    ///
+    /// See [`ClientConfig::with_stream_resume`](super::ClientConfig::with_stream_resume).
+    pub(super) async fn resumable_chat_stream(
+        &self,
+        target: &Target,
+        request: ChatRequest,
+        attempts: u32,
+    ) -> Result<ChatStream> {
+        let first: ChatStream = self
+            .post_ndjson_stream(target, &image_body(self, &request).await?)
+            .await?;
+        let (mut rx, state) = first.track_partial().into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+        let client = self.clone();
+        let target = target.clone();
+
+        tokio::spawn(async move {
+            let mut text = String::new();
+            let mut resumable = true;
+            let mut attempts = attempts;
+            loop {
+                let item = tokio::select! {
+                    item = rx.recv() => item,
+                    () = tx.closed() => return,
+                };
+                let Some(item) = item else {
+                    return;
+                };
+                let item = match item {
+                    Ok(event) => {
+                        text.push_str(event.content().unwrap_or_default());
+                        resumable &= continuable(&event);
+                        Ok(event)
+                    }
+                    Err(Error::StreamInterrupted { message, .. }) if resumable && attempts > 0 => {
+                        attempts -= 1;
+                        let mut next = request.clone();
+                        if !text.is_empty() {
+                            next.messages.push(ChatMessage::assistant(text.clone()));
+                        }
+                        let resumed = match image_body(&client, &next).await {
+                            Ok(body) => {
+                                client
+                                    .post_ndjson_stream::<_, ChatResponse>(&target, &body)
+                                    .await
+                            }
+                            Err(e) => Err(e),
+                        };
+                        match resumed {
+                            Ok(stream) => {
+                                rx = stream.track_partial().into_parts().0;
+                                continue;
+                            }
+                            Err(e) => Err(Error::StreamInterrupted {
+                                partial: text.clone(),
+                                message: format!("{message}; resuming failed: {e}"),
+                            }),
+                        }
+                    }
+                    Err(Error::StreamInterrupted { message, .. }) => {
+                        Err(Error::StreamInterrupted {
+                            partial: text.clone(),
+                            message,
+                        })
+                    }
+                    Err(e) => Err(e),
+                };
+                let end = item.as_ref().is_ok_and(ChatResponse::is_done);
+                if tx.send(item).await.is_err() || end {
+                    return;
+                }
+            }
+        });
+
+        Ok(ChatStream::new(out, state))
+    }
+}
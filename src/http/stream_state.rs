@@ -0,0 +1,142 @@
+//! State shared by a `ResponseStream`, its producer, and its adapters
+
+use std::sync::OnceLock;
+#[cfg(feature = "stream")]
+use std::sync::{Mutex, PoisonError};
+#[cfg(feature = "stream")]
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+/// Start time, time-to-first-token, pause flag, and channel capacity of
+/// one stream.
+///
+/// The producer task and every adapter built from the stream share it, so
+/// pausing the outermost stream pauses reading from the socket, and
+/// adapters buffer as many events as the producer.
+#[derive(Debug)]
+pub(crate) struct StreamState {
+    started: Instant,
+    first_token: OnceLock<Duration>,
+    paused: watch::Sender<bool>,
+    /// Tasks polling the stream while paused, woken on resume
+    #[cfg(feature = "stream")]
+    resume_wakers: Mutex<Vec<Waker>>,
+    buffer: usize,
+}
+
+impl StreamState {
+    /// State for a stream whose request was sent at `started` and whose
+    /// channels hold up to `buffer` events
+    pub(crate) fn new(started: Instant, buffer: usize) -> Self {
+        Self {
+            started,
+            first_token: OnceLock::new(),
+            paused: watch::Sender::new(false),
+            #[cfg(feature = "stream")]
+            resume_wakers: Mutex::new(Vec::new()),
+            buffer: buffer.max(1),
+        }
+    }
+
+    /// Events each channel of the stream holds before its sender waits
+    pub(crate) fn buffer(&self) -> usize {
+        self.buffer
+    }
+
+    /// When the stream's request was sent
+    pub(crate) fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Latency of the first event, once it arrived
+    pub(crate) fn first_token(&self) -> Option<Duration> {
+        self.first_token.get().copied()
+    }
+
+    /// Records the first event's latency; `false` if one was already set.
+    pub(crate) fn set_first_token(&self, latency: Duration) -> bool {
+        self.first_token.set(latency).is_ok()
+    }
+
+    pub(crate) fn set_paused(&self, paused: bool) {
+        self.paused.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        });
+        #[cfg(feature = "stream")]
+        if !paused {
+            let wakers = std::mem::take(
+                &mut *self
+                    .resume_wakers
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner),
+            );
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Completes at once unless paused, otherwise on resume.
+    pub(crate) async fn resumed(&self) {
+        if self.is_paused() {
+            let mut paused = self.paused.subscribe();
+            let _ = paused.wait_for(|p| !*p).await;
+        }
+    }
+
+    /// [`resumed`](Self::resumed) for `Stream::poll_next`.
+    #[cfg(feature = "stream")]
+    pub(crate) fn poll_resumed(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if !self.is_paused() {
+            return Poll::Ready(());
+        }
+        self.resume_wakers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(cx.waker().clone());
+        // A resume between the check and the push would be missed
+        if self.is_paused() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_resumed_waits_while_paused() {
+        let state = Arc::new(StreamState::new(Instant::now(), 1));
+        state.resumed().await;
+
+        state.set_paused(true);
+        let waiter = tokio::spawn({
+            let state = state.clone();
+            async move { state.resumed().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        state.set_paused(false);
+        waiter.await.unwrap();
+    }
+
+    #[test]
+    fn test_first_token_set_once() {
+        let state = StreamState::new(Instant::now(), 0);
+        assert_eq!(state.buffer(), 1);
+        assert!(state.set_first_token(Duration::from_millis(5)));
+        assert!(!state.set_first_token(Duration::from_millis(9)));
+        assert_eq!(state.first_token(), Some(Duration::from_millis(5)));
+    }
+}
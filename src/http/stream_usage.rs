@@ -0,0 +1,138 @@
+//! Running token counts of a streamed response
+
+use std::time::Duration;
+
+use super::TextDelta;
+use super::token_budget::estimate_len_tokens;
+use crate::EvalMetrics;
+
+/// Token counts of a stream so far, paired with each event by
+/// [`ResponseStream::with_usage`](super::ResponseStream::with_usage).
+///
+/// The server reports token counts only on the final event. Until then
+/// `completion_tokens` is estimated from the text and thinking received:
+/// at least one token per event that carried any, and about four
+/// characters per token. The final event's counts replace the estimate and
+/// clear [`estimated`](Self::estimated). Streams cut short, e.g. by
+/// [`stop_at`](super::ResponseStream::stop_at), end with the estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamUsage {
+    /// Tokens in the prompt; `None` until the server reports them
+    pub prompt_tokens: Option<u32>,
+
+    /// Tokens generated so far, thinking included
+    pub completion_tokens: u32,
+
+    /// Whether `completion_tokens` is a client-side estimate rather than
+    /// the server's count
+    pub estimated: bool,
+}
+
+impl StreamUsage {
+    /// Prompt plus completion tokens, once the prompt count is known
+    pub fn total_tokens(&self) -> Option<u32> {
+        self.prompt_tokens
+            .map(|prompt| prompt.saturating_add(self.completion_tokens))
+    }
+}
+
+/// Tracks a [`StreamUsage`] across the events of one stream.
+#[derive(Debug, Default)]
+pub(crate) struct UsageCounter {
+    bytes: usize,
+    deltas: u32,
+    usage: StreamUsage,
+}
+
+impl UsageCounter {
+    /// Count `event` and return the usage so far.
+    pub(crate) fn update<T: TextDelta>(&mut self, event: &T) -> StreamUsage {
+        let metrics = event.eval_metrics();
+        if let Some(prompt) = metrics.prompt_eval_count {
+            self.usage.prompt_tokens = Some(prompt.max(0) as u32);
+        }
+        if let Some(completion) = metrics.eval_count {
+            self.usage.completion_tokens = completion.max(0) as u32;
+            self.usage.estimated = false;
+            return self.usage;
+        }
+
+        let len =
+            event.text_delta().map_or(0, str::len) + event.thinking_delta().map_or(0, str::len);
+        if len > 0 {
+            self.bytes += len;
+            self.deltas += 1;
+        }
+        let estimate = u32::try_from(estimate_len_tokens(self.bytes)).unwrap_or(u32::MAX);
+        self.usage.completion_tokens = self.deltas.max(estimate);
+        self.usage.estimated = true;
+        self.usage
+    }
+
+    /// Metrics for a stream cancelled after running for `elapsed`: the
+    /// token counts so far, estimated unless the server sent them.
+    pub(crate) fn partial_metrics(&self, elapsed: Duration) -> EvalMetrics {
+        EvalMetrics {
+            total_duration: Some(i64::try_from(elapsed.as_nanos()).unwrap_or(i64::MAX)),
+            prompt_eval_count: self.usage.prompt_tokens.map(saturating_i32),
+            eval_count: Some(saturating_i32(self.usage.completion_tokens)),
+            ..EvalMetrics::default()
+        }
+    }
+}
+
+fn saturating_i32(n: u32) -> i32 {
+    i32::try_from(n).unwrap_or(i32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenerateResponse;
+
+    fn delta(text: &str) -> GenerateResponse {
+        GenerateResponse {
+            response: Some(text.to_string()),
+            done: Some(false),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_estimates_then_takes_final_counts() {
+        let mut counter = UsageCounter::default();
+        assert_eq!(counter.update(&delta("Hi")).completion_tokens, 1);
+        assert_eq!(counter.update(&delta("")).completion_tokens, 1);
+        let usage = counter.update(&delta(" there, how are you doing?"));
+        assert_eq!(usage.completion_tokens, 7);
+        assert!(usage.estimated);
+        assert_eq!(usage.total_tokens(), None);
+
+        let last = GenerateResponse {
+            done: Some(true),
+            prompt_eval_count: Some(12),
+            eval_count: Some(8),
+            ..Default::default()
+        };
+        let usage = counter.update(&last);
+        assert_eq!(
+            usage,
+            StreamUsage {
+                prompt_tokens: Some(12),
+                completion_tokens: 8,
+                estimated: false,
+            }
+        );
+        assert_eq!(usage.total_tokens(), Some(20));
+    }
+
+    #[test]
+    fn test_counts_thinking() {
+        let mut counter = UsageCounter::default();
+        let event = GenerateResponse {
+            thinking: Some("Let me think about it".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(counter.update(&event).completion_tokens, 6);
+    }
+}
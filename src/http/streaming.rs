@@ -1,12 +1,129 @@
 //! Streaming response types for NDJSON APIs (e.g. `POST /api/chat` with `stream: true`).
 
-use std::io::{BufRead, BufReader};
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::{ChatResponse, Error, Result};
+use bytes::{Bytes, BytesMut};
+use serde::de::DeserializeOwned;
+
+use super::call_context::CallContext;
+use super::gateway_profile::sse_data;
+use super::lifecycle::InFlight;
+use super::observer::ObserverSet;
+use super::partial_output::PartialOutput;
+use super::post_processor::PostProcessing;
+use super::segmenter::{Boundary, Resegment};
+use super::stop_matcher::StopMatcher;
+use super::stream_state::StreamState;
+use super::stream_usage::UsageCounter;
+use super::{
+    CancelToken, JsonLimits, PauseHandle, PostProcessor, RawChunk, StreamUsage, TextDelta,
+};
+use crate::{
+    CancelReason, ChatResponse, Error, EvalMetrics, GenerateResponse, ResponseEvent, Result,
+};
 
 /// Async stream of [`ChatResponse`] events from a streaming chat request.
+pub type ChatStream = ResponseStream<ChatResponse>;
+
+/// Blocking iterator over [`ChatResponse`] events from a streaming chat request.
+pub type ChatStreamBlocking = ResponseStreamBlocking<ChatResponse>;
+
+/// Async stream of [`GenerateResponse`] events from a streaming generate request.
+pub type GenerateStream = ResponseStream<GenerateResponse>;
+
+/// Blocking iterator over [`GenerateResponse`] events from a streaming generate request.
+pub type GenerateStreamBlocking = ResponseStreamBlocking<GenerateResponse>;
+
+/// Async stream of unparsed lines from a streaming request.
+pub type RawChunkStream = ResponseStream<RawChunk>;
+
+/// Blocking iterator over unparsed lines from a streaming request.
+pub type RawChunkStreamBlocking = ResponseStreamBlocking<RawChunk>;
+
+/// Async stream of response body chunks, as received from the server.
+pub type ByteStream = ResponseStream<Bytes>;
+
+/// Parses one trimmed NDJSON line into a stream item.
+pub(crate) type LineParser<T> = fn(&[u8]) -> Result<T>;
+
+/// [`LineParser`] for JSON events.
+pub(crate) fn parse_json_line<T: DeserializeOwned>(line: &[u8]) -> Result<T> {
+    serde_json::from_slice(line).map_err(|e| Error::StreamError(e.to_string()))
+}
+
+/// [`LineParser`] for [`RawChunk`]s, for readers that hold lines by
+/// reference; the async producer hands over [`Bytes`] directly instead.
+pub(crate) fn parse_raw_line(line: &[u8]) -> Result<RawChunk> {
+    Ok(RawChunk::new(Bytes::copy_from_slice(line)))
+}
+
+/// Splits response chunks into NDJSON lines.
+///
+/// A line that lies within one chunk is returned as a slice of that chunk,
+/// without copying; only lines split across chunks are stitched together in
+/// `pending`.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    pending: BytesMut,
+    current: Bytes,
+}
+
+impl LineBuffer {
+    /// Queue the next chunk; call once [`next_line`](Self::next_line) has
+    /// returned `None`.
+    pub(crate) fn push(&mut self, chunk: Bytes) {
+        debug_assert!(self.current.is_empty());
+        self.current = chunk;
+    }
+
+    /// The next complete line without its `\n` or `\r\n`, or `None` when
+    /// more input is needed.
+    pub(crate) fn next_line(&mut self) -> Option<Bytes> {
+        let Some(idx) = self.current.iter().position(|&b| b == b'\n') else {
+            self.pending.extend_from_slice(&self.current);
+            self.current = Bytes::new();
+            return None;
+        };
+        let head = self.current.split_to(idx + 1);
+        let line = if self.pending.is_empty() {
+            head
+        } else {
+            self.pending.extend_from_slice(&head);
+            self.pending.split().freeze()
+        };
+        let end = line.len() - 1;
+        let end = if line[..end].ends_with(b"\r") {
+            end - 1
+        } else {
+            end
+        };
+        Some(line.slice(..end))
+    }
+
+    /// Bytes buffered towards an incomplete line
+    pub(crate) fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// The unterminated tail once the body has ended, trimmed of whitespace.
+    pub(crate) fn finish(mut self) -> Bytes {
+        self.pending.extend_from_slice(&self.current);
+        let tail = self.pending.freeze();
+        let start = tail.len() - tail.trim_ascii_start().len();
+        let end = tail.trim_ascii_end().len();
+        if start >= end {
+            return Bytes::new();
+        }
+        tail.slice(start..end)
+    }
+}
+
+/// Async stream of events from a streaming (NDJSON) endpoint.
 ///
-/// Each [`next`](Self::next) yields one NDJSON line deserialized as [`ChatResponse`].
+/// Each [`next`](Self::next) yields one NDJSON line deserialized as `T`.
 /// When the server closes the body, [`next`](Self::next) returns `None`.
 ///
 /// # Examples
@@ -28,28 +145,616 @@ use crate::{ChatResponse, Error, Result};
 ///     Ok(())
 /// }
 /// ```
-pub struct ChatStream {
-    rx: tokio::sync::Mutex<tokio::sync::mpsc::Receiver<Result<ChatResponse>>>,
+pub struct ResponseStream<T> {
+    rx: tokio::sync::Mutex<tokio::sync::mpsc::Receiver<Result<T>>>,
+    state: Arc<StreamState>,
 }
 
-impl ChatStream {
+impl<T> ResponseStream<T> {
     /// Wraps a channel receiver produced by the HTTP client streaming helper.
-    pub(crate) fn new(rx: tokio::sync::mpsc::Receiver<Result<ChatResponse>>) -> Self {
+    ///
+    /// The producer records the first event's latency in `state` and stops
+    /// reading while it is paused.
+    pub(crate) fn new(rx: tokio::sync::mpsc::Receiver<Result<T>>, state: Arc<StreamState>) -> Self {
         Self {
             rx: tokio::sync::Mutex::new(rx),
+            state,
         }
     }
 
+    /// Unwraps the channel receiver and shared state, for adapters that
+    /// forward events.
+    pub(crate) fn into_parts(self) -> (tokio::sync::mpsc::Receiver<Result<T>>, Arc<StreamState>) {
+        (self.rx.into_inner(), self.state)
+    }
+
+    /// Time from sending the request to receiving the first event.
+    ///
+    /// This is the time-to-first-token (TTFT) of the stream. It is measured
+    /// when the event arrives from the server, not when [`next`](Self::next)
+    /// returns it, and is `None` until then. Adapters such as
+    /// [`tee`](Self::tee) keep the upstream measurement.
+    pub fn first_token_latency(&self) -> Option<Duration> {
+        self.state.first_token()
+    }
+
     /// Returns the next event, or `None` when the stream has ended.
-    pub async fn next(&self) -> Option<Result<ChatResponse>> {
+    ///
+    /// Waits for [`resume`](Self::resume) while the stream is paused.
+    pub async fn next(&self) -> Option<Result<T>> {
+        self.state.resumed().await;
         self.rx.lock().await.recv().await
     }
 
+    /// Pause the stream without cancelling the request.
+    ///
+    /// The client stops reading the response, so the server's output backs
+    /// up in the connection until the server itself stalls; nothing is
+    /// lost. [`next`](Self::next) waits until [`resume`](Self::resume),
+    /// even for events that had already arrived. Pausing an adapted stream,
+    /// such as one from [`tee`](Self::tee), pauses the whole chain.
+    ///
+    /// The request's total timeout keeps running while paused, as does the
+    /// client's read timeout if one is configured, so very long pauses
+    /// end the stream with an error on resume.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Tell a story.")]);
+    /// let stream = client.chat_stream(&request).await?;
+    /// let pause = stream.pause_handle();
+    ///
+    /// // e.g. from a UI button handler
+    /// tokio::spawn(async move {
+    ///     pause.pause();
+    ///     tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    ///     pause.resume();
+    /// });
+    ///
+    /// while let Some(event) = stream.next().await {
+    ///     print!("{}", event?.content().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pause(&self) {
+        self.state.set_paused(true);
+    }
+
+    /// Continue a stream stopped with [`pause`](Self::pause).
+    pub fn resume(&self) {
+        self.state.set_paused(false);
+    }
+
+    /// Whether the stream is paused
+    pub fn is_paused(&self) -> bool {
+        self.state.is_paused()
+    }
+
+    /// A handle that pauses and resumes this stream from another task.
+    pub fn pause_handle(&self) -> PauseHandle {
+        PauseHandle(self.state.clone())
+    }
+
+    /// Forward each event to `observer` as it passes through.
+    ///
+    /// The returned stream yields exactly the same events, so the primary
+    /// consumer is unaffected; `observer` sees each successful event by
+    /// reference just before it is handed on. Use it for logging, transcript
+    /// recording, or metrics without collecting the stream. Errors are not
+    /// passed to `observer`. Tees can be chained.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi!")]);
+    /// let stream = client
+    ///     .chat_stream(&request)
+    ///     .await?
+    ///     .tee(|chunk| eprintln!("[log] {:?}", chunk.content()));
+    /// while let Some(event) = stream.next().await {
+    ///     print!("{}", event?.content().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tee<F>(self, mut observer: F) -> Self
+    where
+        T: Send + 'static,
+        F: FnMut(&T) + Send + 'static,
+    {
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(item) = rx.recv().await {
+                if let Ok(event) = &item {
+                    observer(event);
+                }
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// End the stream at the first of `sequences` in the generated text.
+    ///
+    /// The text is cut just before the sequence, even when it is split
+    /// across events, and that event is returned as the final one with
+    /// `done` set, a `done_reason` of
+    /// [`DoneReason::ClientStop`](crate::DoneReason::ClientStop), and a
+    /// `cancel_reason` of [`CancelReason::StopSequence`]. The request is then
+    /// cancelled: the connection closes once the server sends its next
+    /// event. Use it where the server-side `stop` option is unavailable or
+    /// not enough, e.g. for sequences that depend on the conversation.
+    ///
+    /// Text that may start a sequence is held back until the next event
+    /// decides it, so output can lag by up to one sequence length. Must be
+    /// called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("List three colors.")]);
+    /// let stream = client.chat_stream(&request).await?.stop_at(["\n4."]);
+    /// while let Some(event) = stream.next().await {
+    ///     print!("{}", event?.content().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stop_at<I, S>(self, sequences: I) -> Self
+    where
+        T: TextDelta + Send + 'static,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut matcher = StopMatcher::new(sequences);
+        if matcher.is_empty() {
+            return self;
+        }
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(mut item) = rx.recv().await {
+                // Errors pass through; the producer ends the stream when
+                // it cannot continue.
+                let end = item.as_mut().is_ok_and(|event| matcher.apply(event));
+                if tx.send(item).await.is_err() || end {
+                    return;
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// Rewrite the generated text with `processor` as it streams.
+    ///
+    /// Gives the same text as [`PostProcessor::apply`] on the whole reply;
+    /// text that may still be part of a tag, fence, or stop sequence is
+    /// held back until a later event decides it. Streams from a client with
+    /// [`ClientConfig::with_post_processor`](super::ClientConfig::with_post_processor)
+    /// are already processed. Must be called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient, PostProcessor};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("deepseek-r1:1.5b", [ChatMessage::user("Hi!")]);
+    /// let stream = client
+    ///     .chat_stream(&request)
+    ///     .await?
+    ///     .post_process(&PostProcessor::new().strip_think().trim());
+    /// while let Some(event) = stream.next().await {
+    ///     print!("{}", event?.content().unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn post_process(self, processor: &PostProcessor) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        if processor.is_empty() {
+            return self;
+        }
+        let mut processing = processor.start();
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(mut item) = rx.recv().await {
+                if let Ok(event) = &mut item {
+                    processing.apply(event);
+                }
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// Yield text in whole words.
+    ///
+    /// Each event's text ends after whitespace; a word split across events
+    /// is held back until it is complete. Events with no complete word yet
+    /// are held and merged into the next one (see
+    /// [`TextDelta::merge_earlier`]), and the final event carries whatever
+    /// remains. Must be called from within a Tokio runtime.
+    pub fn words(self) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        self.resegment(Boundary::Word)
+    }
+
+    /// Yield text in whole sentences, e.g. for text-to-speech or subtitles.
+    ///
+    /// A sentence ends at whitespace after `.`, `!`, `?` or `…` (closing
+    /// quotes and brackets allowed in between), at a newline, or after a
+    /// CJK full stop, so `3.14` is not split. Otherwise behaves like
+    /// [`words`](Self::words).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Tell me a story.")]);
+    /// let stream = client.chat_stream(&request).await?.sentences();
+    /// while let Some(event) = stream.next().await {
+    ///     let sentence = event?.content().unwrap_or_default().to_string();
+    ///     println!("speak: {sentence}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sentences(self) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        self.resegment(Boundary::Sentence)
+    }
+
+    fn resegment(self, boundary: Boundary) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        let mut resegment = Resegment::new(boundary);
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(item) = rx.recv().await {
+                let item = match item {
+                    Ok(event) => match resegment.apply(event) {
+                        Some(event) => Ok(event),
+                        None => continue,
+                    },
+                    Err(e) => Err(e),
+                };
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// Batch events into at most one update per `period`.
+    ///
+    /// Fast models can emit a token every few milliseconds; redrawing a TUI
+    /// or GUI for each one wastes work. The returned stream yields the
+    /// first event at once, then merges everything that arrives within each
+    /// `period` into a single event whose text, thinking, and tool calls are
+    /// the concatenation of the merged deltas (see
+    /// [`TextDelta::merge_earlier`]). The final event, an error, or the end
+    /// of the stream flushes pending text immediately, so nothing is lost
+    /// and the last event still carries the completion metrics. A zero
+    /// `period` returns the stream unchanged.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Tell me a story.")]);
+    /// let stream = client
+    ///     .chat_stream(&request)
+    ///     .await?
+    ///     .coalesce(Duration::from_millis(50));
+    /// while let Some(event) = stream.next().await {
+    ///     print!("{}", event?.content().unwrap_or_default()); // ~20 redraws per second
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn coalesce(self, period: Duration) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        // `interval` panics on a zero period, which would silently end the stream.
+        if period.is_zero() {
+            return self;
+        }
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            let mut pending: Option<T> = None;
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.reset();
+            let mut started = false;
+
+            loop {
+                tokio::select! {
+                    item = rx.recv() => {
+                        let event = match item {
+                            Some(Ok(event)) => event,
+                            end => {
+                                if let Some(event) = pending.take()
+                                    && tx.send(Ok(event)).await.is_err()
+                                {
+                                    return;
+                                }
+                                // Flush around the error, then keep going.
+                                match end {
+                                    Some(Err(e)) => {
+                                        if tx.send(Err(e)).await.is_err() {
+                                            return;
+                                        }
+                                        continue;
+                                    }
+                                    _ => return,
+                                }
+                            }
+                        };
+                        let done = event.is_done();
+                        let event = match pending.take() {
+                            Some(earlier) => {
+                                let mut event = event;
+                                event.merge_earlier(earlier);
+                                event
+                            }
+                            None => event,
+                        };
+                        if done || !started {
+                            started = true;
+                            ticker.reset();
+                            if tx.send(Ok(event)).await.is_err() || done {
+                                return;
+                            }
+                        } else {
+                            pending = Some(event);
+                        }
+                    }
+                    _ = ticker.tick(), if pending.is_some() => {
+                        if let Some(event) = pending.take()
+                            && tx.send(Ok(event)).await.is_err()
+                        {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// Pair each event with the [`StreamUsage`] so far, for live token
+    /// counters.
+    ///
+    /// Counts are estimated from the text until the final event brings the
+    /// server's. Apply it last, after adapters such as
+    /// [`coalesce`](Self::coalesce), so the counts match the events seen.
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Tell me a story.")]);
+    /// let stream = client.chat_stream(&request).await?.with_usage();
+    /// while let Some(event) = stream.next().await {
+    ///     let (chunk, usage) = event?;
+    ///     print!("{}", chunk.content().unwrap_or_default());
+    ///     let marker = if usage.estimated { "~" } else { "" };
+    ///     eprint!("\r[{marker}{} tokens]", usage.completion_tokens);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_usage(self) -> ResponseStream<(T, StreamUsage)>
+    where
+        T: TextDelta + Send + 'static,
+    {
+        let mut counter = UsageCounter::default();
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(item) = rx.recv().await {
+                let item = item.map(|event| {
+                    let usage = counter.update(&event);
+                    (event, usage)
+                });
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        ResponseStream::new(out, state)
+    }
+
+    /// End the stream when `token` is cancelled.
+    ///
+    /// The stream then yields one last event with `done` set, a
+    /// `cancel_reason` saying why, and the metrics gathered so far: the
+    /// time since the request was sent in `total_duration`, and the token
+    /// counts in `prompt_eval_count` and `eval_count`, estimated as in
+    /// [`with_usage`](Self::with_usage) and flagged by `counts_estimated`.
+    /// Errors still arrive as `Err` items, so a cancelled stream can be told
+    /// apart from a failed one. The request is cancelled as with
+    /// [`stop_at`](Self::stop_at). Must be called from within a Tokio
+    /// runtime.
+    ///
+    /// See [`CancelToken`] for an example.
+    pub fn with_cancel(self, token: &CancelToken) -> Self
+    where
+        T: TextDelta + Default + Send + 'static,
+    {
+        let token = token.clone();
+        self.cancel_on(async move { token.cancelled().await })
+    }
+
+    /// End the stream once `timeout` has passed, counted from this call.
+    ///
+    /// Like [`with_cancel`](Self::with_cancel), with a `cancel_reason` of
+    /// [`CancelReason::Timeout`]: the caller gets the partial reply rather
+    /// than the [`Error::TimeoutError`](crate::Error::TimeoutError) of the
+    /// client's request timeout.
+    pub fn with_timeout(self, timeout: Duration) -> Self
+    where
+        T: TextDelta + Default + Send + 'static,
+    {
+        self.cancel_on(async move {
+            tokio::time::sleep(timeout).await;
+            CancelReason::Timeout
+        })
+    }
+
+    /// Forward events until `trigger` completes, then end with a cancelled
+    /// event.
+    fn cancel_on<F>(self, trigger: F) -> Self
+    where
+        T: TextDelta + Default + Send + 'static,
+        F: Future<Output = CancelReason> + Send + 'static,
+    {
+        let mut counter = UsageCounter::default();
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+        let started = state.started();
+
+        tokio::spawn(async move {
+            tokio::pin!(trigger);
+            loop {
+                tokio::select! {
+                    biased;
+                    reason = &mut trigger => {
+                        let metrics = counter.partial_metrics(started.elapsed());
+                        let _ = tx.send(Ok(cancelled_event(reason, metrics))).await;
+                        return;
+                    }
+                    () = tx.closed() => return,
+                    item = rx.recv() => {
+                        let Some(item) = item else {
+                            return;
+                        };
+                        let end = item.as_ref().is_ok_and(|event| {
+                            counter.update(event);
+                            event.is_done()
+                        });
+                        if tx.send(item).await.is_err() || end {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self::new(out, state)
+    }
+
+    /// Attach the reply's text to an [`Error::StreamInterrupted`], and turn
+    /// a body that ends after some events but before the final one into
+    /// one. The stream ends after the final event.
+    pub(crate) fn track_partial(self) -> Self
+    where
+        T: TextDelta + Send + 'static,
+    {
+        let mut partial = PartialOutput::default();
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            loop {
+                // Stop waiting on a stalled body once the consumer is gone,
+                // so the request's slot is freed.
+                let item = tokio::select! {
+                    item = rx.recv() => item,
+                    () = tx.closed() => return,
+                };
+                let Some(item) = item else {
+                    break;
+                };
+                let item = match item {
+                    Ok(event) => {
+                        partial.record(&event);
+                        Ok(event)
+                    }
+                    Err(e) => Err(partial.attach(e)),
+                };
+                let end = item.as_ref().is_ok_and(TextDelta::is_done);
+                if tx.send(item).await.is_err() || end {
+                    return;
+                }
+            }
+            if let Some(e) = partial.ended_early() {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+
+        Self::new(out, state)
+    }
+
     /// Collects all events into a vector, stopping on the first error.
-    pub async fn collect(self) -> Result<Vec<ChatResponse>> {
+    pub async fn collect(self) -> Result<Vec<T>> {
         let mut out = Vec::new();
         let mut rx = self.rx.into_inner();
-        while let Some(item) = rx.recv().await {
+        loop {
+            self.state.resumed().await;
+            let Some(item) = rx.recv().await else {
+                break;
+            };
             match item {
                 Ok(v) => out.push(v),
                 Err(e) => return Err(e),
@@ -59,7 +764,162 @@ impl ChatStream {
     }
 }
 
-/// Blocking iterator over [`ChatResponse`] events from a streaming chat request.
+/// Requires the `stream` feature.
+///
+/// Lets a `ResponseStream` be driven with `StreamExt` combinators and passed
+/// to APIs expecting a `futures::Stream`. Yields the same items as
+/// [`next`](ResponseStream::next), waits while the stream is
+/// [paused](ResponseStream::pause), and ends after the final event.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::TryStreamExt;
+/// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi!")]);
+/// let reply: String = client
+///     .chat_stream(&request)
+///     .await?
+///     .map_ok(|chunk| chunk.content().unwrap_or_default().to_string())
+///     .try_collect()
+///     .await?;
+/// println!("{reply}");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stream")]
+impl<T> futures_core::Stream for ResponseStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        std::task::ready!(this.state.poll_resumed(cx));
+        this.rx.get_mut().poll_recv(cx)
+    }
+}
+
+impl ResponseStream<ChatResponse> {
+    /// Turn the chunks into a stream of [`ResponseEvent`]s.
+    ///
+    /// Each chunk is split with [`ChatResponse::into_events`], so text,
+    /// thinking, tool calls, usage, and completion each arrive as their own
+    /// event. Errors pass through unchanged. Must be called from within a
+    /// Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient, ResponseEvent};
+    ///
+    /// # async fn example() -> ollama_oxide::Result<()> {
+    /// let client = OllamaClient::default()?;
+    /// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi!")]);
+    /// let events = client.chat_stream(&request).await?.events();
+    /// while let Some(event) = events.next().await {
+    ///     match event? {
+    ///         ResponseEvent::ThinkingDelta(text) => eprint!("{text}"),
+    ///         ResponseEvent::TextDelta(text) => print!("{text}"),
+    ///         ResponseEvent::Done { metrics, .. } => println!("\n{metrics}"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn events(self) -> ResponseStream<ResponseEvent> {
+        let (mut rx, state) = self.into_parts();
+        let (tx, out) = tokio::sync::mpsc::channel(state.buffer());
+
+        tokio::spawn(async move {
+            while let Some(item) = rx.recv().await {
+                let items = match item {
+                    Ok(chunk) => chunk.into_events().into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                for item in items {
+                    if tx.send(item).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        ResponseStream::new(out, state)
+    }
+}
+
+/// Secondary consumer registered with [`ResponseStreamBlocking::tee`].
+type Tap<T> = Box<dyn FnMut(&T) + Send + Sync>;
+
+/// Applies a [`StopMatcher`] to an event; see [`StopMatcher::apply`].
+type StopFn<T> = fn(&mut StopMatcher, &mut T) -> bool;
+
+/// Applies a [`PostProcessing`] to an event; see [`PostProcessing::apply`].
+type PostFn<T> = fn(&mut PostProcessing, &mut T);
+
+/// Applies a [`Resegment`] to an event; see [`Resegment::apply`].
+type ResegmentFn<T> = fn(&mut Resegment<T>, T) -> Option<T>;
+
+/// Records an event into a [`PartialOutput`]; see [`PartialOutput::record`].
+type RecordFn<T> = fn(&mut PartialOutput, &T);
+
+/// The terminal event of a stream cancelled for `reason`.
+fn cancelled_event<T: TextDelta + Default>(reason: CancelReason, metrics: EvalMetrics) -> T {
+    let mut event = T::default();
+    event.finish_cancelled(reason, metrics);
+    event
+}
+
+/// Counts `event` into `usage`; whether it was the last one.
+fn count_event<T: TextDelta>(usage: &mut UsageCounter, event: &T) -> bool {
+    usage.update(event);
+    event.is_done()
+}
+
+/// When to cancel a [`ResponseStreamBlocking`], checked before each read.
+struct BlockingCancel<T> {
+    token: Option<CancelToken>,
+    deadline: Option<Instant>,
+    usage: UsageCounter,
+    /// [`count_event`] for `T`
+    count: fn(&mut UsageCounter, &T) -> bool,
+    /// [`cancelled_event`] for `T`
+    finish: fn(CancelReason, EvalMetrics) -> T,
+}
+
+impl<T> BlockingCancel<T> {
+    fn new() -> Self
+    where
+        T: TextDelta + Default,
+    {
+        Self {
+            token: None,
+            deadline: None,
+            usage: UsageCounter::default(),
+            count: count_event::<T>,
+            finish: cancelled_event::<T>,
+        }
+    }
+
+    fn reason(&self) -> Option<CancelReason> {
+        self.token
+            .as_ref()
+            .and_then(CancelToken::reason)
+            .or_else(|| {
+                self.deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                    .then_some(CancelReason::Timeout)
+            })
+    }
+}
+
+/// Blocking iterator over events from a streaming (NDJSON) endpoint.
 ///
 /// Implements [`Iterator`] so you can use `for`/`while let` over events.
 ///
@@ -81,38 +941,394 @@ impl ChatStream {
 ///     Ok(())
 /// }
 /// ```
-pub struct ChatStreamBlocking {
-    lines: std::io::Lines<BufReader<reqwest::blocking::Response>>,
+pub struct ResponseStreamBlocking<T> {
+    reader: BufReader<reqwest::blocking::Response>,
+    max_line: Option<usize>,
+    json_limits: JsonLimits,
+    done: bool,
+    observers: ObserverSet,
+    call: CallContext,
+    /// Held until the stream ends; `None` afterwards
+    in_flight: Option<InFlight>,
+    first_token: Option<Duration>,
+    taps: Vec<Tap<T>>,
+    line: Vec<u8>,
+    parse: LineParser<T>,
+    /// Lines are server-sent events whose `data` holds the JSON
+    sse: bool,
+    stop: Option<(StopMatcher, StopFn<T>)>,
+    post: Option<(PostProcessing, PostFn<T>)>,
+    resegment: Option<(Resegment<T>, ResegmentFn<T>)>,
+    cancel: Option<BlockingCancel<T>>,
+    partial: Option<(PartialOutput, RecordFn<T>)>,
 }
 
-impl ChatStreamBlocking {
+impl<T> ResponseStreamBlocking<T> {
     /// Builds a line iterator over the blocking response body.
-    pub(crate) fn new(response: reqwest::blocking::Response) -> Self {
+    ///
+    /// Lines longer than `max_line` bytes end the stream with
+    /// [`Error::ResponseTooLarge`], as do lines that break `json_limits`.
+    /// `call` is the request the response belongs to and `in_flight` keeps
+    /// it counted until the stream ends; `parse` turns each non-empty line
+    /// into an item.
+    pub(crate) fn new(
+        response: reqwest::blocking::Response,
+        max_line: Option<usize>,
+        json_limits: JsonLimits,
+        observers: ObserverSet,
+        call: CallContext,
+        in_flight: InFlight,
+        parse: LineParser<T>,
+    ) -> Self {
         Self {
-            lines: BufReader::new(response).lines(),
+            reader: BufReader::new(response),
+            max_line,
+            json_limits,
+            done: false,
+            observers,
+            call,
+            in_flight: Some(in_flight),
+            first_token: None,
+            taps: Vec::new(),
+            line: Vec::new(),
+            parse,
+            sse: false,
+            stop: None,
+            post: None,
+            resegment: None,
+            cancel: None,
+            partial: None,
+        }
+    }
+
+    /// Time from sending the request to reading the first event.
+    ///
+    /// `None` until the first event has been returned by the iterator;
+    /// iterate with [`by_ref`](Iterator::by_ref) to query it mid-stream.
+    pub fn first_token_latency(&self) -> Option<Duration> {
+        self.first_token
+    }
+
+    /// Forward each event to `observer` as it passes through (blocking).
+    ///
+    /// See [`ResponseStream::tee`]; `observer` runs on the iterating thread
+    /// just before each successful event is returned.
+    pub fn tee<F>(mut self, observer: F) -> Self
+    where
+        F: FnMut(&T) + Send + Sync + 'static,
+    {
+        self.taps.push(Box::new(observer));
+        self
+    }
+
+    /// End the stream at the first of `sequences` in the generated text
+    /// (blocking).
+    ///
+    /// See [`ResponseStream::stop_at`]. The iterator ends after the cut
+    /// event; drop it to close the connection.
+    pub fn stop_at<I, S>(mut self, sequences: I) -> Self
+    where
+        T: TextDelta,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let matcher = StopMatcher::new(sequences);
+        if !matcher.is_empty() {
+            self.stop = Some((matcher, StopMatcher::apply::<T>));
+        }
+        self
+    }
+
+    /// Rewrite the generated text with `processor` as it streams
+    /// (blocking).
+    ///
+    /// See [`ResponseStream::post_process`].
+    pub fn post_process(mut self, processor: &PostProcessor) -> Self
+    where
+        T: TextDelta,
+    {
+        if !processor.is_empty() {
+            self.post = Some((processor.start(), PostProcessing::apply::<T>));
+        }
+        self
+    }
+
+    /// Yield text in whole words (blocking).
+    ///
+    /// See [`ResponseStream::words`].
+    pub fn words(self) -> Self
+    where
+        T: TextDelta,
+    {
+        self.resegment(Boundary::Word)
+    }
+
+    /// Yield text in whole sentences (blocking).
+    ///
+    /// See [`ResponseStream::sentences`].
+    pub fn sentences(self) -> Self
+    where
+        T: TextDelta,
+    {
+        self.resegment(Boundary::Sentence)
+    }
+
+    fn resegment(mut self, boundary: Boundary) -> Self
+    where
+        T: TextDelta,
+    {
+        self.resegment = Some((Resegment::new(boundary), Resegment::apply));
+        self
+    }
+
+    /// Pair each event with the [`StreamUsage`] so far (blocking).
+    ///
+    /// See [`ResponseStream::with_usage`].
+    pub fn with_usage(self) -> impl Iterator<Item = Result<(T, StreamUsage)>>
+    where
+        T: TextDelta,
+    {
+        let mut counter = UsageCounter::default();
+        self.map(move |item| {
+            item.map(|event| {
+                let usage = counter.update(&event);
+                (event, usage)
+            })
+        })
+    }
+
+    /// End the stream when `token` is cancelled (blocking).
+    ///
+    /// See [`ResponseStream::with_cancel`]. The token is checked before
+    /// each read, so a cancel takes effect once the event being read
+    /// arrives; drop the iterator after the cancelled event to close the
+    /// connection.
+    pub fn with_cancel(mut self, token: &CancelToken) -> Self
+    where
+        T: TextDelta + Default,
+    {
+        self.cancel.get_or_insert_with(BlockingCancel::new).token = Some(token.clone());
+        self
+    }
+
+    /// End the stream once `timeout` has passed, counted from this call
+    /// (blocking).
+    ///
+    /// See [`ResponseStream::with_timeout`]; like
+    /// [`with_cancel`](Self::with_cancel), the deadline is checked before
+    /// each read.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self
+    where
+        T: TextDelta + Default,
+    {
+        self.cancel.get_or_insert_with(BlockingCancel::new).deadline =
+            Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Attach the reply's text to an [`Error::StreamInterrupted`], and turn
+    /// a body that ends after some events but before the final one into
+    /// one (blocking).
+    pub(crate) fn track_partial(mut self) -> Self
+    where
+        T: TextDelta,
+    {
+        self.partial = Some((PartialOutput::default(), PartialOutput::record::<T>));
+        self
+    }
+
+    /// Read lines as server-sent events when `sse` is set.
+    pub(crate) fn with_sse(mut self, sse: bool) -> Self {
+        self.sse = sse;
+        self
+    }
+
+    /// Reads the next raw line into the reused line buffer, without
+    /// buffering past the per-line limit.
+    fn read_line(&mut self) -> Option<Result<()>> {
+        self.line.clear();
+        let read = match self.max_line {
+            // Allow for a trailing "\r\n" beyond the limit.
+            Some(limit) => (&mut self.reader)
+                .take(limit as u64 + 2)
+                .read_until(b'\n', &mut self.line),
+            None => self.reader.read_until(b'\n', &mut self.line),
+        };
+        match read {
+            Ok(0) => None,
+            Err(e) => Some(Err(Error::StreamInterrupted {
+                partial: String::new(),
+                message: e.to_string(),
+            })),
+            Ok(_) => match self.max_line {
+                Some(limit) if self.line.trim_ascii_end().len() > limit => {
+                    Some(Err(Error::ResponseTooLarge { limit }))
+                }
+                _ => Some(Ok(())),
+            },
         }
     }
 }
 
-impl Iterator for ChatStreamBlocking {
-    type Item = Result<ChatResponse>;
+impl ResponseStreamBlocking<ChatResponse> {
+    /// Turn the chunks into [`ResponseEvent`]s (blocking).
+    ///
+    /// See [`ResponseStream::events`].
+    pub fn events(self) -> impl Iterator<Item = Result<ResponseEvent>> {
+        self.flat_map(|item| match item {
+            Ok(chunk) => chunk.into_events().into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        })
+    }
+}
+
+impl<T> Iterator for ResponseStreamBlocking<T> {
+    type Item = Result<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let was_done = self.done;
+        let mut item = self.next_item();
+        if let Some((partial, _)) = &mut self.partial {
+            match item {
+                Some(Err(e)) => item = Some(Err(partial.attach(e))),
+                None if !was_done => {
+                    self.done = true;
+                    item = partial.ended_early().map(Err);
+                }
+                _ => {}
+            }
+        }
+        if self.done || item.is_none() {
+            self.in_flight = None;
+        }
+        item
+    }
+}
+
+impl<T> ResponseStreamBlocking<T> {
+    fn next_item(&mut self) -> Option<Result<T>> {
+        if self.done {
+            return None;
+        }
+        if self.in_flight.as_ref().is_some_and(InFlight::is_cancelled) {
+            self.done = true;
+            self.observers.error(&self.call, &Error::ClientShutdown);
+            return Some(Err(Error::ClientShutdown));
+        }
+        if let Some(cancel) = &self.cancel
+            && let Some(reason) = cancel.reason()
+        {
+            self.done = true;
+            let metrics = cancel.usage.partial_metrics(self.call.started().elapsed());
+            return Some(Ok((cancel.finish)(reason, metrics)));
+        }
         loop {
-            match self.lines.next() {
+            match self.read_line() {
                 None => return None,
-                Some(Err(e)) => return Some(Err(Error::StreamError(e.to_string()))),
-                Some(Ok(line)) => {
-                    let trimmed = line.trim();
+                Some(Err(e)) => {
+                    self.done = true;
+                    self.observers.error(&self.call, &e);
+                    return Some(Err(e));
+                }
+                Some(Ok(())) => {
+                    let mut trimmed = self.line.trim_ascii();
+                    if self.sse {
+                        match sse_data(trimmed) {
+                            Some(data) => trimmed = data,
+                            None => continue,
+                        }
+                    }
                     if trimmed.is_empty() {
                         continue;
                     }
-                    return Some(
-                        serde_json::from_str::<ChatResponse>(trimmed)
-                            .map_err(|e| Error::StreamError(e.to_string())),
-                    );
+                    self.observers.chunk(&self.call, trimmed);
+                    let mut item = self
+                        .json_limits
+                        .check(trimmed)
+                        .and_then(|()| (self.parse)(trimmed));
+                    if let Ok(event) = &item
+                        && let Some((partial, record)) = &mut self.partial
+                    {
+                        record(partial, event);
+                    }
+                    if let Ok(event) = &mut item
+                        && let Some((matcher, apply)) = &mut self.stop
+                        && apply(matcher, event)
+                    {
+                        self.done = true;
+                    }
+                    if let Ok(event) = &mut item
+                        && let Some((processing, apply)) = &mut self.post
+                    {
+                        apply(processing, event);
+                    }
+                    if let Some((resegment, apply)) = &mut self.resegment
+                        && let Ok(event) = item
+                    {
+                        match apply(resegment, event) {
+                            Some(event) => item = Ok(event),
+                            None => continue,
+                        }
+                    }
+                    match &item {
+                        Ok(event) => {
+                            if self.first_token.is_none() {
+                                let latency = self.call.started().elapsed();
+                                self.first_token = Some(latency);
+                                self.observers.first_token(&self.call, latency);
+                            }
+                            self.taps.iter_mut().for_each(|tap| tap(event));
+                            if let Some(cancel) = &mut self.cancel
+                                && (cancel.count)(&mut cancel.usage, event)
+                            {
+                                self.done = true;
+                            }
+                        }
+                        Err(e) => self.observers.error(&self.call, e),
+                    }
+                    return Some(item);
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(lines: &mut LineBuffer) -> Vec<Bytes> {
+        std::iter::from_fn(|| lines.next_line()).collect()
+    }
+
+    #[test]
+    fn test_line_within_chunk_is_not_copied() {
+        let chunk = Bytes::from_static(b"{\"a\":1}\n{\"b\":2}\r\n");
+        let mut lines = LineBuffer::default();
+        lines.push(chunk.clone());
+        let out = drain(&mut lines);
+        assert_eq!(out, [&b"{\"a\":1}"[..], &b"{\"b\":2}"[..]]);
+        assert_eq!(out[0].as_ptr(), chunk.as_ptr());
+    }
+
+    #[test]
+    fn test_line_split_across_chunks() {
+        let mut lines = LineBuffer::default();
+        lines.push(Bytes::from_static(b"{\"a\":"));
+        assert!(drain(&mut lines).is_empty());
+        assert_eq!(lines.pending_len(), 5);
+        lines.push(Bytes::from_static(b"1}\n{\"b\""));
+        assert_eq!(drain(&mut lines), [&b"{\"a\":1}"[..]]);
+        lines.push(Bytes::from_static(b":2} "));
+        assert!(drain(&mut lines).is_empty());
+        assert_eq!(lines.finish(), &b"{\"b\":2}"[..]);
+    }
+
+    #[test]
+    fn test_finish_blank_tail() {
+        let mut lines = LineBuffer::default();
+        lines.push(Bytes::from_static(b"\n  "));
+        assert_eq!(drain(&mut lines), [Bytes::new()]);
+        assert!(lines.finish().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Access to the text delta of streamed events
+
+use crate::{CancelReason, ChatResponse, DoneReason, EvalMetrics, GenerateResponse};
+
+/// A streamed event carrying a piece of generated text.
+///
+/// Implemented for [`ChatResponse`] (the message content) and
+/// [`GenerateResponse`] (the response text), so stream adapters such as
+/// [`ResponseStream::stop_at`](super::ResponseStream::stop_at) and
+/// [`ResponseStream::coalesce`](super::ResponseStream::coalesce) can rewrite
+/// the text as it passes through.
+pub trait TextDelta {
+    /// The text this event adds, if any
+    fn text_delta(&self) -> Option<&str>;
+
+    /// Replace the text this event adds.
+    fn set_text_delta(&mut self, text: String);
+
+    /// The reasoning this event adds, if any
+    fn thinking_delta(&self) -> Option<&str> {
+        None
+    }
+
+    /// Timing and token counts this event reports, which the server sends
+    /// on the final event
+    fn eval_metrics(&self) -> EvalMetrics {
+        EvalMetrics::default()
+    }
+
+    /// Whether this is the final event of the stream
+    fn is_done(&self) -> bool;
+
+    /// Mark this event as the last one, ended by a client-side stop
+    /// sequence.
+    fn finish_stopped(&mut self);
+
+    /// Mark this event as the last one of a stream the client cancelled,
+    /// carrying whatever `metrics` were gathered before it, as estimates.
+    fn finish_cancelled(&mut self, reason: CancelReason, metrics: EvalMetrics);
+
+    /// Fold an `earlier` event into this one.
+    ///
+    /// Streamed deltas (text, thinking, tool calls, logprobs) become the
+    /// earlier event's followed by this one's; every other field keeps this
+    /// event's value.
+    fn merge_earlier(&mut self, earlier: Self)
+    where
+        Self: Sized;
+}
+
+/// `earlier` followed by `later`, for optional streamed deltas.
+fn concat_text(earlier: Option<String>, later: Option<String>) -> Option<String> {
+    match (earlier, later) {
+        (Some(mut a), Some(b)) => {
+            a.push_str(&b);
+            Some(a)
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+/// `earlier` followed by `later`, for optional streamed lists.
+fn concat_vec<T>(earlier: Option<Vec<T>>, later: Option<Vec<T>>) -> Option<Vec<T>> {
+    match (earlier, later) {
+        (Some(mut a), Some(b)) => {
+            a.extend(b);
+            Some(a)
+        }
+        (a, b) => a.or(b),
+    }
+}
+
+impl TextDelta for ChatResponse {
+    fn text_delta(&self) -> Option<&str> {
+        self.content()
+    }
+
+    fn set_text_delta(&mut self, text: String) {
+        self.message.get_or_insert_with(Default::default).content = Some(text);
+    }
+
+    fn thinking_delta(&self) -> Option<&str> {
+        self.thinking()
+    }
+
+    fn eval_metrics(&self) -> EvalMetrics {
+        self.metrics()
+    }
+
+    fn is_done(&self) -> bool {
+        self.done == Some(true)
+    }
+
+    fn finish_stopped(&mut self) {
+        self.done = Some(true);
+        self.done_reason = Some(DoneReason::ClientStop);
+        self.cancel_reason = Some(CancelReason::StopSequence);
+    }
+
+    fn finish_cancelled(&mut self, reason: CancelReason, metrics: EvalMetrics) {
+        self.done = Some(true);
+        self.cancel_reason = Some(reason);
+        self.total_duration = metrics.total_duration;
+        self.prompt_eval_count = metrics.prompt_eval_count;
+        self.eval_count = metrics.eval_count;
+        self.counts_estimated = true;
+    }
+
+    fn merge_earlier(&mut self, earlier: Self) {
+        self.logprobs = concat_vec(earlier.logprobs, self.logprobs.take());
+        let Some(before) = earlier.message else {
+            return;
+        };
+        let message = self.message.get_or_insert_with(Default::default);
+        message.content = concat_text(before.content, message.content.take());
+        message.thinking = concat_text(before.thinking, message.thinking.take());
+        #[cfg(feature = "tools")]
+        {
+            message.tool_calls = concat_vec(before.tool_calls, message.tool_calls.take());
+        }
+        message.images = concat_vec(before.images, message.images.take());
+        if message.role.is_none() {
+            message.role = before.role;
+        }
+    }
+}
+
+impl TextDelta for GenerateResponse {
+    fn text_delta(&self) -> Option<&str> {
+        self.response.as_deref()
+    }
+
+    fn set_text_delta(&mut self, text: String) {
+        self.response = Some(text);
+    }
+
+    fn thinking_delta(&self) -> Option<&str> {
+        self.thinking.as_deref()
+    }
+
+    fn eval_metrics(&self) -> EvalMetrics {
+        self.metrics()
+    }
+
+    fn is_done(&self) -> bool {
+        self.done == Some(true)
+    }
+
+    fn finish_stopped(&mut self) {
+        self.done = Some(true);
+        self.done_reason = Some(DoneReason::ClientStop);
+        self.cancel_reason = Some(CancelReason::StopSequence);
+    }
+
+    fn finish_cancelled(&mut self, reason: CancelReason, metrics: EvalMetrics) {
+        self.done = Some(true);
+        self.cancel_reason = Some(reason);
+        self.total_duration = metrics.total_duration;
+        self.prompt_eval_count = metrics.prompt_eval_count;
+        self.eval_count = metrics.eval_count;
+        self.counts_estimated = true;
+    }
+
+    fn merge_earlier(&mut self, earlier: Self) {
+        self.response = concat_text(earlier.response, self.response.take());
+        self.thinking = concat_text(earlier.thinking, self.thinking.take());
+        self.logprobs = concat_vec(earlier.logprobs, self.logprobs.take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResponseMessage;
+
+    fn chat(content: &str, thinking: Option<&str>) -> ChatResponse {
+        ChatResponse {
+            message: Some(ResponseMessage {
+                role: Some("assistant".to_string()),
+                content: Some(content.to_string()),
+                thinking: thinking.map(str::to_string),
+                ..Default::default()
+            }),
+            done: Some(false),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_chat_merge_earlier_concatenates_deltas() {
+        let mut later = chat("lo", None);
+        later.eval_count = Some(3);
+        later.merge_earlier(chat("Hel", Some("hmm")));
+        assert_eq!(later.content(), Some("Hello"));
+        assert_eq!(later.thinking(), Some("hmm"));
+        assert_eq!(later.eval_count, Some(3));
+    }
+
+    #[test]
+    fn test_generate_merge_earlier_concatenates_deltas() {
+        let mut later = GenerateResponse {
+            response: Some(" world".to_string()),
+            done: Some(true),
+            ..Default::default()
+        };
+        later.merge_earlier(GenerateResponse {
+            response: Some("Hello".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(later.response.as_deref(), Some("Hello world"));
+        assert!(later.is_done());
+    }
+}
@@ -0,0 +1,218 @@
+//! Incremental text rewrites behind `PostProcessor`
+
+use super::stop_matcher::StopMatcher;
+
+const THINK_OPEN: &str = "<think>";
+const THINK_CLOSE: &str = "</think>";
+const FENCE: &str = "```";
+
+/// Length of the longest proper prefix of `pattern` that `text` ends with.
+fn partial_suffix(text: &str, pattern: &str) -> usize {
+    (1..pattern.len())
+        .rev()
+        .filter(|&n| pattern.is_char_boundary(n))
+        .find(|&n| text.ends_with(&pattern[..n]))
+        .unwrap_or(0)
+}
+
+/// Where a [`TextFilter::Code`] is relative to the fenced blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Fence {
+    /// Outside any block
+    Outside,
+    /// On an opening fence line, e.g. the `rust` of `` ```rust ``
+    Info,
+    /// Inside a block
+    Inside,
+}
+
+/// One step of a post-processing chain, with the state it needs to work on
+/// text arriving in pieces.
+///
+/// Each step holds back text that might still turn out to be part of a
+/// marker (a `<think>` tag, a fence, a stop sequence, or trailing
+/// whitespace) until a later piece or the last one decides it.
+#[derive(Debug, Clone)]
+pub(crate) enum TextFilter {
+    /// Drop leading and trailing whitespace
+    Trim { started: bool, held: String },
+    /// Drop `<think>...</think>` blocks, including an unclosed last one
+    Think { inside: bool, held: String },
+    /// Keep only the contents of fenced code blocks
+    Code { fence: Fence, held: String },
+    /// Cut the text at the first stop sequence
+    Stop(StopMatcher),
+}
+
+impl TextFilter {
+    pub(crate) fn trim() -> Self {
+        Self::Trim {
+            started: false,
+            held: String::new(),
+        }
+    }
+
+    pub(crate) fn think() -> Self {
+        Self::Think {
+            inside: false,
+            held: String::new(),
+        }
+    }
+
+    pub(crate) fn code() -> Self {
+        Self::Code {
+            fence: Fence::Outside,
+            held: String::new(),
+        }
+    }
+
+    /// Feed the next piece of text; returns the text to emit now.
+    ///
+    /// `last` flushes or drops whatever is held back.
+    pub(crate) fn push(&mut self, delta: &str, last: bool) -> String {
+        match self {
+            Self::Trim { started, held } => {
+                let mut delta = delta;
+                if !*started {
+                    delta = delta.trim_start();
+                    if delta.is_empty() {
+                        return String::new();
+                    }
+                    *started = true;
+                }
+                let mut text = std::mem::take(held);
+                text.push_str(delta);
+                let end = text.trim_end().len();
+                if !last {
+                    *held = text[end..].to_string();
+                }
+                text.truncate(end);
+                text
+            }
+            Self::Think { inside, held } => {
+                let mut text = std::mem::take(held);
+                text.push_str(delta);
+                let mut out = String::new();
+                loop {
+                    if *inside {
+                        if let Some(at) = text.find(THINK_CLOSE) {
+                            text.drain(..at + THINK_CLOSE.len());
+                            *inside = false;
+                            continue;
+                        }
+                        if !last {
+                            let keep = partial_suffix(&text, THINK_CLOSE);
+                            *held = text.split_off(text.len() - keep);
+                        }
+                    } else {
+                        if let Some(at) = text.find(THINK_OPEN) {
+                            out.push_str(&text[..at]);
+                            text.drain(..at + THINK_OPEN.len());
+                            *inside = true;
+                            continue;
+                        }
+                        if !last {
+                            let keep = partial_suffix(&text, THINK_OPEN);
+                            *held = text.split_off(text.len() - keep);
+                        }
+                        out.push_str(&text);
+                    }
+                    return out;
+                }
+            }
+            Self::Code { fence, held } => {
+                let mut text = std::mem::take(held);
+                text.push_str(delta);
+                let mut out = String::new();
+                loop {
+                    match fence {
+                        Fence::Outside => {
+                            if let Some(at) = text.find(FENCE) {
+                                text.drain(..at + FENCE.len());
+                                *fence = Fence::Info;
+                                continue;
+                            }
+                            if !last {
+                                let keep = partial_suffix(&text, FENCE);
+                                *held = text.split_off(text.len() - keep);
+                            }
+                        }
+                        Fence::Info => {
+                            if let Some(at) = text.find('\n') {
+                                text.drain(..=at);
+                                *fence = Fence::Inside;
+                                continue;
+                            }
+                        }
+                        Fence::Inside => {
+                            if let Some(at) = text.find(FENCE) {
+                                out.push_str(&text[..at]);
+                                text.drain(..at + FENCE.len());
+                                *fence = Fence::Outside;
+                                continue;
+                            }
+                            if !last {
+                                let keep = partial_suffix(&text, FENCE);
+                                *held = text.split_off(text.len() - keep);
+                            }
+                            out.push_str(&text);
+                        }
+                    }
+                    return out;
+                }
+            }
+            Self::Stop(matcher) => matcher.push(delta, last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mut filter: TextFilter, deltas: &[&str]) -> String {
+        deltas
+            .iter()
+            .enumerate()
+            .map(|(i, delta)| filter.push(delta, i + 1 == deltas.len()))
+            .collect()
+    }
+
+    #[test]
+    fn test_trim_across_deltas() {
+        assert_eq!(
+            run(TextFilter::trim(), &["\n ", " Hi", " there ", "\n", ""]),
+            "Hi there"
+        );
+    }
+
+    #[test]
+    fn test_think_blocks_split_across_deltas() {
+        let deltas = ["<thi", "nk>plan</th", "ink>Answer <think>", "more</think>!"];
+        assert_eq!(run(TextFilter::think(), &deltas), "Answer !");
+    }
+
+    #[test]
+    fn test_unclosed_think_block_is_dropped() {
+        assert_eq!(
+            run(TextFilter::think(), &["Hi<think>still", " thinking"]),
+            "Hi"
+        );
+    }
+
+    #[test]
+    fn test_code_keeps_fenced_contents() {
+        let deltas = ["Here:\n`", "``rust\nfn main() {}\n`", "`` and\n```\nx\n```"];
+        assert_eq!(run(TextFilter::code(), &deltas), "fn main() {}\nx\n");
+    }
+
+    #[test]
+    fn test_code_without_fences_is_empty() {
+        assert_eq!(run(TextFilter::code(), &["just ", "prose"]), "");
+    }
+
+    #[test]
+    fn test_text_held_for_a_tag_is_released() {
+        assert_eq!(run(TextFilter::think(), &["a <th", "ing"]), "a <thing");
+    }
+}
@@ -0,0 +1,285 @@
+//! Client-side estimate of prompt size against the context window
+
+use std::borrow::Cow;
+
+use super::{ClientConfig, OverflowPolicy};
+use crate::{ChatMessage, ChatRequest, Error, GenerateRequest, ModelOptions, Result};
+
+/// Rough characters per token.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Per-message token overhead (role and template markers).
+const MESSAGE_OVERHEAD: usize = 4;
+
+/// Estimated tokens of `text`: about four characters per token.
+pub(crate) fn estimate_text_tokens(text: &str) -> usize {
+    estimate_len_tokens(text.len())
+}
+
+/// Estimated tokens of `len` bytes of text.
+pub(crate) fn estimate_len_tokens(len: usize) -> usize {
+    len.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Estimated tokens of `messages`, including per-message overhead.
+pub(crate) fn estimate_message_tokens(messages: &[ChatMessage]) -> usize {
+    messages
+        .iter()
+        .map(|m| estimate_text_tokens(&m.content) + MESSAGE_OVERHEAD)
+        .sum()
+}
+
+/// Checks generate and chat requests against the model's context window
+/// before they are sent.
+///
+/// The prompt is estimated at about four characters per token, and the
+/// requested output (`num_predict`) is added on top. The context window is
+/// the request's `num_ctx` option when set, otherwise
+/// [`context_window`](Self::context_window). Requests that do not fit are
+/// rejected or truncated according to the [`OverflowPolicy`], instead of
+/// being silently truncated by the server.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ClientConfig, OverflowPolicy, TokenBudget};
+///
+/// let config = ClientConfig::default().with_token_budget(
+///     TokenBudget::new(8_192)
+///         .with_reserved_output(512)
+///         .with_policy(OverflowPolicy::Truncate),
+/// );
+/// assert_eq!(config.token_budget().unwrap().context_window(), 8_192);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBudget {
+    context_window: usize,
+    reserved_output: usize,
+    policy: OverflowPolicy,
+}
+
+impl TokenBudget {
+    /// Budget requests against a context window of `context_window` tokens.
+    pub fn new(context_window: usize) -> Self {
+        Self {
+            context_window,
+            reserved_output: 0,
+            policy: OverflowPolicy::Reject,
+        }
+    }
+
+    /// Tokens to reserve for the reply when a request sets no positive
+    /// `num_predict`.
+    pub fn with_reserved_output(mut self, tokens: usize) -> Self {
+        self.reserved_output = tokens;
+        self
+    }
+
+    /// Handle oversized requests according to `policy`.
+    pub fn with_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Context window used when a request sets no `num_ctx`
+    pub fn context_window(&self) -> usize {
+        self.context_window
+    }
+
+    /// Output tokens reserved when a request sets no `num_predict`
+    pub fn reserved_output(&self) -> usize {
+        self.reserved_output
+    }
+
+    /// What happens to oversized requests
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Context window and output reservation for a request with `options`.
+    fn limits(&self, options: Option<&ModelOptions>) -> (usize, usize) {
+        let positive = |v: Option<i32>| v.filter(|v| *v > 0).map(|v| v as usize);
+        let window = positive(options.and_then(|o| o.num_ctx)).unwrap_or(self.context_window);
+        let output = positive(options.and_then(|o| o.num_predict)).unwrap_or(self.reserved_output);
+        (window, output)
+    }
+}
+
+/// Requests a [`TokenBudget`] can measure and shrink.
+pub(super) trait BudgetedRequest: Clone {
+    /// Options holding `num_ctx` and `num_predict`
+    fn budget_options(&self) -> Option<&ModelOptions>;
+
+    /// Estimated prompt tokens
+    fn estimate_tokens(&self) -> usize;
+
+    /// Drop input so the prompt fits in `limit` tokens. Returns `false` if
+    /// it cannot.
+    fn truncate_to(&mut self, limit: usize) -> bool;
+}
+
+/// Check `request` against the configured budget, truncating a copy if the
+/// policy allows.
+///
+/// # Errors
+///
+/// [`Error::TokenBudgetExceeded`] if the request does not fit.
+pub(super) fn within_budget<'a, R: BudgetedRequest>(
+    config: &ClientConfig,
+    request: Cow<'a, R>,
+) -> Result<Cow<'a, R>> {
+    let Some(budget) = config.token_budget() else {
+        return Ok(request);
+    };
+    let (window, output) = budget.limits(request.budget_options());
+    let estimated = request.estimate_tokens() + output;
+    if estimated <= window {
+        return Ok(request);
+    }
+
+    let exceeded = Error::TokenBudgetExceeded {
+        estimated,
+        limit: window,
+    };
+    match budget.policy() {
+        OverflowPolicy::Truncate if output < window => {
+            let mut request = request;
+            if request.to_mut().truncate_to(window - output) {
+                Ok(request)
+            } else {
+                Err(exceeded)
+            }
+        }
+        _ => Err(exceeded),
+    }
+}
+
+impl BudgetedRequest for GenerateRequest {
+    fn budget_options(&self) -> Option<&ModelOptions> {
+        self.options.as_ref()
+    }
+
+    fn estimate_tokens(&self) -> usize {
+        [&self.system, &self.prompt, &self.suffix]
+            .into_iter()
+            .flatten()
+            .map(|text| estimate_text_tokens(text))
+            .sum()
+    }
+
+    fn truncate_to(&mut self, limit: usize) -> bool {
+        let fixed = self.estimate_tokens() - self.prompt.as_deref().map_or(0, estimate_text_tokens);
+        let Some(prompt) = &mut self.prompt else {
+            return false;
+        };
+        let Some(room) = limit.checked_sub(fixed) else {
+            return false;
+        };
+        // Keep the end of the prompt, where the question usually is.
+        let keep = room * CHARS_PER_TOKEN;
+        if prompt.len() > keep {
+            let mut start = prompt.len() - keep;
+            while !prompt.is_char_boundary(start) {
+                start += 1;
+            }
+            prompt.drain(..start);
+        }
+        true
+    }
+}
+
+impl BudgetedRequest for ChatRequest {
+    fn budget_options(&self) -> Option<&ModelOptions> {
+        self.options.as_ref()
+    }
+
+    fn estimate_tokens(&self) -> usize {
+        estimate_message_tokens(&self.messages)
+    }
+
+    fn truncate_to(&mut self, limit: usize) -> bool {
+        let start = self.messages.iter().take_while(|m| m.is_system()).count();
+        while self.estimate_tokens() > limit {
+            if start + 1 >= self.messages.len() {
+                return false;
+            }
+            self.messages.remove(start);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(budget: TokenBudget) -> ClientConfig {
+        ClientConfig::default().with_token_budget(budget)
+    }
+
+    #[test]
+    fn test_estimate_message_tokens() {
+        let messages = [ChatMessage::user("abcdefgh"), ChatMessage::assistant("abc")];
+        assert_eq!(estimate_message_tokens(&messages), 2 + 4 + 1 + 4);
+    }
+
+    #[test]
+    fn test_fitting_request_is_borrowed() {
+        let config = config(TokenBudget::new(100));
+        let request = GenerateRequest::new("m", "short");
+        let checked = within_budget(&config, Cow::Borrowed(&request)).unwrap();
+        assert!(matches!(checked, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_num_ctx_and_num_predict_override_budget() {
+        let config = config(TokenBudget::new(1_000));
+        let options = ModelOptions::default().with_num_ctx(10).with_num_predict(8);
+        let request = GenerateRequest::new("m", "a".repeat(12)).with_options(options);
+        let err = within_budget(&config, Cow::Borrowed(&request)).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TokenBudgetExceeded {
+                estimated: 11,
+                limit: 10
+            }
+        ));
+    }
+
+    #[test]
+    fn test_truncate_generate_keeps_prompt_tail() {
+        let config = config(TokenBudget::new(2).with_policy(OverflowPolicy::Truncate));
+        let request = GenerateRequest::new("m", "0123456789");
+        let checked = within_budget(&config, Cow::Borrowed(&request)).unwrap();
+        assert_eq!(checked.prompt.as_deref(), Some("23456789"));
+    }
+
+    #[test]
+    fn test_truncate_chat_drops_oldest_after_system() {
+        let config = config(TokenBudget::new(14).with_policy(OverflowPolicy::Truncate));
+        let request = ChatRequest::new(
+            "m",
+            [
+                ChatMessage::system("sys"),
+                ChatMessage::user("old question"),
+                ChatMessage::assistant("old answer"),
+                ChatMessage::user("new"),
+            ],
+        );
+        let checked = within_budget(&config, Cow::Borrowed(&request)).unwrap();
+        let contents: Vec<&str> = checked
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents, ["sys", "new"]);
+    }
+
+    #[test]
+    fn test_truncate_fails_when_last_message_is_too_large() {
+        let config = config(TokenBudget::new(4).with_policy(OverflowPolicy::Truncate));
+        let request = ChatRequest::new("m", [ChatMessage::user("far too long for the window")]);
+        let err = within_budget(&config, Cow::Borrowed(&request)).unwrap_err();
+        assert!(matches!(err, Error::TokenBudgetExceeded { .. }));
+    }
+}
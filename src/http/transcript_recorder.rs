@@ -0,0 +1,235 @@
+//! NDJSON transcript of every request and response
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+
+use super::{CallId, Observer};
+use crate::Error;
+
+/// Fields that streamed chunks deliver as deltas.
+const DELTA_FIELDS: [&[&str]; 5] = [
+    &["response"],
+    &["thinking"],
+    &["message", "content"],
+    &["message", "thinking"],
+    &["message", "tool_calls"],
+];
+
+pub(super) fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+pub(super) fn decode(body: &[u8]) -> Value {
+    serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+fn field_mut<'a>(value: &'a mut Value, path: &[&str]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |v, key| v.get_mut(*key))
+}
+
+/// Prepend `prior` to the field at `path` in `chunk`, creating the field
+/// if the chunk omits it.
+fn prepend(chunk: &mut Value, path: &[&str], prior: Value) {
+    let (last, parent) = path.split_last().expect("non-empty path");
+    let Some(parent) = field_mut(chunk, parent).and_then(Value::as_object_mut) else {
+        return;
+    };
+    match (parent.get_mut(*last), prior) {
+        (Some(Value::String(delta)), Value::String(prior)) => delta.insert_str(0, &prior),
+        (Some(Value::Array(items)), Value::Array(prior)) => {
+            items.splice(0..0, prior);
+        }
+        (None, prior) => {
+            parent.insert(last.to_string(), prior);
+        }
+        _ => {}
+    }
+}
+
+/// Fold one streamed chunk into the partial response for its call.
+///
+/// Delta fields accumulate; every other field is taken from the latest
+/// chunk, so the final metrics win.
+pub(super) fn merge_chunk(total: &mut Value, mut chunk: Value) {
+    for path in DELTA_FIELDS {
+        let prior = field_mut(total, path).map(Value::take);
+        if let Some(prior) = prior.filter(|p| !p.is_null()) {
+            prepend(&mut chunk, path, prior);
+        }
+    }
+    *total = chunk;
+}
+
+/// [`Observer`] that appends every request and response to an NDJSON file.
+///
+/// Each line is one JSON object:
+///
+/// ```json
+/// {"ts_ms":1760000000000,"kind":"request","url":"http://localhost:11434/api/chat","body":{...}}
+/// {"ts_ms":1760000000950,"kind":"response","url":"http://localhost:11434/api/chat","body":{...}}
+/// ```
+///
+/// `ts_ms` is milliseconds since the Unix epoch. Streamed generate and chat
+/// responses are reassembled: chunks are buffered until the `done` chunk
+/// arrives, then written as a single response whose text is the
+/// concatenation of all deltas. Other bodies are written as they arrive;
+/// bodies that are not JSON are stored as strings.
+///
+/// Chunks are grouped by [`CallId`], so concurrent streams to one endpoint
+/// are reassembled separately; the chunks of a stream that fails are
+/// dropped. Write failures are ignored so recording never fails an API
+/// call.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use ollama_oxide::http::TranscriptRecorder;
+/// use ollama_oxide::OllamaClient;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let recorder = Arc::new(TranscriptRecorder::create("session.ndjson")?);
+/// let client = OllamaClient::default()?.with_observer(recorder);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TranscriptRecorder {
+    writer: Mutex<BufWriter<File>>,
+    pending: Mutex<HashMap<CallId, Value>>,
+}
+
+impl TranscriptRecorder {
+    /// Open `path` for appending, creating it if needed.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+            pending: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn write(&self, kind: &str, url: &str, body: Value) {
+        let entry = json!({ "ts_ms": now_ms(), "kind": kind, "url": url, "body": body });
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = serde_json::to_writer(&mut *writer, &entry);
+            let _ = writer.write_all(b"\n");
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl Observer for TranscriptRecorder {
+    fn on_request_body(&self, _call: CallId, url: &str, body: &[u8]) {
+        self.write("request", url, decode(body));
+    }
+
+    fn on_response_body(&self, call: CallId, url: &str, body: &[u8]) {
+        let body = decode(body);
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+
+        let finished = match body.get("done").and_then(Value::as_bool) {
+            // A streamed chunk: buffer until the final one.
+            Some(false) => {
+                let total = pending.entry(call).or_insert(Value::Null);
+                merge_chunk(total, body);
+                return;
+            }
+            Some(true) => match pending.remove(&call) {
+                Some(mut total) => {
+                    merge_chunk(&mut total, body);
+                    total
+                }
+                None => body,
+            },
+            None => body,
+        };
+        drop(pending);
+        self.write("response", url, finished);
+    }
+
+    fn on_error(&self, call: CallId, _url: &str, _error: &Error) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&call);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_chunk_generate() {
+        let mut total = Value::Null;
+        merge_chunk(&mut total, json!({"response": "Hel", "done": false}));
+        merge_chunk(
+            &mut total,
+            json!({"response": "lo", "done": true, "eval_count": 2}),
+        );
+        assert_eq!(
+            total,
+            json!({"response": "Hello", "done": true, "eval_count": 2})
+        );
+    }
+
+    #[test]
+    fn test_merge_chunk_chat_with_tool_calls() {
+        let mut total = Value::Null;
+        merge_chunk(
+            &mut total,
+            json!({"message": {"role": "assistant", "content": "", "thinking": "Hm",
+                               "tool_calls": [{"function": {"name": "a"}}]}, "done": false}),
+        );
+        merge_chunk(
+            &mut total,
+            json!({"message": {"role": "assistant", "content": "Hi"}, "done": true}),
+        );
+        assert_eq!(total["message"]["content"], "Hi");
+        assert_eq!(total["message"]["thinking"], "Hm");
+        assert_eq!(total["message"]["tool_calls"][0]["function"]["name"], "a");
+    }
+
+    #[test]
+    fn test_decode_non_json_body() {
+        assert_eq!(decode(b"oops"), json!("oops"));
+    }
+
+    #[test]
+    fn test_interleaved_streams_are_reassembled_separately() {
+        let path = std::env::temp_dir().join(format!(
+            "ollama-oxide-transcript-interleaved-{}.ndjson",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let recorder = TranscriptRecorder::create(&path).unwrap();
+        let url = "http://localhost:11434/api/generate";
+        let (a, b) = (CallId::next(), CallId::next());
+
+        recorder.on_response_body(a, url, br#"{"response":"a1","done":false}"#);
+        recorder.on_response_body(b, url, br#"{"response":"b1","done":false}"#);
+        recorder.on_response_body(a, url, br#"{"response":"a2","done":true}"#);
+        recorder.on_response_body(b, url, br#"{"response":"b2","done":true}"#);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let texts: Vec<Value> = written
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["body"]["response"].take())
+            .collect();
+        assert_eq!(texts, [json!("a1a2"), json!("b1b2")]);
+        assert!(recorder.pending.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Lazily built HTTP clients shared across `OllamaClient` clones
+
+use std::sync::{Mutex, PoisonError};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use super::ClientConfig;
+use crate::{Error, Result};
+
+/// Headers sent on every request: the bearer token when an API key is set,
+/// in the header the gateway expects.
+fn default_headers(config: &ClientConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    if let Some(key) = config.api_key() {
+        let mut value = HeaderValue::from_str(&format!("Bearer {key}"))
+            .map_err(|_| Error::ValidationError("API key is not a valid header value".into()))?;
+        value.set_sensitive(true);
+        let gateway = config.known_gateway().unwrap_or_default();
+        headers.insert(HeaderName::from_static(gateway.auth_header()), value);
+    }
+    Ok(headers)
+}
+
+/// An async client builder with the configured timeouts
+fn async_builder(config: &ClientConfig) -> reqwest::ClientBuilder {
+    let mut builder = reqwest::Client::builder().timeout(config.timeout());
+    if let Some(connect) = config.connect_timeout() {
+        builder = builder.connect_timeout(connect);
+    }
+    if let Some(read) = config.read_timeout() {
+        builder = builder.read_timeout(read);
+    }
+    builder
+}
+
+/// The async and blocking reqwest clients behind an [`OllamaClient`](super::OllamaClient).
+///
+/// None is built until its first request, so creating an `OllamaClient`
+/// does no TLS or connector setup. Clones share this value through an
+/// `Arc`, and with it one connection pool per flavour, until
+/// [`release`](Self::release) drops every pool for good.
+#[derive(Debug)]
+pub(super) struct Transport {
+    headers: HeaderMap,
+    clients: Mutex<Clients>,
+}
+
+/// The built clients, and whether they were released
+#[derive(Debug, Default)]
+struct Clients {
+    client: Option<reqwest::Client>,
+    /// Async client without the API key, for hosts other than the server
+    external: Option<reqwest::Client>,
+    blocking: Option<reqwest::blocking::Client>,
+    released: bool,
+}
+
+impl Transport {
+    /// Validate the headers `config` implies; no client is built yet.
+    pub(super) fn new(config: &ClientConfig) -> Result<Self> {
+        Ok(Self {
+            headers: default_headers(config)?,
+            clients: Mutex::default(),
+        })
+    }
+
+    fn clients(&self) -> std::sync::MutexGuard<'_, Clients> {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the async client, building it on first use.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ClientShutdown`] once the clients were released.
+    pub(super) fn client(&self, config: &ClientConfig) -> Result<reqwest::Client> {
+        let mut clients = self.clients();
+        if clients.released {
+            return Err(Error::ClientShutdown);
+        }
+        if let Some(client) = &clients.client {
+            return Ok(client.clone());
+        }
+        let client = async_builder(config)
+            .default_headers(self.headers.clone())
+            .build()?;
+        Ok(clients.client.insert(client).clone())
+    }
+
+    /// Get the async client for hosts other than the server, such as job
+    /// webhooks, building it on first use. It never sends the API key.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ClientShutdown`] once the clients were released.
+    #[cfg_attr(not(feature = "conveniences"), allow(dead_code))]
+    pub(super) fn external(&self, config: &ClientConfig) -> Result<reqwest::Client> {
+        let mut clients = self.clients();
+        if clients.released {
+            return Err(Error::ClientShutdown);
+        }
+        if let Some(client) = &clients.external {
+            return Ok(client.clone());
+        }
+        let client = async_builder(config).build()?;
+        Ok(clients.external.insert(client).clone())
+    }
+
+    /// Get the blocking client, building it on first use.
+    ///
+    /// It has no client-wide timeout; callers set one per request.
+    pub(super) fn blocking(&self, config: &ClientConfig) -> Result<reqwest::blocking::Client> {
+        let mut clients = self.clients();
+        if clients.released {
+            return Err(Error::ClientShutdown);
+        }
+        if let Some(client) = &clients.blocking {
+            return Ok(client.clone());
+        }
+        let mut builder = reqwest::blocking::Client::builder()
+            .timeout(None)
+            .default_headers(self.headers.clone());
+        if let Some(connect) = config.connect_timeout() {
+            builder = builder.connect_timeout(connect);
+        }
+        let client = builder.build()?;
+        Ok(clients.blocking.insert(client).clone())
+    }
+
+    /// Drops every client and refuses to build new ones.
+    ///
+    /// Returns the blocking client, if built, since it must not be dropped
+    /// on an async task.
+    pub(super) fn release(&self) -> Option<reqwest::blocking::Client> {
+        let mut clients = self.clients();
+        clients.released = true;
+        clients.client = None;
+        clients.external = None;
+        clients.blocking.take()
+    }
+
+    /// Whether any client has been built
+    #[cfg(test)]
+    pub(super) fn is_built(&self) -> bool {
+        let clients = self.clients();
+        clients.client.is_some() || clients.external.is_some() || clients.blocking.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GatewayProfile;
+
+    #[test]
+    fn test_built_on_first_use_only() {
+        let config = ClientConfig::default();
+        let transport = Transport::new(&config).unwrap();
+        assert!(!transport.is_built());
+        transport.client(&config).unwrap();
+        transport.client(&config).unwrap();
+        assert!(transport.is_built());
+    }
+
+    #[test]
+    fn test_external_client_is_separate_and_released() {
+        let config = ClientConfig::default().with_api_key("k");
+        let transport = Transport::new(&config).unwrap();
+        transport.external(&config).unwrap();
+        assert!(transport.is_built());
+        assert!(transport.clients().client.is_none());
+        transport.release();
+        assert!(!transport.is_built());
+        assert!(matches!(
+            transport.external(&config),
+            Err(Error::ClientShutdown)
+        ));
+    }
+
+    #[test]
+    fn test_release_refuses_rebuild() {
+        let config = ClientConfig::default();
+        let transport = Transport::new(&config).unwrap();
+        transport.client(&config).unwrap();
+        assert!(transport.release().is_none());
+        assert!(!transport.is_built());
+        assert!(matches!(
+            transport.client(&config),
+            Err(Error::ClientShutdown)
+        ));
+    }
+
+    #[test]
+    fn test_api_key_header_follows_gateway() {
+        let config = ClientConfig::default().with_api_key("k");
+        assert!(
+            default_headers(&config)
+                .unwrap()
+                .contains_key("authorization")
+        );
+        let config = config.with_gateway(GatewayProfile::LiteLlm);
+        let headers = default_headers(&config).unwrap();
+        assert_eq!(headers["x-litellm-api-key"], "Bearer k");
+        assert!(!headers.contains_key("authorization"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_api_key() {
+        let config = ClientConfig::default().with_api_key("bad\nkey");
+        assert!(matches!(
+            Transport::new(&config),
+            Err(Error::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Sign-binarized embeddings
+
+use serde::{Deserialize, Serialize};
+
+use super::EmbeddingFloat;
+use super::validation;
+use crate::Result;
+
+/// An embedding reduced to one bit per dimension: set where the value is
+/// positive.
+///
+/// Takes 1/32 the memory of `f32` values while keeping most of their
+/// ranking power, so a common retrieval setup shortlists by
+/// [`hamming`](Self::hamming) distance over binary vectors and re-ranks the
+/// shortlist with the full embeddings. Build one with
+/// [`from_values`](Self::from_values) or
+/// [`EmbedResponse::binarize`](super::EmbedResponse::binarize).
+///
+/// # Example
+///
+/// ```
+/// use ollama_oxide::BinaryEmbedding;
+///
+/// let a = BinaryEmbedding::from_values(&[0.3_f32, -0.1, 0.2, -0.4]);
+/// let b = BinaryEmbedding::from_values(&[0.1_f32, 0.5, 0.2, -0.3]);
+/// assert_eq!(a.hamming(&b)?, 1);
+/// assert!((a.jaccard(&b)? - 2.0 / 3.0).abs() < 1e-9);
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct BinaryEmbedding {
+    dimensions: usize,
+    bits: Vec<u64>,
+}
+
+impl BinaryEmbedding {
+    /// One bit per value, set where the value is greater than zero
+    pub fn from_values<F: EmbeddingFloat>(values: &[F]) -> Self {
+        let mut bits = vec![0u64; values.len().div_ceil(64)];
+        for (i, value) in values.iter().enumerate() {
+            if value.to_f64() > 0.0 {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        Self {
+            dimensions: values.len(),
+            bits,
+        }
+    }
+
+    /// Number of dimensions of the source embedding
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Whether dimension `index` is set, `None` past the end
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.dimensions).then(|| {
+            self.bits
+                .get(index / 64)
+                .is_some_and(|w| w & (1 << (index % 64)) != 0)
+        })
+    }
+
+    /// Number of set dimensions
+    pub fn count_ones(&self) -> u32 {
+        self.bits.iter().map(|w| w.count_ones()).sum()
+    }
+
+    /// The packed bits, dimension `i` at bit `i % 64` of word `i / 64`
+    pub fn as_words(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// Number of dimensions where the two embeddings differ.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`](crate::Error::ValidationError) if the
+    /// dimensions differ.
+    pub fn hamming(&self, other: &Self) -> Result<u32> {
+        validation::same_dimensions(self.dimensions, other.dimensions)?;
+        Ok(self
+            .bits
+            .iter()
+            .zip(&other.bits)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum())
+    }
+
+    /// Set bits in both over set bits in either, from 0.0 to 1.0; 1.0 when
+    /// neither has any.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`](crate::Error::ValidationError) if the
+    /// dimensions differ.
+    pub fn jaccard(&self, other: &Self) -> Result<f64> {
+        validation::same_dimensions(self.dimensions, other.dimensions)?;
+        let (both, either) = self
+            .bits
+            .iter()
+            .zip(&other.bits)
+            .fold((0u32, 0u32), |(both, either), (a, b)| {
+                (both + (a & b).count_ones(), either + (a | b).count_ones())
+            });
+        Ok(if either == 0 {
+            1.0
+        } else {
+            f64::from(both) / f64::from(either)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_follow_sign_across_words() {
+        let mut values = vec![-1.0_f64; 70];
+        values[0] = 0.5;
+        values[65] = 0.1;
+        let binary = BinaryEmbedding::from_values(&values);
+
+        assert_eq!(binary.dimensions(), 70);
+        assert_eq!(binary.as_words().len(), 2);
+        assert_eq!(binary.count_ones(), 2);
+        assert_eq!(binary.get(65), Some(true));
+        assert_eq!(binary.get(64), Some(false));
+        assert_eq!(binary.get(70), None);
+    }
+
+    #[test]
+    fn test_zero_is_unset() {
+        let binary = BinaryEmbedding::from_values(&[0.0_f32, -0.0]);
+        assert_eq!(binary.count_ones(), 0);
+    }
+
+    #[test]
+    fn test_empty_sets_are_identical() {
+        let a = BinaryEmbedding::from_values(&[-1.0_f32; 3]);
+        assert_eq!(a.jaccard(&a).unwrap(), 1.0);
+        assert_eq!(a.hamming(&a).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_mismatched_dimensions_rejected() {
+        let a = BinaryEmbedding::from_values(&[1.0_f32; 3]);
+        let b = BinaryEmbedding::from_values(&[1.0_f32; 4]);
+        assert!(a.hamming(&b).is_err());
+        assert!(a.jaccard(&b).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+//! Cancel reason primitive type
+
+use std::fmt;
+
+/// Why a stream was ended by the client rather than the server
+///
+/// Set in `cancel_reason` on the terminal event of a cancelled chat or
+/// generate stream, so callers can tell a deliberate stop from a failure:
+/// failures end the stream with an `Err` instead. Never sent by the
+/// server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CancelReason {
+    /// `CancelToken::cancel` was called
+    User,
+    /// The guard from `CancelToken::drop_guard` was dropped
+    Dropped,
+    /// The stream's `with_timeout` deadline passed
+    Timeout,
+    /// A client-side stop sequence from `stop_at` was reached
+    StopSequence,
+}
+
+impl CancelReason {
+    /// Short lowercase name, e.g. `stop_sequence`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Dropped => "dropped",
+            Self::Timeout => "timeout",
+            Self::StopSequence => "stop_sequence",
+        }
+    }
+
+    /// Whether the caller asked for the stop, as opposed to a deadline
+    pub fn is_user_initiated(self) -> bool {
+        matches!(self, Self::User | Self::Dropped)
+    }
+}
+
+impl fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::ChatRole;
+use super::{ChatRole, ContentPart, ImageData};
 
 #[cfg(feature = "tools")]
 use crate::tools::ToolCall;
@@ -46,7 +46,20 @@ use crate::tools::ToolCall;
 /// let message = ChatMessage::user("What's in this image?")
 ///     .with_image("base64_encoded_image_data_here");
 /// ```
+///
+/// ## Building from Content Parts
+///
+/// ```no_run
+/// use ollama_oxide::{ChatMessage, ChatRole, ContentPart};
+///
+/// let message = ChatMessage::from_parts(ChatRole::User, [
+///     ContentPart::text("Compare these two photos."),
+///     ContentPart::image("first_base64_image"),
+///     ContentPart::image("second_base64_image"),
+/// ]);
+/// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChatMessage {
     /// Role of the message author (system, user, assistant, or tool).
     pub role: ChatRole,
@@ -54,12 +67,12 @@ pub struct ChatMessage {
     /// Text content of the message.
     pub content: String,
 
-    /// Optional base64-encoded images for multimodal models.
+    /// Optional images for multimodal models.
     ///
     /// When using vision-capable models, you can include images
-    /// as base64-encoded strings.
+    /// as base64-encoded strings or raw bytes.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub images: Option<Vec<String>>,
+    pub images: Option<Vec<ImageData>>,
 
     /// Tool calls made by the assistant (for assistant messages).
     ///
@@ -68,6 +81,7 @@ pub struct ChatMessage {
     ///
     /// Requires the `tools` feature.
     #[cfg(feature = "tools")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ToolCall>>,
 }
@@ -99,6 +113,58 @@ impl ChatMessage {
         }
     }
 
+    /// Create a message from typed content parts.
+    ///
+    /// Parts are collapsed into Ollama's wire format: text parts are joined
+    /// with newlines into `content`, and image parts become `images` in
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_oxide::{ChatMessage, ChatRole, ContentPart};
+    ///
+    /// let msg = ChatMessage::from_parts(ChatRole::User, [
+    ///     ContentPart::text("What is this?"),
+    ///     ContentPart::image("aGVsbG8="),
+    /// ]);
+    /// assert_eq!(msg.content, "What is this?");
+    /// assert_eq!(msg.images.unwrap(), ["aGVsbG8="]);
+    /// ```
+    pub fn from_parts<I>(role: ChatRole, parts: I) -> Self
+    where
+        I: IntoIterator<Item = ContentPart>,
+    {
+        let mut texts = Vec::new();
+        let mut images = Vec::new();
+        for part in parts {
+            match part {
+                ContentPart::Text { text } => texts.push(text),
+                ContentPart::Image { data } => images.push(data),
+            }
+        }
+        let mut message = Self::new(role, texts.join("\n"));
+        if !images.is_empty() {
+            message.images = Some(images);
+        }
+        message
+    }
+
+    /// The message content as typed parts.
+    ///
+    /// Yields the text (if any) followed by each image. The inverse of
+    /// [`from_parts`](Self::from_parts) up to the joining of text parts.
+    pub fn parts(&self) -> Vec<ContentPart> {
+        let text = (!self.content.is_empty()).then(|| ContentPart::text(self.content.clone()));
+        let images = self
+            .images
+            .iter()
+            .flatten()
+            .cloned()
+            .map(ContentPart::image);
+        text.into_iter().chain(images).collect()
+    }
+
     /// Create a system message.
     ///
     /// System messages set the behavior and context for the conversation.
@@ -185,14 +251,15 @@ impl ChatMessage {
         Self::new(ChatRole::Tool, content)
     }
 
-    /// Add an image to the message (base64-encoded).
+    /// Add an image to the message.
     ///
-    /// For use with multimodal/vision models. Images should be
-    /// base64-encoded.
+    /// For use with multimodal/vision models. Strings are sent as
+    /// base64 data; raw bytes (see [`ImageData::from_bytes`]) are encoded
+    /// while the request is serialized.
     ///
     /// # Arguments
     ///
-    /// * `image` - Base64-encoded image data
+    /// * `image` - Base64-encoded image data or raw image bytes
     ///
     /// # Examples
     ///
@@ -204,7 +271,7 @@ impl ChatMessage {
     /// assert!(msg.images.is_some());
     /// assert_eq!(msg.images.as_ref().unwrap().len(), 1);
     /// ```
-    pub fn with_image(mut self, image: impl Into<String>) -> Self {
+    pub fn with_image(mut self, image: impl Into<ImageData>) -> Self {
         self.images.get_or_insert_with(Vec::new).push(image.into());
         self
     }
@@ -213,7 +280,7 @@ impl ChatMessage {
     ///
     /// # Arguments
     ///
-    /// * `images` - Iterator of base64-encoded image data
+    /// * `images` - Iterator of base64-encoded image data or raw image bytes
     ///
     /// # Examples
     ///
@@ -227,7 +294,7 @@ impl ChatMessage {
     pub fn with_images<I, S>(mut self, images: I) -> Self
     where
         I: IntoIterator<Item = S>,
-        S: Into<String>,
+        S: Into<ImageData>,
     {
         self.images = Some(images.into_iter().map(|s| s.into()).collect());
         self
@@ -515,10 +582,7 @@ mod tests {
         }"#;
 
         let msg: ChatMessage = serde_json::from_str(json).unwrap();
-        assert_eq!(
-            msg.images,
-            Some(vec!["img1".to_string(), "img2".to_string()])
-        );
+        assert_eq!(msg.images.unwrap(), ["img1", "img2"]);
     }
 
     #[cfg(feature = "tools")]
@@ -565,4 +629,35 @@ mod tests {
         let msg = ChatMessage::user("borrowed");
         assert_eq!(msg.content, "borrowed");
     }
+
+    #[test]
+    fn test_chat_message_from_parts_collapses_to_wire_format() {
+        let msg = ChatMessage::from_parts(
+            ChatRole::User,
+            [
+                ContentPart::text("Look:"),
+                ContentPart::image("img1"),
+                ContentPart::text("What differs?"),
+                ContentPart::image("img2"),
+            ],
+        );
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["content"], "Look:\nWhat differs?");
+        assert_eq!(json["images"], json!(["img1", "img2"]));
+    }
+
+    #[test]
+    fn test_chat_message_parts_round_trip() {
+        let msg = ChatMessage::user("Hi").with_image("img");
+        let parts = msg.parts();
+        assert_eq!(parts, [ContentPart::text("Hi"), ContentPart::image("img")]);
+        assert_eq!(ChatMessage::from_parts(ChatRole::User, parts), msg);
+    }
+
+    #[test]
+    fn test_chat_message_from_text_only_parts_has_no_images() {
+        let msg = ChatMessage::from_parts(ChatRole::User, [ContentPart::from("Hi")]);
+        assert_eq!(msg.images, None);
+        assert!(!serde_json::to_string(&msg).unwrap().contains("images"));
+    }
 }
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::request_fingerprint::{self, RequestFingerprint};
+use super::validation;
 use super::{ChatMessage, FormatSetting, KeepAliveSetting, ModelOptions, ThinkSetting};
+use crate::Result;
 #[cfg(feature = "tools")]
 use crate::tools::ToolDefinition;
 
@@ -66,6 +69,7 @@ use crate::tools::ToolDefinition;
 /// ]);
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChatRequest {
     /// Name of the model to use.
     pub model: String,
@@ -84,6 +88,7 @@ pub struct ChatRequest {
     ///
     /// Requires the `tools` feature.
     #[cfg(feature = "tools")]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<ToolDefinition>>,
 
@@ -455,6 +460,57 @@ impl ChatRequest {
     pub fn tools(&self) -> Option<&[ToolDefinition]> {
         self.tools.as_deref()
     }
+
+    /// Check the request for obvious mistakes without contacting the server.
+    ///
+    /// Rejects an empty model name, images sent to a
+    /// [`KnownModel`](crate::KnownModel) without vision support, and invalid
+    /// option values (`num_predict` below `-2`, non-positive `num_ctx`).
+    /// The client calls this before sending every chat request.
+    ///
+    /// An empty message list is accepted: Ollama loads the model for such a
+    /// request, or unloads it when `keep_alive` is `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`](crate::Error::ValidationError)
+    /// describing the first problem found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ollama_oxide::{ChatMessage, ChatRequest};
+    ///
+    /// // Loads the model without generating anything
+    /// let request = ChatRequest::new("llama3.2", Vec::<ChatMessage>::new());
+    /// assert!(request.validate().is_ok());
+    ///
+    /// let request = ChatRequest::new("", [ChatMessage::user("Hi")]);
+    /// assert!(request.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        validation::model_name(&self.model)?;
+        if self
+            .messages
+            .iter()
+            .any(|m| m.images.as_ref().is_some_and(|i| !i.is_empty()))
+        {
+            validation::images_allowed(&self.model)?;
+        }
+        validation::options(self.options.as_ref())
+    }
+
+    /// A stable hash of what this request asks for, ignoring `stream` and
+    /// `keep_alive`; see [`RequestFingerprint`] for the normalization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if the request cannot be serialized, e.g. an attached image file
+    /// cannot be read.
+    pub fn fingerprint(&self) -> Result<RequestFingerprint> {
+        request_fingerprint::fingerprint("chat", self)
+    }
 }
 
 #[cfg(test)]
@@ -463,6 +519,78 @@ mod tests {
     #[allow(unused)]
     use serde_json::json;
 
+    #[test]
+    fn test_validate_accepts_valid_request() {
+        let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hello")])
+            .with_options(ModelOptions::default().with_num_predict(-1));
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_delivery_fields() {
+        let request = ChatRequest::new("qwen3", [ChatMessage::user("Hello")]);
+        let streamed = request.clone().with_stream(true).with_keep_alive("10m");
+        assert_eq!(
+            request.fingerprint().unwrap(),
+            streamed.fingerprint().unwrap()
+        );
+
+        let other = ChatRequest::new("qwen3", [ChatMessage::user("Hello!")]);
+        assert_ne!(request.fingerprint().unwrap(), other.fingerprint().unwrap());
+        let hot = request
+            .clone()
+            .with_options(ModelOptions::default().with_temperature(0.9));
+        assert_ne!(request.fingerprint().unwrap(), hot.fingerprint().unwrap());
+    }
+
+    #[test]
+    fn test_fingerprint_fails_on_unreadable_image() {
+        let message = ChatMessage::user("Describe").with_image(crate::ImageData::from_path(
+            "/nonexistent/ollama-oxide-fingerprint.png",
+        ));
+        let request = ChatRequest::new("llava", [message]);
+        assert!(matches!(
+            request.fingerprint(),
+            Err(crate::Error::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_model_but_not_messages() {
+        let request = ChatRequest::new("  ", [ChatMessage::user("Hello")]);
+        assert!(matches!(
+            request.validate(),
+            Err(crate::Error::ValidationError(_))
+        ));
+
+        let unload = ChatRequest::new("qwen3", Vec::<ChatMessage>::new()).with_keep_alive("0");
+        assert!(unload.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_images_on_known_text_model() {
+        let message = ChatMessage::user("What is this?").with_image("aGVsbG8=");
+        let request = ChatRequest::new("mistral:7b", [message.clone()]);
+        assert!(request.validate().is_err());
+
+        let request = ChatRequest::new("llava", [message.clone()]);
+        assert!(request.validate().is_ok());
+
+        let request = ChatRequest::new("my-custom-model", [message]);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_options() {
+        let request = ChatRequest::new("qwen3", [ChatMessage::user("Hi")])
+            .with_options(ModelOptions::default().with_num_predict(-5));
+        assert!(request.validate().is_err());
+
+        let request = ChatRequest::new("qwen3", [ChatMessage::user("Hi")])
+            .with_options(ModelOptions::default().with_num_ctx(0));
+        assert!(request.validate().is_err());
+    }
+
     #[test]
     fn test_chat_request_new_with_vec() {
         let request = ChatRequest::new("qwen3:0.6b", vec![ChatMessage::user("Hello")]);
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::{Logprob, ResponseMessage};
+use super::{CancelReason, DoneReason, EvalMetrics, Logprob, ResponseMessage};
 #[cfg(feature = "tools")]
 use crate::tools::ToolCall;
 
@@ -53,10 +53,8 @@ pub struct ChatResponse {
     pub done: Option<bool>,
 
     /// Reason the generation stopped.
-    ///
-    /// Common values: "stop" (natural end), "length" (max tokens reached).
     #[serde(default)]
-    pub done_reason: Option<String>,
+    pub done_reason: Option<DoneReason>,
 
     /// Total time spent generating the response in nanoseconds.
     #[serde(default)]
@@ -85,6 +83,17 @@ pub struct ChatResponse {
     /// Log probability information (if logprobs was enabled).
     #[serde(default)]
     pub logprobs: Option<Vec<Logprob>>,
+
+    /// Why the client ended the stream early, on the terminal event of a
+    /// cancelled stream; never sent by the server.
+    #[serde(skip)]
+    pub cancel_reason: Option<CancelReason>,
+
+    /// Whether `eval_count` and `prompt_eval_count` are client-side
+    /// estimates rather than the server's counts, as on the terminal event
+    /// of a cancelled stream; never sent by the server.
+    #[serde(skip)]
+    pub counts_estimated: bool,
 }
 
 impl ChatResponse {
@@ -231,6 +240,28 @@ impl ChatResponse {
         }
     }
 
+    /// Get the timing and token usage metrics as one value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::ChatResponse;
+    ///
+    /// let json = r#"{"eval_count": 100, "eval_duration": 1000000000}"#;
+    /// let response: ChatResponse = serde_json::from_str(json).unwrap();
+    /// println!("{}", response.metrics()); // "100 tokens in 1.00s (100.0 tok/s)"
+    /// ```
+    pub fn metrics(&self) -> EvalMetrics {
+        EvalMetrics {
+            total_duration: self.total_duration,
+            load_duration: self.load_duration,
+            prompt_eval_count: self.prompt_eval_count,
+            prompt_eval_duration: self.prompt_eval_duration,
+            eval_count: self.eval_count,
+            eval_duration: self.eval_duration,
+        }
+    }
+
     /// Get the model name.
     pub fn model(&self) -> Option<&str> {
         self.model.as_deref()
@@ -242,8 +273,8 @@ impl ChatResponse {
     }
 
     /// Get the done reason.
-    pub fn done_reason(&self) -> Option<&str> {
-        self.done_reason.as_deref()
+    pub fn done_reason(&self) -> Option<&DoneReason> {
+        self.done_reason.as_ref()
     }
 
     /// Get the prompt token count.
@@ -429,7 +460,7 @@ mod tests {
     fn test_chat_response_done_reason() {
         let json = r#"{"done_reason": "stop"}"#;
         let response: ChatResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(response.done_reason(), Some("stop"));
+        assert_eq!(response.done_reason(), Some(&DoneReason::Stop));
     }
 
     #[test]
@@ -486,7 +517,7 @@ mod tests {
         assert_eq!(response.model(), Some("qwen3:0.6b"));
         assert_eq!(response.content(), Some("Hello! How can I help you today?"));
         assert!(response.is_done());
-        assert_eq!(response.done_reason(), Some("stop"));
+        assert_eq!(response.done_reason(), Some(&DoneReason::Stop));
         assert_eq!(response.prompt_tokens(), Some(11));
         assert_eq!(response.completion_tokens(), Some(18));
         assert!(response.tokens_per_second().is_some());
@@ -538,10 +569,12 @@ mod tests {
 
     #[test]
     fn test_chat_response_serialize() {
-        let mut response = ChatResponse::default();
-        response.model = Some("test".to_string());
-        response.done = Some(true);
-        response.message = Some(ResponseMessage::new("Hello"));
+        let response = ChatResponse {
+            model: Some("test".to_string()),
+            done: Some(true),
+            message: Some(ResponseMessage::new("Hello")),
+            ..Default::default()
+        };
 
         let json = serde_json::to_value(&response).unwrap();
 
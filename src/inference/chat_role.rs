@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 /// Role of a message in the chat conversation.
 ///
 /// Determines who authored a particular message in the conversation history.
+/// New roles may be added in minor releases, so matches need a wildcard arm.
 ///
 /// # Examples
 ///
@@ -19,7 +20,9 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(role, ChatRole::Assistant);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum ChatRole {
     /// System message that sets behavior and context for the conversation.
     ///
@@ -122,6 +125,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_chat_role_clone() {
         let role = ChatRole::Assistant;
         let cloned = role.clone();
@@ -0,0 +1,112 @@
+//! Typed pieces of multimodal message content.
+
+use serde::{Deserialize, Serialize};
+
+use super::ImageData;
+
+/// One piece of a message's content.
+///
+/// Ollama's wire format carries a message as one `content` string plus a
+/// separate `images` array. `ContentPart` gives that a typed, ordered shape
+/// that can grow new kinds (such as audio) without changing
+/// [`ChatMessage`](super::ChatMessage): build a message with
+/// [`ChatMessage::from_parts`](super::ChatMessage::from_parts) and read it
+/// back with [`ChatMessage::parts`](super::ChatMessage::parts). The enum is
+/// `#[non_exhaustive]`, so match it with a wildcard arm.
+///
+/// On its own, a part serializes with a `type` tag, which is convenient for
+/// storing transcripts:
+///
+/// ```
+/// use ollama_oxide::ContentPart;
+///
+/// let part = ContentPart::text("Describe this.");
+/// assert_eq!(
+///     serde_json::to_string(&part).unwrap(),
+///     r#"{"type":"text","text":"Describe this."}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ContentPart {
+    /// Plain text.
+    Text {
+        /// The text.
+        text: String,
+    },
+
+    /// An image, for vision-capable models.
+    Image {
+        /// The image, base64-encoded or raw.
+        data: ImageData,
+    },
+}
+
+impl ContentPart {
+    /// Create a text part.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text { text: text.into() }
+    }
+
+    /// Create an image part from a base64 string or raw bytes.
+    pub fn image(data: impl Into<ImageData>) -> Self {
+        Self::Image { data: data.into() }
+    }
+
+    /// The text, if this is a text part.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text { text } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The image data, if this is an image part.
+    pub fn as_image(&self) -> Option<&ImageData> {
+        match self {
+            Self::Image { data } => Some(data),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for ContentPart {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for ContentPart {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_part_serde_round_trip() {
+        let parts = vec![ContentPart::text("Hi"), ContentPart::image("aGVsbG8=")];
+        let value = serde_json::to_value(&parts).unwrap();
+        assert_eq!(
+            value,
+            json!([
+                {"type": "text", "text": "Hi"},
+                {"type": "image", "data": "aGVsbG8="}
+            ])
+        );
+        let back: Vec<ContentPart> = serde_json::from_value(value).unwrap();
+        assert_eq!(back, parts);
+    }
+
+    #[test]
+    fn test_content_part_accessors() {
+        assert_eq!(ContentPart::from("a").as_text(), Some("a"));
+        assert_eq!(ContentPart::text("a").as_image(), None);
+        assert!(ContentPart::image("b").as_image().is_some_and(|i| i == "b"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Done reason primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// Why the server stopped generating
+///
+/// Reported in `done_reason` on the final chunk of a generate or chat
+/// response. Values this crate does not know yet are kept verbatim in
+/// [`Unknown`](Self::Unknown), so new server values never fail
+/// deserialization.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::DoneReason;
+///
+/// let reason: DoneReason = serde_json::from_str("\"length\"").unwrap();
+/// assert_eq!(reason, DoneReason::Length);
+///
+/// let reason: DoneReason = serde_json::from_str("\"unload\"").unwrap();
+/// assert_eq!(reason, DoneReason::Unknown("unload".to_string()));
+/// assert_eq!(reason.as_str(), "unload");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum DoneReason {
+    /// The model finished on its own or hit a stop sequence
+    Stop,
+    /// The token limit (`num_predict` or context) was reached
+    Length,
+    /// The model was loaded without generating (empty prompt)
+    Load,
+    /// A client-side stop sequence from `stop_at` was reached; never sent
+    /// by the server
+    ClientStop,
+    /// A value this crate does not recognize, as sent by the server
+    Unknown(String),
+}
+
+impl DoneReason {
+    /// The value as the server spells it
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Stop => "stop",
+            Self::Length => "length",
+            Self::Load => "load",
+            Self::ClientStop => "client_stop",
+            Self::Unknown(s) => s,
+        }
+    }
+
+    /// Whether generation was cut off by the token limit
+    pub fn is_truncated(&self) -> bool {
+        *self == Self::Length
+    }
+}
+
+impl From<String> for DoneReason {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "stop" => Self::Stop,
+            "length" => Self::Length,
+            "load" => Self::Load,
+            "client_stop" => Self::ClientStop,
+            _ => Self::Unknown(s),
+        }
+    }
+}
+
+impl From<&str> for DoneReason {
+    fn from(s: &str) -> Self {
+        Self::from(s.to_string())
+    }
+}
+
+impl From<DoneReason> for String {
+    fn from(reason: DoneReason) -> Self {
+        match reason {
+            DoneReason::Unknown(s) => s,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for DoneReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_done_reason_deserialize_known() {
+        let reason: DoneReason = serde_json::from_str("\"stop\"").unwrap();
+        assert_eq!(reason, DoneReason::Stop);
+        let reason: DoneReason = serde_json::from_str("\"length\"").unwrap();
+        assert_eq!(reason, DoneReason::Length);
+        let reason: DoneReason = serde_json::from_str("\"load\"").unwrap();
+        assert_eq!(reason, DoneReason::Load);
+    }
+
+    #[test]
+    fn test_done_reason_deserialize_unknown() {
+        let reason: DoneReason = serde_json::from_str("\"unload\"").unwrap();
+        assert_eq!(reason, DoneReason::Unknown("unload".to_string()));
+    }
+
+    #[test]
+    fn test_done_reason_serialize_roundtrip() {
+        for value in [
+            "\"stop\"",
+            "\"length\"",
+            "\"load\"",
+            "\"client_stop\"",
+            "\"unload\"",
+        ] {
+            let reason: DoneReason = serde_json::from_str(value).unwrap();
+            assert_eq!(serde_json::to_string(&reason).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_done_reason_is_truncated() {
+        assert!(DoneReason::Length.is_truncated());
+        assert!(!DoneReason::Stop.is_truncated());
+    }
+
+    #[test]
+    fn test_done_reason_display() {
+        assert_eq!(DoneReason::Stop.to_string(), "stop");
+        assert_eq!(DoneReason::from("custom").to_string(), "custom");
+    }
+}
@@ -34,6 +34,7 @@ use serde::{Deserialize, Serialize};
 /// ["First text", "Second text"]
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum EmbedInput {
     /// Single text input
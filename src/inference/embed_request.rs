@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::request_fingerprint::{self, RequestFingerprint};
+use super::validation;
 use super::{EmbedInput, ModelOptions};
+use crate::Result;
 
 /// Request body for POST /api/embed endpoint
 ///
@@ -37,6 +40,7 @@ use super::{EmbedInput, ModelOptions};
 ///     .with_keep_alive("5m");
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EmbedRequest {
     /// Name of the embedding model to use
     pub model: String,
@@ -117,4 +121,31 @@ impl EmbedRequest {
         self.options = Some(options);
         self
     }
+
+    /// Check the request for obvious mistakes without contacting the server.
+    ///
+    /// Rejects an empty model name, empty input, and invalid option values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`](crate::Error::ValidationError).
+    pub fn validate(&self) -> Result<()> {
+        validation::model_name(&self.model)?;
+        if self.input.is_empty() {
+            return Err(validation::invalid("embedding input must not be empty"));
+        }
+        validation::options(self.options.as_ref())
+    }
+
+    /// A stable hash of what this request asks for, ignoring `stream` and
+    /// `keep_alive`; see [`RequestFingerprint`] for the normalization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if the request cannot be serialized, e.g. an attached image file
+    /// cannot be read.
+    pub fn fingerprint(&self) -> Result<RequestFingerprint> {
+        request_fingerprint::fingerprint("embed", self)
+    }
 }
@@ -2,9 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::{BinaryEmbedding, EmbeddingFloat, SparseEmbedding};
+#[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+use crate::{Error, Result};
+
 /// Response from POST /api/embed endpoint
 ///
-/// Contains the generated embeddings and timing information.
+/// Contains the generated embeddings and timing information. Values are
+/// parsed as `f32` unless another [`EmbeddingFloat`] is named, e.g.
+/// `EmbedResponse<f64>` from `OllamaClient::embed_as::<f64>`.
 ///
 /// # Example Response
 ///
@@ -18,7 +24,8 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-pub struct EmbedResponse {
+#[serde(bound = "F: EmbeddingFloat")]
+pub struct EmbedResponse<F: EmbeddingFloat = f32> {
     /// Model that produced the embeddings
     #[serde(default)]
     pub model: Option<String>,
@@ -27,7 +34,7 @@ pub struct EmbedResponse {
     ///
     /// Each inner vector contains the embedding dimensions (e.g., 768 or 1024 floats).
     #[serde(default)]
-    pub embeddings: Vec<Vec<f64>>,
+    pub embeddings: Vec<Vec<F>>,
 
     /// Total time spent generating embeddings in nanoseconds
     #[serde(default)]
@@ -42,7 +49,7 @@ pub struct EmbedResponse {
     pub prompt_eval_count: Option<i32>,
 }
 
-impl EmbedResponse {
+impl<F: EmbeddingFloat> EmbedResponse<F> {
     /// Get the number of embeddings returned
     ///
     /// This corresponds to the number of input texts provided.
@@ -65,7 +72,7 @@ impl EmbedResponse {
     /// Get the first embedding (convenience for single-input requests)
     ///
     /// Returns None if there are no embeddings.
-    pub fn first_embedding(&self) -> Option<&Vec<f64>> {
+    pub fn first_embedding(&self) -> Option<&Vec<F>> {
         self.embeddings.first()
     }
 
@@ -82,4 +89,84 @@ impl EmbedResponse {
     pub fn load_duration_ms(&self) -> Option<f64> {
         self.load_duration.map(|ns| ns as f64 / 1_000_000.0)
     }
+
+    /// The same response with values converted to precision `G`
+    pub fn cast<G: EmbeddingFloat>(self) -> EmbedResponse<G> {
+        EmbedResponse {
+            model: self.model,
+            embeddings: self
+                .embeddings
+                .into_iter()
+                .map(|e| e.into_iter().map(|v| G::from_f64(v.to_f64())).collect())
+                .collect(),
+            total_duration: self.total_duration,
+            load_duration: self.load_duration,
+            prompt_eval_count: self.prompt_eval_count,
+        }
+    }
+
+    /// Each embedding reduced to one bit per dimension, set where the value
+    /// is positive
+    pub fn binarize(&self) -> Vec<BinaryEmbedding> {
+        self.embeddings
+            .iter()
+            .map(|e| BinaryEmbedding::from_values(e))
+            .collect()
+    }
+
+    /// Each embedding reduced to its `k` largest-magnitude values
+    pub fn sparsify(&self, k: usize) -> Vec<SparseEmbedding<F>> {
+        self.embeddings
+            .iter()
+            .map(|e| SparseEmbedding::top_k(e, k))
+            .collect()
+    }
+
+    /// The embeddings as a matrix with one row per input.
+    ///
+    /// Requires the `ndarray` feature.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] if the embeddings differ in length.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2(&self) -> Result<ndarray::Array2<F>> {
+        let dims = self.uniform_dimensions()?;
+        let values = self.embeddings.iter().flatten().copied().collect();
+        ndarray::Array2::from_shape_vec((self.embeddings.len(), dims), values)
+            .map_err(|e| Error::ValidationError(e.to_string()))
+    }
+
+    /// The embeddings as a matrix with one row per input.
+    ///
+    /// Requires the `nalgebra` feature.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`] if the embeddings differ in length.
+    #[cfg(feature = "nalgebra")]
+    pub fn to_dmatrix(&self) -> Result<nalgebra::DMatrix<F>>
+    where
+        F: nalgebra::Scalar,
+    {
+        let dims = self.uniform_dimensions()?;
+        Ok(nalgebra::DMatrix::from_row_iterator(
+            self.embeddings.len(),
+            dims,
+            self.embeddings.iter().flatten().copied(),
+        ))
+    }
+
+    /// Length shared by every embedding, 0 when there are none
+    #[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+    fn uniform_dimensions(&self) -> Result<usize> {
+        let dims = self.dimensions().unwrap_or(0);
+        match self.embeddings.iter().position(|e| e.len() != dims) {
+            Some(i) => Err(Error::ValidationError(format!(
+                "embedding {i} has {} dimensions, expected {dims}",
+                self.embeddings[i].len()
+            ))),
+            None => Ok(dims),
+        }
+    }
 }
@@ -0,0 +1,58 @@
+//! Float types embeddings can be parsed into
+
+use std::fmt::Debug;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Precision of the values in an [`EmbedResponse`](super::EmbedResponse):
+/// `f32` (the default) or `f64`.
+///
+/// The server computes embeddings in single precision, so `f32` loses
+/// nothing and takes half the memory; pick `f64` when the vectors feed
+/// double-precision numerics. Sealed: implemented only for `f32` and `f64`.
+pub trait EmbeddingFloat:
+    sealed::Sealed
+    + Copy
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Debug
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + 'static
+{
+    /// The value widened to `f64`
+    fn to_f64(self) -> f64;
+
+    /// `value` at this precision, rounded if narrower
+    fn from_f64(value: f64) -> Self;
+}
+
+impl EmbeddingFloat for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl EmbeddingFloat for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
@@ -0,0 +1,196 @@
+//! Model and dimensions that embeddings belong to
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::{EmbedInput, EmbedRequest, EmbedResponse, EmbeddingFloat, SpaceVector};
+use crate::{Error, Result};
+
+/// The model and embedding length a set of vectors was produced with.
+///
+/// Vectors from different embedding models live in unrelated spaces:
+/// their cosine similarity is a meaningless number rather than an error,
+/// so mixing them (say, after switching models without re-indexing)
+/// silently wrecks retrieval. Tagging vectors with their space through
+/// [`vectors`](Self::vectors) or [`vector`](Self::vector) turns that into an
+/// [`Error::EmbeddingSpaceMismatch`] from every comparison.
+///
+/// Model names are compared with an implicit `:latest` tag, so
+/// `nomic-embed-text` and `nomic-embed-text:latest` are the same space.
+///
+/// # Example
+///
+/// ```no_run
+/// use ollama_oxide::{EmbeddingSpace, OllamaApiAsync, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let space = EmbeddingSpace::new("nomic-embed-text", 768);
+///
+/// let response = client.embed(&space.request(["a cat", "a kitten"])).await?;
+/// let vectors = space.vectors(response)?;
+/// println!("similarity: {:.3}", vectors[0].cosine(&vectors[1])?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingSpace {
+    model: String,
+    dimensions: usize,
+}
+
+impl EmbeddingSpace {
+    /// The space of `dimensions`-long vectors from `model`
+    pub fn new(model: impl Into<String>, dimensions: usize) -> Self {
+        Self {
+            model: model.into(),
+            dimensions,
+        }
+    }
+
+    /// Model name, as given
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Length of every vector in the space
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Whether `model` names this space's model
+    pub fn is_model(&self, model: &str) -> bool {
+        model_key(&self.model) == model_key(model)
+    }
+
+    /// An embed request for `input` with this space's model
+    pub fn request(&self, input: impl Into<EmbedInput>) -> EmbedRequest {
+        EmbedRequest::new(self.model.as_str(), input)
+    }
+
+    /// `values` tagged with this space.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] if `values` is not
+    /// [`dimensions`](Self::dimensions) long.
+    pub fn vector<F: EmbeddingFloat>(&self, values: Vec<F>) -> Result<SpaceVector<F>> {
+        self.tag(&Arc::new(self.clone()), values)
+    }
+
+    /// The embeddings of `response`, tagged with this space.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] if the response names a different
+    /// model or any embedding is not [`dimensions`](Self::dimensions) long.
+    pub fn vectors<F: EmbeddingFloat>(
+        &self,
+        response: EmbedResponse<F>,
+    ) -> Result<Vec<SpaceVector<F>>> {
+        if let Some(model) = &response.model
+            && !self.is_model(model)
+        {
+            return Err(self.mismatch(model, response.dimensions().unwrap_or(0)));
+        }
+        let space = Arc::new(self.clone());
+        response
+            .embeddings
+            .into_iter()
+            .map(|values| self.tag(&space, values))
+            .collect()
+    }
+
+    /// Fails unless `other` is the same space.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] naming both spaces.
+    pub fn ensure_same(&self, other: &Self) -> Result<()> {
+        if self != other {
+            return Err(self.mismatch(&other.model, other.dimensions));
+        }
+        Ok(())
+    }
+
+    fn tag<F: EmbeddingFloat>(&self, space: &Arc<Self>, values: Vec<F>) -> Result<SpaceVector<F>> {
+        if values.len() != self.dimensions {
+            return Err(self.mismatch(&self.model, values.len()));
+        }
+        Ok(SpaceVector::new(Arc::clone(space), values))
+    }
+
+    fn mismatch(&self, model: &str, dimensions: usize) -> Error {
+        Error::EmbeddingSpaceMismatch {
+            expected: self.to_string(),
+            found: Self::new(model, dimensions).to_string(),
+        }
+    }
+}
+
+/// `model` without an explicit `:latest` tag
+fn model_key(model: &str) -> &str {
+    model.strip_suffix(":latest").unwrap_or(model)
+}
+
+impl PartialEq for EmbeddingSpace {
+    fn eq(&self, other: &Self) -> bool {
+        self.dimensions == other.dimensions && self.is_model(&other.model)
+    }
+}
+
+impl Eq for EmbeddingSpace {}
+
+impl Hash for EmbeddingSpace {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        model_key(&self.model).hash(state);
+        self.dimensions.hash(state);
+    }
+}
+
+impl fmt::Display for EmbeddingSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} dimensions)", self.model, self.dimensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_tag_is_implicit() {
+        let space = EmbeddingSpace::new("nomic-embed-text", 3);
+        assert_eq!(space, EmbeddingSpace::new("nomic-embed-text:latest", 3));
+        assert_ne!(space, EmbeddingSpace::new("nomic-embed-text:v1.5", 3));
+        assert_ne!(space, EmbeddingSpace::new("nomic-embed-text", 4));
+    }
+
+    #[test]
+    fn test_vector_checks_length() {
+        let space = EmbeddingSpace::new("m", 2);
+        assert!(space.vector(vec![0.1_f32, 0.2]).is_ok());
+        let err = space.vector(vec![0.1_f32]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Embedding space mismatch: expected m (2 dimensions), found m (1 dimensions)"
+        );
+    }
+
+    #[test]
+    fn test_vectors_reject_other_model() {
+        let space = EmbeddingSpace::new("m", 2);
+        let response: EmbedResponse = EmbedResponse {
+            model: Some("other".to_string()),
+            embeddings: vec![vec![0.1, 0.2]],
+            ..Default::default()
+        };
+        assert!(matches!(
+            space.vectors(response),
+            Err(Error::EmbeddingSpaceMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+//! Evaluation metrics shared by chat and generate responses
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::humanize;
+
+/// Timing and token usage reported by the server when generation finishes.
+///
+/// Obtained from [`ChatResponse::metrics`](super::ChatResponse::metrics) or
+/// [`GenerateResponse::metrics`](super::GenerateResponse::metrics). All
+/// durations are in nanoseconds, as sent by Ollama.
+///
+/// The `Display` implementation prints a one-line, human-readable summary:
+///
+/// ```
+/// use ollama_oxide::EvalMetrics;
+///
+/// let metrics = EvalMetrics {
+///     eval_count: Some(18),
+///     eval_duration: Some(52_479_709),
+///     prompt_eval_count: Some(11),
+///     prompt_eval_duration: Some(13_074_791),
+///     total_duration: Some(174_560_334),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     metrics.to_string(),
+///     "18 tokens in 52.5ms (343.0 tok/s), prompt 11 tokens in 13.1ms, total 174.6ms"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EvalMetrics {
+    /// Total time spent generating the response
+    #[serde(default)]
+    pub total_duration: Option<i64>,
+
+    /// Time spent loading the model
+    #[serde(default)]
+    pub load_duration: Option<i64>,
+
+    /// Number of input tokens in the prompt
+    #[serde(default)]
+    pub prompt_eval_count: Option<i32>,
+
+    /// Time spent evaluating the prompt
+    #[serde(default)]
+    pub prompt_eval_duration: Option<i64>,
+
+    /// Number of output tokens generated
+    #[serde(default)]
+    pub eval_count: Option<i32>,
+
+    /// Time spent generating output tokens
+    #[serde(default)]
+    pub eval_duration: Option<i64>,
+}
+
+impl EvalMetrics {
+    /// Output tokens per second, if count and duration are known
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        rate(self.eval_count, self.eval_duration)
+    }
+
+    /// Prompt tokens processed per second, if count and duration are known
+    pub fn prompt_tokens_per_second(&self) -> Option<f64> {
+        rate(self.prompt_eval_count, self.prompt_eval_duration)
+    }
+
+    /// Prompt plus output tokens, if either is known; saturates at
+    /// `i32::MAX`
+    pub fn total_tokens(&self) -> Option<i32> {
+        match (self.prompt_eval_count, self.eval_count) {
+            (None, None) => None,
+            (p, e) => Some(p.unwrap_or(0).saturating_add(e.unwrap_or(0))),
+        }
+    }
+
+    /// Returns `true` if no metric is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn rate(count: Option<i32>, duration_ns: Option<i64>) -> Option<f64> {
+    match (count, duration_ns) {
+        (Some(count), Some(ns)) if ns > 0 => Some(count as f64 / (ns as f64 / 1_000_000_000.0)),
+        _ => None,
+    }
+}
+
+impl fmt::Display for EvalMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+
+        if let Some(count) = self.eval_count {
+            let mut part = format!("{} tokens", count);
+            if let Some(ns) = self.eval_duration {
+                part.push_str(&format!(" in {}", humanize::duration_ns(ns)));
+            }
+            if let Some(tps) = self.tokens_per_second() {
+                part.push_str(&format!(" ({:.1} tok/s)", tps));
+            }
+            parts.push(part);
+        }
+        if let Some(count) = self.prompt_eval_count {
+            let mut part = format!("prompt {} tokens", count);
+            if let Some(ns) = self.prompt_eval_duration {
+                part.push_str(&format!(" in {}", humanize::duration_ns(ns)));
+            }
+            parts.push(part);
+        }
+        if let Some(ns) = self.load_duration {
+            parts.push(format!("load {}", humanize::duration_ns(ns)));
+        }
+        if let Some(ns) = self.total_duration {
+            parts.push(format!("total {}", humanize::duration_ns(ns)));
+        }
+
+        if parts.is_empty() {
+            write!(f, "no metrics")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_per_second() {
+        let metrics = EvalMetrics {
+            eval_count: Some(100),
+            eval_duration: Some(2_000_000_000),
+            ..Default::default()
+        };
+        assert_eq!(metrics.tokens_per_second(), Some(50.0));
+    }
+
+    #[test]
+    fn test_tokens_per_second_zero_duration() {
+        let metrics = EvalMetrics {
+            eval_count: Some(100),
+            eval_duration: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(metrics.tokens_per_second(), None);
+    }
+
+    #[test]
+    fn test_total_tokens() {
+        let metrics = EvalMetrics {
+            prompt_eval_count: Some(11),
+            eval_count: Some(18),
+            ..Default::default()
+        };
+        assert_eq!(metrics.total_tokens(), Some(29));
+        assert_eq!(EvalMetrics::default().total_tokens(), None);
+
+        let huge = EvalMetrics {
+            prompt_eval_count: Some(i32::MAX),
+            eval_count: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(huge.total_tokens(), Some(i32::MAX));
+    }
+
+    #[test]
+    fn test_display_empty() {
+        assert!(EvalMetrics::default().is_empty());
+        assert_eq!(EvalMetrics::default().to_string(), "no metrics");
+    }
+
+    #[test]
+    fn test_display_with_load() {
+        let metrics = EvalMetrics {
+            eval_count: Some(3),
+            load_duration: Some(1_500_000_000),
+            ..Default::default()
+        };
+        assert_eq!(metrics.to_string(), "3 tokens, load 1.50s");
+    }
+}
@@ -55,3 +55,22 @@ impl From<serde_json::Value> for FormatSetting {
         Self::Schema(v)
     }
 }
+
+/// Generates either a format string or a small object schema with string properties.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for FormatSetting {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? {
+            return Ok(Self::String(u.arbitrary()?));
+        }
+        let names: Vec<String> = u.arbitrary()?;
+        let properties: serde_json::Map<String, serde_json::Value> = names
+            .into_iter()
+            .map(|name| (name, serde_json::json!({ "type": "string" })))
+            .collect();
+        Ok(Self::Schema(serde_json::json!({
+            "type": "object",
+            "properties": properties,
+        })))
+    }
+}
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::{FormatSetting, KeepAliveSetting, ModelOptions, ThinkSetting};
+use super::request_fingerprint::{self, RequestFingerprint};
+use super::validation;
+use super::{FormatSetting, ImageData, KeepAliveSetting, ModelOptions, ThinkSetting};
+use crate::Result;
 
 /// Request body for POST /api/generate endpoint
 ///
@@ -34,6 +37,7 @@ use super::{FormatSetting, KeepAliveSetting, ModelOptions, ThinkSetting};
 ///     .with_format(FormatSetting::json());
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GenerateRequest {
     /// Name of the model to use
     pub model: String,
@@ -46,9 +50,9 @@ pub struct GenerateRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub suffix: Option<String>,
 
-    /// Base64-encoded images for multimodal models
+    /// Images for multimodal models
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub images: Option<Vec<String>>,
+    pub images: Option<Vec<ImageData>>,
 
     /// Output format (string like "json" or JSON schema object)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -85,6 +89,10 @@ pub struct GenerateRequest {
     /// Number of top log probabilities to return
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_logprobs: Option<i32>,
+
+    /// Context tokens from a previous response, to continue that conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<i64>>,
 }
 
 impl GenerateRequest {
@@ -111,6 +119,7 @@ impl GenerateRequest {
             options: None,
             logprobs: None,
             top_logprobs: None,
+            context: None,
         }
     }
 
@@ -120,8 +129,8 @@ impl GenerateRequest {
         self
     }
 
-    /// Add an image (base64-encoded)
-    pub fn with_image(mut self, image: impl Into<String>) -> Self {
+    /// Add an image (base64 string or raw bytes)
+    pub fn with_image(mut self, image: impl Into<ImageData>) -> Self {
         self.images.get_or_insert_with(Vec::new).push(image.into());
         self
     }
@@ -130,7 +139,7 @@ impl GenerateRequest {
     pub fn with_images<I, S>(mut self, images: I) -> Self
     where
         I: IntoIterator<Item = S>,
-        S: Into<String>,
+        S: Into<ImageData>,
     {
         self.images = Some(images.into_iter().map(|s| s.into()).collect());
         self
@@ -183,4 +192,39 @@ impl GenerateRequest {
         self.top_logprobs = Some(n);
         self
     }
+
+    /// Continue from the `context` returned by a previous response
+    pub fn with_context(mut self, context: Vec<i64>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Check the request for obvious mistakes without contacting the server.
+    ///
+    /// Rejects an empty model name, images sent to a
+    /// [`KnownModel`](crate::KnownModel) without vision support, and invalid
+    /// option values. An empty prompt is allowed, since it loads the model.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`](crate::Error::ValidationError).
+    pub fn validate(&self) -> Result<()> {
+        validation::model_name(&self.model)?;
+        if self.images.as_ref().is_some_and(|i| !i.is_empty()) {
+            validation::images_allowed(&self.model)?;
+        }
+        validation::options(self.options.as_ref())
+    }
+
+    /// A stable hash of what this request asks for, ignoring `stream` and
+    /// `keep_alive`; see [`RequestFingerprint`] for the normalization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if the request cannot be serialized, e.g. an attached image file
+    /// cannot be read.
+    pub fn fingerprint(&self) -> Result<RequestFingerprint> {
+        request_fingerprint::fingerprint("generate", self)
+    }
 }
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::Logprob;
+use super::{CancelReason, DoneReason, EvalMetrics, Logprob};
 
 /// Response from POST /api/generate endpoint
 ///
@@ -47,9 +47,9 @@ pub struct GenerateResponse {
     #[serde(default)]
     pub done: Option<bool>,
 
-    /// Reason the generation stopped (e.g., "stop", "length")
+    /// Reason the generation stopped
     #[serde(default)]
-    pub done_reason: Option<String>,
+    pub done_reason: Option<DoneReason>,
 
     /// Total time spent generating the response in nanoseconds
     #[serde(default)]
@@ -78,6 +78,22 @@ pub struct GenerateResponse {
     /// Log probability information (if logprobs was enabled)
     #[serde(default)]
     pub logprobs: Option<Vec<Logprob>>,
+
+    /// Encoded conversation state; pass back via `GenerateRequest::with_context`
+    /// to continue from this response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<Vec<i64>>,
+
+    /// Why the client ended the stream early, on the terminal event of a
+    /// cancelled stream; never sent by the server
+    #[serde(skip)]
+    pub cancel_reason: Option<CancelReason>,
+
+    /// Whether `eval_count` and `prompt_eval_count` are client-side
+    /// estimates rather than the server's counts, as on the terminal event
+    /// of a cancelled stream; never sent by the server
+    #[serde(skip)]
+    pub counts_estimated: bool,
 }
 
 impl GenerateResponse {
@@ -116,6 +132,18 @@ impl GenerateResponse {
         self.eval_duration.map(|ns| ns as f64 / 1_000_000.0)
     }
 
+    /// Get the timing and token usage metrics as one value
+    pub fn metrics(&self) -> EvalMetrics {
+        EvalMetrics {
+            total_duration: self.total_duration,
+            load_duration: self.load_duration,
+            prompt_eval_count: self.prompt_eval_count,
+            prompt_eval_duration: self.prompt_eval_duration,
+            eval_count: self.eval_count,
+            eval_duration: self.eval_duration,
+        }
+    }
+
     /// Calculate tokens per second for generation
     pub fn tokens_per_second(&self) -> Option<f64> {
         match (self.eval_count, self.eval_duration) {
@@ -0,0 +1,163 @@
+//! Resumable state for multi-step generation jobs
+
+use serde::{Deserialize, Serialize};
+
+use super::{GenerateRequest, GenerateResponse, ModelOptions};
+
+/// Everything needed to continue a generation after a restart.
+///
+/// Captures the model, options, system prompt, last prompt, and the
+/// `context` tokens returned by the server. Serialize it with serde after
+/// each step; after a crash, deserialize it and call
+/// [`next_request`](Self::next_request) to pick up where the job left off.
+///
+/// # Example
+///
+/// ```no_run
+/// use ollama_oxide::{GenerateRequest, GenerationCheckpoint, OllamaApiAsync, OllamaClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = OllamaClient::default()?;
+/// let path = "job.checkpoint.json";
+///
+/// let mut checkpoint = match std::fs::read_to_string(path) {
+///     Ok(json) => serde_json::from_str(&json)?,
+///     Err(_) => GenerationCheckpoint::new(&GenerateRequest::new("qwen3:0.6b", "")),
+/// };
+///
+/// for step in ["Outline a story.", "Write chapter one.", "Write chapter two."]
+///     .iter()
+///     .skip(checkpoint.step as usize)
+/// {
+///     let request = checkpoint.next_request(*step);
+///     let response = client.generate(&request).await?;
+///     checkpoint.record(&request, &response);
+///     std::fs::write(path, serde_json::to_string(&checkpoint)?)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct GenerationCheckpoint {
+    /// Model used for every step
+    pub model: String,
+
+    /// Runtime options used for every step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<ModelOptions>,
+
+    /// System prompt used for every step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+
+    /// Prompt of the last completed step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Context tokens returned by the last completed step
+    #[serde(default)]
+    pub context: Vec<i64>,
+
+    /// Text generated by the last completed step
+    #[serde(default)]
+    pub last_output: String,
+
+    /// Number of completed steps
+    #[serde(default)]
+    pub step: u32,
+}
+
+impl GenerationCheckpoint {
+    /// Start a checkpoint from the settings of `request` (no steps completed).
+    pub fn new(request: &GenerateRequest) -> Self {
+        Self {
+            model: request.model.clone(),
+            options: request.options.clone(),
+            system: request.system.clone(),
+            context: request.context.clone().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Build a checkpoint from a completed request/response pair.
+    pub fn from_exchange(request: &GenerateRequest, response: &GenerateResponse) -> Self {
+        let mut checkpoint = Self::new(request);
+        checkpoint.record(request, response);
+        checkpoint
+    }
+
+    /// Record a completed step.
+    ///
+    /// Keeps the previous context if the response carried none.
+    pub fn record(&mut self, request: &GenerateRequest, response: &GenerateResponse) {
+        self.prompt = request.prompt.clone();
+        if let Some(context) = &response.context {
+            self.context = context.clone();
+        }
+        self.last_output = response.text().unwrap_or_default().to_string();
+        self.step += 1;
+    }
+
+    /// Build the request for the next step, continuing from the saved context.
+    pub fn next_request(&self, prompt: impl Into<String>) -> GenerateRequest {
+        let mut request = GenerateRequest::new(self.model.clone(), prompt);
+        request.options = self.options.clone();
+        request.system = self.system.clone();
+        if !self.context.is_empty() {
+            request.context = Some(self.context.clone());
+        }
+        request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_resume() {
+        let request = GenerateRequest::new("qwen3:0.6b", "Step one")
+            .with_options(ModelOptions::default().with_temperature(0.2));
+        let response = GenerateResponse {
+            response: Some("Done one.".to_string()),
+            context: Some(vec![1, 2, 3]),
+            ..Default::default()
+        };
+
+        let checkpoint = GenerationCheckpoint::from_exchange(&request, &response);
+        assert_eq!(checkpoint.step, 1);
+        assert_eq!(checkpoint.last_output, "Done one.");
+
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let restored: GenerationCheckpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, checkpoint);
+
+        let next = restored.next_request("Step two");
+        assert_eq!(next.model, "qwen3:0.6b");
+        assert_eq!(next.context, Some(vec![1, 2, 3]));
+        assert_eq!(next.options, request.options);
+        assert_eq!(next.prompt.as_deref(), Some("Step two"));
+    }
+
+    #[test]
+    fn test_record_keeps_context_when_missing() {
+        let request = GenerateRequest::new("m", "p");
+        let mut checkpoint = GenerationCheckpoint::from_exchange(
+            &request,
+            &GenerateResponse {
+                context: Some(vec![9]),
+                ..Default::default()
+            },
+        );
+        checkpoint.record(&request, &GenerateResponse::default());
+
+        assert_eq!(checkpoint.context, vec![9]);
+        assert_eq!(checkpoint.step, 2);
+    }
+
+    #[test]
+    fn test_fresh_checkpoint_sends_no_context() {
+        let checkpoint = GenerationCheckpoint::new(&GenerateRequest::new("m", ""));
+        assert_eq!(checkpoint.next_request("hi").context, None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Human-readable formatting helpers shared by `Display` implementations
+
+/// Formats a byte count with decimal units, as the Ollama CLI does (e.g. `3.3 GB`).
+#[cfg_attr(not(feature = "model"), allow(dead_code))]
+pub(crate) fn bytes(n: u64) -> String {
+    const UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+    if n < 1000 {
+        return format!("{} B", n);
+    }
+    let mut value = n as f64;
+    let mut unit = "B";
+    for u in UNITS {
+        // Step up at 999.95 as well, which would round to "1000.0".
+        if value < 999.95 {
+            break;
+        }
+        value /= 1000.0;
+        unit = u;
+    }
+    format!("{:.1} {}", value, unit)
+}
+
+/// Formats a nanosecond duration with an appropriate unit (e.g. `52.5ms`, `1.75s`).
+///
+/// Each unit starts where the one below would round up to its limit, so
+/// 999.96ms prints as `1.00s` rather than `1000.0ms`.
+pub(crate) fn duration_ns(ns: i64) -> String {
+    let abs = ns.unsigned_abs() as f64;
+    let sign = if ns < 0 { "-" } else { "" };
+    if abs >= 59_995_000_000.0 {
+        let total_secs = (abs / 1_000_000_000.0).round() as u64;
+        format!("{}{}m{}s", sign, total_secs / 60, total_secs % 60)
+    } else if abs >= 999_950_000.0 {
+        format!("{}{:.2}s", sign, abs / 1_000_000_000.0)
+    } else if abs >= 999_950.0 {
+        format!("{}{:.1}ms", sign, abs / 1_000_000.0)
+    } else if abs >= 1_000.0 {
+        format!("{}{:.1}µs", sign, abs / 1_000.0)
+    } else {
+        format!("{}{}ns", sign, abs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(999), "999 B");
+        assert_eq!(bytes(1_500), "1.5 KB");
+        assert_eq!(bytes(3_338_801_804), "3.3 GB");
+        assert_eq!(bytes(2_000_000_000_000), "2.0 TB");
+    }
+
+    #[test]
+    fn test_bytes_rounding_carries_into_next_unit() {
+        assert_eq!(bytes(999_949), "999.9 KB");
+        assert_eq!(bytes(999_950), "1.0 MB");
+        assert_eq!(bytes(999_999_999), "1.0 GB");
+    }
+
+    #[test]
+    fn test_duration_ns() {
+        assert_eq!(duration_ns(500), "500ns");
+        assert_eq!(duration_ns(1_500), "1.5µs");
+        assert_eq!(duration_ns(52_479_709), "52.5ms");
+        assert_eq!(duration_ns(1_750_000_000), "1.75s");
+        assert_eq!(duration_ns(125_000_000_000), "2m5s");
+        assert_eq!(duration_ns(-2_000_000), "-2.0ms");
+    }
+
+    #[test]
+    fn test_duration_ns_rounding_carries_into_next_unit() {
+        assert_eq!(duration_ns(119_700_000_000), "2m0s");
+        assert_eq!(duration_ns(119_400_000_000), "1m59s");
+        assert_eq!(duration_ns(59_996_000_000), "1m0s");
+        assert_eq!(duration_ns(59_990_000_000), "59.99s");
+        assert_eq!(duration_ns(999_960_000), "1.00s");
+        assert_eq!(duration_ns(999_960), "1.0ms");
+        assert_eq!(duration_ns(999_940), "999.9µs");
+    }
+}
@@ -0,0 +1,360 @@
+//! Image payload primitive type
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bytes::Bytes;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Input bytes per encoded chunk; a multiple of 3 so chunks need no padding.
+const CHUNK: usize = 3 * 1024;
+
+/// An image attached to a request: already base64-encoded, raw bytes, a
+/// file, or a reader.
+///
+/// Everything but [`ImageData::Base64`] is base64-encoded with a SIMD
+/// encoder while the request is serialized, straight into the request body.
+/// No intermediate base64 `String` is built, and files are streamed in
+/// small chunks. A reader is drained into memory the first time it is
+/// encoded, so the request can be encoded again, e.g. when it is retried
+/// or its fingerprint is taken.
+///
+/// The async client encodes requests carrying files or readers on a
+/// blocking thread, so no file I/O runs on the async runtime.
+///
+/// Strings convert into [`ImageData::Base64`], so existing calls such as
+/// `with_image("iVBORw0...")` keep working.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatMessage, ImageData};
+///
+/// let png = vec![0x89, b'P', b'N', b'G'];
+/// let message = ChatMessage::user("Describe this.")
+///     .with_image(ImageData::from_bytes(png))
+///     .with_image(ImageData::from_path("photos/cat.jpg"));
+/// assert_eq!(message.images.unwrap()[0], "iVBORw==");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageData {
+    /// Base64-encoded image data, sent as-is
+    Base64(String),
+
+    /// Raw image bytes, encoded as the request is serialized
+    Bytes(Bytes),
+
+    /// An image file, read and encoded as the request is serialized
+    File(PathBuf),
+
+    /// A reader, drained the first time the request is serialized
+    Reader(ImageReader),
+}
+
+/// An image source, created by [`ImageData::from_reader`].
+///
+/// The reader is drained the first time the image is encoded and its bytes
+/// kept, so later encodings reuse them. Clones share the same reader and
+/// compare equal only to each other.
+#[derive(Clone)]
+pub struct ImageReader(Arc<Mutex<ReaderState>>);
+
+enum ReaderState {
+    /// Not read yet
+    Pending(Box<dyn Read + Send>),
+    /// Drained into memory
+    Buffered(Bytes),
+    /// Reading failed, so the bytes are lost
+    Failed,
+}
+
+impl ImageReader {
+    /// The reader's bytes, draining it on first use.
+    fn bytes(&self) -> io::Result<Bytes> {
+        let mut state = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match mem::replace(&mut *state, ReaderState::Failed) {
+            ReaderState::Pending(mut reader) => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes)?;
+                let bytes = Bytes::from(bytes);
+                *state = ReaderState::Buffered(bytes.clone());
+                Ok(bytes)
+            }
+            ReaderState::Buffered(bytes) => {
+                *state = ReaderState::Buffered(bytes.clone());
+                Ok(bytes)
+            }
+            ReaderState::Failed => Err(io::Error::other("image reader failed on an earlier read")),
+        }
+    }
+}
+
+impl fmt::Debug for ImageReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ImageReader(..)")
+    }
+}
+
+impl PartialEq for ImageReader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ImageReader {}
+
+impl ImageData {
+    /// Create image data from raw bytes
+    pub fn from_bytes(bytes: impl Into<Bytes>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+
+    /// Attach an image file; it is read when the request is serialized
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        Self::File(path.into())
+    }
+
+    /// Attach an image from a reader; it is drained the first time the
+    /// request is serialized
+    pub fn from_reader(reader: impl Read + Send + 'static) -> Self {
+        let state = ReaderState::Pending(Box::new(reader));
+        Self::Reader(ImageReader(Arc::new(Mutex::new(state))))
+    }
+
+    /// Get the base64 form, encoding bytes, files, and readers as needed
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if a file or reader cannot be read.
+    pub fn to_base64(&self) -> io::Result<Cow<'_, str>> {
+        match self {
+            Self::Base64(data) => Ok(Cow::Borrowed(data)),
+            Self::Bytes(bytes) => Ok(Cow::Owned(base64_simd::STANDARD.encode_to_string(bytes))),
+            Self::File(path) => Ok(Cow::Owned(
+                base64_simd::STANDARD.encode_to_string(std::fs::read(path)?),
+            )),
+            Self::Reader(reader) => Ok(Cow::Owned(
+                base64_simd::STANDARD.encode_to_string(reader.bytes()?),
+            )),
+        }
+    }
+
+    /// Length of the base64 form in bytes
+    ///
+    /// Returns `None` for readers and for files whose size cannot be read.
+    pub fn encoded_len(&self) -> Option<usize> {
+        let raw_len = match self {
+            Self::Base64(data) => return Some(data.len()),
+            Self::Bytes(bytes) => bytes.len(),
+            Self::File(path) => usize::try_from(std::fs::metadata(path).ok()?.len()).ok()?,
+            Self::Reader(_) => return None,
+        };
+        Some(base64_simd::STANDARD.encoded_length(raw_len))
+    }
+}
+
+impl From<String> for ImageData {
+    fn from(data: String) -> Self {
+        Self::Base64(data)
+    }
+}
+
+impl From<&str> for ImageData {
+    fn from(data: &str) -> Self {
+        Self::Base64(data.to_string())
+    }
+}
+
+impl From<Bytes> for ImageData {
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for ImageData {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+/// Compares by base64 form, so raw bytes equal their encoding. Files and
+/// readers never equal a string.
+impl PartialEq<str> for ImageData {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Self::Base64(data) => data == other,
+            Self::Bytes(bytes) => base64_simd::STANDARD.encode_to_string(bytes) == other,
+            Self::File(_) | Self::Reader(_) => false,
+        }
+    }
+}
+
+impl PartialEq<&str> for ImageData {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for ImageData {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Serialize for ImageData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let reader: Box<dyn Read> = match self {
+            Self::Base64(data) => return serializer.serialize_str(data),
+            Self::Bytes(bytes) => return serializer.collect_str(&Base64Display(bytes)),
+            Self::File(path) => Box::new(File::open(path).map_err(|e| {
+                S::Error::custom(format_args!("cannot open image {}: {e}", path.display()))
+            })?),
+            Self::Reader(reader) => {
+                let bytes = reader
+                    .bytes()
+                    .map_err(|e| S::Error::custom(format_args!("cannot read image: {e}")))?;
+                return serializer.collect_str(&Base64Display(&bytes));
+            }
+        };
+        let display = ReaderDisplay {
+            reader: RefCell::new(reader),
+            error: RefCell::new(None),
+        };
+        serializer.collect_str(&display).map_err(|e| {
+            display.error.into_inner().map_or(e, |io| {
+                S::Error::custom(format_args!("cannot read image: {io}"))
+            })
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::Base64)
+    }
+}
+
+/// Generates base64 strings or raw bytes.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ImageData {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let bytes: Vec<u8> = u.arbitrary()?;
+        if u.arbitrary()? {
+            return Ok(Self::Base64(base64_simd::STANDARD.encode_to_string(bytes)));
+        }
+        Ok(Self::Bytes(bytes.into()))
+    }
+}
+
+/// Writes base64 in fixed-size chunks through a stack buffer, so
+/// `collect_str` streams it into the serializer without allocating.
+struct Base64Display<'a>(&'a [u8]);
+
+impl fmt::Display for Base64Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = [0u8; CHUNK / 3 * 4];
+        for chunk in self.0.chunks(CHUNK) {
+            write_chunk(chunk, &mut buffer, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Like [`Base64Display`], but pulls chunks from a reader. An I/O error is
+/// parked in `error` so the serializer can report it instead of a bare
+/// formatting error.
+struct ReaderDisplay {
+    reader: RefCell<Box<dyn Read>>,
+    error: RefCell<Option<io::Error>>,
+}
+
+impl fmt::Display for ReaderDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut reader = self.reader.borrow_mut();
+        let mut input = [0u8; CHUNK];
+        let mut buffer = [0u8; CHUNK / 3 * 4];
+        loop {
+            let filled = fill(&mut **reader, &mut input).map_err(|e| {
+                *self.error.borrow_mut() = Some(e);
+                fmt::Error
+            })?;
+            write_chunk(&input[..filled], &mut buffer, f)?;
+            if filled < CHUNK {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Read until `buf` is full or the reader ends, so only the last chunk can
+/// need padding.
+fn fill(reader: &mut dyn Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn write_chunk(chunk: &[u8], buffer: &mut [u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let encoded = base64_simd::STANDARD.encode_as_str(chunk, base64_simd::Out::from_slice(buffer));
+    f.write_str(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_serialize_as_base64() {
+        let image = ImageData::from_bytes(&b"hello"[..]);
+        assert_eq!(serde_json::to_string(&image).unwrap(), r#""aGVsbG8=""#);
+    }
+
+    #[test]
+    fn test_chunked_encoding_matches_one_shot() {
+        let bytes: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let image = ImageData::from_bytes(bytes.clone());
+        let json = serde_json::to_string(&image).unwrap();
+        let expected = base64_simd::STANDARD.encode_to_string(&bytes);
+        assert_eq!(json, format!("\"{expected}\""));
+        assert_eq!(image.encoded_len(), Some(expected.len()));
+    }
+
+    #[test]
+    fn test_reader_is_buffered_for_later_encodings() {
+        let bytes: Vec<u8> = (0..10_000u32).map(|i| (i % 7) as u8).collect();
+        let image = ImageData::from_reader(io::Cursor::new(bytes.clone()));
+        let json = serde_json::to_string(&image).unwrap();
+        let expected = base64_simd::STANDARD.encode_to_string(&bytes);
+        assert_eq!(json, format!("\"{expected}\""));
+        assert_eq!(serde_json::to_string(&image.clone()).unwrap(), json);
+        assert_eq!(image.to_base64().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_missing_file_fails_serialization() {
+        let image = ImageData::from_path("/nonexistent/image.png");
+        let err = serde_json::to_string(&image).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/image.png"));
+    }
+
+    #[test]
+    fn test_deserialize_as_base64() {
+        let image: ImageData = serde_json::from_str(r#""aGVsbG8=""#).unwrap();
+        assert_eq!(image, ImageData::Base64("aGVsbG8=".to_string()));
+        assert_eq!(image, "aGVsbG8=");
+    }
+}
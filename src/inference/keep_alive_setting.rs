@@ -1,7 +1,11 @@
 //! Keep alive setting primitive type
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::server_time;
+
 /// Keep alive setting for model caching
 ///
 /// Controls how long the model stays loaded in memory.
@@ -17,6 +21,7 @@ use serde::{Deserialize, Serialize};
 /// let unload = KeepAliveSetting::unload_immediately();
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum KeepAliveSetting {
     /// Duration string (e.g., "5m", "1h", "30s")
@@ -40,6 +45,30 @@ impl KeepAliveSetting {
     pub fn unload_immediately() -> Self {
         Self::Seconds(0)
     }
+
+    /// How long the model stays loaded, parsing Go duration strings such
+    /// as `"4m0s"`, `"1h30m"`, or `"500ms"`.
+    ///
+    /// Returns `None` for a negative value, which keeps the model loaded
+    /// indefinitely, and for a string that is not a duration; the raw
+    /// string stays in [`Duration`](Self::Duration).
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ollama_oxide::KeepAliveSetting;
+    ///
+    /// let keep_alive = KeepAliveSetting::duration("4m0s");
+    /// assert_eq!(keep_alive.as_duration(), Some(Duration::from_secs(240)));
+    /// assert_eq!(KeepAliveSetting::seconds(-1).as_duration(), None);
+    /// ```
+    pub fn as_duration(&self) -> Option<Duration> {
+        let nanos = match self {
+            Self::Duration(d) => server_time::go_duration_ns(d)?,
+            Self::Seconds(s) => i128::from(*s) * 1_000_000_000,
+        };
+        let nanos = u64::try_from(nanos).ok()?;
+        Some(Duration::from_nanos(nanos))
+    }
 }
 
 impl From<&str> for KeepAliveSetting {
@@ -53,3 +82,22 @@ impl From<i64> for KeepAliveSetting {
         Self::Seconds(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_duration() {
+        let d = |s: &str| KeepAliveSetting::duration(s).as_duration();
+        assert_eq!(d("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(d("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(d("300"), Some(Duration::from_secs(300)));
+        assert_eq!(d("-1m"), None);
+        assert_eq!(d("forever"), None);
+        assert_eq!(
+            KeepAliveSetting::unload_immediately().as_duration(),
+            Some(Duration::ZERO)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Conditions on the metadata of vector index entries
+
+use serde_json::Value;
+
+/// A condition on an entry's metadata, for
+/// [`VectorIndex::search_filtered`](super::VectorIndex::search_filtered).
+///
+/// Metadata is matched in its serde JSON form. Field names may be dotted
+/// paths into nested objects (`"doc.lang"`); a missing field matches no
+/// comparison, only [`missing`](Self::missing). Combine conditions with
+/// [`and`](Self::and), [`or`](Self::or), and `!`.
+///
+/// # Example
+///
+/// ```
+/// use ollama_oxide::MetadataFilter;
+///
+/// // source == "wiki" && lang == "en"
+/// let filter = MetadataFilter::eq("source", "wiki").and(MetadataFilter::eq("lang", "en"));
+///
+/// assert!(filter.matches(&serde_json::json!({"source": "wiki", "lang": "en"})));
+/// assert!(!filter.matches(&serde_json::json!({"source": "wiki", "lang": "de"})));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataFilter {
+    /// The field equals the value
+    Eq(String, Value),
+    /// The field is one of the values
+    In(String, Vec<Value>),
+    /// The field is a number greater than or equal to the bound
+    Gte(String, f64),
+    /// The field is a number less than or equal to the bound
+    Lte(String, f64),
+    /// The field is present, even if `null`
+    Exists(String),
+    /// Every condition holds; true when empty
+    All(Vec<MetadataFilter>),
+    /// At least one condition holds; false when empty
+    Any(Vec<MetadataFilter>),
+    /// The condition does not hold
+    Not(Box<MetadataFilter>),
+}
+
+impl MetadataFilter {
+    /// `field == value`
+    pub fn eq(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::Eq(field.into(), value.into())
+    }
+
+    /// `field != value`, also true when the field is missing
+    pub fn ne(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        !Self::eq(field, value)
+    }
+
+    /// `field` equals one of `values`
+    pub fn one_of<V: Into<Value>>(
+        field: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        Self::In(field.into(), values.into_iter().map(Into::into).collect())
+    }
+
+    /// `field >= bound`
+    pub fn gte(field: impl Into<String>, bound: f64) -> Self {
+        Self::Gte(field.into(), bound)
+    }
+
+    /// `field <= bound`
+    pub fn lte(field: impl Into<String>, bound: f64) -> Self {
+        Self::Lte(field.into(), bound)
+    }
+
+    /// `field` is present
+    pub fn exists(field: impl Into<String>) -> Self {
+        Self::Exists(field.into())
+    }
+
+    /// `field` is absent
+    pub fn missing(field: impl Into<String>) -> Self {
+        !Self::exists(field)
+    }
+
+    /// Both this and `other` hold.
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All(mut all) => {
+                all.push(other);
+                Self::All(all)
+            }
+            this => Self::All(vec![this, other]),
+        }
+    }
+
+    /// This or `other` holds.
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Any(mut any) => {
+                any.push(other);
+                Self::Any(any)
+            }
+            this => Self::Any(vec![this, other]),
+        }
+    }
+
+    /// Whether `metadata` satisfies the condition
+    pub fn matches(&self, metadata: &Value) -> bool {
+        match self {
+            Self::Eq(field, value) => lookup(metadata, field) == Some(value),
+            Self::In(field, values) => lookup(metadata, field).is_some_and(|v| values.contains(v)),
+            Self::Gte(field, bound) => number(metadata, field).is_some_and(|n| n >= *bound),
+            Self::Lte(field, bound) => number(metadata, field).is_some_and(|n| n <= *bound),
+            Self::Exists(field) => lookup(metadata, field).is_some(),
+            Self::All(all) => all.iter().all(|f| f.matches(metadata)),
+            Self::Any(any) => any.iter().any(|f| f.matches(metadata)),
+            Self::Not(inner) => !inner.matches(metadata),
+        }
+    }
+}
+
+impl std::ops::Not for MetadataFilter {
+    type Output = Self;
+
+    /// The condition does not hold.
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// The value at dotted `path` in `metadata`
+fn lookup<'a>(metadata: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(metadata, |value, key| value.as_object()?.get(key))
+}
+
+fn number(metadata: &Value, path: &str) -> Option<f64> {
+    lookup(metadata, path)?.as_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_nested_paths_and_missing_fields() {
+        let meta = json!({"doc": {"lang": "en"}, "year": 2021});
+        assert!(MetadataFilter::eq("doc.lang", "en").matches(&meta));
+        assert!(!MetadataFilter::eq("doc.title", "x").matches(&meta));
+        assert!(MetadataFilter::ne("doc.title", "x").matches(&meta));
+        assert!(MetadataFilter::missing("doc.title").matches(&meta));
+        assert!(!MetadataFilter::exists("year.month").matches(&meta));
+    }
+
+    #[test]
+    fn test_ranges_and_sets() {
+        let meta = json!({"year": 2021, "tag": "rust"});
+        assert!(MetadataFilter::gte("year", 2020.0).matches(&meta));
+        assert!(!MetadataFilter::lte("year", 2020.0).matches(&meta));
+        assert!(!MetadataFilter::gte("tag", 0.0).matches(&meta));
+        assert!(MetadataFilter::one_of("tag", ["go", "rust"]).matches(&meta));
+    }
+
+    #[test]
+    fn test_combinators_flatten() {
+        let filter = MetadataFilter::eq("a", 1)
+            .and(MetadataFilter::eq("b", 2))
+            .and(MetadataFilter::eq("c", 3));
+        assert!(matches!(&filter, MetadataFilter::All(all) if all.len() == 3));
+
+        let either = MetadataFilter::eq("a", 1).or(MetadataFilter::eq("a", 2));
+        assert!(either.matches(&json!({"a": 2})));
+        assert!(!either.matches(&json!({"a": 3})));
+        assert!(MetadataFilter::All(vec![]).matches(&json!({})));
+        assert!(!MetadataFilter::Any(vec![]).matches(&json!({})));
+    }
+}
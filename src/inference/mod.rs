@@ -4,45 +4,77 @@
 //! chat completions, text generation, and embeddings.
 
 // Chat types
+mod cancel_reason;
 mod chat_message;
 mod chat_request;
 mod chat_response;
 mod chat_role;
+mod content_part;
+mod done_reason;
+mod response_event;
 mod response_message;
 
 // Inference types
+mod binary_embedding;
 mod embed_input;
 mod embed_request;
 mod embed_response;
+mod embedding_float;
+mod embedding_space;
+mod eval_metrics;
 mod format_setting;
 mod generate_request;
 mod generate_response;
+mod generation_checkpoint;
+pub(crate) mod humanize;
+mod image_data;
 mod keep_alive_setting;
 mod logprob;
+mod metadata_filter;
 mod model_options;
+mod request_fingerprint;
+mod space_vector;
+mod sparse_embedding;
 mod stop_setting;
 mod think_setting;
 mod token_logprob;
+mod validation;
+mod vector_index;
 mod version;
 
 // Chat types re-exports
+pub use cancel_reason::CancelReason;
 pub use chat_message::ChatMessage;
 pub use chat_request::ChatRequest;
 pub use chat_response::ChatResponse;
 pub use chat_role::ChatRole;
+pub use content_part::ContentPart;
+pub use done_reason::DoneReason;
+pub use response_event::ResponseEvent;
 pub use response_message::ResponseMessage;
 
 // Inference types re-exports
+pub use binary_embedding::BinaryEmbedding;
 pub use embed_input::EmbedInput;
 pub use embed_request::EmbedRequest;
 pub use embed_response::EmbedResponse;
+pub use embedding_float::EmbeddingFloat;
+pub use embedding_space::EmbeddingSpace;
+pub use eval_metrics::EvalMetrics;
 pub use format_setting::FormatSetting;
 pub use generate_request::GenerateRequest;
 pub use generate_response::GenerateResponse;
+pub use generation_checkpoint::GenerationCheckpoint;
+pub use image_data::{ImageData, ImageReader};
 pub use keep_alive_setting::KeepAliveSetting;
 pub use logprob::Logprob;
+pub use metadata_filter::MetadataFilter;
 pub use model_options::ModelOptions;
+pub use request_fingerprint::RequestFingerprint;
+pub use space_vector::SpaceVector;
+pub use sparse_embedding::SparseEmbedding;
 pub use stop_setting::StopSetting;
 pub use think_setting::ThinkSetting;
 pub use token_logprob::TokenLogprob;
+pub use vector_index::{SearchHit, VectorIndex};
 pub use version::VersionResponse;
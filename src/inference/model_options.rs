@@ -19,6 +19,7 @@ use super::StopSetting;
 ///     .with_num_ctx(4096);
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ModelOptions {
     /// Random seed for reproducible outputs
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,234 @@
+//! Stable hashes of normalized inference requests
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Names the normalization and hash below. Any change to either must bump
+/// it, so fingerprints from different schemes never compare equal.
+const SCHEME: &[u8] = b"ollama-oxide/request-fingerprint/v1";
+
+/// Request fields that change how a response is delivered but not what it
+/// says.
+const DELIVERY_FIELDS: [&str; 2] = ["stream", "keep_alive"];
+
+const FNV_OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const FNV_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// A 128-bit hash identifying what a request asks for, from
+/// [`ChatRequest::fingerprint`](super::ChatRequest::fingerprint),
+/// [`GenerateRequest::fingerprint`](super::GenerateRequest::fingerprint), or
+/// [`EmbedRequest::fingerprint`](super::EmbedRequest::fingerprint).
+///
+/// Two requests share a fingerprint when they would send the server the
+/// same content. Before hashing, a request is normalized:
+///
+/// - `stream` and `keep_alive` are ignored, since they only change how and
+///   for how long the answer is delivered
+/// - a model name without a tag gets `:latest`, as the server assumes
+/// - object keys are sorted, unset fields are skipped, and whole-number
+///   floats are written as integers
+///
+/// The request kind is hashed too, so a chat and a generate request never
+/// collide. The hash is 128-bit FNV-1a over that canonical form. Both are
+/// fixed: a given request has the same fingerprint on every platform, in
+/// every process, and in every version of this crate, which makes it
+/// suitable as a key for response caches, request coalescing, and replay
+/// logs that outlive the program. The [`Display`](fmt::Display) form is 32
+/// lowercase hex digits, and parses back with [`str::parse`].
+///
+/// Computing one serializes the request, so image files are read and
+/// image readers drained (a drained reader keeps its bytes for the actual
+/// request); a request that cannot be serialized has no fingerprint.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatMessage, ChatRequest, RequestFingerprint};
+///
+/// let a = ChatRequest::new("qwen3", [ChatMessage::user("Hi")]);
+/// let b = ChatRequest::new("qwen3:latest", [ChatMessage::user("Hi")]).with_stream(true);
+/// assert_eq!(a.fingerprint()?, b.fingerprint()?);
+///
+/// let key = a.fingerprint()?.to_string();
+/// assert_eq!(key.parse::<RequestFingerprint>()?, a.fingerprint()?);
+/// # Ok::<(), ollama_oxide::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestFingerprint(u128);
+
+impl RequestFingerprint {
+    /// The hash as a number
+    pub fn as_u128(self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for RequestFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl FromStr for RequestFingerprint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 32 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::ValidationError(format!(
+                "request fingerprint must be 32 hex digits, got {s:?}"
+            )));
+        }
+        u128::from_str_radix(s, 16)
+            .map(Self)
+            .map_err(|e| Error::ValidationError(format!("invalid request fingerprint: {e}")))
+    }
+}
+
+/// Incremental 128-bit FNV-1a
+struct Fnv(u128);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u128::from(b);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// `name` with the default `:latest` tag if it has none.
+fn with_tag(name: &str) -> String {
+    let base = name.rsplit('/').next().unwrap_or(name);
+    if base.contains(':') {
+        name.to_string()
+    } else {
+        format!("{name}:latest")
+    }
+}
+
+/// Writes `value` in canonical form: sorted keys, no nulls, and integral
+/// floats as integers.
+fn write_canonical(value: &Value, out: &mut Fnv) {
+    match value {
+        Value::Null => out.write(b"null"),
+        Value::Bool(b) => out.write(if *b { b"true" } else { b"false" }),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 9.0e15 => {
+                out.write((f as i64).to_string().as_bytes());
+            }
+            _ => out.write(n.to_string().as_bytes()),
+        },
+        Value::String(s) => out.write(Value::String(s.clone()).to_string().as_bytes()),
+        Value::Array(items) => {
+            out.write(b"[");
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.write(b",");
+                }
+                write_canonical(item, out);
+            }
+            out.write(b"]");
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().filter(|(_, v)| !v.is_null()).collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.write(b"{");
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.write(b",");
+                }
+                out.write(Value::String(key.clone()).to_string().as_bytes());
+                out.write(b":");
+                write_canonical(value, out);
+            }
+            out.write(b"}");
+        }
+    }
+}
+
+/// Fingerprint of a request of `kind` (`"chat"`, `"generate"`, ...).
+pub(super) fn fingerprint(kind: &str, request: &impl Serialize) -> Result<RequestFingerprint> {
+    let mut value = serde_json::to_value(request)?;
+    if let Value::Object(map) = &mut value {
+        for field in DELIVERY_FIELDS {
+            map.remove(field);
+        }
+        if let Some(Value::String(model)) = map.get_mut("model") {
+            *model = with_tag(model);
+        }
+    }
+
+    let mut hash = Fnv(FNV_OFFSET);
+    hash.write(SCHEME);
+    hash.write(b"\n");
+    hash.write(kind.as_bytes());
+    hash.write(b"\n");
+    write_canonical(&value, &mut hash);
+    Ok(RequestFingerprint(hash.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn of(value: Value) -> RequestFingerprint {
+        fingerprint("chat", &value).unwrap()
+    }
+
+    #[test]
+    fn test_fnv_reference_value() {
+        // FNV-1a 128 of "a", from the reference test vectors
+        let mut hash = Fnv(FNV_OFFSET);
+        hash.write(b"a");
+        assert_eq!(hash.0, 0xd228cb696f1a8caf78912b704e4a8964);
+    }
+
+    #[test]
+    fn test_fingerprint_is_pinned() {
+        // Changing this value breaks every stored fingerprint; bump SCHEME
+        // instead of updating it.
+        let request = json!({ "model": "qwen3:latest" });
+        assert_eq!(of(request).to_string(), "947813cc9fa57340b5cedffbc9a181c2");
+    }
+
+    #[test]
+    fn test_normalization() {
+        let base = json!({ "model": "library/qwen3", "options": { "temperature": 1.0 } });
+        assert_eq!(
+            of(base.clone()),
+            of(json!({
+                "options": { "temperature": 1, "seed": null },
+                "model": "library/qwen3:latest",
+                "stream": true,
+                "keep_alive": "5m"
+            }))
+        );
+        assert_ne!(of(base.clone()), fingerprint("generate", &base).unwrap());
+        assert_ne!(
+            of(base),
+            of(json!({ "model": "library/qwen3", "options": { "temperature": 0.5 } }))
+        );
+    }
+
+    #[test]
+    fn test_tag_defaults_only_when_missing() {
+        assert_eq!(with_tag("qwen3"), "qwen3:latest");
+        assert_eq!(with_tag("qwen3:0.6b"), "qwen3:0.6b");
+        assert_eq!(with_tag("host:5000/ns/model"), "host:5000/ns/model:latest");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let fp = of(json!({ "model": "qwen3" }));
+        assert_eq!(fp.to_string().len(), 32);
+        assert_eq!(fp.to_string().parse::<RequestFingerprint>().unwrap(), fp);
+        assert!("xyz".parse::<RequestFingerprint>().is_err());
+        assert!("+".repeat(32).parse::<RequestFingerprint>().is_err());
+    }
+}
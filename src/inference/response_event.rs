@@ -0,0 +1,165 @@
+//! One kind of information from a streamed chat response
+
+use super::{ChatResponse, DoneReason, EvalMetrics};
+#[cfg(feature = "tools")]
+use crate::tools::ToolCall;
+
+/// A single piece of a streamed chat response.
+///
+/// A raw [`ChatResponse`] chunk can carry text, thinking, tool calls, and
+/// final metrics at once, each in an optional field.
+/// [`ChatResponse::into_events`] splits a chunk into these events so a
+/// consumer writes one `match` instead of probing fields.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatResponse, ResponseEvent};
+///
+/// let chunk: ChatResponse = serde_json::from_str(
+///     r#"{"message":{"role":"assistant","content":"Hi"},"done":true,"done_reason":"stop","eval_count":1}"#,
+/// )
+/// .unwrap();
+///
+/// for event in chunk.into_events() {
+///     match event {
+///         ResponseEvent::TextDelta(text) => print!("{text}"),
+///         ResponseEvent::ThinkingDelta(_) => {}
+///         ResponseEvent::UsageUpdate { completion_tokens, .. } => {
+///             assert_eq!(completion_tokens, Some(1));
+///         }
+///         ResponseEvent::Done { reason, metrics } => {
+///             println!("\n[{:?}] {}", reason, metrics);
+///         }
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ResponseEvent {
+    /// Next piece of the answer text
+    TextDelta(String),
+    /// Next piece of the model's reasoning
+    ThinkingDelta(String),
+    /// A tool call requested by the model
+    ///
+    /// Requires the `tools` feature.
+    #[cfg(feature = "tools")]
+    ToolCall(ToolCall),
+    /// Token counts reported by the server
+    UsageUpdate {
+        /// Tokens in the prompt
+        prompt_tokens: Option<i32>,
+        /// Tokens generated so far
+        completion_tokens: Option<i32>,
+    },
+    /// The response is complete
+    Done {
+        /// Why generation stopped
+        reason: Option<DoneReason>,
+        /// Timing and token usage of the whole response
+        metrics: EvalMetrics,
+    },
+}
+
+impl ChatResponse {
+    /// Split this chunk into [`ResponseEvent`]s.
+    ///
+    /// Events come in a fixed order: thinking, text, tool calls, usage,
+    /// done. Empty text and thinking are skipped; `UsageUpdate` appears only
+    /// when the chunk carries token counts, and `Done` only on the final
+    /// chunk.
+    pub fn into_events(self) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        let metrics = self.metrics();
+        if let Some(message) = self.message {
+            if let Some(thinking) = message.thinking.filter(|t| !t.is_empty()) {
+                events.push(ResponseEvent::ThinkingDelta(thinking));
+            }
+            if let Some(content) = message.content.filter(|c| !c.is_empty()) {
+                events.push(ResponseEvent::TextDelta(content));
+            }
+            #[cfg(feature = "tools")]
+            events.extend(
+                message
+                    .tool_calls
+                    .into_iter()
+                    .flatten()
+                    .map(ResponseEvent::ToolCall),
+            );
+        }
+        if metrics.prompt_eval_count.is_some() || metrics.eval_count.is_some() {
+            events.push(ResponseEvent::UsageUpdate {
+                prompt_tokens: metrics.prompt_eval_count,
+                completion_tokens: metrics.eval_count,
+            });
+        }
+        if self.done == Some(true) {
+            events.push(ResponseEvent::Done {
+                reason: self.done_reason,
+                metrics,
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_events_order() {
+        let chunk: ChatResponse = serde_json::from_str(
+            r#"{"message":{"role":"assistant","content":"Hi","thinking":"hm"},
+                "done":true,"done_reason":"length","prompt_eval_count":3,"eval_count":1}"#,
+        )
+        .unwrap();
+        let events = chunk.into_events();
+        assert_eq!(events[0], ResponseEvent::ThinkingDelta("hm".to_string()));
+        assert_eq!(events[1], ResponseEvent::TextDelta("Hi".to_string()));
+        assert_eq!(
+            events[2],
+            ResponseEvent::UsageUpdate {
+                prompt_tokens: Some(3),
+                completion_tokens: Some(1)
+            }
+        );
+        assert!(matches!(
+            &events[3],
+            ResponseEvent::Done { reason: Some(DoneReason::Length), metrics }
+                if metrics.eval_count == Some(1)
+        ));
+    }
+
+    #[test]
+    fn test_into_events_skips_empty_chunk() {
+        let chunk: ChatResponse =
+            serde_json::from_str(r#"{"message":{"role":"assistant","content":""},"done":false}"#)
+                .unwrap();
+        assert!(chunk.into_events().is_empty());
+    }
+
+    #[cfg(feature = "tools")]
+    #[test]
+    fn test_into_events_tool_calls() {
+        let chunk: ChatResponse = serde_json::from_str(
+            r#"{"message":{"role":"assistant","content":"","tool_calls":[
+                {"function":{"name":"a","arguments":{}}},{"function":{"name":"b","arguments":{}}}]},
+                "done":false}"#,
+        )
+        .unwrap();
+        let names: Vec<_> = chunk
+            .into_events()
+            .into_iter()
+            .map(|e| match e {
+                ResponseEvent::ToolCall(call) => {
+                    call.function_name().unwrap_or_default().to_string()
+                }
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(names, ["a", "b"]);
+    }
+}
@@ -0,0 +1,113 @@
+//! Embeddings tagged with their space
+
+use std::sync::Arc;
+
+use super::{EmbeddingFloat, EmbeddingSpace};
+use crate::Result;
+
+/// An embedding tagged with the [`EmbeddingSpace`] it came from, created by
+/// [`EmbeddingSpace::vector`] or [`EmbeddingSpace::vectors`].
+///
+/// Comparisons fail with
+/// [`Error::EmbeddingSpaceMismatch`](crate::Error::EmbeddingSpaceMismatch)
+/// instead of returning a score when the two vectors are from different
+/// spaces. Cloning copies the values; the space is shared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceVector<F: EmbeddingFloat = f32> {
+    space: Arc<EmbeddingSpace>,
+    values: Vec<F>,
+}
+
+impl<F: EmbeddingFloat> SpaceVector<F> {
+    pub(super) fn new(space: Arc<EmbeddingSpace>, values: Vec<F>) -> Self {
+        Self { space, values }
+    }
+
+    /// The space the vector belongs to
+    pub fn space(&self) -> &EmbeddingSpace {
+        &self.space
+    }
+
+    /// The embedding values
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+
+    /// The embedding values, dropping the tag
+    pub fn into_values(self) -> Vec<F> {
+        self.values
+    }
+
+    /// Dot product with `other`.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`](crate::Error::EmbeddingSpaceMismatch)
+    /// if `other` is from a different space.
+    pub fn dot(&self, other: &Self) -> Result<f64> {
+        self.space.ensure_same(&other.space)?;
+        Ok(dot(&self.values, &other.values))
+    }
+
+    /// Cosine similarity with `other`, from -1.0 to 1.0; 0.0 if either is
+    /// all zeros.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`](crate::Error::EmbeddingSpaceMismatch)
+    /// if `other` is from a different space.
+    pub fn cosine(&self, other: &Self) -> Result<f64> {
+        self.space.ensure_same(&other.space)?;
+        Ok(cosine(&self.values, &other.values))
+    }
+}
+
+impl<F: EmbeddingFloat> AsRef<[F]> for SpaceVector<F> {
+    fn as_ref(&self) -> &[F] {
+        &self.values
+    }
+}
+
+/// Dot product of two equal-length slices
+pub(crate) fn dot<F: EmbeddingFloat>(a: &[F], b: &[F]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x.to_f64() * y.to_f64()).sum()
+}
+
+/// Cosine similarity of two equal-length slices, 0.0 if either is all zeros
+pub(crate) fn cosine<F: EmbeddingFloat>(a: &[F], b: &[F]) -> f64 {
+    let norms = dot(a, a).sqrt() * dot(b, b).sqrt();
+    if norms == 0.0 { 0.0 } else { dot(a, b) / norms }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_cosine_within_space() {
+        let space = EmbeddingSpace::new("m", 2);
+        let a = space.vector(vec![1.0_f32, 0.0]).unwrap();
+        let b = space.vector(vec![1.0_f32, 1.0]).unwrap();
+        assert!((a.cosine(&b).unwrap() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(a.dot(&b).unwrap(), 1.0);
+
+        let zero = space.vector(vec![0.0_f32, 0.0]).unwrap();
+        assert_eq!(a.cosine(&zero).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_comparing_across_spaces_fails() {
+        let a = EmbeddingSpace::new("m", 2)
+            .vector(vec![1.0_f32, 0.0])
+            .unwrap();
+        let b = EmbeddingSpace::new("n", 2)
+            .vector(vec![1.0_f32, 0.0])
+            .unwrap();
+        assert!(matches!(
+            a.cosine(&b),
+            Err(Error::EmbeddingSpaceMismatch { .. })
+        ));
+        assert!(a.dot(&b).is_err());
+    }
+}
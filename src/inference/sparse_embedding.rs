@@ -0,0 +1,245 @@
+//! Top-k sparsified embeddings
+
+use serde::{Deserialize, Serialize};
+
+use super::EmbeddingFloat;
+use super::validation;
+use crate::Result;
+
+/// An embedding reduced to its `k` largest-magnitude values, stored as
+/// `(index, value)` pairs in index order.
+///
+/// Most of a dense embedding's dot product comes from a few large
+/// components, so keeping the top 5-10% of them shrinks the vector while
+/// preserving [`dot`](Self::dot) scores closely; [`jaccard`](Self::jaccard)
+/// compares which dimensions were kept. Build one with
+/// [`top_k`](Self::top_k) or
+/// [`EmbedResponse::sparsify`](super::EmbedResponse::sparsify).
+/// Deserializing checks that every index is below `dimensions` and that
+/// the indices are strictly increasing.
+///
+/// # Example
+///
+/// ```
+/// use ollama_oxide::SparseEmbedding;
+///
+/// let sparse = SparseEmbedding::top_k(&[0.1_f32, -0.9, 0.0, 0.5], 2);
+/// assert_eq!(sparse.entries(), [(1, -0.9), (3, 0.5)]);
+/// assert_eq!(sparse.to_dense(), [0.0, -0.9, 0.0, 0.5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(bound = "F: EmbeddingFloat", try_from = "RawSparseEmbedding<F>")]
+pub struct SparseEmbedding<F: EmbeddingFloat = f32> {
+    dimensions: usize,
+    entries: Vec<(u32, F)>,
+}
+
+/// A [`SparseEmbedding`] as deserialized, before its entries are checked
+#[derive(Deserialize)]
+#[serde(bound = "F: EmbeddingFloat")]
+struct RawSparseEmbedding<F: EmbeddingFloat> {
+    dimensions: usize,
+    entries: Vec<(u32, F)>,
+}
+
+impl<F: EmbeddingFloat> TryFrom<RawSparseEmbedding<F>> for SparseEmbedding<F> {
+    type Error = String;
+
+    fn try_from(raw: RawSparseEmbedding<F>) -> std::result::Result<Self, String> {
+        if let Some(&(i, _)) = raw
+            .entries
+            .iter()
+            .find(|(i, _)| *i as usize >= raw.dimensions)
+        {
+            return Err(format!(
+                "sparse embedding index {i} is out of bounds for {} dimensions",
+                raw.dimensions
+            ));
+        }
+        if let Some(pair) = raw.entries.windows(2).find(|pair| pair[0].0 >= pair[1].0) {
+            return Err(format!(
+                "sparse embedding indices must be strictly increasing, found {} then {}",
+                pair[0].0, pair[1].0
+            ));
+        }
+        Ok(Self {
+            dimensions: raw.dimensions,
+            entries: raw.entries,
+        })
+    }
+}
+
+impl<F: EmbeddingFloat> SparseEmbedding<F> {
+    /// The `k` values of largest magnitude; ties keep the lower index.
+    ///
+    /// Zeros are never kept, so fewer than `k` entries remain when `values`
+    /// has fewer non-zero values.
+    pub fn top_k(values: &[F], k: usize) -> Self {
+        let mut entries: Vec<(u32, F)> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.to_f64() != 0.0)
+            .map(|(i, v)| (i as u32, *v))
+            .collect();
+        if k < entries.len() {
+            let by_magnitude = |a: &(u32, F), b: &(u32, F)| {
+                b.1.to_f64()
+                    .abs()
+                    .total_cmp(&a.1.to_f64().abs())
+                    .then(a.0.cmp(&b.0))
+            };
+            if k > 0 {
+                entries.select_nth_unstable_by(k - 1, by_magnitude);
+            }
+            entries.truncate(k);
+        }
+        entries.sort_unstable_by_key(|(i, _)| *i);
+        Self {
+            dimensions: values.len(),
+            entries,
+        }
+    }
+
+    /// Number of dimensions of the source embedding
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Number of kept values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no values were kept
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The kept `(index, value)` pairs, in index order
+    pub fn entries(&self) -> &[(u32, F)] {
+        &self.entries
+    }
+
+    /// The full-length vector, zero outside the kept indices
+    pub fn to_dense(&self) -> Vec<F> {
+        let mut dense = vec![F::default(); self.dimensions];
+        for &(i, v) in &self.entries {
+            dense[i as usize] = v;
+        }
+        dense
+    }
+
+    /// Dot product over the indices both kept.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`](crate::Error::ValidationError) if the
+    /// dimensions differ.
+    pub fn dot(&self, other: &Self) -> Result<f64> {
+        validation::same_dimensions(self.dimensions, other.dimensions)?;
+        let mut sum = 0.0;
+        self.merge(other, |a, b| sum += a.to_f64() * b.to_f64());
+        Ok(sum)
+    }
+
+    /// Shared kept indices over all kept indices, from 0.0 to 1.0; 1.0 when
+    /// neither kept any.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::ValidationError`](crate::Error::ValidationError) if the
+    /// dimensions differ.
+    pub fn jaccard(&self, other: &Self) -> Result<f64> {
+        validation::same_dimensions(self.dimensions, other.dimensions)?;
+        let mut both = 0usize;
+        self.merge(other, |_, _| both += 1);
+        let either = self.len() + other.len() - both;
+        Ok(if either == 0 {
+            1.0
+        } else {
+            both as f64 / either as f64
+        })
+    }
+
+    /// Calls `f` with both values at each index kept by both.
+    fn merge(&self, other: &Self, mut f: impl FnMut(F, F)) {
+        let (mut a, mut b) = (
+            self.entries.iter().peekable(),
+            other.entries.iter().peekable(),
+        );
+        while let (Some(&&(i, x)), Some(&&(j, y))) = (a.peek(), b.peek()) {
+            match i.cmp(&j) {
+                std::cmp::Ordering::Less => {
+                    a.next();
+                }
+                std::cmp::Ordering::Greater => {
+                    b.next();
+                }
+                std::cmp::Ordering::Equal => {
+                    f(x, y);
+                    a.next();
+                    b.next();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k_keeps_largest_magnitudes_in_index_order() {
+        let sparse = SparseEmbedding::top_k(&[0.2_f32, -0.8, 0.5, 0.1, -0.6], 3);
+        assert_eq!(sparse.entries(), [(1, -0.8), (2, 0.5), (4, -0.6)]);
+        assert_eq!(sparse.dimensions(), 5);
+    }
+
+    #[test]
+    fn test_top_k_ties_keep_lower_index_and_skip_zeros() {
+        let sparse = SparseEmbedding::top_k(&[0.5_f64, 0.0, -0.5, 0.5], 2);
+        assert_eq!(sparse.entries(), [(0, 0.5), (2, -0.5)]);
+
+        let zeros = SparseEmbedding::top_k(&[0.0_f32, 0.3, 0.0], 5);
+        assert_eq!(zeros.len(), 1);
+        assert!(SparseEmbedding::top_k(&[0.3_f32], 0).is_empty());
+    }
+
+    #[test]
+    fn test_dot_and_jaccard_over_shared_indices() {
+        let a = SparseEmbedding::top_k(&[1.0_f32, 2.0, 0.0, 0.0], 2);
+        let b = SparseEmbedding::top_k(&[0.0_f32, 3.0, 4.0, 0.0], 2);
+        assert_eq!(a.dot(&b).unwrap(), 6.0);
+        assert!((a.jaccard(&b).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_deserialize_checks_entries() {
+        let sparse = SparseEmbedding::top_k(&[0.1_f32, -0.9, 0.0, 0.5], 2);
+        let json = serde_json::to_string(&sparse).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SparseEmbedding>(&json).unwrap(),
+            sparse
+        );
+
+        for bad in [
+            r#"{"dimensions":4,"entries":[[1,0.5],[4,0.5]]}"#,
+            r#"{"dimensions":4,"entries":[[3,0.5],[1,0.5]]}"#,
+            r#"{"dimensions":4,"entries":[[1,0.5],[1,0.5]]}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<SparseEmbedding>(bad).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mismatched_dimensions_rejected() {
+        let a = SparseEmbedding::top_k(&[1.0_f32; 3], 2);
+        let b = SparseEmbedding::top_k(&[1.0_f32; 4], 2);
+        assert!(a.dot(&b).is_err());
+        assert!(a.jaccard(&b).is_err());
+    }
+}
@@ -16,6 +16,7 @@ use serde::{Deserialize, Serialize};
 /// let multiple = StopSetting::multiple([".", "!", "?"]);
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum StopSetting {
     /// Single stop sequence
@@ -16,6 +16,7 @@ use serde::{Deserialize, Serialize};
 /// let high = ThinkSetting::high();
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum ThinkSetting {
     /// Boolean: true to enable, false to disable
@@ -0,0 +1,63 @@
+//! Shared client-side checks used by request `validate()` methods
+
+use crate::{Error, KnownModel, Result};
+
+/// Lowest accepted `num_predict`; `-1` means unlimited and `-2` fills the context.
+const MIN_NUM_PREDICT: i32 = -2;
+
+pub(crate) fn invalid(message: impl Into<String>) -> Error {
+    Error::ValidationError(message.into())
+}
+
+/// Rejects empty or whitespace-only model names.
+pub(crate) fn model_name(model: &str) -> Result<()> {
+    if model.trim().is_empty() {
+        return Err(invalid("model name must not be empty"));
+    }
+    Ok(())
+}
+
+/// Rejects images for models known not to accept them.
+///
+/// Unknown models pass, since only the server can tell for sure.
+pub(crate) fn images_allowed(model: &str) -> Result<()> {
+    match KnownModel::find(model) {
+        Some(known) if !known.supports_vision() => Err(invalid(format!(
+            "model '{}' does not support images",
+            model
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects option values the server would refuse or misinterpret.
+pub(crate) fn options(options: Option<&crate::ModelOptions>) -> Result<()> {
+    let Some(options) = options else {
+        return Ok(());
+    };
+    if let Some(n) = options.num_predict
+        && n < MIN_NUM_PREDICT
+    {
+        return Err(invalid(format!(
+            "num_predict must be >= {} (got {})",
+            MIN_NUM_PREDICT, n
+        )));
+    }
+    if let Some(n) = options.num_ctx
+        && n <= 0
+    {
+        return Err(invalid(format!("num_ctx must be positive (got {})", n)));
+    }
+    Ok(())
+}
+
+/// Rejects comparing embeddings of different lengths.
+pub(crate) fn same_dimensions(left: usize, right: usize) -> Result<()> {
+    if left != right {
+        return Err(invalid(format!(
+            "embedding dimensions differ ({} vs {})",
+            left, right
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,264 @@
+//! In-memory nearest-neighbour search over tagged embeddings
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::space_vector::cosine;
+use super::{EmbeddingFloat, EmbeddingSpace, MetadataFilter, SpaceVector};
+use crate::{Error, Result};
+
+/// An in-memory index of embeddings from one [`EmbeddingSpace`], each with
+/// an id and serde metadata, searched by cosine similarity.
+///
+/// Vectors from any other space are refused on insert and query. Searches
+/// are exact, scoring every entry, which is fast enough for the tens of
+/// thousands of chunks a local retrieval setup typically holds. Metadata
+/// can be any `Serialize` type; filters see it in its JSON form.
+///
+/// # Example
+///
+/// ```no_run
+/// use ollama_oxide::{EmbeddingSpace, MetadataFilter, OllamaApiAsync, OllamaClient, VectorIndex};
+/// use serde_json::json;
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let space = EmbeddingSpace::new("nomic-embed-text", 768);
+/// let mut index = VectorIndex::new(space.clone());
+///
+/// let docs = [("a", "Rust is a systems language", "wiki", "en"),
+///             ("b", "Rust ist eine Systemsprache", "wiki", "de")];
+/// let texts: Vec<String> = docs.iter().map(|d| d.1.to_string()).collect();
+/// let vectors = space.vectors(client.embed(&space.request(texts)).await?)?;
+/// for (doc, vector) in docs.iter().zip(vectors) {
+///     index.insert(doc.0, vector, json!({"source": doc.2, "lang": doc.3}))?;
+/// }
+///
+/// let query = space.vectors(client.embed(&space.request("systems programming")).await?)?;
+/// let filter = MetadataFilter::eq("source", "wiki").and(MetadataFilter::eq("lang", "en"));
+/// for hit in index.search_filtered(&query[0], 5, &filter)? {
+///     println!("{} {:.3} {}", hit.id, hit.score, hit.metadata);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VectorIndex<M = Value, F: EmbeddingFloat = f32> {
+    space: EmbeddingSpace,
+    entries: Vec<Entry<M, F>>,
+    positions: HashMap<String, usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<M, F> {
+    id: String,
+    values: Vec<F>,
+    metadata: M,
+    /// `metadata` in JSON form, for [`MetadataFilter`]s
+    json: Value,
+}
+
+/// One result of a [`VectorIndex`] search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchHit<'a, M> {
+    /// Id the entry was inserted with
+    pub id: &'a str,
+    /// Cosine similarity to the query, from -1.0 to 1.0
+    pub score: f64,
+    /// Metadata the entry was inserted with
+    pub metadata: &'a M,
+}
+
+impl<M: Serialize, F: EmbeddingFloat> VectorIndex<M, F> {
+    /// An empty index for vectors from `space`
+    pub fn new(space: EmbeddingSpace) -> Self {
+        Self {
+            space,
+            entries: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// The space every entry belongs to
+    pub fn space(&self) -> &EmbeddingSpace {
+        &self.space
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add `vector` under `id`, replacing any entry with the same id.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::EmbeddingSpaceMismatch`] if `vector` is from another space
+    /// - [`Error::SerializationError`] if `metadata` fails to serialize
+    pub fn insert(
+        &mut self,
+        id: impl Into<String>,
+        vector: SpaceVector<F>,
+        metadata: M,
+    ) -> Result<()> {
+        self.space.ensure_same(vector.space())?;
+        let json = serde_json::to_value(&metadata)
+            .map_err(|e| Error::SerializationError(e.to_string()))?;
+        let entry = Entry {
+            id: id.into(),
+            values: vector.into_values(),
+            metadata,
+            json,
+        };
+        match self.positions.get(&entry.id) {
+            Some(&i) => self.entries[i] = entry,
+            None => {
+                self.positions.insert(entry.id.clone(), self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+        Ok(())
+    }
+
+    /// Metadata of the entry with `id`
+    pub fn get(&self, id: &str) -> Option<&M> {
+        self.positions.get(id).map(|&i| &self.entries[i].metadata)
+    }
+
+    /// Remove the entry with `id`, returning its metadata.
+    pub fn remove(&mut self, id: &str) -> Option<M> {
+        let i = self.positions.remove(id)?;
+        let entry = self.entries.swap_remove(i);
+        if let Some(moved) = self.entries.get(i) {
+            self.positions.insert(moved.id.clone(), i);
+        }
+        Some(entry.metadata)
+    }
+
+    /// The `k` entries most similar to `query`, best first.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] if `query` is from another space.
+    pub fn search(&self, query: &SpaceVector<F>, k: usize) -> Result<Vec<SearchHit<'_, M>>> {
+        self.search_where(query, k, |_| true)
+    }
+
+    /// The `k` entries most similar to `query` among those whose metadata
+    /// matches `filter`, best first.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] if `query` is from another space.
+    pub fn search_filtered(
+        &self,
+        query: &SpaceVector<F>,
+        k: usize,
+        filter: &MetadataFilter,
+    ) -> Result<Vec<SearchHit<'_, M>>> {
+        self.search_entries(query, k, |entry| filter.matches(&entry.json))
+    }
+
+    /// The `k` entries most similar to `query` among those whose metadata
+    /// satisfies `keep`, best first; for conditions easier to write
+    /// against the metadata type than as a [`MetadataFilter`].
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmbeddingSpaceMismatch`] if `query` is from another space.
+    pub fn search_where(
+        &self,
+        query: &SpaceVector<F>,
+        k: usize,
+        keep: impl Fn(&M) -> bool,
+    ) -> Result<Vec<SearchHit<'_, M>>> {
+        self.search_entries(query, k, |entry| keep(&entry.metadata))
+    }
+
+    fn search_entries(
+        &self,
+        query: &SpaceVector<F>,
+        k: usize,
+        keep: impl Fn(&Entry<M, F>) -> bool,
+    ) -> Result<Vec<SearchHit<'_, M>>> {
+        self.space.ensure_same(query.space())?;
+        let mut hits: Vec<SearchHit<'_, M>> = self
+            .entries
+            .iter()
+            .filter(|entry| keep(entry))
+            .map(|entry| SearchHit {
+                id: &entry.id,
+                score: cosine(query.values(), &entry.values),
+                metadata: &entry.metadata,
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(k);
+        Ok(hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn index() -> (EmbeddingSpace, VectorIndex) {
+        let space = EmbeddingSpace::new("m", 2);
+        let mut index = VectorIndex::new(space.clone());
+        for (id, values, meta) in [
+            ("a", [1.0, 0.0], json!({"source": "wiki", "lang": "en"})),
+            ("b", [0.9, 0.1], json!({"source": "wiki", "lang": "de"})),
+            ("c", [0.0, 1.0], json!({"source": "blog", "lang": "en"})),
+        ] {
+            index
+                .insert(id, space.vector(values.to_vec()).unwrap(), meta)
+                .unwrap();
+        }
+        (space, index)
+    }
+
+    #[test]
+    fn test_insert_replaces_and_remove_keeps_ids_consistent() {
+        let (space, mut index) = index();
+        index
+            .insert("a", space.vector(vec![0.5, 0.5]).unwrap(), json!({}))
+            .unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("a"), Some(&json!({})));
+
+        assert!(index.remove("a").is_some());
+        assert!(index.remove("a").is_none());
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get("c").unwrap()["source"], "blog");
+    }
+
+    #[test]
+    fn test_search_ranks_by_cosine() {
+        let (space, index) = index();
+        let query = space.vector(vec![1.0, 0.0]).unwrap();
+        let ids: Vec<&str> = index
+            .search(&query, 2)
+            .unwrap()
+            .iter()
+            .map(|h| h.id)
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_other_space_rejected() {
+        let (_, mut index) = index();
+        let other = EmbeddingSpace::new("n", 2).vector(vec![1.0, 0.0]).unwrap();
+        assert!(index.search(&other, 1).is_err());
+        assert!(index.insert("d", other, json!({})).is_err());
+        assert_eq!(index.len(), 3);
+    }
+}
@@ -37,6 +37,9 @@ mod error;
 
 pub use error::{Error, Result};
 
+#[cfg(feature = "inference")]
+mod server_time;
+
 // ============================================================================
 // Inference Module
 // ============================================================================
@@ -46,27 +49,54 @@ pub mod inference;
 
 #[cfg(feature = "inference")]
 pub use inference::{
+    BinaryEmbedding,
     // Chat types
+    CancelReason,
     ChatMessage,
     ChatRequest,
     ChatResponse,
     ChatRole,
+    ContentPart,
+    DoneReason,
     EmbedInput,
     EmbedRequest,
     EmbedResponse,
+    EmbeddingFloat,
+    EmbeddingSpace,
+    EvalMetrics,
     FormatSetting,
     GenerateRequest,
     GenerateResponse,
+    GenerationCheckpoint,
+    ImageData,
+    ImageReader,
     KeepAliveSetting,
     Logprob,
+    MetadataFilter,
     ModelOptions,
+    RequestFingerprint,
+    ResponseEvent,
     ResponseMessage,
+    SearchHit,
+    SpaceVector,
+    SparseEmbedding,
     StopSetting,
     ThinkSetting,
     TokenLogprob,
+    VectorIndex,
     VersionResponse,
 };
 
+// ============================================================================
+// Well-Known Models
+// ============================================================================
+
+#[cfg(feature = "inference")]
+pub mod models;
+
+#[cfg(feature = "inference")]
+pub use models::KnownModel;
+
 // ============================================================================
 // HTTP Client Module
 // ============================================================================
@@ -76,7 +106,14 @@ pub mod http;
 
 #[cfg(feature = "http")]
 pub use http::{
-    ChatStream, ChatStreamBlocking, ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient,
+    ByteStream, CallId, CancelGuard, CancelToken, ChatStream, ChatStreamBlocking, ClientConfig,
+    ConfigProfile, ConfigProfiles, DatasetExporter, ErrorReport, Extensions, FnGuardrail,
+    GatewayProfile, GenerateStream, GenerateStreamBlocking, Guardrail, GuardrailAction,
+    HedgedClient, JsonLimits, LatencyStats, LatencySummary, ModelHandle, NdjsonReader, Observer,
+    OllamaApiAsync, OllamaApiSync, OllamaClient, OverflowPolicy, PauseHandle, Percentiles,
+    PostProcessor, Prefetch, Priority, RawChunk, RawChunkStream, RawChunkStreamBlocking,
+    ResponseStream, ResponseStreamBlocking, ServerStatus, StreamUsage, SystemPromptPolicy,
+    TextDelta, TokenBudget, TranscriptRecorder,
 };
 
 // ============================================================================
@@ -88,11 +125,16 @@ pub mod model;
 
 #[cfg(feature = "model")]
 pub use model::{
-    CopyRequest, CreateRequest, CreateResponse, DeleteRequest, LicenseSetting, ListResponse,
-    ModelDetails, ModelSummary, PsResponse, PullRequest, PullResponse, PushRequest, PushResponse,
-    RunningModel, ShowModelDetails, ShowRequest, ShowResponse,
+    CopyRequest, CreateRequest, CreateResponse, DeleteRequest, LayerProgress, LayerState,
+    LicenseSetting, ListResponse, ModelCapabilities, ModelDetails, ModelDiff, ModelSummary,
+    ParameterChange, PromptTemplate, PsResponse, PullProgress, PullRequest, PullResponse,
+    PushRequest, PushResponse, RunningModel, ShowModelDetails, ShowRequest, ShowResponse,
+    TensorInfo, TextChange,
 };
 
+#[cfg(feature = "model")]
+pub use http::{PullProgressStream, PullProgressStreamBlocking, PullStream, PullStreamBlocking};
+
 // ============================================================================
 // Ergonomic Tools Module (requires "tools" feature)
 // ============================================================================
@@ -102,7 +144,62 @@ pub mod tools;
 
 // Tool types re-exports (requires both "inference" and "tools" features)
 #[cfg(feature = "tools")]
-pub use tools::{ToolCall, ToolCallFunction, ToolDefinition, ToolFunction};
+pub use tools::{ToolArguments, ToolCall, ToolCallFunction, ToolDefinition, ToolFunction};
+
+// ============================================================================
+// High-Level Conveniences (requires "conveniences" feature)
+// ============================================================================
+
+#[cfg(feature = "conveniences")]
+pub mod conveniences;
+
+#[cfg(feature = "conveniences")]
+pub use conveniences::{
+    ChatSession, Classification, CodeBlock, ComparisonCase, ComparisonReport, EvalCase, EvalCheck,
+    EvalReport, EvalResult, EvalRunner, JobBackend, JobCallback, JobOutput, JobQueue, JobRecord,
+    JobRequest, JobStatus, JsonDirBackend, MemoryBackend, Moderation, ModerationCategory,
+    ModerationGuardrail, PromptComparison, PromptWinner, RaceWinner, SessionBackend, SessionRecord,
+    SessionStore, SummarizePolicy, TranslatedChunk, Translation, Verdict, Vote,
+    extract_code_blocks,
+};
+
+#[cfg(all(feature = "conveniences", feature = "model"))]
+pub use conveniences::{BulkItem, BulkReport, DiskUsage, FamilyUsage, PrunePlan};
+
+// ============================================================================
+// Local Server Module (requires "server" feature)
+// ============================================================================
+
+#[cfg(feature = "server")]
+pub mod server;
+
+// ============================================================================
+// Live Server Test Helpers (requires "testing" feature)
+// ============================================================================
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+// ============================================================================
+// Hosted Web APIs (requires "web" feature)
+// ============================================================================
+
+#[cfg(feature = "web")]
+pub mod web;
+
+// ============================================================================
+// Unstable Endpoints (requires "experimental" feature, semver-exempt)
+// ============================================================================
+
+#[cfg(feature = "experimental")]
+pub mod experimental;
+
+// ============================================================================
+// Property Testing Strategies (requires "proptest" feature)
+// ============================================================================
+
+#[cfg(feature = "proptest")]
+pub mod strategies;
 
 // ============================================================================
 // Prelude
@@ -113,42 +210,67 @@ pub mod prelude {
 
     #[cfg(feature = "http")]
     pub use crate::{
-        ChatStream, ChatStreamBlocking, ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient,
+        ChatStream, ChatStreamBlocking, ClientConfig, GenerateStream, GenerateStreamBlocking,
+        ModelHandle, Observer, OllamaApiAsync, OllamaApiSync, OllamaClient, ResponseStream,
+        ResponseStreamBlocking, ServerStatus,
     };
 
     #[cfg(feature = "inference")]
     pub use crate::{
+        BinaryEmbedding,
         // Chat types
+        CancelReason,
         ChatMessage,
         ChatRequest,
         ChatResponse,
         ChatRole,
+        ContentPart,
         // Inference types
+        DoneReason,
         EmbedInput,
         EmbedRequest,
         EmbedResponse,
+        EmbeddingFloat,
+        EmbeddingSpace,
+        EvalMetrics,
         FormatSetting,
         GenerateRequest,
         GenerateResponse,
+        GenerationCheckpoint,
+        ImageData,
         KeepAliveSetting,
+        KnownModel,
         Logprob,
+        MetadataFilter,
         ModelOptions,
+        RequestFingerprint,
+        ResponseEvent,
         ResponseMessage,
+        SearchHit,
+        SpaceVector,
+        SparseEmbedding,
         StopSetting,
         ThinkSetting,
         TokenLogprob,
+        VectorIndex,
         VersionResponse,
     };
 
     // Tool types (requires "tools" features)
     #[cfg(feature = "tools")]
-    pub use crate::{ToolCall, ToolCallFunction, ToolDefinition, ToolFunction};
+    pub use crate::{ToolArguments, ToolCall, ToolCallFunction, ToolDefinition, ToolFunction};
+
+    // Convenience types (requires "conveniences" feature)
+    #[cfg(feature = "conveniences")]
+    pub use crate::{
+        ChatSession, Classification, RaceWinner, SessionStore, SummarizePolicy, Verdict, Vote,
+    };
 
     // Model types (requires "model" feature)
     #[cfg(feature = "model")]
     pub use crate::{
         CopyRequest, CreateRequest, CreateResponse, DeleteRequest, LicenseSetting, ListResponse,
-        ModelDetails, ModelSummary, PsResponse, RunningModel, ShowModelDetails, ShowRequest,
-        ShowResponse,
+        ModelCapabilities, ModelDetails, ModelSummary, PsResponse, PullProgress, PullStream,
+        RunningModel, ShowModelDetails, ShowRequest, ShowResponse, TensorInfo,
     };
 }
@@ -10,6 +10,7 @@ use crate::ChatMessage;
 ///
 /// Creates a custom model from an existing model with custom configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CreateRequest {
     /// Name for the model to create (required)
     pub model: String,
@@ -31,6 +32,7 @@ pub struct CreateRequest {
     pub system: Option<String>,
 
     /// Key-value parameters for the model (JSON object)
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Value>,
 
@@ -0,0 +1,50 @@
+//! Per-layer state within a pull progress summary
+
+use serde::{Deserialize, Serialize};
+
+/// Download state of a single model layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum LayerState {
+    /// Bytes are still arriving
+    Downloading,
+    /// All bytes received
+    Complete,
+}
+
+/// Progress of one layer (blob) of a model being pulled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerProgress {
+    /// Layer digest (e.g. `sha256:b112...`)
+    pub digest: String,
+
+    /// Layer size in bytes
+    pub total: u64,
+
+    /// Bytes received so far
+    pub completed: u64,
+
+    /// Current state
+    pub state: LayerState,
+}
+
+impl LayerProgress {
+    /// Percentage of this layer received (0.0 to 100.0)
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return if self.state == LayerState::Complete {
+                100.0
+            } else {
+                0.0
+            };
+        }
+        (self.completed.min(self.total) as f64 / self.total as f64) * 100.0
+    }
+
+    /// Short digest for display (first 12 hex characters)
+    pub fn short_digest(&self) -> &str {
+        let hex = self.digest.strip_prefix("sha256:").unwrap_or(&self.digest);
+        hex.get(..12).unwrap_or(hex)
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Can be either a single license string or multiple licenses.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[serde(untagged)]
 pub enum LicenseSetting {
     /// Single license string
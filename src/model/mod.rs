@@ -1,7 +1,9 @@
 mod create_request;
 mod create_response;
 mod delete_request;
+mod layer_progress;
 mod license_setting;
+mod pull_progress;
 mod pull_request;
 mod pull_response;
 mod push_request;
@@ -9,18 +11,29 @@ mod push_response;
 
 mod copy_request;
 mod list_response;
+mod model_capabilities;
 mod model_details;
+mod model_diff;
 mod model_summary;
+mod parameter_change;
+mod prompt_template;
 mod ps_response;
 mod running_model;
 mod show_model_details;
 mod show_request;
 mod show_response;
+mod template_eval;
+mod template_syntax;
+mod tensor_info;
+mod text_change;
 
 pub use create_request::CreateRequest;
 pub use create_response::CreateResponse;
 pub use delete_request::DeleteRequest;
+pub use layer_progress::{LayerProgress, LayerState};
 pub use license_setting::LicenseSetting;
+pub use pull_progress::PullProgress;
+pub(crate) use pull_progress::PullProgressTracker;
 pub use pull_request::PullRequest;
 pub use pull_response::PullResponse;
 pub use push_request::PushRequest;
@@ -28,10 +41,16 @@ pub use push_response::PushResponse;
 
 pub use copy_request::CopyRequest;
 pub use list_response::ListResponse;
+pub use model_capabilities::ModelCapabilities;
 pub use model_details::ModelDetails;
+pub use model_diff::ModelDiff;
 pub use model_summary::ModelSummary;
+pub use parameter_change::ParameterChange;
+pub use prompt_template::PromptTemplate;
 pub use ps_response::PsResponse;
 pub use running_model::RunningModel;
 pub use show_model_details::ShowModelDetails;
 pub use show_request::ShowRequest;
 pub use show_response::ShowResponse;
+pub use tensor_info::TensorInfo;
+pub use text_change::TextChange;
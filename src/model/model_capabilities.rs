@@ -0,0 +1,179 @@
+//! Model capability summary derived from POST /api/show
+
+use serde::{Deserialize, Serialize};
+
+use super::ShowResponse;
+use crate::KnownModel;
+
+/// What a model can do, as far as the client can tell.
+///
+/// Returned by `model_capabilities()` on the API traits. Recent Ollama
+/// servers report a `capabilities` list directly; for older servers the
+/// flags are inferred from the prompt template, model families, and
+/// metadata keys, and finally from [`KnownModel`] when the name is known.
+///
+/// # Example
+///
+/// ```no_run
+/// use ollama_oxide::{ChatMessage, ChatRequest, OllamaApiAsync, OllamaClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = OllamaClient::default()?;
+/// let caps = client.model_capabilities("qwen3:0.6b").await?;
+///
+/// let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+/// let request = if caps.thinking { request.with_think(true) } else { request };
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    /// Text generation (chat and generate)
+    pub completion: bool,
+
+    /// Tool (function) calling
+    pub tools: bool,
+
+    /// Image inputs
+    pub vision: bool,
+
+    /// Separate thinking output (`think`)
+    pub thinking: bool,
+
+    /// Embedding generation
+    pub embedding: bool,
+
+    /// Maximum context window reported in the model metadata
+    pub context_length: Option<u64>,
+}
+
+impl ModelCapabilities {
+    /// Derive capabilities from a show response.
+    ///
+    /// Uses the server-reported `capabilities` list when present, otherwise
+    /// falls back to template and metadata heuristics.
+    pub fn from_show_response(response: &ShowResponse) -> Self {
+        let context_length = context_length(response);
+
+        if let Some(caps) = &response.capabilities {
+            let has = |c: &str| caps.iter().any(|x| x.eq_ignore_ascii_case(c));
+            return Self {
+                completion: has("completion"),
+                tools: has("tools"),
+                vision: has("vision"),
+                thinking: has("thinking"),
+                embedding: has("embedding"),
+                context_length,
+            };
+        }
+
+        let template = response.template.as_deref().unwrap_or_default();
+        let families: Vec<&str> = response
+            .details
+            .as_ref()
+            .and_then(|d| d.families.as_ref())
+            .map(|f| f.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        let info = response.model_info_map().ok().flatten().unwrap_or_default();
+        let info_keys: Vec<&str> = info.keys().map(String::as_str).collect();
+
+        let embedding = info_keys.iter().any(|k| k.ends_with(".pooling_type"));
+        Self {
+            completion: !embedding,
+            tools: template.contains(".Tools"),
+            vision: families.iter().any(|f| matches!(*f, "clip" | "mllama"))
+                || info_keys.iter().any(|k| k.contains(".vision.")),
+            thinking: template.contains(".Think") || template.contains("<think>"),
+            embedding,
+            context_length,
+        }
+    }
+
+    /// Resolve capabilities for `name`, filling gaps from [`KnownModel`]
+    /// when the server gave no explicit `capabilities` list.
+    pub(crate) fn resolve(name: &str, response: &ShowResponse) -> Self {
+        let mut caps = Self::from_show_response(response);
+        if response.capabilities.is_none()
+            && let Some(known) = KnownModel::find(name)
+        {
+            caps.tools |= known.supports_tools();
+            caps.vision |= known.supports_vision();
+            caps.thinking |= known.supports_thinking();
+            caps.embedding |= known.is_embedding();
+            caps.completion = !caps.embedding;
+            caps.context_length = caps
+                .context_length
+                .or(Some(u64::from(known.context_length())));
+        }
+        caps
+    }
+}
+
+/// Reads `<architecture>.context_length` from the model metadata.
+fn context_length(response: &ShowResponse) -> Option<u64> {
+    let info = response.model_info_map().ok()??;
+    info.iter()
+        .find(|(k, _)| k.ends_with(".context_length"))
+        .and_then(|(_, v)| v.as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_reported_capabilities() {
+        let response: ShowResponse = serde_json::from_str(
+            r#"{
+                "capabilities": ["completion", "tools", "thinking"],
+                "model_info": {"qwen3.context_length": 40960}
+            }"#,
+        )
+        .unwrap();
+        let caps = ModelCapabilities::from_show_response(&response);
+        assert!(caps.completion && caps.tools && caps.thinking);
+        assert!(!caps.vision && !caps.embedding);
+        assert_eq!(caps.context_length, Some(40960));
+    }
+
+    #[test]
+    fn test_heuristics_without_capabilities_list() {
+        let response: ShowResponse = serde_json::from_str(
+            r#"{
+                "template": "{{ if .Tools }}tools{{ end }}{{ .Prompt }}",
+                "details": {"families": ["llama", "clip"]}
+            }"#,
+        )
+        .unwrap();
+        let caps = ModelCapabilities::from_show_response(&response);
+        assert!(caps.completion && caps.tools && caps.vision);
+        assert!(!caps.thinking);
+    }
+
+    #[test]
+    fn test_heuristics_detect_embedding_model() {
+        let response: ShowResponse =
+            serde_json::from_str(r#"{"model_info": {"nomic-bert.pooling_type": 1}}"#).unwrap();
+        let caps = ModelCapabilities::from_show_response(&response);
+        assert!(caps.embedding);
+        assert!(!caps.completion);
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_known_model() {
+        let caps = ModelCapabilities::resolve("gemma3:4b", &ShowResponse::default());
+        assert!(caps.vision);
+        assert!(caps.completion);
+        assert_eq!(caps.context_length, Some(131_072));
+    }
+
+    #[test]
+    fn test_resolve_trusts_reported_capabilities() {
+        let response = ShowResponse {
+            capabilities: Some(vec!["completion".to_string()]),
+            ..Default::default()
+        };
+        let caps = ModelCapabilities::resolve("gemma3", &response);
+        assert!(!caps.vision);
+    }
+}
@@ -1,5 +1,7 @@
 //! Model details primitive type
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
 /// Additional information about a model's format and family
@@ -40,3 +42,25 @@ pub struct ModelDetails {
     #[serde(default)]
     pub quantization_level: Option<String>,
 }
+
+impl ModelDetails {
+    /// Family, parameter size, and quantization level that are set, in that order
+    pub(crate) fn labels(&self) -> Vec<&str> {
+        [
+            self.family.as_deref(),
+            self.parameter_size.as_deref(),
+            self.quantization_level.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect()
+    }
+}
+
+/// Formats as `family, parameter size, quantization` (e.g. `gemma, 4.3B, Q4_K_M`).
+impl fmt::Display for ModelDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.labels().join(", "))
+    }
+}
@@ -0,0 +1,179 @@
+//! Model configuration diff primitive type
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{ParameterChange, ShowResponse, TextChange};
+
+/// What differs between the configurations of two models
+///
+/// Built by [`ShowResponse::diff`](super::ShowResponse::diff). Only
+/// differences are listed: an empty diff means the parameters, template,
+/// and system prompt all match. An empty template or system prompt counts
+/// as unset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ModelDiff {
+    /// Changed parameters, sorted by name
+    pub parameters: Vec<ParameterChange>,
+
+    /// The prompt template, if it changed
+    pub template: Option<TextChange>,
+
+    /// The system prompt, if it changed
+    pub system: Option<TextChange>,
+}
+
+impl ModelDiff {
+    pub(super) fn between(before: &ShowResponse, after: &ShowResponse) -> Self {
+        let mut params: BTreeMap<&str, (Vec<String>, Vec<String>)> = BTreeMap::new();
+        for (name, value) in parameter_lines(&before.parameters) {
+            params.entry(name).or_default().0.push(value.to_string());
+        }
+        for (name, value) in parameter_lines(&after.parameters) {
+            params.entry(name).or_default().1.push(value.to_string());
+        }
+        let parameters = params
+            .into_iter()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(name, (before, after))| ParameterChange {
+                name: name.to_string(),
+                before,
+                after,
+            })
+            .collect();
+
+        Self {
+            parameters,
+            template: text_change(&before.template, &after.template),
+            system: text_change(&before.system, &after.system),
+        }
+    }
+
+    /// Whether the two configurations match
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty() && self.template.is_none() && self.system.is_none()
+    }
+}
+
+/// Splits the `parameters` text into `(name, value)` pairs, one per line.
+fn parameter_lines(parameters: &Option<String>) -> impl Iterator<Item = (&str, &str)> {
+    parameters
+        .as_deref()
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (name, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            (!name.is_empty()).then(|| (name, value.trim()))
+        })
+}
+
+fn text_change(before: &Option<String>, after: &Option<String>) -> Option<TextChange> {
+    let set = |text: &Option<String>| text.clone().filter(|t| !t.is_empty());
+    let (before, after) = (set(before), set(after));
+    (before != after).then_some(TextChange { before, after })
+}
+
+/// Formats one line per difference; templates and system prompts are only
+/// flagged, as they are usually long.
+///
+/// ```
+/// use ollama_oxide::ShowResponse;
+///
+/// let v1: ShowResponse = serde_json::from_str(
+///     r#"{"parameters": "num_ctx 4096\nstop \"<|end|>\"", "template": "{{ .Prompt }}"}"#,
+/// ).unwrap();
+/// let v2: ShowResponse = serde_json::from_str(
+///     r#"{"parameters": "num_ctx 8192\ntemperature 0.2", "template": "{{ .System }}{{ .Prompt }}"}"#,
+/// ).unwrap();
+/// assert_eq!(
+///     v1.diff(&v2).to_string(),
+///     "num_ctx: 4096 -> 8192\nstop: \"<|end|>\" -> (unset)\ntemperature: (unset) -> 0.2\ntemplate changed"
+/// );
+/// ```
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn values(values: &[String]) -> String {
+            if values.is_empty() {
+                "(unset)".to_string()
+            } else {
+                values.join(", ")
+            }
+        }
+
+        let mut lines: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|p| format!("{}: {} -> {}", p.name, values(&p.before), values(&p.after)))
+            .collect();
+        for (label, change) in [("template", &self.template), ("system", &self.system)] {
+            if change.is_some() {
+                lines.push(format!("{} changed", label));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn show(parameters: &str) -> ShowResponse {
+        ShowResponse {
+            parameters: Some(parameters.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_repeated_parameters_compare_as_lists() {
+        let before = show("stop \"<|a|>\"\nstop \"<|b|>\"");
+        let after = show("stop   \"<|a|>\"\nstop   \"<|c|>\"");
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.parameters,
+            [ParameterChange {
+                name: "stop".into(),
+                before: vec!["\"<|a|>\"".into(), "\"<|b|>\"".into()],
+                after: vec!["\"<|a|>\"".into(), "\"<|c|>\"".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_added_and_removed_parameters() {
+        let diff = show("num_ctx 4096\n\n").diff(&show("top_k 20"));
+        assert_eq!(diff.parameters.len(), 2);
+        assert!(diff.parameters[0].is_removed());
+        assert!(diff.parameters[1].is_added());
+    }
+
+    #[test]
+    fn test_identical_models_have_empty_diff() {
+        let mut before = show("num_ctx 4096");
+        before.system = Some(String::new());
+        let diff = before.diff(&show("num_ctx    4096"));
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn test_system_change_keeps_both_texts() {
+        let before = ShowResponse {
+            system: Some("Be brief.".into()),
+            ..Default::default()
+        };
+        let diff = before.diff(&ShowResponse::default());
+        assert_eq!(
+            diff.system,
+            Some(TextChange {
+                before: Some("Be brief.".into()),
+                after: None,
+            })
+        );
+        assert_eq!(diff.to_string(), "system changed");
+    }
+}
@@ -1,8 +1,13 @@
 //! Model summary primitive type
 
+use std::fmt;
+use std::time::SystemTime;
+
 use serde::{Deserialize, Serialize};
 
 use super::ModelDetails;
+use crate::inference::humanize;
+use crate::server_time;
 
 /// Summary information for a locally available model
 ///
@@ -44,3 +49,89 @@ pub struct ModelSummary {
     #[serde(default)]
     pub details: Option<ModelDetails>,
 }
+
+/// Formats as `name (size, family, parameters, quantization, modified date)`.
+///
+/// ```
+/// use ollama_oxide::ModelSummary;
+///
+/// let model: ModelSummary = serde_json::from_str(r#"{
+///     "name": "gemma3",
+///     "modified_at": "2025-10-03T23:34:03.409490317-07:00",
+///     "size": 3338801804,
+///     "details": {"family": "gemma", "parameter_size": "4.3B", "quantization_level": "Q4_K_M"}
+/// }"#).unwrap();
+/// assert_eq!(
+///     model.to_string(),
+///     "gemma3 (3.3 GB, gemma, 4.3B, Q4_K_M, modified 2025-10-03)"
+/// );
+/// ```
+impl ModelSummary {
+    /// Get `modified_at` as seconds since the Unix epoch.
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339.
+    ///
+    /// ```
+    /// use ollama_oxide::ModelSummary;
+    ///
+    /// let model: ModelSummary = serde_json::from_str(
+    ///     r#"{"name": "gemma3", "modified_at": "2025-10-03T23:34:03.409490317-07:00"}"#,
+    /// ).unwrap();
+    /// assert_eq!(model.modified_at_unix(), Some(1_759_559_643));
+    /// ```
+    pub fn modified_at_unix(&self) -> Option<i64> {
+        self.modified_at
+            .as_deref()
+            .and_then(server_time::rfc3339_unix)
+    }
+
+    /// Get `modified_at` as a [`SystemTime`].
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339; the
+    /// raw string stays in `modified_at`.
+    pub fn modified_at_time(&self) -> Option<SystemTime> {
+        self.modified_at.as_deref().and_then(server_time::rfc3339)
+    }
+}
+
+impl fmt::Display for ModelSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(size) = self.size {
+            parts.push(humanize::bytes(size));
+        }
+        if let Some(details) = &self.details {
+            parts.extend(details.labels().into_iter().map(str::to_string));
+        }
+        if let Some(modified) = &self.modified_at {
+            parts.push(format!(
+                "modified {}",
+                modified.get(..10).unwrap_or(modified)
+            ));
+        }
+
+        if parts.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} ({})", self.name, parts.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name_only() {
+        let model: ModelSummary = serde_json::from_str(r#"{"name": "llama3.2"}"#).unwrap();
+        assert_eq!(model.to_string(), "llama3.2");
+    }
+
+    #[test]
+    fn test_display_size_only() {
+        let model: ModelSummary =
+            serde_json::from_str(r#"{"name": "llama3.2", "size": 2019393189}"#).unwrap();
+        assert_eq!(model.to_string(), "llama3.2 (2.0 GB)");
+    }
+}
@@ -0,0 +1,32 @@
+//! Parameter change primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// A Modelfile parameter that differs between two models
+///
+/// Values are kept as the server prints them. A parameter may be set more
+/// than once (e.g. several `stop` sequences), so each side lists every
+/// value in order; an empty list means the model does not set it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct ParameterChange {
+    /// Parameter name (e.g., "num_ctx")
+    pub name: String,
+
+    /// Values in the first model
+    pub before: Vec<String>,
+
+    /// Values in the second model
+    pub after: Vec<String>,
+}
+
+impl ParameterChange {
+    /// Whether only the second model sets the parameter
+    pub fn is_added(&self) -> bool {
+        self.before.is_empty()
+    }
+
+    /// Whether only the first model sets the parameter
+    pub fn is_removed(&self) -> bool {
+        self.after.is_empty()
+    }
+}
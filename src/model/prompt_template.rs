@@ -0,0 +1,263 @@
+//! Client-side rendering of a model's prompt template
+
+use serde_json::{Value, json};
+
+use super::ShowResponse;
+use super::template_eval::execute;
+use super::template_syntax::{Node, cut_after_response, mentions, parse};
+use crate::{ChatMessage, ChatRole, Result};
+
+/// A model's Go-style prompt template, parsed so prompts can be rendered
+/// locally to see exactly what the model is given.
+///
+/// Covers the parts of Go's `text/template` that model templates use:
+/// `{{ if }}` / `{{ else if }}` / `{{ else }}`, `{{ with }}`,
+/// `{{ range $i, $m := .Messages }}`, variables, pipelines, trim markers,
+/// comments, and the `and`, `or`, `not`, `eq`, `ne`, `lt`, `le`, `gt`,
+/// `ge`, `len`, `index`, `slice`, `print`, `printf`, `json`, and
+/// `currentDate` functions.
+///
+/// Rendering follows the server: templates that use `.Messages` see the
+/// whole conversation, with consecutive messages of one role merged and
+/// `.System` holding the system messages. Older templates written for
+/// `.System` / `.Prompt` / `.Response` are rendered once per turn, and the
+/// last turn stops right after `{{ .Response }}`, where the model's reply
+/// begins. No tools are offered, so `.Tools` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatMessage, PromptTemplate};
+///
+/// let template = PromptTemplate::parse(
+///     "{{ range .Messages }}<|{{ .Role }}|>{{ .Content }}\n{{ end }}<|assistant|>",
+/// )
+/// .unwrap();
+/// let prompt = template
+///     .render(&[ChatMessage::system("Be brief."), ChatMessage::user("Hi")])
+///     .unwrap();
+/// assert_eq!(prompt, "<|system|>Be brief.\n<|user|>Hi\n<|assistant|>");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    source: String,
+    nodes: Vec<Node>,
+    /// The template for the open last turn of a prompt-style template
+    last_turn: Vec<Node>,
+    uses_messages: bool,
+}
+
+impl PromptTemplate {
+    /// Parse a template, such as [`ShowResponse::template`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TemplateError`](crate::Error::TemplateError) if the
+    /// template is malformed or uses unsupported actions (`define`,
+    /// `template`, `block`, `break`, `continue`).
+    pub fn parse(source: &str) -> Result<Self> {
+        let nodes = parse(source)?;
+        Ok(Self {
+            source: source.to_string(),
+            last_turn: cut_after_response(&nodes),
+            uses_messages: mentions(&nodes, "Messages"),
+            nodes,
+        })
+    }
+
+    /// The template the server uses for models without one
+    pub(crate) fn fallback() -> Result<Self> {
+        Self::parse("{{ .Prompt }}")
+    }
+
+    /// The template text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the template renders the conversation from `.Messages`
+    /// rather than one `.Prompt` / `.Response` turn at a time
+    pub fn uses_messages(&self) -> bool {
+        self.uses_messages
+    }
+
+    /// Render the prompt the model sees for `messages`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TemplateError`](crate::Error::TemplateError) if the
+    /// template fails at runtime, e.g. calls an unknown function.
+    pub fn render(&self, messages: &[ChatMessage]) -> Result<String> {
+        let (system, messages) = collate(messages);
+        if self.uses_messages {
+            let data = json!({
+                "System": system,
+                "Messages": messages.iter().map(message_value).collect::<Vec<_>>(),
+                "Tools": [],
+                "Prompt": "",
+                "Response": "",
+                "Think": false,
+                "ThinkLevel": "",
+                "IsThinkSet": false,
+            });
+            return execute(&self.nodes, &data);
+        }
+
+        let mut out = String::new();
+        let mut turn = Turn {
+            system,
+            ..Turn::default()
+        };
+        for message in &messages {
+            match message.role {
+                ChatRole::System => {
+                    if !turn.prompt.is_empty() || !turn.response.is_empty() {
+                        out.push_str(&execute(&self.nodes, &turn.take())?);
+                    }
+                    turn.system = message.content.clone();
+                }
+                ChatRole::User => {
+                    if !turn.response.is_empty() {
+                        out.push_str(&execute(&self.nodes, &turn.take())?);
+                    }
+                    turn.prompt = message.content.clone();
+                }
+                ChatRole::Assistant => turn.response = message.content.clone(),
+                ChatRole::Tool => {}
+            }
+        }
+        out.push_str(&execute(&self.last_turn, &turn.take())?);
+        Ok(out)
+    }
+}
+
+/// Values of one turn of a prompt-style template
+#[derive(Default)]
+struct Turn {
+    system: String,
+    prompt: String,
+    response: String,
+}
+
+impl Turn {
+    /// The turn's template data, leaving the turn empty
+    fn take(&mut self) -> Value {
+        let turn = std::mem::take(self);
+        json!({
+            "System": turn.system,
+            "Prompt": turn.prompt,
+            "Response": turn.response,
+        })
+    }
+}
+
+/// Joins the system messages for `.System` and merges consecutive
+/// messages of the same role, except tool results, as the server does.
+fn collate(messages: &[ChatMessage]) -> (String, Vec<ChatMessage>) {
+    let mut system = Vec::new();
+    let mut collated: Vec<ChatMessage> = Vec::new();
+    for message in messages {
+        if message.role == ChatRole::System {
+            system.push(message.content.as_str());
+        }
+        match collated.last_mut() {
+            Some(last) if last.role == message.role && message.role != ChatRole::Tool => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&message.content);
+            }
+            _ => collated.push(message.clone()),
+        }
+    }
+    (system.join("\n\n"), collated)
+}
+
+/// A message as the server's templates see it
+fn message_value(message: &ChatMessage) -> Value {
+    #[cfg(feature = "tools")]
+    let tool_calls: Vec<Value> = message
+        .tool_calls
+        .iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, call)| {
+            json!({
+                "Function": {
+                    "Index": index,
+                    "Name": call.function_name().unwrap_or_default(),
+                    "Arguments": call.arguments(),
+                }
+            })
+        })
+        .collect();
+    #[cfg(not(feature = "tools"))]
+    let tool_calls: Vec<Value> = Vec::new();
+
+    json!({
+        "Role": message.role.to_string(),
+        "Content": message.content,
+        "Thinking": "",
+        "ToolName": "",
+        "ToolCalls": tool_calls,
+    })
+}
+
+impl ShowResponse {
+    /// Parse the model's [`template`](Self::template)
+    ///
+    /// Returns `Ok(None)` when the server sent no template.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TemplateError`](crate::Error::TemplateError) if the
+    /// template cannot be parsed; see [`PromptTemplate::parse`].
+    pub fn prompt_template(&self) -> Result<Option<PromptTemplate>> {
+        self.template
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .map(PromptTemplate::parse)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISTRAL: &str =
+        "{{ if .System }}{{ .System }} {{ end }}[INST] {{ .Prompt }} [/INST] {{ .Response }}</s>";
+
+    #[test]
+    fn test_prompt_template_renders_each_turn() {
+        let template = PromptTemplate::parse(MISTRAL).unwrap();
+        assert!(!template.uses_messages());
+        let prompt = template
+            .render(&[
+                ChatMessage::system("Be brief."),
+                ChatMessage::user("Hi"),
+                ChatMessage::assistant("Hello!"),
+                ChatMessage::user("Bye"),
+            ])
+            .unwrap();
+        assert_eq!(
+            prompt,
+            "Be brief. [INST] Hi [/INST] Hello!</s>[INST] Bye [/INST] "
+        );
+    }
+
+    #[test]
+    fn test_consecutive_messages_are_merged() {
+        let template = PromptTemplate::parse(
+            "{{ .System }}|{{ range .Messages }}{{ .Role }}={{ .Content }};{{ end }}",
+        )
+        .unwrap();
+        let prompt = template
+            .render(&[
+                ChatMessage::system("A"),
+                ChatMessage::system("B"),
+                ChatMessage::user("1"),
+                ChatMessage::user("2"),
+            ])
+            .unwrap();
+        assert_eq!(prompt, "A\n\nB|system=A\n\nB;user=1\n\n2;");
+    }
+}
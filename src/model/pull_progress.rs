@@ -0,0 +1,236 @@
+//! Coalesced pull progress for rendering
+
+use serde::{Deserialize, Serialize};
+
+use super::{LayerProgress, LayerState, PullResponse};
+
+/// Snapshot of a model pull, emitted only when something visible changed.
+///
+/// Produced by `progress_summary()` on a pull stream. The raw stream repeats
+/// near-identical events for every few kilobytes; the summary collapses
+/// them into phase changes (`pulling manifest`, `verifying sha256 digest`,
+/// ...), per-layer state transitions, and whole-percent steps of the
+/// overall download.
+///
+/// # Example
+///
+/// ```no_run
+/// use ollama_oxide::{OllamaApiAsync, OllamaClient, PullRequest};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::default()?;
+/// let stream = client.pull_model_stream(&PullRequest::new("qwen3:0.6b")).await?;
+/// let progress = stream.progress_summary();
+/// while let Some(update) = progress.next().await {
+///     let update = update?;
+///     println!("{:>5.1}% {}", update.percent(), update.status);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PullProgress {
+    /// Latest phase reported by the server (e.g. `pulling manifest`, `success`)
+    pub status: String,
+
+    /// Known layers in the order the server announced them
+    pub layers: Vec<LayerProgress>,
+}
+
+impl PullProgress {
+    /// Bytes received across all known layers
+    pub fn completed(&self) -> u64 {
+        self.layers.iter().map(|l| l.completed.min(l.total)).sum()
+    }
+
+    /// Total bytes across all known layers
+    pub fn total(&self) -> u64 {
+        self.layers.iter().map(|l| l.total).sum()
+    }
+
+    /// Overall percentage (0.0 to 100.0); 100.0 once the pull succeeded
+    pub fn percent(&self) -> f64 {
+        if self.is_success() {
+            return 100.0;
+        }
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.completed() as f64 / total as f64) * 100.0
+    }
+
+    /// Returns `true` once the server reported `success`
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// Folds raw pull events into [`PullProgress`] snapshots.
+#[derive(Debug, Default)]
+pub(crate) struct PullProgressTracker {
+    progress: PullProgress,
+    last_percent: Option<u32>,
+}
+
+impl PullProgressTracker {
+    /// Applies one event; returns a snapshot if anything visible changed.
+    pub(crate) fn update(&mut self, event: &PullResponse) -> Option<PullProgress> {
+        let mut changed = false;
+
+        if let Some(digest) = &event.digest {
+            changed |= self.update_layer(digest, event);
+        } else if let Some(status) = &event.status
+            && *status != self.progress.status
+        {
+            // Phases after the download imply every layer arrived.
+            if status.starts_with("verifying") || status == "success" {
+                for layer in &mut self.progress.layers {
+                    layer.completed = layer.total;
+                    layer.state = LayerState::Complete;
+                }
+            }
+            changed = true;
+        }
+        if let Some(status) = &event.status {
+            self.progress.status.clone_from(status);
+        }
+
+        let percent = self.progress.percent().floor() as u32;
+        if self.last_percent != Some(percent) {
+            self.last_percent = Some(percent);
+            changed = true;
+        }
+
+        changed.then(|| self.progress.clone())
+    }
+
+    /// Updates (or adds) a layer; returns `true` on a state transition.
+    fn update_layer(&mut self, digest: &str, event: &PullResponse) -> bool {
+        let total = event.total.unwrap_or(0);
+        let completed = event.completed.unwrap_or(0);
+        let state = if total > 0 && completed >= total {
+            LayerState::Complete
+        } else {
+            LayerState::Downloading
+        };
+
+        match self.progress.layers.iter_mut().find(|l| l.digest == digest) {
+            Some(layer) => {
+                layer.total = layer.total.max(total);
+                layer.completed = layer.completed.max(completed);
+                let transitioned = layer.state != state && layer.state != LayerState::Complete;
+                if transitioned {
+                    layer.state = state;
+                }
+                transitioned
+            }
+            None => {
+                self.progress.layers.push(LayerProgress {
+                    digest: digest.to_string(),
+                    total,
+                    completed,
+                    state,
+                });
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: &str) -> PullResponse {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_repeated_status_lines_coalesce() {
+        let mut tracker = PullProgressTracker::default();
+        assert!(
+            tracker
+                .update(&event(r#"{"status":"pulling manifest"}"#))
+                .is_some()
+        );
+        assert!(
+            tracker
+                .update(&event(r#"{"status":"pulling manifest"}"#))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_layer_updates_emit_on_whole_percent_only() {
+        let mut tracker = PullProgressTracker::default();
+        let first = tracker
+            .update(&event(
+                r#"{"status":"pulling a","digest":"sha256:a","total":1000,"completed":0}"#,
+            ))
+            .unwrap();
+        assert_eq!(first.layers.len(), 1);
+        assert_eq!(first.layers[0].state, LayerState::Downloading);
+
+        // 0.5% -> still 0 whole percent: suppressed
+        assert!(
+            tracker
+                .update(&event(
+                    r#"{"status":"pulling a","digest":"sha256:a","total":1000,"completed":5}"#
+                ))
+                .is_none()
+        );
+
+        let update = tracker
+            .update(&event(
+                r#"{"status":"pulling a","digest":"sha256:a","total":1000,"completed":500}"#,
+            ))
+            .unwrap();
+        assert_eq!(update.percent(), 50.0);
+    }
+
+    #[test]
+    fn test_layer_completion_transition() {
+        let mut tracker = PullProgressTracker::default();
+        tracker.update(&event(
+            r#"{"status":"pulling a","digest":"sha256:a","total":10,"completed":0}"#,
+        ));
+        tracker.update(&event(
+            r#"{"status":"pulling b","digest":"sha256:b","total":10,"completed":0}"#,
+        ));
+        let update = tracker
+            .update(&event(
+                r#"{"status":"pulling a","digest":"sha256:a","total":10,"completed":10}"#,
+            ))
+            .unwrap();
+
+        assert_eq!(update.layers[0].state, LayerState::Complete);
+        assert_eq!(update.layers[1].state, LayerState::Downloading);
+        assert_eq!(update.percent(), 50.0);
+    }
+
+    #[test]
+    fn test_success_completes_everything() {
+        let mut tracker = PullProgressTracker::default();
+        tracker.update(&event(
+            r#"{"status":"pulling a","digest":"sha256:a","total":10,"completed":3}"#,
+        ));
+        tracker.update(&event(r#"{"status":"verifying sha256 digest"}"#));
+        let done = tracker.update(&event(r#"{"status":"success"}"#)).unwrap();
+
+        assert!(done.is_success());
+        assert_eq!(done.percent(), 100.0);
+        assert_eq!(done.layers[0].state, LayerState::Complete);
+    }
+
+    #[test]
+    fn test_short_digest() {
+        let layer = LayerProgress {
+            digest: "sha256:b112e727c6f18875636c".to_string(),
+            total: 1,
+            completed: 0,
+            state: LayerState::Downloading,
+        };
+        assert_eq!(layer.short_digest(), "b112e727c6f1");
+    }
+}
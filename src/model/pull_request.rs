@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PullRequest {
     /// Name of the model to download (e.g., "llama3.2:latest", "gemma:7b")
     pub model: String,
@@ -80,6 +81,14 @@ impl PullRequest {
         self.insecure = Some(insecure);
         self
     }
+
+    /// Copy of this request with streaming enabled, for the streaming pull methods.
+    pub(crate) fn streaming(&self) -> Self {
+        Self {
+            stream: Some(true),
+            ..self.clone()
+        }
+    }
 }
 
 #[cfg(test)]
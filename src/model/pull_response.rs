@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 
 /// Response from POST /api/pull endpoint.
 ///
-/// Contains the status of the pull operation.
+/// Contains the status of the pull operation. When streaming, each event
+/// also carries the layer `digest` and byte counts while downloading.
 ///
 /// # JSON Example
 ///
@@ -11,11 +12,34 @@ use serde::{Deserialize, Serialize};
 ///   "status": "success"
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Streaming download event:
+///
+/// ```json
+/// {
+///   "status": "pulling b112e727c6f1",
+///   "digest": "sha256:b112e727c6f18875636c56a779790a590d705aec9e1c0eb5a97d51fc2a778583",
+///   "total": 13118,
+///   "completed": 4096
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct PullResponse {
     /// Status message indicating the result of the operation
     #[serde(default)]
     pub status: Option<String>,
+
+    /// Digest of the layer being downloaded (streaming only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    /// Total size of the layer in bytes (streaming only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+
+    /// Bytes of the layer downloaded so far (streaming only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
 }
 
 impl PullResponse {
@@ -46,13 +70,14 @@ mod tests {
     fn test_status_returns_value() {
         let response = PullResponse {
             status: Some("success".to_string()),
+            ..Default::default()
         };
         assert_eq!(response.status(), Some("success"));
     }
 
     #[test]
     fn test_status_returns_none_when_missing() {
-        let response = PullResponse { status: None };
+        let response = PullResponse::default();
         assert_eq!(response.status(), None);
     }
 
@@ -60,6 +85,7 @@ mod tests {
     fn test_is_success_true() {
         let response = PullResponse {
             status: Some("success".to_string()),
+            ..Default::default()
         };
         assert!(response.is_success());
     }
@@ -68,13 +94,14 @@ mod tests {
     fn test_is_success_false_on_other_status() {
         let response = PullResponse {
             status: Some("downloading".to_string()),
+            ..Default::default()
         };
         assert!(!response.is_success());
     }
 
     #[test]
     fn test_is_success_false_on_none() {
-        let response = PullResponse { status: None };
+        let response = PullResponse::default();
         assert!(!response.is_success());
     }
 
@@ -95,4 +122,15 @@ mod tests {
         assert_eq!(response.status(), None);
         assert!(!response.is_success());
     }
+
+    #[test]
+    fn test_deserialization_download_event() {
+        let json =
+            r#"{"status": "pulling abc", "digest": "sha256:abc", "total": 100, "completed": 40}"#;
+        let response: PullResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.digest.as_deref(), Some("sha256:abc"));
+        assert_eq!(response.total, Some(100));
+        assert_eq!(response.completed, Some(40));
+    }
 }
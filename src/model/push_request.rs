@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PushRequest {
     /// Name of the model to push (e.g., "namespace/model:tag")
     pub model: String,
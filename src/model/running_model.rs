@@ -1,8 +1,13 @@
 //! Running model primitive type
 
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
 use serde::{Deserialize, Serialize};
 
 use super::ModelDetails;
+use crate::inference::humanize;
+use crate::server_time;
 
 /// Information about a model currently loaded in memory
 ///
@@ -52,6 +57,102 @@ pub struct RunningModel {
     pub context_length: Option<u32>,
 }
 
+impl RunningModel {
+    /// Share of the model held in GPU memory, in percent (0-100)
+    pub fn gpu_percent(&self) -> Option<u8> {
+        match (self.size, self.size_vram) {
+            (Some(size), Some(vram)) if size > 0 => {
+                Some(((vram.min(size) as f64 / size as f64) * 100.0).round() as u8)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get `expires_at` as seconds since the Unix epoch.
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339.
+    pub fn expires_at_unix(&self) -> Option<i64> {
+        self.expires_at
+            .as_deref()
+            .and_then(server_time::rfc3339_unix)
+    }
+
+    /// Get `expires_at` as a [`SystemTime`].
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339; the
+    /// raw string stays in `expires_at`.
+    ///
+    /// ```
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use ollama_oxide::RunningModel;
+    ///
+    /// let model: RunningModel = serde_json::from_str(
+    ///     r#"{"model": "gemma3", "expires_at": "2025-10-17T16:47:07.5-07:00"}"#,
+    /// ).unwrap();
+    /// assert_eq!(
+    ///     model.expires_at_time(),
+    ///     Some(UNIX_EPOCH + Duration::from_millis(1_760_744_827_500))
+    /// );
+    /// ```
+    pub fn expires_at_time(&self) -> Option<SystemTime> {
+        self.expires_at.as_deref().and_then(server_time::rfc3339)
+    }
+
+    /// Time left until the model is unloaded, zero once `expires_at` has
+    /// passed.
+    ///
+    /// Returns `None` when `expires_at` is missing or not RFC 3339.
+    pub fn expires_in(&self) -> Option<Duration> {
+        let expires = self.expires_at_time()?;
+        Some(
+            expires
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        )
+    }
+}
+
+/// Formats as `model (size, GPU share, context length, expiry)`.
+///
+/// ```
+/// use ollama_oxide::RunningModel;
+///
+/// let model: RunningModel = serde_json::from_str(r#"{
+///     "model": "gemma3",
+///     "size": 6591830464,
+///     "size_vram": 5333539264,
+///     "context_length": 4096,
+///     "expires_at": "2025-10-17T16:47:07.93355-07:00"
+/// }"#).unwrap();
+/// assert_eq!(
+///     model.to_string(),
+///     "gemma3 (6.6 GB, 81% GPU, ctx 4096, expires 2025-10-17T16:47:07)"
+/// );
+/// ```
+impl fmt::Display for RunningModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(size) = self.size {
+            parts.push(humanize::bytes(size));
+        }
+        if let Some(percent) = self.gpu_percent() {
+            parts.push(format!("{}% GPU", percent));
+        }
+        if let Some(ctx) = self.context_length {
+            parts.push(format!("ctx {}", ctx));
+        }
+        if let Some(expires) = &self.expires_at {
+            parts.push(format!("expires {}", expires.get(..19).unwrap_or(expires)));
+        }
+
+        if parts.is_empty() {
+            write!(f, "{}", self.model)
+        } else {
+            write!(f, "{} ({})", self.model, parts.join(", "))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +207,40 @@ mod tests {
         assert_send_sync::<RunningModel>();
     }
 
+    #[test]
+    fn test_gpu_percent() {
+        let json = r#"{"model": "m", "size": 1000, "size_vram": 0}"#;
+        let model: RunningModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.gpu_percent(), Some(0));
+
+        let json = r#"{"model": "m", "size": 1000, "size_vram": 1000}"#;
+        let model: RunningModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.gpu_percent(), Some(100));
+    }
+
+    #[test]
+    fn test_expires_at_typed() {
+        let json = r#"{"model": "m", "expires_at": "2025-01-01T00:00:00Z"}"#;
+        let model: RunningModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.expires_at_unix(), Some(1_735_689_600));
+        assert_eq!(model.expires_in(), Some(Duration::ZERO));
+
+        let json = r#"{"model": "m", "expires_at": "2999-01-01T00:00:00Z"}"#;
+        let model: RunningModel = serde_json::from_str(json).unwrap();
+        assert!(model.expires_in().unwrap() > Duration::from_secs(86_400));
+
+        let json = r#"{"model": "m", "expires_at": "soon"}"#;
+        let model: RunningModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.expires_at_time(), None);
+        assert_eq!(model.expires_at.as_deref(), Some("soon"));
+    }
+
+    #[test]
+    fn test_display_minimal() {
+        let model: RunningModel = serde_json::from_str(r#"{"model": "llama3.2"}"#).unwrap();
+        assert_eq!(model.to_string(), "llama3.2");
+    }
+
     #[test]
     fn test_running_model_with_details() {
         let json = r#"{
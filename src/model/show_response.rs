@@ -1,8 +1,15 @@
 //! Show model response primitive type
 
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::{Map, Value};
 
-use super::ShowModelDetails;
+use super::{ModelDiff, ShowModelDetails, TensorInfo};
+use crate::{Result, server_time};
 
 /// Response from POST /api/show endpoint
 ///
@@ -26,7 +33,13 @@ use super::ShowModelDetails;
 ///   "modified_at": "2024-01-15T10:30:00Z"
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+///
+/// The large `model_info` and `tensors` sections are kept as raw JSON and
+/// only parsed when read through [`model_info_map`](Self::model_info_map),
+/// [`model_info_as`](Self::model_info_as), or
+/// [`tensor_infos`](Self::tensor_infos), so a verbose show of a large model
+/// costs little when those sections go unused.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ShowResponse {
     /// Model parameter settings serialized as text
     ///
@@ -50,22 +63,68 @@ pub struct ShowResponse {
     #[serde(default)]
     pub template: Option<String>,
 
+    /// The system prompt set in the model's Modelfile
+    #[serde(default)]
+    pub system: Option<String>,
+
     /// List of supported features (e.g., "completion", "vision")
     #[serde(default)]
     pub capabilities: Option<Vec<String>>,
 
-    /// Additional model metadata
+    /// Additional model metadata, as unparsed JSON
     ///
     /// This is a flexible key-value structure that contains
     /// model-specific information like attention head counts,
     /// context length, embedding dimensions, etc.
     ///
-    /// Use `serde_json::Value` to access nested properties.
-    #[serde(default)]
-    pub model_info: Option<serde_json::Value>,
+    /// Use [`model_info_map`](Self::model_info_map) or
+    /// [`model_info_as`](Self::model_info_as) to parse it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_info: Option<Box<RawValue>>,
+
+    /// Per-tensor metadata, as unparsed JSON (verbose requests only)
+    ///
+    /// Use [`tensor_infos`](Self::tensor_infos) to parse it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tensors: Option<Box<RawValue>>,
+}
+
+/// Raw sections compare by their JSON text.
+impl PartialEq for ShowResponse {
+    fn eq(&self, other: &Self) -> bool {
+        fn raw(value: &Option<Box<RawValue>>) -> Option<&str> {
+            value.as_deref().map(RawValue::get)
+        }
+        self.parameters == other.parameters
+            && self.license == other.license
+            && self.modified_at == other.modified_at
+            && self.details == other.details
+            && self.template == other.template
+            && self.system == other.system
+            && self.capabilities == other.capabilities
+            && raw(&self.model_info) == raw(&other.model_info)
+            && raw(&self.tensors) == raw(&other.tensors)
+    }
 }
 
 impl ShowResponse {
+    /// Get `modified_at` as seconds since the Unix epoch.
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339.
+    pub fn modified_at_unix(&self) -> Option<i64> {
+        self.modified_at
+            .as_deref()
+            .and_then(server_time::rfc3339_unix)
+    }
+
+    /// Get `modified_at` as a [`SystemTime`].
+    ///
+    /// Returns `None` when the timestamp is missing or not RFC 3339; the
+    /// raw string stays in `modified_at`.
+    pub fn modified_at_time(&self) -> Option<SystemTime> {
+        self.modified_at.as_deref().and_then(server_time::rfc3339)
+    }
+
     /// Check if the model supports a specific capability
     ///
     /// # Arguments
@@ -90,4 +149,150 @@ impl ShowResponse {
             .as_ref()
             .is_some_and(|caps| caps.iter().any(|c| c == capability))
     }
+
+    /// Parse `model_info` into a key-value map
+    ///
+    /// Returns `Ok(None)` when the server sent no `model_info`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if `model_info` is not a JSON object.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ollama_oxide::ShowResponse;
+    ///
+    /// let response: ShowResponse =
+    ///     serde_json::from_str(r#"{"model_info": {"llama.context_length": 8192}}"#).unwrap();
+    /// let info = response.model_info_map().unwrap().unwrap();
+    /// assert_eq!(info["llama.context_length"], 8192);
+    /// ```
+    pub fn model_info_map(&self) -> Result<Option<Map<String, Value>>> {
+        self.model_info_as()
+    }
+
+    /// Parse `model_info` into a caller-defined type
+    ///
+    /// Useful to pick out a few keys without building the full map.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if `model_info` does not match `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ollama_oxide::ShowResponse;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Arch {
+    ///     #[serde(rename = "general.architecture")]
+    ///     architecture: String,
+    /// }
+    ///
+    /// let response: ShowResponse = serde_json::from_str(
+    ///     r#"{"model_info": {"general.architecture": "llama", "llama.block_count": 32}}"#,
+    /// ).unwrap();
+    /// let arch: Arch = response.model_info_as().unwrap().unwrap();
+    /// assert_eq!(arch.architecture, "llama");
+    /// ```
+    pub fn model_info_as<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        parse_raw(&self.model_info)
+    }
+
+    /// Compare this model's configuration with `other`'s
+    ///
+    /// Reports the parameters, template, and system prompt that differ,
+    /// e.g. to audit what changed between `my-model:v1` and `my-model:v2`.
+    /// `self` is the "before" side.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ollama_oxide::ShowResponse;
+    ///
+    /// let v1: ShowResponse =
+    ///     serde_json::from_str(r#"{"parameters": "num_ctx 4096\ntemperature 0.7"}"#).unwrap();
+    /// let v2: ShowResponse = serde_json::from_str(
+    ///     r#"{"parameters": "num_ctx 8192\ntemperature 0.7", "system": "Be brief."}"#,
+    /// ).unwrap();
+    ///
+    /// let diff = v1.diff(&v2);
+    /// assert_eq!(diff.parameters[0].name, "num_ctx");
+    /// assert_eq!(diff.parameters[0].after, ["8192"]);
+    /// assert!(diff.template.is_none());
+    /// assert!(diff.system.is_some());
+    /// ```
+    pub fn diff(&self, other: &ShowResponse) -> ModelDiff {
+        ModelDiff::between(self, other)
+    }
+
+    /// Parse `tensors` into a list of [`TensorInfo`]
+    ///
+    /// Returns `Ok(None)` unless the response came from a verbose request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SerializationError`](crate::Error::SerializationError)
+    /// if `tensors` is malformed.
+    pub fn tensor_infos(&self) -> Result<Option<Vec<TensorInfo>>> {
+        parse_raw(&self.tensors)
+    }
+}
+
+fn parse_raw<T: DeserializeOwned>(raw: &Option<Box<RawValue>>) -> Result<Option<T>> {
+    raw.as_deref()
+        .map(|raw| serde_json::from_str(raw.get()))
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Formats as a short multi-line report (format, family, parameters,
+/// quantization, capabilities, first license line); absent fields are skipped.
+///
+/// ```
+/// use ollama_oxide::ShowResponse;
+///
+/// let response: ShowResponse = serde_json::from_str(r#"{
+///     "license": "MIT License\n\nCopyright...",
+///     "capabilities": ["completion", "vision"],
+///     "details": {"format": "gguf", "family": "llama", "parameter_size": "8B"}
+/// }"#).unwrap();
+/// assert_eq!(
+///     response.to_string(),
+///     "format: gguf\nfamily: llama\nparameters: 8B\ncapabilities: completion, vision\nlicense: MIT License"
+/// );
+/// ```
+impl fmt::Display for ShowResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines: Vec<String> = Vec::new();
+        if let Some(details) = &self.details {
+            let fields = [
+                ("format", &details.format),
+                ("family", &details.family),
+                ("parameters", &details.parameter_size),
+                ("quantization", &details.quantization_level),
+            ];
+            for (label, value) in fields {
+                if let Some(value) = value.as_deref().filter(|v| !v.is_empty()) {
+                    lines.push(format!("{}: {}", label, value));
+                }
+            }
+        }
+        if let Some(caps) = self.capabilities.as_ref().filter(|c| !c.is_empty()) {
+            lines.push(format!("capabilities: {}", caps.join(", ")));
+        }
+        if let Some(license) = self
+            .license
+            .as_deref()
+            .and_then(|l| l.lines().map(str::trim).find(|l| !l.is_empty()))
+        {
+            lines.push(format!("license: {}", license));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
 }
@@ -0,0 +1,479 @@
+//! Evaluator for parsed model templates
+//!
+//! Data is plain JSON: fields are object keys, and printing, truthiness,
+//! and the built-in functions follow Go's `text/template` for the values
+//! JSON can hold. Ollama's own `json` and `currentDate` functions are
+//! provided too.
+
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use super::template_syntax::{Node, Pipeline, Term};
+use crate::server_time::{civil_date, today};
+use crate::{Error, Result};
+
+fn error(message: impl Into<String>) -> Error {
+    Error::TemplateError(message.into())
+}
+
+/// Render `nodes` with `data` as both the dot and `$`.
+pub(super) fn execute(nodes: &[Node], data: &Value) -> Result<String> {
+    let mut exec = Exec {
+        out: String::new(),
+        vars: vec![("$".to_string(), data.clone())],
+    };
+    exec.nodes(nodes, data)?;
+    Ok(exec.out)
+}
+
+struct Exec {
+    out: String,
+    /// Variables in scope, innermost last
+    vars: Vec<(String, Value)>,
+}
+
+impl Exec {
+    fn nodes(&mut self, nodes: &[Node], dot: &Value) -> Result<()> {
+        nodes.iter().try_for_each(|node| self.node(node, dot))
+    }
+
+    fn node(&mut self, node: &Node, dot: &Value) -> Result<()> {
+        match node {
+            Node::Text(text) => self.out.push_str(text),
+            Node::Action(pipeline) => {
+                let value = self.pipeline(pipeline, dot)?;
+                if pipeline.vars.is_empty() {
+                    print(&mut self.out, &value);
+                }
+            }
+            Node::Branch {
+                branches,
+                otherwise,
+            } => {
+                let scope = self.vars.len();
+                let mut taken = false;
+                for branch in branches {
+                    let value = self.pipeline(&branch.pipeline, dot)?;
+                    if truthy(&value) {
+                        let dot = if branch.with { &value } else { dot };
+                        self.nodes(&branch.body, dot)?;
+                        taken = true;
+                        break;
+                    }
+                }
+                if !taken {
+                    self.nodes(otherwise, dot)?;
+                }
+                self.vars.truncate(scope);
+            }
+            Node::Range {
+                pipeline,
+                body,
+                otherwise,
+            } => {
+                let scope = self.vars.len();
+                // The declared variables are set per element, not to the
+                // collection
+                let collection = self.commands(&pipeline.commands, dot)?;
+                let elements: Vec<(Value, Value)> = match collection {
+                    Value::Array(items) => items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, item)| (Value::from(i), item))
+                        .collect(),
+                    Value::Object(map) => {
+                        let mut entries: Vec<_> = map.into_iter().collect();
+                        entries.sort_by(|a, b| a.0.cmp(&b.0));
+                        entries
+                            .into_iter()
+                            .map(|(key, value)| (Value::String(key), value))
+                            .collect()
+                    }
+                    Value::Number(n) if n.as_u64().is_some() => (0..n.as_u64().unwrap_or(0))
+                        .map(|i| (Value::from(i), Value::from(i)))
+                        .collect(),
+                    Value::Null => Vec::new(),
+                    other => return Err(error(format!("range can't iterate over {other}"))),
+                };
+                if elements.is_empty() {
+                    self.nodes(otherwise, dot)?;
+                }
+                for (key, element) in elements {
+                    match pipeline.vars.as_slice() {
+                        [value] => self.vars.push((value.clone(), element.clone())),
+                        [index, value] => {
+                            self.vars.push((index.clone(), key));
+                            self.vars.push((value.clone(), element.clone()));
+                        }
+                        _ => {}
+                    }
+                    self.nodes(body, &element)?;
+                    self.vars.truncate(scope);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pipeline(&mut self, pipeline: &Pipeline, dot: &Value) -> Result<Value> {
+        let value = self.commands(&pipeline.commands, dot)?;
+        for name in &pipeline.vars {
+            if pipeline.declare {
+                self.vars.push((name.clone(), value.clone()));
+            } else {
+                let slot = self
+                    .vars
+                    .iter_mut()
+                    .rev()
+                    .find(|(n, _)| n == name)
+                    .ok_or_else(|| error(format!("undefined variable {name}")))?;
+                slot.1 = value.clone();
+            }
+        }
+        Ok(value)
+    }
+
+    fn commands(&mut self, commands: &[Vec<Term>], dot: &Value) -> Result<Value> {
+        let mut piped = None;
+        for command in commands {
+            piped = Some(self.command(command, dot, piped)?);
+        }
+        Ok(piped.unwrap_or(Value::Null))
+    }
+
+    fn command(&mut self, terms: &[Term], dot: &Value, piped: Option<Value>) -> Result<Value> {
+        if let [Term::Function(name), args @ ..] = terms {
+            let mut values = args
+                .iter()
+                .map(|term| self.term(term, dot))
+                .collect::<Result<Vec<_>>>()?;
+            values.extend(piped);
+            return call(name, values);
+        }
+        match terms {
+            [term] if piped.is_none() => self.term(term, dot),
+            _ => Err(error("can't give arguments to a non-function")),
+        }
+    }
+
+    fn term(&mut self, term: &Term, dot: &Value) -> Result<Value> {
+        match term {
+            Term::Field(path) => fields(dot, path),
+            Term::Var(name, path) => {
+                let value = self
+                    .vars
+                    .iter()
+                    .rev()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| error(format!("undefined variable {name}")))?;
+                fields(value, path)
+            }
+            Term::Group(pipeline, path) => {
+                let value = self.pipeline(pipeline, dot)?;
+                fields(&value, path)
+            }
+            Term::Function(name) => call(name, Vec::new()),
+            Term::Literal(value) => Ok(value.clone()),
+        }
+    }
+}
+
+fn fields(value: &Value, path: &[String]) -> Result<Value> {
+    let mut value = value;
+    for field in path {
+        value = match value {
+            Value::Object(map) => map.get(field).unwrap_or(&Value::Null),
+            Value::Null => &Value::Null,
+            other => return Err(error(format!("can't evaluate field {field} in {other}"))),
+        };
+    }
+    Ok(value.clone())
+}
+
+/// Go's truthiness: false, 0, nil, and empty strings and collections are
+/// false.
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+/// Strings print as-is, nil as nothing, collections as JSON.
+fn print(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::String(s) => out.push_str(s),
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Result<Ordering> {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .zip(y.as_f64())
+            .and_then(|(x, y)| x.partial_cmp(&y))
+            .ok_or_else(|| error("incomparable numbers")),
+        (Value::String(x), Value::String(y)) => Ok(x.cmp(y)),
+        _ => Err(error(format!(
+            "incompatible types for comparison: {a} and {b}"
+        ))),
+    }
+}
+
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(_), Value::Number(_)) => matches!(compare(a, b), Ok(Ordering::Equal)),
+        _ => a == b,
+    }
+}
+
+fn index_of(value: &Value) -> Result<usize> {
+    value
+        .as_u64()
+        .and_then(|i| usize::try_from(i).ok())
+        .ok_or_else(|| error(format!("cannot index with {value}")))
+}
+
+fn arity(name: &str, args: &[Value], min: usize, max: usize) -> Result<()> {
+    if args.len() < min || args.len() > max {
+        return Err(error(format!(
+            "wrong number of arguments for {name}: {}",
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+fn call(name: &str, args: Vec<Value>) -> Result<Value> {
+    match name {
+        "and" | "or" => {
+            arity(name, &args, 1, usize::MAX)?;
+            let stop_on = name == "or";
+            let last = args.len() - 1;
+            Ok(args
+                .into_iter()
+                .enumerate()
+                .find(|(i, v)| truthy(v) == stop_on || *i == last)
+                .map(|(_, v)| v)
+                .unwrap_or_default())
+        }
+        "not" => {
+            arity(name, &args, 1, 1)?;
+            Ok(Value::Bool(!truthy(&args[0])))
+        }
+        "eq" => {
+            arity(name, &args, 2, usize::MAX)?;
+            Ok(Value::Bool(args[1..].iter().any(|b| equal(&args[0], b))))
+        }
+        "ne" => {
+            arity(name, &args, 2, 2)?;
+            Ok(Value::Bool(!equal(&args[0], &args[1])))
+        }
+        "lt" | "le" | "gt" | "ge" => {
+            arity(name, &args, 2, 2)?;
+            let ordering = compare(&args[0], &args[1])?;
+            Ok(Value::Bool(match name {
+                "lt" => ordering.is_lt(),
+                "le" => ordering.is_le(),
+                "gt" => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        "len" => {
+            arity(name, &args, 1, 1)?;
+            match &args[0] {
+                Value::String(s) => Ok(Value::from(s.len())),
+                Value::Array(items) => Ok(Value::from(items.len())),
+                Value::Object(map) => Ok(Value::from(map.len())),
+                other => Err(error(format!("len of {other}"))),
+            }
+        }
+        "index" => {
+            arity(name, &args, 1, usize::MAX)?;
+            let mut args = args.into_iter();
+            let mut value = args.next().unwrap_or_default();
+            for key in args {
+                value = match (&value, &key) {
+                    (Value::Array(items), _) => {
+                        let i = index_of(&key)?;
+                        items
+                            .get(i)
+                            .cloned()
+                            .ok_or_else(|| error(format!("index out of range: {i}")))?
+                    }
+                    (Value::Object(map), Value::String(k)) => {
+                        map.get(k).cloned().unwrap_or_default()
+                    }
+                    (Value::Null, _) => Value::Null,
+                    _ => return Err(error(format!("can't index {value} with {key}"))),
+                };
+            }
+            Ok(value)
+        }
+        "slice" => {
+            arity(name, &args, 1, 3)?;
+            let bounds = args[1..].iter().map(index_of).collect::<Result<Vec<_>>>()?;
+            let range = |len: usize| -> Result<(usize, usize)> {
+                let start = bounds.first().copied().unwrap_or(0);
+                let end = bounds.get(1).copied().unwrap_or(len);
+                if start > end || end > len {
+                    return Err(error(format!("slice bounds out of range [{start}:{end}]")));
+                }
+                Ok((start, end))
+            };
+            match &args[0] {
+                Value::Array(items) => {
+                    let (start, end) = range(items.len())?;
+                    Ok(Value::Array(items[start..end].to_vec()))
+                }
+                Value::String(s) => {
+                    let (start, end) = range(s.len())?;
+                    s.get(start..end)
+                        .map(Value::from)
+                        .ok_or_else(|| error("slice splits a character"))
+                }
+                other => Err(error(format!("can't slice {other}"))),
+            }
+        }
+        "json" => {
+            arity(name, &args, 1, 1)?;
+            Ok(Value::String(args[0].to_string()))
+        }
+        "print" => {
+            let mut out = String::new();
+            for (i, arg) in args.iter().enumerate() {
+                // Go puts spaces between operands when neither is a string
+                if i > 0 && !args[i - 1].is_string() && !arg.is_string() {
+                    out.push(' ');
+                }
+                print(&mut out, arg);
+            }
+            Ok(Value::String(out))
+        }
+        "printf" => {
+            arity(name, &args, 1, usize::MAX)?;
+            printf(&args[0], &args[1..]).map(Value::String)
+        }
+        "currentDate" => {
+            arity(name, &args, 0, 0)?;
+            Ok(Value::String(civil_date(today())))
+        }
+        _ => Err(error(format!("function \"{name}\" not defined"))),
+    }
+}
+
+/// `printf` with the `%s`, `%v`, `%d`, `%q`, and `%%` verbs
+fn printf(format: &Value, args: &[Value]) -> Result<String> {
+    let format = format
+        .as_str()
+        .ok_or_else(|| error("printf format must be a string"))?;
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some(verb @ ('s' | 'v' | 'd' | 'q')) => {
+                let arg = args
+                    .next()
+                    .ok_or_else(|| error(format!("missing argument for %{verb}")))?;
+                if verb == 'q' {
+                    out.push_str(
+                        &Value::String(
+                            arg.as_str().map_or_else(|| arg.to_string(), str::to_string),
+                        )
+                        .to_string(),
+                    );
+                } else {
+                    print(&mut out, arg);
+                }
+            }
+            other => {
+                return Err(error(format!(
+                    "unsupported printf verb %{}",
+                    other.map(String::from).unwrap_or_default()
+                )));
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::template_syntax::parse;
+    use super::*;
+    use serde_json::json;
+
+    fn render(source: &str, data: Value) -> Result<String> {
+        execute(&parse(source)?, &data)
+    }
+
+    #[test]
+    fn test_range_with_last_element_check() {
+        let source = r#"{{- range $i, $m := .Messages }}
+{{- $last := eq (len (slice $.Messages $i)) 1 -}}
+[{{ $m.Role }}:{{ .Content }}{{ if $last }}!{{ end }}]
+{{- end }}"#;
+        let data = json!({"Messages": [
+            {"Role": "user", "Content": "hi"},
+            {"Role": "assistant", "Content": "yo"}
+        ]});
+        assert_eq!(render(source, data).unwrap(), "[user:hi][assistant:yo!]");
+    }
+
+    #[test]
+    fn test_with_else_and_functions() {
+        let data = json!({"Tools": [{"name": "f"}], "System": "", "N": 3});
+        assert_eq!(
+            render(
+                r#"{{ with .Tools }}{{ json . }}{{ end }}|{{ if .System }}s{{ else if gt .N 2 }}big{{ else }}x{{ end }}"#,
+                data.clone()
+            )
+            .unwrap(),
+            r#"[{"name":"f"}]|big"#
+        );
+        assert_eq!(
+            render(r#"{{ and .N .System }}|{{ or .System "dflt" }}|{{ not .Missing }}|{{ index .Tools 0 "name" }}"#, data.clone()).unwrap(),
+            "|dflt|true|f"
+        );
+        assert_eq!(
+            render(
+                r#"{{ printf "%s=%d %q" "n" .N "x" }}|{{ .N | print "n" }}"#,
+                data
+            )
+            .unwrap(),
+            r#"n=3 "x"|n3"#
+        );
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        for source in [
+            "{{ $nope }}",
+            "{{ unknown 1 }}",
+            "{{ .A.B }}",
+            "{{ slice .A 5 }}",
+        ] {
+            assert!(
+                matches!(
+                    render(source, json!({"A": "x"})),
+                    Err(Error::TemplateError(_))
+                ),
+                "{source:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,669 @@
+//! Parser for the subset of Go `text/template` that model templates use
+//!
+//! Supports text with `{{- -}}` trimming, comments, `if` / `else if` /
+//! `else`, `with`, `range` (with `$i, $v :=`), variables, pipelines, and
+//! parenthesized calls. `define`, `template`, `block`, `break`, and
+//! `continue` are rejected.
+
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// A parsed template item
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Node {
+    Text(String),
+    /// `{{ pipeline }}`: prints the value unless it declares variables
+    Action(Pipeline),
+    /// An `if` or `with` chain, its `else if` / `else with` branches, and
+    /// the final `else`
+    Branch {
+        branches: Vec<Branch>,
+        otherwise: Vec<Node>,
+    },
+    /// `{{ range }}` with its optional `else`
+    Range {
+        pipeline: Pipeline,
+        body: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// One arm of a [`Node::Branch`]
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Branch {
+    /// `with` arms run their body with the value as the dot
+    pub(super) with: bool,
+    pub(super) pipeline: Pipeline,
+    pub(super) body: Vec<Node>,
+}
+
+/// `$a, $b := cmd | cmd`
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Pipeline {
+    /// Variables declared (`:=`) or assigned (`=`) by the pipeline
+    pub(super) vars: Vec<String>,
+    pub(super) declare: bool,
+    /// Each command's terms; a command's output is the last argument of
+    /// the next
+    pub(super) commands: Vec<Vec<Term>>,
+}
+
+/// An operand or function name within a command
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Term {
+    /// `.A.B`, or `.` itself when empty
+    Field(Vec<String>),
+    /// `$name.A.B`; `$` alone is the root data
+    Var(String, Vec<String>),
+    /// `(pipeline).A.B`
+    Group(Box<Pipeline>, Vec<String>),
+    Function(String),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Field(Vec<String>),
+    Var(String, Vec<String>),
+    Ident(String),
+    Literal(Value),
+    Declare,
+    Assign,
+    Comma,
+    Pipe,
+    Open,
+    /// `)` and the fields read from the group's value
+    Close(Vec<String>),
+}
+
+enum Item {
+    Text(String),
+    Action(Vec<Token>),
+}
+
+fn error(message: impl Into<String>) -> Error {
+    Error::TemplateError(message.into())
+}
+
+/// Parse `source` into nodes.
+pub(super) fn parse(source: &str) -> Result<Vec<Node>> {
+    let mut parser = Parser {
+        items: split(source)?.into_iter(),
+    };
+    match parser.list()? {
+        (nodes, Stop::Eof) => Ok(nodes),
+        (_, Stop::End) => Err(error("unexpected {{end}}")),
+        (_, Stop::Else(_)) => Err(error("unexpected {{else}}")),
+    }
+}
+
+/// Whether any field or variable path in `nodes` mentions `name`.
+pub(super) fn mentions(nodes: &[Node], name: &str) -> bool {
+    fn pipeline(p: &Pipeline, name: &str) -> bool {
+        p.commands.iter().flatten().any(|term| match term {
+            Term::Field(path) | Term::Var(_, path) => path.iter().any(|f| f == name),
+            Term::Group(inner, path) => path.iter().any(|f| f == name) || pipeline(inner, name),
+            Term::Function(_) | Term::Literal(_) => false,
+        })
+    }
+    nodes.iter().any(|node| match node {
+        Node::Text(_) => false,
+        Node::Action(p) => pipeline(p, name),
+        Node::Branch {
+            branches,
+            otherwise,
+        } => {
+            branches
+                .iter()
+                .any(|b| pipeline(&b.pipeline, name) || mentions(&b.body, name))
+                || mentions(otherwise, name)
+        }
+        Node::Range {
+            pipeline: p,
+            body,
+            otherwise,
+        } => pipeline(p, name) || mentions(body, name) || mentions(otherwise, name),
+    })
+}
+
+/// Copy of `nodes` that stops right after the first `.Response` field,
+/// dropping everything that would print after the reply, the way Ollama
+/// renders the open last turn of a prompt-style template.
+///
+/// Conditions of `if`, `with`, and `range` are not inspected, matching the
+/// server.
+pub(super) fn cut_after_response(nodes: &[Node]) -> Vec<Node> {
+    fn list(nodes: &[Node], cut: &mut bool) -> Vec<Node> {
+        nodes.iter().filter_map(|n| node(n, cut)).collect()
+    }
+    fn node(n: &Node, cut: &mut bool) -> Option<Node> {
+        if *cut {
+            return None;
+        }
+        Some(match n {
+            Node::Text(_) => n.clone(),
+            Node::Action(p) => Node::Action(pipeline(p, cut)?),
+            Node::Branch {
+                branches,
+                otherwise,
+            } => Node::Branch {
+                branches: branches
+                    .iter()
+                    .map(|b| Branch {
+                        with: b.with,
+                        pipeline: b.pipeline.clone(),
+                        body: list(&b.body, cut),
+                    })
+                    .collect(),
+                otherwise: list(otherwise, cut),
+            },
+            Node::Range {
+                pipeline,
+                body,
+                otherwise,
+            } => Node::Range {
+                pipeline: pipeline.clone(),
+                body: list(body, cut),
+                otherwise: list(otherwise, cut),
+            },
+        })
+    }
+    fn pipeline(p: &Pipeline, cut: &mut bool) -> Option<Pipeline> {
+        let mut commands = Vec::new();
+        for command in &p.commands {
+            let mut terms = Vec::new();
+            for term in command {
+                if *cut {
+                    continue;
+                }
+                match term {
+                    Term::Field(path) if path.iter().any(|f| f == "Response") => {
+                        *cut = true;
+                        terms.push(term.clone());
+                    }
+                    Term::Group(inner, path) => {
+                        terms.push(Term::Group(Box::new(pipeline(inner, cut)?), path.clone()))
+                    }
+                    _ => terms.push(term.clone()),
+                }
+            }
+            if terms.is_empty() {
+                return None;
+            }
+            commands.push(terms);
+        }
+        Some(Pipeline {
+            vars: p.vars.clone(),
+            declare: p.declare,
+            commands,
+        })
+    }
+    list(nodes, &mut false)
+}
+
+// ============================================================================
+// Lexing
+// ============================================================================
+
+/// Split `source` into text and action tokens, applying trim markers.
+fn split(source: &str) -> Result<Vec<Item>> {
+    let mut items = Vec::new();
+    let mut rest = source;
+    let mut trim_next = false;
+    loop {
+        let Some(open) = rest.find("{{") else {
+            push_text(&mut items, rest, trim_next, false);
+            return Ok(items);
+        };
+        let mut after = &rest[open + 2..];
+        let trim_left = after.starts_with('-') && after[1..].starts_with(char::is_whitespace);
+        if trim_left {
+            after = &after[1..];
+        }
+        push_text(&mut items, &rest[..open], trim_next, trim_left);
+
+        let body = after.trim_start();
+        let (tokens, remainder, trim_right) = if body.starts_with("/*") {
+            let close = body.find("*/").ok_or_else(|| error("unclosed comment"))?;
+            let tail = body[close + 2..].trim_start();
+            let (remainder, trim_right) = match tail.strip_prefix("-}}") {
+                Some(remainder) => (remainder, true),
+                None => (
+                    tail.strip_prefix("}}")
+                        .ok_or_else(|| error("comment must end its action"))?,
+                    false,
+                ),
+            };
+            (None, remainder, trim_right)
+        } else {
+            let (tokens, remainder, trim_right) = lex_action(after)?;
+            (Some(tokens), remainder, trim_right)
+        };
+        if let Some(tokens) = tokens {
+            if tokens.is_empty() {
+                return Err(error("empty action"));
+            }
+            items.push(Item::Action(tokens));
+        }
+        rest = remainder;
+        trim_next = trim_right;
+    }
+}
+
+fn push_text(items: &mut Vec<Item>, text: &str, trim_start: bool, trim_end: bool) {
+    let text = if trim_start { text.trim_start() } else { text };
+    let text = if trim_end { text.trim_end() } else { text };
+    if !text.is_empty() {
+        items.push(Item::Text(text.to_string()));
+    }
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Reads `.A.B` at the start of `s`; returns the names and the rest.
+fn lex_fields(mut s: &str) -> (Vec<String>, &str) {
+    let mut fields = Vec::new();
+    while let Some(after) = s.strip_prefix('.') {
+        let end = after.find(|c| !is_ident(c)).unwrap_or(after.len());
+        if end == 0 {
+            break;
+        }
+        fields.push(after[..end].to_string());
+        s = &after[end..];
+    }
+    (fields, s)
+}
+
+/// Lex one action up to its `}}`. Returns the tokens, the text after the
+/// action, and whether it ended with `-}}`.
+fn lex_action(mut s: &str) -> Result<(Vec<Token>, &str, bool)> {
+    let mut tokens = Vec::new();
+    loop {
+        s = s.trim_start();
+        if let Some(rest) = s.strip_prefix("-}}") {
+            return Ok((tokens, rest, true));
+        }
+        if let Some(rest) = s.strip_prefix("}}") {
+            return Ok((tokens, rest, false));
+        }
+        let c = s.chars().next().ok_or_else(|| error("unclosed action"))?;
+        let (token, rest) = match c {
+            '.' => {
+                let (fields, rest) = lex_fields(s);
+                if fields.is_empty() {
+                    (Token::Field(fields), &s[1..])
+                } else {
+                    (Token::Field(fields), rest)
+                }
+            }
+            '$' => {
+                let after = &s[1..];
+                let end = after.find(|c| !is_ident(c)).unwrap_or(after.len());
+                let (fields, rest) = lex_fields(&after[end..]);
+                (Token::Var(format!("${}", &after[..end]), fields), rest)
+            }
+            '"' => lex_string(s)?,
+            '`' => {
+                let close = s[1..]
+                    .find('`')
+                    .ok_or_else(|| error("unclosed raw string"))?;
+                (
+                    Token::Literal(Value::String(s[1..close + 1].to_string())),
+                    &s[close + 2..],
+                )
+            }
+            ':' if s.starts_with(":=") => (Token::Declare, &s[2..]),
+            '=' => (Token::Assign, &s[1..]),
+            ',' => (Token::Comma, &s[1..]),
+            '|' => (Token::Pipe, &s[1..]),
+            '(' => (Token::Open, &s[1..]),
+            ')' => {
+                let (fields, rest) = lex_fields(&s[1..]);
+                (Token::Close(fields), rest)
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => lex_number(s)?,
+            c if is_ident(c) => {
+                let end = s.find(|c| !is_ident(c)).unwrap_or(s.len());
+                let token = match &s[..end] {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "nil" => Token::Literal(Value::Null),
+                    ident => Token::Ident(ident.to_string()),
+                };
+                (token, &s[end..])
+            }
+            c => return Err(error(format!("unexpected '{c}' in action"))),
+        };
+        tokens.push(token);
+        s = rest;
+    }
+}
+
+fn lex_string(s: &str) -> Result<(Token, &str)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((Token::Literal(Value::String(value)), &s[i + 1..])),
+            '\\' => {
+                let (_, escaped) = chars.next().ok_or_else(|| error("unclosed string"))?;
+                value.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '0' => '\0',
+                    other => other,
+                });
+            }
+            '\n' => break,
+            c => value.push(c),
+        }
+    }
+    Err(error("unclosed string"))
+}
+
+fn lex_number(s: &str) -> Result<(Token, &str)> {
+    let end = s
+        .char_indices()
+        .find(|&(i, c)| {
+            !(c.is_ascii_alphanumeric() || c == '.' || (i == 0 && (c == '-' || c == '+')))
+                && !((c == '-' || c == '+') && s[..i].ends_with(['e', 'E']))
+        })
+        .map_or(s.len(), |(i, _)| i);
+    let text = &s[..end];
+    let value = match text.parse::<i64>() {
+        Ok(n) => Value::from(n),
+        Err(_) => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| error(format!("bad number '{text}'")))?,
+    };
+    Ok((Token::Literal(value), &s[end..]))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// How a list of nodes ended
+enum Stop {
+    Eof,
+    End,
+    /// `{{ else }}`, with any tokens after `else`
+    Else(Vec<Token>),
+}
+
+struct Parser {
+    items: std::vec::IntoIter<Item>,
+}
+
+impl Parser {
+    /// Nodes up to the next `end`, `else`, or the end of the template
+    fn list(&mut self) -> Result<(Vec<Node>, Stop)> {
+        let mut nodes = Vec::new();
+        while let Some(item) = self.items.next() {
+            let tokens = match item {
+                Item::Text(text) => {
+                    nodes.push(Node::Text(text));
+                    continue;
+                }
+                Item::Action(tokens) => tokens,
+            };
+            let keyword = match &tokens[0] {
+                Token::Ident(ident) => ident.as_str(),
+                _ => "",
+            };
+            match keyword {
+                "end" if tokens.len() == 1 => return Ok((nodes, Stop::End)),
+                "else" => return Ok((nodes, Stop::Else(tokens[1..].to_vec()))),
+                "if" | "with" => nodes.push(self.branch(keyword == "with", &tokens[1..])?),
+                "range" => nodes.push(self.range(&tokens[1..])?),
+                "end" | "define" | "template" | "block" | "break" | "continue" => {
+                    return Err(error(format!("{{{{{keyword}}}}} is not supported")));
+                }
+                _ => nodes.push(Node::Action(pipeline(&tokens)?)),
+            }
+        }
+        Ok((nodes, Stop::Eof))
+    }
+
+    fn branch(&mut self, with: bool, tokens: &[Token]) -> Result<Node> {
+        let mut branches = vec![Branch {
+            with,
+            pipeline: pipeline(tokens)?,
+            body: Vec::new(),
+        }];
+        loop {
+            let (body, stop) = self.list()?;
+            if let Some(branch) = branches.last_mut() {
+                branch.body = body;
+            }
+            match stop {
+                Stop::End => {
+                    return Ok(Node::Branch {
+                        branches,
+                        otherwise: Vec::new(),
+                    });
+                }
+                Stop::Else(rest) if rest.is_empty() => {
+                    return Ok(Node::Branch {
+                        branches,
+                        otherwise: self.closing_else()?,
+                    });
+                }
+                Stop::Else(rest) => {
+                    let with = match &rest[0] {
+                        Token::Ident(k) if k == "if" => false,
+                        Token::Ident(k) if k == "with" => true,
+                        _ => return Err(error("expected 'if' or 'with' after 'else'")),
+                    };
+                    branches.push(Branch {
+                        with,
+                        pipeline: pipeline(&rest[1..])?,
+                        body: Vec::new(),
+                    });
+                }
+                Stop::Eof => return Err(error("unclosed {{if}} or {{with}}")),
+            }
+        }
+    }
+
+    fn range(&mut self, tokens: &[Token]) -> Result<Node> {
+        let pipeline = pipeline(tokens)?;
+        let (body, stop) = self.list()?;
+        let otherwise = match stop {
+            Stop::End => Vec::new(),
+            Stop::Else(rest) if rest.is_empty() => self.closing_else()?,
+            Stop::Else(_) => return Err(error("{{range}} cannot have 'else if'")),
+            Stop::Eof => return Err(error("unclosed {{range}}")),
+        };
+        Ok(Node::Range {
+            pipeline,
+            body,
+            otherwise,
+        })
+    }
+
+    /// Body of a final `else`, which must end with `end`
+    fn closing_else(&mut self) -> Result<Vec<Node>> {
+        match self.list()? {
+            (nodes, Stop::End) => Ok(nodes),
+            (_, Stop::Else(_)) => Err(error("unexpected {{else}} after {{else}}")),
+            (_, Stop::Eof) => Err(error("unclosed {{else}}")),
+        }
+    }
+}
+
+fn pipeline(tokens: &[Token]) -> Result<Pipeline> {
+    let (vars, declare, rest) = match tokens {
+        [
+            Token::Var(a, p),
+            op @ (Token::Declare | Token::Assign),
+            rest @ ..,
+        ] if p.is_empty() => (vec![a.clone()], *op == Token::Declare, rest),
+        [
+            Token::Var(a, p),
+            Token::Comma,
+            Token::Var(b, q),
+            op @ (Token::Declare | Token::Assign),
+            rest @ ..,
+        ] if p.is_empty() && q.is_empty() => {
+            (vec![a.clone(), b.clone()], *op == Token::Declare, rest)
+        }
+        _ => (Vec::new(), false, tokens),
+    };
+    if rest.is_empty() {
+        return Err(error("missing value in pipeline"));
+    }
+
+    let mut commands = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    for (i, token) in rest.iter().enumerate() {
+        match token {
+            Token::Open => depth += 1,
+            Token::Close(_) => depth = depth.saturating_sub(1),
+            Token::Pipe if depth == 0 => {
+                commands.push(command(&rest[start..i])?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    commands.push(command(&rest[start..])?);
+    Ok(Pipeline {
+        vars,
+        declare,
+        commands,
+    })
+}
+
+fn command(tokens: &[Token]) -> Result<Vec<Term>> {
+    if tokens.is_empty() {
+        return Err(error("missing command in pipeline"));
+    }
+    let mut terms = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let term = match &tokens[i] {
+            Token::Field(path) => Term::Field(path.clone()),
+            Token::Var(name, path) => Term::Var(name.clone(), path.clone()),
+            Token::Ident(name) => Term::Function(name.clone()),
+            Token::Literal(value) => Term::Literal(value.clone()),
+            Token::Open => {
+                let mut depth = 0usize;
+                let close = tokens[i..]
+                    .iter()
+                    .position(|t| {
+                        match t {
+                            Token::Open => depth += 1,
+                            Token::Close(_) => depth -= 1,
+                            _ => {}
+                        }
+                        depth == 0
+                    })
+                    .ok_or_else(|| error("unclosed '('"))?
+                    + i;
+                let Token::Close(path) = &tokens[close] else {
+                    unreachable!("position stops at a closing token");
+                };
+                let inner = pipeline(&tokens[i + 1..close])?;
+                i = close;
+                Term::Group(Box::new(inner), path.clone())
+            }
+            token => return Err(error(format!("unexpected {token:?} in command"))),
+        };
+        terms.push(term);
+        i += 1;
+    }
+    Ok(terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_markers_and_comments() {
+        let nodes = parse("a  {{- /* note */ -}}  b {{- .X }}\n").unwrap();
+        assert_eq!(
+            nodes,
+            [
+                Node::Text("a".into()),
+                Node::Text("b".into()),
+                Node::Action(Pipeline {
+                    vars: vec![],
+                    declare: false,
+                    commands: vec![vec![Term::Field(vec!["X".into()])]],
+                }),
+                Node::Text("\n".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_declaration_and_groups() {
+        let nodes =
+            parse(r#"{{ range $i, $m := .Messages }}{{ $last := eq (len (slice $.Messages $i)) 1 }}{{ end }}"#)
+                .unwrap();
+        let Node::Range { pipeline, body, .. } = &nodes[0] else {
+            panic!("expected range, got {nodes:?}");
+        };
+        assert_eq!(pipeline.vars, ["$i", "$m"]);
+        assert!(pipeline.declare);
+        let Node::Action(last) = &body[0] else {
+            panic!("expected action");
+        };
+        assert_eq!(last.vars, ["$last"]);
+        assert!(matches!(last.commands[0][1], Term::Group(..)));
+    }
+
+    #[test]
+    fn test_else_if_chain() {
+        let nodes =
+            parse(r#"{{ if eq .Role "user" }}u{{ else if .Tools }}t{{ else }}o{{ end }}"#).unwrap();
+        let Node::Branch {
+            branches,
+            otherwise,
+        } = &nodes[0]
+        else {
+            panic!("expected branch");
+        };
+        assert_eq!(branches.len(), 2);
+        assert_eq!(otherwise, &[Node::Text("o".into())]);
+    }
+
+    #[test]
+    fn test_malformed_templates_are_errors() {
+        for source in [
+            "{{ if .X }}",
+            "{{ end }}",
+            "{{ .X ",
+            "{{ \"open }}",
+            "{{ }}",
+            "{{ template \"x\" }}",
+            "{{ (len .X }}",
+        ] {
+            assert!(
+                matches!(parse(source), Err(Error::TemplateError(_))),
+                "{source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cut_after_response() {
+        let nodes =
+            parse("<s>{{ .Prompt }}[/INST]{{ .Response }}</s>{{ if .X }}x{{ end }}").unwrap();
+        let cut = cut_after_response(&nodes);
+        assert_eq!(cut.len(), 4);
+        assert!(
+            matches!(&cut[3], Node::Action(p) if p.commands[0][0] == Term::Field(vec!["Response".into()]))
+        );
+    }
+}
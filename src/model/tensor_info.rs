@@ -0,0 +1,27 @@
+//! Tensor metadata primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// One tensor from the `tensors` list of a verbose POST /api/show response
+///
+/// Parsed on demand by [`ShowResponse::tensor_infos`](super::ShowResponse::tensor_infos).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TensorInfo {
+    /// Tensor name (e.g., "blk.0.attn_q.weight")
+    pub name: String,
+
+    /// Storage type (e.g., "Q4_K", "F32")
+    #[serde(rename = "type")]
+    pub tensor_type: String,
+
+    /// Dimensions of the tensor
+    #[serde(default)]
+    pub shape: Vec<u64>,
+}
+
+impl TensorInfo {
+    /// Number of elements in the tensor (product of `shape`)
+    pub fn element_count(&self) -> u64 {
+        self.shape.iter().product()
+    }
+}
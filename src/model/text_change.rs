@@ -0,0 +1,16 @@
+//! Text change primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// A text setting, such as a template or system prompt, that differs
+/// between two models
+///
+/// `None` means the model does not set it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TextChange {
+    /// The text in the first model
+    pub before: Option<String>,
+
+    /// The text in the second model
+    pub after: Option<String>,
+}
@@ -0,0 +1,253 @@
+//! Well-known model descriptor and catalog constants
+
+use std::fmt;
+
+use crate::{ChatMessage, ChatRequest, EmbedInput, EmbedRequest, GenerateRequest};
+
+/// A popular model from the Ollama library with its known capabilities.
+///
+/// Use the associated constants instead of bare strings; the value converts
+/// into the model name wherever one is expected.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::{ChatMessage, KnownModel};
+///
+/// let model = KnownModel::LLAMA3_2;
+/// assert!(model.supports_tools());
+/// assert_eq!(model.context_length(), 131_072);
+///
+/// let request = model.chat([ChatMessage::user("Hello!")]);
+/// assert_eq!(request.model, "llama3.2");
+///
+/// let tagged = model.with_tag("1b");
+/// assert_eq!(tagged, "llama3.2:1b");
+///
+/// assert_eq!(KnownModel::find("qwen3:0.6b"), Some(KnownModel::QWEN3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KnownModel {
+    name: &'static str,
+    context_length: u32,
+    tools: bool,
+    vision: bool,
+    thinking: bool,
+    embedding: bool,
+}
+
+impl KnownModel {
+    /// Meta Llama 3.1 (8B, 70B, 405B)
+    pub const LLAMA3_1: Self = Self::chat_model("llama3.1", 131_072).with_tools();
+    /// Meta Llama 3.2 (1B, 3B)
+    pub const LLAMA3_2: Self = Self::chat_model("llama3.2", 131_072).with_tools();
+    /// Meta Llama 3.2 Vision (11B, 90B)
+    pub const LLAMA3_2_VISION: Self = Self::chat_model("llama3.2-vision", 131_072).with_vision();
+    /// Meta Llama 3.3 (70B)
+    pub const LLAMA3_3: Self = Self::chat_model("llama3.3", 131_072).with_tools();
+    /// Mistral 7B
+    pub const MISTRAL: Self = Self::chat_model("mistral", 32_768).with_tools();
+    /// Mistral NeMo (12B)
+    pub const MISTRAL_NEMO: Self = Self::chat_model("mistral-nemo", 131_072).with_tools();
+    /// Qwen 2.5 (0.5B to 72B)
+    pub const QWEN2_5: Self = Self::chat_model("qwen2.5", 32_768).with_tools();
+    /// Qwen 2.5 Coder (0.5B to 32B)
+    pub const QWEN2_5_CODER: Self = Self::chat_model("qwen2.5-coder", 32_768).with_tools();
+    /// Qwen 3 (0.6B to 235B)
+    pub const QWEN3: Self = Self::chat_model("qwen3", 40_960)
+        .with_tools()
+        .with_thinking();
+    /// Google Gemma 3 (1B to 27B)
+    pub const GEMMA3: Self = Self::chat_model("gemma3", 131_072).with_vision();
+    /// Microsoft Phi-4 (14B)
+    pub const PHI4: Self = Self::chat_model("phi4", 16_384);
+    /// DeepSeek-R1 (1.5B to 671B)
+    pub const DEEPSEEK_R1: Self = Self::chat_model("deepseek-r1", 131_072)
+        .with_tools()
+        .with_thinking();
+    /// LLaVA (7B, 13B, 34B)
+    pub const LLAVA: Self = Self::chat_model("llava", 4_096).with_vision();
+    /// Nomic text embeddings (137M)
+    pub const NOMIC_EMBED_TEXT: Self = Self::embedding_model("nomic-embed-text", 8_192);
+    /// mixedbread.ai large embeddings (335M)
+    pub const MXBAI_EMBED_LARGE: Self = Self::embedding_model("mxbai-embed-large", 512);
+    /// Sentence-transformers all-MiniLM embeddings (23M)
+    pub const ALL_MINILM: Self = Self::embedding_model("all-minilm", 512);
+
+    /// Every model in the catalog
+    pub const ALL: &'static [Self] = &[
+        Self::LLAMA3_1,
+        Self::LLAMA3_2,
+        Self::LLAMA3_2_VISION,
+        Self::LLAMA3_3,
+        Self::MISTRAL,
+        Self::MISTRAL_NEMO,
+        Self::QWEN2_5,
+        Self::QWEN2_5_CODER,
+        Self::QWEN3,
+        Self::GEMMA3,
+        Self::PHI4,
+        Self::DEEPSEEK_R1,
+        Self::LLAVA,
+        Self::NOMIC_EMBED_TEXT,
+        Self::MXBAI_EMBED_LARGE,
+        Self::ALL_MINILM,
+    ];
+
+    const fn chat_model(name: &'static str, context_length: u32) -> Self {
+        Self {
+            name,
+            context_length,
+            tools: false,
+            vision: false,
+            thinking: false,
+            embedding: false,
+        }
+    }
+
+    const fn embedding_model(name: &'static str, context_length: u32) -> Self {
+        let mut model = Self::chat_model(name, context_length);
+        model.embedding = true;
+        model
+    }
+
+    const fn with_tools(mut self) -> Self {
+        self.tools = true;
+        self
+    }
+
+    const fn with_vision(mut self) -> Self {
+        self.vision = true;
+        self
+    }
+
+    const fn with_thinking(mut self) -> Self {
+        self.thinking = true;
+        self
+    }
+
+    /// Look up a model by name, ignoring any tag and namespace.
+    ///
+    /// `qwen3:0.6b`, `library/qwen3`, and `registry.ollama.ai/library/qwen3:latest`
+    /// all resolve to [`KnownModel::QWEN3`].
+    pub fn find(name: &str) -> Option<Self> {
+        let base = name.rsplit('/').next().unwrap_or(name);
+        let base = base.split(':').next().unwrap_or(base);
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|m| m.name.eq_ignore_ascii_case(base))
+    }
+
+    /// Model name without a tag (resolves to `:latest`)
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Maximum context window in tokens
+    pub const fn context_length(&self) -> u32 {
+        self.context_length
+    }
+
+    /// Whether the model supports tool calling
+    pub const fn supports_tools(&self) -> bool {
+        self.tools
+    }
+
+    /// Whether the model accepts images
+    pub const fn supports_vision(&self) -> bool {
+        self.vision
+    }
+
+    /// Whether the model can emit a separate thinking trace
+    pub const fn supports_thinking(&self) -> bool {
+        self.thinking
+    }
+
+    /// Whether the model produces embeddings rather than text
+    pub const fn is_embedding(&self) -> bool {
+        self.embedding
+    }
+
+    /// Model name with an explicit tag (e.g. `llama3.2:1b`)
+    pub fn with_tag(&self, tag: &str) -> String {
+        format!("{}:{}", self.name, tag)
+    }
+
+    /// Build a chat request for this model
+    pub fn chat<I>(&self, messages: I) -> ChatRequest
+    where
+        I: IntoIterator<Item = ChatMessage>,
+    {
+        ChatRequest::new(self.name, messages)
+    }
+
+    /// Build a generate request for this model
+    pub fn generate(&self, prompt: impl Into<String>) -> GenerateRequest {
+        GenerateRequest::new(self.name, prompt)
+    }
+
+    /// Build an embed request for this model
+    pub fn embed(&self, input: impl Into<EmbedInput>) -> EmbedRequest {
+        EmbedRequest::new(self.name, input)
+    }
+}
+
+impl fmt::Display for KnownModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl From<KnownModel> for String {
+    fn from(model: KnownModel) -> Self {
+        model.name.to_string()
+    }
+}
+
+impl AsRef<str> for KnownModel {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_strips_tag_and_namespace() {
+        assert_eq!(KnownModel::find("llama3.2"), Some(KnownModel::LLAMA3_2));
+        assert_eq!(KnownModel::find("llama3.2:3b"), Some(KnownModel::LLAMA3_2));
+        assert_eq!(
+            KnownModel::find("registry.ollama.ai/library/llama3.2-vision:latest"),
+            Some(KnownModel::LLAMA3_2_VISION)
+        );
+        assert_eq!(KnownModel::find("my-finetune"), None);
+    }
+
+    #[test]
+    fn test_catalog_names_are_unique() {
+        let mut names: Vec<_> = KnownModel::ALL.iter().map(|m| m.name()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), KnownModel::ALL.len());
+    }
+
+    #[test]
+    fn test_embedding_models_have_no_chat_capabilities() {
+        for model in KnownModel::ALL.iter().filter(|m| m.is_embedding()) {
+            assert!(!model.supports_tools(), "{}", model);
+            assert!(!model.supports_vision(), "{}", model);
+        }
+    }
+
+    #[test]
+    fn test_converts_into_model_name() {
+        let request = KnownModel::NOMIC_EMBED_TEXT.embed("hello");
+        assert_eq!(request.model, "nomic-embed-text");
+
+        let name: String = KnownModel::MISTRAL.into();
+        assert_eq!(name, "mistral");
+    }
+}
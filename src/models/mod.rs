@@ -0,0 +1,8 @@
+//! Well-known model catalog
+//!
+//! Typed constants for popular models, with their context window and
+//! capabilities, to avoid stringly-typed model names.
+
+mod known_model;
+
+pub use known_model::KnownModel;
@@ -0,0 +1,35 @@
+//! Local Ollama server process management
+//!
+//! This module launches `ollama serve` as a child process, waits until it
+//! answers HTTP requests, and terminates it when the handle is dropped.
+//! Useful for integration tests and desktop applications that bundle Ollama.
+//!
+//! # Feature Flag
+//!
+//! This module requires the `server` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! ollama-oxide = { version = "0.2", features = ["server"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::server::{OllamaServerHandle, ServerOptions};
+//! use ollama_oxide::OllamaApiAsync;
+//!
+//! # async fn example() -> ollama_oxide::Result<()> {
+//! let server = OllamaServerHandle::spawn(ServerOptions::new().with_port(11500)).await?;
+//! let version = server.client().version().await?;
+//! println!("Spawned Ollama {}", version.version);
+//! // The process is killed when `server` goes out of scope.
+//! # Ok(())
+//! # }
+//! ```
+
+mod ollama_server_handle;
+mod server_options;
+
+pub use ollama_server_handle::OllamaServerHandle;
+pub use server_options::ServerOptions;
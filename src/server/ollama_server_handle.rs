@@ -0,0 +1,220 @@
+//! Handle to a locally spawned `ollama serve` process
+
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use super::ServerOptions;
+use crate::{ClientConfig, Error, OllamaApiAsync, OllamaApiSync, OllamaClient, Result};
+
+/// Delay between readiness probes while the server starts.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Timeout for each readiness probe; readiness is polled, not waited on.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A running `ollama serve` child process.
+///
+/// The process is killed (and reaped) when the handle is dropped, so a
+/// server spawned for a test or a desktop session never outlives its owner.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::server::{OllamaServerHandle, ServerOptions};
+/// use ollama_oxide::OllamaApiSync;
+///
+/// # fn example() -> ollama_oxide::Result<()> {
+/// let server = OllamaServerHandle::spawn_blocking(ServerOptions::new().with_port(11500))?;
+/// let version = server.client().version_blocking()?;
+/// println!("Ollama {} ready at {}", version.version, server.base_url());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OllamaServerHandle {
+    child: Child,
+    client: OllamaClient,
+    base_url: String,
+}
+
+impl OllamaServerHandle {
+    /// Launch `ollama serve` and wait (async) until it answers `GET /api/version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ServerProcessError`] if a server already answers at
+    /// the configured address, or the executable cannot be started, exits
+    /// before becoming ready, or does not answer within the startup timeout.
+    pub async fn spawn(options: ServerOptions) -> Result<Self> {
+        let probe = Self::probe_client(&options)?;
+        if probe.version().await.is_ok() {
+            return Err(Self::address_in_use(&options));
+        }
+        let mut handle = Self::launch(&options)?;
+        let deadline = Instant::now() + options.startup_timeout();
+
+        loop {
+            let ready = probe.version().await.is_ok();
+            // A probe answered by some other server must not count as ours.
+            handle.check_running(deadline, &options)?;
+            if ready {
+                return Ok(handle);
+            }
+            tokio::time::sleep(READINESS_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Launch `ollama serve` and wait (blocking) until it answers `GET /api/version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ServerProcessError`] if a server already answers at
+    /// the configured address, or the executable cannot be started, exits
+    /// before becoming ready, or does not answer within the startup timeout.
+    pub fn spawn_blocking(options: ServerOptions) -> Result<Self> {
+        let probe = Self::probe_client(&options)?;
+        if probe.version_blocking().is_ok() {
+            return Err(Self::address_in_use(&options));
+        }
+        let mut handle = Self::launch(&options)?;
+        let deadline = Instant::now() + options.startup_timeout();
+
+        loop {
+            let ready = probe.version_blocking().is_ok();
+            // A probe answered by some other server must not count as ours.
+            handle.check_running(deadline, &options)?;
+            if ready {
+                return Ok(handle);
+            }
+            std::thread::sleep(READINESS_POLL_INTERVAL);
+        }
+    }
+
+    /// Returns a client configured for the spawned server, with the default
+    /// timeout and retry settings
+    pub fn client(&self) -> &OllamaClient {
+        &self.client
+    }
+
+    /// Returns the base URL of the spawned server
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the operating system process id of the server
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Stop the server now instead of waiting for the handle to be dropped
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ServerProcessError`] if the process cannot be killed.
+    pub fn shutdown(mut self) -> Result<()> {
+        self.terminate()
+    }
+
+    /// Client for readiness probes only. Probes use a short timeout and no
+    /// retries; readiness is polled instead.
+    fn probe_client(options: &ServerOptions) -> Result<OllamaClient> {
+        let config = ClientConfig::new(options.base_url(), PROBE_TIMEOUT, 0)?;
+        OllamaClient::new(config)
+    }
+
+    /// The child would fail to bind, while the existing server answered
+    /// every probe in its place.
+    fn address_in_use(options: &ServerOptions) -> Error {
+        Error::ServerProcessError(format!(
+            "a server is already listening at {}",
+            options.base_url()
+        ))
+    }
+
+    fn launch(options: &ServerOptions) -> Result<Self> {
+        let base_url = options.base_url();
+        let client = OllamaClient::with_base_url(base_url.clone())?;
+        let mut command = Command::new(options.binary());
+        command.arg("serve").envs(options.environment());
+        if options.inherit_output() {
+            command.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+        } else {
+            command.stdout(Stdio::null()).stderr(Stdio::null());
+        }
+
+        let child = command.spawn().map_err(|e| {
+            Error::ServerProcessError(format!(
+                "failed to start {}: {}",
+                options.binary().display(),
+                e
+            ))
+        })?;
+
+        Ok(Self {
+            child,
+            client,
+            base_url,
+        })
+    }
+
+    /// Fails if the child has exited or the startup deadline has passed.
+    fn check_running(&mut self, deadline: Instant, options: &ServerOptions) -> Result<()> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => {
+                return Err(Error::ServerProcessError(format!(
+                    "server exited before becoming ready ({})",
+                    status
+                )));
+            }
+            Ok(None) => {}
+            Err(e) => return Err(Error::ServerProcessError(e.to_string())),
+        }
+
+        if Instant::now() >= deadline {
+            return Err(Error::ServerProcessError(format!(
+                "server did not become ready within {:?}",
+                options.startup_timeout()
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn terminate(&mut self) -> Result<()> {
+        if let Ok(Some(_)) = self.child.try_wait() {
+            return Ok(());
+        }
+        self.child
+            .kill()
+            .map_err(|e| Error::ServerProcessError(e.to_string()))?;
+        self.child
+            .wait()
+            .map_err(|e| Error::ServerProcessError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl Drop for OllamaServerHandle {
+    fn drop(&mut self) {
+        let _ = self.terminate();
+    }
+}
+
+// The test runs the Unix `yes` binary as a stand-in server.
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_does_not_carry_probe_timeout() {
+        // `yes serve` stays alive without binding the port.
+        let options = ServerOptions::new().with_binary("yes").with_port(1);
+        let handle = OllamaServerHandle::launch(&options).unwrap();
+
+        let config = &handle.client().config;
+        assert_ne!(config.timeout(), PROBE_TIMEOUT);
+        assert_eq!(config.timeout(), ClientConfig::default().timeout());
+        assert_eq!(config.max_retries(), ClientConfig::default().max_retries());
+        assert_eq!(handle.base_url(), "http://127.0.0.1:1");
+    }
+}
@@ -0,0 +1,208 @@
+//! Options for spawning a local Ollama server
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Options used by [`OllamaServerHandle::spawn`](super::OllamaServerHandle::spawn)
+/// to launch `ollama serve`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::server::ServerOptions;
+/// use std::time::Duration;
+///
+/// let options = ServerOptions::new()
+///     .with_port(11500)
+///     .with_models_dir("/tmp/ollama-models")
+///     .with_env("OLLAMA_DEBUG", "1")
+///     .with_startup_timeout(Duration::from_secs(60));
+/// assert_eq!(options.base_url(), "http://127.0.0.1:11500");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerOptions {
+    /// Path or name of the `ollama` executable
+    binary: PathBuf,
+
+    /// Host interface the server binds to
+    host: String,
+
+    /// TCP port the server listens on
+    port: u16,
+
+    /// Directory where the server stores models (`OLLAMA_MODELS`)
+    models_dir: Option<PathBuf>,
+
+    /// Additional environment variables for the child process
+    env: Vec<(String, String)>,
+
+    /// How long to wait for the server to answer requests
+    startup_timeout: Duration,
+
+    /// Whether the child inherits stdout/stderr instead of discarding them
+    inherit_output: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            binary: PathBuf::from("ollama"),
+            host: "127.0.0.1".to_string(),
+            port: 11434,
+            models_dir: None,
+            env: Vec::new(),
+            startup_timeout: Duration::from_secs(30),
+            inherit_output: false,
+        }
+    }
+}
+
+impl ServerOptions {
+    /// Create options with defaults (`ollama` from `PATH`, `127.0.0.1:11434`, 30s startup timeout)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the path of the `ollama` executable
+    pub fn with_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = binary.into();
+        self
+    }
+
+    /// Set the host interface to bind
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Set the port to listen on
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Set the models directory (exported as `OLLAMA_MODELS`)
+    pub fn with_models_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.models_dir = Some(dir.into());
+        self
+    }
+
+    /// Add an environment variable for the server process
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set how long to wait for the server to become ready
+    pub fn with_startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Forward the server's stdout/stderr to this process instead of discarding them
+    pub fn with_inherit_output(mut self, inherit: bool) -> Self {
+        self.inherit_output = inherit;
+        self
+    }
+
+    /// Returns the executable path
+    #[inline]
+    pub fn binary(&self) -> &PathBuf {
+        &self.binary
+    }
+
+    /// Returns the host interface
+    #[inline]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port
+    #[inline]
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the models directory, if set
+    #[inline]
+    pub fn models_dir(&self) -> Option<&PathBuf> {
+        self.models_dir.as_ref()
+    }
+
+    /// Returns the startup timeout
+    #[inline]
+    pub fn startup_timeout(&self) -> Duration {
+        self.startup_timeout
+    }
+
+    /// Returns whether output is inherited
+    #[inline]
+    pub fn inherit_output(&self) -> bool {
+        self.inherit_output
+    }
+
+    /// Base URL the spawned server will answer on
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", self.host, self.port)
+    }
+
+    /// Environment passed to the child process (`OLLAMA_HOST`, `OLLAMA_MODELS`, custom)
+    pub(super) fn environment(&self) -> Vec<(String, String)> {
+        let mut env = vec![(
+            "OLLAMA_HOST".to_string(),
+            format!("{}:{}", self.host, self.port),
+        )];
+        if let Some(dir) = &self.models_dir {
+            env.push((
+                "OLLAMA_MODELS".to_string(),
+                dir.to_string_lossy().into_owned(),
+            ));
+        }
+        env.extend(self.env.iter().cloned());
+        env
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let options = ServerOptions::new();
+        assert_eq!(options.binary(), &PathBuf::from("ollama"));
+        assert_eq!(options.host(), "127.0.0.1");
+        assert_eq!(options.port(), 11434);
+        assert!(options.models_dir().is_none());
+        assert_eq!(options.startup_timeout(), Duration::from_secs(30));
+        assert!(!options.inherit_output());
+    }
+
+    #[test]
+    fn test_base_url() {
+        let options = ServerOptions::new().with_host("0.0.0.0").with_port(8080);
+        assert_eq!(options.base_url(), "http://0.0.0.0:8080");
+    }
+
+    #[test]
+    fn test_environment_contains_host_and_models() {
+        let options = ServerOptions::new()
+            .with_port(11500)
+            .with_models_dir("/tmp/models")
+            .with_env("OLLAMA_DEBUG", "1");
+
+        let env = options.environment();
+        assert_eq!(
+            env[0],
+            ("OLLAMA_HOST".to_string(), "127.0.0.1:11500".to_string())
+        );
+        assert!(env.contains(&("OLLAMA_MODELS".to_string(), "/tmp/models".to_string())));
+        assert!(env.contains(&("OLLAMA_DEBUG".to_string(), "1".to_string())));
+    }
+
+    #[test]
+    fn test_environment_without_models_dir() {
+        let env = ServerOptions::new().environment();
+        assert_eq!(env.len(), 1);
+    }
+}
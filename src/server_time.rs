@@ -0,0 +1,197 @@
+//! Parsing of the timestamp and duration strings the server sends
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.frac](Z|±HH:MM)` into Unix seconds and
+/// nanoseconds.
+fn rfc3339_parts(s: &str) -> Option<(i64, u32)> {
+    let num = |range: std::ops::Range<usize>| s.get(range)?.parse::<i64>().ok();
+    let bytes = s.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[13] != b':' {
+        return None;
+    }
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+
+    let mut rest = &s[19..];
+    let mut nanos = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let digits = frac.bytes().take_while(u8::is_ascii_digit).count();
+        for (i, d) in frac.bytes().take(digits.min(9)).enumerate() {
+            nanos += u32::from(d - b'0') * 10u32.pow(8 - i as u32);
+        }
+        rest = &frac[digits..];
+    }
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let hours = rest[1..3].parse::<i64>().ok()?;
+            let minutes = rest[4..6].parse::<i64>().ok()?;
+            sign * (hours * 3600 + minutes * 60)
+        }
+        _ => return None,
+    };
+
+    // Days since 1970-01-01 for a proleptic Gregorian date.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Some((
+        days * 86_400 + hour * 3600 + minute * 60 + second - offset,
+        nanos,
+    ))
+}
+
+/// Parses an RFC 3339 timestamp into Unix seconds, dropping any fraction.
+#[cfg_attr(not(feature = "model"), allow(dead_code))]
+pub(crate) fn rfc3339_unix(s: &str) -> Option<i64> {
+    rfc3339_parts(s).map(|(secs, _)| secs)
+}
+
+/// Parses an RFC 3339 timestamp into a [`SystemTime`], keeping the fraction
+/// to the nanosecond.
+#[cfg_attr(not(feature = "model"), allow(dead_code))]
+pub(crate) fn rfc3339(s: &str) -> Option<SystemTime> {
+    let (secs, nanos) = rfc3339_parts(s)?;
+    let whole = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    };
+    whole.checked_add(Duration::from_nanos(u64::from(nanos)))
+}
+
+/// Today's UTC date as days since 1970-01-01.
+#[cfg_attr(not(feature = "http"), allow(dead_code))]
+pub(crate) fn today() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| (d.as_secs() / 86_400) as i64)
+        .unwrap_or_default()
+}
+
+/// `YYYY-MM-DD` of the proleptic Gregorian date `days` after 1970-01-01.
+#[cfg_attr(not(feature = "http"), allow(dead_code))]
+pub(crate) fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Parses a Go duration string (`"4m0s"`, `"1h30m"`, `"1.5h"`, `"300ms"`)
+/// into a signed number of nanoseconds.
+///
+/// A bare number counts as seconds, as the server reads `keep_alive`.
+pub(crate) fn go_duration_ns(s: &str) -> Option<i128> {
+    let s = s.trim();
+    if let Ok(seconds) = s.parse::<i64>() {
+        return Some(i128::from(seconds) * 1_000_000_000);
+    }
+    let (sign, mut rest) = match s.as_bytes().first()? {
+        b'-' => (-1, &s[1..]),
+        b'+' => (1, &s[1..]),
+        _ => (1, s),
+    };
+    if rest == "0" {
+        return Some(0);
+    }
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut total = 0f64;
+    while !rest.is_empty() {
+        let number_len = rest
+            .bytes()
+            .take_while(|b| b.is_ascii_digit() || *b == b'.')
+            .count();
+        let value: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .char_indices()
+            .find(|(_, c)| c.is_ascii_digit() || *c == '.')
+            .map_or(rest.len(), |(i, _)| i);
+        let scale = match &rest[..unit_len] {
+            "ns" => 1.0,
+            "us" | "µs" | "μs" => 1e3,
+            "ms" => 1e6,
+            "s" => 1e9,
+            "m" => 60e9,
+            "h" => 3600e9,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total += value * scale;
+    }
+    Some(sign * total.round() as i128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339_unix() {
+        assert_eq!(rfc3339_unix("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(rfc3339_unix("2024-01-15T10:30:00Z"), Some(1_705_314_600));
+        assert_eq!(
+            rfc3339_unix("2024-01-15T12:30:00.5+02:00"),
+            Some(1_705_314_600)
+        );
+        assert_eq!(rfc3339_unix("2024-01-15"), None);
+        assert_eq!(rfc3339_unix("2024-01-15T10:30:00"), None);
+    }
+
+    #[test]
+    fn test_rfc3339_keeps_fraction() {
+        let time = rfc3339("2024-01-15T10:30:00.93355-07:00").unwrap();
+        let since = time.duration_since(UNIX_EPOCH).unwrap();
+        assert_eq!(since.as_secs(), 1_705_339_800);
+        assert_eq!(since.subsec_nanos(), 933_550_000);
+
+        let zero = rfc3339("0001-01-01T00:00:00Z").unwrap();
+        assert!(zero < UNIX_EPOCH);
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(19_782), "2024-02-29");
+        assert_eq!(civil_date(20_742), "2026-10-16");
+        assert_eq!(civil_date(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn test_go_duration() {
+        assert_eq!(go_duration_ns("4m0s"), Some(240_000_000_000));
+        assert_eq!(go_duration_ns("1h30m"), Some(5_400_000_000_000));
+        assert_eq!(go_duration_ns("1.5h"), Some(5_400_000_000_000));
+        assert_eq!(go_duration_ns("300ms"), Some(300_000_000));
+        assert_eq!(go_duration_ns("2µs500ns"), Some(2_500));
+        assert_eq!(go_duration_ns("-1m"), Some(-60_000_000_000));
+        assert_eq!(go_duration_ns("0"), Some(0));
+        assert_eq!(go_duration_ns("300"), Some(300_000_000_000));
+        assert_eq!(go_duration_ns("-1"), Some(-1_000_000_000));
+        assert_eq!(go_duration_ns("5 minutes"), None);
+        assert_eq!(go_duration_ns("m"), None);
+        assert_eq!(go_duration_ns(""), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! Proptest strategies for request and option types
+//!
+//! Strategies are derived from the [`arbitrary::Arbitrary`] implementations
+//! enabled by the `arbitrary` feature, plus a few domain-aware generators
+//! (model names, chat messages) that produce realistic-looking values.
+//!
+//! # Feature Flag
+//!
+//! This module requires the `proptest` feature (which implies `arbitrary`):
+//!
+//! ```toml
+//! [dev-dependencies]
+//! ollama-oxide = { version = "0.2", features = ["proptest"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::strategies;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     #[test]
+//!     fn chat_requests_serialize(request in strategies::chat_request()) {
+//!         serde_json::to_string(&request).unwrap();
+//!     }
+//! }
+//! ```
+
+mod request_strategies;
+
+pub use request_strategies::{
+    arbitrary_value, chat_message, chat_request, embed_request, generate_request, model_name,
+    model_options,
+};
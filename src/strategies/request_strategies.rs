@@ -0,0 +1,144 @@
+//! Strategy constructors for request types
+
+use arbitrary::{Arbitrary, Unstructured};
+use proptest::prelude::*;
+
+use crate::{ChatMessage, ChatRequest, ChatRole, EmbedRequest, GenerateRequest, ModelOptions};
+
+/// Upper bound on the random bytes fed to `Arbitrary` per generated value.
+const MAX_ARBITRARY_BYTES: usize = 1024;
+
+/// Strategy for any type implementing [`Arbitrary`].
+///
+/// Feeds random byte buffers through [`Unstructured`], so shrinking the
+/// buffer shrinks the generated value.
+pub fn arbitrary_value<T>() -> BoxedStrategy<T>
+where
+    T: for<'a> Arbitrary<'a> + std::fmt::Debug + 'static,
+{
+    prop::collection::vec(any::<u8>(), 0..MAX_ARBITRARY_BYTES)
+        .prop_filter_map("arbitrary input exhausted", |bytes| {
+            T::arbitrary(&mut Unstructured::new(&bytes)).ok()
+        })
+        .boxed()
+}
+
+/// Realistic model names such as `llama3`, `qwen3:0.6b`, or `library/phi4:latest`.
+pub fn model_name() -> BoxedStrategy<String> {
+    "([a-z]{1,8}/)?[a-z][a-z0-9.\\-]{0,15}(:[a-z0-9.]{1,10})?".boxed()
+}
+
+/// Chat messages with any role and arbitrary (possibly non-ASCII) content.
+pub fn chat_message() -> BoxedStrategy<ChatMessage> {
+    (
+        prop_oneof![
+            Just(ChatRole::System),
+            Just(ChatRole::User),
+            Just(ChatRole::Assistant),
+            Just(ChatRole::Tool),
+        ],
+        any::<String>(),
+    )
+        .prop_map(|(role, content)| ChatMessage::new(role, content))
+        .boxed()
+}
+
+/// Arbitrary model options, with non-finite sampling parameters dropped
+/// since JSON cannot carry them.
+pub fn model_options() -> BoxedStrategy<ModelOptions> {
+    arbitrary_value::<ModelOptions>()
+        .prop_map(|mut options| {
+            for value in [
+                &mut options.temperature,
+                &mut options.top_p,
+                &mut options.min_p,
+            ] {
+                *value = value.filter(|v| v.is_finite());
+            }
+            options
+        })
+        .boxed()
+}
+
+/// Chat requests with a realistic model name, up to 8 messages, and arbitrary options.
+pub fn chat_request() -> BoxedStrategy<ChatRequest> {
+    (
+        model_name(),
+        prop::collection::vec(chat_message(), 0..8),
+        prop::option::of(model_options()),
+    )
+        .prop_map(|(model, messages, options)| {
+            let mut request = ChatRequest::new(model, messages);
+            request.options = options;
+            request
+        })
+        .boxed()
+}
+
+/// Generate requests with a realistic model name, options from
+/// [`model_options`], and otherwise arbitrary fields.
+pub fn generate_request() -> BoxedStrategy<GenerateRequest> {
+    (
+        model_name(),
+        arbitrary_value::<GenerateRequest>(),
+        prop::option::of(model_options()),
+    )
+        .prop_map(|(model, mut request, options)| {
+            request.model = model;
+            request.options = options;
+            request
+        })
+        .boxed()
+}
+
+/// Embed requests with a realistic model name, options from
+/// [`model_options`], and otherwise arbitrary fields.
+pub fn embed_request() -> BoxedStrategy<EmbedRequest> {
+    (
+        model_name(),
+        arbitrary_value::<EmbedRequest>(),
+        prop::option::of(model_options()),
+    )
+        .prop_map(|(model, mut request, options)| {
+            request.model = model;
+            request.options = options;
+            request
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_model_names_are_non_empty(name in model_name()) {
+            prop_assert!(!name.is_empty());
+            prop_assert!(!name.contains(' '));
+        }
+
+        #[test]
+        fn test_chat_request_serializes(request in chat_request()) {
+            let json = serde_json::to_value(&request).unwrap();
+            prop_assert_eq!(json["model"].as_str(), Some(request.model.as_str()));
+        }
+
+        #[test]
+        fn test_chat_message_roundtrip(message in chat_message()) {
+            let json = serde_json::to_string(&message).unwrap();
+            let back: ChatMessage = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(back, message);
+        }
+
+        #[test]
+        fn test_generate_request_serializes(request in generate_request()) {
+            prop_assert!(serde_json::to_vec(&request).is_ok());
+        }
+
+        #[test]
+        fn test_embed_request_serializes(request in embed_request()) {
+            prop_assert!(serde_json::to_vec(&request).is_ok());
+        }
+    }
+}
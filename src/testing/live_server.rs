@@ -0,0 +1,204 @@
+//! Live server detection for optional integration tests
+
+use std::time::Duration;
+
+use crate::{ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient};
+
+/// Default server address when no environment variable is set.
+const DEFAULT_LIVE_URL: &str = "http://localhost:11434";
+
+/// Timeout for the reachability probe; live tests should skip quickly.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of looking for a live Ollama server.
+#[derive(Debug, Clone)]
+pub enum LiveServer {
+    /// A server answered `GET /api/version`; the client targets it.
+    Available(OllamaClient),
+
+    /// No usable server; the string explains why the test is skipped.
+    Skipped(String),
+}
+
+impl LiveServer {
+    /// Returns the client if a server is available.
+    pub fn client(self) -> Option<OllamaClient> {
+        match self {
+            Self::Available(client) => Some(client),
+            Self::Skipped(_) => None,
+        }
+    }
+
+    /// Returns `true` if the test should be skipped.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skipped(_))
+    }
+
+    /// Applies `OLLAMA_OXIDE_REQUIRE_LIVE`: panics on skip when it is set.
+    fn enforce_requirement(self) -> Self {
+        if let Self::Skipped(reason) = &self
+            && env_flag("OLLAMA_OXIDE_REQUIRE_LIVE")
+        {
+            panic!("live Ollama server required but unavailable: {}", reason);
+        }
+        self
+    }
+}
+
+/// Returns `true` if the environment variable is set to `1` or `true`.
+fn env_flag(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Resolves the server URL from `OLLAMA_OXIDE_LIVE_URL`, then `OLLAMA_HOST`.
+///
+/// `OLLAMA_HOST` may omit the scheme (`127.0.0.1:11434`), as the Ollama CLI allows.
+pub(crate) fn resolve_base_url(live_url: Option<String>, ollama_host: Option<String>) -> String {
+    let raw = live_url
+        .or(ollama_host)
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty());
+
+    match raw {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => url,
+        Some(host) => format!("http://{}", host),
+        None => DEFAULT_LIVE_URL.to_string(),
+    }
+}
+
+fn env_base_url() -> String {
+    resolve_base_url(
+        std::env::var("OLLAMA_OXIDE_LIVE_URL").ok(),
+        std::env::var("OLLAMA_HOST").ok(),
+    )
+}
+
+fn probe_client(base_url: &str) -> Result<OllamaClient, String> {
+    let config = ClientConfig::new(base_url.to_string(), PROBE_TIMEOUT, 0)
+        .map_err(|e| format!("invalid live server URL {}: {}", base_url, e))?;
+    OllamaClient::new(config).map_err(|e| e.to_string())
+}
+
+/// Detect a live server (async) using the environment configuration.
+///
+/// The returned client uses the default timeout and retry settings.
+///
+/// # Panics
+///
+/// Panics instead of skipping when `OLLAMA_OXIDE_REQUIRE_LIVE` is set.
+pub async fn live() -> LiveServer {
+    if env_flag("OLLAMA_OXIDE_SKIP_LIVE") {
+        return LiveServer::Skipped("OLLAMA_OXIDE_SKIP_LIVE is set".to_string());
+    }
+    live_at(&env_base_url()).await.enforce_requirement()
+}
+
+/// Detect a live server (blocking) using the environment configuration.
+///
+/// # Panics
+///
+/// Panics instead of skipping when `OLLAMA_OXIDE_REQUIRE_LIVE` is set.
+pub fn live_blocking() -> LiveServer {
+    if env_flag("OLLAMA_OXIDE_SKIP_LIVE") {
+        return LiveServer::Skipped("OLLAMA_OXIDE_SKIP_LIVE is set".to_string());
+    }
+    live_at_blocking(&env_base_url()).enforce_requirement()
+}
+
+/// Probe a specific base URL (async), ignoring the environment.
+pub async fn live_at(base_url: &str) -> LiveServer {
+    let probe = match probe_client(base_url) {
+        Ok(client) => client,
+        Err(reason) => return LiveServer::Skipped(reason),
+    };
+    match probe.version().await {
+        Ok(_) => available(base_url),
+        Err(e) => LiveServer::Skipped(format!("no Ollama server at {}: {}", base_url, e)),
+    }
+}
+
+/// Probe a specific base URL (blocking), ignoring the environment.
+pub fn live_at_blocking(base_url: &str) -> LiveServer {
+    let probe = match probe_client(base_url) {
+        Ok(client) => client,
+        Err(reason) => return LiveServer::Skipped(reason),
+    };
+    match probe.version_blocking() {
+        Ok(_) => available(base_url),
+        Err(e) => LiveServer::Skipped(format!("no Ollama server at {}: {}", base_url, e)),
+    }
+}
+
+fn available(base_url: &str) -> LiveServer {
+    match OllamaClient::with_base_url(base_url) {
+        Ok(client) => LiveServer::Available(client),
+        Err(e) => LiveServer::Skipped(e.to_string()),
+    }
+}
+
+/// Get a client for a live server or return early from the (async) test.
+///
+/// Prints the skip reason to stderr so `cargo test -- --nocapture` shows it.
+#[macro_export]
+macro_rules! require_live {
+    () => {
+        match $crate::testing::live().await {
+            $crate::testing::LiveServer::Available(client) => client,
+            $crate::testing::LiveServer::Skipped(reason) => {
+                eprintln!("skipping live test: {}", reason);
+                return;
+            }
+        }
+    };
+}
+
+/// Get a client for a live server or return early from the (blocking) test.
+///
+/// Prints the skip reason to stderr so `cargo test -- --nocapture` shows it.
+#[macro_export]
+macro_rules! require_live_blocking {
+    () => {
+        match $crate::testing::live_blocking() {
+            $crate::testing::LiveServer::Available(client) => client,
+            $crate::testing::LiveServer::Skipped(reason) => {
+                eprintln!("skipping live test: {}", reason);
+                return;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_default() {
+        assert_eq!(resolve_base_url(None, None), "http://localhost:11434");
+    }
+
+    #[test]
+    fn test_resolve_live_url_takes_precedence() {
+        let url = resolve_base_url(
+            Some("http://gpu-box:11434/".to_string()),
+            Some("127.0.0.1:9999".to_string()),
+        );
+        assert_eq!(url, "http://gpu-box:11434");
+    }
+
+    #[test]
+    fn test_resolve_ollama_host_without_scheme() {
+        let url = resolve_base_url(None, Some("0.0.0.0:11500".to_string()));
+        assert_eq!(url, "http://0.0.0.0:11500");
+    }
+
+    #[test]
+    fn test_resolve_empty_values_fall_back() {
+        assert_eq!(
+            resolve_base_url(Some("  ".to_string()), None),
+            "http://localhost:11434"
+        );
+    }
+}
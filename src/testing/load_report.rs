@@ -0,0 +1,98 @@
+//! Results of a `LoadTest` run
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::http::Percentiles;
+
+/// Throughput, error rate, and latency of a [`LoadTest`](super::LoadTest) run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadReport {
+    /// Wall-clock time the run took
+    pub elapsed: Duration,
+
+    /// Requests sent, successful or not
+    pub requests: usize,
+
+    /// Requests that returned an error
+    pub errors: usize,
+
+    /// Latency of successful requests, or `None` if none succeeded
+    pub latency: Option<Percentiles>,
+
+    /// Up to ten distinct error messages seen during the run
+    pub error_samples: Vec<String>,
+}
+
+impl LoadReport {
+    /// Requests completed per second.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.requests as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Share of requests that failed, in `0.0..=1.0`.
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests in {:.1}s ({:.2} req/s), {:.1}% errors",
+            self.requests,
+            self.elapsed.as_secs_f64(),
+            self.throughput(),
+            self.error_rate() * 100.0
+        )?;
+        if let Some(latency) = &self.latency {
+            write!(f, ", latency {latency}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_and_display() {
+        let report = LoadReport {
+            elapsed: Duration::from_secs(2),
+            requests: 10,
+            errors: 1,
+            latency: None,
+            error_samples: vec!["HTTP status error: 500".to_string()],
+        };
+        assert_eq!(report.throughput(), 5.0);
+        assert_eq!(report.error_rate(), 0.1);
+        assert_eq!(
+            report.to_string(),
+            "10 requests in 2.0s (5.00 req/s), 10.0% errors"
+        );
+    }
+
+    #[test]
+    fn test_empty_run() {
+        let report = LoadReport {
+            elapsed: Duration::ZERO,
+            requests: 0,
+            errors: 0,
+            latency: None,
+            error_samples: Vec::new(),
+        };
+        assert_eq!(report.throughput(), 0.0);
+        assert_eq!(report.error_rate(), 0.0);
+    }
+}
@@ -0,0 +1,228 @@
+//! Concurrent load generation for capacity testing
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinSet;
+
+use super::LoadReport;
+use crate::http::Percentiles;
+use crate::{
+    ChatRequest, EmbedRequest, Error, GenerateRequest, OllamaApiAsync, OllamaClient, Result,
+};
+
+/// Most distinct error messages kept in a [`LoadReport`].
+const MAX_ERROR_SAMPLES: usize = 10;
+
+/// One request in a [`LoadTest`] mix.
+#[derive(Debug, Clone)]
+pub enum LoadRequest {
+    /// `POST /api/generate`
+    Generate(GenerateRequest),
+    /// `POST /api/chat`
+    Chat(ChatRequest),
+    /// `POST /api/embed`
+    Embed(EmbedRequest),
+}
+
+impl LoadRequest {
+    async fn send(&self, client: &OllamaClient) -> Result<()> {
+        match self {
+            Self::Generate(r) => client.generate(r).await.map(drop),
+            Self::Chat(r) => client.chat(r).await.map(drop),
+            Self::Embed(r) => client.embed(r).await.map(drop),
+        }
+    }
+}
+
+impl From<GenerateRequest> for LoadRequest {
+    fn from(request: GenerateRequest) -> Self {
+        Self::Generate(request)
+    }
+}
+
+impl From<ChatRequest> for LoadRequest {
+    fn from(request: ChatRequest) -> Self {
+        Self::Chat(request)
+    }
+}
+
+impl From<EmbedRequest> for LoadRequest {
+    fn from(request: EmbedRequest) -> Self {
+        Self::Embed(request)
+    }
+}
+
+/// Outcome of one request, collected by the workers.
+struct Outcome {
+    latency: Duration,
+    error: Option<String>,
+}
+
+/// Fires a weighted mix of generate, chat, and embed requests at a server
+/// with fixed concurrency, then reports throughput, error rate, and latency
+/// percentiles.
+///
+/// Requests are picked from the mix in a fixed weighted rotation, so a mix
+/// of `chat` (weight 3) and `embed` (weight 1) sends three chats for every
+/// embed. Each of the `concurrency` workers sends one request at a time
+/// until the duration elapses or the request budget is spent; requests
+/// already in flight when time runs out are allowed to finish.
+///
+/// Requests go through the client as-is, including its retry and timeout
+/// settings; use a client with `max_retries` of 0 to measure raw failures.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ollama_oxide::testing::LoadTest;
+/// use ollama_oxide::{ChatMessage, ChatRequest, EmbedRequest, OllamaClient};
+///
+/// # async fn example() -> ollama_oxide::Result<()> {
+/// let client = OllamaClient::with_base_url("http://gpu-box:11434")?;
+/// let report = LoadTest::new(client)
+///     .with_request(ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]), 3)
+///     .with_request(EmbedRequest::new("nomic-embed-text", "hello"), 1)
+///     .with_concurrency(8)
+///     .with_duration(Duration::from_secs(60))
+///     .run()
+///     .await?;
+/// println!("{report}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadTest {
+    client: OllamaClient,
+    mix: Vec<(LoadRequest, u32)>,
+    concurrency: usize,
+    duration: Duration,
+    max_requests: Option<usize>,
+}
+
+impl LoadTest {
+    /// Start a load test against the server `client` targets.
+    ///
+    /// Defaults to a concurrency of 1 for 10 seconds with an empty mix.
+    pub fn new(client: OllamaClient) -> Self {
+        Self {
+            client,
+            mix: Vec::new(),
+            concurrency: 1,
+            duration: Duration::from_secs(10),
+            max_requests: None,
+        }
+    }
+
+    /// Add a request to the mix with a relative `weight`.
+    ///
+    /// Requests with a weight of 0 are never sent.
+    pub fn with_request(mut self, request: impl Into<LoadRequest>, weight: u32) -> Self {
+        self.mix.push((request.into(), weight));
+        self
+    }
+
+    /// Set the number of requests in flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set how long to keep sending new requests.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Stop after sending `max_requests`, even if time remains.
+    pub fn with_max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = Some(max_requests);
+        self
+    }
+
+    /// Run the test and report the results.
+    ///
+    /// Request failures are counted in the report, not returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if the mix has no request with a
+    /// positive weight or the concurrency is 0.
+    pub async fn run(self) -> Result<LoadReport> {
+        let schedule: Vec<usize> = self
+            .mix
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (_, weight))| std::iter::repeat_n(i, *weight as usize))
+            .collect();
+        if schedule.is_empty() {
+            return Err(Error::ValidationError(
+                "load test needs at least one request with a positive weight".to_string(),
+            ));
+        }
+        if self.concurrency == 0 {
+            return Err(Error::ValidationError(
+                "load test concurrency must be at least 1".to_string(),
+            ));
+        }
+
+        let started = Instant::now();
+        let deadline = started + self.duration;
+        let next = Arc::new(AtomicUsize::new(0));
+        let mix: Arc<Vec<LoadRequest>> = Arc::new(self.mix.into_iter().map(|(r, _)| r).collect());
+        let schedule = Arc::new(schedule);
+        let max_requests = self.max_requests.unwrap_or(usize::MAX);
+
+        let mut workers = JoinSet::new();
+        for _ in 0..self.concurrency {
+            let client = self.client.clone();
+            let (next, mix, schedule) = (next.clone(), mix.clone(), schedule.clone());
+            workers.spawn(async move {
+                let mut outcomes = Vec::new();
+                while Instant::now() < deadline {
+                    let n = next.fetch_add(1, Ordering::Relaxed);
+                    if n >= max_requests {
+                        break;
+                    }
+                    let request = &mix[schedule[n % schedule.len()]];
+                    let sent = Instant::now();
+                    let result = request.send(&client).await;
+                    outcomes.push(Outcome {
+                        latency: sent.elapsed(),
+                        error: result.err().map(|e| e.to_string()),
+                    });
+                }
+                outcomes
+            });
+        }
+
+        let mut outcomes = Vec::new();
+        while let Some(joined) = workers.join_next().await {
+            outcomes.extend(joined.map_err(|e| Error::ApiError {
+                message: format!("load test worker failed: {e}"),
+            })?);
+        }
+
+        let mut error_samples: Vec<String> = Vec::new();
+        for error in outcomes.iter().filter_map(|o| o.error.as_ref()) {
+            if error_samples.len() < MAX_ERROR_SAMPLES && !error_samples.contains(error) {
+                error_samples.push(error.clone());
+            }
+        }
+
+        Ok(LoadReport {
+            elapsed: started.elapsed(),
+            requests: outcomes.len(),
+            errors: outcomes.iter().filter(|o| o.error.is_some()).count(),
+            latency: Percentiles::of(
+                outcomes
+                    .iter()
+                    .filter(|o| o.error.is_none())
+                    .map(|o| o.latency),
+            ),
+            error_samples,
+        })
+    }
+}
@@ -0,0 +1,49 @@
+//! Helpers for writing optional tests against a live Ollama server
+//!
+//! Downstream crates often have a handful of tests that only make sense with
+//! a real Ollama instance. [`live`] detects a reachable server and returns
+//! either a ready-to-use client or a skip marker, so those tests pass
+//! (and say why they skipped) on machines without Ollama.
+//!
+//! [`LoadTest`] drives a server with a weighted mix of generate, chat, and
+//! embed requests at fixed concurrency and reports throughput, error rate,
+//! and latency percentiles, for capacity planning.
+//!
+//! # Environment
+//!
+//! | Variable | Effect |
+//! |----------|--------|
+//! | `OLLAMA_OXIDE_LIVE_URL` | Base URL of the server to test against |
+//! | `OLLAMA_HOST` | Fallback server address (same format as the Ollama CLI) |
+//! | `OLLAMA_OXIDE_SKIP_LIVE` | When set to `1`/`true`, always skip live tests |
+//! | `OLLAMA_OXIDE_REQUIRE_LIVE` | When set to `1`/`true`, panic instead of skipping |
+//!
+//! # Feature Flag
+//!
+//! This module requires the `testing` feature, usually as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! ollama-oxide = { version = "0.2", features = ["testing"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::OllamaApiAsync;
+//!
+//! #[tokio::test]
+//! async fn lists_models_on_live_server() {
+//!     let client = ollama_oxide::require_live!();
+//!     let version = client.version().await.unwrap();
+//!     assert!(!version.version.is_empty());
+//! }
+//! ```
+
+mod live_server;
+mod load_report;
+mod load_test;
+
+pub use live_server::{LiveServer, live, live_at, live_at_blocking, live_blocking};
+pub use load_report::LoadReport;
+pub use load_test::{LoadRequest, LoadTest};
@@ -8,6 +8,7 @@ use std::pin::Pin;
 
 use crate::ToolDefinition;
 
+use super::tool_arguments::ToolArguments;
 use super::tool_error::{ToolError, ToolResult};
 
 /// Type-erased version of `Tool` for storing in collections
@@ -62,13 +63,7 @@ where
     ) -> Pin<Box<dyn Future<Output = ToolResult<serde_json::Value>> + Send + 'a>> {
         Box::pin(async move {
             // Deserialize arguments
-            let params: T::Params = serde_json::from_value(args).map_err(|e| {
-                ToolError::DeserializationError(format!(
-                    "failed to deserialize arguments for '{}': {}",
-                    self.tool.name(),
-                    e
-                ))
-            })?;
+            let params: T::Params = ToolArguments::new(args).args_for(self.tool.name())?;
 
             // Execute the tool
             let output = self.tool.execute(params).await?;
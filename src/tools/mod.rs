@@ -101,6 +101,7 @@
 //! ```
 
 mod erased_tool;
+mod tool_arguments;
 mod tool_call;
 mod tool_call_function;
 mod tool_definition;
@@ -109,6 +110,7 @@ mod tool_function;
 mod tool_registry;
 mod tool_trait;
 
+pub use tool_arguments::ToolArguments;
 pub use tool_call::ToolCall;
 pub use tool_call_function::ToolCallFunction;
 pub use tool_definition::ToolDefinition;
@@ -0,0 +1,146 @@
+//! Arguments of a tool call, with typed decoding.
+
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+use super::tool_error::{ToolError, ToolResult};
+
+/// Arguments the model passed to a tool call.
+///
+/// Serializes as the plain JSON object the API sends. The raw JSON stays
+/// available through [`as_value`](Self::as_value) (and `Deref`, so
+/// `args["location"]` works), while [`args`](Self::args) decodes it into
+/// your own parameter type.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_oxide::tools::ToolArguments;
+/// use serde::Deserialize;
+/// use serde_json::json;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct WeatherArgs {
+///     location: String,
+/// }
+///
+/// let args = ToolArguments::new(json!({"location": "Paris"}));
+/// assert_eq!(args["location"], "Paris");
+///
+/// let weather: WeatherArgs = args.args().unwrap();
+/// assert_eq!(weather.location, "Paris");
+///
+/// let err = ToolArguments::new(json!({})).args::<WeatherArgs>().unwrap_err();
+/// assert!(err.to_string().contains("missing field `location`"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ToolArguments(serde_json::Value);
+
+impl ToolArguments {
+    /// Wrap a raw JSON value.
+    pub fn new(value: serde_json::Value) -> Self {
+        Self(value)
+    }
+
+    /// The raw JSON value.
+    pub fn as_value(&self) -> &serde_json::Value {
+        &self.0
+    }
+
+    /// Unwrap into the raw JSON value.
+    pub fn into_value(self) -> serde_json::Value {
+        self.0
+    }
+
+    /// Decode the arguments into `T`.
+    ///
+    /// Decodes straight from the stored JSON without cloning it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::DeserializationError`] with serde's description
+    /// of the mismatch, e.g. ``missing field `location` `` or
+    /// `invalid type: string "ten", expected i32`.
+    pub fn args<T>(&self) -> ToolResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        T::deserialize(&self.0).map_err(ToolError::from_json_error)
+    }
+
+    /// Like [`args`](Self::args), naming the tool in the error message.
+    pub(crate) fn args_for<T>(&self, tool: &str) -> ToolResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        T::deserialize(&self.0).map_err(|e| {
+            ToolError::DeserializationError(format!(
+                "failed to deserialize arguments for '{}': {}",
+                tool, e
+            ))
+        })
+    }
+}
+
+impl Deref for ToolArguments {
+    type Target = serde_json::Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<serde_json::Value> for ToolArguments {
+    fn from(value: serde_json::Value) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ToolArguments> for serde_json::Value {
+    fn from(args: ToolArguments) -> Self {
+        args.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Args {
+        x: i32,
+    }
+
+    #[test]
+    fn test_tool_arguments_transparent_serde() {
+        let args: ToolArguments = serde_json::from_str(r#"{"x": 1}"#).unwrap();
+        assert_eq!(args.as_value(), &json!({"x": 1}));
+        assert_eq!(serde_json::to_string(&args).unwrap(), r#"{"x":1}"#);
+    }
+
+    #[test]
+    fn test_tool_arguments_args() {
+        let args = ToolArguments::new(json!({"x": 7}));
+        assert_eq!(args.args::<Args>().unwrap(), Args { x: 7 });
+        assert_eq!(args["x"], 7);
+    }
+
+    #[test]
+    fn test_tool_arguments_args_error_message() {
+        let args = ToolArguments::new(json!({"x": "ten"}));
+        let err = args.args::<Args>().unwrap_err();
+        assert!(matches!(err, ToolError::DeserializationError(_)));
+        assert!(err.to_string().contains("invalid type: string \"ten\""));
+    }
+
+    #[test]
+    fn test_tool_arguments_args_for_names_tool() {
+        let err = ToolArguments::default()
+            .args_for::<Args>("add")
+            .unwrap_err();
+        assert!(err.to_string().contains("'add'"));
+    }
+}
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::ToolCallFunction;
+use super::tool_arguments::ToolArguments;
+use super::tool_error::{ToolError, ToolResult};
 
 /// A tool call requested by the assistant.
 ///
@@ -95,7 +97,8 @@ impl ToolCall {
     ///
     /// # Returns
     ///
-    /// Returns `Some(&Value)` with the arguments if present, `None` otherwise.
+    /// Returns `Some(&ToolArguments)` with the arguments if present, `None` otherwise.
+    /// Index it like a `Value`, or decode it with [`ToolArguments::args`].
     ///
     /// # Examples
     ///
@@ -109,7 +112,7 @@ impl ToolCall {
     /// let args = call.arguments().unwrap();
     /// assert_eq!(args["x"], 42);
     /// ```
-    pub fn arguments(&self) -> Option<&serde_json::Value> {
+    pub fn arguments(&self) -> Option<&ToolArguments> {
         self.function.as_ref().and_then(|f| f.arguments.as_ref())
     }
 
@@ -154,6 +157,47 @@ impl ToolCall {
         self.function.as_ref().and_then(|f| f.arguments_as())
     }
 
+    /// Decode the function arguments into `T`.
+    ///
+    /// See [`ToolCallFunction::args`]; missing arguments decode as an
+    /// empty object.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::InvalidToolCall`] if the call has no function,
+    /// or [`ToolError::DeserializationError`] describing why the arguments
+    /// do not fit `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::{ToolCall, ToolCallFunction};
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct CalcArgs {
+    ///     expression: String,
+    /// }
+    ///
+    /// let call = ToolCall::new(ToolCallFunction::with_arguments(
+    ///     "calculate",
+    ///     json!({"expression": "2 + 2"}),
+    /// ));
+    /// let args: CalcArgs = call.args()?;
+    /// assert_eq!(args.expression, "2 + 2");
+    /// # Ok::<(), ollama_oxide::tools::ToolError>(())
+    /// ```
+    pub fn args<T>(&self) -> ToolResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.function
+            .as_ref()
+            .ok_or(ToolError::InvalidToolCall)?
+            .args()
+    }
+
     /// Check if this tool call has valid function details.
     ///
     /// # Returns
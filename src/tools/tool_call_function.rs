@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::tool_arguments::ToolArguments;
+use super::tool_error::ToolResult;
+
 /// Function call details in a tool call response.
 ///
 /// Contains the name of the function to call and the arguments
@@ -40,7 +43,7 @@ pub struct ToolCallFunction {
     /// The structure of arguments depends on the function's parameter schema.
     /// This may be `None` for functions that take no arguments.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub arguments: Option<serde_json::Value>,
+    pub arguments: Option<ToolArguments>,
 }
 
 impl ToolCallFunction {
@@ -87,11 +90,11 @@ impl ToolCallFunction {
     /// assert_eq!(func.name, "get_weather");
     /// assert!(func.arguments.is_some());
     /// ```
-    pub fn with_arguments(name: impl Into<String>, arguments: serde_json::Value) -> Self {
+    pub fn with_arguments(name: impl Into<String>, arguments: impl Into<ToolArguments>) -> Self {
         Self {
             name: name.into(),
             description: None,
-            arguments: Some(arguments),
+            arguments: Some(arguments.into()),
         }
     }
 
@@ -148,9 +151,44 @@ impl ToolCallFunction {
     where
         T: for<'de> Deserialize<'de>,
     {
-        self.arguments
-            .as_ref()
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
+        self.arguments.as_ref().and_then(|a| a.args().ok())
+    }
+
+    /// Decode the arguments into `T`, reporting why decoding failed.
+    ///
+    /// Missing arguments decode as an empty object, so parameter types
+    /// whose fields are all optional still succeed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ToolError::DeserializationError`](super::ToolError::DeserializationError)
+    /// naming the function and the mismatch, e.g.
+    /// ``failed to deserialize arguments for 'get_weather': missing field `location` ``.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_oxide::ToolCallFunction;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct WeatherArgs {
+    ///     location: String,
+    /// }
+    ///
+    /// let func = ToolCallFunction::with_arguments("get_weather", json!({"location": "Paris"}));
+    /// let args: WeatherArgs = func.args().unwrap();
+    /// assert_eq!(args.location, "Paris");
+    /// ```
+    pub fn args<T>(&self) -> ToolResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        match &self.arguments {
+            Some(arguments) => arguments.args_for(&self.name),
+            None => ToolArguments::new(serde_json::json!({})).args_for(&self.name),
+        }
     }
 }
 
@@ -172,7 +210,7 @@ mod tests {
         let args = json!({"key": "value"});
         let func = ToolCallFunction::with_arguments("test", args.clone());
         assert_eq!(func.name, "test");
-        assert_eq!(func.arguments, Some(args));
+        assert_eq!(func.arguments, Some(args.into()));
     }
 
     #[test]
@@ -264,6 +302,33 @@ mod tests {
         assert!(args.is_none()); // Deserialization fails
     }
 
+    #[test]
+    fn test_tool_call_function_args_error_names_function() {
+        #[derive(Deserialize, Debug)]
+        struct Args {
+            #[allow(unused)]
+            x: i32,
+        }
+
+        let func = ToolCallFunction::with_arguments("add", json!({"y": 1}));
+        let err = func.args::<Args>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to deserialize arguments: failed to deserialize arguments for 'add': missing field `x`"
+        );
+    }
+
+    #[test]
+    fn test_tool_call_function_args_missing_is_empty_object() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Args {
+            x: Option<i32>,
+        }
+
+        let func = ToolCallFunction::new("optional");
+        assert_eq!(func.args::<Args>().unwrap(), Args { x: None });
+    }
+
     #[test]
     fn test_tool_call_function_clone() {
         let func =
@@ -134,7 +134,7 @@ impl ToolRegistry {
 
         let args = call
             .arguments()
-            .cloned()
+            .map(|a| a.as_value().clone())
             .unwrap_or_else(|| serde_json::json!({}));
 
         let tool = {
@@ -178,7 +178,7 @@ impl ToolRegistry {
 
         let args = call
             .arguments()
-            .cloned()
+            .map(|a| a.as_value().clone())
             .unwrap_or_else(|| serde_json::json!({}));
 
         let tool = {
@@ -0,0 +1,57 @@
+//! Ollama hosted web APIs
+//!
+//! Types for the web search and web fetch endpoints hosted at `ollama.com`,
+//! plus ready-made tools for the [`ToolRegistry`](crate::tools::ToolRegistry)
+//! when the `tools` feature is also enabled.
+//!
+//! These endpoints require an Ollama account API key; build the client from
+//! [`ClientConfig::ollama_cloud`](crate::ClientConfig::ollama_cloud).
+//!
+//! # Feature Flag
+//!
+//! This module requires the `web` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! ollama-oxide = { version = "0.2", features = ["web"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ollama_oxide::web::WebSearchRequest;
+//! use ollama_oxide::{ClientConfig, OllamaApiAsync, OllamaClient};
+//!
+//! # async fn example() -> ollama_oxide::Result<()> {
+//! let api_key = std::env::var("OLLAMA_API_KEY").unwrap_or_default();
+//! let client = OllamaClient::new(ClientConfig::ollama_cloud(api_key))?;
+//!
+//! let response = client
+//!     .web_search(&WebSearchRequest::new("what is ollama?").with_max_results(3))
+//!     .await?;
+//! for result in &response.results {
+//!     println!("{} - {}", result.title, result.url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod web_fetch_request;
+mod web_fetch_response;
+#[cfg(feature = "tools")]
+mod web_fetch_tool;
+mod web_search_request;
+mod web_search_response;
+mod web_search_result;
+#[cfg(feature = "tools")]
+mod web_search_tool;
+
+pub use web_fetch_request::WebFetchRequest;
+pub use web_fetch_response::WebFetchResponse;
+#[cfg(feature = "tools")]
+pub use web_fetch_tool::{WebFetchParams, WebFetchTool};
+pub use web_search_request::WebSearchRequest;
+pub use web_search_response::WebSearchResponse;
+pub use web_search_result::WebSearchResult;
+#[cfg(feature = "tools")]
+pub use web_search_tool::{WebSearchParams, WebSearchTool};
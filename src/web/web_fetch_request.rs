@@ -0,0 +1,70 @@
+//! Web fetch request primitive type
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// Request body for POST /api/web_fetch
+///
+/// # Example
+///
+/// ```
+/// use ollama_oxide::web::WebFetchRequest;
+///
+/// let request = WebFetchRequest::new("https://ollama.com");
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"url":"https://ollama.com"}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebFetchRequest {
+    /// The page to fetch
+    pub url: String,
+}
+
+impl WebFetchRequest {
+    /// Create a fetch request for `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    /// Check the request before sending it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if the URL does not parse or is not
+    /// `http`/`https`.
+    pub fn validate(&self) -> Result<()> {
+        let parsed = url::Url::parse(self.url.trim()).map_err(|e| {
+            Error::ValidationError(format!("invalid fetch url '{}': {e}", self.url))
+        })?;
+        match parsed.scheme() {
+            "http" | "https" => Ok(()),
+            scheme => Err(Error::ValidationError(format!(
+                "fetch url must be http or https, got '{scheme}'"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(
+            WebFetchRequest::new("https://ollama.com/blog")
+                .validate()
+                .is_ok()
+        );
+        assert!(WebFetchRequest::new("").validate().is_err());
+        assert!(WebFetchRequest::new("ollama.com").validate().is_err());
+        assert!(
+            WebFetchRequest::new("file:///etc/passwd")
+                .validate()
+                .is_err()
+        );
+    }
+}
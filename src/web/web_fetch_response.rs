@@ -0,0 +1,50 @@
+//! Web fetch response primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// Response from POST /api/web_fetch
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "title": "Ollama",
+///   "content": "Cloud models are now available in Ollama...",
+///   "links": ["https://ollama.com/models", "https://ollama.com/blog"]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WebFetchResponse {
+    /// Page title
+    #[serde(default)]
+    pub title: String,
+
+    /// Main text content of the page
+    #[serde(default)]
+    pub content: String,
+
+    /// Links found on the page
+    #[serde(default)]
+    pub links: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let json =
+            r#"{"title":"Ollama","content":"Run models.","links":["https://ollama.com/blog"]}"#;
+        let response: WebFetchResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.title, "Ollama");
+        assert_eq!(response.links, vec!["https://ollama.com/blog"]);
+    }
+
+    #[test]
+    fn test_deserialize_missing_fields() {
+        let response: WebFetchResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.content.is_empty());
+        assert!(response.links.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+//! Web fetch as a registrable tool
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{WebFetchRequest, WebFetchResponse};
+use crate::tools::{Tool, ToolError, ToolResult};
+use crate::{OllamaApiAsync, OllamaClient};
+
+/// Arguments the model passes to [`WebFetchTool`]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WebFetchParams {
+    /// The http or https URL of the page to read
+    pub url: String,
+}
+
+/// Ready-made `web_fetch` tool backed by `POST /api/web_fetch`.
+///
+/// Pairs with [`WebSearchTool`](super::WebSearchTool): the model searches,
+/// then fetches the pages it wants to read. The client must target
+/// `ollama.com` with an API key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::tools::ToolRegistry;
+/// use ollama_oxide::web::{WebFetchTool, WebSearchTool};
+/// use ollama_oxide::{ClientConfig, OllamaClient};
+///
+/// # fn example() -> ollama_oxide::Result<()> {
+/// let cloud = OllamaClient::new(ClientConfig::ollama_cloud("key"))?;
+/// let mut registry = ToolRegistry::new();
+/// registry.register(WebSearchTool::new(cloud.clone()));
+/// registry.register(WebFetchTool::new(cloud));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebFetchTool {
+    client: OllamaClient,
+}
+
+impl WebFetchTool {
+    /// Create the tool; fetches are sent through `client`
+    pub fn new(client: OllamaClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Tool for WebFetchTool {
+    type Params = WebFetchParams;
+    type Output = WebFetchResponse;
+
+    fn name(&self) -> &'static str {
+        "web_fetch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fetch a web page by URL and return its title, main text content, and links"
+    }
+
+    async fn execute(&self, params: Self::Params) -> ToolResult<Self::Output> {
+        self.client
+            .web_fetch(&WebFetchRequest::new(params.url))
+            .await
+            .map_err(|e| ToolError::ExecutionError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition() {
+        let tool = WebFetchTool::new(OllamaClient::default().unwrap());
+        assert_eq!(tool.to_definition().name(), "web_fetch");
+        assert!(tool.parameters_schema()["properties"]["url"].is_object());
+    }
+}
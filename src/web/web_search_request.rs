@@ -0,0 +1,99 @@
+//! Web search request primitive type
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// Request body for POST /api/web_search
+///
+/// # Example
+///
+/// ```
+/// use ollama_oxide::web::WebSearchRequest;
+///
+/// let request = WebSearchRequest::new("rust async runtimes").with_max_results(5);
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"query":"rust async runtimes","max_results":5}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebSearchRequest {
+    /// The search query
+    pub query: String,
+
+    /// Maximum results to return (server default 5, maximum 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_results: Option<u32>,
+}
+
+impl WebSearchRequest {
+    /// Largest `max_results` the server accepts
+    pub const MAX_RESULTS: u32 = 10;
+
+    /// Create a search request for `query`
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            max_results: None,
+        }
+    }
+
+    /// Limit the number of results
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Check the request before sending it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] if the query is blank or
+    /// `max_results` is outside `1..=10`.
+    pub fn validate(&self) -> Result<()> {
+        if self.query.trim().is_empty() {
+            return Err(Error::ValidationError(
+                "web search query must not be empty".to_string(),
+            ));
+        }
+        if let Some(n) = self.max_results
+            && !(1..=Self::MAX_RESULTS).contains(&n)
+        {
+            return Err(Error::ValidationError(format!(
+                "max_results must be between 1 and {}, got {n}",
+                Self::MAX_RESULTS
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_omits_unset_max_results() {
+        let json = serde_json::to_string(&WebSearchRequest::new("q")).unwrap();
+        assert_eq!(json, r#"{"query":"q"}"#);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(WebSearchRequest::new("q").validate().is_ok());
+        assert!(WebSearchRequest::new("  ").validate().is_err());
+        assert!(
+            WebSearchRequest::new("q")
+                .with_max_results(0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            WebSearchRequest::new("q")
+                .with_max_results(11)
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Web search response primitive type
+
+use serde::{Deserialize, Serialize};
+
+use super::WebSearchResult;
+
+/// Response from POST /api/web_search
+///
+/// # Example Response
+///
+/// ```json
+/// {
+///   "results": [
+///     {
+///       "title": "Ollama",
+///       "url": "https://ollama.com/",
+///       "content": "Cloud models are now available..."
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WebSearchResponse {
+    /// Matching pages, most relevant first
+    #[serde(default)]
+    pub results: Vec<WebSearchResult>,
+}
+
+impl WebSearchResponse {
+    /// Check whether the search found nothing
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{"results":[{"title":"Ollama","url":"https://ollama.com/","content":"Run models."}]}"#;
+        let response: WebSearchResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].url, "https://ollama.com/");
+    }
+
+    #[test]
+    fn test_deserialize_missing_results() {
+        let response: WebSearchResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.is_empty());
+    }
+}
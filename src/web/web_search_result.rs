@@ -0,0 +1,19 @@
+//! Web search result primitive type
+
+use serde::{Deserialize, Serialize};
+
+/// One page returned by web search
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WebSearchResult {
+    /// Page title
+    #[serde(default)]
+    pub title: String,
+
+    /// Page URL
+    #[serde(default)]
+    pub url: String,
+
+    /// Relevant text extracted from the page
+    #[serde(default)]
+    pub content: String,
+}
@@ -0,0 +1,87 @@
+//! Web search as a registrable tool
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{WebSearchRequest, WebSearchResponse};
+use crate::tools::{Tool, ToolError, ToolResult};
+use crate::{OllamaApiAsync, OllamaClient};
+
+/// Arguments the model passes to [`WebSearchTool`]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WebSearchParams {
+    /// What to search the web for
+    pub query: String,
+
+    /// Maximum number of results (1-10)
+    #[serde(default)]
+    pub max_results: Option<u32>,
+}
+
+/// Ready-made `web_search` tool backed by `POST /api/web_search`.
+///
+/// Register it with a [`ToolRegistry`](crate::tools::ToolRegistry) so a
+/// model can search the web during a tool-calling loop. The client must
+/// target `ollama.com` with an API key.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_oxide::tools::ToolRegistry;
+/// use ollama_oxide::web::WebSearchTool;
+/// use ollama_oxide::{ClientConfig, OllamaClient};
+///
+/// # fn example() -> ollama_oxide::Result<()> {
+/// let cloud = OllamaClient::new(ClientConfig::ollama_cloud("key"))?;
+/// let mut registry = ToolRegistry::new();
+/// registry.register(WebSearchTool::new(cloud));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebSearchTool {
+    client: OllamaClient,
+}
+
+impl WebSearchTool {
+    /// Create the tool; searches are sent through `client`
+    pub fn new(client: OllamaClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Tool for WebSearchTool {
+    type Params = WebSearchParams;
+    type Output = WebSearchResponse;
+
+    fn name(&self) -> &'static str {
+        "web_search"
+    }
+
+    fn description(&self) -> &'static str {
+        "Search the web and return matching pages with their title, URL, and relevant content"
+    }
+
+    async fn execute(&self, params: Self::Params) -> ToolResult<Self::Output> {
+        let mut request = WebSearchRequest::new(params.query);
+        request.max_results = params.max_results;
+        self.client
+            .web_search(&request)
+            .await
+            .map_err(|e| ToolError::ExecutionError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition() {
+        let tool = WebSearchTool::new(OllamaClient::default().unwrap());
+        let definition = tool.to_definition();
+        assert_eq!(definition.name(), "web_search");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["query"].is_object());
+    }
+}
@@ -0,0 +1,277 @@
+//! Tests for request and response body size limits and JSON guards
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, Error, GenerateRequest, JsonLimits, OllamaApiAsync,
+    OllamaApiSync, OllamaClient,
+};
+use std::time::Duration;
+
+fn client_with_limits(
+    server: &mockito::Server,
+    request_limit: Option<usize>,
+    response_limit: Option<usize>,
+) -> OllamaClient {
+    let mut config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    if let Some(limit) = request_limit {
+        config = config.with_max_request_bytes(limit);
+    }
+    if let Some(limit) = response_limit {
+        config = config.with_max_response_bytes(limit);
+    }
+    OllamaClient::new(config).unwrap()
+}
+
+/// One small NDJSON chat line followed by one of roughly 570 bytes.
+fn oversized_stream_body() -> String {
+    let small = r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":false}"#;
+    let large = format!(
+        r#"{{"model":"m","message":{{"role":"assistant","content":"{}"}},"done":true}}"#,
+        "a".repeat(500)
+    );
+    format!("{}\n{}\n", small, large)
+}
+
+#[tokio::test]
+async fn test_request_too_large_not_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client_with_limits(&server, Some(64), None);
+    let request = GenerateRequest::new("qwen3:0.6b", "x".repeat(1000));
+    let result = client.generate(&request).await;
+
+    match result {
+        Err(Error::RequestTooLarge { size, limit }) => {
+            assert_eq!(limit, 64);
+            assert!(size > 1000);
+        }
+        other => panic!("expected RequestTooLarge, got {:?}", other),
+    }
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_request_within_limit_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"qwen3:0.6b","response":"ok","done":true}"#)
+        .create_async()
+        .await;
+
+    let client = client_with_limits(&server, Some(4096), Some(4096));
+    let request = GenerateRequest::new("qwen3:0.6b", "Hello");
+    let response = client.generate(&request).await.unwrap();
+
+    assert_eq!(response.response.as_deref(), Some("ok"));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_response_too_large_async() {
+    let mut server = mockito::Server::new_async().await;
+    let body = format!(
+        r#"{{"model":"m","response":"{}","done":true}}"#,
+        "a".repeat(500)
+    );
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let client = client_with_limits(&server, None, Some(100));
+    let request = GenerateRequest::new("qwen3:0.6b", "Hello");
+    let result = client.generate(&request).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::ResponseTooLarge { limit: 100 })
+    ));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_response_too_large_blocking() {
+    let mut server = mockito::Server::new();
+    let body = format!(r#"{{"version":"{}"}}"#, "9".repeat(500));
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create();
+
+    let client = client_with_limits(&server, None, Some(100));
+    let result = client.version_blocking();
+
+    assert!(matches!(
+        result,
+        Err(Error::ResponseTooLarge { limit: 100 })
+    ));
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_stream_line_too_large_async() {
+    let mut server = mockito::Server::new_async().await;
+    let body = oversized_stream_body();
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let client = client_with_limits(&server, None, Some(200));
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap();
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.content(), Some("ok"));
+    let second = stream.next().await.unwrap();
+    assert!(matches!(
+        second,
+        Err(Error::ResponseTooLarge { limit: 200 })
+    ));
+    assert!(stream.next().await.is_none());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_stream_line_too_large_blocking() {
+    let mut server = mockito::Server::new();
+    let body = oversized_stream_body();
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(body)
+        .create();
+
+    let client = client_with_limits(&server, None, Some(200));
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let mut stream = client.chat_stream_blocking(&request).unwrap();
+
+    let first = stream.next().unwrap().unwrap();
+    assert_eq!(first.content(), Some("ok"));
+    assert!(matches!(
+        stream.next(),
+        Some(Err(Error::ResponseTooLarge { limit: 200 }))
+    ));
+    assert!(stream.next().is_none());
+    mock.assert();
+}
+
+// ============================================================================
+// JSON Guard Tests
+// ============================================================================
+
+fn client_with_json_limits(server: &mockito::Server, limits: JsonLimits) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_json_limits(limits);
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_response_too_deep_async() {
+    let mut server = mockito::Server::new_async().await;
+    let body = format!(
+        r#"{{"version":"0.12.6","extra":{}{}}}"#,
+        "[".repeat(100),
+        "]".repeat(100)
+    );
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let client = client_with_json_limits(&server, JsonLimits::new().with_max_depth(16));
+    let result = client.version().await;
+
+    assert!(matches!(result, Err(Error::ResponseTooLarge { limit: 16 })));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_response_string_too_large_blocking() {
+    let mut server = mockito::Server::new();
+    let body = format!(r#"{{"version":"{}"}}"#, "9".repeat(500));
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(body)
+        .create();
+
+    let client = client_with_json_limits(&server, JsonLimits::new().with_max_string_bytes(100));
+    let result = client.version_blocking();
+
+    assert!(matches!(
+        result,
+        Err(Error::ResponseTooLarge { limit: 100 })
+    ));
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_stream_line_string_too_large_async() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(oversized_stream_body())
+        .create_async()
+        .await;
+
+    let client = client_with_json_limits(&server, JsonLimits::new().with_max_string_bytes(100));
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap();
+
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("ok"));
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(Error::ResponseTooLarge { limit: 100 }))
+    ));
+    assert!(stream.next().await.is_none());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_stream_line_too_deep_blocking() {
+    let mut server = mockito::Server::new();
+    let body = format!(
+        "{}\n{{\"model\":\"m\",\"done\":true,\"x\":{}{}}}\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":false}"#,
+        "[".repeat(64),
+        "]".repeat(64)
+    );
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(body)
+        .create();
+
+    let client = client_with_json_limits(&server, JsonLimits::new().with_max_depth(8));
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let mut stream = client.chat_stream_blocking(&request).unwrap();
+
+    assert_eq!(stream.next().unwrap().unwrap().content(), Some("ok"));
+    assert!(matches!(
+        stream.next(),
+        Some(Err(Error::ResponseTooLarge { limit: 8 }))
+    ));
+    assert!(stream.next().is_none());
+    mock.assert();
+}
@@ -0,0 +1,196 @@
+//! Integration tests for per-token callback helpers (generate/chat with callback).
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, DoneReason, Error, GenerateRequest, OllamaClient,
+};
+use serde_json::json;
+use std::time::Duration;
+
+const GENERATE_BODY: &str = concat!(
+    r#"{"model":"m","response":"The ","done":false}"#,
+    "\n",
+    r#"{"model":"m","response":"sky","done":false}"#,
+    "\n",
+    r#"{"model":"m","response":"","done":true,"done_reason":"stop","eval_count":2,"context":[1,2,3]}"#,
+    "\n",
+);
+
+const CHAT_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"","thinking":"Greeting."},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"!"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"eval_count":2}"#,
+    "\n",
+);
+
+fn client_for(url: String) -> OllamaClient {
+    let config = ClientConfig::new(url, Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_generate_with_callback_async() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(json!({ "stream": true })))
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(GENERATE_BODY)
+        .create_async()
+        .await;
+
+    let client = client_for(server.url());
+    let request = GenerateRequest::new("m", "Why is the sky blue?");
+    let mut tokens = Vec::new();
+    let response = client
+        .generate_with_callback(&request, |t| tokens.push(t.to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(tokens, ["The ", "sky"]);
+    assert_eq!(response.text(), Some("The sky"));
+    assert!(response.is_done());
+    assert_eq!(response.done_reason, Some(DoneReason::Stop));
+    assert_eq!(response.eval_count, Some(2));
+    assert_eq!(response.context, Some(vec![1, 2, 3]));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_generate_with_callback_blocking() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(json!({ "stream": true })))
+        .with_status(200)
+        .with_body(GENERATE_BODY)
+        .create();
+
+    let client = client_for(server.url());
+    let request = GenerateRequest::new("m", "Why is the sky blue?");
+    let mut count = 0;
+    let response = client
+        .generate_with_callback_blocking(&request, |_| count += 1)
+        .unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(response.text(), Some("The sky"));
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_chat_with_callback_async() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(json!({ "stream": true })))
+        .with_status(200)
+        .with_body(CHAT_BODY)
+        .create_async()
+        .await;
+
+    let client = client_for(server.url());
+    let request = ChatRequest::new("m", [ChatMessage::user("Hello")]);
+    let mut text = String::new();
+    let response = client
+        .chat_with_callback(&request, |t| text.push_str(t))
+        .await
+        .unwrap();
+
+    assert_eq!(text, "Hi!");
+    assert_eq!(response.content(), Some("Hi!"));
+    assert_eq!(response.thinking(), Some("Greeting."));
+    assert_eq!(response.eval_count, Some(2));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_chat_with_callback_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(CHAT_BODY)
+        .create();
+
+    let client = client_for(server.url());
+    let request = ChatRequest::new("m", [ChatMessage::user("Hello")]);
+    let mut text = String::new();
+    let response = client
+        .chat_with_callback_blocking(&request, |t| text.push_str(t))
+        .unwrap();
+
+    assert_eq!(text, "Hi!");
+    assert!(response.is_done());
+}
+
+#[tokio::test]
+async fn test_generate_with_callback_stream_error() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body("{\"response\":\"ok\",\"done\":false}\nnot json\n")
+        .create_async()
+        .await;
+
+    let client = client_for(server.url());
+    let request = GenerateRequest::new("m", "x");
+    let err = client
+        .generate_with_callback(&request, |_| {})
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.partial_output(), Some("ok"));
+    let Error::PartialOutput { error, .. } = err else {
+        panic!("expected PartialOutput, got {err:?}");
+    };
+    assert!(matches!(*error, Error::StreamError(_)));
+}
+
+#[tokio::test]
+async fn test_generate_with_callback_http_error() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(500)
+        .create_async()
+        .await;
+
+    let client = client_for(server.url());
+    let request = GenerateRequest::new("m", "x");
+    let err = client
+        .generate_with_callback(&request, |_| {})
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::HttpStatusError(500)));
+}
+
+#[test]
+fn test_chat_with_callback_blocking_interrupted_keeps_text() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":"!"},"done":false}"#,
+            "\n",
+        ))
+        .create();
+
+    let client = client_for(server.url());
+    let request = ChatRequest::new("m", [ChatMessage::user("Hello")]);
+    let err = client
+        .chat_with_callback_blocking(&request, |_| {})
+        .unwrap_err();
+
+    assert!(matches!(err, Error::StreamInterrupted { .. }));
+    assert_eq!(err.partial_output(), Some("Hi!"));
+}
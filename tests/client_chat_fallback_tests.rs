@@ -0,0 +1,181 @@
+//! Tests for the chat-to-generate fallback (POST /api/chat via /api/generate)
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient,
+    PromptTemplate,
+};
+use serde_json::json;
+use std::time::Duration;
+
+const FALLBACK: &str =
+    "{{ range .Messages }}### {{ .Role }}\n{{ .Content }}\n\n{{ end }}### assistant\n";
+
+fn fallback_client(url: String) -> OllamaClient {
+    let config = ClientConfig::new(url, Duration::from_secs(5), 0)
+        .unwrap()
+        .with_chat_fallback(PromptTemplate::parse(FALLBACK).unwrap());
+    OllamaClient::new(config).unwrap()
+}
+
+fn conversation() -> ChatRequest {
+    ChatRequest::new(
+        "base-model",
+        [ChatMessage::system("Be brief."), ChatMessage::user("Hi")],
+    )
+}
+
+// ============================================================================
+// Configuration Tests
+// ============================================================================
+
+#[test]
+fn test_chat_fallback_is_off_by_default() {
+    assert!(ClientConfig::default().chat_fallback().is_none());
+    let config =
+        ClientConfig::default().with_chat_fallback(PromptTemplate::parse(FALLBACK).unwrap());
+    assert_eq!(config.chat_fallback().unwrap().source(), FALLBACK);
+}
+
+// ============================================================================
+// Async API Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_chat_async_falls_back_to_raw_generate() {
+    let mut server = mockito::Server::new_async().await;
+    let show = server
+        .mock("POST", "/api/show")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"template": "{{ .Prompt }}"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let generate = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(json!({
+            "model": "base-model",
+            "prompt": "### system\nBe brief.\n\n### user\nHi\n\n### assistant\n",
+            "raw": true,
+            "stream": false
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model": "base-model", "response": "Hello!", "done": true, "eval_count": 3}"#,
+        )
+        .expect(2)
+        .create_async()
+        .await;
+    let chat = server
+        .mock("POST", "/api/chat")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = fallback_client(server.url());
+    for _ in 0..2 {
+        let response = client.chat(&conversation()).await.unwrap();
+        assert_eq!(response.content(), Some("Hello!"));
+        assert_eq!(
+            response.message.as_ref().unwrap().role.as_deref(),
+            Some("assistant")
+        );
+        assert_eq!(response.eval_count, Some(3));
+    }
+
+    show.assert_async().await;
+    generate.assert_async().await;
+    chat.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_async_keeps_chat_endpoint_for_templated_models() {
+    let mut server = mockito::Server::new_async().await;
+    let show = server
+        .mock("POST", "/api/show")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"template": "[INST] {{ .Prompt }} [/INST]"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model": "base-model", "message": {"role": "assistant", "content": "Hi!"}, "done": true}"#,
+        )
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = fallback_client(server.url());
+    for _ in 0..2 {
+        let response = client.chat(&conversation()).await.unwrap();
+        assert_eq!(response.content(), Some("Hi!"));
+    }
+
+    show.assert_async().await;
+    chat.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_async_without_fallback_skips_show() {
+    let mut server = mockito::Server::new_async().await;
+    let show = server
+        .mock("POST", "/api/show")
+        .expect(0)
+        .create_async()
+        .await;
+    let chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model": "base-model", "message": {"role": "assistant", "content": "Hi!"}, "done": true}"#,
+        )
+        .create_async()
+        .await;
+
+    let client =
+        OllamaClient::new(ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap())
+            .unwrap();
+    client.chat(&conversation()).await.unwrap();
+
+    show.assert_async().await;
+    chat.assert_async().await;
+}
+
+// ============================================================================
+// Sync API Tests
+// ============================================================================
+
+#[test]
+fn test_chat_sync_falls_back_when_template_is_missing() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut server = runtime.block_on(mockito::Server::new_async());
+    let _show = runtime.block_on(
+        server
+            .mock("POST", "/api/show")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"license": "MIT"}"#)
+            .create_async(),
+    );
+    let generate = runtime.block_on(
+        server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(json!({"raw": true})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"model": "base-model", "response": "Hello!", "done": true}"#)
+            .create_async(),
+    );
+
+    let client = fallback_client(server.url());
+    let response = client.chat_blocking(&conversation()).unwrap();
+    assert_eq!(response.content(), Some("Hello!"));
+    runtime.block_on(generate.assert_async());
+}
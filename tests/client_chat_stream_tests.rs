@@ -1,7 +1,8 @@
 //! Integration tests for streaming chat (POST /api/chat, NDJSON).
 
 use ollama_oxide::{
-    ChatMessage, ChatRequest, ClientConfig, Error, OllamaApiAsync, OllamaApiSync, OllamaClient,
+    CancelReason, CancelToken, ChatMessage, ChatRequest, ClientConfig, Error, OllamaApiAsync,
+    OllamaApiSync, OllamaClient, PostProcessor,
 };
 use serde_json::json;
 use std::time::Duration;
@@ -227,3 +228,1158 @@ fn test_chat_request_with_stream_builder() {
     let r = ChatRequest::new("m", [ChatMessage::user("x")]).with_stream(true);
     assert_eq!(r.stream, Some(true));
 }
+
+// ============================================================================
+// tee
+// ============================================================================
+
+const TEE_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"B"},"done":true}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_tee_async_forwards_untouched() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(String::new()));
+    let log = seen.clone();
+    let stream = client.chat_stream(&request).await.unwrap().tee(move |c| {
+        log.lock()
+            .unwrap()
+            .push_str(c.content().unwrap_or_default());
+    });
+
+    let events = stream.collect().await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].content(), Some("B"));
+    assert_eq!(*seen.lock().unwrap(), "AB");
+}
+
+#[test]
+fn test_chat_stream_tee_blocking_forwards_untouched() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let first = count.clone();
+    let second = count.clone();
+    let stream = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .tee(move |_| {
+            first.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        })
+        .tee(move |_| {
+            second.fetch_add(10, std::sync::atomic::Ordering::SeqCst);
+        });
+
+    let contents: Vec<String> = stream
+        .map(|e| e.unwrap().content().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(contents, ["A", "B"]);
+    assert_eq!(count.load(std::sync::atomic::Ordering::SeqCst), 22);
+}
+
+// ============================================================================
+// stop_at
+// ============================================================================
+
+const STOP_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"Answer: 42</ans"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"wer> ignored"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":" never seen"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop"}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_stop_at_async_cuts_across_chunks() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(STOP_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .stop_at(["</answer>"])
+        .collect()
+        .await
+        .unwrap();
+
+    let text: String = events
+        .iter()
+        .map(|e| e.content().unwrap_or_default())
+        .collect();
+    assert_eq!(text, "Answer: 42");
+    assert_eq!(events.len(), 2);
+    assert!(events[1].is_done());
+    assert_eq!(
+        events[1].done_reason(),
+        Some(&ollama_oxide::DoneReason::ClientStop)
+    );
+    assert_eq!(events[1].cancel_reason, Some(CancelReason::StopSequence));
+    assert!(!events[1].counts_estimated);
+}
+
+#[test]
+fn test_chat_stream_stop_at_blocking_cuts_across_chunks() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(STOP_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let contents: Vec<String> = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .stop_at(["</answer>"])
+        .map(|e| e.unwrap().content().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(contents, ["Answer: 42", ""]);
+}
+
+// ============================================================================
+// post_process
+// ============================================================================
+
+const THINK_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"<thi"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"nk>hmm</think>\n\n"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"Hello "},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"eval_count":4}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_configured_post_processor_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(THINK_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_post_processor(PostProcessor::new().strip_think().trim());
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let contents: Vec<&str> = events
+        .iter()
+        .map(|e| e.content().unwrap_or_default())
+        .collect();
+    assert_eq!(contents, ["", "", "Hello", ""]);
+    assert_eq!(events[3].eval_count, Some(4));
+}
+
+#[test]
+fn test_chat_stream_post_process_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(THINK_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let text: String = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .post_process(&PostProcessor::new().strip_think().trim())
+        .map(|e| e.unwrap().content().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(text, "Hello");
+}
+
+// ============================================================================
+// coalesce
+// ============================================================================
+
+#[tokio::test]
+async fn test_chat_stream_coalesce_merges_fast_deltas() {
+    let mut server = mockito::Server::new_async().await;
+    let body = concat!(
+        r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+        "\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"B","thinking":"t"},"done":false}"#,
+        "\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"C"},"done":false}"#,
+        "\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"D"},"done":true,"eval_count":4}"#,
+        "\n",
+    );
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .coalesce(Duration::from_secs(60))
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].content(), Some("A"));
+    assert_eq!(events[1].content(), Some("BCD"));
+    assert_eq!(events[1].thinking(), Some("t"));
+    assert!(events[1].is_done());
+    assert_eq!(events[1].eval_count, Some(4));
+}
+
+#[tokio::test]
+async fn test_chat_stream_coalesce_zero_period_passes_through() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":"B"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":"C"},"done":true}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .coalesce(Duration::ZERO)
+        .collect()
+        .await
+        .unwrap();
+
+    let contents: Vec<&str> = events.iter().filter_map(|e| e.content()).collect();
+    assert_eq!(contents, ["A", "B", "C"]);
+    assert!(events[2].is_done());
+}
+
+// ============================================================================
+// words / sentences
+// ============================================================================
+
+const SEGMENT_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"It is 3."},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"14 today. Te"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"mp"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"! Bye"},"done":true}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_sentences_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(SEGMENT_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .sentences()
+        .collect()
+        .await
+        .unwrap();
+
+    let sentences: Vec<&str> = events
+        .iter()
+        .map(|e| e.content().unwrap_or_default())
+        .collect();
+    assert_eq!(sentences, ["It is 3.14 today. ", "Temp! Bye"]);
+    assert!(events[1].is_done());
+}
+
+#[test]
+fn test_chat_stream_words_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(SEGMENT_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let words: Vec<String> = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .words()
+        .map(|e| e.unwrap().content().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(words, ["It is ", "3.14 today. ", "Temp! Bye"]);
+}
+
+// ============================================================================
+// events
+// ============================================================================
+
+const EVENTS_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"","thinking":"Hm"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":5,"eval_count":2}"#,
+    "\n",
+);
+
+fn describe(event: ollama_oxide::ResponseEvent) -> String {
+    use ollama_oxide::ResponseEvent;
+    match event {
+        ResponseEvent::TextDelta(text) => format!("text:{text}"),
+        ResponseEvent::ThinkingDelta(text) => format!("thinking:{text}"),
+        ResponseEvent::UsageUpdate {
+            prompt_tokens,
+            completion_tokens,
+        } => format!("usage:{prompt_tokens:?}/{completion_tokens:?}"),
+        ResponseEvent::Done { reason, metrics } => {
+            format!("done:{}:{:?}", reason.unwrap(), metrics.eval_count)
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_chat_stream_events_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(EVENTS_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events: Vec<String> = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .events()
+        .collect()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(describe)
+        .collect();
+    assert_eq!(
+        events,
+        [
+            "thinking:Hm",
+            "text:Hi",
+            "usage:Some(5)/Some(2)",
+            "done:stop:Some(2)"
+        ]
+    );
+}
+
+#[test]
+fn test_chat_stream_events_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(EVENTS_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events: Vec<String> = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .events()
+        .map(|e| describe(e.unwrap()))
+        .collect();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[1], "text:Hi");
+}
+
+#[tokio::test]
+async fn test_chat_stream_with_minimal_buffer() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_stream_buffer(1);
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .tee(|_| {})
+        .collect()
+        .await
+        .unwrap();
+    let texts: Vec<_> = events.iter().filter_map(|e| e.content()).collect();
+    assert_eq!(texts, ["A", "B"]);
+}
+
+// ============================================================================
+// pause / resume
+// ============================================================================
+
+#[tokio::test]
+async fn test_chat_stream_pause_holds_events_until_resume() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let stream = client.chat_stream(&request).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("A"));
+
+    stream.pause();
+    assert!(stream.is_paused());
+    let held = tokio::time::timeout(Duration::from_millis(100), stream.next()).await;
+    assert!(held.is_err(), "next() must wait while paused");
+
+    stream.resume();
+    assert!(!stream.is_paused());
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("B"));
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_chat_stream_pause_handle_through_adapter() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let stream = client.chat_stream(&request).await.unwrap().tee(|_| {});
+    let handle = stream.pause_handle();
+    handle.pause();
+    assert!(stream.is_paused());
+
+    let resumer = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.resume();
+    });
+    let started = std::time::Instant::now();
+    let events = stream.collect().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(40));
+    assert_eq!(events.len(), 2);
+    resumer.await.unwrap();
+}
+
+// ============================================================================
+// first_token_latency
+// ============================================================================
+
+#[tokio::test]
+async fn test_chat_stream_first_token_latency_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let stream = client.chat_stream(&request).await.unwrap().tee(|_| {});
+    stream.next().await.unwrap().unwrap();
+    let ttft = stream
+        .first_token_latency()
+        .expect("measured after first chunk");
+
+    while stream.next().await.is_some() {}
+    assert_eq!(stream.first_token_latency(), Some(ttft));
+}
+
+#[test]
+fn test_chat_stream_first_token_latency_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(TEE_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let mut stream = client.chat_stream_blocking(&request).unwrap();
+    assert_eq!(stream.first_token_latency(), None);
+    stream.next().unwrap().unwrap();
+    let ttft = stream
+        .first_token_latency()
+        .expect("measured after first chunk");
+
+    for _ in stream.by_ref() {}
+    assert_eq!(stream.first_token_latency(), Some(ttft));
+}
+
+#[derive(serde::Deserialize)]
+struct BorrowedChunk<'a> {
+    #[serde(borrow)]
+    message: BorrowedMessage<'a>,
+    done: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct BorrowedMessage<'a> {
+    content: &'a str,
+}
+
+#[tokio::test]
+async fn test_chat_stream_raw_async_yields_lines() {
+    let mut server = mockito::Server::new_async().await;
+
+    let body = concat!(
+        r#"{"model":"m","message":{"role":"assistant","content":"He"},"done":false}"#,
+        "\r\n\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"llo"},"done":true}"#,
+    );
+
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(json!({"stream": true})))
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+
+    let chunks = client
+        .chat_stream_raw(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks[0].as_bytes().starts_with(b"{"));
+    assert!(chunks[0].as_bytes().ends_with(b"}"));
+    let first: BorrowedChunk = chunks[0].parse().unwrap();
+    let last: BorrowedChunk = chunks[1].parse().unwrap();
+    assert_eq!(first.message.content, "He");
+    assert_eq!(last.message.content, "llo");
+    assert!(last.done);
+
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_chat_stream_raw_blocking_yields_lines() {
+    let mut server = mockito::Server::new();
+
+    let body = concat!(
+        r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+        "\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"B"},"done":true}"#,
+        "\n",
+    );
+
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(body)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("x")]);
+
+    let chunks: Vec<_> = client
+        .chat_stream_raw_blocking(&request)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(chunks.len(), 2);
+    let last: BorrowedChunk = chunks[1].parse().unwrap();
+    assert_eq!(last.message.content, "B");
+    assert!(last.done);
+
+    mock.assert();
+}
+
+const USAGE_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"Hello"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":" there, friend"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":9,"eval_count":5}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_with_usage_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(USAGE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap().with_usage();
+
+    let mut usages = Vec::new();
+    while let Some(event) = stream.next().await {
+        let (_, usage) = event.unwrap();
+        usages.push(usage);
+    }
+
+    let counts: Vec<_> = usages
+        .iter()
+        .map(|u| (u.completion_tokens, u.estimated))
+        .collect();
+    assert_eq!(counts, [(2, true), (5, true), (5, false)]);
+    assert_eq!(usages[1].prompt_tokens, None);
+    assert_eq!(usages[2].total_tokens(), Some(14));
+}
+
+#[test]
+fn test_chat_stream_with_usage_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(USAGE_BODY)
+        .create();
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let last = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .with_usage()
+        .last()
+        .unwrap()
+        .unwrap();
+
+    assert!(last.0.is_done());
+    assert_eq!(last.1.prompt_tokens, Some(9));
+    assert_eq!(last.1.completion_tokens, 5);
+    assert!(!last.1.estimated);
+}
+
+#[tokio::test]
+async fn test_chat_stream_with_cancel_dropped_guard_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(USAGE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let token = CancelToken::new();
+    drop(token.drop_guard());
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .with_cancel(&token)
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 1);
+    assert!(events[0].is_done());
+    assert_eq!(events[0].cancel_reason, Some(CancelReason::Dropped));
+    assert_eq!(events[0].eval_count, Some(0));
+    assert!(events[0].total_duration.is_some());
+}
+
+#[tokio::test]
+async fn test_chat_stream_with_timeout_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_chunked_body(|w| {
+            let (first, rest) = USAGE_BODY.split_once('\n').unwrap();
+            w.write_all(first.as_bytes())?;
+            w.write_all(b"\n")?;
+            w.flush()?;
+            std::thread::sleep(Duration::from_millis(500));
+            w.write_all(rest.as_bytes())
+        })
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .with_timeout(Duration::from_millis(100))
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].content(), Some("Hello"));
+    assert!(events[1].is_done());
+    assert_eq!(events[1].cancel_reason, Some(CancelReason::Timeout));
+    assert_eq!(events[1].eval_count, Some(2));
+    assert!(events[1].counts_estimated);
+}
+
+#[tokio::test]
+async fn test_chat_stream_with_cancel_untriggered_is_transparent() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(USAGE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let token = CancelToken::new();
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .with_cancel(&token)
+        .collect()
+        .await
+        .unwrap();
+    token.cancel();
+
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| e.cancel_reason.is_none()));
+    assert!(events.iter().all(|e| !e.counts_estimated));
+    assert_eq!(events[2].eval_count, Some(5));
+}
+
+#[test]
+fn test_chat_stream_with_cancel_blocking_mid_stream() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(USAGE_BODY)
+        .create();
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let token = CancelToken::new();
+    let mut stream = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .with_cancel(&token);
+
+    assert_eq!(stream.next().unwrap().unwrap().content(), Some("Hello"));
+    token.cancel();
+    let last = stream.next().unwrap().unwrap();
+    assert!(stream.next().is_none());
+
+    assert!(last.is_done());
+    assert_eq!(last.content(), None);
+    assert_eq!(last.cancel_reason, Some(CancelReason::User));
+    assert_eq!(last.eval_count, Some(2));
+    assert_eq!(last.prompt_eval_count, None);
+    assert!(last.counts_estimated);
+}
+
+const CUT_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"Hello"},"done":false}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":" there"},"done":false}"#,
+    "\n",
+);
+
+fn interrupted_partial(err: Error) -> String {
+    match err {
+        Error::StreamInterrupted { partial, .. } => partial,
+        other => panic!("expected StreamInterrupted, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_chat_stream_ended_early_is_interrupted_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(CUT_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap();
+
+    let mut items = Vec::new();
+    while let Some(item) = stream.next().await {
+        items.push(item);
+    }
+    assert_eq!(items.len(), 3);
+    let err = items.pop().unwrap().unwrap_err();
+    assert_eq!(interrupted_partial(err), "Hello there");
+}
+
+#[test]
+fn test_chat_stream_ended_early_is_interrupted_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(CUT_BODY)
+        .create();
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let items: Vec<_> = client.chat_stream_blocking(&request).unwrap().collect();
+
+    assert_eq!(items.len(), 3);
+    let err = items.into_iter().last().unwrap().unwrap_err();
+    assert_eq!(interrupted_partial(err), "Hello there");
+}
+
+#[tokio::test]
+async fn test_chat_stream_resumes_with_partial_reply() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(json!({
+            "messages": [{"role": "user", "content": "Hi"}]
+        })))
+        .with_status(200)
+        .with_body(CUT_BODY)
+        .create_async()
+        .await;
+    let resumed = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(json!({
+            "messages": [
+                {"role": "user", "content": "Hi"},
+                {"role": "assistant", "content": "Hello there"}
+            ]
+        })))
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":", friend"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":""},"done":true}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_stream_resume(1);
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let text: String = events
+        .iter()
+        .map(|e| e.content().unwrap_or_default())
+        .collect();
+    assert_eq!(text, "Hello there, friend");
+    assert!(events.last().unwrap().is_done());
+    first.assert_async().await;
+    resumed.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_stream_resume_gives_up_after_attempts() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(CUT_BODY)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_stream_resume(1);
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let err = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap_err();
+
+    assert_eq!(interrupted_partial(err), "Hello thereHello there");
+    mock.assert_async().await;
+}
+
+const BAD_LINE_BODY: &str = concat!(
+    r#"{"model":"m","message":{"role":"assistant","content":"Hel"},"done":false}"#,
+    "\n",
+    "not-json\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"lo"},"done":true}"#,
+    "\n",
+    r#"{"model":"m","message":{"role":"assistant","content":"trailing"},"done":false}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_chat_stream_bad_line_is_reported_and_skipped_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(BAD_LINE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap();
+
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("Hel"));
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, Error::StreamError(_)));
+    let last = stream.next().await.unwrap().unwrap();
+    assert_eq!(last.content(), Some("lo"));
+    assert!(last.is_done());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_chat_stream_adapters_skip_bad_line_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(BAD_LINE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .stop_at(["never"])
+        .coalesce(Duration::from_secs(60))
+        .with_cancel(&CancelToken::new());
+
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("Hel"));
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, Error::StreamError(_)));
+    let last = stream.next().await.unwrap().unwrap();
+    assert_eq!(last.content(), Some("lo"));
+    assert!(last.is_done());
+    assert!(stream.next().await.is_none());
+}
+
+#[test]
+fn test_chat_stream_adapters_skip_bad_line_blocking() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(BAD_LINE_BODY)
+        .create();
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let items: Vec<_> = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .stop_at(["never"])
+        .with_cancel(&CancelToken::new())
+        .collect();
+
+    assert_eq!(items.len(), 3);
+    assert!(matches!(items[1], Err(Error::StreamError(_))));
+    assert!(items[2].as_ref().unwrap().is_done());
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_chat_stream_as_futures_stream_ends_at_done() {
+    use futures::StreamExt;
+
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(BAD_LINE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let stream = client.chat_stream(&request).await.unwrap();
+    let items: Vec<_> = StreamExt::collect(stream).await;
+
+    assert_eq!(items.len(), 3);
+    assert!(items[1].is_err());
+    let text: String = items
+        .iter()
+        .filter_map(|item| item.as_ref().ok()?.content())
+        .collect();
+    assert_eq!(text, "Hello");
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_chat_stream_as_futures_stream_waits_while_paused() {
+    use futures::StreamExt;
+
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(USAGE_BODY)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url()).unwrap();
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let mut stream = client.chat_stream(&request).await.unwrap();
+    let pause = stream.pause_handle();
+    pause.pause();
+
+    let waiting =
+        tokio::time::timeout(Duration::from_millis(50), StreamExt::next(&mut stream)).await;
+    assert!(waiting.is_err(), "a paused stream must not yield");
+
+    pause.resume();
+    let first = StreamExt::next(&mut stream).await.unwrap().unwrap();
+    assert_eq!(first.content(), Some("Hello"));
+}
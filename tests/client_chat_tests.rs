@@ -1,7 +1,7 @@
 //! Tests for chat API methods (POST /api/chat)
 
 use ollama_oxide::{
-    ChatMessage, ChatRequest, ChatResponse, ChatRole, ClientConfig, FormatSetting,
+    ChatMessage, ChatRequest, ChatResponse, ChatRole, ClientConfig, DoneReason, FormatSetting,
     KeepAliveSetting, ModelOptions, OllamaApiAsync, OllamaApiSync, OllamaClient, ResponseMessage,
     ThinkSetting,
 };
@@ -560,7 +560,7 @@ fn test_chat_response_deserialization_full() {
     assert_eq!(response.model(), Some("qwen3:0.6b"));
     assert_eq!(response.content(), Some("Hello! How can I help you today?"));
     assert!(response.is_done());
-    assert_eq!(response.done_reason(), Some("stop"));
+    assert_eq!(response.done_reason(), Some(&DoneReason::Stop));
     assert_eq!(response.prompt_tokens(), Some(11));
     assert_eq!(response.completion_tokens(), Some(18));
 }
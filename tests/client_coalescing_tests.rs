@@ -0,0 +1,154 @@
+//! Tests for coalescing identical concurrent calls (`ClientConfig::with_request_coalescing`)
+
+use ollama_oxide::{
+    CallId, ChatMessage, ChatRequest, ClientConfig, Error, ModelOptions, Observer, OllamaApiAsync,
+    OllamaClient,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const CHAT_RESPONSE: &str =
+    r#"{"model":"m","message":{"role":"assistant","content":"shared"},"done":true}"#;
+
+#[derive(Default)]
+struct CoalescedCounter(AtomicUsize);
+
+impl Observer for CoalescedCounter {
+    fn on_coalesced(&self, _call: CallId, _url: &str) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A chat mock that answers after a delay, so concurrent calls overlap.
+async fn slow_chat(server: &mut mockito::Server, expected: usize) -> mockito::Mock {
+    server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            std::thread::sleep(Duration::from_millis(200));
+            w.write_all(CHAT_RESPONSE.as_bytes())
+        })
+        .expect(expected)
+        .create_async()
+        .await
+}
+
+fn client_for(server: &mockito::Server, coalesce: bool) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let config = if coalesce {
+        config.with_request_coalescing()
+    } else {
+        config
+    };
+    OllamaClient::new(config).unwrap()
+}
+
+fn chat_request(options: Option<ModelOptions>) -> ChatRequest {
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    match options {
+        Some(options) => request.with_options(options),
+        None => request,
+    }
+}
+
+/// Sends `n` copies of `request` at once and returns their answers.
+async fn chat_concurrently(client: &OllamaClient, request: &ChatRequest, n: usize) -> Vec<String> {
+    let calls: Vec<_> = (0..n)
+        .map(|_| {
+            let (client, request) = (client.clone(), request.clone());
+            tokio::spawn(async move { client.chat(&request).await })
+        })
+        .collect();
+    let mut answers = Vec::new();
+    for call in calls {
+        let response = call.await.unwrap().unwrap();
+        answers.push(response.content().unwrap_or_default().to_string());
+    }
+    answers
+}
+
+#[tokio::test]
+async fn test_identical_deterministic_calls_share_one_request() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = slow_chat(&mut server, 1).await;
+    let counter = Arc::new(CoalescedCounter::default());
+    let client = client_for(&server, true).with_observer(counter.clone());
+    let request = chat_request(Some(ModelOptions::default().with_temperature(0.0)));
+
+    let answers = chat_concurrently(&client, &request, 5).await;
+    assert_eq!(answers, vec!["shared"; 5]);
+    assert_eq!(counter.0.load(Ordering::SeqCst), 4);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_seeded_calls_are_coalesced() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = slow_chat(&mut server, 1).await;
+    let client = client_for(&server, true);
+    let request = chat_request(Some(ModelOptions::default().with_seed(42)));
+
+    assert_eq!(chat_concurrently(&client, &request, 3).await.len(), 3);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_sampled_calls_are_not_coalesced() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = slow_chat(&mut server, 2).await;
+    let client = client_for(&server, true);
+
+    assert_eq!(
+        chat_concurrently(&client, &chat_request(None), 2)
+            .await
+            .len(),
+        2
+    );
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_coalescing_is_off_by_default() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = slow_chat(&mut server, 2).await;
+    let client = client_for(&server, false);
+    let request = chat_request(Some(ModelOptions::default().with_temperature(0.0)));
+
+    assert_eq!(chat_concurrently(&client, &request, 2).await.len(), 2);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_coalesced_calls_share_errors() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            std::thread::sleep(Duration::from_millis(200));
+            w.write_all(br#"{"version":"0.12.6"}"#)
+        })
+        .expect(1)
+        .create_async()
+        .await;
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_request_coalescing()
+        .with_max_response_bytes(8);
+    let client = OllamaClient::new(config).unwrap();
+
+    let calls: Vec<_> = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.version().await })
+        })
+        .collect();
+    for call in calls {
+        assert!(matches!(
+            call.await.unwrap(),
+            Err(Error::ResponseTooLarge { limit: 8 })
+        ));
+    }
+    mock.assert_async().await;
+}
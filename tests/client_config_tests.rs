@@ -1,7 +1,7 @@
 // Client Configuration Tests - Phase 0 TDD
 // These tests validate the ClientConfig struct
 
-use ollama_oxide::ClientConfig;
+use ollama_oxide::{ClientConfig, KeepAliveSetting, ModelOptions, Priority};
 use std::time::Duration;
 
 #[test]
@@ -106,6 +106,29 @@ fn test_client_config_with_custom_port() {
     assert_eq!(config.base_url(), "http://localhost:9999");
 }
 
+#[test]
+fn test_client_config_endpoint_url() {
+    let config = ClientConfig::default();
+    assert_eq!(
+        config.endpoint_url("/api/chat").as_str(),
+        "http://localhost:11434/api/chat"
+    );
+    assert_eq!(config.url("/api/chat"), "http://localhost:11434/api/chat");
+}
+
+#[test]
+fn test_client_config_endpoint_url_keeps_base_path() {
+    let config = ClientConfig::with_base_url("http://proxy/ollama/".to_string()).unwrap();
+    assert_eq!(
+        config.endpoint_url("/api/tags").as_str(),
+        "http://proxy/ollama/api/tags"
+    );
+    assert_eq!(
+        config.endpoint_url("/api/x?verbose=true").as_str(),
+        "http://proxy/ollama/api/x?verbose=true"
+    );
+}
+
 #[test]
 fn test_client_config_with_long_timeout() {
     let config = ClientConfig::new(
@@ -129,3 +152,147 @@ fn test_client_config_with_many_retries() {
 
     assert_eq!(config.max_retries(), 10);
 }
+
+#[test]
+fn test_client_config_body_limits_default_to_unlimited() {
+    let config = ClientConfig::default();
+
+    assert_eq!(config.max_request_bytes(), None);
+    assert_eq!(config.max_response_bytes(), None);
+}
+
+#[test]
+fn test_client_config_with_body_limits() {
+    let config = ClientConfig::default()
+        .with_max_request_bytes(1024)
+        .with_max_response_bytes(2048);
+
+    assert_eq!(config.max_request_bytes(), Some(1024));
+    assert_eq!(config.max_response_bytes(), Some(2048));
+}
+
+#[test]
+fn test_client_config_split_timeouts_default_to_unset() {
+    let config = ClientConfig::default();
+
+    assert_eq!(config.timeout(), Duration::from_secs(30));
+    assert_eq!(config.connect_timeout(), None);
+    assert_eq!(config.read_timeout(), None);
+}
+
+#[test]
+fn test_client_config_with_split_timeouts() {
+    let config = ClientConfig::default()
+        .with_connect_timeout(Duration::from_secs(2))
+        .with_read_timeout(Duration::from_secs(600))
+        .with_timeout(Duration::from_secs(900));
+
+    assert_eq!(config.connect_timeout(), Some(Duration::from_secs(2)));
+    assert_eq!(config.read_timeout(), Some(Duration::from_secs(600)));
+    assert_eq!(config.timeout(), Duration::from_secs(900));
+}
+
+#[test]
+fn test_client_config_api_key() {
+    let config = ClientConfig::default();
+    assert_eq!(config.api_key(), None);
+
+    let config = config.with_api_key("secret-key");
+    assert_eq!(config.api_key(), Some("secret-key"));
+    assert!(!format!("{config:?}").contains("secret-key"));
+}
+
+#[test]
+fn test_client_config_ollama_cloud() {
+    let config = ClientConfig::ollama_cloud("cloud-key");
+
+    assert_eq!(config.base_url(), ClientConfig::OLLAMA_CLOUD_URL);
+    assert_eq!(config.api_key(), Some("cloud-key"));
+    assert_eq!(config.timeout(), Duration::from_secs(120));
+    assert_eq!(config.max_retries(), 3);
+    assert_eq!(config.url("/api/chat"), "https://ollama.com/api/chat");
+}
+
+#[test]
+fn test_client_config_request_defaults() {
+    let config = ClientConfig::default();
+    assert_eq!(config.default_model(), None);
+    assert!(config.default_options().is_none());
+    assert!(config.default_keep_alive().is_none());
+
+    let config = config
+        .with_default_model("qwen3:0.6b")
+        .with_default_options(ModelOptions::default().with_temperature(0.2))
+        .with_default_keep_alive(-1);
+    assert_eq!(config.default_model(), Some("qwen3:0.6b"));
+    assert!(config.default_options().is_some());
+    assert_eq!(
+        config.default_keep_alive(),
+        Some(&KeepAliveSetting::Seconds(-1))
+    );
+}
+
+#[test]
+fn test_client_config_stream_buffer() {
+    let config = ClientConfig::default();
+    assert_eq!(config.stream_buffer(), 32);
+    assert_eq!(config.clone().with_stream_buffer(256).stream_buffer(), 256);
+    // A zero-capacity channel cannot exist, so 0 means lockstep
+    assert_eq!(config.with_stream_buffer(0).stream_buffer(), 1);
+}
+
+#[test]
+fn test_client_config_concurrency_and_priorities() {
+    let config = ClientConfig::default();
+    assert_eq!(config.max_concurrency(), None);
+    assert_eq!(config.model_priority("nomic-embed-text"), None);
+
+    let config = config
+        .with_max_concurrency(0)
+        .with_model_priority("nomic-embed-text", Priority::Low);
+    assert_eq!(config.max_concurrency(), Some(1));
+    assert_eq!(
+        config.model_priority("nomic-embed-text"),
+        Some(Priority::Low)
+    );
+    assert_eq!(config.model_priority("qwen3:0.6b"), None);
+}
+
+#[test]
+fn test_client_config_profiles_file_round_trip() {
+    use ollama_oxide::{ConfigProfile, ConfigProfiles, GatewayProfile};
+
+    let path =
+        std::env::temp_dir().join(format!("ollama-oxide-profiles-{}.json", std::process::id()));
+    let staging = ClientConfig::new(
+        "https://ollama.staging.internal".to_string(),
+        Duration::from_secs(90),
+        5,
+    )
+    .unwrap()
+    .with_gateway(GatewayProfile::OpenWebUi)
+    .with_default_model("qwen3:8b");
+    ConfigProfiles::new()
+        .with_profile("local", ConfigProfile::default())
+        .with_profile("staging", ConfigProfile::from(&staging))
+        .with_default("staging")
+        .save(&path)
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(json["profiles"]["staging"]["gateway"], "open_webui");
+
+    let profiles = ConfigProfiles::load(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    let config = profiles.config("staging").unwrap();
+    assert_eq!(config.base_url(), "https://ollama.staging.internal");
+    assert_eq!(config.timeout(), Duration::from_secs(90));
+    assert_eq!(config.max_retries(), 5);
+    assert_eq!(config.gateway(), Some(GatewayProfile::OpenWebUi));
+    assert_eq!(config.default_model(), Some("qwen3:8b"));
+    assert_eq!(
+        profiles.config("local").unwrap().base_url(),
+        "http://localhost:11434"
+    );
+    assert!(ConfigProfiles::load(&path).is_err());
+}
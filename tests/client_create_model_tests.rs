@@ -226,6 +226,57 @@ async fn test_create_model_async_not_found() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_create_model_async_not_retried_on_server_error() {
+    let mut server = Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/api/create")
+        .with_status(500)
+        .expect(1) // Create is not idempotent: no retry
+        .create_async()
+        .await;
+
+    let config = make_config(server.url());
+    let client = OllamaClient::new(config).unwrap();
+
+    let request = CreateRequest::from_model("mario", "qwen3:0.6b");
+    let result = client.create_model(&request).await;
+
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_create_model_async_retried_on_rate_limit() {
+    let mut server = Server::new_async().await;
+
+    let limited = server
+        .mock("POST", "/api/create")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .expect(1)
+        .create_async()
+        .await;
+    let success = server
+        .mock("POST", "/api/create")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"status": "success"}"#)
+        .create_async()
+        .await;
+
+    let config = make_config(server.url());
+    let client = OllamaClient::new(config).unwrap();
+
+    let request = CreateRequest::from_model("mario", "qwen3:0.6b");
+    let response = client.create_model(&request).await.unwrap();
+
+    assert!(response.is_success());
+    limited.assert_async().await;
+    success.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_create_model_async_response_status() {
     let mut server = Server::new_async().await;
@@ -327,6 +378,26 @@ fn test_create_model_sync_not_found() {
     mock.assert();
 }
 
+#[test]
+fn test_create_model_sync_not_retried_on_server_error() {
+    let mut server = Server::new();
+
+    let mock = server
+        .mock("POST", "/api/create")
+        .with_status(500)
+        .expect(1) // Create is not idempotent: no retry
+        .create();
+
+    let config = make_config(server.url());
+    let client = OllamaClient::new(config).unwrap();
+
+    let request = CreateRequest::from_model("mario", "qwen3:0.6b");
+    let result = client.create_model_blocking(&request);
+
+    assert!(result.is_err());
+    mock.assert();
+}
+
 // ============================================================================
 // CreateResponse Tests
 // ============================================================================
@@ -1,8 +1,9 @@
 //! Tests for embed API methods (POST /api/embed)
 
 use ollama_oxide::{
-    ClientConfig, EmbedInput, EmbedRequest, EmbedResponse, ModelOptions, OllamaApiAsync,
-    OllamaApiSync, OllamaClient,
+    BinaryEmbedding, ClientConfig, EmbedInput, EmbedRequest, EmbedResponse, EmbeddingSpace, Error,
+    MetadataFilter, ModelOptions, OllamaApiAsync, OllamaApiSync, OllamaClient, SparseEmbedding,
+    VectorIndex,
 };
 use std::time::Duration;
 
@@ -269,7 +270,7 @@ fn test_embed_response_deserialization() {
 
 #[test]
 fn test_embed_response_default() {
-    let response = EmbedResponse::default();
+    let response: EmbedResponse = EmbedResponse::default();
     assert!(response.model.is_none());
     assert!(response.embeddings.is_empty());
     assert!(response.total_duration.is_none());
@@ -286,7 +287,7 @@ fn test_embed_response_len() {
 
 #[test]
 fn test_embed_response_is_empty() {
-    let empty = EmbedResponse::default();
+    let empty: EmbedResponse = EmbedResponse::default();
     assert!(empty.is_empty());
 
     let non_empty = EmbedResponse {
@@ -308,7 +309,7 @@ fn test_embed_response_dimensions() {
 
 #[test]
 fn test_embed_response_dimensions_empty() {
-    let response = EmbedResponse::default();
+    let response: EmbedResponse = EmbedResponse::default();
     assert_eq!(response.dimensions(), None);
 }
 
@@ -324,13 +325,13 @@ fn test_embed_response_first_embedding() {
 
 #[test]
 fn test_embed_response_first_embedding_empty() {
-    let response = EmbedResponse::default();
+    let response: EmbedResponse = EmbedResponse::default();
     assert_eq!(response.first_embedding(), None);
 }
 
 #[test]
 fn test_embed_response_duration_conversion() {
-    let response = EmbedResponse {
+    let response: EmbedResponse = EmbedResponse {
         total_duration: Some(1_000_000), // 1ms in nanoseconds
         load_duration: Some(500_000),    // 0.5ms in nanoseconds
         ..Default::default()
@@ -342,7 +343,7 @@ fn test_embed_response_duration_conversion() {
 
 #[test]
 fn test_embed_response_duration_conversion_none() {
-    let response = EmbedResponse::default();
+    let response: EmbedResponse = EmbedResponse::default();
     assert!(response.total_duration_ms().is_none());
     assert!(response.load_duration_ms().is_none());
 }
@@ -637,3 +638,379 @@ fn test_embed_response_clone() {
     let cloned = response.clone();
     assert_eq!(response, cloned);
 }
+
+// ============================================================================
+// Precision and Matrix Conversion Tests
+// ============================================================================
+
+const PRECISE_BODY: &str = r#"{
+    "model": "nomic-embed-text",
+    "embeddings": [[0.123456789012345, -0.5]]
+}"#;
+
+#[tokio::test]
+async fn test_embed_as_f64_keeps_full_precision() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(PRECISE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = EmbedRequest::new("nomic-embed-text", "Hello");
+    let response = client.embed_as::<f64>(&request).await.unwrap();
+
+    assert_eq!(response.embeddings[0], vec![0.123456789012345_f64, -0.5]);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_embed_defaults_to_f32() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(PRECISE_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = EmbedRequest::new("nomic-embed-text", "Hello");
+    let response: EmbedResponse<f32> = client.embed(&request).await.unwrap();
+
+    assert_eq!(
+        response.embeddings[0],
+        vec![0.123456789012345_f64 as f32, -0.5]
+    );
+}
+
+#[test]
+fn test_embed_as_blocking_f64() {
+    let mut server = mockito::Server::new();
+    server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(PRECISE_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = EmbedRequest::new("nomic-embed-text", "Hello");
+    let response = client.embed_as_blocking::<f64>(&request).unwrap();
+
+    assert_eq!(response.dimensions(), Some(2));
+    assert_eq!(response.embeddings[0][0], 0.123456789012345);
+}
+
+#[test]
+fn test_embed_response_cast() {
+    let response: EmbedResponse<f64> = EmbedResponse {
+        model: Some("nomic-embed-text".to_string()),
+        embeddings: vec![vec![0.5, -0.25]],
+        prompt_eval_count: Some(2),
+        ..Default::default()
+    };
+    let narrowed: EmbedResponse<f32> = response.clone().cast();
+
+    assert_eq!(narrowed.embeddings, vec![vec![0.5_f32, -0.25]]);
+    assert_eq!(narrowed.model, response.model);
+    assert_eq!(narrowed.prompt_eval_count, Some(2));
+    assert_eq!(narrowed.cast::<f64>(), response);
+}
+
+#[test]
+fn test_embed_response_binarize() {
+    let response: EmbedResponse = EmbedResponse {
+        embeddings: vec![vec![0.4, -0.2, 0.1], vec![0.3, 0.5, -0.7]],
+        ..Default::default()
+    };
+    let binary = response.binarize();
+
+    assert_eq!(binary.len(), 2);
+    assert_eq!(
+        binary[0],
+        BinaryEmbedding::from_values(&[1.0_f32, -1.0, 1.0])
+    );
+    assert_eq!(binary[0].hamming(&binary[1]).unwrap(), 2);
+    assert!((binary[0].jaccard(&binary[1]).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_embed_response_sparsify() {
+    let response: EmbedResponse<f64> = EmbedResponse {
+        embeddings: vec![vec![0.4, -0.9, 0.1, 0.2], vec![0.8, 0.0, -0.3, 0.6]],
+        ..Default::default()
+    };
+    let sparse = response.sparsify(2);
+
+    assert_eq!(sparse[0].entries(), [(0, 0.4), (1, -0.9)]);
+    assert_eq!(sparse[1].entries(), [(0, 0.8), (3, 0.6)]);
+    assert!((sparse[0].dot(&sparse[1]).unwrap() - 0.32).abs() < 1e-9);
+    assert!((sparse[0].jaccard(&sparse[1]).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_sparse_embedding_serde_roundtrip() {
+    let sparse = SparseEmbedding::top_k(&[0.1_f32, -0.5, 0.3], 2);
+    let json = serde_json::to_string(&sparse).unwrap();
+    let back: SparseEmbedding = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, sparse);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_embed_response_to_array2() {
+    let response: EmbedResponse = EmbedResponse {
+        embeddings: vec![vec![0.1, 0.2, 0.3], vec![0.4, 0.5, 0.6]],
+        ..Default::default()
+    };
+    let matrix = response.to_array2().unwrap();
+
+    assert_eq!(matrix.dim(), (2, 3));
+    assert_eq!(matrix[[1, 0]], 0.4);
+    assert_eq!(matrix.row(0).to_vec(), response.embeddings[0]);
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_embed_response_to_array2_ragged() {
+    let response: EmbedResponse = EmbedResponse {
+        embeddings: vec![vec![0.1, 0.2], vec![0.3]],
+        ..Default::default()
+    };
+
+    let err = response.to_array2().unwrap_err();
+    assert!(matches!(err, Error::ValidationError(_)));
+    assert!(err.to_string().contains("embedding 1 has 1 dimensions"));
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_embed_response_to_dmatrix() {
+    let response: EmbedResponse<f64> = EmbedResponse {
+        embeddings: vec![vec![0.1, 0.2, 0.3], vec![0.4, 0.5, 0.6]],
+        ..Default::default()
+    };
+    let matrix = response.to_dmatrix().unwrap();
+
+    assert_eq!(matrix.shape(), (2, 3));
+    assert_eq!(matrix[(0, 2)], 0.3);
+    assert_eq!(matrix[(1, 0)], 0.4);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_embed_response_to_dmatrix_ragged() {
+    let response: EmbedResponse = EmbedResponse {
+        embeddings: vec![vec![0.1], vec![0.2, 0.3]],
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        response.to_dmatrix(),
+        Err(Error::ValidationError(_))
+    ));
+}
+
+// ============================================================================
+// Embedding Space Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_embedding_space_tags_response_vectors() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/embed")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "model": "nomic-embed-text",
+            "input": ["a cat", "a kitten"]
+        })))
+        .with_status(200)
+        .with_body(r#"{"model":"nomic-embed-text:latest","embeddings":[[0.6,0.8],[0.8,0.6]]}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let space = EmbeddingSpace::new("nomic-embed-text", 2);
+
+    let response = client
+        .embed(&space.request(["a cat", "a kitten"]))
+        .await
+        .unwrap();
+    let vectors = space.vectors(response).unwrap();
+
+    assert_eq!(vectors.len(), 2);
+    assert_eq!(vectors[0].space(), &space);
+    assert!((vectors[0].cosine(&vectors[1]).unwrap() - 0.96).abs() < 1e-6);
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_embedding_space_refuses_mixed_models() {
+    let nomic = EmbeddingSpace::new("nomic-embed-text", 3);
+    let minilm = EmbeddingSpace::new("all-minilm", 3);
+    let a = nomic.vector(vec![0.1_f32, 0.2, 0.3]).unwrap();
+    let b = minilm.vector(vec![0.1_f32, 0.2, 0.3]).unwrap();
+
+    let err = a.cosine(&b).unwrap_err();
+    assert!(matches!(err, Error::EmbeddingSpaceMismatch { .. }));
+    assert_eq!(
+        err.to_string(),
+        "Embedding space mismatch: expected nomic-embed-text (3 dimensions), found all-minilm (3 dimensions)"
+    );
+}
+
+#[test]
+fn test_embedding_space_refuses_wrong_dimensions_in_response() {
+    let space = EmbeddingSpace::new("nomic-embed-text", 768);
+    let response: EmbedResponse = EmbedResponse {
+        model: Some("nomic-embed-text".to_string()),
+        embeddings: vec![vec![0.1; 384]],
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        space.vectors(response),
+        Err(Error::EmbeddingSpaceMismatch { .. })
+    ));
+}
+
+// ============================================================================
+// Vector Index Tests
+// ============================================================================
+
+#[derive(Debug, serde::Serialize)]
+struct Doc {
+    source: &'static str,
+    lang: &'static str,
+    year: u16,
+}
+
+fn doc_index() -> (EmbeddingSpace, VectorIndex<Doc>) {
+    let space = EmbeddingSpace::new("nomic-embed-text", 2);
+    let mut index = VectorIndex::new(space.clone());
+    for (id, values, doc) in [
+        (
+            "wiki-en",
+            [1.0, 0.1],
+            Doc {
+                source: "wiki",
+                lang: "en",
+                year: 2020,
+            },
+        ),
+        (
+            "wiki-de",
+            [1.0, 0.0],
+            Doc {
+                source: "wiki",
+                lang: "de",
+                year: 2022,
+            },
+        ),
+        (
+            "blog-en",
+            [0.9, 0.2],
+            Doc {
+                source: "blog",
+                lang: "en",
+                year: 2024,
+            },
+        ),
+    ] {
+        index
+            .insert(id, space.vector(values.to_vec()).unwrap(), doc)
+            .unwrap();
+    }
+    (space, index)
+}
+
+#[test]
+fn test_vector_index_filters_on_metadata() {
+    let (space, index) = doc_index();
+    let query = space.vector(vec![1.0, 0.0]).unwrap();
+
+    let all: Vec<&str> = index
+        .search(&query, 3)
+        .unwrap()
+        .iter()
+        .map(|h| h.id)
+        .collect();
+    assert_eq!(all, ["wiki-de", "wiki-en", "blog-en"]);
+
+    let filter = MetadataFilter::eq("source", "wiki").and(MetadataFilter::eq("lang", "en"));
+    let hits = index.search_filtered(&query, 3, &filter).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, "wiki-en");
+    assert_eq!(hits[0].metadata.year, 2020);
+
+    let recent = MetadataFilter::gte("year", 2022.0);
+    let ids: Vec<&str> = index
+        .search_filtered(&query, 3, &recent)
+        .unwrap()
+        .iter()
+        .map(|h| h.id)
+        .collect();
+    assert_eq!(ids, ["wiki-de", "blog-en"]);
+}
+
+#[test]
+fn test_vector_index_search_where_uses_typed_metadata() {
+    let (space, index) = doc_index();
+    let query = space.vector(vec![1.0, 0.0]).unwrap();
+
+    let hits = index
+        .search_where(&query, 3, |doc| doc.lang == "en" && doc.year > 2021)
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, "blog-en");
+}
+
+#[tokio::test]
+async fn test_vector_index_with_embedded_documents() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(r#"{"model":"nomic-embed-text","embeddings":[[0.6,0.8],[0.8,0.6]]}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let space = EmbeddingSpace::new("nomic-embed-text", 2);
+    let mut index = VectorIndex::new(space.clone());
+
+    let response = client
+        .embed(&space.request(["cats", "dogs"]))
+        .await
+        .unwrap();
+    for (id, vector) in ["cats", "dogs"]
+        .into_iter()
+        .zip(space.vectors(response).unwrap())
+    {
+        index
+            .insert(id, vector, serde_json::json!({"source": "wiki"}))
+            .unwrap();
+    }
+
+    let query = space.vector(vec![0.6, 0.8]).unwrap();
+    let hits = index
+        .search_filtered(&query, 1, &MetadataFilter::eq("source", "wiki"))
+        .unwrap();
+    assert_eq!(hits[0].id, "cats");
+    assert!((hits[0].score - 1.0).abs() < 1e-6);
+
+    let stranger = EmbeddingSpace::new("all-minilm", 2)
+        .vector(vec![0.6, 0.8])
+        .unwrap();
+    assert!(matches!(
+        index.search(&stranger, 1),
+        Err(Error::EmbeddingSpaceMismatch { .. })
+    ));
+}
@@ -0,0 +1,128 @@
+//! Tests for gateway detection and quirks (Open WebUI, LiteLLM)
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, GatewayProfile, OllamaApiAsync, OllamaApiSync,
+    OllamaClient,
+};
+use std::time::Duration;
+
+const SSE_BODY: &str = concat!(
+    "event: message\n",
+    r#"data: {"model":"m","message":{"role":"assistant","content":"He"},"done":false}"#,
+    "\n\n",
+    ": keep-alive\n",
+    r#"data: {"model":"m","message":{"role":"assistant","content":"llo"},"done":true}"#,
+    "\n\n",
+    "data: [DONE]\n\n",
+);
+
+fn client(url: String) -> OllamaClient {
+    let config = ClientConfig::new(url, Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_litellm_detected_from_response_headers() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("x-litellm-version", "1.60.0")
+        .with_body(r#"{"version":"0.6.0"}"#)
+        .create_async()
+        .await;
+
+    let client = client(server.url());
+    assert_eq!(client.gateway(), GatewayProfile::Direct);
+    client.version().await.unwrap();
+    assert_eq!(client.gateway(), GatewayProfile::LiteLlm);
+    assert_eq!(client.clone().gateway(), GatewayProfile::LiteLlm);
+}
+
+#[test]
+fn test_litellm_profile_sends_its_api_key_header() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/api/version")
+        .match_header("x-litellm-api-key", "Bearer sk-1234")
+        .match_header("authorization", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body(r#"{"version":"0.6.0"}"#)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_gateway(GatewayProfile::LiteLlm)
+        .with_api_key("sk-1234");
+    let client = OllamaClient::new(config).unwrap();
+    client.version_blocking().unwrap();
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_open_webui_detected_from_base_url() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/ollama/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.6.0"}"#)
+        .create_async()
+        .await;
+
+    let client = client(format!("{}/ollama", server.url()));
+    assert_eq!(client.gateway(), GatewayProfile::OpenWebUi);
+    client.version().await.unwrap();
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_stream_async_reads_server_sent_events() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "text/event-stream")
+        .with_body(SSE_BODY)
+        .create_async()
+        .await;
+
+    let client = client(server.url());
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let text: String = events.iter().filter_map(|e| e.content()).collect();
+    assert_eq!(text, "Hello");
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn test_chat_stream_blocking_reads_server_sent_events_from_gateway() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(SSE_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_gateway(GatewayProfile::OpenWebUi);
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+    let events: Vec<_> = client
+        .chat_stream_blocking(&request)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let text: String = events.iter().filter_map(|e| e.content()).collect();
+    assert_eq!(text, "Hello");
+}
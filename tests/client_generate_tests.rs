@@ -1,9 +1,9 @@
 //! Tests for generate API methods (POST /api/generate)
 
 use ollama_oxide::{
-    ClientConfig, FormatSetting, GenerateRequest, GenerateResponse, KeepAliveSetting, Logprob,
-    ModelOptions, OllamaApiAsync, OllamaApiSync, OllamaClient, StopSetting, ThinkSetting,
-    TokenLogprob,
+    ClientConfig, DoneReason, FormatSetting, GenerateRequest, GenerateResponse, ImageData,
+    KeepAliveSetting, Logprob, ModelOptions, OllamaApiAsync, OllamaApiSync, OllamaClient,
+    PostProcessor, StopSetting, ThinkSetting, TokenLogprob,
 };
 use std::time::Duration;
 
@@ -362,24 +362,24 @@ fn test_generate_request_with_image() {
         .with_image("base64data1")
         .with_image("base64data2");
 
-    assert_eq!(
-        request.images,
-        Some(vec!["base64data1".to_string(), "base64data2".to_string()])
-    );
+    assert_eq!(request.images.unwrap(), ["base64data1", "base64data2"]);
 }
 
 #[test]
 fn test_generate_request_with_images() {
     let request = GenerateRequest::new("model", "Describe").with_images(["img1", "img2", "img3"]);
 
-    assert_eq!(
-        request.images,
-        Some(vec![
-            "img1".to_string(),
-            "img2".to_string(),
-            "img3".to_string()
-        ])
-    );
+    assert_eq!(request.images.unwrap(), ["img1", "img2", "img3"]);
+}
+
+#[test]
+fn test_generate_request_raw_image_serializes_as_base64() {
+    let request = GenerateRequest::new("model", "Describe")
+        .with_image(ImageData::from_bytes(&b"hello"[..]))
+        .with_image("d29ybGQ=");
+
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["images"], serde_json::json!(["aGVsbG8=", "d29ybGQ="]));
 }
 
 #[test]
@@ -441,7 +441,7 @@ fn test_generate_response_deserialization() {
         Some("The sky is blue because...".to_string())
     );
     assert_eq!(response.done, Some(true));
-    assert_eq!(response.done_reason, Some("stop".to_string()));
+    assert_eq!(response.done_reason, Some(DoneReason::Stop));
     assert_eq!(response.eval_count, Some(18));
 }
 
@@ -623,6 +623,42 @@ async fn test_generate_async_success() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_generate_async_encodes_file_and_reader_images() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "images": ["aGVsbG8=", "d29ybGQ="]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model": "llava", "response": "Two words.", "done": true}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let path = std::env::temp_dir().join(format!("ollama-oxide-image-{}.bin", std::process::id()));
+    std::fs::write(&path, b"hello").unwrap();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = GenerateRequest::new("llava", "What do these say?")
+        .with_image(ImageData::from_path(&path))
+        .with_image(ImageData::from_reader(std::io::Cursor::new(
+            b"world".to_vec(),
+        )));
+    let response = client.generate(&request).await;
+    // The reader keeps its bytes, so the same request can be sent again
+    let again = client.generate(&request).await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(response.unwrap().text(), Some("Two words."));
+    assert_eq!(again.unwrap().text(), Some("Two words."));
+    mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_generate_async_with_options() {
     let mut server = mockito::Server::new_async().await;
@@ -732,6 +768,68 @@ async fn test_generate_async_max_retries_exceeded() {
     mock.assert_async().await;
 }
 
+// ============================================================================
+// Prefetch Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_prefetch_awaits_response() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "prompt": "Next step"
+        })))
+        .with_status(200)
+        .with_body(r#"{"model":"m","response":"Ready.","done":true}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let prefetch = client.prefetch(&GenerateRequest::new("m", "Next step"));
+
+    let response = prefetch.await.unwrap();
+    assert_eq!(response.text(), Some("Ready."));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_prefetch_cancel_closes_connection() {
+    use std::io::Read;
+
+    // A server that reads the request, never answers, and reports when the
+    // client hangs up.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (received_tx, received_rx) = std::sync::mpsc::channel();
+    let (closed_tx, closed_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut socket, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = received_tx.send(socket.read(&mut buf).unwrap());
+        while socket.read(&mut buf).unwrap_or(0) > 0 {}
+        let _ = closed_tx.send(());
+    });
+
+    let config = ClientConfig::new(url, Duration::from_secs(30), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let prefetch = client.prefetch(&GenerateRequest::new("m", "Maybe"));
+
+    let received = tokio::task::spawn_blocking(move || received_rx.recv().unwrap())
+        .await
+        .unwrap();
+    assert!(received > 0);
+    assert!(!prefetch.is_finished());
+
+    prefetch.cancel();
+    let closed =
+        tokio::task::spawn_blocking(move || closed_rx.recv_timeout(Duration::from_secs(5)))
+            .await
+            .unwrap();
+    assert!(closed.is_ok(), "connection was not closed after cancel");
+}
+
 // ============================================================================
 // Sync API Tests
 // ============================================================================
@@ -814,6 +912,120 @@ fn test_generate_sync_retry_on_server_error() {
     mock_success.assert();
 }
 
+// ============================================================================
+// Post-Processing Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_generate_async_applies_post_processor() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"m","response":"<think>ok</think>Here:\n```py\nprint(1)\n```<|end|>","done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_post_processor(
+            PostProcessor::new()
+                .strip_think()
+                .strip_stop(["<|end|>"])
+                .extract_code(),
+        );
+    let client = OllamaClient::new(config).unwrap();
+
+    let response = client
+        .generate(&GenerateRequest::new("m", "Print one."))
+        .await
+        .unwrap();
+    assert_eq!(response.text(), Some("print(1)\n"));
+}
+
+#[test]
+fn test_generate_sync_without_post_processor_keeps_text() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"m","response":"  <think>ok</think> Hi ","done":true}"#)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_post_processor(PostProcessor::new());
+    assert!(config.post_processor().is_none());
+    let client = OllamaClient::new(config).unwrap();
+
+    let response = client
+        .generate_blocking(&GenerateRequest::new("m", "Hi"))
+        .unwrap();
+    assert_eq!(response.text(), Some("  <think>ok</think> Hi "));
+}
+
+// ============================================================================
+// Byte Stream Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_generate_stream_bytes_forwards_body_verbatim() {
+    let mut server = mockito::Server::new_async().await;
+    let body = concat!(
+        r#"{"model":"m","response":"<think>","done":false}"#,
+        "\r\n\n",
+        r#"{"model":"m","response":"Hi","done":true}"#,
+    );
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"stream": true}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_post_processor(PostProcessor::new().strip_think());
+    let client = OllamaClient::new(config).unwrap();
+
+    let chunks = client
+        .generate_stream_bytes(&GenerateRequest::new("m", "Hi"))
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(chunks.concat(), body.as_bytes());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_generate_stream_bytes_error_status() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .with_body(r#"{"error":"model not found"}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+
+    let result = client
+        .generate_stream_bytes(&GenerateRequest::new("m", "Hi"))
+        .await;
+    assert!(result.is_err());
+}
+
 // ============================================================================
 // Type Safety Tests
 // ============================================================================
@@ -916,3 +1128,144 @@ fn test_generate_request_debug() {
     assert!(debug_str.contains("model"));
     assert!(debug_str.contains("Hello"));
 }
+
+// ============================================================================
+// Streaming API Tests
+// ============================================================================
+
+const STREAM_BODY: &str = concat!(
+    r#"{"model":"m","response":"Hel","done":false}"#,
+    "\n",
+    r#"{"model":"m","response":"lo","done":false}"#,
+    "\n",
+    r#"{"model":"m","response":"","done":true,"done_reason":"stop","eval_count":2}"#,
+    "\n",
+);
+
+#[tokio::test]
+async fn test_generate_stream_async_yields_tokens() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "model": "m",
+            "prompt": "Hi",
+            "stream": true
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body(STREAM_BODY)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = GenerateRequest::new("m", "Hi");
+
+    let events = client
+        .generate_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let tokens: Vec<&str> = events.iter().filter_map(|e| e.text()).collect();
+    assert_eq!(tokens, ["Hel", "lo", ""]);
+    assert!(events[2].is_done());
+    assert_eq!(events[2].done_reason, Some(DoneReason::Stop));
+    assert_eq!(events[2].eval_count, Some(2));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_generate_stream_blocking_yields_tokens() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"stream": true}),
+        ))
+        .with_status(200)
+        .with_body(STREAM_BODY)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = GenerateRequest::new("m", "Hi");
+
+    let text: String = client
+        .generate_stream_blocking(&request)
+        .unwrap()
+        .map(|e| e.unwrap().text().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(text, "Hello");
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_generate_stream_applies_post_processor() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","response":"<think>hmm</think>","done":false}"#,
+            "\n",
+            r#"{"model":"m","response":" Hello ","done":false}"#,
+            "\n",
+            r#"{"model":"m","response":"","done":true}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_post_processor(PostProcessor::new().strip_think().trim());
+    let client = OllamaClient::new(config).unwrap();
+    let request = GenerateRequest::new("m", "Hi");
+
+    let events = client
+        .generate_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let text: String = events.iter().filter_map(|e| e.text()).collect();
+    assert_eq!(text, "Hello");
+}
+
+#[tokio::test]
+async fn test_generate_stream_ended_early_is_interrupted() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","response":"Hel","done":false}"#,
+            "\n",
+            r#"{"model":"m","response":"lo","done":false}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let request = GenerateRequest::new("m", "Hi");
+
+    let err = client
+        .generate_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap_err();
+    match err {
+        ollama_oxide::Error::StreamInterrupted { partial, .. } => assert_eq!(partial, "Hello"),
+        other => panic!("expected StreamInterrupted, got {other:?}"),
+    }
+}
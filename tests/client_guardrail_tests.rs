@@ -0,0 +1,203 @@
+//! Tests for guardrails on prompts and replies
+
+use mockito::{Matcher, Server};
+use ollama_oxide::{
+    CallId, ChatMessage, ChatRequest, Error, FnGuardrail, GenerateRequest, GuardrailAction,
+    Observer, OllamaApiAsync, OllamaApiSync, OllamaClient,
+};
+use std::sync::{Arc, Mutex};
+
+fn redact_digits() -> Arc<FnGuardrail> {
+    Arc::new(
+        FnGuardrail::new("redact-digits")
+            .on_input(|text| GuardrailAction::Replace(text.replace(char::is_numeric, "#")))
+            .on_output(|text| match text.contains("password") {
+                true => GuardrailAction::Block("reply leaks a credential".to_string()),
+                false => GuardrailAction::Annotate(format!("{} chars", text.len())),
+            }),
+    )
+}
+
+#[derive(Default)]
+struct Notes(Mutex<Vec<String>>);
+
+impl Observer for Notes {
+    fn on_guardrail_note(&self, _call: CallId, _url: &str, guardrail: &str, note: &str) {
+        self.0.lock().unwrap().push(format!("{guardrail}: {note}"));
+    }
+}
+
+#[tokio::test]
+async fn test_chat_sends_rewritten_prompt_and_reports_notes() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "messages": [{"role": "user", "content": "card ####"}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"noted"},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let notes = Arc::new(Notes::default());
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(redact_digits())
+        .with_observer(notes.clone());
+    let request = ChatRequest::new("qwen3", [ChatMessage::user("card 1234")]);
+    let response = client.chat(&request).await.unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(response.content(), Some("noted"));
+    assert_eq!(*notes.0.lock().unwrap(), ["redact-digits: 5 chars"]);
+}
+
+#[tokio::test]
+async fn test_blocked_prompt_is_never_sent() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(no_poker());
+    let result = client
+        .generate(&GenerateRequest::new("qwen3", "poker odds?"))
+        .await;
+
+    mock.assert_async().await;
+    assert!(matches!(
+        result,
+        Err(Error::Blocked { guardrail, reason }) if guardrail == "no-poker" && reason == "gambling"
+    ));
+}
+
+#[test]
+fn test_blocking_chat_blocks_reply() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"the password is hunter2"},"done":true}"#,
+        )
+        .create();
+
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(redact_digits());
+    let request = ChatRequest::new("qwen3", [ChatMessage::user("hi")]);
+    let result = client.chat_blocking(&request);
+
+    mock.assert();
+    assert!(matches!(result, Err(Error::Blocked { .. })));
+}
+
+fn no_poker() -> Arc<FnGuardrail> {
+    Arc::new(
+        FnGuardrail::new("no-poker").on_input(|text| match text.contains("poker") {
+            true => GuardrailAction::Block("gambling".to_string()),
+            false => GuardrailAction::Allow,
+        }),
+    )
+}
+
+#[tokio::test]
+async fn test_raw_byte_and_callback_streams_check_prompts() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(no_poker());
+    let chat = ChatRequest::new("qwen3", [ChatMessage::user("poker odds?")]);
+    let generate = GenerateRequest::new("qwen3", "poker odds?");
+
+    assert!(matches!(
+        client.chat_stream_raw(&chat).await,
+        Err(Error::Blocked { .. })
+    ));
+    assert!(matches!(
+        client.generate_stream_bytes(&generate).await,
+        Err(Error::Blocked { .. })
+    ));
+    assert!(matches!(
+        client.chat_with_callback(&chat, |_| {}).await,
+        Err(Error::Blocked { .. })
+    ));
+    assert!(matches!(
+        client.generate_with_callback(&generate, |_| {}).await,
+        Err(Error::Blocked { .. })
+    ));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_blocking_raw_and_callback_streams_check_prompts() {
+    let mut server = Server::new();
+    let mock = server.mock("POST", Matcher::Any).expect(0).create();
+
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(no_poker());
+    let chat = ChatRequest::new("qwen3", [ChatMessage::user("poker odds?")]);
+    let generate = GenerateRequest::new("qwen3", "poker odds?");
+
+    assert!(matches!(
+        client.chat_stream_raw_blocking(&chat),
+        Err(Error::Blocked { .. })
+    ));
+    assert!(matches!(
+        client.chat_with_callback_blocking(&chat, |_| {}),
+        Err(Error::Blocked { .. })
+    ));
+    assert!(matches!(
+        client.generate_with_callback_blocking(&generate, |_| {}),
+        Err(Error::Blocked { .. })
+    ));
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_raw_stream_sends_rewritten_prompt() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "messages": [{"role": "user", "content": "card ####"}]
+        })))
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"ok"},"done":true}"#,
+            "\n"
+        ))
+        .create_async()
+        .await;
+
+    let client = OllamaClient::with_base_url(server.url())
+        .unwrap()
+        .with_guardrail(redact_digits());
+    let request = ChatRequest::new("qwen3", [ChatMessage::user("card 1234")]);
+    let chunks = client
+        .chat_stream_raw(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(chunks.len(), 1);
+}
@@ -0,0 +1,154 @@
+//! Tests for the background health watcher (`OllamaClient::watch_health`)
+
+use ollama_oxide::{ClientConfig, OllamaClient, ServerStatus};
+use std::time::Duration;
+
+async fn next_status(rx: &mut tokio::sync::watch::Receiver<ServerStatus>) -> ServerStatus {
+    tokio::time::timeout(Duration::from_secs(5), rx.changed())
+        .await
+        .expect("status did not change in time")
+        .expect("watcher stopped");
+    rx.borrow().clone()
+}
+
+#[tokio::test]
+async fn test_watch_health_initial_status_is_unknown() {
+    let client = OllamaClient::with_base_url("http://127.0.0.1:1").unwrap();
+    let rx = client.watch_health(Duration::from_secs(60));
+    assert_eq!(*rx.borrow(), ServerStatus::Unknown);
+}
+
+#[tokio::test]
+async fn test_watch_health_reports_up() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::from_millis(50));
+
+    let status = next_status(&mut rx).await;
+    assert_eq!(
+        status,
+        ServerStatus::Up {
+            version: "0.12.6".to_string()
+        }
+    );
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_watch_health_zero_interval_still_probes() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::ZERO);
+
+    assert!(next_status(&mut rx).await.is_up());
+}
+
+#[tokio::test]
+async fn test_watch_health_reports_degraded_on_server_error() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/api/version")
+        .with_status(503)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::from_millis(50));
+
+    let status = next_status(&mut rx).await;
+    assert!(matches!(status, ServerStatus::Degraded { .. }));
+    assert!(status.last_error().unwrap().contains("503"));
+}
+
+#[tokio::test]
+async fn test_watch_health_reports_down_when_unreachable() {
+    let config =
+        ClientConfig::new("http://127.0.0.1:1".to_string(), Duration::from_secs(1), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::from_millis(50));
+
+    let status = next_status(&mut rx).await;
+    assert!(matches!(status, ServerStatus::Down { .. }));
+    assert!(!status.is_up());
+}
+
+#[tokio::test]
+async fn test_watch_health_notifies_on_recovery() {
+    let mut server = mockito::Server::new_async().await;
+    let failing = server
+        .mock("GET", "/api/version")
+        .with_status(500)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::from_millis(50));
+
+    assert!(matches!(
+        next_status(&mut rx).await,
+        ServerStatus::Degraded { .. }
+    ));
+
+    failing.remove_async().await;
+    let _ok = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    assert!(next_status(&mut rx).await.is_up());
+}
+
+#[tokio::test]
+async fn test_watch_health_new_error_text_does_not_notify() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("GET", "/api/version")
+        .with_status(500)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let mut rx = client.watch_health(Duration::from_millis(20));
+
+    assert!(matches!(
+        next_status(&mut rx).await,
+        ServerStatus::Degraded { .. }
+    ));
+
+    first.remove_async().await;
+    let _second = server
+        .mock("GET", "/api/version")
+        .with_status(503)
+        .create_async()
+        .await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(!rx.has_changed().unwrap());
+    assert!(matches!(
+        &*rx.borrow(),
+        ServerStatus::Degraded { last_error } if last_error.contains("503")
+    ));
+}
@@ -0,0 +1,125 @@
+//! Tests for HedgedClient (hedged requests across replicas)
+
+use mockito::Server;
+use ollama_oxide::{ClientConfig, EmbedRequest, Error, HedgedClient, OllamaClient};
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+const EMBED_BODY: &str = r#"{"model":"nomic-embed-text","embeddings":[[0.5,0.25]]}"#;
+
+fn client(url: String) -> OllamaClient {
+    let config = ClientConfig::new(url, Duration::from_secs(10), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+/// Accepts connections but never answers, standing in for a stalled replica.
+fn stalled_replica() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    (listener, url)
+}
+
+/// A base URL nothing listens on, so requests fail immediately.
+fn dead_replica() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    url
+}
+
+#[tokio::test]
+async fn test_fast_primary_skips_hedge() {
+    let mut primary = Server::new_async().await;
+    let mut backup = Server::new_async().await;
+    let primary_mock = primary
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(EMBED_BODY)
+        .create_async()
+        .await;
+    let backup_mock = backup
+        .mock("POST", "/api/embed")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let hedged = HedgedClient::new(
+        vec![client(primary.url()), client(backup.url())],
+        Duration::from_secs(5),
+    )
+    .unwrap();
+    let response = hedged
+        .embed(&EmbedRequest::new("nomic-embed-text", "hi"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.embeddings.len(), 1);
+    primary_mock.assert_async().await;
+    backup_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stalled_primary_is_hedged_after_threshold() {
+    let (_listener, stalled) = stalled_replica();
+    let mut backup = Server::new_async().await;
+    let backup_mock = backup
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(EMBED_BODY)
+        .create_async()
+        .await;
+
+    let hedged = HedgedClient::new(
+        vec![client(stalled), client(backup.url())],
+        Duration::from_millis(100),
+    )
+    .unwrap();
+    let started = Instant::now();
+    let response = hedged
+        .embed(&EmbedRequest::new("nomic-embed-text", "hi"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.embeddings.len(), 1);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    backup_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_failed_primary_is_hedged_immediately() {
+    let mut backup = Server::new_async().await;
+    let _backup_mock = backup
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let hedged = HedgedClient::new(
+        vec![client(dead_replica()), client(backup.url())],
+        Duration::from_secs(30),
+    )
+    .unwrap();
+    let started = Instant::now();
+    let version = hedged.version().await.unwrap();
+
+    assert_eq!(version.version, "0.12.6");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_both_replicas_fail() {
+    let hedged = HedgedClient::new(
+        vec![client(dead_replica()), client(dead_replica())],
+        Duration::from_millis(10),
+    )
+    .unwrap();
+
+    let result = hedged.version().await;
+
+    assert!(matches!(result, Err(Error::MaxRetriesExceededError(0))));
+}
@@ -0,0 +1,113 @@
+//! Tests for OllamaClient::model and ModelHandle
+
+use mockito::{Matcher, Server};
+use ollama_oxide::{ChatMessage, ClientConfig, ModelOptions, OllamaClient};
+use std::time::Duration;
+
+fn make_client(base_url: String) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_model_handle_generate_binds_model_and_options() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "llama3.2",
+            "prompt": "Why is the sky blue?",
+            "options": {"temperature": 0.5}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"llama3.2","response":"Rayleigh scattering.","done":true}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let llama = client
+        .model("llama3.2")
+        .with_options(ModelOptions::default().with_temperature(0.5));
+    let response = llama.generate("Why is the sky blue?").await.unwrap();
+
+    assert_eq!(response.text(), Some("Rayleigh scattering."));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_model_handle_chat() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "llama3.2",
+            "messages": [{"role": "user", "content": "Hello!"}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"Hi there."},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let response = client
+        .model("llama3.2")
+        .chat([ChatMessage::user("Hello!")])
+        .await
+        .unwrap();
+
+    assert_eq!(response.content(), Some("Hi there."));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_model_handle_embed() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/embed")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "nomic-embed-text",
+            "input": ["first", "second"]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"nomic-embed-text","embeddings":[[0.1,0.2],[0.3,0.4]]}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let response = client
+        .model("nomic-embed-text")
+        .embed(["first", "second"])
+        .await
+        .unwrap();
+
+    assert_eq!(response.embeddings.len(), 2);
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_model_handle_generate_blocking() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::PartialJson(
+            serde_json::json!({"model": "qwen3:0.6b"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"qwen3:0.6b","response":"ok","done":true}"#)
+        .create();
+
+    let client = make_client(server.url());
+    let response = client
+        .model("qwen3:0.6b")
+        .generate_blocking("ping")
+        .unwrap();
+
+    assert_eq!(response.text(), Some("ok"));
+    mock.assert();
+}
@@ -0,0 +1,820 @@
+//! Tests for Observer lifecycle callbacks
+
+use ollama_oxide::{
+    CallId, ChatMessage, ChatRequest, ClientConfig, Error, ErrorReport, Extensions, Observer,
+    OllamaApiAsync, OllamaApiSync, OllamaClient,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records every callback as a short string.
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<String>>,
+}
+
+impl Recorder {
+    fn events(&self) -> Vec<String> {
+        self.events.lock().unwrap().clone()
+    }
+
+    fn record(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl Observer for Recorder {
+    fn on_request_start(&self, _call: CallId, method: &str, _url: &str) {
+        self.record(format!("start {}", method));
+    }
+
+    fn on_chunk(&self, _call: CallId, _url: &str, _bytes: usize) {
+        self.record("chunk".to_string());
+    }
+
+    fn on_first_token(&self, _call: CallId, _url: &str, _latency: Duration) {
+        self.record("first_token".to_string());
+    }
+
+    fn on_complete(&self, _call: CallId, _url: &str, status: u16, _elapsed: Duration) {
+        self.record(format!("complete {}", status));
+    }
+
+    fn on_retry(&self, _call: CallId, _url: &str, attempt: u32, reason: &str) {
+        self.record(format!("retry {} {}", attempt, reason));
+    }
+
+    fn on_error(&self, _call: CallId, _url: &str, error: &Error) {
+        self.record(format!("error {}", error));
+    }
+}
+
+fn observed_client(server: &mockito::Server, max_retries: u32) -> (OllamaClient, Arc<Recorder>) {
+    let recorder = Arc::new(Recorder::default());
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), max_retries).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(recorder.clone());
+    (client, recorder)
+}
+
+#[tokio::test]
+async fn test_observer_success_async() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let (client, recorder) = observed_client(&server, 0);
+    assert!(client.has_observers());
+    client.version().await.unwrap();
+
+    assert_eq!(recorder.events(), vec!["start GET", "complete 200"]);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_observer_retry_then_success_async() {
+    let mut server = mockito::Server::new_async().await;
+    let fail = server
+        .mock("GET", "/api/version")
+        .with_status(500)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let (client, recorder) = observed_client(&server, 1);
+    client.version().await.unwrap();
+
+    assert_eq!(
+        recorder.events(),
+        vec!["start GET", "retry 1 500", "complete 200"]
+    );
+    fail.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[test]
+fn test_observer_client_error_blocking() {
+    let mut server = mockito::Server::new();
+    let mock = server.mock("POST", "/api/chat").with_status(404).create();
+
+    let (client, recorder) = observed_client(&server, 0);
+    let request = ChatRequest::new("missing", [ChatMessage::user("Hi")]);
+    let result = client.chat_blocking(&request);
+
+    assert!(result.is_err());
+    assert_eq!(
+        recorder.events(),
+        vec!["start POST", "complete 404", "error HTTP status error: 404"]
+    );
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_observer_stream_chunks_async() {
+    let mut server = mockito::Server::new_async().await;
+    let body = concat!(
+        r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+        "\n",
+        r#"{"model":"m","message":{"role":"assistant","content":"B"},"done":true}"#,
+        "\n",
+    );
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+
+    let (client, recorder) = observed_client(&server, 0);
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let events = client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        recorder.events(),
+        vec![
+            "start POST",
+            "complete 200",
+            "chunk",
+            "first_token",
+            "chunk"
+        ]
+    );
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_observer_stream_chunks_blocking() {
+    let mut server = mockito::Server::new();
+    let body = concat!(
+        r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+        "\n",
+        "not json\n",
+    );
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(body)
+        .create();
+
+    let (client, recorder) = observed_client(&server, 0);
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let results: Vec<_> = client.chat_stream_blocking(&request).unwrap().collect();
+
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    let events = recorder.events();
+    assert_eq!(
+        &events[..5],
+        &[
+            "start POST",
+            "complete 200",
+            "chunk",
+            "first_token",
+            "chunk"
+        ]
+    );
+    assert!(events[5].starts_with("error Stream error"));
+    mock.assert();
+}
+
+#[test]
+fn test_client_without_observers() {
+    let client = OllamaClient::default().unwrap();
+    assert!(!client.has_observers());
+}
+
+// ============================================================================
+// TranscriptRecorder
+// ============================================================================
+
+fn transcript_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "ollama-oxide-{}-{}.ndjson",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn read_transcript(path: &std::path::Path) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_transcript_recorder_reassembles_stream() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":"He"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":"llo"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":""},"done":true,"eval_count":2}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+
+    let path = transcript_path("stream");
+    let recorder = Arc::new(ollama_oxide::http::TranscriptRecorder::create(&path).unwrap());
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap().with_observer(recorder);
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+
+    client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+
+    let entries = read_transcript(&path);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["kind"], "request");
+    assert_eq!(entries[0]["body"]["messages"][0]["content"], "Hi");
+    assert_eq!(entries[0]["body"]["stream"], true);
+    assert_eq!(entries[1]["kind"], "response");
+    assert_eq!(entries[1]["body"]["message"]["content"], "Hello");
+    assert_eq!(entries[1]["body"]["eval_count"], 2);
+    assert!(entries[1]["ts_ms"].as_u64().unwrap() >= entries[0]["ts_ms"].as_u64().unwrap());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_transcript_recorder_appends_blocking_calls() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(r#"{"model":"m","message":{"role":"assistant","content":"Yo"},"done":true}"#)
+        .expect(2)
+        .create();
+
+    let path = transcript_path("blocking");
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+    for _ in 0..2 {
+        let recorder = Arc::new(ollama_oxide::http::TranscriptRecorder::create(&path).unwrap());
+        let client = OllamaClient::new(config.clone())
+            .unwrap()
+            .with_observer(recorder);
+        client.chat_blocking(&request).unwrap();
+    }
+
+    let entries = read_transcript(&path);
+    let kinds: Vec<&str> = entries
+        .iter()
+        .map(|e| e["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["request", "response", "request", "response"]);
+    assert_eq!(entries[3]["body"]["message"]["content"], "Yo");
+    let _ = std::fs::remove_file(&path);
+}
+
+// ============================================================================
+// DatasetExporter
+// ============================================================================
+
+#[tokio::test]
+async fn test_dataset_exporter_pairs_prompts_and_completions() {
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":"He"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":"llo"},"done":true,"eval_count":2}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+    let _generate = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(r#"{"model":"m","response":"Blue.","done":true,"done_reason":"stop"}"#)
+        .create_async()
+        .await;
+    let _version = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let path = transcript_path("dataset");
+    let exporter = ollama_oxide::DatasetExporter::create(&path)
+        .unwrap()
+        .with_redactor(|text| text.replace("Ada", "[NAME]"));
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(Arc::new(exporter));
+
+    let request = ChatRequest::new("m", [ChatMessage::user("I am Ada")]);
+    client
+        .chat_stream(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    client
+        .generate(&ollama_oxide::GenerateRequest::new("m", "Sky color?").with_system("Ada's bot"))
+        .await
+        .unwrap();
+    client.version().await.unwrap();
+
+    let entries = read_transcript(&path);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["prompt"][0]["content"], "I am [NAME]");
+    assert_eq!(entries[0]["completion"], "Hello");
+    assert_eq!(entries[0]["metadata"]["endpoint"], "chat");
+    assert_eq!(entries[0]["metadata"]["eval_count"], 2);
+    assert_eq!(entries[1]["prompt"], "Sky color?");
+    assert_eq!(entries[1]["completion"], "Blue.");
+    assert_eq!(entries[1]["metadata"]["system"], "[NAME]'s bot");
+    assert_eq!(entries[1]["metadata"]["done_reason"], "stop");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_dataset_exporter_samples_and_skips_failures() {
+    let mut server = mockito::Server::new_async().await;
+    let ok = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(r#"{"model":"m","response":"ok","done":true}"#)
+        .expect(4)
+        .create_async()
+        .await;
+
+    let path = transcript_path("dataset-sampled");
+    let exporter = ollama_oxide::DatasetExporter::create(&path)
+        .unwrap()
+        .with_sample_rate(0.5);
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(Arc::new(exporter));
+
+    for i in 0..4 {
+        let request = ollama_oxide::GenerateRequest::new("m", format!("prompt {i}"));
+        client.generate(&request).await.unwrap();
+    }
+    ok.remove_async().await;
+    let _failing = server
+        .mock("POST", "/api/generate")
+        .with_status(400)
+        .create_async()
+        .await;
+    let failed = ollama_oxide::GenerateRequest::new("m", "fails");
+    assert!(client.generate(&failed).await.is_err());
+
+    let prompts: Vec<String> = read_transcript(&path)
+        .iter()
+        .map(|e| e["prompt"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(prompts, ["prompt 1", "prompt 3"]);
+    let _ = std::fs::remove_file(&path);
+}
+
+// ============================================================================
+// LatencyStats
+// ============================================================================
+
+#[tokio::test]
+async fn test_latency_stats_observes_stream_and_plain_calls() {
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(concat!(
+            r#"{"model":"m","message":{"role":"assistant","content":"A"},"done":false}"#,
+            "\n",
+            r#"{"model":"m","message":{"role":"assistant","content":""},"done":true}"#,
+            "\n",
+        ))
+        .create_async()
+        .await;
+    let _version = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let stats = Arc::new(ollama_oxide::LatencyStats::new(Duration::from_secs(60)));
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(stats.clone());
+
+    let request = ChatRequest::new("m", [ChatMessage::user("Hi")]);
+    for _ in 0..3 {
+        client
+            .chat_stream(&request)
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+    }
+    client.version().await.unwrap();
+
+    let chat = stats.summary("/api/chat", Some("m")).unwrap();
+    assert_eq!(chat.count, 3);
+    let ttft = chat.ttft.unwrap();
+    assert!(ttft.p50 <= chat.total.p50);
+
+    let version = stats.summary("/api/version", None).unwrap();
+    assert_eq!(version.count, 1);
+    assert_eq!(version.ttft, None);
+    assert_eq!(stats.snapshot().len(), 2);
+}
+
+// ============================================================================
+// Idempotency Key Tests
+// ============================================================================
+
+/// Records the idempotency keys handed to observers.
+#[derive(Default)]
+struct KeyRecorder {
+    keys: Mutex<Vec<String>>,
+}
+
+impl Observer for KeyRecorder {
+    fn on_idempotency_key(&self, _call: CallId, _url: &str, key: &str) {
+        self.keys.lock().unwrap().push(key.to_string());
+    }
+}
+
+/// A mock body that records the `Idempotency-Key` header it was sent.
+fn capture_key(
+    seen: &Arc<Mutex<Vec<String>>>,
+    body: &'static str,
+) -> impl Fn(&mockito::Request) -> Vec<u8> + Send + Sync + 'static {
+    let seen = seen.clone();
+    move |request| {
+        for value in request.header("idempotency-key") {
+            seen.lock()
+                .unwrap()
+                .push(value.to_str().unwrap().to_string());
+        }
+        body.as_bytes().to_vec()
+    }
+}
+
+#[tokio::test]
+async fn test_idempotency_key_reused_across_retries_async() {
+    let mut server = mockito::Server::new_async().await;
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let fail = server
+        .mock("POST", "/api/generate")
+        .with_status(500)
+        .with_body_from_request(capture_key(&seen, ""))
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body_from_request(capture_key(
+            &seen,
+            r#"{"model":"m","response":"hi","done":true}"#,
+        ))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let recorder = Arc::new(KeyRecorder::default());
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 1)
+        .unwrap()
+        .with_idempotency_keys();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(recorder.clone());
+
+    let request = ollama_oxide::GenerateRequest::new("m", "hello");
+    client.generate(&request).await.unwrap();
+    client.generate(&request).await.unwrap();
+
+    let seen = seen.lock().unwrap().clone();
+    let keys = recorder.keys.lock().unwrap().clone();
+    assert_eq!(seen.len(), 3);
+    assert_eq!(seen[0], seen[1], "retry must reuse the key");
+    assert_ne!(seen[1], seen[2], "each call gets its own key");
+    assert_eq!(keys, vec![seen[0].clone(), seen[2].clone()]);
+    assert_eq!(keys[0].len(), 36);
+    fail.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[test]
+fn test_idempotency_key_custom_header_blocking() {
+    let mut server = mockito::Server::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_header(
+            "x-request-id",
+            mockito::Matcher::Regex("^[0-9a-f-]{36}$".into()),
+        )
+        .with_status(200)
+        .with_body(r#"{"model":"m","response":"hi","done":true}"#)
+        .create();
+    let version = server
+        .mock("GET", "/api/version")
+        .match_header("x-request-id", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body_from_request(capture_key(&seen, r#"{"version":"0.12.6"}"#))
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_idempotency_header("X-Request-Id");
+    let client = OllamaClient::new(config).unwrap();
+    client
+        .generate_blocking(&ollama_oxide::GenerateRequest::new("m", "hello"))
+        .unwrap();
+    client.version_blocking().unwrap();
+
+    assert!(seen.lock().unwrap().is_empty());
+    mock.assert();
+    version.assert();
+}
+
+#[test]
+fn test_idempotency_keys_disabled_by_default() {
+    let config = ClientConfig::default();
+    assert_eq!(config.idempotency_header(), None);
+}
+
+// ============================================================================
+// Error Report Tests
+// ============================================================================
+
+/// An owned copy of an `ErrorReport`.
+#[derive(Debug, Clone, PartialEq)]
+struct Report {
+    method: String,
+    endpoint: Option<&'static str>,
+    model: Option<String>,
+    attempts: u32,
+    chain: Vec<String>,
+}
+
+#[derive(Default)]
+struct ReportRecorder {
+    reports: Mutex<Vec<Report>>,
+}
+
+impl ReportRecorder {
+    fn reports(&self) -> Vec<Report> {
+        self.reports.lock().unwrap().clone()
+    }
+}
+
+impl Observer for ReportRecorder {
+    fn on_error_report(&self, report: &ollama_oxide::ErrorReport<'_>) {
+        assert!(report.url.ends_with(report.endpoint.unwrap_or_default()));
+        self.reports.lock().unwrap().push(Report {
+            method: report.method.to_string(),
+            endpoint: report.endpoint,
+            model: report.model.map(str::to_string),
+            attempts: report.attempts,
+            chain: report.chain(),
+        });
+    }
+}
+
+fn reporting_client(
+    server: &mockito::Server,
+    config: impl FnOnce(ClientConfig) -> ClientConfig,
+) -> (OllamaClient, Arc<ReportRecorder>) {
+    let recorder = Arc::new(ReportRecorder::default());
+    let base = ClientConfig::new(server.url(), Duration::from_secs(5), 1).unwrap();
+    let client = OllamaClient::new(config(base))
+        .unwrap()
+        .with_observer(recorder.clone());
+    (client, recorder)
+}
+
+#[tokio::test]
+async fn test_error_report_after_retries_async() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(503)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let (client, recorder) = reporting_client(&server, |c| c);
+    let request = ollama_oxide::GenerateRequest::new("qwen3:0.6b", "hello");
+    let error = client.generate(&request).await.unwrap_err();
+
+    let reports = recorder.reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].method, "POST");
+    assert_eq!(reports[0].endpoint, Some("/api/generate"));
+    assert_eq!(reports[0].model.as_deref(), Some("qwen3:0.6b"));
+    assert_eq!(reports[0].attempts, 2);
+    assert_eq!(reports[0].chain, vec![error.to_string()]);
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_error_report_before_sending_blocking() {
+    let server = mockito::Server::new();
+    let (client, recorder) = reporting_client(&server, |c| c.with_max_request_bytes(10));
+
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("a long message")]);
+    let error = client.chat_blocking(&request).unwrap_err();
+    assert!(matches!(error, Error::RequestTooLarge { .. }));
+
+    let reports = recorder.reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].endpoint, Some("/api/chat"));
+    assert_eq!(reports[0].model, None);
+    assert_eq!(reports[0].attempts, 0);
+}
+
+#[test]
+fn test_error_report_mid_stream_blocking() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(
+            "{\"model\":\"m\",\"message\":{\"role\":\"assistant\",\"content\":\"ok\"},\"done\":false}\nnot json\n",
+        )
+        .create();
+
+    let (client, recorder) = reporting_client(&server, |c| c);
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")]);
+    let results: Vec<_> = client.chat_stream_blocking(&request).unwrap().collect();
+    assert!(results[1].is_err());
+
+    let reports = recorder.reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].endpoint, Some("/api/chat"));
+    assert_eq!(reports[0].model.as_deref(), Some("qwen3:0.6b"));
+    assert_eq!(reports[0].attempts, 1);
+    assert!(reports[0].chain[0].starts_with("Stream error"));
+    mock.assert();
+}
+
+// ============================================================================
+// Extensions
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct TenantId(&'static str);
+
+/// Records the tenant each call and failed call carried.
+#[derive(Default)]
+struct TenantRecorder {
+    calls: Mutex<Vec<Option<TenantId>>>,
+    failures: Mutex<Vec<Option<TenantId>>>,
+}
+
+impl Observer for TenantRecorder {
+    fn on_extensions(&self, _call: CallId, _url: &str, extensions: &Extensions) {
+        let tenant = extensions.get::<TenantId>().cloned();
+        self.calls.lock().unwrap().push(tenant);
+    }
+
+    fn on_error_report(&self, report: &ErrorReport<'_>) {
+        let tenant = report.extensions.get::<TenantId>().cloned();
+        self.failures.lock().unwrap().push(tenant);
+    }
+}
+
+#[tokio::test]
+async fn test_extensions_reach_observers_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _ok = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+    let _fail = server
+        .mock("POST", "/api/generate")
+        .with_status(400)
+        .create_async()
+        .await;
+
+    let recorder = Arc::new(TenantRecorder::default());
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(recorder.clone());
+    let tenant = client.clone().with_extension(TenantId("acme"));
+
+    client.version().await.unwrap();
+    tenant.version().await.unwrap();
+    let request = ollama_oxide::GenerateRequest::new("m", "hi");
+    tenant.generate(&request).await.unwrap_err();
+
+    assert_eq!(
+        *recorder.calls.lock().unwrap(),
+        vec![Some(TenantId("acme")), Some(TenantId("acme"))]
+    );
+    assert_eq!(
+        *recorder.failures.lock().unwrap(),
+        vec![Some(TenantId("acme"))]
+    );
+}
+
+// ============================================================================
+// Call ids
+// ============================================================================
+
+/// Records the id every hook of every call reported.
+#[derive(Default)]
+struct IdRecorder {
+    events: Mutex<Vec<(&'static str, CallId)>>,
+}
+
+impl Observer for IdRecorder {
+    fn on_request_start(&self, call: CallId, _method: &str, _url: &str) {
+        self.events.lock().unwrap().push(("start", call));
+    }
+
+    fn on_response_body(&self, call: CallId, _url: &str, _body: &[u8]) {
+        self.events.lock().unwrap().push(("body", call));
+    }
+
+    fn on_complete(&self, call: CallId, _url: &str, _status: u16, _elapsed: Duration) {
+        self.events.lock().unwrap().push(("complete", call));
+    }
+
+    fn on_error_report(&self, report: &ErrorReport<'_>) {
+        self.events.lock().unwrap().push(("error", report.call));
+    }
+}
+
+#[tokio::test]
+async fn test_each_call_has_its_own_id_async() {
+    let mut server = mockito::Server::new_async().await;
+    let _ok = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+    let _fail = server
+        .mock("POST", "/api/generate")
+        .with_status(400)
+        .create_async()
+        .await;
+
+    let recorder = Arc::new(IdRecorder::default());
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config)
+        .unwrap()
+        .with_observer(recorder.clone());
+
+    client.version().await.unwrap();
+    let request = ollama_oxide::GenerateRequest::new("m", "hi");
+    client.generate(&request).await.unwrap_err();
+
+    let events = recorder.events.lock().unwrap().clone();
+    let hooks: Vec<_> = events.iter().map(|(hook, _)| *hook).collect();
+    assert_eq!(
+        hooks,
+        ["start", "complete", "body", "start", "complete", "error"]
+    );
+    let (first, second) = (events[0].1, events[3].1);
+    assert_ne!(first, second);
+    assert!(events[..3].iter().all(|(_, id)| *id == first));
+    assert!(events[3..].iter().all(|(_, id)| *id == second));
+}
@@ -0,0 +1,192 @@
+//! Tests for priority scheduling under a concurrency limit
+//! (`ClientConfig::with_max_concurrency`)
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, EmbedRequest, OllamaApiAsync, OllamaApiSync,
+    OllamaClient, Priority,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const CHAT_RESPONSE: &str =
+    r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":true}"#;
+/// A mid-reply event, so the stream stays live after it
+const CHAT_DELTA: &str =
+    r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":false}"#;
+const EMBED_RESPONSE: &str = r#"{"model":"m","embeddings":[[0.1,0.2]]}"#;
+
+/// Mocks chat and embed, recording `<endpoint> <model>` in the order the
+/// server receives them. The chat for model `slow` takes a while, holding
+/// the only slot so later calls queue up.
+async fn recording_server(seen: &Arc<Mutex<Vec<String>>>) -> mockito::ServerGuard {
+    let mut server = mockito::Server::new_async().await;
+    for (path, body) in [("/api/chat", CHAT_RESPONSE), ("/api/embed", EMBED_RESPONSE)] {
+        let seen = seen.clone();
+        server
+            .mock("POST", path)
+            .with_status(200)
+            .with_body_from_request(move |request| {
+                let json: serde_json::Value =
+                    serde_json::from_slice(request.body().unwrap()).unwrap();
+                let model = json["model"].as_str().unwrap_or_default().to_string();
+                seen.lock()
+                    .unwrap()
+                    .push(format!("{} {}", &path[5..], model));
+                if model == "slow" {
+                    std::thread::sleep(Duration::from_millis(200));
+                }
+                body.as_bytes().to_vec()
+            })
+            .create_async()
+            .await;
+    }
+    server
+}
+
+fn client_for(
+    server: &mockito::Server,
+    config: impl FnOnce(ClientConfig) -> ClientConfig,
+) -> OllamaClient {
+    let base = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config(base)).unwrap()
+}
+
+fn chat(model: &str) -> ChatRequest {
+    ChatRequest::new(model, [ChatMessage::user("Hi")])
+}
+
+async fn join(calls: Vec<tokio::task::JoinHandle<()>>) {
+    for call in calls {
+        call.await.unwrap();
+    }
+}
+
+/// Mocks a chat stream that stalls after its first line, and a quick embed.
+async fn stalling_server() -> mockito::ServerGuard {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            w.write_all(format!("{CHAT_DELTA}\n").as_bytes())?;
+            // Blank keep-alive lines, so a closed connection ends the mock
+            for _ in 0..40 {
+                w.flush()?;
+                std::thread::sleep(Duration::from_millis(50));
+                w.write_all(b"\n")?;
+            }
+            Ok(())
+        })
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(EMBED_RESPONSE)
+        .create_async()
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_chat_is_dispatched_before_queued_embeddings() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let server = recording_server(&seen).await;
+    let client = client_for(&server, |c| c.with_max_concurrency(1));
+
+    let mut calls = Vec::new();
+    let c = client.clone();
+    calls.push(tokio::spawn(async move {
+        c.chat(&chat("slow")).await.unwrap();
+    }));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let c = client.clone();
+    calls.push(tokio::spawn(async move {
+        c.embed(&EmbedRequest::new("embedder", "text"))
+            .await
+            .unwrap();
+    }));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let c = client.clone();
+    calls.push(tokio::spawn(async move {
+        c.chat(&chat("chatter")).await.unwrap();
+    }));
+    join(calls).await;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["chat slow", "chat chatter", "embed embedder"]
+    );
+}
+
+#[tokio::test]
+async fn test_client_priority_overrides_model_priority() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let server = recording_server(&seen).await;
+    let client = client_for(&server, |c| {
+        c.with_max_concurrency(1)
+            .with_model_priority("background", Priority::Low)
+    });
+    assert_eq!(client.priority(), None);
+
+    let mut calls = Vec::new();
+    let c = client.clone();
+    calls.push(tokio::spawn(async move {
+        c.chat(&chat("slow")).await.unwrap();
+    }));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let c = client.clone();
+    calls.push(tokio::spawn(async move {
+        c.chat(&chat("background")).await.unwrap();
+    }));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let c = client.clone().with_priority(Priority::High);
+    assert_eq!(c.priority(), Some(Priority::High));
+    calls.push(tokio::spawn(async move {
+        c.embed(&EmbedRequest::new("urgent", "text")).await.unwrap();
+    }));
+    join(calls).await;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["chat slow", "embed urgent", "chat background"]
+    );
+}
+
+#[tokio::test]
+async fn test_stream_holds_its_slot_until_dropped() {
+    let server = stalling_server().await;
+    let client = client_for(&server, |c| c.with_max_concurrency(1));
+    let embed = EmbedRequest::new("embedder", "text");
+
+    let stream = client.chat_stream(&chat("streamer")).await.unwrap();
+    assert!(stream.next().await.unwrap().is_ok());
+    let waiting = tokio::time::timeout(Duration::from_millis(100), client.embed(&embed)).await;
+    assert!(waiting.is_err(), "embed must wait for the stream's slot");
+
+    drop(stream);
+    let embedded = tokio::time::timeout(Duration::from_millis(500), client.embed(&embed)).await;
+    assert!(embedded.unwrap().is_ok());
+}
+
+#[test]
+fn test_blocking_calls_share_the_limit() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(stalling_server());
+    let client = client_for(&server, |c| c.with_max_concurrency(1));
+
+    let mut stream = client.chat_stream_blocking(&chat("streamer")).unwrap();
+    assert!(stream.next().unwrap().is_ok());
+    let embed = std::thread::spawn({
+        let client = client.clone();
+        move || client.embed_blocking(&EmbedRequest::new("embedder", "text"))
+    });
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(
+        !embed.is_finished(),
+        "embed must wait for the stream's slot"
+    );
+
+    drop(stream);
+    assert!(embed.join().unwrap().is_ok());
+}
@@ -195,3 +195,121 @@ fn test_pull_model_blocking_empty_response() {
     assert_eq!(response.status(), None);
     mock.assert();
 }
+
+// ============================================================================
+// Streaming Progress Tests
+// ============================================================================
+
+/// Realistic pull stream: repeated manifest lines and many small layer updates.
+fn pull_stream_body() -> String {
+    let mut lines = vec![
+        r#"{"status":"pulling manifest"}"#.to_string(),
+        r#"{"status":"pulling manifest"}"#.to_string(),
+    ];
+    for completed in (0..=1000).step_by(5) {
+        lines.push(format!(
+            r#"{{"status":"pulling aaa","digest":"sha256:aaa","total":1000,"completed":{}}}"#,
+            completed
+        ));
+    }
+    lines.push(r#"{"status":"verifying sha256 digest"}"#.to_string());
+    lines.push(r#"{"status":"writing manifest"}"#.to_string());
+    lines.push(r#"{"status":"success"}"#.to_string());
+    lines.join("\n") + "\n"
+}
+
+#[tokio::test]
+async fn test_pull_model_stream_async_sends_stream_true() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/pull")
+        .match_body(Matcher::Json(serde_json::json!({
+            "model": "qwen3:0.6b",
+            "stream": true
+        })))
+        .with_status(200)
+        .with_body(pull_stream_body())
+        .create_async()
+        .await;
+
+    let client = OllamaClient::new(make_config(server.url())).unwrap();
+    let stream = client
+        .pull_model_stream(&PullRequest::new("qwen3:0.6b"))
+        .await
+        .unwrap();
+    let events = stream.collect().await.unwrap();
+
+    assert_eq!(events.len(), 206);
+    assert_eq!(events[2].digest.as_deref(), Some("sha256:aaa"));
+    assert!(events.last().unwrap().is_success());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_pull_progress_summary_async_coalesces() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/pull")
+        .with_status(200)
+        .with_body(pull_stream_body())
+        .create_async()
+        .await;
+
+    let client = OllamaClient::new(make_config(server.url())).unwrap();
+    let stream = client
+        .pull_model_stream(&PullRequest::new("qwen3:0.6b"))
+        .await
+        .unwrap();
+    let updates = stream.progress_summary().collect().await.unwrap();
+
+    // manifest, then one update per whole percent (0..=100), then 3 phases
+    assert!(updates.len() <= 110, "got {} updates", updates.len());
+    assert_eq!(updates[0].status, "pulling manifest");
+    let percents: Vec<f64> = updates.iter().map(|u| u.percent()).collect();
+    assert!(percents.windows(2).all(|w| w[0] <= w[1]));
+    let last = updates.last().unwrap();
+    assert!(last.is_success());
+    assert_eq!(last.layers.len(), 1);
+    assert_eq!(last.percent(), 100.0);
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_pull_progress_summary_blocking() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/pull")
+        .with_status(200)
+        .with_body(pull_stream_body())
+        .create();
+
+    let client = OllamaClient::new(make_config(server.url())).unwrap();
+    let stream = client
+        .pull_model_stream_blocking(&PullRequest::new("qwen3:0.6b"))
+        .unwrap();
+    let updates: Vec<_> = stream
+        .progress_summary()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let statuses: Vec<&str> = updates.iter().map(|u| u.status.as_str()).collect();
+    assert!(statuses.contains(&"verifying sha256 digest"));
+    assert!(statuses.contains(&"writing manifest"));
+    assert_eq!(statuses.last(), Some(&"success"));
+    mock.assert();
+}
+
+#[test]
+fn test_pull_model_stream_blocking_http_error() {
+    let mut server = Server::new();
+    let mock = server.mock("POST", "/api/pull").with_status(404).create();
+
+    let client = OllamaClient::new(make_config(server.url())).unwrap();
+    let result = client.pull_model_stream_blocking(&PullRequest::new("missing"));
+
+    assert!(matches!(
+        result,
+        Err(ollama_oxide::Error::HttpStatusError(404))
+    ));
+    mock.assert();
+}
@@ -0,0 +1,123 @@
+//! Tests for 429 Too Many Requests handling (retry, Retry-After, RateLimited)
+
+use mockito::Server;
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, Error, GenerateRequest, OllamaApiAsync, OllamaApiSync,
+    OllamaClient,
+};
+use std::time::Duration;
+
+fn make_client(base_url: String, max_retries: u32) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), max_retries).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_rate_limited_after_retries_exhausted() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .with_body(r#"{"error":"too many requests"}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url(), 2);
+    let result = client
+        .generate(&GenerateRequest::new("qwen3:0.6b", "hi"))
+        .await;
+
+    match result {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::ZERO));
+        }
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_rate_limited_then_success_is_retried() {
+    let mut server = Server::new_async().await;
+    let limited = server
+        .mock("GET", "/api/version")
+        .with_status(429)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url(), 1);
+    let version = client.version().await.unwrap();
+
+    assert_eq!(version.version, "0.12.6");
+    limited.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_rate_limited_without_retry_after() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(429)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url(), 0);
+    let result = client
+        .generate(&GenerateRequest::new("qwen3:0.6b", "hi"))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited { retry_after: None })
+    ));
+}
+
+#[tokio::test]
+async fn test_chat_stream_rate_limited() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(429)
+        .with_header("retry-after", "12")
+        .create_async()
+        .await;
+
+    let client = make_client(server.url(), 0);
+    let request = ChatRequest::new("qwen3:0.6b", [ChatMessage::user("hi")]);
+    let result = client.chat_stream(&request).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(d)
+        }) if d == Duration::from_secs(12)
+    ));
+}
+
+#[test]
+fn test_rate_limited_blocking() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .expect(2)
+        .create();
+
+    let client = make_client(server.url(), 1);
+    let result = client.generate_blocking(&GenerateRequest::new("qwen3:0.6b", "hi"));
+
+    assert!(matches!(result, Err(Error::RateLimited { .. })));
+    mock.assert();
+}
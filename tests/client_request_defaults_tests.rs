@@ -0,0 +1,216 @@
+//! Tests for client-level default model, options, keep_alive, and system
+//! prompt policy
+
+use mockito::{Matcher, Server};
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, EmbedRequest, Error, GenerateRequest, ModelOptions,
+    OllamaApiAsync, OllamaApiSync, OllamaClient, SystemPromptPolicy,
+};
+use std::time::Duration;
+
+fn make_client(base_url: String) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), 0)
+        .unwrap()
+        .with_default_model("qwen3:0.6b")
+        .with_default_options(ModelOptions::default().with_temperature(0.25))
+        .with_default_keep_alive("10m");
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_generate_uses_defaults_when_omitted() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b",
+            "options": {"temperature": 0.25},
+            "keep_alive": "10m"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"qwen3:0.6b","response":"ok","done":true}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    client
+        .generate(&GenerateRequest::new("", "hi"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_keeps_explicit_fields() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "llama3.2",
+            "options": {"temperature": 0.9},
+            "keep_alive": 0
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"ok"},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")])
+        .with_options(ModelOptions::default().with_temperature(0.9))
+        .with_keep_alive(0);
+    client.chat(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_model_handle_inherits_default_options() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "llama3.2",
+            "options": {"temperature": 0.25}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"llama3.2","response":"ok","done":true}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    client.model("llama3.2").generate("hi").await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_embed_blocking_uses_default_model() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/embed")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b",
+            "keep_alive": "10m"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"model":"qwen3:0.6b","embeddings":[[0.1]]}"#)
+        .create();
+
+    let client = make_client(server.url());
+    client
+        .embed_blocking(&EmbedRequest::new("", "text"))
+        .unwrap();
+
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_missing_model_without_default_is_rejected() {
+    let client = OllamaClient::default().unwrap();
+    let result = client.generate(&GenerateRequest::new("", "hi")).await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_system_prompt_policy_wraps_every_chat() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "messages": [
+                {"role": "system", "content": "Be safe.\n\nYou help.\n\nBe brief."},
+                {"role": "user", "content": "hi"}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"ok"},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(30), 0)
+        .unwrap()
+        .with_system_prompt_policy(
+            SystemPromptPolicy::new()
+                .with_prefix("Be safe.")
+                .with_suffix("Be brief."),
+        );
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new(
+        "llama3.2",
+        [ChatMessage::system("You help."), ChatMessage::user("hi")],
+    );
+    client.chat(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_system_prompt_policy_opt_out_keeps_modelfile_system_prompt() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "messages": [{"role": "user", "content": "hi"}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"ok"},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(30), 0)
+        .unwrap()
+        .with_system_prompt_policy(
+            SystemPromptPolicy::new()
+                .with_prefix("Be safe.")
+                .without_missing_prompts(),
+        );
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")]);
+    client.chat(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_system_prompt_policy_fills_missing_system_prompt() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "messages": [
+                {"role": "system", "content": "Be safe."},
+                {"role": "user", "content": "hi"}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"ok"},"done":true}"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(30), 0)
+        .unwrap()
+        .with_system_prompt_policy(SystemPromptPolicy::new().with_prefix("Be safe."));
+    let client = OllamaClient::new(config).unwrap();
+    let request = ChatRequest::new("llama3.2", [ChatMessage::user("hi")]);
+    client.chat(&request).await.unwrap();
+
+    mock.assert_async().await;
+}
@@ -1,8 +1,8 @@
 //! Tests for show_model API methods (POST /api/show)
 
 use ollama_oxide::{
-    ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient, ShowModelDetails, ShowRequest,
-    ShowResponse,
+    ChatMessage, ClientConfig, Error, OllamaApiAsync, OllamaApiSync, OllamaClient,
+    ShowModelDetails, ShowRequest, ShowResponse,
 };
 use std::time::Duration;
 
@@ -56,6 +56,7 @@ async fn test_show_model_async_success() {
     assert!(response.capabilities.is_some());
     assert!(response.has_capability("completion"));
     assert!(response.has_capability("vision"));
+    assert_eq!(response.modified_at_unix(), Some(1_705_314_600));
     mock.assert_async().await;
 }
 
@@ -450,6 +451,7 @@ fn test_show_response_default() {
     assert!(response.modified_at.is_none());
     assert!(response.details.is_none());
     assert!(response.template.is_none());
+    assert!(response.system.is_none());
     assert!(response.capabilities.is_none());
     assert!(response.model_info.is_none());
 }
@@ -465,6 +467,36 @@ fn test_show_response_clone() {
     assert_eq!(response, cloned);
 }
 
+#[test]
+fn test_show_response_diff_between_tags() {
+    let v1: ShowResponse = serde_json::from_value(serde_json::json!({
+        "parameters": "num_ctx                        4096\ntemperature                    0.7",
+        "template": "{{ .Prompt }}",
+        "system": "You are a helpful assistant."
+    }))
+    .unwrap();
+    let v2: ShowResponse = serde_json::from_value(serde_json::json!({
+        "parameters": "num_ctx                        8192\ntemperature                    0.7",
+        "template": "{{ .Prompt }}",
+        "system": "You are a terse assistant."
+    }))
+    .unwrap();
+
+    let diff = v1.diff(&v2);
+    assert_eq!(diff.parameters.len(), 1);
+    assert_eq!(diff.parameters[0].name, "num_ctx");
+    assert_eq!(diff.parameters[0].before, ["4096"]);
+    assert_eq!(diff.parameters[0].after, ["8192"]);
+    assert!(diff.template.is_none());
+    let system = diff.system.unwrap();
+    assert_eq!(
+        system.before.as_deref(),
+        Some("You are a helpful assistant.")
+    );
+    assert_eq!(system.after.as_deref(), Some("You are a terse assistant."));
+    assert!(v2.diff(&v2).is_empty());
+}
+
 // ============================================================================
 // ShowModelDetails Tests
 // ============================================================================
@@ -508,3 +540,224 @@ fn test_show_model_details_clone() {
     let cloned = details.clone();
     assert_eq!(details, cloned);
 }
+
+// ============================================================================
+// Model Capabilities Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_model_capabilities_async_reported() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/api/show")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "model": "qwen3:0.6b"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "capabilities": ["completion", "tools", "thinking"],
+                "model_info": {"qwen3.context_length": 40960}
+            }"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+
+    let caps = client.model_capabilities("qwen3:0.6b").await.unwrap();
+    assert!(caps.tools);
+    assert!(caps.thinking);
+    assert!(!caps.vision);
+    assert_eq!(caps.context_length, Some(40960));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_model_capabilities_sync_inferred_from_template() {
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("POST", "/api/show")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"template": "{{- if .Tools }}{{ .Tools }}{{ end }}{{ .Prompt }}"}"#)
+        .create();
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+
+    let caps = client.model_capabilities_blocking("custom-model").unwrap();
+    assert!(caps.completion);
+    assert!(caps.tools);
+    assert!(!caps.thinking);
+    mock.assert();
+}
+
+#[tokio::test]
+async fn test_model_capabilities_async_not_found() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/api/show")
+        .with_status(404)
+        .with_body(r#"{"error": "model 'missing' not found"}"#)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+
+    assert!(client.model_capabilities("missing").await.is_err());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_show_model_raw_sections_parse_lazily() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/api/show")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "model_info": {"general.architecture": "llama", "llama.block_count": 32},
+                "tensors": [
+                    {"name": "token_embd.weight", "type": "Q4_K", "shape": [4096, 128256]},
+                    {"name": "output_norm.weight", "type": "F32", "shape": [4096]}
+                ]
+            }"#,
+        )
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    let client = OllamaClient::new(config).unwrap();
+    let response = client
+        .show_model(&ShowRequest::verbose("llama3.1"))
+        .await
+        .unwrap();
+
+    let info = response.model_info_map().unwrap().unwrap();
+    assert_eq!(info["llama.block_count"], 32);
+
+    let tensors = response.tensor_infos().unwrap().unwrap();
+    assert_eq!(tensors.len(), 2);
+    assert_eq!(tensors[0].tensor_type, "Q4_K");
+    assert_eq!(tensors[1].element_count(), 4096);
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_show_response_raw_sections_absent() {
+    let response: ShowResponse = serde_json::from_str(r#"{"license": "MIT"}"#).unwrap();
+    assert!(response.model_info_map().unwrap().is_none());
+    assert!(response.tensor_infos().unwrap().is_none());
+}
+
+#[test]
+fn test_show_response_model_info_type_mismatch() {
+    let response: ShowResponse = serde_json::from_str(r#"{"model_info": [1, 2]}"#).unwrap();
+    assert!(matches!(
+        response.model_info_map(),
+        Err(ollama_oxide::Error::SerializationError(_))
+    ));
+}
+
+// ============================================================================
+// Prompt Rendering Tests
+// ============================================================================
+
+/// Abridged from the llama3.2 template served by `/api/show`
+const LLAMA_TEMPLATE: &str = r#"<|start_header_id|>system<|end_header_id|>
+
+Cutting Knowledge Date: December 2023
+
+{{ if .System }}{{ .System }}
+{{- end }}<|eot_id|>
+{{- range $i, $_ := .Messages }}
+{{- $last := eq (len (slice $.Messages $i)) 1 }}
+{{- if eq .Role "user" }}<|start_header_id|>user<|end_header_id|>
+
+{{ .Content }}<|eot_id|>{{ if $last }}<|start_header_id|>assistant<|end_header_id|>
+
+{{ end }}
+{{- else if eq .Role "assistant" }}<|start_header_id|>assistant<|end_header_id|>
+
+{{ .Content }}{{ if not $last }}<|eot_id|>{{ end }}
+{{- end }}
+{{- end }}"#;
+
+async fn mock_template(server: &mut mockito::Server, template: Option<&str>) -> mockito::Mock {
+    server
+        .mock("POST", "/api/show")
+        .with_status(200)
+        .with_body(serde_json::json!({ "template": template }).to_string())
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_render_prompt_async_messages_template() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = mock_template(&mut server, Some(LLAMA_TEMPLATE)).await;
+    let client =
+        OllamaClient::new(ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap())
+            .unwrap();
+
+    let prompt = client
+        .render_prompt(
+            "llama3.2",
+            &[
+                ChatMessage::system("Be brief."),
+                ChatMessage::user("Hi"),
+                ChatMessage::assistant("Hello!"),
+                ChatMessage::user("Bye"),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        prompt,
+        "<|start_header_id|>system<|end_header_id|>\n\nCutting Knowledge Date: December 2023\n\n\
+         Be brief.<|eot_id|>\
+         <|start_header_id|>user<|end_header_id|>\n\nHi<|eot_id|>\
+         <|start_header_id|>assistant<|end_header_id|>\n\nHello!<|eot_id|>\
+         <|start_header_id|>user<|end_header_id|>\n\nBye<|eot_id|>\
+         <|start_header_id|>assistant<|end_header_id|>\n\n"
+    );
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_render_prompt_sync_without_template_uses_prompt() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut server = runtime.block_on(mockito::Server::new_async());
+    let _mock = runtime.block_on(mock_template(&mut server, None));
+    let client =
+        OllamaClient::new(ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap())
+            .unwrap();
+
+    let prompt = client
+        .render_prompt_blocking("raw", &[ChatMessage::user("Hello")])
+        .unwrap();
+    assert_eq!(prompt, "Hello");
+}
+
+#[test]
+fn test_prompt_template_reports_unsupported_templates() {
+    let response = ShowResponse {
+        template: Some(r#"{{ template "x" }}"#.to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        response.prompt_template(),
+        Err(Error::TemplateError(_))
+    ));
+    assert!(ShowResponse::default().prompt_template().unwrap().is_none());
+}
@@ -0,0 +1,142 @@
+//! Tests for graceful shutdown (`OllamaClient::shutdown`)
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, Error, GenerateRequest, OllamaApiAsync, OllamaApiSync,
+    OllamaClient,
+};
+use std::time::Duration;
+
+const CHUNK: &str = r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":false}"#;
+
+fn client_for(server: &mockito::Server) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(30), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+fn chat_request() -> ChatRequest {
+    ChatRequest::new("qwen3:0.6b", [ChatMessage::user("Hi")])
+}
+
+#[tokio::test]
+async fn test_shutdown_refuses_new_requests() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let clone = client.clone();
+    client.version().await.unwrap();
+
+    assert_eq!(client.shutdown(Duration::from_secs(1)).await, 0);
+    assert!(clone.is_shut_down());
+    assert!(matches!(clone.version().await, Err(Error::ClientShutdown)));
+    assert!(matches!(
+        clone.chat_stream(&chat_request()).await,
+        Err(Error::ClientShutdown)
+    ));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_request() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            std::thread::sleep(Duration::from_millis(200));
+            w.write_all(br#"{"model":"m","response":"done","done":true}"#)
+        })
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let request = GenerateRequest::new("qwen3:0.6b", "Hello");
+    let call = tokio::spawn({
+        let client = client.clone();
+        async move { client.generate(&request).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(client.shutdown(Duration::from_secs(5)).await, 0);
+    let response = call.await.unwrap().unwrap();
+    assert_eq!(response.text(), Some("done"));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_shutdown_cancels_stream_after_grace() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            w.write_all(format!("{CHUNK}\n").as_bytes())?;
+            w.flush()?;
+            std::thread::sleep(Duration::from_secs(2));
+            w.write_all(format!("{CHUNK}\n").as_bytes())
+        })
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let stream = client.chat_stream(&chat_request()).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap().content(), Some("ok"));
+
+    assert_eq!(client.shutdown(Duration::from_millis(50)).await, 1);
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(Error::ClientShutdown))
+    ));
+    assert!(stream.next().await.is_none());
+}
+
+#[test]
+fn test_shutdown_cancels_blocking_stream() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(format!("{CHUNK}\n{CHUNK}\n"))
+        .create();
+
+    let client = client_for(&server);
+    let mut stream = client.chat_stream_blocking(&chat_request()).unwrap();
+    assert!(stream.next().unwrap().is_ok());
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(
+        runtime.block_on(client.shutdown(Duration::from_millis(10))),
+        1
+    );
+    assert!(matches!(stream.next(), Some(Err(Error::ClientShutdown))));
+    assert!(stream.next().is_none());
+    assert!(matches!(
+        client.version_blocking(),
+        Err(Error::ClientShutdown)
+    ));
+}
+
+#[test]
+fn test_finished_blocking_stream_is_not_in_flight() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(format!("{}\n", CHUNK.replace("false", "true")))
+        .create();
+
+    let client = client_for(&server);
+    let mut stream = client.chat_stream_blocking(&chat_request()).unwrap();
+    assert!(stream.next().unwrap().is_ok());
+    assert!(stream.next().is_none());
+
+    // The stream is still alive but done, so nothing is left to drain
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(client.shutdown(Duration::from_secs(5))), 0);
+}
@@ -0,0 +1,61 @@
+//! Tests for connect and read timeouts against servers that never answer
+
+use ollama_oxide::{ClientConfig, OllamaApiAsync, OllamaApiSync, OllamaClient};
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+/// Accepts connections but never writes a response.
+fn silent_server() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    (listener, url)
+}
+
+#[tokio::test]
+async fn test_read_timeout_fails_before_total_timeout() {
+    let (_listener, url) = silent_server();
+    let config = ClientConfig::new(url, Duration::from_secs(30), 0)
+        .unwrap()
+        .with_read_timeout(Duration::from_millis(200));
+    let client = OllamaClient::new(config).unwrap();
+
+    let started = Instant::now();
+    let result = client.version().await;
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_total_timeout_applies_to_blocking_calls() {
+    let (_listener, url) = silent_server();
+    let config = ClientConfig::new(url, Duration::from_millis(300), 0)
+        .unwrap()
+        .with_connect_timeout(Duration::from_secs(2));
+    let client = OllamaClient::new(config).unwrap();
+
+    let started = Instant::now();
+    let result = client.version_blocking();
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_connect_timeout_on_unroutable_host() {
+    // 10.255.255.1 is non-routable: the connection attempt hangs or is refused.
+    let config = ClientConfig::new(
+        "http://10.255.255.1:11434".to_string(),
+        Duration::from_secs(60),
+        0,
+    )
+    .unwrap()
+    .with_connect_timeout(Duration::from_millis(300));
+    let client = OllamaClient::new(config).unwrap();
+
+    let started = Instant::now();
+    let result = client.version().await;
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
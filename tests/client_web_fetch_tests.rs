@@ -0,0 +1,123 @@
+//! Tests for POST /api/web_fetch endpoint (web_fetch, web_fetch_blocking)
+
+use mockito::{Matcher, Server};
+use ollama_oxide::web::WebFetchRequest;
+use ollama_oxide::{ClientConfig, Error, OllamaApiAsync, OllamaApiSync, OllamaClient};
+use std::time::Duration;
+
+const FETCH_BODY: &str = r#"{
+    "title": "Ollama",
+    "content": "Get up and running with large language models.",
+    "links": ["https://ollama.com/models", "https://ollama.com/blog"]
+}"#;
+
+fn make_client(base_url: String) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), 0)
+        .unwrap()
+        .with_api_key("test-key");
+    OllamaClient::new(config).unwrap()
+}
+
+// ============================================================================
+// Async Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_web_fetch_sends_api_key_and_decodes_page() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_fetch")
+        .match_header("authorization", "Bearer test-key")
+        .match_body(Matcher::Json(
+            serde_json::json!({"url": "https://ollama.com"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(FETCH_BODY)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let page = client
+        .web_fetch(&WebFetchRequest::new("https://ollama.com"))
+        .await
+        .unwrap();
+
+    assert_eq!(page.title, "Ollama");
+    assert!(page.content.starts_with("Get up and running"));
+    assert_eq!(page.links.len(), 2);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_web_fetch_rejects_invalid_url_before_sending() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_fetch")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let result = client.web_fetch(&WebFetchRequest::new("not a url")).await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+    mock.assert_async().await;
+}
+
+// ============================================================================
+// Sync Tests
+// ============================================================================
+
+#[test]
+fn test_web_fetch_blocking() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/web_fetch")
+        .match_header("authorization", "Bearer test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(FETCH_BODY)
+        .create();
+
+    let client = make_client(server.url());
+    let page = client
+        .web_fetch_blocking(&WebFetchRequest::new("https://ollama.com"))
+        .unwrap();
+
+    assert_eq!(page.links[0], "https://ollama.com/models");
+    mock.assert();
+}
+
+// ============================================================================
+// Tool Tests
+// ============================================================================
+
+#[cfg(feature = "tools")]
+#[tokio::test]
+async fn test_web_fetch_tool_executes_fetch() {
+    use ollama_oxide::tools::ToolRegistry;
+    use ollama_oxide::web::WebFetchTool;
+    use ollama_oxide::{ToolCall, ToolCallFunction};
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_fetch")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(FETCH_BODY)
+        .create_async()
+        .await;
+
+    let mut registry = ToolRegistry::new();
+    registry.register(WebFetchTool::new(make_client(server.url())));
+
+    let call = ToolCall::new(ToolCallFunction::with_arguments(
+        "web_fetch",
+        serde_json::json!({"url": "https://ollama.com"}),
+    ));
+    let output = registry.execute(&call).await.unwrap();
+
+    assert_eq!(output["title"], "Ollama");
+    mock.assert_async().await;
+}
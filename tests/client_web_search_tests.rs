@@ -0,0 +1,143 @@
+//! Tests for POST /api/web_search endpoint (web_search, web_search_blocking)
+
+use mockito::{Matcher, Server};
+use ollama_oxide::web::WebSearchRequest;
+use ollama_oxide::{ClientConfig, Error, OllamaApiAsync, OllamaApiSync, OllamaClient};
+use std::time::Duration;
+
+const SEARCH_BODY: &str = r#"{"results":[
+    {"title":"Ollama","url":"https://ollama.com/","content":"Get up and running with large language models."},
+    {"title":"Ollama Blog","url":"https://ollama.com/blog","content":"Web search is now available."}
+]}"#;
+
+fn make_client(base_url: String) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), 0)
+        .unwrap()
+        .with_api_key("test-key");
+    OllamaClient::new(config).unwrap()
+}
+
+// ============================================================================
+// Async Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_web_search_sends_api_key_and_decodes_results() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_search")
+        .match_header("authorization", "Bearer test-key")
+        .match_body(Matcher::Json(serde_json::json!({
+            "query": "what is ollama",
+            "max_results": 2
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(SEARCH_BODY)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let request = WebSearchRequest::new("what is ollama").with_max_results(2);
+    let response = client.web_search(&request).await.unwrap();
+
+    assert_eq!(response.results.len(), 2);
+    assert_eq!(response.results[0].title, "Ollama");
+    assert_eq!(response.results[1].url, "https://ollama.com/blog");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_web_search_unauthorized() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_search")
+        .with_status(401)
+        .with_body(r#"{"error":"unauthorized"}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let result = client.web_search(&WebSearchRequest::new("ollama")).await;
+
+    assert!(matches!(result, Err(Error::HttpStatusError(401))));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_web_search_rejects_invalid_request_before_sending() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_search")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let blank = client.web_search(&WebSearchRequest::new(" ")).await;
+    let too_many = client
+        .web_search(&WebSearchRequest::new("ollama").with_max_results(50))
+        .await;
+
+    assert!(matches!(blank, Err(Error::ValidationError(_))));
+    assert!(matches!(too_many, Err(Error::ValidationError(_))));
+    mock.assert_async().await;
+}
+
+// ============================================================================
+// Sync Tests
+// ============================================================================
+
+#[test]
+fn test_web_search_blocking() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/api/web_search")
+        .match_header("authorization", "Bearer test-key")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(SEARCH_BODY)
+        .create();
+
+    let client = make_client(server.url());
+    let response = client
+        .web_search_blocking(&WebSearchRequest::new("ollama"))
+        .unwrap();
+
+    assert_eq!(response.results.len(), 2);
+    mock.assert();
+}
+
+// ============================================================================
+// Tool Tests
+// ============================================================================
+
+#[cfg(feature = "tools")]
+#[tokio::test]
+async fn test_web_search_tool_executes_search() {
+    use ollama_oxide::tools::ToolRegistry;
+    use ollama_oxide::web::WebSearchTool;
+    use ollama_oxide::{ToolCall, ToolCallFunction};
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/web_search")
+        .match_body(Matcher::Json(serde_json::json!({"query": "ollama"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(SEARCH_BODY)
+        .create_async()
+        .await;
+
+    let mut registry = ToolRegistry::new();
+    registry.register(WebSearchTool::new(make_client(server.url())));
+
+    let call = ToolCall::new(ToolCallFunction::with_arguments(
+        "web_search",
+        serde_json::json!({"query": "ollama"}),
+    ));
+    let output = registry.execute(&call).await.unwrap();
+
+    assert_eq!(output["results"][0]["title"], "Ollama");
+    mock.assert_async().await;
+}
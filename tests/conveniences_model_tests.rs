@@ -0,0 +1,189 @@
+//! Tests for the model-management conveniences (disk usage, prune, bulk operations)
+
+use mockito::{Matcher, Server};
+use ollama_oxide::{ClientConfig, OllamaClient};
+use std::time::Duration;
+
+fn client_for(server: &Server) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+const TAGS_BODY: &str = r#"{"models":[
+    {"name":"gemma3:4b","modified_at":"2020-01-01T00:00:00Z","size":3000000000,"details":{"family":"gemma3"}},
+    {"name":"gemma3:1b","modified_at":"2099-01-01T00:00:00Z","size":1000000000,"details":{"family":"gemma3"}},
+    {"name":"llama3.2:latest","modified_at":"2020-06-01T00:00:00Z","size":2000000000,"details":{"family":"llama"}},
+    {"name":"mystery:latest","size":500000000}
+]}"#;
+
+async fn mock_tags(server: &mut Server) -> mockito::Mock {
+    server
+        .mock("GET", "/api/tags")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(TAGS_BODY)
+        .create_async()
+        .await
+}
+
+fn names(models: &[ollama_oxide::ModelSummary]) -> Vec<&str> {
+    models.iter().map(|m| m.name.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_disk_usage_groups_by_family() {
+    let mut server = Server::new_async().await;
+    let _tags = mock_tags(&mut server).await;
+
+    let usage = client_for(&server).disk_usage().await.unwrap();
+
+    assert_eq!(usage.total, 6_500_000_000);
+    assert_eq!(usage.model_count(), 4);
+    let families: Vec<&str> = usage.families.iter().map(|f| f.family.as_str()).collect();
+    assert_eq!(families, vec!["gemma3", "llama", "unknown"]);
+    assert_eq!(usage.family("gemma3").unwrap().size, 4_000_000_000);
+}
+
+#[tokio::test]
+async fn test_prune_keeps_named_models_without_deleting() {
+    let mut server = Server::new_async().await;
+    let _tags = mock_tags(&mut server).await;
+    let delete = server
+        .mock("DELETE", "/api/delete")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let plan = client_for(&server)
+        .prune(&["llama3.2", "gemma3:1b"])
+        .await
+        .unwrap();
+
+    assert_eq!(names(&plan.delete), vec!["gemma3:4b", "mystery:latest"]);
+    assert_eq!(names(&plan.keep), vec!["gemma3:1b", "llama3.2:latest"]);
+    assert_eq!(plan.reclaimable(), 3_500_000_000);
+    delete.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_prune_older_than_skips_unknown_dates() {
+    let mut server = Server::new_async().await;
+    let _tags = mock_tags(&mut server).await;
+
+    let plan = client_for(&server)
+        .prune_older_than(Duration::from_secs(365 * 24 * 3600))
+        .await
+        .unwrap();
+
+    assert_eq!(names(&plan.delete), vec!["gemma3:4b", "llama3.2:latest"]);
+    assert!(names(&plan.keep).contains(&"mystery:latest"));
+}
+
+#[tokio::test]
+async fn test_prune_older_than_max_age_deletes_nothing() {
+    let mut server = Server::new_async().await;
+    let _tags = mock_tags(&mut server).await;
+
+    let plan = client_for(&server)
+        .prune_older_than(Duration::MAX)
+        .await
+        .unwrap();
+
+    assert!(plan.delete.is_empty());
+    assert_eq!(plan.keep.len(), 4);
+}
+
+#[tokio::test]
+async fn test_apply_prune_deletes_planned_models() {
+    let mut server = Server::new_async().await;
+    let _tags = mock_tags(&mut server).await;
+    let delete_old = server
+        .mock("DELETE", "/api/delete")
+        .match_body(Matcher::Json(serde_json::json!({"model": "gemma3:4b"})))
+        .with_status(200)
+        .create_async()
+        .await;
+    let delete_mystery = server
+        .mock("DELETE", "/api/delete")
+        .match_body(Matcher::Json(
+            serde_json::json!({"model": "mystery:latest"}),
+        ))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let plan = client.prune(&["llama3.2", "gemma3:1b"]).await.unwrap();
+    client.apply_prune(&plan).await.unwrap();
+
+    delete_old.assert_async().await;
+    delete_mystery.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_models_reports_each_outcome() {
+    let mut server = Server::new_async().await;
+    let _ok = server
+        .mock("DELETE", "/api/delete")
+        .match_body(Matcher::Json(serde_json::json!({"model": "a"})))
+        .with_status(200)
+        .create_async()
+        .await;
+    let _missing = server
+        .mock("DELETE", "/api/delete")
+        .match_body(Matcher::Json(serde_json::json!({"model": "b"})))
+        .with_status(404)
+        .create_async()
+        .await;
+    let _ok_c = server
+        .mock("DELETE", "/api/delete")
+        .match_body(Matcher::Json(serde_json::json!({"model": "c"})))
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let report = client_for(&server).delete_models(&["a", "b", "c"]).await;
+
+    assert!(!report.is_success());
+    let outcome: Vec<(&str, bool)> = report
+        .items
+        .iter()
+        .map(|i| (i.name.as_str(), i.is_ok()))
+        .collect();
+    assert_eq!(outcome, vec![("a", true), ("b", false), ("c", true)]);
+    assert!(matches!(
+        report.failed().next().unwrap().result,
+        Err(ollama_oxide::Error::HttpStatusError(404))
+    ));
+}
+
+#[tokio::test]
+async fn test_pull_models_keeps_order_and_partial_failures() {
+    let mut server = Server::new_async().await;
+    for name in ["one", "three"] {
+        server
+            .mock("POST", "/api/pull")
+            .match_body(Matcher::PartialJson(serde_json::json!({"model": name})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"success"}"#)
+            .create_async()
+            .await;
+    }
+    server
+        .mock("POST", "/api/pull")
+        .match_body(Matcher::PartialJson(serde_json::json!({"model": "two"})))
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let report = client_for(&server)
+        .pull_models(&["one", "two", "three"], 2)
+        .await;
+
+    let names: Vec<&str> = report.items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["one", "two", "three"]);
+    assert_eq!(report.succeeded().count(), 2);
+    assert_eq!(report.failed().next().unwrap().name, "two");
+    assert!(report.items[0].result.as_ref().unwrap().is_success());
+}
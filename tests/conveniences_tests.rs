@@ -0,0 +1,1960 @@
+//! Tests for the high-level conveniences
+
+use ollama_oxide::{
+    ClientConfig, Error, EvalCase, EvalCheck, GenerateRequest, ModelOptions, ModerationCategory,
+    OllamaClient, PromptWinner,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn client_for(server: &mockito::Server) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+fn chat_reply(content: &str) -> String {
+    serde_json::json!({
+        "model": "qwen3:0.6b",
+        "message": { "role": "assistant", "content": content },
+        "done": true
+    })
+    .to_string()
+}
+
+// ============================================================================
+// classify
+// ============================================================================
+
+#[tokio::test]
+async fn test_classify_constrains_labels() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b",
+            "format": {
+                "properties": { "label": { "enum": ["spam", "ham"] } }
+            },
+            "options": { "temperature": 0.0 }
+        })))
+        .with_status(200)
+        .with_body(chat_reply(r#"{"label": "spam", "confidence": 1.4}"#))
+        .create_async()
+        .await;
+
+    let result = client_for(&server)
+        .classify("qwen3:0.6b", "Win a free cruise!!!", &["spam", "ham"])
+        .await
+        .unwrap();
+
+    assert_eq!(result.label, "spam");
+    assert_eq!(result.confidence, 1.0);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_classify_rejects_unknown_label() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply(r#"{"label": "eggs", "confidence": 0.5}"#))
+        .create_async()
+        .await;
+
+    let err = client_for(&server)
+        .classify("qwen3:0.6b", "text", &["spam", "ham"])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SerializationError(_)));
+}
+
+#[tokio::test]
+async fn test_classify_requires_labels() {
+    let server = mockito::Server::new_async().await;
+    let err = client_for(&server)
+        .classify("qwen3:0.6b", "text", &[])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::ValidationError(_)));
+}
+
+// ============================================================================
+// choose
+// ============================================================================
+
+#[tokio::test]
+async fn test_choose_returns_index_of_constrained_option() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "format": {
+                "properties": { "choice": { "enum": ["Paris", "Lyon", "Marseille"] } }
+            },
+            "options": { "temperature": 0.0 }
+        })))
+        .with_status(200)
+        .with_body(chat_reply(r#"{"choice": "Lyon"}"#))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let index = client_for(&server)
+        .choose(
+            "qwen3:0.6b",
+            "Second city?",
+            &["Paris", "Lyon", "Marseille"],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(index, 1);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_choose_retries_after_invalid_reply() {
+    let mut server = mockito::Server::new_async().await;
+    let retry = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex("not a valid answer".to_string()))
+        .with_status(200)
+        .with_body(chat_reply(r#"{"choice": "no"}"#))
+        .expect(1)
+        .create_async()
+        .await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply(r#"{"choice": "perhaps"}"#))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let index = client_for(&server)
+        .choose("qwen3:0.6b", "Is water dry?", &["yes", "no"])
+        .await
+        .unwrap();
+
+    assert_eq!(index, 1);
+    first.assert_async().await;
+    retry.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_choose_gives_up_after_three_attempts() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("maybe"))
+        .expect(3)
+        .create_async()
+        .await;
+
+    let err = client_for(&server)
+        .choose("qwen3:0.6b", "Is water dry?", &["yes", "no"])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SerializationError(_)));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_choose_validates_options() {
+    let server = mockito::Server::new_async().await;
+    let client = client_for(&server);
+
+    let empty = client.choose("qwen3:0.6b", "text", &[]).await.unwrap_err();
+    let duplicate = client
+        .choose("qwen3:0.6b", "text", &["a", "b", "a"])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(empty, Error::ValidationError(_)));
+    assert!(matches!(duplicate, Error::ValidationError(_)));
+}
+
+// ============================================================================
+// translate
+// ============================================================================
+
+#[tokio::test]
+async fn test_translate_detects_source_language() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "format": { "required": ["translation", "confidence", "source_language"] }
+        })))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"translation": "Where is the station?", "source_language": "French", "confidence": 0.9}"#,
+        ))
+        .create_async()
+        .await;
+
+    let translation = client_for(&server)
+        .translate("qwen3:0.6b", "Où est la gare ?", "English")
+        .await
+        .unwrap();
+
+    assert_eq!(translation.text, "Where is the station?");
+    assert_eq!(translation.source_lang, "French");
+    assert_eq!(translation.chunks.len(), 1);
+    assert_eq!(translation.chunks[0].source, "Où est la gare ?");
+    assert_eq!(translation.confidence(), 0.9);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_translate_chunks_long_text() {
+    let mut server = mockito::Server::new_async().await;
+    let rest = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex(
+            "from French into English".to_string(),
+        ))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"translation": "Second.", "confidence": 0.5}"#,
+        ))
+        .expect(1)
+        .create_async()
+        .await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"translation": "First.", "source_language": "French", "confidence": 1.0}"#,
+        ))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let text = format!("{}\n\n{}", "a".repeat(1500), "b".repeat(1500));
+    let translation = client_for(&server)
+        .translate("qwen3:0.6b", &text, "English")
+        .await
+        .unwrap();
+
+    assert_eq!(translation.text, "First.\n\nSecond.");
+    assert_eq!(translation.chunks.len(), 2);
+    assert_eq!(translation.confidence(), 0.5);
+    first.assert_async().await;
+    rest.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_translate_from_skips_detection() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "format": { "required": ["translation", "confidence"] }
+        })))
+        .with_status(200)
+        .with_body(chat_reply(r#"{"translation": "Hallo", "confidence": 0.8}"#))
+        .create_async()
+        .await;
+
+    let translation = client_for(&server)
+        .translate_from("qwen3:0.6b", "Hello", "English", "German")
+        .await
+        .unwrap();
+
+    assert_eq!(translation.source_lang, "English");
+    assert_eq!(translation.target_lang, "German");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_translate_requires_text_and_language() {
+    let server = mockito::Server::new_async().await;
+    let client = client_for(&server);
+
+    let no_text = client.translate("qwen3:0.6b", " ", "German").await;
+    let no_target = client.translate("qwen3:0.6b", "Hello", "").await;
+
+    assert!(matches!(no_text, Err(Error::ValidationError(_))));
+    assert!(matches!(no_target, Err(Error::ValidationError(_))));
+}
+
+// ============================================================================
+// extract
+// ============================================================================
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Contact {
+    name: String,
+    email: Option<String>,
+}
+
+#[tokio::test]
+async fn test_extract_decodes_json_mode_reply() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "format": "json" }),
+        ))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#" {"name": "Ana", "email": "ana@example.com"} "#,
+        ))
+        .create_async()
+        .await;
+
+    let contact: Contact = client_for(&server)
+        .extract("qwen3:0.6b", "Reach Ana at ana@example.com.")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contact,
+        Contact {
+            name: "Ana".to_string(),
+            email: Some("ana@example.com".to_string()),
+        }
+    );
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_extract_with_schema_sends_schema() {
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" } },
+        "required": ["name"]
+    });
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "format": schema }),
+        ))
+        .with_status(200)
+        .with_body(chat_reply(r#"{"name": "Bo"}"#))
+        .create_async()
+        .await;
+
+    let contact: Contact = client_for(&server)
+        .extract_with_schema("qwen3:0.6b", "Bo called.", schema)
+        .await
+        .unwrap();
+
+    assert_eq!(contact.name, "Bo");
+    assert_eq!(contact.email, None);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_extract_malformed_reply_is_serialization_error() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("not json"))
+        .create_async()
+        .await;
+
+    let err = client_for(&server)
+        .extract::<Contact>("qwen3:0.6b", "text")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SerializationError(_)));
+}
+
+// ============================================================================
+// generate_code
+// ============================================================================
+
+#[tokio::test]
+async fn test_generate_code_prefers_requested_language() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex("tagged `rust`".to_string()))
+        .with_status(200)
+        .with_body(chat_reply(
+            "Build with:\n```sh\ncargo build\n```\nCode:\n```Rust\nfn main() {}\n```\n",
+        ))
+        .create_async()
+        .await;
+
+    let block = client_for(&server)
+        .generate_code("qwen3:0.6b", "An empty program.", "rust")
+        .await
+        .unwrap();
+
+    assert_eq!(block.language.as_deref(), Some("Rust"));
+    assert_eq!(block.code, "fn main() {}\n");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_generate_code_accepts_bare_code() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("  print('hi')\n"))
+        .create_async()
+        .await;
+
+    let block = client_for(&server)
+        .generate_code("qwen3:0.6b", "Greet.", "python")
+        .await
+        .unwrap();
+
+    assert_eq!(block.language.as_deref(), Some("python"));
+    assert_eq!(block.to_string(), "print('hi')");
+}
+
+#[tokio::test]
+async fn test_generate_code_empty_reply_is_serialization_error() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("```rust\n```"))
+        .create_async()
+        .await;
+
+    let err = client_for(&server)
+        .generate_code("qwen3:0.6b", "Nothing.", "rust")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SerializationError(_)));
+}
+
+// ============================================================================
+// judge
+// ============================================================================
+
+#[tokio::test]
+async fn test_judge_returns_verdict() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex(
+            "Answers in one sentence\\..*Output to evaluate:\\\\nParis".to_string(),
+        ))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"score": 9, "pass": true, "rationale": "Correct and brief."}"#,
+        ))
+        .create_async()
+        .await;
+
+    let verdict = client_for(&server)
+        .judge("qwen3:8b", "Answers in one sentence.", "Paris.")
+        .await
+        .unwrap();
+
+    assert_eq!(verdict.score, 9);
+    assert!(verdict.meets(7));
+    assert_eq!(verdict.rationale, "Correct and brief.");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_judge_clamps_score() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"score": 0, "pass": false, "rationale": "Off topic."}"#,
+        ))
+        .create_async()
+        .await;
+
+    let verdict = client_for(&server)
+        .judge("qwen3:8b", "Stays on topic.", "Bananas.")
+        .await
+        .unwrap();
+
+    assert_eq!(verdict.score, 1);
+    assert!(!verdict.pass);
+}
+
+// ============================================================================
+// generate_n_and_vote
+// ============================================================================
+
+fn generate_reply(text: &str) -> String {
+    serde_json::json!({ "model": "qwen3:0.6b", "response": text, "done": true }).to_string()
+}
+
+async fn mock_seed(server: &mut mockito::Server, seed: i64, text: &str) -> mockito::Mock {
+    server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "options": { "seed": seed, "temperature": 0.7 }
+        })))
+        .with_status(200)
+        .with_body(generate_reply(text))
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_generate_n_and_vote_picks_majority() {
+    let mut server = mockito::Server::new_async().await;
+    let mocks = [
+        mock_seed(&mut server, 10, "so 390").await,
+        mock_seed(&mut server, 11, "so 391").await,
+        mock_seed(&mut server, 12, "so 391").await,
+        mock_seed(&mut server, 13, "no idea").await,
+    ];
+
+    let request = GenerateRequest::new("qwen3:0.6b", "17 * 23?")
+        .with_options(ModelOptions::default().with_seed(10).with_temperature(0.7));
+    let vote = client_for(&server)
+        .generate_n_and_vote(&request, 4, |r| {
+            r.text()?.split_whitespace().last()?.parse::<i64>().ok()
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(vote.answer, 391);
+    assert_eq!(vote.votes, 2);
+    assert_eq!(vote.answered, 3);
+    assert_eq!(vote.samples, 4);
+    assert_eq!(vote.response.text(), Some("so 391"));
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_generate_n_and_vote_tie_goes_to_first_sample() {
+    let mut server = mockito::Server::new_async().await;
+    let _a = mock_seed(&mut server, 0, "b").await;
+    let _b = mock_seed(&mut server, 1, "a").await;
+
+    let request = GenerateRequest::new("qwen3:0.6b", "pick")
+        .with_options(ModelOptions::default().with_temperature(0.7));
+    let vote = client_for(&server)
+        .generate_n_and_vote(&request, 2, |r| r.text().map(str::to_string))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(vote.answer, "b");
+}
+
+#[tokio::test]
+async fn test_generate_n_and_vote_wraps_seed_past_max() {
+    let mut server = mockito::Server::new_async().await;
+    let mocks = [
+        mock_seed(&mut server, i64::MAX, "a").await,
+        mock_seed(&mut server, i64::MIN, "a").await,
+    ];
+
+    let request = GenerateRequest::new("qwen3:0.6b", "pick").with_options(
+        ModelOptions::default()
+            .with_seed(i64::MAX)
+            .with_temperature(0.7),
+    );
+    let vote = client_for(&server)
+        .generate_n_and_vote(&request, 2, |r| r.text().map(str::to_string))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(vote.votes, 2);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_generate_n_and_vote_no_answers() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = mock_seed(&mut server, 0, "nothing").await;
+
+    let request = GenerateRequest::new("qwen3:0.6b", "pick")
+        .with_options(ModelOptions::default().with_temperature(0.7));
+    let vote = client_for(&server)
+        .generate_n_and_vote(&request, 1, |r| r.text()?.parse::<i64>().ok())
+        .await
+        .unwrap();
+
+    assert!(vote.is_none());
+}
+
+#[tokio::test]
+async fn test_generate_n_and_vote_requires_samples() {
+    let server = mockito::Server::new_async().await;
+    let request = GenerateRequest::new("qwen3:0.6b", "pick");
+    let err = client_for(&server)
+        .generate_n_and_vote(&request, 0, |r| r.text().map(str::to_string))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::ValidationError(_)));
+}
+
+// ============================================================================
+// generate_json_with_retries
+// ============================================================================
+
+#[tokio::test]
+async fn test_generate_json_with_retries_reprompts_with_error() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({ "prompt": "Give me a contact." }),
+        ))
+        .with_status(200)
+        .with_body(generate_reply("Name: Ana"))
+        .create_async()
+        .await;
+    let second = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::Regex(
+            "Your previous reply was:\\\\nName: Ana.*could not be parsed as JSON".to_string(),
+        ))
+        .with_status(200)
+        .with_body(generate_reply("Here you go: {\"name\": \"Ana\"}"))
+        .create_async()
+        .await;
+
+    let request = GenerateRequest::new("qwen3:0.6b", "Give me a contact.");
+    let contact: Contact = client_for(&server)
+        .generate_json_with_retries(&request, 3)
+        .await
+        .unwrap();
+
+    assert_eq!(contact.name, "Ana");
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_generate_json_with_retries_gives_up() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(generate_reply("no json here"))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let request = GenerateRequest::new("qwen3:0.6b", "Give me a contact.");
+    let err = client_for(&server)
+        .generate_json_with_retries::<Contact>(&request, 2)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::SerializationError(_)));
+    mock.assert_async().await;
+}
+
+// ============================================================================
+// race_generate
+// ============================================================================
+
+fn generate_body(model: &str, text: &str) -> String {
+    serde_json::json!({"model": model, "response": text, "done": true}).to_string()
+}
+
+#[tokio::test]
+async fn test_race_generate_returns_a_successful_model() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"model": "broken"}),
+        ))
+        .with_status(404)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(
+            serde_json::json!({"model": "working"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(generate_body("working", "blue"))
+        .create_async()
+        .await;
+
+    let winner = client_for(&server)
+        .race_generate(&["broken", "working"], "Name a colour.")
+        .await
+        .unwrap();
+
+    assert_eq!(winner.model, "working");
+    assert_eq!(winner.text(), Some("blue"));
+    assert_eq!(winner.host, server.url());
+}
+
+#[tokio::test]
+async fn test_race_generate_n_across_hosts() {
+    let mut first = mockito::Server::new_async().await;
+    let mut second = mockito::Server::new_async().await;
+    for (server, model) in [(&mut first, "a"), (&mut second, "b")] {
+        server
+            .mock("POST", "/api/generate")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(generate_body(model, model))
+            .create_async()
+            .await;
+    }
+
+    let handles = [
+        client_for(&first).model("a"),
+        client_for(&second).model("b"),
+    ];
+    let winners = ollama_oxide::ModelHandle::race(&handles, "hi", 2)
+        .await
+        .unwrap();
+
+    let mut models: Vec<&str> = winners.iter().map(|w| w.model.as_str()).collect();
+    models.sort();
+    assert_eq!(models, vec!["a", "b"]);
+}
+
+#[tokio::test]
+async fn test_race_generate_all_fail() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let result = client_for(&server).race_generate(&["x", "y"], "hi").await;
+
+    assert!(matches!(result, Err(Error::HttpStatusError(404))));
+}
+
+#[tokio::test]
+async fn test_race_generate_requires_models() {
+    let server = mockito::Server::new_async().await;
+    let result = client_for(&server).race_generate(&[], "hi").await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+}
+
+// ============================================================================
+// ChatSession
+// ============================================================================
+
+fn chat_reply_with_counts(content: &str, prompt_eval_count: i32, eval_count: i32) -> String {
+    serde_json::json!({
+        "model": "qwen3:8b",
+        "message": { "role": "assistant", "content": content },
+        "done": true,
+        "prompt_eval_count": prompt_eval_count,
+        "eval_count": eval_count
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_session_keeps_history() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Hi!"))
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server)
+        .session("qwen3:0.6b")
+        .with_system("Be brief.");
+    session.send("Hello").await.unwrap();
+    first.remove_async().await;
+
+    let second = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "Hello" },
+                { "role": "assistant", "content": "Hi!" },
+                { "role": "user", "content": "Again" }
+            ]
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Hi again!"))
+        .create_async()
+        .await;
+
+    let reply = session.send("Again").await.unwrap();
+
+    assert_eq!(reply.content(), Some("Hi again!"));
+    assert_eq!(session.messages().len(), 5);
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_failed_send_leaves_history_unchanged() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server).session("qwen3:0.6b");
+    let result = session.send("Hello").await;
+
+    assert!(matches!(result, Err(Error::HttpStatusError(404))));
+    assert!(session.messages().is_empty());
+}
+
+#[tokio::test]
+async fn test_session_summarizes_oldest_turns_over_budget() {
+    let mut server = mockito::Server::new_async().await;
+    let mut session = client_for(&server)
+        .session("qwen3:8b")
+        .with_system("Be brief.")
+        .with_summarize(
+            ollama_oxide::SummarizePolicy::new(100)
+                .with_model("qwen3:0.6b")
+                .with_keep_recent(2),
+        );
+
+    let small = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply_with_counts("One.", 10, 2))
+        .create_async()
+        .await;
+    session.send("First").await.unwrap();
+    small.remove_async().await;
+
+    let large = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply_with_counts("Two.", 90, 10))
+        .create_async()
+        .await;
+    session.send("Second").await.unwrap();
+    large.remove_async().await;
+    assert_eq!(session.context_tokens(), 100);
+
+    let summarizer = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b"
+        })))
+        .with_status(200)
+        .with_body(chat_reply("The user said First."))
+        .expect(1)
+        .create_async()
+        .await;
+    let next = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:8b"
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Three."))
+        .create_async()
+        .await;
+    session.send("Third").await.unwrap();
+
+    let contents: Vec<&str> = session
+        .messages()
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(
+        contents,
+        [
+            "Be brief.",
+            "Summary of the earlier conversation:\nThe user said First.",
+            "Second",
+            "Two.",
+            "Third",
+            "Three."
+        ]
+    );
+    summarizer.assert_async().await;
+    next.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_fork_continues_independently() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Red."))
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server)
+        .session("qwen3:0.6b")
+        .with_system("Be brief.");
+    session.send("Name a color.").await.unwrap();
+    first.remove_async().await;
+
+    let branch_mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "Name a fruit." }
+            ]
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Apple."))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut branch = session.fork(1);
+    branch.send("Name a fruit.").await.unwrap();
+
+    let contents = |s: &ollama_oxide::ChatSession| {
+        s.messages()
+            .iter()
+            .map(|m| m.content.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(contents(&session), ["Be brief.", "Name a color.", "Red."]);
+    assert_eq!(contents(&branch), ["Be brief.", "Name a fruit.", "Apple."]);
+    assert_eq!(session.fork(usize::MAX).messages().len(), 3);
+    branch_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_regenerate_replaces_last_reply() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Tom."))
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server).session("qwen3:0.6b").with_options(
+        ModelOptions::default()
+            .with_temperature(0.2)
+            .with_num_ctx(4096),
+    );
+    session.send("Name a cat.").await.unwrap();
+    first.remove_async().await;
+
+    let again = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "model": "qwen3:8b",
+            "messages": [{ "role": "user", "content": "Name a cat." }],
+            "options": { "temperature": 1.0, "seed": 7, "num_ctx": 4096 },
+            "stream": false
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Whiskers."))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let options = ModelOptions::default().with_temperature(1.0).with_seed(7);
+    let reply = session
+        .regenerate_with("qwen3:8b", Some(options))
+        .await
+        .unwrap();
+
+    assert_eq!(reply.content(), Some("Whiskers."));
+    assert_eq!(session.model(), "qwen3:0.6b");
+    assert_eq!(session.messages().len(), 2);
+    assert_eq!(session.messages()[1].content, "Whiskers.");
+    again.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_session_failed_regenerate_keeps_reply() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Tom."))
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server).session("qwen3:0.6b");
+    session.send("Name a cat.").await.unwrap();
+    first.remove_async().await;
+    let _failing = server
+        .mock("POST", "/api/chat")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let result = session.regenerate(None).await;
+
+    assert!(matches!(result, Err(Error::HttpStatusError(404))));
+    assert_eq!(session.messages()[1].content, "Tom.");
+}
+
+#[tokio::test]
+async fn test_session_regenerate_needs_a_question() {
+    let server = mockito::Server::new_async().await;
+    let mut session = client_for(&server)
+        .session("qwen3:0.6b")
+        .with_system("Be brief.");
+
+    let result = session.regenerate(None).await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+    assert_eq!(session.messages().len(), 1);
+}
+
+#[tokio::test]
+async fn test_session_switch_model_sends_history_to_new_model() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Maybe 4?"))
+        .create_async()
+        .await;
+
+    let mut session = client_for(&server).session("qwen3:0.6b");
+    session.send("What is 2 + 2?").await.unwrap();
+    first.remove_async().await;
+
+    let escalated = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:32b",
+            "messages": [
+                { "role": "user", "content": "What is 2 + 2?" },
+                { "role": "assistant", "content": "Maybe 4?" },
+                { "role": "system", "content": "A smaller model answered before." },
+                { "role": "user", "content": "Are you sure?" }
+            ]
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Yes, 4."))
+        .expect(1)
+        .create_async()
+        .await;
+
+    session.switch_model("qwen3:32b", Some("A smaller model answered before."));
+    session.send("Are you sure?").await.unwrap();
+
+    assert_eq!(session.to_record().model, "qwen3:32b");
+    escalated.assert_async().await;
+}
+
+// ============================================================================
+// chat_batch
+// ============================================================================
+
+fn user_prompt_mock(server: &mut mockito::Server, prompt: &str) -> mockito::Mock {
+    server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "messages": [{ "role": "user", "content": prompt }]
+        })))
+}
+
+#[tokio::test]
+async fn test_chat_batch_returns_results_in_order() {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (prompt, reply) in [("one", "1"), ("two", "2"), ("four", "4")] {
+        mocks.push(
+            user_prompt_mock(&mut server, prompt)
+                .with_status(200)
+                .with_body(chat_reply(reply))
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+    let failing = user_prompt_mock(&mut server, "three")
+        .with_status(404)
+        .with_body(r#"{"error":"model not found"}"#)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let requests = ["one", "two", "three", "four"].map(|prompt| {
+        ollama_oxide::ChatRequest::new("qwen3:0.6b", [ollama_oxide::ChatMessage::user(prompt)])
+    });
+    let results = client.chat_batch(requests, 2).await;
+
+    assert_eq!(results.len(), 4);
+    let contents: Vec<_> = results
+        .iter()
+        .map(|r| r.as_ref().ok().and_then(|r| r.content()))
+        .collect();
+    assert_eq!(contents, [Some("1"), Some("2"), None, Some("4")]);
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+    failing.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_batch_with_zero_concurrency_still_runs() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("ok"))
+        .expect(3)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let requests = (0..3).map(|i| {
+        ollama_oxide::ChatRequest::new(
+            "qwen3:0.6b",
+            [ollama_oxide::ChatMessage::user(format!("q{i}"))],
+        )
+    });
+    let results = client.chat_batch(requests, 0).await;
+
+    assert!(results.iter().all(|r| r.is_ok()));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_send_batch_keeps_histories_independent() {
+    let mut server = mockito::Server::new_async().await;
+    let red = user_prompt_mock(&mut server, "Name a color.")
+        .with_status(200)
+        .with_body(chat_reply("Red."))
+        .create_async()
+        .await;
+    let failing = user_prompt_mock(&mut server, "Name a fruit.")
+        .with_status(404)
+        .with_body(r#"{"error":"model not found"}"#)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let mut sessions = vec![client.session("qwen3:0.6b"), client.session("qwen3:0.6b")];
+    let results = ollama_oxide::ChatSession::send_batch(
+        &mut sessions,
+        &["Name a color.", "Name a fruit."],
+        4,
+    )
+    .await;
+
+    assert_eq!(results[0].as_ref().unwrap().content(), Some("Red."));
+    assert!(results[1].is_err());
+    let contents = |s: &ollama_oxide::ChatSession| {
+        s.messages()
+            .iter()
+            .map(|m| m.content.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(contents(&sessions[0]), ["Name a color.", "Red."]);
+    assert!(contents(&sessions[1]).is_empty());
+    red.assert_async().await;
+    failing.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_send_batch_rejects_mismatched_prompts() {
+    let server = mockito::Server::new_async().await;
+    let client = client_for(&server);
+    let mut sessions = vec![client.session("qwen3:0.6b"), client.session("qwen3:0.6b")];
+
+    let results = ollama_oxide::ChatSession::send_batch(&mut sessions, &["Hello"], 2).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(
+        results
+            .iter()
+            .all(|r| matches!(r, Err(Error::ValidationError(_))))
+    );
+}
+
+// ============================================================================
+// EvalRunner
+// ============================================================================
+
+#[tokio::test]
+async fn test_eval_runner_reports_each_case() {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (prompt, reply) in [("What is 2+2?", " 4\n"), ("What is 3+3?", "7")] {
+        mocks.push(
+            user_prompt_mock(&mut server, prompt)
+                .with_status(200)
+                .with_body(chat_reply(reply))
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+    let capital = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b",
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "What is the capital of France?" }
+            ],
+            "options": { "temperature": 0.0 }
+        })))
+        .with_status(200)
+        .with_body(chat_reply("The capital is Paris."))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let report = client_for(&server)
+        .eval_runner("qwen3:0.6b", "What is {a}+{a}?")
+        .with_concurrency(2)
+        .with_cases([
+            EvalCase::new("two", EvalCheck::exact("4")).with_var("a", "2"),
+            EvalCase::new("three", EvalCheck::exact("6")).with_var("a", "3"),
+            EvalCase::new("missing", EvalCheck::exact("")),
+        ])
+        .run()
+        .await;
+    let capital_report = client_for(&server)
+        .eval_runner("qwen3:0.6b", "What is the capital of {country}?")
+        .with_system("Be brief.")
+        .with_options(ModelOptions::default().with_temperature(0.0))
+        .with_case(
+            EvalCase::new("france", EvalCheck::regex(r"\bParis\b").unwrap())
+                .with_var("country", "France"),
+        )
+        .run()
+        .await;
+
+    let names: Vec<_> = report.results.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["two", "three", "missing"]);
+    assert!(report.results[0].passed());
+    assert_eq!(report.results[1].output.as_deref(), Some("7"));
+    assert!(matches!(
+        report.results[2].result,
+        Err(Error::ValidationError(_))
+    ));
+    assert_eq!(
+        report.to_string(),
+        "1 passed, 1 failed, 1 errors\n\
+         - three: got \"7\"\n\
+         - missing: error: Validation error: no value for eval template placeholder {a}"
+    );
+    assert!(capital_report.is_success());
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+    capital.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_eval_runner_judges_with_judge_model() {
+    let mut server = mockito::Server::new_async().await;
+    let answer = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b"
+        })))
+        .with_status(200)
+        .with_body(chat_reply("Thanks so much!"))
+        .expect(2)
+        .create_async()
+        .await;
+    let judge = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:8b"
+        })))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"score": 6, "pass": true, "rationale": "Polite but generic."}"#,
+        ))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let report = client_for(&server)
+        .eval_runner("qwen3:0.6b", "Thank {name}.")
+        .with_judge_model("qwen3:8b")
+        .with_cases([
+            EvalCase::new("lenient", EvalCheck::judge("Is polite.")).with_var("name", "Ann"),
+            EvalCase::new("strict", EvalCheck::judge("Is polite.").with_min_score(8))
+                .with_var("name", "Bo"),
+        ])
+        .run()
+        .await;
+
+    assert!(report.results[0].passed());
+    assert!(!report.results[1].passed());
+    assert_eq!(report.results[1].verdict.as_ref().map(|v| v.score), Some(6));
+    assert_eq!(report.pass_rate(), 0.5);
+    answer.assert_async().await;
+    judge.assert_async().await;
+}
+
+// ============================================================================
+// compare_prompts
+// ============================================================================
+
+#[tokio::test]
+async fn test_compare_prompts_scores_with_same_seeds() {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (seed, prompt, reply) in [
+        (7, "Short: cats", "Cats."),
+        (7, "Long: cats", "Cats are small carnivores."),
+        (8, "Short: dogs", "Dogs are loyal companions."),
+        (8, "Long: dogs", "Dogs."),
+    ] {
+        mocks.push(
+            server
+                .mock("POST", "/api/chat")
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "messages": [{ "role": "user", "content": prompt }],
+                    "options": { "seed": seed }
+                })))
+                .with_status(200)
+                .with_body(chat_reply(reply))
+                .expect(1)
+                .create_async()
+                .await,
+        );
+    }
+
+    let report = client_for(&server)
+        .compare_prompts(
+            "qwen3:0.6b",
+            "Short: {input}",
+            "Long: {input}",
+            ["cats", "dogs"],
+        )
+        .with_options(ModelOptions::default().with_seed(7))
+        .with_scorer(|_input, output| output.len() as f64)
+        .with_concurrency(2)
+        .run()
+        .await;
+
+    assert_eq!(report.cases[0].winner(), Some(PromptWinner::B));
+    assert_eq!(report.cases[0].scores, Some((5.0, 26.0)));
+    assert_eq!(report.cases[1].winner(), Some(PromptWinner::A));
+    assert_eq!(report.overall(), PromptWinner::Tie);
+    assert_eq!(report.to_string(), "A won 1, B won 1, 0 ties, 0 errors");
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_compare_prompts_wraps_seed_past_max() {
+    let mut server = mockito::Server::new_async().await;
+    let mut mocks = Vec::new();
+    for (seed, input) in [(i64::MAX, "cats"), (i64::MIN, "dogs")] {
+        mocks.push(
+            server
+                .mock("POST", "/api/chat")
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "messages": [{ "role": "user", "content": format!("Q: {input}") }],
+                    "options": { "seed": seed }
+                })))
+                .with_status(200)
+                .with_body(chat_reply("reply"))
+                .expect(2)
+                .create_async()
+                .await,
+        );
+    }
+
+    let report = client_for(&server)
+        .compare_prompts("qwen3:0.6b", "Q: {input}", "Q: {input}", ["cats", "dogs"])
+        .with_options(ModelOptions::default().with_seed(i64::MAX))
+        .with_scorer(|_input, output| output.len() as f64)
+        .run()
+        .await;
+
+    assert_eq!(report.to_string(), "A won 0, B won 0, 2 ties, 0 errors");
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_compare_prompts_judge_sees_alternating_order() {
+    let mut server = mockito::Server::new_async().await;
+    let answers = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "qwen3:0.6b"
+        })))
+        .with_status(200)
+        .with_body(chat_reply("reply"))
+        .expect(4)
+        .create_async()
+        .await;
+    let judge = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex(
+            "Prefer brevity\\..*First reply".to_string(),
+        ))
+        .with_status(200)
+        .with_body(chat_reply(
+            r#"{"winner": "first", "rationale": "Clearer."}"#,
+        ))
+        .expect(2)
+        .create_async()
+        .await;
+
+    let report = client_for(&server)
+        .compare_prompts("qwen3:0.6b", "A {input}", "B {input}", ["x", "y"])
+        .with_judge("qwen3:8b", "Prefer brevity.")
+        .run()
+        .await;
+
+    let winners: Vec<_> = report.cases.iter().map(|c| c.winner()).collect();
+    assert_eq!(winners, [Some(PromptWinner::A), Some(PromptWinner::B)]);
+    assert_eq!(report.cases[0].rationale.as_deref(), Some("Clearer."));
+    answers.assert_async().await;
+    judge.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_compare_prompts_reports_failed_calls() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(404)
+        .with_body(r#"{"error":"model not found"}"#)
+        .create_async()
+        .await;
+
+    let report = client_for(&server)
+        .compare_prompts("missing", "A {input}", "B {input}", ["x"])
+        .with_scorer(|_, _| 0.0)
+        .run()
+        .await;
+
+    assert!(report.cases[0].winner().is_none());
+    assert_eq!(report.errors().count(), 1);
+}
+
+// ============================================================================
+// SessionStore
+// ============================================================================
+
+#[tokio::test]
+async fn test_session_store_keeps_sessions_apart() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Noted."))
+        .create_async()
+        .await;
+
+    let store = client_for(&server)
+        .session_store("qwen3:0.6b")
+        .with_system("Be brief.");
+    store.send("alice", "I like tea.").await.unwrap();
+    store.send("alice", "And cake.").await.unwrap();
+    store.send("bob", "I like coffee.").await.unwrap();
+
+    assert_eq!(store.names().await.unwrap(), ["alice", "bob"]);
+    assert_eq!(store.get("alice").await.unwrap().messages().len(), 5);
+    assert_eq!(store.get("bob").await.unwrap().messages().len(), 3);
+    assert_eq!(store.get("carol").await.unwrap().messages().len(), 1);
+
+    assert!(store.remove("alice").await.unwrap());
+    assert!(!store.remove("alice").await.unwrap());
+    assert_eq!(store.names().await.unwrap(), ["bob"]);
+}
+
+#[tokio::test]
+async fn test_session_store_failed_send_saves_nothing() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let store = client_for(&server).session_store("qwen3:0.6b");
+    let result = store.send("alice", "Hello").await;
+
+    assert!(matches!(result, Err(Error::HttpStatusError(404))));
+    assert!(store.names().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_session_store_json_dir_backend_round_trip() {
+    let dir = std::env::temp_dir().join(format!("ollama-oxide-sessions-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Hi!"))
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let backend = std::sync::Arc::new(ollama_oxide::JsonDirBackend::new(&dir));
+    let store = client
+        .session_store("qwen3:0.6b")
+        .with_backend(backend.clone());
+    store.send("user-42", "Hello").await.unwrap();
+    assert!(dir.join("user-42.json").is_file());
+
+    // A fresh store over the same directory sees the saved conversation.
+    let reopened = client.session_store("other").with_backend(backend);
+    let session = reopened.get("user-42").await.unwrap();
+    assert_eq!(session.model(), "qwen3:0.6b");
+    assert_eq!(session.messages().len(), 2);
+    assert_eq!(session.messages()[1].content, "Hi!");
+
+    assert!(matches!(
+        reopened.send("../escape", "Hi").await,
+        Err(Error::ValidationError(_))
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_json_dir_backend_concurrent_saves_leave_one_whole_file() {
+    let dir = std::env::temp_dir().join(format!(
+        "ollama-oxide-sessions-concurrent-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    let server = mockito::Server::new_async().await;
+    let backend = std::sync::Arc::new(ollama_oxide::JsonDirBackend::new(&dir));
+    let store = client_for(&server)
+        .session_store("qwen3:0.6b")
+        .with_backend(backend.clone());
+    let record = store.get("user-42").await.unwrap().to_record();
+
+    // Two writers sharing a directory bypass each other's locks.
+    let saves = (0..64).map(|_| {
+        let backend = backend.clone();
+        let record = record.clone();
+        tokio::spawn(async move {
+            ollama_oxide::SessionBackend::save(&*backend, "user-42", &record).await
+        })
+    });
+    for result in futures::future::join_all(saves).await {
+        result.unwrap().unwrap();
+    }
+
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(files, ["user-42.json"]);
+    assert_eq!(store.get("user-42").await.unwrap().model(), "qwen3:0.6b");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ============================================================================
+// JobQueue
+// ============================================================================
+
+fn job_chat() -> ollama_oxide::ChatRequest {
+    ollama_oxide::ChatRequest::new("qwen3:0.6b", [ollama_oxide::ChatMessage::user("Hi")])
+}
+
+#[tokio::test]
+async fn test_job_queue_runs_submitted_job() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Done."))
+        .create_async()
+        .await;
+
+    let jobs = client_for(&server).job_queue();
+    let id = jobs.submit(job_chat()).await.unwrap();
+
+    let job = jobs.wait(&id).await.unwrap();
+    assert_eq!(job.status, ollama_oxide::JobStatus::Succeeded);
+    assert_eq!(job.text(), Some("Done."));
+    assert!(job.finished_at.is_some());
+    assert_eq!(jobs.status(&id).await.unwrap(), Some(job));
+    assert_eq!(jobs.jobs().await.unwrap().len(), 1);
+
+    assert!(jobs.remove(&id).await.unwrap());
+    assert_eq!(jobs.status(&id).await.unwrap(), None);
+    assert!(matches!(
+        jobs.wait(&id).await,
+        Err(Error::ValidationError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_job_queue_records_failure() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let jobs = client_for(&server).job_queue();
+    let id = jobs
+        .submit(GenerateRequest::new("missing", "Hi"))
+        .await
+        .unwrap();
+
+    let job = jobs.wait(&id).await.unwrap();
+    assert_eq!(job.status, ollama_oxide::JobStatus::Failed);
+    assert!(job.error.unwrap().contains("404"));
+    assert_eq!(job.output, None);
+}
+
+#[tokio::test]
+async fn test_job_queue_cancel() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_chunked_body(|w| {
+            std::thread::sleep(Duration::from_millis(500));
+            w.write_all(chat_reply("Too late.").as_bytes())
+        })
+        .create_async()
+        .await;
+
+    let jobs = client_for(&server).job_queue();
+    let running = jobs.submit(job_chat()).await.unwrap();
+    let queued = jobs.submit(job_chat()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(
+        jobs.status(&running).await.unwrap().unwrap().status,
+        ollama_oxide::JobStatus::Running
+    );
+    assert_eq!(
+        jobs.status(&queued).await.unwrap().unwrap().status,
+        ollama_oxide::JobStatus::Queued
+    );
+
+    assert!(jobs.cancel(&queued).await.unwrap());
+    assert!(jobs.cancel(&running).await.unwrap());
+    assert!(!jobs.cancel(&running).await.unwrap());
+    for id in [running, queued] {
+        let job = jobs.wait(&id).await.unwrap();
+        assert_eq!(job.status, ollama_oxide::JobStatus::Cancelled);
+        assert_eq!(job.output, None);
+    }
+}
+
+#[tokio::test]
+async fn test_job_queue_resumes_unfinished_jobs() {
+    use ollama_oxide::{JobBackend, JobRecord, JobStatus};
+
+    let dir = std::env::temp_dir().join(format!("ollama-oxide-jobs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Resumed."))
+        .create_async()
+        .await;
+
+    // A job an earlier process was running when it stopped
+    let backend = std::sync::Arc::new(ollama_oxide::JsonDirBackend::new(&dir));
+    let stale = JobRecord {
+        id: "job-1".into(),
+        request: job_chat().into(),
+        status: JobStatus::Running,
+        output: None,
+        error: None,
+        submitted_at: 1,
+        finished_at: None,
+        webhook: None,
+        webhook_error: None,
+    };
+    backend.save_job(&stale).await.unwrap();
+
+    let jobs = client_for(&server).job_queue().with_backend(backend);
+    assert_eq!(jobs.resume().await.unwrap(), 1);
+    let job = jobs.wait("job-1").await.unwrap();
+    assert_eq!(job.status, JobStatus::Succeeded);
+    assert_eq!(job.text(), Some("Resumed."));
+    assert!(dir.join("job-1.json").is_file());
+    assert_eq!(jobs.resume().await.unwrap(), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_job_queue_wait_reports_lost_final_record() {
+    use ollama_oxide::{JobBackend, JobRecord, MemoryBackend};
+
+    /// Refuses to store finished jobs
+    #[derive(Default)]
+    struct FullDisk(MemoryBackend);
+
+    #[async_trait::async_trait]
+    impl JobBackend for FullDisk {
+        async fn load_job(&self, id: &str) -> ollama_oxide::Result<Option<JobRecord>> {
+            self.0.load_job(id).await
+        }
+
+        async fn save_job(&self, record: &JobRecord) -> ollama_oxide::Result<()> {
+            if record.status.is_finished() {
+                return Err(Error::StorageError("disk full".into()));
+            }
+            self.0.save_job(record).await
+        }
+
+        async fn delete_job(&self, id: &str) -> ollama_oxide::Result<bool> {
+            self.0.delete_job(id).await
+        }
+
+        async fn list_jobs(&self) -> ollama_oxide::Result<Vec<String>> {
+            self.0.list_jobs().await
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Done."))
+        .create_async()
+        .await;
+
+    let jobs = client_for(&server)
+        .job_queue()
+        .with_backend(Arc::new(FullDisk::default()));
+    let id = jobs.submit(job_chat()).await.unwrap();
+
+    let err = jobs.wait(&id).await.unwrap_err();
+    assert!(matches!(err, Error::StorageError(ref m) if m == "disk full"));
+}
+
+#[tokio::test]
+async fn test_job_queue_posts_result_to_webhook() {
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Done."))
+        .create_async()
+        .await;
+    let hook = server
+        .mock("POST", "/hooks/job")
+        .match_header("authorization", mockito::Matcher::Missing)
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "status": "succeeded",
+            "output": { "endpoint": "chat", "response": { "message": { "content": "Done." } } }
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_api_key("secret");
+    let jobs = OllamaClient::new(config).unwrap().job_queue();
+    let webhook = format!("{}/hooks/job", server.url());
+    let id = jobs
+        .submit_with_webhook(job_chat(), &webhook)
+        .await
+        .unwrap();
+
+    let job = jobs.wait(&id).await.unwrap();
+    assert_eq!(job.webhook.as_deref(), Some(webhook.as_str()));
+    assert_eq!(job.webhook_error, None);
+    hook.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_job_queue_records_webhook_failure() {
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Done."))
+        .create_async()
+        .await;
+    let _hook = server
+        .mock("POST", "/hooks/job")
+        .with_status(503)
+        .create_async()
+        .await;
+
+    let jobs = client_for(&server).job_queue();
+    let webhook = format!("{}/hooks/job", server.url());
+    let id = jobs
+        .submit_with_webhook(job_chat(), &webhook)
+        .await
+        .unwrap();
+
+    let job = jobs.wait(&id).await.unwrap();
+    assert_eq!(job.status, ollama_oxide::JobStatus::Succeeded);
+    assert!(job.webhook_error.unwrap().contains("503"));
+
+    for invalid in ["not a url", "ftp://example.com/hook"] {
+        assert!(matches!(
+            jobs.submit_with_webhook(job_chat(), invalid).await,
+            Err(Error::ValidationError(_))
+        ));
+    }
+    assert_eq!(jobs.jobs().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_job_queue_calls_callbacks() {
+    use ollama_oxide::{JobCallback, JobRecord, JobStatus};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Finished(Mutex<Vec<JobStatus>>);
+
+    #[async_trait::async_trait]
+    impl JobCallback for Finished {
+        async fn on_finished(&self, job: &JobRecord) {
+            self.0.lock().unwrap().push(job.status);
+        }
+    }
+
+    let mut server = mockito::Server::new_async().await;
+    let _chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply("Done."))
+        .create_async()
+        .await;
+    let _generate = server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let finished = Arc::new(Finished::default());
+    let jobs = client_for(&server)
+        .job_queue()
+        .with_callback(finished.clone());
+    let ok = jobs.submit(job_chat()).await.unwrap();
+    jobs.wait(&ok).await.unwrap();
+    let failed = jobs
+        .submit(GenerateRequest::new("missing", "Hi"))
+        .await
+        .unwrap();
+    jobs.wait(&failed).await.unwrap();
+
+    assert_eq!(
+        *finished.0.lock().unwrap(),
+        [JobStatus::Succeeded, JobStatus::Failed]
+    );
+}
+
+// ============================================================================
+// moderate
+// ============================================================================
+
+fn moderation_reply(violence: f64) -> String {
+    chat_reply(
+        &serde_json::json!({
+            "hate": 0.0,
+            "harassment": 0.1,
+            "self_harm": 0.0,
+            "sexual": -0.2,
+            "violence": violence,
+            "illicit": 0.2
+        })
+        .to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_moderate_scores_every_category() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::Regex(
+            "self_harm.*Text to classify:\\\\nI will hurt him".to_string(),
+        ))
+        .with_status(200)
+        .with_body(moderation_reply(1.4))
+        .create_async()
+        .await;
+
+    let moderation = client_for(&server)
+        .moderate("llama-guard3:1b", "I will hurt him")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(moderation.scores.len(), ModerationCategory::ALL.len());
+    assert_eq!(moderation.score(ModerationCategory::Violence), 1.0);
+    assert_eq!(moderation.score(ModerationCategory::Sexual), 0.0);
+    assert_eq!(moderation.flagged(0.5), [ModerationCategory::Violence]);
+}
+
+#[tokio::test]
+async fn test_moderate_rejects_missing_category() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(chat_reply(r#"{"hate": 0.0}"#))
+        .create_async()
+        .await;
+
+    let result = client_for(&server).moderate("llama-guard3:1b", "hi").await;
+
+    assert!(matches!(result, Err(Error::SerializationError(_))));
+}
+
+#[tokio::test]
+async fn test_moderation_guardrail_blocks_flagged_prompt() {
+    use ollama_oxide::OllamaApiAsync;
+
+    let mut server = mockito::Server::new_async().await;
+    let moderation = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(moderation_reply(0.9))
+        .create_async()
+        .await;
+    let generate = server
+        .mock("POST", "/api/generate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let guardrail = client.moderation_guardrail("llama-guard3:1b");
+    let client = client.with_guardrail(Arc::new(guardrail));
+    let result = client
+        .generate(&GenerateRequest::new("qwen3:0.6b", "I will hurt him"))
+        .await;
+
+    moderation.assert_async().await;
+    generate.assert_async().await;
+    match result {
+        Err(Error::Blocked { guardrail, reason }) => {
+            assert_eq!(guardrail, "moderation");
+            assert_eq!(reason, "flagged for violence 0.90");
+        }
+        other => panic!("expected a block, got {other:?}"),
+    }
+}
@@ -108,3 +108,65 @@ fn test_error_debug_format() {
     let debug = format!("{:?}", error);
     assert!(debug.contains("ApiError"));
 }
+
+#[test]
+fn test_rate_limited_error_display() {
+    let error = Error::RateLimited {
+        retry_after: Some(std::time::Duration::from_secs(7)),
+    };
+    assert_eq!(error.to_string(), "Rate limited by server; retry after 7s");
+
+    let error = Error::RateLimited { retry_after: None };
+    assert_eq!(error.to_string(), "Rate limited by server");
+}
+
+#[test]
+fn test_blocked_error_display() {
+    let error = Error::Blocked {
+        guardrail: "no-pii".to_string(),
+        reason: "contains an email address".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Blocked by guardrail no-pii: contains an email address"
+    );
+}
+
+#[test]
+fn test_stream_interrupted_error_display() {
+    let error = Error::StreamInterrupted {
+        partial: "Hello".to_string(),
+        message: "connection reset".to_string(),
+    };
+    assert_eq!(error.to_string(), "Stream interrupted: connection reset");
+}
+
+#[test]
+fn test_embedding_space_mismatch_error_display() {
+    let error = Error::EmbeddingSpaceMismatch {
+        expected: "nomic-embed-text (768 dimensions)".to_string(),
+        found: "all-minilm (384 dimensions)".to_string(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "Embedding space mismatch: expected nomic-embed-text (768 dimensions), found all-minilm (384 dimensions)"
+    );
+}
+
+#[test]
+fn test_partial_output_error_display() {
+    let error = Error::PartialOutput {
+        partial: "Hello".to_string(),
+        error: Box::new(Error::ResponseTooLarge { limit: 8 }),
+    };
+    assert_eq!(error.partial_output(), Some("Hello"));
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "{} (after 5 bytes of output)",
+            Error::ResponseTooLarge { limit: 8 }
+        )
+    );
+    assert!(std::error::Error::source(&error).is_some());
+    assert_eq!(Error::ClientShutdown.partial_output(), None);
+}
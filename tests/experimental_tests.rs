@@ -0,0 +1,110 @@
+//! Tests for the experimental raw endpoint namespace
+
+use mockito::{Matcher, Server};
+use ollama_oxide::{ClientConfig, Error, OllamaClient};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+fn make_client(base_url: String) -> OllamaClient {
+    let config = ClientConfig::new(base_url, Duration::from_secs(30), 0)
+        .unwrap()
+        .with_api_key("test-key");
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_experimental_get_decodes_value() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/api/status")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"healthy":true,"queue":3}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let reply: Value = client.experimental().get("/api/status").await.unwrap();
+
+    assert_eq!(reply["queue"], 3);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_experimental_post_sends_body_and_auth() {
+    #[derive(Debug, Deserialize)]
+    struct Reply {
+        ok: bool,
+    }
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/new_thing")
+        .match_header("authorization", "Bearer test-key")
+        .match_body(Matcher::Json(json!({"model": "qwen3:0.6b"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"ok":true}"#)
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let reply: Reply = client
+        .experimental()
+        .post("/api/new_thing", &json!({"model": "qwen3:0.6b"}))
+        .await
+        .unwrap();
+
+    assert!(reply.ok);
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_experimental_post_stream() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/progress")
+        .with_status(200)
+        .with_header("content-type", "application/x-ndjson")
+        .with_body("{\"step\":1}\n{\"step\":2}\n")
+        .create_async()
+        .await;
+
+    let client = make_client(server.url());
+    let stream = client
+        .experimental()
+        .post_stream::<_, Value>("/api/progress", &json!({"stream": true}))
+        .await
+        .unwrap();
+
+    let mut steps = Vec::new();
+    while let Some(item) = stream.next().await {
+        steps.push(item.unwrap()["step"].as_i64().unwrap());
+    }
+    assert_eq!(steps, vec![1, 2]);
+}
+
+#[test]
+fn test_experimental_post_blocking_status_error() {
+    let mut server = Server::new();
+    let _mock = server
+        .mock("POST", "/api/missing")
+        .with_status(404)
+        .create();
+
+    let client = make_client(server.url());
+    let result: Result<Value, Error> = client
+        .experimental()
+        .post_blocking("/api/missing", &json!({}));
+
+    assert!(matches!(result, Err(Error::HttpStatusError(404))));
+}
+
+#[tokio::test]
+async fn test_experimental_rejects_relative_path() {
+    let client = OllamaClient::default().unwrap();
+    let result: Result<Value, Error> = client.experimental().get("api/status").await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+}
@@ -0,0 +1,181 @@
+//! Tests that invalid requests are rejected before any network I/O
+
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, DoneReason, EmbedInput, EmbedRequest, Error,
+    GenerateRequest, ModelOptions, OllamaApiAsync, OllamaApiSync, OllamaClient,
+};
+use std::time::Duration;
+
+fn client_for(server: &mockito::Server) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_chat_empty_messages_loads_model() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJsonString(
+            r#"{"model":"qwen3:0.6b","messages":[]}"#.to_string(),
+        ))
+        .with_status(200)
+        .with_body(
+            r#"{"model":"qwen3:0.6b","message":{"role":"assistant","content":""},"done_reason":"load","done":true}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let request = ChatRequest::new("qwen3:0.6b", Vec::<ChatMessage>::new());
+    let response = client.chat(&request).await.unwrap();
+
+    assert_eq!(response.done_reason, Some(DoneReason::Load));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_chat_stream_empty_model_not_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/chat")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let request = ChatRequest::new("", [ChatMessage::user("Hi")]);
+    let result = client.chat_stream(&request).await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_generate_images_on_text_model_not_sent() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client_for(&server);
+    let mut request = GenerateRequest::new("phi4", "Describe this");
+    request.images = Some(vec!["aGVsbG8=".into()]);
+    let result = client.generate(&request).await;
+
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("does not support images"));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_generate_blocking_negative_num_predict_not_sent() {
+    let mut server = mockito::Server::new();
+    let mock = server.mock("POST", "/api/generate").expect(0).create();
+
+    let client = client_for(&server);
+    let request = GenerateRequest::new("qwen3:0.6b", "Hello")
+        .with_options(ModelOptions::default().with_num_predict(-10));
+    let result = client.generate_blocking(&request);
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+    mock.assert();
+}
+
+#[test]
+fn test_embed_blocking_empty_input_not_sent() {
+    let mut server = mockito::Server::new();
+    let mock = server.mock("POST", "/api/embed").expect(0).create();
+
+    let client = client_for(&server);
+    let request = EmbedRequest::new(
+        "nomic-embed-text",
+        EmbedInput::multiple(Vec::<String>::new()),
+    );
+    let result = client.embed_blocking(&request);
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+    mock.assert();
+}
+
+#[test]
+fn test_validation_error_display() {
+    let err = Error::ValidationError("model name must not be empty".to_string());
+    assert_eq!(
+        err.to_string(),
+        "Validation error: model name must not be empty"
+    );
+}
+
+// ============================================================================
+// Token budget
+// ============================================================================
+
+fn budget_client(server: &mockito::Server, budget: ollama_oxide::TokenBudget) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0)
+        .unwrap()
+        .with_token_budget(budget);
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_token_budget_rejects_before_sending() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = budget_client(
+        &server,
+        ollama_oxide::TokenBudget::new(100).with_reserved_output(90),
+    );
+    let request = GenerateRequest::new("qwen3:0.6b", "x".repeat(80));
+    let result = client.generate(&request).await;
+
+    assert!(matches!(
+        result,
+        Err(Error::TokenBudgetExceeded {
+            estimated: 110,
+            limit: 100
+        })
+    ));
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_token_budget_truncates_chat_history() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/api/chat")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "Latest question" }
+            ]
+        })))
+        .with_status(200)
+        .with_body(r#"{"model":"m","message":{"role":"assistant","content":"ok"},"done":true}"#)
+        .create();
+
+    let client = budget_client(
+        &server,
+        ollama_oxide::TokenBudget::new(20).with_policy(ollama_oxide::OverflowPolicy::Truncate),
+    );
+    let request = ChatRequest::new(
+        "qwen3:0.6b",
+        [
+            ChatMessage::system("Be brief."),
+            ChatMessage::user("An old question that no longer fits"),
+            ChatMessage::assistant("An old answer"),
+            ChatMessage::user("Latest question"),
+        ],
+    );
+    client.chat_blocking(&request).unwrap();
+
+    mock.assert();
+}
@@ -0,0 +1,89 @@
+//! Tests for local server process management (`server` feature)
+
+use ollama_oxide::Error;
+use ollama_oxide::server::{OllamaServerHandle, ServerOptions};
+use std::time::Duration;
+
+#[test]
+fn test_spawn_blocking_missing_binary() {
+    let options = ServerOptions::new().with_binary("/nonexistent/ollama-oxide-test-binary");
+    let err = OllamaServerHandle::spawn_blocking(options).unwrap_err();
+    assert!(matches!(err, Error::ServerProcessError(_)));
+    assert!(err.to_string().contains("failed to start"));
+}
+
+#[tokio::test]
+async fn test_spawn_async_missing_binary() {
+    let options = ServerOptions::new().with_binary("/nonexistent/ollama-oxide-test-binary");
+    let err = OllamaServerHandle::spawn(options).await.unwrap_err();
+    assert!(matches!(err, Error::ServerProcessError(_)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_spawn_blocking_process_exits_early() {
+    // `sh serve` fails immediately because there is no script named "serve".
+    let options = ServerOptions::new()
+        .with_binary("sh")
+        .with_port(1)
+        .with_startup_timeout(Duration::from_secs(10));
+    let err = OllamaServerHandle::spawn_blocking(options).unwrap_err();
+    assert!(matches!(err, Error::ServerProcessError(_)));
+    assert!(err.to_string().contains("exited"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_spawn_async_times_out() {
+    // `yes serve` runs forever without ever answering HTTP.
+    let options = ServerOptions::new()
+        .with_binary("yes")
+        .with_port(1)
+        .with_startup_timeout(Duration::from_millis(300));
+    let err = OllamaServerHandle::spawn(options).await.unwrap_err();
+    assert!(matches!(err, Error::ServerProcessError(_)));
+    assert!(err.to_string().contains("did not become ready"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_spawn_blocking_refuses_port_in_use() {
+    let mut existing = mockito::Server::new();
+    existing
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.5.0"}"#)
+        .create();
+    let port = existing.socket_address().port();
+
+    // `yes` would never exit on its own, so only the pre-launch probe fails it.
+    let options = ServerOptions::new()
+        .with_binary("yes")
+        .with_host("127.0.0.1")
+        .with_port(port)
+        .with_startup_timeout(Duration::from_secs(5));
+    let err = OllamaServerHandle::spawn_blocking(options).unwrap_err();
+    assert!(matches!(err, Error::ServerProcessError(_)));
+    assert!(err.to_string().contains("already listening"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_spawn_async_refuses_port_in_use() {
+    let mut existing = mockito::Server::new_async().await;
+    existing
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.5.0"}"#)
+        .create_async()
+        .await;
+    let port = existing.socket_address().port();
+
+    let options = ServerOptions::new()
+        .with_binary("yes")
+        .with_host("127.0.0.1")
+        .with_port(port)
+        .with_startup_timeout(Duration::from_secs(5));
+    let err = OllamaServerHandle::spawn(options).await.unwrap_err();
+    assert!(err.to_string().contains("already listening"));
+}
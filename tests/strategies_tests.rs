@@ -0,0 +1,62 @@
+//! Tests for the proptest strategies (`ollama_oxide::strategies`)
+
+#![cfg(feature = "proptest")]
+
+use ollama_oxide::{ChatRequest, EmbedRequest, GenerateRequest, strategies};
+use proptest::prelude::*;
+
+/// Whether `name` has the `[namespace/]name[:tag]` shape `model_name` promises.
+fn is_model_name(name: &str) -> bool {
+    let (namespace, rest) = match name.split_once('/') {
+        Some((namespace, rest)) => (Some(namespace), rest),
+        None => (None, name),
+    };
+    let (base, tag) = match rest.split_once(':') {
+        Some((base, tag)) => (base, Some(tag)),
+        None => (rest, None),
+    };
+    let namespace_ok = namespace
+        .is_none_or(|ns| (1..=8).contains(&ns.len()) && ns.bytes().all(|b| b.is_ascii_lowercase()));
+    let base_ok = (1..=16).contains(&base.len())
+        && base.as_bytes()[0].is_ascii_lowercase()
+        && base
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'.' || b == b'-');
+    let tag_ok = tag.is_none_or(|tag| {
+        (1..=10).contains(&tag.len())
+            && tag
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'.')
+    });
+    namespace_ok && base_ok && tag_ok
+}
+
+// Images may decode to a different variant than they were built with, so
+// round trips compare the JSON sent rather than the values.
+proptest! {
+    #[test]
+    fn test_model_name_shape(name in strategies::model_name()) {
+        prop_assert!(is_model_name(&name), "unexpected model name {:?}", name);
+    }
+
+    #[test]
+    fn test_chat_request_roundtrip(request in strategies::chat_request()) {
+        let json = serde_json::to_string(&request).unwrap();
+        let back: ChatRequest = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn test_generate_request_roundtrip(request in strategies::generate_request()) {
+        let json = serde_json::to_string(&request).unwrap();
+        let back: GenerateRequest = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+
+    #[test]
+    fn test_embed_request_roundtrip(request in strategies::embed_request()) {
+        let json = serde_json::to_string(&request).unwrap();
+        let back: EmbedRequest = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+}
@@ -0,0 +1,66 @@
+//! Tests for the live-server test harness (`testing` feature)
+
+use ollama_oxide::OllamaApiAsync;
+use ollama_oxide::testing::{LiveServer, live_at, live_at_blocking};
+
+#[tokio::test]
+async fn test_live_at_available() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .expect_at_least(1)
+        .create_async()
+        .await;
+
+    let live = live_at(&server.url()).await;
+    assert!(!live.is_skipped());
+
+    let client = live.client().unwrap();
+    assert_eq!(client.version().await.unwrap().version, "0.12.6");
+}
+
+#[tokio::test]
+async fn test_live_at_unreachable_is_skipped() {
+    let live = live_at("http://127.0.0.1:1").await;
+    match live {
+        LiveServer::Skipped(reason) => assert!(reason.contains("127.0.0.1:1")),
+        LiveServer::Available(_) => panic!("expected skip"),
+    }
+}
+
+#[tokio::test]
+async fn test_live_at_invalid_url_is_skipped() {
+    assert!(live_at("not a url").await.is_skipped());
+}
+
+#[test]
+fn test_live_at_blocking_available() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("GET", "/api/version")
+        .with_status(200)
+        .with_body(r#"{"version":"0.12.6"}"#)
+        .create();
+
+    assert!(!live_at_blocking(&server.url()).is_skipped());
+}
+
+#[test]
+fn test_live_at_blocking_server_error_is_skipped() {
+    let mut server = mockito::Server::new();
+    let _mock = server.mock("GET", "/api/version").with_status(500).create();
+
+    assert!(live_at_blocking(&server.url()).is_skipped());
+}
+
+#[tokio::test]
+async fn test_require_live_macro_returns_early_without_server() {
+    // SAFETY: no other test in this binary reads or writes the environment.
+    unsafe {
+        std::env::set_var("OLLAMA_OXIDE_SKIP_LIVE", "1");
+    }
+    let _client = ollama_oxide::require_live!();
+    panic!("require_live! should have returned early");
+}
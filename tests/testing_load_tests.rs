@@ -0,0 +1,111 @@
+//! Tests for the load-generation helper (`testing` feature)
+
+use ollama_oxide::testing::LoadTest;
+use ollama_oxide::{
+    ChatMessage, ChatRequest, ClientConfig, EmbedRequest, Error, GenerateRequest, OllamaClient,
+};
+use std::time::Duration;
+
+fn client_for(server: &mockito::Server) -> OllamaClient {
+    let config = ClientConfig::new(server.url(), Duration::from_secs(5), 0).unwrap();
+    OllamaClient::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_load_test_weighted_mix() {
+    let mut server = mockito::Server::new_async().await;
+    let chat = server
+        .mock("POST", "/api/chat")
+        .with_status(200)
+        .with_body(r#"{"model":"m","message":{"role":"assistant","content":"hi"},"done":true}"#)
+        .expect(9)
+        .create_async()
+        .await;
+    let embed = server
+        .mock("POST", "/api/embed")
+        .with_status(200)
+        .with_body(r#"{"model":"e","embeddings":[[0.1,0.2]]}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    let report = LoadTest::new(client_for(&server))
+        .with_request(ChatRequest::new("m", [ChatMessage::user("Hi")]), 3)
+        .with_request(EmbedRequest::new("e", "hello"), 1)
+        .with_concurrency(4)
+        .with_duration(Duration::from_secs(30))
+        .with_max_requests(12)
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(report.requests, 12);
+    assert_eq!(report.errors, 0);
+    assert_eq!(report.error_rate(), 0.0);
+    assert!(report.throughput() > 0.0);
+    assert!(report.latency.is_some());
+    chat.assert_async().await;
+    embed.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_load_test_counts_errors() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let report = LoadTest::new(client_for(&server))
+        .with_request(GenerateRequest::new("m", "x"), 1)
+        .with_concurrency(2)
+        .with_max_requests(4)
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(report.requests, 4);
+    assert_eq!(report.errors, 4);
+    assert_eq!(report.error_rate(), 1.0);
+    assert_eq!(report.latency, None);
+    assert_eq!(report.error_samples, ["HTTP status error: 404"]);
+}
+
+#[tokio::test]
+async fn test_load_test_stops_at_duration() {
+    let mut server = mockito::Server::new_async().await;
+    let _mock = server
+        .mock("POST", "/api/generate")
+        .with_status(200)
+        .with_body(r#"{"response":"ok","done":true}"#)
+        .create_async()
+        .await;
+
+    let report = LoadTest::new(client_for(&server))
+        .with_request(GenerateRequest::new("m", "x"), 1)
+        .with_duration(Duration::from_millis(200))
+        .run()
+        .await
+        .unwrap();
+
+    assert!(report.requests > 0);
+    assert!(report.elapsed < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_load_test_rejects_empty_mix_and_zero_concurrency() {
+    let server = mockito::Server::new_async().await;
+    let empty = LoadTest::new(client_for(&server))
+        .with_request(GenerateRequest::new("m", "x"), 0)
+        .run()
+        .await;
+    assert!(matches!(empty, Err(Error::ValidationError(_))));
+
+    let idle = LoadTest::new(client_for(&server))
+        .with_request(GenerateRequest::new("m", "x"), 1)
+        .with_concurrency(0)
+        .run()
+        .await;
+    assert!(matches!(idle, Err(Error::ValidationError(_))));
+}